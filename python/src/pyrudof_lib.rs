@@ -4,10 +4,11 @@ use pyo3::{
     exceptions::PyValueError, pyclass, pymethods, Py, PyErr, PyRef, PyRefMut, PyResult, Python,
 };
 use rudof_lib::{
-    iri, DCTAPFormat, PrefixMap, QueryShapeMap, QuerySolution, QuerySolutions, RDFFormat, RdfData,
-    ReaderMode, ResultShapeMap, Rudof, RudofConfig, RudofError, ShExFormat, ShExFormatter,
-    ShExSchema, ShaclFormat, ShaclSchema, ShaclValidationMode, ShapeMapFormat, ShapeMapFormatter,
-    ShapesGraphSource, UmlGenerationMode, ValidationReport, ValidationStatus, VarName, DCTAP,
+    iri, DCTAPFormat, GraphSelector, PrefixMap, QueryShapeMap, QuerySolution, QuerySolutions,
+    RDFFormat, RdfData, ReaderMode, ResultShapeMap, Rudof, RudofConfig, RudofError, ShExFormat,
+    ShExFormatter, ShExSchema, ShaclFormat, ShaclSchema, ShaclValidationMode, ShapeMapFormat,
+    ShapeMapFormatter, ShapesGraphSource, UmlGenerationMode, ValidationReport, ValidationStatus,
+    VarName, DCTAP,
 };
 use std::{ffi::OsStr, fs::File, io::BufReader, path::Path};
 
@@ -290,6 +291,17 @@ impl PyRudof {
         Ok(())
     }
 
+    /// Selects the named graph of the current RDF data that is validated and queried.
+    /// If `graph` is `None`, the default graph is used
+    #[pyo3(signature = (graph = None))]
+    pub fn set_data_graph(&mut self, graph: Option<&str>) {
+        let graph_selector = match graph {
+            Some(graph) => GraphSelector::NamedGraph(iri!(graph)),
+            None => GraphSelector::DefaultGraph,
+        };
+        self.inner.set_data_graph(graph_selector);
+    }
+
     /// Adds RDF data read from a String to the current RDF Data
     #[pyo3(signature = (input, format = &PyRDFFormat::Turtle, base = None, reader_mode = &PyReaderMode::Lax))]
     pub fn read_data_str(
@@ -351,15 +363,20 @@ impl PyRudof {
     /// shapes_graph_source: Indicates the source of the shapes graph,
     /// which can be extracted from the current RDF data,
     /// or from the current SHACL schema.
-    /// If there is no current SHACL schema, it tries to get it from the current RDF data
-    #[pyo3(signature = (mode = &PyShaclValidationMode::Native, shapes_graph_source = &PyShapesGraphSource::CurrentSchema ))]
+    /// If there is no current SHACL schema, it tries to get it from the current RDF data.
+    /// If `shapes_graph` is set, the shapes are taken from that named graph of the current RDF data
+    #[pyo3(signature = (mode = &PyShaclValidationMode::Native, shapes_graph_source = &PyShapesGraphSource::CurrentSchema, shapes_graph = None))]
     pub fn validate_shacl(
         &mut self,
         mode: &PyShaclValidationMode,
         shapes_graph_source: &PyShapesGraphSource,
+        shapes_graph: Option<&str>,
     ) -> PyResult<PyValidationReport> {
         let mode = cnv_shacl_validation_mode(mode);
-        let shapes_graph_source = cnv_shapes_graph_source(shapes_graph_source);
+        let shapes_graph_source = match shapes_graph {
+            Some(graph) => ShapesGraphSource::named_graph(iri!(graph)),
+            None => cnv_shapes_graph_source(shapes_graph_source),
+        };
         let result = self
             .inner
             .validate_shacl(&mode, &shapes_graph_source)
@@ -460,19 +477,17 @@ impl PyRudof {
     pub fn add_endpoint(&mut self, endpoint: &str) -> PyResult<()> {
         // TODO: Check if it is in the RDF Data Config endpoints...
         let config = self.inner.config();
-        let (endpoint_iri, prefixmap) =
-            if let Some(endpoint_descr) = config.rdf_data_config().find_endpoint(endpoint) {
-                (
-                    endpoint_descr.query_url().clone(),
-                    endpoint_descr.prefixmap().clone(),
-                )
-            } else {
-                let iri = iri!(endpoint);
-                (iri, PrefixMap::basic())
-            };
-        self.inner
-            .add_endpoint(&endpoint_iri, &prefixmap)
-            .map_err(cnv_err)
+        if let Some(endpoint_descr) = config.rdf_data_config().find_endpoint(endpoint) {
+            let endpoint_descr = endpoint_descr.clone();
+            self.inner
+                .add_endpoint_description(&endpoint_descr)
+                .map_err(cnv_err)
+        } else {
+            let iri = iri!(endpoint);
+            self.inner
+                .add_endpoint(&iri, &PrefixMap::basic())
+                .map_err(cnv_err)
+        }
     }
 }
 
@@ -506,6 +521,7 @@ pub enum PyRDFFormat {
     TriG,
     N3,
     NQuads,
+    JsonLd,
 }
 
 #[allow(clippy::upper_case_acronyms)]
@@ -531,6 +547,11 @@ pub enum PyShExFormat {
     ShExC,
     ShExJ,
     Turtle,
+    NTriples,
+    RDFXML,
+    TriG,
+    N3,
+    NQuads,
 }
 
 #[allow(clippy::upper_case_acronyms)]
@@ -543,6 +564,7 @@ pub enum PyShaclFormat {
     TriG,
     N3,
     NQuads,
+    JsonLd,
 }
 
 #[pyclass(frozen, name = "ShExFormatter")]
@@ -871,6 +893,7 @@ fn cnv_rdf_format(format: &PyRDFFormat) -> RDFFormat {
         PyRDFFormat::TriG => RDFFormat::TriG,
         PyRDFFormat::N3 => RDFFormat::N3,
         PyRDFFormat::NQuads => RDFFormat::NQuads,
+        PyRDFFormat::JsonLd => RDFFormat::JsonLd,
     }
 }
 
@@ -886,6 +909,11 @@ fn cnv_shex_format(format: &PyShExFormat) -> ShExFormat {
         PyShExFormat::ShExC => ShExFormat::ShExC,
         PyShExFormat::ShExJ => ShExFormat::ShExJ,
         PyShExFormat::Turtle => ShExFormat::Turtle,
+        PyShExFormat::NTriples => ShExFormat::NTriples,
+        PyShExFormat::RDFXML => ShExFormat::RDFXML,
+        PyShExFormat::TriG => ShExFormat::TriG,
+        PyShExFormat::N3 => ShExFormat::N3,
+        PyShExFormat::NQuads => ShExFormat::NQuads,
     }
 }
 
@@ -897,6 +925,7 @@ fn cnv_shacl_format(format: &PyShaclFormat) -> ShaclFormat {
         PyShaclFormat::TriG => ShaclFormat::TriG,
         PyShaclFormat::N3 => ShaclFormat::N3,
         PyShaclFormat::NQuads => ShaclFormat::NQuads,
+        PyShaclFormat::JsonLd => ShaclFormat::JsonLd,
     }
 }
 
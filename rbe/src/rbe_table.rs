@@ -93,8 +93,11 @@ where
                 "No candidates for rbe: {:?}, candidates: {:?}, pairs_found: {pairs_found}",
                 self.rbe, candidates,
             );
+            // An rbe that accepts the empty bag matches when there are no candidates
+            let nullable = self.rbe.match_bag(&Bag::new(), self.open).is_ok();
             Ok(MatchTableIter::Empty(EmptyIter {
                 is_first: true,
+                nullable,
                 rbe: cnv_rbe(&self.rbe, self),
                 values: Values::from(&values),
             }))
@@ -251,6 +254,7 @@ where
     R: Ref,
 {
     is_first: bool,
+    nullable: bool,
     rbe: Rbe1<K, V, R>,
     values: Values<K, V>,
 }
@@ -266,6 +270,9 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         if self.is_first {
             self.is_first = false;
+            if self.nullable {
+                return Some(Ok(Pending::new()));
+            }
             Some(Err(RbeError::EmptyCandidates {
                 rbe: Box::new(self.rbe.clone()),
                 values: self.values.clone(),
@@ -283,6 +290,11 @@ where
     R: Ref,
 {
     match rbe {
+        Rbe::Fail { error } => Rbe1::Fail {
+            error: RbeError::MsgError {
+                msg: format!("{error}"),
+            },
+        },
         Rbe::Empty => Rbe1::Empty,
         Rbe::And { values } => {
             let values1 = values.iter().map(|c| cnv_rbe(c, table)).collect();
@@ -301,7 +313,16 @@ where
                 card: (*card).clone(),
             }
         }
-        _ => todo!(),
+        Rbe::Star { value } => Rbe1::Star {
+            expr: Box::new(cnv_rbe(value, table)),
+        },
+        Rbe::Plus { value } => Rbe1::Plus {
+            expr: Box::new(cnv_rbe(value, table)),
+        },
+        Rbe::Repeat { value, card } => Rbe1::Repeat {
+            expr: Box::new(cnv_rbe(value, table)),
+            card: (*card).clone(),
+        },
     }
 }
 
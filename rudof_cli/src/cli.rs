@@ -165,6 +165,41 @@ pub enum Command {
         #[arg(short = 'e', long = "endpoint", value_name = "Endpoint with RDF data")]
         endpoint: Option<String>,
 
+        /// Directory of a local store whose RDF data is validated, see the `store` command
+        #[arg(long = "store", value_name = "Store directory")]
+        store: Option<PathBuf>,
+
+        /// Named graph of the RDF data or the endpoint that is validated
+        #[arg(long = "data-graph", value_name = "Named graph IRI")]
+        data_graph: Option<String>,
+
+        /// Validate a subject-sorted N-Triples or N-Quads file one subject at a time without loading it in memory.
+        /// Results are written as they are found, in compact or json result format, or also ntriples or nquads for ShEx
+        #[arg(long = "stream", default_value_t = false)]
+        stream: bool,
+
+        /// Fetch the neighbourhood of the nodes to validate from the endpoint with batched and concurrent requests
+        /// The nodes of the triple patterns and SPARQL queries of ShEx shapemaps are selected in the endpoint
+        #[arg(long = "async", default_value_t = false)]
+        async_endpoint: bool,
+
+        /// Hops of references whose outgoing arcs are loaded for each node in streaming or asynchronous validation.
+        /// If unset, streaming validation follows references as far as the shapes need, which requires it for recursive shapes
+        #[arg(long = "reference-depth", value_name = "Number of hops")]
+        reference_depth: Option<usize>,
+
+        /// Don't cache the answers of the endpoint even if the configuration has an `endpoint_cache`
+        #[arg(long = "no-cache", default_value_t = false)]
+        no_cache: bool,
+
+        /// Ignore the persisted cache of the endpoint, which is replaced at the end of the validation
+        #[arg(long = "clear-cache", default_value_t = false)]
+        clear_cache: bool,
+
+        /// Named graph of the RDF data or the endpoint that contains the SHACL shapes
+        #[arg(long = "shapes-graph", value_name = "Named graph IRI")]
+        shapes_graph: Option<String>,
+
         #[arg(
             long = "max-steps",
             value_name = "max steps to run",
@@ -172,6 +207,14 @@ pub enum Command {
         )]
         max_steps: usize,
 
+        #[arg(
+            short = 'r',
+            long = "result-format",
+            value_name = "Output result format",
+            default_value_t = ResultValidationFormat::default()
+        )]
+        result_format: ResultValidationFormat,
+
         /// Execution mode
         #[arg(
             short = 'S',
@@ -263,9 +306,48 @@ pub enum Command {
         )]
         reader_mode: RDFReaderMode,
 
+        #[arg(
+            short = 'r',
+            long = "result-format",
+            value_name = "Output result format",
+            default_value_t = ResultValidationFormat::default()
+        )]
+        result_format: ResultValidationFormat,
+
         #[arg(short = 'e', long = "endpoint", value_name = "Endpoint with RDF data")]
         endpoint: Option<String>,
 
+        /// Directory of a local store whose RDF data is validated, see the `store` command
+        #[arg(long = "store", value_name = "Store directory")]
+        store: Option<PathBuf>,
+
+        /// Named graph of the RDF data or the endpoint that is validated
+        #[arg(long = "data-graph", value_name = "Named graph IRI")]
+        data_graph: Option<String>,
+
+        /// Validate a subject-sorted N-Triples or N-Quads file one subject at a time without loading it in memory.
+        /// Results are written as they are found, in compact or json result format, or also ntriples or nquads for ShEx
+        #[arg(long = "stream", default_value_t = false)]
+        stream: bool,
+
+        /// Fetch the neighbourhood of the nodes to validate from the endpoint with batched and concurrent requests
+        /// The nodes of the triple patterns and SPARQL queries of ShEx shapemaps are selected in the endpoint
+        #[arg(long = "async", default_value_t = false)]
+        async_endpoint: bool,
+
+        /// Hops of references whose outgoing arcs are loaded for each node in streaming or asynchronous validation.
+        /// If unset, streaming validation follows references as far as the shapes need, which requires it for recursive shapes
+        #[arg(long = "reference-depth", value_name = "Number of hops")]
+        reference_depth: Option<usize>,
+
+        /// Don't cache the answers of the endpoint even if the configuration has an `endpoint_cache`
+        #[arg(long = "no-cache", default_value_t = false)]
+        no_cache: bool,
+
+        /// Ignore the persisted cache of the endpoint, which is replaced at the end of the validation
+        #[arg(long = "clear-cache", default_value_t = false)]
+        clear_cache: bool,
+
         #[arg(
             short = 'o',
             long = "output-file",
@@ -320,6 +402,40 @@ pub enum Command {
         #[arg(short = 'e', long = "endpoint", value_name = "Endpoint with RDF data")]
         endpoint: Option<String>,
 
+        /// Directory of a local store whose RDF data is validated, see the `store` command
+        #[arg(long = "store", value_name = "Store directory")]
+        store: Option<PathBuf>,
+
+        /// Named graph of the RDF data or the endpoint that is validated
+        #[arg(long = "data-graph", value_name = "Named graph IRI")]
+        data_graph: Option<String>,
+
+        /// Validate a subject-sorted N-Triples or N-Quads file one subject at a time without loading it in memory.
+        /// Results are written as they are found, in compact or json result format, or also ntriples or nquads for ShEx
+        #[arg(long = "stream", default_value_t = false)]
+        stream: bool,
+
+        /// Fetch the neighbourhood of the nodes to validate from the endpoint with batched and concurrent requests
+        #[arg(long = "async", default_value_t = false)]
+        async_endpoint: bool,
+
+        /// Hops of references whose outgoing arcs are loaded for each node in streaming or asynchronous validation.
+        /// If unset, streaming validation follows references as far as the shapes need, which requires it for recursive shapes
+        #[arg(long = "reference-depth", value_name = "Number of hops")]
+        reference_depth: Option<usize>,
+
+        /// Don't cache the answers of the endpoint even if the configuration has an `endpoint_cache`
+        #[arg(long = "no-cache", default_value_t = false)]
+        no_cache: bool,
+
+        /// Ignore the persisted cache of the endpoint, which is replaced at the end of the validation
+        #[arg(long = "clear-cache", default_value_t = false)]
+        clear_cache: bool,
+
+        /// Named graph of the RDF data or the endpoint that contains the SHACL shapes
+        #[arg(long = "shapes-graph", value_name = "Named graph IRI")]
+        shapes_graph: Option<String>,
+
         /// Execution mode
         #[arg(
             short = 'm',
@@ -330,6 +446,14 @@ pub enum Command {
         )]
         mode: ShaclValidationMode,
 
+        #[arg(
+            short = 'r',
+            long = "result-format",
+            value_name = "Output result format",
+            default_value_t = ResultValidationFormat::default()
+        )]
+        result_format: ResultValidationFormat,
+
         #[arg(
             short = 'o',
             long = "output-file",
@@ -698,6 +822,10 @@ pub enum Command {
         #[arg(short = 'e', long = "endpoint", value_name = "Endpoint with RDF data")]
         endpoint: Option<String>,
 
+        /// Directory of a local store whose RDF data is queried, see the `store` command
+        #[arg(long = "store", value_name = "Store directory")]
+        store: Option<PathBuf>,
+
         #[arg(
             short = 'o',
             long = "output-file",
@@ -724,6 +852,66 @@ pub enum Command {
         )]
         force_overwrite: bool,
     },
+
+    /// Load, update and inspect a local store of RDF data that can be validated and queried many times
+    Store {
+        /// Operation on the store
+        #[arg(value_enum)]
+        operation: StoreOperation,
+
+        /// RDF data that is loaded in or removed from the store
+        #[clap(value_parser = clap::value_parser!(InputSpec))]
+        data: Vec<InputSpec>,
+
+        /// Directory of the store, an empty store is created if it doesn't exist
+        #[arg(long = "store", value_name = "Store directory")]
+        store: PathBuf,
+
+        #[arg(
+            short = 't',
+            long = "data-format",
+            value_name = "RDF Data format",
+            default_value_t = DataFormat::Turtle
+        )]
+        data_format: DataFormat,
+
+        /// RDF Reader mode
+        #[arg(
+            long = "reader-mode",
+            value_name = "RDF Reader mode",
+            default_value_t = RDFReaderMode::default(),
+            value_enum
+        )]
+        reader_mode: RDFReaderMode,
+
+        /// Config file path, if unset it assumes default config
+        #[arg(short = 'c', long = "config-file", value_name = "Config file name")]
+        config: Option<PathBuf>,
+    },
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+#[clap(rename_all = "lower")]
+pub enum StoreOperation {
+    /// Adds the triples of the data to the store
+    Load,
+    /// Removes the triples of the data from the store
+    Remove,
+    /// Removes all the triples of the store
+    Clear,
+    /// Shows the number of triples and the named graphs of the store
+    Info,
+}
+
+impl Display for StoreOperation {
+    fn fmt(&self, dest: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            StoreOperation::Load => write!(dest, "load"),
+            StoreOperation::Remove => write!(dest, "remove"),
+            StoreOperation::Clear => write!(dest, "clear"),
+            StoreOperation::Info => write!(dest, "info"),
+        }
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
@@ -798,6 +986,7 @@ impl Display for ShExFormat {
 #[clap(rename_all = "lower")]
 pub enum ShapeMapFormat {
     Compact,
+    Json,
     Internal,
 }
 
@@ -805,6 +994,7 @@ impl Display for ShapeMapFormat {
     fn fmt(&self, dest: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self {
             ShapeMapFormat::Compact => write!(dest, "compact"),
+            ShapeMapFormat::Json => write!(dest, "json"),
             ShapeMapFormat::Internal => write!(dest, "internal"),
         }
     }
@@ -819,6 +1009,7 @@ pub enum DataFormat {
     TriG,
     N3,
     NQuads,
+    JsonLd,
 }
 
 pub trait MimeType {
@@ -834,6 +1025,7 @@ impl MimeType for DataFormat {
             DataFormat::TriG => "application/trig".to_string(),
             DataFormat::N3 => "text/n3".to_string(),
             DataFormat::NQuads => "application/n-quads".to_string(),
+            DataFormat::JsonLd => "application/ld+json".to_string(),
         }
     }
 }
@@ -847,6 +1039,7 @@ impl From<DataFormat> for RDFFormat {
             DataFormat::TriG => RDFFormat::TriG,
             DataFormat::N3 => RDFFormat::N3,
             DataFormat::NQuads => RDFFormat::NQuads,
+            DataFormat::JsonLd => RDFFormat::JsonLd,
         }
     }
 }
@@ -860,6 +1053,7 @@ impl Display for DataFormat {
             DataFormat::TriG => write!(dest, "trig"),
             DataFormat::N3 => write!(dest, "n3"),
             DataFormat::NQuads => write!(dest, "nquads"),
+            DataFormat::JsonLd => write!(dest, "jsonld"),
         }
     }
 }
@@ -875,6 +1069,7 @@ pub enum ShaclFormat {
     TriG,
     N3,
     NQuads,
+    JsonLd,
 }
 
 impl MimeType for ShaclFormat {
@@ -886,6 +1081,7 @@ impl MimeType for ShaclFormat {
             ShaclFormat::TriG => "application/trig".to_string(),
             ShaclFormat::N3 => "text/n3".to_string(),
             ShaclFormat::NQuads => "application/n-quads".to_string(),
+            ShaclFormat::JsonLd => "application/ld+json".to_string(),
             ShaclFormat::Internal => "text/turtle".to_string(),
         }
     }
@@ -901,6 +1097,7 @@ impl Display for ShaclFormat {
             ShaclFormat::TriG => write!(dest, "trig"),
             ShaclFormat::N3 => write!(dest, "n3"),
             ShaclFormat::NQuads => write!(dest, "nquads"),
+            ShaclFormat::JsonLd => write!(dest, "jsonld"),
         }
     }
 }
@@ -959,6 +1156,37 @@ impl Display for ValidationMode {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Default)]
+#[clap(rename_all = "lower")]
+pub enum ResultValidationFormat {
+    #[default]
+    Compact,
+    JSON,
+    Turtle,
+    NTriples,
+    RDFXML,
+    TriG,
+    N3,
+    NQuads,
+    JsonLd,
+}
+
+impl Display for ResultValidationFormat {
+    fn fmt(&self, dest: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            ResultValidationFormat::Compact => write!(dest, "compact"),
+            ResultValidationFormat::JSON => write!(dest, "json"),
+            ResultValidationFormat::Turtle => write!(dest, "turtle"),
+            ResultValidationFormat::NTriples => write!(dest, "ntriples"),
+            ResultValidationFormat::RDFXML => write!(dest, "rdfxml"),
+            ResultValidationFormat::TriG => write!(dest, "trig"),
+            ResultValidationFormat::N3 => write!(dest, "n3"),
+            ResultValidationFormat::NQuads => write!(dest, "nquads"),
+            ResultValidationFormat::JsonLd => write!(dest, "jsonld"),
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
 #[clap(rename_all = "lower")]
 pub enum InputConvertMode {
@@ -18,23 +18,27 @@ use anyhow::*;
 use clap::Parser;
 use cli::{
     Cli, Command, DCTapFormat, DCTapResultFormat, DataFormat, InputConvertMode, MimeType,
-    OutputConvertMode, RDFReaderMode, ResultQueryFormat, ResultServiceFormat, ShowNodeMode,
-    ValidationMode,
+    OutputConvertMode, RDFReaderMode, ResultQueryFormat, ResultServiceFormat,
+    ResultValidationFormat, ShowNodeMode, StoreOperation, ValidationMode,
 };
 use dctap::DCTAPFormat;
 use iri_s::IriS;
 use prefixmap::{IriRef, PrefixMap};
 use rudof_lib::{
-    Rudof, RudofConfig, ShExFormat, ShExFormatter, ShaclFormat, ShaclValidationMode,
-    ShapeMapFormatter, ShapeMapParser, ShapesGraphSource,
+    AsyncSRDFSparql, Rudof, RudofConfig, RudofError, ShExFormat, ShExFormatter, ShaclFormat,
+    ShaclValidationMode, ShapeMapFormatter, ShapeMapParser, ShapesGraphSource, ValidationReport,
 };
 use shapemap::{NodeSelector, ShapeMapFormat as ShapemapFormat, ShapeSelector};
 use shapes_converter::ShEx2Sparql;
-use shapes_converter::{ImageFormat, ShEx2Html, ShEx2Uml, Shacl2ShEx, Tap2ShEx, UmlGenerationMode};
-use shex_ast::object_value::ObjectValue;
-use shex_ast::{ShapeExprLabel, SimpleReprSchema};
+use shapes_converter::{
+    ImageFormat, ShEx2Html, ShEx2Uml, Shacl2Html, Shacl2ShEx, Shacl2Uml, Tap2ShEx,
+    UmlGenerationMode,
+};
+use shex_ast::{Node, ShapeExprLabel, SimpleReprSchema};
 use sparql_service::{RdfData, ServiceDescription};
-use srdf::{QuerySolution, RDFFormat, ReaderMode, VarName, SRDF};
+use srdf::{
+    EndpointDescription, GraphSelector, Object, QuerySolution, RDFFormat, ReaderMode, VarName, SRDF,
+};
 use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
 use std::io::{self, BufWriter, Write};
@@ -135,29 +139,50 @@ fn main() -> Result<()> {
             data_format,
             reader_mode,
             endpoint,
+            store,
+            data_graph,
+            stream,
+            async_endpoint,
+            reference_depth,
+            no_cache,
+            clear_cache,
+            shapes_graph,
             node,
             shape,
             shapemap,
             shapemap_format,
             max_steps,
+            result_format,
             shacl_validation_mode,
             output,
             config,
             force_overwrite,
         }) => {
-            let config = get_config(config)?;
+            let config = get_cache_config(
+                get_stream_config(get_config(config)?, reference_depth),
+                *no_cache,
+                *clear_cache,
+            );
             match validation_mode {
+                ValidationMode::ShEx if shapes_graph.is_some() => {
+                    bail!("Option --shapes-graph is only supported in SHACL validation")
+                }
                 ValidationMode::ShEx => run_validate_shex(
                     schema,
                     schema_format,
                     data,
                     data_format,
                     endpoint,
+                    store,
+                    data_graph,
+                    *stream,
+                    *async_endpoint,
                     reader_mode,
                     node,
                     shape,
                     shapemap,
                     shapemap_format,
+                    result_format,
                     cli.debug,
                     output,
                     &config,
@@ -177,8 +202,14 @@ fn main() -> Result<()> {
                         data,
                         data_format,
                         endpoint,
+                        store,
+                        data_graph,
+                        *stream,
+                        *async_endpoint,
+                        shapes_graph,
                         reader_mode,
                         *shacl_validation_mode,
+                        result_format,
                         cli.debug,
                         output,
                         &config,
@@ -194,26 +225,43 @@ fn main() -> Result<()> {
             data_format,
             reader_mode,
             endpoint,
+            store,
+            data_graph,
+            stream,
+            async_endpoint,
+            reference_depth,
+            no_cache,
+            clear_cache,
             node,
             shape,
             shapemap,
             shapemap_format,
+            result_format,
             output,
             config,
             force_overwrite,
         }) => {
-            let config = get_config(config)?;
+            let config = get_cache_config(
+                get_stream_config(get_config(config)?, reference_depth),
+                *no_cache,
+                *clear_cache,
+            );
             run_validate_shex(
                 schema,
                 schema_format,
                 data,
                 data_format,
                 endpoint,
+                store,
+                data_graph,
+                *stream,
+                *async_endpoint,
                 reader_mode,
                 node,
                 shape,
                 shapemap,
                 shapemap_format,
+                result_format,
                 cli.debug,
                 output,
                 &config,
@@ -227,20 +275,39 @@ fn main() -> Result<()> {
             data_format,
             reader_mode,
             endpoint,
+            store,
+            data_graph,
+            stream,
+            async_endpoint,
+            reference_depth,
+            no_cache,
+            clear_cache,
+            shapes_graph,
             mode,
+            result_format,
             output,
             force_overwrite,
             config,
         }) => {
-            let config = get_config(config)?;
+            let config = get_cache_config(
+                get_stream_config(get_config(config)?, reference_depth),
+                *no_cache,
+                *clear_cache,
+            );
             run_validate_shacl(
                 shapes,
                 shapes_format,
                 data,
                 data_format,
                 endpoint,
+                store,
+                data_graph,
+                *stream,
+                *async_endpoint,
+                shapes_graph,
                 reader_mode,
                 *mode,
+                result_format,
                 cli.debug,
                 output,
                 &config,
@@ -379,6 +446,7 @@ fn main() -> Result<()> {
             data,
             data_format,
             endpoint,
+            store,
             reader_mode,
             output,
             result_query_format,
@@ -390,6 +458,7 @@ fn main() -> Result<()> {
                 data,
                 data_format,
                 endpoint,
+                store,
                 reader_mode,
                 query,
                 result_query_format,
@@ -399,6 +468,17 @@ fn main() -> Result<()> {
                 *force_overwrite,
             )
         }
+        Some(Command::Store {
+            operation,
+            data,
+            store,
+            data_format,
+            reader_mode,
+            config,
+        }) => {
+            let config = get_config(config)?;
+            run_store(operation, data, store, data_format, reader_mode, &config)
+        }
         None => {
             bail!("Command not specified")
         }
@@ -540,11 +620,16 @@ fn run_validate_shex(
     data: &Vec<InputSpec>,
     data_format: &DataFormat,
     endpoint: &Option<String>,
+    store: &Option<PathBuf>,
+    data_graph: &Option<String>,
+    stream: bool,
+    async_endpoint: bool,
     reader_mode: &RDFReaderMode,
     maybe_node: &Option<String>,
     maybe_shape: &Option<String>,
     shapemap: &Option<InputSpec>,
     shapemap_format: &CliShapeMapFormat,
+    result_format: &ResultValidationFormat,
     _debug: u8,
     output: &Option<PathBuf>,
     config: &RudofConfig,
@@ -556,14 +641,38 @@ fn run_validate_shex(
         let schema_format = schema_format.unwrap_or_default();
         let schema_reader = schema.open_read(Some(&schema_format.mime_type()))?;
         let schema_format = match schema_format {
-            CliShExFormat::ShExC => ShExFormat::ShExC,
-            CliShExFormat::ShExJ => ShExFormat::ShExJ,
-            _ => bail!("ShExJ validation not yet implemented"),
+            CliShExFormat::Internal | CliShExFormat::Simple => {
+                bail!("Validation with a ShEx schema in {schema_format} format not supported")
+            }
+            _ => shex_format_convert(&schema_format),
         };
         let base_iri = config.shex_config().base;
         let schema_base = base_iri.as_ref().map(|iri| iri.as_str());
         rudof.read_shex(schema_reader, &schema_format, schema_base)?;
-        get_data_rudof(&mut rudof, data, data_format, endpoint, reader_mode, config)?;
+        let async_sparql = if async_endpoint {
+            Some(get_async_endpoint(
+                data, endpoint, store, data_graph, config,
+            )?)
+        } else {
+            None
+        };
+        let stream_path = if stream {
+            Some(get_stream_data_path(data, endpoint, store, data_graph)?)
+        } else if async_sparql.is_some() {
+            None
+        } else {
+            get_data_or_store_rudof(
+                &mut rudof,
+                store,
+                data,
+                data_format,
+                endpoint,
+                reader_mode,
+                config,
+            )?;
+            set_data_graph_rudof(&mut rudof, data_graph)?;
+            None
+        };
 
         let shapemap_format = shapemap_format_convert(shapemap_format);
         if let Some(shapemap_spec) = shapemap {
@@ -591,9 +700,30 @@ fn run_validate_shex(
                 )
             }
         };
-        let result = rudof.validate_shex()?;
-        writeln!(writer, "Result:\n{}", result)?;
-        Ok(())
+        if let Some(path) = stream_path {
+            return run_validate_shex_stream(
+                &mut rudof,
+                &path,
+                data_format,
+                result_format,
+                &mut writer,
+            );
+        }
+        let result = match async_sparql {
+            Some(sparql) => {
+                tokio::runtime::Runtime::new()?.block_on(rudof.validate_shex_async(&sparql))?
+            }
+            None => rudof.validate_shex()?,
+        };
+        match result_format {
+            ResultValidationFormat::Compact => writeln!(writer, "Result:\n{}", result)?,
+            ResultValidationFormat::JSON => writeln!(writer, "{}", result.as_json_string()?)?,
+            _ => {
+                let rdf_format = result_validation_format_to_rdf_format(result_format)?;
+                result.serialize_rdf(&rdf_format, &mut writer)?
+            }
+        }
+        report_endpoint_cache(&rudof)
     } else {
         bail!("No ShEx schema specified")
     }
@@ -606,8 +736,14 @@ fn run_validate_shacl(
     data: &Vec<InputSpec>,
     data_format: &DataFormat,
     endpoint: &Option<String>,
+    store: &Option<PathBuf>,
+    data_graph: &Option<String>,
+    stream: bool,
+    async_endpoint: bool,
+    shapes_graph: &Option<String>,
     reader_mode: &RDFReaderMode,
     mode: ShaclValidationMode,
+    result_format: &ResultValidationFormat,
     _debug: u8,
     output: &Option<PathBuf>,
     config: &RudofConfig,
@@ -615,20 +751,322 @@ fn run_validate_shacl(
 ) -> Result<()> {
     let (mut writer, _color) = get_writer(output, force_overwrite)?;
     let mut rudof = Rudof::new(config);
-    get_data_rudof(&mut rudof, data, data_format, endpoint, reader_mode, config)?;
-    let result = if let Some(schema) = schema {
+    if stream {
+        let path = get_stream_data_path(data, endpoint, store, data_graph)?;
+        let schema = match (schema, shapes_graph) {
+            (Some(schema), None) => schema,
+            _ => bail!("Streaming validation requires the shapes to be given with --shapes"),
+        };
         let reader_mode = reader_mode_convert(*reader_mode);
         let shapes_format = shapes_format.unwrap_or_default();
         add_shacl_schema_rudof(&mut rudof, schema, &shapes_format, &reader_mode, config)?;
-        rudof.validate_shacl(&mode, &ShapesGraphSource::current_schema())
-    } else {
-        rudof.validate_shacl(&mode, &ShapesGraphSource::current_data())
+        return run_validate_shacl_stream(
+            &mut rudof,
+            &mode,
+            &path,
+            data_format,
+            result_format,
+            &mut writer,
+        );
+    }
+    if async_endpoint {
+        let sparql = get_async_endpoint(data, endpoint, store, data_graph, config)?;
+        let schema = match (schema, shapes_graph) {
+            (Some(schema), None) => schema,
+            _ => bail!("Asynchronous validation requires the shapes to be given with --shapes"),
+        };
+        let reader_mode = reader_mode_convert(*reader_mode);
+        let shapes_format = shapes_format.unwrap_or_default();
+        add_shacl_schema_rudof(&mut rudof, schema, &shapes_format, &reader_mode, config)?;
+        let result =
+            tokio::runtime::Runtime::new()?.block_on(rudof.validate_shacl_async(&mode, &sparql))?;
+        return write_validation_report(&result, result_format, &mut writer);
+    }
+    get_data_or_store_rudof(
+        &mut rudof,
+        store,
+        data,
+        data_format,
+        endpoint,
+        reader_mode,
+        config,
+    )?;
+    set_data_graph_rudof(&mut rudof, data_graph)?;
+    let result = match (schema, shapes_graph) {
+        (Some(_), Some(_)) => {
+            bail!("Only one of 'shapes' or 'shapes-graph' supported at the same time")
+        }
+        (Some(schema), None) => {
+            let reader_mode = reader_mode_convert(*reader_mode);
+            let shapes_format = shapes_format.unwrap_or_default();
+            add_shacl_schema_rudof(&mut rudof, schema, &shapes_format, &reader_mode, config)?;
+            rudof.validate_shacl(&mode, &ShapesGraphSource::current_schema())
+        }
+        (None, Some(shapes_graph)) => {
+            let name = IriS::from_str(shapes_graph)?;
+            rudof.validate_shacl(&mode, &ShapesGraphSource::named_graph(name))
+        }
+        (None, None) => rudof.validate_shacl(&mode, &ShapesGraphSource::current_data()),
     }?;
+    write_validation_report(&result, result_format, &mut writer)?;
+    report_endpoint_cache(&rudof)
+}
 
-    writeln!(writer, "Result:\n{}", result)?;
+/// Shows the statistics of the endpoint caches in the standard error and persists them
+fn report_endpoint_cache(rudof: &Rudof) -> Result<()> {
+    if let Some(stats) = rudof.endpoint_cache_stats() {
+        eprintln!("{stats}")
+    }
+    rudof.save_endpoint_caches()?;
     Ok(())
 }
 
+fn write_validation_report(
+    result: &ValidationReport,
+    result_format: &ResultValidationFormat,
+    writer: &mut Box<dyn Write>,
+) -> Result<()> {
+    match result_format {
+        ResultValidationFormat::Compact => writeln!(writer, "Result:\n{}", result)?,
+        ResultValidationFormat::JSON => writeln!(writer, "{}", result.as_json_string()?)?,
+        _ => {
+            let rdf_format = result_validation_format_to_rdf_format(result_format)?;
+            result.serialize_rdf(&rdf_format, writer)?
+        }
+    }
+    Ok(())
+}
+
+/// Validates a subject-sorted data file block by block writing the results of each block as soon as they are available.
+/// JSON results are written as the elements of an array and RDF results as a result shape map per block,
+/// so only line-based RDF formats, whose documents can be concatenated, are supported
+fn run_validate_shex_stream(
+    rudof: &mut Rudof,
+    path: &Path,
+    data_format: &DataFormat,
+    result_format: &ResultValidationFormat,
+    writer: &mut Box<dyn Write>,
+) -> Result<()> {
+    let rdf_format = data_format2rdf_format(data_format);
+    let result_rdf_format = match result_format {
+        ResultValidationFormat::Compact | ResultValidationFormat::JSON => None,
+        ResultValidationFormat::NTriples | ResultValidationFormat::NQuads => {
+            Some(result_validation_format_to_rdf_format(result_format)?)
+        }
+        _ => bail!(
+            "Result format {result_format} not supported in streaming validation, use compact, json, ntriples or nquads"
+        ),
+    };
+    let mut json = JsonArrayWriter::default();
+    match result_format {
+        ResultValidationFormat::Compact => writeln!(writer, "Result:")?,
+        ResultValidationFormat::JSON => json.start(writer)?,
+        _ => {}
+    }
+    let progress = rudof.validate_shex_stream(
+        path,
+        &rdf_format,
+        |block_result| {
+            let written = match (result_format, &result_rdf_format) {
+                (ResultValidationFormat::Compact, _) => {
+                    write!(writer, "{block_result}").map_err(|e| e.to_string())
+                }
+                (_, Some(rdf_format)) => block_result
+                    .serialize_rdf(rdf_format, writer)
+                    .map_err(|e| e.to_string()),
+                (_, None) => json
+                    .write_elements(writer, block_result.as_json())
+                    .map_err(|e| e.to_string()),
+            };
+            written.map_err(|e| stream_write_error(path, e))
+        },
+        |progress| eprintln!("{progress}"),
+    )?;
+    debug!("Streaming validation finished: {progress}");
+    if let ResultValidationFormat::JSON = result_format {
+        json.end(writer)?;
+        writeln!(writer)?
+    }
+    Ok(())
+}
+
+/// Validates a subject-sorted data file block by block against the current SHACL shapes writing the results as they arrive.
+/// In compact mode only the reports with errors are written.
+/// JSON reports are written with their results first, as whether the data conforms is only known at the end.
+/// RDF reports are not supported because they must declare whether the data conforms in the same report node as the results
+fn run_validate_shacl_stream(
+    rudof: &mut Rudof,
+    mode: &ShaclValidationMode,
+    path: &Path,
+    data_format: &DataFormat,
+    result_format: &ResultValidationFormat,
+    writer: &mut Box<dyn Write>,
+) -> Result<()> {
+    let rdf_format = data_format2rdf_format(data_format);
+    let compact = match result_format {
+        ResultValidationFormat::Compact => true,
+        ResultValidationFormat::JSON => false,
+        _ => bail!(
+            "Result format {result_format} not supported in streaming SHACL validation, use compact or json"
+        ),
+    };
+    let mut errors = 0;
+    let mut conforms = true;
+    let mut json = JsonArrayWriter::default();
+    if compact {
+        writeln!(writer, "Result:")?;
+    } else {
+        writeln!(writer, "{{")?;
+        write!(writer, "  \"results\": ")?;
+        json.start(writer)?;
+    }
+    rudof.validate_shacl_stream(
+        mode,
+        path,
+        &rdf_format,
+        |report| {
+            // The reports are configured as in non-streaming validation, so that they conform in the same cases
+            conforms = conforms && report.conforms();
+            let written = if compact {
+                if report.results().is_empty() {
+                    Ok(())
+                } else {
+                    errors += report.results().len();
+                    writeln!(writer, "{report}").map_err(|e| e.to_string())
+                }
+            } else {
+                let results = report.results().iter().map(|r| r.as_json()).collect();
+                json.write_elements(writer, serde_json::Value::Array(results))
+                    .map_err(|e| e.to_string())
+            };
+            written.map_err(|e| stream_write_error(path, e))
+        },
+        |progress| eprintln!("{progress}"),
+    )?;
+    if compact {
+        if errors == 0 {
+            let report = rudof.configure_shacl_report(ValidationReport::new());
+            writeln!(writer, "{report}")?
+        }
+    } else {
+        json.end(writer)?;
+        writeln!(writer, ",")?;
+        writeln!(writer, "  \"conforms\": {conforms}")?;
+        writeln!(writer, "}}")?;
+    }
+    Ok(())
+}
+
+/// Writes the elements of a JSON array as they arrive, so the whole array is never kept in memory
+#[derive(Default)]
+struct JsonArrayWriter {
+    empty: bool,
+}
+
+impl JsonArrayWriter {
+    fn start<W: Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.empty = true;
+        write!(writer, "[")
+    }
+
+    /// Writes the elements of `elements`, which must be a JSON array
+    fn write_elements<W: Write>(
+        &mut self,
+        writer: &mut W,
+        elements: serde_json::Value,
+    ) -> io::Result<()> {
+        if let serde_json::Value::Array(elements) = elements {
+            for element in elements {
+                if !self.empty {
+                    write!(writer, ",")?;
+                }
+                self.empty = false;
+                write!(writer, "\n{}", serde_json::to_string(&element)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn end<W: Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        if self.empty {
+            write!(writer, "]")
+        } else {
+            write!(writer, "\n]")
+        }
+    }
+}
+
+fn stream_write_error(path: &Path, error: impl std::fmt::Display) -> RudofError {
+    RudofError::StreamingValidation {
+        path: path.display().to_string(),
+        error: error.to_string(),
+    }
+}
+
+/// Streaming validation reads a single local file, it can't be combined with endpoints, stores or named graphs
+fn get_stream_data_path(
+    data: &[InputSpec],
+    endpoint: &Option<String>,
+    store: &Option<PathBuf>,
+    data_graph: &Option<String>,
+) -> Result<PathBuf> {
+    if endpoint.is_some() || store.is_some() || data_graph.is_some() {
+        bail!("Options --endpoint, --store and --data-graph are not supported in streaming validation")
+    }
+    match data {
+        [InputSpec::Path(path)] => Ok(path.clone()),
+        _ => bail!("Streaming validation requires exactly one local data file"),
+    }
+}
+
+/// Asynchronous validation fetches the data from a single endpoint, it can't be combined with data files or stores
+fn get_async_endpoint(
+    data: &[InputSpec],
+    endpoint: &Option<String>,
+    store: &Option<PathBuf>,
+    data_graph: &Option<String>,
+    config: &RudofConfig,
+) -> Result<AsyncSRDFSparql> {
+    if !data.is_empty() || store.is_some() {
+        bail!("Options --data and --store are not supported in asynchronous validation")
+    }
+    let endpoint = match endpoint {
+        Some(endpoint) => endpoint,
+        None => bail!("Asynchronous validation requires an --endpoint"),
+    };
+    let endpoint_descr = resolve_endpoint(endpoint, config)?;
+    let graph = match data_graph {
+        Some(name) => Some(IriS::from_str(name)?),
+        None => None,
+    };
+    Ok(AsyncSRDFSparql::from_endpoint_description(&endpoint_descr)?
+        .with_graph(graph)
+        .with_config(config.async_sparql_config()))
+}
+
+fn get_stream_config(config: RudofConfig, reference_depth: &Option<usize>) -> RudofConfig {
+    match reference_depth {
+        Some(depth) => {
+            let stream_config = config.stream_config().with_reference_depth(*depth);
+            let async_sparql_config = config.async_sparql_config().with_reference_depth(*depth);
+            config
+                .with_stream_config(stream_config)
+                .with_async_sparql_config(async_sparql_config)
+        }
+        None => config,
+    }
+}
+
+fn get_cache_config(config: RudofConfig, no_cache: bool, clear_cache: bool) -> RudofConfig {
+    match config.endpoint_cache_config() {
+        Some(_) if no_cache => config.without_endpoint_cache(),
+        Some(cache_config) if clear_cache => {
+            config.with_endpoint_cache_config(cache_config.with_refresh(true))
+        }
+        _ => config,
+    }
+}
+
 fn run_shacl(
     input: &InputSpec,
     shapes_format: &CliShaclFormat,
@@ -712,6 +1150,9 @@ fn run_convert(
         (InputConvertMode::SHACL, OutputConvertMode::ShEx) => {
             run_shacl2shex(input, format, output, result_format, &config, force_overwrite, reader_mode)
         }
+        (InputConvertMode::SHACL, OutputConvertMode::UML) => {
+            run_shacl2uml(input, format, output, result_format, maybe_shape_str, &config, force_overwrite, reader_mode)
+        }
         (InputConvertMode::ShEx, OutputConvertMode::HTML) => {
             match target_folder {
                 None => Err(anyhow!(
@@ -722,6 +1163,16 @@ fn run_convert(
                 }
             }
         }
+        (InputConvertMode::SHACL, OutputConvertMode::HTML) => {
+            match target_folder {
+                None => Err(anyhow!(
+            "Conversion from SHACL to HTML requires an output parameter to indicate where to write the generated HTML files"
+                )),
+                Some(output_path) => {
+                    run_shacl2html(input, format, output_path, &config, reader_mode)
+                }
+            }
+        }
         (InputConvertMode::DCTAP, OutputConvertMode::UML, ) => {
             run_tap2uml(input, format, output, maybe_shape_str, result_format, &config, force_overwrite)
         }
@@ -826,6 +1277,14 @@ fn generate_uml_output(
             uml_converter.as_plantuml(writer, &mode)?;
             Ok(())
         }
+        OutputConvertFormat::Mermaid => {
+            uml_converter.as_mermaid(writer, &mode)?;
+            Ok(())
+        }
+        OutputConvertFormat::DOT => {
+            uml_converter.as_dot(writer, &mode)?;
+            Ok(())
+        }
         OutputConvertFormat::SVG => {
             uml_converter.as_image(writer, ImageFormat::SVG, &mode)?;
             Ok(())
@@ -844,6 +1303,46 @@ fn generate_uml_output(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn run_shacl2uml(
+    input: &InputSpec,
+    format: &InputConvertFormat,
+    output: &Option<PathBuf>,
+    result_format: &OutputConvertFormat,
+    maybe_shape: &Option<String>,
+    config: &RudofConfig,
+    force_overwrite: bool,
+    reader_mode: &RDFReaderMode,
+) -> Result<()> {
+    let schema_format = match format {
+        InputConvertFormat::Turtle => Ok(CliShaclFormat::Turtle),
+        _ => Err(anyhow!("Can't obtain SHACL format from {format}")),
+    }?;
+    let mut rudof = Rudof::new(config);
+    let reader_mode = reader_mode_convert(*reader_mode);
+    add_shacl_schema_rudof(&mut rudof, input, &schema_format, &reader_mode, config)?;
+    let shacl_schema = rudof.get_shacl().unwrap();
+    let mut converter = Shacl2Uml::new(&config.shex2uml_config());
+    converter.convert(shacl_schema)?;
+    let (mut writer, _color) = get_writer(output, force_overwrite)?;
+    let mode = if let Some(str) = maybe_shape {
+        UmlGenerationMode::neighs(str)
+    } else {
+        UmlGenerationMode::all()
+    };
+    match result_format {
+        OutputConvertFormat::PlantUML | OutputConvertFormat::Default => {
+            converter.as_plantuml(&mut writer, &mode)?
+        }
+        OutputConvertFormat::Mermaid => converter.as_mermaid(&mut writer, &mode)?,
+        OutputConvertFormat::DOT => converter.as_dot(&mut writer, &mode)?,
+        OutputConvertFormat::SVG => converter.as_image(&mut writer, ImageFormat::SVG, &mode)?,
+        OutputConvertFormat::PNG => converter.as_image(&mut writer, ImageFormat::PNG, &mode)?,
+        _ => bail!("Conversion from SHACL to UML does not support output format {result_format}"),
+    }
+    Ok(())
+}
+
 fn run_shex2html<P: AsRef<Path>>(
     input: &InputSpec,
     format: &InputConvertFormat,
@@ -877,6 +1376,37 @@ fn run_shex2html<P: AsRef<Path>>(
     Ok(())
 }
 
+fn run_shacl2html<P: AsRef<Path>>(
+    input: &InputSpec,
+    format: &InputConvertFormat,
+    output_folder: P,
+    config: &RudofConfig,
+    reader_mode: &RDFReaderMode,
+) -> Result<()> {
+    debug!("Starting shacl2html");
+    let schema_format = match format {
+        InputConvertFormat::Turtle => Ok(CliShaclFormat::Turtle),
+        _ => Err(anyhow!("Can't obtain SHACL format from {format}")),
+    }?;
+    let mut rudof = Rudof::new(config);
+    let reader_mode = reader_mode_convert(*reader_mode);
+    add_shacl_schema_rudof(&mut rudof, input, &schema_format, &reader_mode, config)?;
+    if let Some(schema) = rudof.get_shacl() {
+        let config = config
+            .shacl2html_config()
+            .with_target_folder(output_folder.as_ref());
+        let landing_page = config.landing_page().to_string_lossy().to_string();
+        debug!("Landing page will be generated at {landing_page}\nStarted converter...");
+        let mut converter = Shacl2Html::new(config);
+        converter.convert(schema)?;
+        converter.export_schema()?;
+        debug!("HTML pages generated at {}", landing_page);
+    } else {
+        bail!("No SHACL schema")
+    }
+    Ok(())
+}
+
 fn run_tap2html<P: AsRef<Path>>(
     input: &InputSpec,
     format: &InputConvertFormat,
@@ -1014,6 +1544,19 @@ enum ColorSupport {
     WithColor,
 }
 
+fn result_validation_format_to_rdf_format(format: &ResultValidationFormat) -> Result<RDFFormat> {
+    match format {
+        ResultValidationFormat::Turtle => Ok(RDFFormat::Turtle),
+        ResultValidationFormat::NTriples => Ok(RDFFormat::NTriples),
+        ResultValidationFormat::RDFXML => Ok(RDFFormat::RDFXML),
+        ResultValidationFormat::TriG => Ok(RDFFormat::TriG),
+        ResultValidationFormat::N3 => Ok(RDFFormat::N3),
+        ResultValidationFormat::NQuads => Ok(RDFFormat::NQuads),
+        ResultValidationFormat::JsonLd => Ok(RDFFormat::JsonLd),
+        _ => bail!("Result format {format} is not an RDF format"),
+    }
+}
+
 fn get_writer(
     output: &Option<PathBuf>,
     force_overwrite: bool,
@@ -1087,6 +1630,40 @@ fn add_shacl_schema_rudof(
     Ok(())
 }
 
+fn set_data_graph_rudof(rudof: &mut Rudof, data_graph: &Option<String>) -> Result<()> {
+    if let Some(data_graph) = data_graph {
+        let name = IriS::from_str(data_graph)?;
+        rudof.set_data_graph(GraphSelector::NamedGraph(name));
+    }
+    Ok(())
+}
+
+/// Takes the RDF data from the local store if it is given, merging with it the data files in memory.
+/// Otherwise, the data is obtained from the data files or the endpoint
+fn get_data_or_store_rudof(
+    rudof: &mut Rudof,
+    store: &Option<PathBuf>,
+    data: &Vec<InputSpec>,
+    data_format: &DataFormat,
+    endpoint: &Option<String>,
+    reader_mode: &RDFReaderMode,
+    config: &RudofConfig,
+) -> Result<()> {
+    match store {
+        None => get_data_rudof(rudof, data, data_format, endpoint, reader_mode, config),
+        Some(_) if endpoint.is_some() => {
+            bail!("Only one of 'store' or 'endpoint' supported at the same time")
+        }
+        Some(dir) => {
+            rudof.open_store(dir)?;
+            if !data.is_empty() {
+                get_data_rudof(rudof, data, data_format, endpoint, reader_mode, config)?
+            }
+            Ok(())
+        }
+    }
+}
+
 fn get_data_rudof(
     rudof: &mut Rudof,
     data: &Vec<InputSpec>,
@@ -1113,17 +1690,8 @@ fn get_data_rudof(
             Ok(())
         }
         (true, Some(endpoint)) => {
-            let (endpoint_iri, prefixmap) =
-                if let Some(endpoint_descr) = config.rdf_data_config().find_endpoint(endpoint) {
-                    (
-                        endpoint_descr.query_url().clone(),
-                        endpoint_descr.prefixmap().clone(),
-                    )
-                } else {
-                    let iri = IriS::from_str(endpoint.as_str())?;
-                    (iri, PrefixMap::basic())
-                };
-            rudof.add_endpoint(&endpoint_iri, &prefixmap)?;
+            let endpoint_descr = resolve_endpoint(endpoint, config)?;
+            rudof.add_endpoint_description(&endpoint_descr)?;
             Ok(())
         }
         (false, Some(_)) => {
@@ -1132,6 +1700,14 @@ fn get_data_rudof(
     }
 }
 
+/// Endpoints can be given by the name they have in the configuration or by their IRI
+fn resolve_endpoint(endpoint: &str, config: &RudofConfig) -> Result<EndpointDescription> {
+    match config.rdf_data_config().find_endpoint(endpoint) {
+        Some(endpoint_descr) => Ok(endpoint_descr.clone()),
+        None => Ok(EndpointDescription::from_str(endpoint)?.with_prefixmap(PrefixMap::basic())),
+    }
+}
+
 /*fn get_query_str(input: &InputSpec) -> Result<String> {
     let mut str = String::new();
     let mut data = input.open_read(None)?;
@@ -1175,10 +1751,11 @@ fn run_node(
     let (mut writer, _color) = get_writer(output, force_overwrite)?;
     let mut rudof = Rudof::new(config);
     get_data_rudof(&mut rudof, data, data_format, endpoint, reader_mode, config)?;
-    let data = rudof.get_rdf_data();
     let node_selector = parse_node_selector(node_str)?;
+    let nodes = rudof.select_nodes(&node_selector)?;
+    let data = rudof.get_rdf_data();
     show_node_info(
-        node_selector,
+        nodes,
         predicates,
         data,
         show_node_mode,
@@ -1188,7 +1765,7 @@ fn run_node(
 }
 
 fn show_node_info<S, W: Write>(
-    node_selector: NodeSelector,
+    nodes: Vec<Node>,
     predicates: &Vec<String>,
     rdf: &S,
     show_node_mode: &ShowNodeMode,
@@ -1198,8 +1775,8 @@ fn show_node_info<S, W: Write>(
 where
     S: SRDF,
 {
-    for node in node_selector.iter_node(rdf) {
-        let subject = node_to_subject(node, rdf)?;
+    for node in nodes {
+        let subject = node_to_subject::<S>(&node)?;
         writeln!(
             writer,
             "Information about {}",
@@ -1307,27 +1884,16 @@ fn run_shapemap(
     Ok(())
 }
 
-fn node_to_subject<S>(node: &ObjectValue, rdf: &S) -> Result<S::Subject>
+fn node_to_subject<S>(node: &Node) -> Result<S::Subject>
 where
     S: SRDF,
 {
-    match node {
-        ObjectValue::IriRef(iri_ref) => {
-            let iri = match iri_ref {
-                IriRef::Iri(iri_s) => S::iri_s2iri(iri_s),
-                IriRef::Prefixed { prefix, local } => {
-                    let iri_s = rdf.resolve_prefix_local(prefix, local)?;
-
-                    S::iri_s2iri(&iri_s)
-                }
-            };
-            let term = S::iri_as_term(iri);
-            match S::term_as_subject(&term) {
-                None => bail!("node_to_subject: Can't convert term {term} to subject"),
-                Some(subject) => Ok(subject),
-            }
-        }
-        ObjectValue::Literal(lit) => Err(anyhow!("Node must be an IRI, but found a literal {lit}")),
+    match node.as_object() {
+        Object::Literal(lit) => Err(anyhow!("Node must be an IRI, but found a literal {lit}")),
+        object => match S::object_as_subject(object) {
+            None => bail!("node_to_subject: Can't convert {object} to subject"),
+            Some(subject) => Ok(subject),
+        },
     }
 }
 
@@ -1355,6 +1921,7 @@ fn run_query(
     data: &Vec<InputSpec>,
     data_format: &DataFormat,
     endpoint: &Option<String>,
+    store: &Option<PathBuf>,
     reader_mode: &RDFReaderMode,
     query: &InputSpec,
     _result_query_format: &ResultQueryFormat,
@@ -1365,7 +1932,15 @@ fn run_query(
 ) -> Result<()> {
     let (mut writer, _color) = get_writer(output, force_overwrite)?;
     let mut rudof = Rudof::new(config);
-    get_data_rudof(&mut rudof, data, data_format, endpoint, reader_mode, config)?;
+    get_data_or_store_rudof(
+        &mut rudof,
+        store,
+        data,
+        data_format,
+        endpoint,
+        reader_mode,
+        config,
+    )?;
     let mut reader = query.open_read(None)?;
     let results = rudof.run_query(&mut reader)?;
     let mut results_iter = results.iter().peekable();
@@ -1380,6 +1955,63 @@ fn run_query(
     Ok(())
 }
 
+fn run_store(
+    operation: &StoreOperation,
+    data: &Vec<InputSpec>,
+    store: &Path,
+    data_format: &DataFormat,
+    reader_mode: &RDFReaderMode,
+    config: &RudofConfig,
+) -> Result<()> {
+    let mut rudof = Rudof::new(config);
+    rudof.open_store(store)?;
+    let base = config.rdf_data_base();
+    let rdf_format = data_format2rdf_format(data_format);
+    let reader_mode = reader_mode_convert(*reader_mode);
+    match operation {
+        StoreOperation::Load | StoreOperation::Remove if data.is_empty() => {
+            bail!("No RDF data specified to {operation} in the store")
+        }
+        StoreOperation::Load => {
+            for d in data {
+                let data_reader = d.open_read(Some(&data_format.mime_type()))?;
+                let len = rudof.load_store(data_reader, &rdf_format, base, &reader_mode)?;
+                println!("Loaded {d}, the store contains {len} triples");
+            }
+        }
+        StoreOperation::Remove => {
+            for d in data {
+                let data_reader = d.open_read(Some(&data_format.mime_type()))?;
+                let removal =
+                    rudof.remove_from_store(data_reader, &rdf_format, base, &reader_mode)?;
+                println!("Removed {} triples of {d}", removal.removed);
+                if removal.with_blank_nodes > 0 {
+                    println!(
+                        "Skipped {} triples of {d} with blank nodes, which are renamed when they are loaded",
+                        removal.with_blank_nodes
+                    );
+                }
+            }
+        }
+        StoreOperation::Clear => {
+            rudof.clear_store()?;
+            println!("Removed all the triples of the store");
+        }
+        StoreOperation::Info => {
+            if !data.is_empty() {
+                bail!("Operation {operation} doesn't take RDF data")
+            }
+        }
+    }
+    if let Some(local_store) = rudof.get_store() {
+        println!("Store {}: {} triples", store.display(), local_store.len()?);
+        for name in local_store.graph_names()? {
+            println!("  Named graph {name}")
+        }
+    }
+    Ok(())
+}
+
 fn show_variables<'a, W: Write>(
     writer: &mut W,
     vars: impl Iterator<Item = &'a VarName>,
@@ -1466,6 +2098,7 @@ fn shacl_format_convert(shacl_format: &cli::ShaclFormat) -> Result<ShaclFormat>
         cli::ShaclFormat::TriG => Ok(ShaclFormat::TriG),
         cli::ShaclFormat::N3 => Ok(ShaclFormat::N3),
         cli::ShaclFormat::NQuads => Ok(ShaclFormat::NQuads),
+        cli::ShaclFormat::JsonLd => Ok(ShaclFormat::JsonLd),
         cli::ShaclFormat::Internal => {
             bail!("Cannot convert internal SHACL format to RDF data format")
         }
@@ -1476,6 +2109,7 @@ fn data_format2rdf_format(data_format: &DataFormat) -> RDFFormat {
     match data_format {
         DataFormat::N3 => RDFFormat::N3,
         DataFormat::NQuads => RDFFormat::NQuads,
+        DataFormat::JsonLd => RDFFormat::JsonLd,
         DataFormat::NTriples => RDFFormat::NTriples,
         DataFormat::RDFXML => RDFFormat::RDFXML,
         DataFormat::TriG => RDFFormat::TriG,
@@ -1555,7 +2189,8 @@ fn show_extends_table<R: Write>(
 fn shapemap_format_convert(shapemap_format: &CliShapeMapFormat) -> ShapemapFormat {
     match shapemap_format {
         CliShapeMapFormat::Compact => ShapemapFormat::Compact,
-        CliShapeMapFormat::Internal => ShapemapFormat::JSON,
+        CliShapeMapFormat::Json => ShapemapFormat::JSON,
+        CliShapeMapFormat::Internal => ShapemapFormat::Internal,
     }
 }
 
@@ -1564,6 +2199,11 @@ fn shex_format_convert(shex_format: &CliShExFormat) -> ShExFormat {
         CliShExFormat::ShExC => ShExFormat::ShExC,
         CliShExFormat::ShExJ => ShExFormat::ShExJ,
         CliShExFormat::Turtle => ShExFormat::Turtle,
+        CliShExFormat::NTriples => ShExFormat::NTriples,
+        CliShExFormat::RDFXML => ShExFormat::RDFXML,
+        CliShExFormat::TriG => ShExFormat::TriG,
+        CliShExFormat::N3 => ShExFormat::N3,
+        CliShExFormat::NQuads => ShExFormat::NQuads,
         _ => ShExFormat::ShExC,
     }
 }
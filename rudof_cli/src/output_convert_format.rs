@@ -12,6 +12,8 @@ pub enum OutputConvertFormat {
     ShExJ,
     Turtle,
     PlantUML,
+    Mermaid,
+    DOT,
     HTML,
     SVG,
     PNG,
@@ -27,6 +29,8 @@ impl Display for OutputConvertFormat {
             OutputConvertFormat::ShExJ => write!(dest, "shexj"),
             OutputConvertFormat::Turtle => write!(dest, "turtle"),
             OutputConvertFormat::PlantUML => write!(dest, "uml"),
+            OutputConvertFormat::Mermaid => write!(dest, "mermaid"),
+            OutputConvertFormat::DOT => write!(dest, "dot"),
             OutputConvertFormat::HTML => write!(dest, "html"),
             OutputConvertFormat::PNG => write!(dest, "png"),
             OutputConvertFormat::SVG => write!(dest, "svg"),
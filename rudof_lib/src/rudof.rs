@@ -1,17 +1,33 @@
 use crate::{RudofConfig, RudofError, ShapesGraphSource};
 use iri_s::IriS;
+use prefixmap::IriRef;
+use shacl_ast::target::Target;
 use shacl_ast::{ShaclParser, ShaclWriter};
 use shacl_validation::shacl_processor::{GraphValidation, ShaclProcessor};
 use shacl_validation::store::graph::Graph;
 
+use oxrdf::{NamedNode as OxNamedNode, Subject as OxSubject, Term as OxTerm, Triple as OxTriple};
 use shapemap::{NodeSelector, ShapeSelector};
-use shapes_converter::{ShEx2Uml, Tap2ShEx};
+use shapes_converter::{ShEx2Uml, Shacl2Uml, Tap2ShEx};
 use shex_ast::compiled::compiled_schema::CompiledSchema;
+use shex_ast::object_value::ObjectValue;
+use shex_ast::shexr::shexr_parser::ShExRParser;
+use shex_ast::shexr::shexr_writer::ShExRWriter;
+use shex_ast::Node;
 use shex_compact::ShExParser;
+use shex_validation::ValidatorError;
 use shex_validation::{ResolveMethod, SchemaWithoutImports};
+use sparql_service::RdfDataError;
+use srdf::lang::Lang;
 use srdf::QuerySRDF;
-use srdf::{FocusRDF, SRDFGraph};
+use srdf::{
+    AsyncNeighbourhood, AsyncSRDF, DiskIndex, FocusRDF, NodeArcs, SRDFBasic, SRDFGraph,
+    StreamConfig, SubjectBlock, SubjectBlocks, RDF_TYPE,
+};
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
+use std::fs::File;
+use std::io::BufReader;
 use std::path::Path;
 use std::str::FromStr;
 use std::{io, result};
@@ -27,12 +43,16 @@ pub use shapemap::{QueryShapeMap, ResultShapeMap, ShapeMapFormat, ValidationStat
 pub use shex_compact::{ShExFormatter, ShapeMapParser, ShapemapFormatter as ShapeMapFormatter};
 pub use shex_validation::Validator as ShExValidator;
 pub use shex_validation::{ShExFormat, ValidatorConfig};
-pub use srdf::{QuerySolution, QuerySolutions, RDFFormat, ReaderMode, SRDFSparql, VarName};
+pub use srdf::{
+    AsyncSRDFSparql, AsyncSparqlConfig, CacheConfig, CacheStats, EndpointAuth, EndpointDescription,
+    GraphSelector, QueryMethod, QuerySolution, QuerySolutions, RDFFormat, ReaderMode, SRDFSparql,
+    SparqlConnectionConfig, StreamProgress, VarName,
+};
 pub type Result<T> = result::Result<T, RudofError>;
 pub use shacl_ast::ast::Schema as ShaclSchema;
 pub use shapes_converter::UmlGenerationMode;
 pub use shex_ast::Schema as ShExSchema;
-pub use sparql_service::RdfData;
+pub use sparql_service::{LocalStore, RdfData, Removal};
 
 /// This represents the public API to interact with `rudof`
 #[derive(Debug)]
@@ -45,6 +65,7 @@ pub struct Rudof {
     shex_validator: Option<ShExValidator>,
     shapemap: Option<QueryShapeMap>,
     dctap: Option<DCTAP>,
+    store: Option<LocalStore>,
 }
 
 // TODO: We added this declaration so PyRudof can contain Rudof and be Send as required by PyO3
@@ -62,6 +83,7 @@ impl Rudof {
             rdf_data: RdfData::new(),
             shapemap: None,
             dctap: None,
+            store: None,
         }
     }
 
@@ -73,9 +95,10 @@ impl Rudof {
         self.config = config.clone();
     }
 
-    /// Resets the current RDF Data
+    /// Resets the current RDF Data and closes the local store
     pub fn reset_data(&mut self) {
-        self.rdf_data = RdfData::new()
+        self.rdf_data = RdfData::new();
+        self.store = None
     }
 
     /// Resets the current DCTAP
@@ -149,6 +172,39 @@ impl Rudof {
         mode: &UmlGenerationMode,
         writer: &mut W,
     ) -> Result<()> {
+        self.shex2uml()?.as_plantuml(writer, mode).map_err(|e| {
+            RudofError::ShEx2PlantUmlErrorAsPlantUML {
+                error: format!("{e}"),
+            }
+        })?;
+        Ok(())
+    }
+
+    /// Generate a UML Class-like representation of a ShEx schema as a Mermaid class diagram
+    pub fn shex2mermaid<W: io::Write>(
+        &self,
+        mode: &UmlGenerationMode,
+        writer: &mut W,
+    ) -> Result<()> {
+        self.shex2uml()?
+            .as_mermaid(writer, mode)
+            .map_err(|e| RudofError::UmlDiagramError {
+                format: "Mermaid".to_string(),
+                error: format!("{e}"),
+            })
+    }
+
+    /// Generate a UML Class-like representation of a ShEx schema as a Graphviz DOT graph
+    pub fn shex2dot<W: io::Write>(&self, mode: &UmlGenerationMode, writer: &mut W) -> Result<()> {
+        self.shex2uml()?
+            .as_dot(writer, mode)
+            .map_err(|e| RudofError::UmlDiagramError {
+                format: "DOT".to_string(),
+                error: format!("{e}"),
+            })
+    }
+
+    fn shex2uml(&self) -> Result<ShEx2Uml> {
         if let Some(shex) = &self.shex_schema {
             let mut converter = ShEx2Uml::new(&self.config.shex2uml_config());
             converter
@@ -156,17 +212,64 @@ impl Rudof {
                 .map_err(|e| RudofError::ShEx2PlantUmlError {
                     error: format!("{e}"),
                 })?;
-            converter.as_plantuml(writer, mode).map_err(|e| {
-                RudofError::ShEx2PlantUmlErrorAsPlantUML {
-                    error: format!("{e}"),
-                }
-            })?;
-            Ok(())
+            Ok(converter)
         } else {
             Err(RudofError::ShEx2UmlWithoutShEx)
         }
     }
 
+    /// Generate a UML Class-like representation of a SHACL schema according to PlantUML syntax
+    pub fn shacl2plant_uml<W: io::Write>(
+        &self,
+        mode: &UmlGenerationMode,
+        writer: &mut W,
+    ) -> Result<()> {
+        self.shacl2uml()?
+            .as_plantuml(writer, mode)
+            .map_err(|e| RudofError::UmlDiagramError {
+                format: "PlantUML".to_string(),
+                error: format!("{e}"),
+            })
+    }
+
+    /// Generate a UML Class-like representation of a SHACL schema as a Mermaid class diagram
+    pub fn shacl2mermaid<W: io::Write>(
+        &self,
+        mode: &UmlGenerationMode,
+        writer: &mut W,
+    ) -> Result<()> {
+        self.shacl2uml()?
+            .as_mermaid(writer, mode)
+            .map_err(|e| RudofError::UmlDiagramError {
+                format: "Mermaid".to_string(),
+                error: format!("{e}"),
+            })
+    }
+
+    /// Generate a UML Class-like representation of a SHACL schema as a Graphviz DOT graph
+    pub fn shacl2dot<W: io::Write>(&self, mode: &UmlGenerationMode, writer: &mut W) -> Result<()> {
+        self.shacl2uml()?
+            .as_dot(writer, mode)
+            .map_err(|e| RudofError::UmlDiagramError {
+                format: "DOT".to_string(),
+                error: format!("{e}"),
+            })
+    }
+
+    fn shacl2uml(&self) -> Result<Shacl2Uml> {
+        if let Some(shacl) = &self.shacl_schema {
+            let mut converter = Shacl2Uml::new(&self.config.shex2uml_config());
+            converter
+                .convert(shacl)
+                .map_err(|e| RudofError::Shacl2UmlError {
+                    error: format!("{e}"),
+                })?;
+            Ok(converter)
+        } else {
+            Err(RudofError::Shacl2UmlWithoutShacl)
+        }
+    }
+
     pub fn serialize_data<W: io::Write>(&self, format: &RDFFormat, writer: &mut W) -> Result<()> {
         self.rdf_data
             .serialize(format, writer)
@@ -193,6 +296,20 @@ impl Rudof {
                     })
                 }
                 ShapeMapFormat::JSON => {
+                    let json = shapemap.as_json_string().map_err(|e| {
+                        RudofError::ErrorFormattingShapeMap {
+                            shapemap: format!("{:?}", shapemap.clone()),
+                            error: format!("{e}"),
+                        }
+                    })?;
+                    writer.write_all(json.as_bytes()).map_err(|e| {
+                        RudofError::ErrorFormattingShapeMap {
+                            shapemap: format!("{:?}", shapemap.clone()),
+                            error: format!("{e}"),
+                        }
+                    })
+                }
+                ShapeMapFormat::Internal => {
                     serde_json::to_writer_pretty(writer, &shapemap).map_err(|e| {
                         RudofError::ErrorWritingShExJson {
                             schema: format!("{:?}", shapemap.clone()),
@@ -234,9 +351,22 @@ impl Rudof {
                     })?;
                     Ok(())
                 }
-                ShExFormat::Turtle => Err(RudofError::NotImplemented {
-                    msg: format!("ShEx to ShExR for {shex:?}"),
-                }),
+                _ => {
+                    let data_format = shex_format2rdf_format(format)?;
+                    let mut shexr_writer: ShExRWriter<SRDFGraph> = ShExRWriter::new();
+                    shexr_writer
+                        .write(shex)
+                        .map_err(|e| RudofError::WritingShExR {
+                            schema: format!("{:?}", shex.clone()),
+                            error: format!("{e}"),
+                        })?;
+                    shexr_writer.serialize(&data_format, writer).map_err(|e| {
+                        RudofError::SerializingShEx {
+                            error: format!("{e}"),
+                        }
+                    })?;
+                    Ok(())
+                }
             }
         } else {
             Err(RudofError::NoShExSchemaToSerialize)
@@ -338,13 +468,17 @@ impl Rudof {
             ShaclFormat::TriG => Ok(RDFFormat::TriG),
             ShaclFormat::N3 => Ok(RDFFormat::N3),
             ShaclFormat::NQuads => Ok(RDFFormat::NQuads),
+            ShaclFormat::JsonLd => Ok(RDFFormat::JsonLd),
         }?;
 
-        let rdf_graph =
-            SRDFGraph::from_reader(reader, &format, base, reader_mode).map_err(|e| {
-                RudofError::ReadError {
-                    error: format!("{e}"),
-                }
+        let mut rdf_graph = SRDFGraph::new();
+        for (iri, document) in self.jsonld_contexts()? {
+            rdf_graph.add_jsonld_context(&iri, &document);
+        }
+        rdf_graph
+            .merge_from_reader(reader, &format, base, reader_mode)
+            .map_err(|e| RudofError::ReadError {
+                error: format!("{e}"),
             })?;
         let schema = shacl_schema_from_data(rdf_graph)?;
         self.shacl_schema = Some(schema);
@@ -437,16 +571,19 @@ impl Rudof {
                     })?;
                 Ok(schema_json)
             }
-            ShExFormat::Turtle => {
-                todo!()
-                /*let rdf = parse_data(
-                    &vec![input.clone()],
-                    &DataFormat::Turtle,
-                    reader_mode,
-                    &config.rdf_config(),
-                )?;
-                let schema = ShExRParser::new(rdf).parse()?;
-                Ok(schema) */
+            _ => {
+                let rdf_format = shex_format2rdf_format(format)?;
+                let rdf = SRDFGraph::from_reader(reader, &rdf_format, base, &ReaderMode::default())
+                    .map_err(|e| RudofError::ReadError {
+                        error: format!("{e}"),
+                    })?;
+                let schema_json =
+                    ShExRParser::new(rdf)
+                        .parse()
+                        .map_err(|e| RudofError::ShExRParserError {
+                            error: format!("{e}"),
+                        })?;
+                Ok(schema_json)
             }
         }?;
         self.shex_schema = Some(schema_json.clone());
@@ -483,6 +620,20 @@ impl Rudof {
                 })?;
                 Ok((compiled_schema, ast_schema.clone()))
             }
+            ShapesGraphSource::NamedGraph(name) => {
+                let shapes_data = self
+                    .rdf_data
+                    .clone()
+                    .with_graph_selector(GraphSelector::NamedGraph(name.clone()));
+                let ast_schema = shacl_schema_from_data(shapes_data)?;
+                let compiled_schema = ast_schema.to_owned().try_into().map_err(|e| {
+                    RudofError::SHACLCompilationError {
+                        error: format!("{e}"),
+                        schema: Box::new(ast_schema.clone()),
+                    }
+                })?;
+                Ok((compiled_schema, ast_schema))
+            }
             _ => {
                 let ast_schema = shacl_schema_from_data(self.rdf_data.clone())?;
                 let compiled_schema = ast_schema.to_owned().try_into().map_err(|e| {
@@ -494,6 +645,13 @@ impl Rudof {
                 Ok((compiled_schema, ast_schema))
             }
         }?;
+        if *mode == ShaclValidationMode::Sparql || shacl_schema.has_sparql_constraints() {
+            self.rdf_data
+                .check_store()
+                .map_err(|e| RudofError::StorageError {
+                    error: format!("{e}"),
+                })?;
+        }
         let validator = GraphValidation::from_graph(Graph::from_data(self.rdf_data.clone()), *mode);
         let result = ShaclProcessor::validate(&validator, &compiled_schema).map_err(|e| {
             RudofError::SHACLValidationError {
@@ -501,7 +659,17 @@ impl Rudof {
                 schema: Box::new(shacl_schema),
             }
         })?;
-        Ok(result)
+        Ok(self.configure_shacl_report(result))
+    }
+
+    /// Applies the severity threshold and the preferred language of the SHACL configuration to a report,
+    /// like the reports returned by the SHACL validation methods
+    pub fn configure_shacl_report(&self, report: ValidationReport) -> ValidationReport {
+        let shacl_config = self.config.shacl_config();
+        let preferred_language = shacl_config.preferred_language.as_deref().map(Lang::new);
+        report
+            .with_severity_threshold(shacl_config.severity_threshold)
+            .with_preferred_language(preferred_language)
     }
 
     /// Validate RDF data using ShEx
@@ -513,8 +681,21 @@ impl Rudof {
             Some(ref mut validator) => match &self.shapemap {
                 None => Err(RudofError::NoShapeMap { schema: schema_str }),
                 Some(shapemap) => {
+                    // The neighbourhoods of the nodes are fetched in batches from cached endpoints
+                    self.rdf_data
+                        .prefetch_neighbourhoods(&shapemap_subjects(shapemap))
+                        .map_err(|e| RudofError::EndpointCache {
+                            error: format!("{e}"),
+                        })?;
+                    if shapemap.has_sparql_selectors() {
+                        self.rdf_data
+                            .check_store()
+                            .map_err(|e| RudofError::StorageError {
+                                error: format!("{e}"),
+                            })?;
+                    }
                     validator
-                        .validate_shapemap(shapemap, &self.rdf_data)
+                        .validate_shapemap_with_queries(shapemap, &self.rdf_data)
                         .map_err(|e| RudofError::ShExValidatorError {
                             schema: schema_str.clone(),
                             rdf_data: format!("{:?}", self.rdf_data),
@@ -535,6 +716,353 @@ impl Rudof {
         }
     }
 
+    /// Validates with ShEx a subject-sorted N-Triples or N-Quads dump without loading it in memory
+    ///
+    /// The dump is read one subject at a time and the nodes of the current shapemap are
+    /// validated when their subject is read, using its outgoing arcs as data.
+    /// If the shapes reference other nodes or have inverse triple constraints, a disk index is built
+    /// in a first pass and the incoming arcs of the node and the outgoing arcs of the nodes it references
+    /// are also available. References are followed as far as the shapes need unless the stream configuration
+    /// sets a `reference_depth`, which is required if the shapes are recursive. Only shapemaps whose nodes are IRIs are supported.
+    ///
+    /// `on_result` is called with the result of each validated node and `on_progress`
+    /// every `progress_every` subjects and when the dump has been read
+    pub fn validate_shex_stream<F, P>(
+        &mut self,
+        path: &Path,
+        format: &RDFFormat,
+        mut on_result: F,
+        mut on_progress: P,
+    ) -> Result<StreamProgress>
+    where
+        F: FnMut(ResultShapeMap) -> Result<()>,
+        P: FnMut(&StreamProgress),
+    {
+        let stream_config = self.config.stream_config();
+        let validator = match self.shex_validator {
+            None => return Err(RudofError::ShExValidatorUndefined {}),
+            Some(ref mut validator) => validator,
+        };
+        let shapemap = match &self.shapemap {
+            None => {
+                return Err(RudofError::NoShapeMap {
+                    schema: format!("{validator:?}"),
+                })
+            }
+            Some(shapemap) => shapemap,
+        };
+        let nodes_prefixmap = shapemap.nodes_prefixmap();
+        let mut pending = shapemap_iri_nodes(shapemap)?;
+        let reach = match &self.shex_schema {
+            Some(schema) => StreamReach::new(
+                path,
+                &stream_config,
+                schema.reference_depth(),
+                schema.has_inverse_triple_constraints(),
+            )?,
+            None => StreamReach::new(path, &stream_config, None, true)?,
+        };
+        let index = stream_index(path, format, &stream_config, &reach)?;
+        let mut progress = StreamProgress::default();
+        let mut blocks = subject_blocks(path, format)?;
+        while let Some(block) = blocks.next() {
+            let block = block.map_err(|e| stream_error(path, e))?;
+            progress.subjects += 1;
+            progress.triples = blocks.triples_read();
+            let subject =
+                SRDFGraph::subject_as_iri(block.subject()).map(|iri| SRDFGraph::iri2iri_s(&iri));
+            if let Some(shapes) = subject.as_ref().and_then(|iri| pending.remove(iri)) {
+                let graph = stream_neighbourhood(path, &block, &index, &reach)?;
+                let result = validate_shex_stream_node(
+                    validator,
+                    subject.as_ref().unwrap(),
+                    shapes,
+                    &graph,
+                    &nodes_prefixmap,
+                )
+                .map_err(|e| stream_error(path, e))?;
+                progress.validated += 1;
+                on_result(result)?;
+            }
+            if progress.subjects % stream_config.progress_every() == 0 {
+                on_progress(&progress)
+            }
+        }
+
+        // The nodes that are not subjects in the dump are validated with their incoming arcs
+        let mut pending: Vec<(IriS, Vec<ShapeSelector>)> = pending.into_iter().collect();
+        pending.sort_by(|(iri1, _), (iri2, _)| iri1.as_str().cmp(iri2.as_str()));
+        for (iri, shapes) in pending {
+            let triples = match &index {
+                Some(index) => index
+                    .incoming(&SRDFGraph::iri_s2term(&iri))
+                    .map_err(|e| stream_error(path, e))?,
+                None => Vec::new(),
+            };
+            let graph = SRDFGraph::from_triples(triples);
+            let result =
+                validate_shex_stream_node(validator, &iri, shapes, &graph, &nodes_prefixmap)
+                    .map_err(|e| stream_error(path, e))?;
+            progress.validated += 1;
+            on_result(result)?;
+        }
+        on_progress(&progress);
+        Ok(progress)
+    }
+
+    /// Validates with SHACL a subject-sorted N-Triples or N-Quads dump without loading it in memory
+    ///
+    /// The dump is read one subject at a time and each subject is validated against the shapes of
+    /// the current SHACL schema whose targets select it in its neighbourhood.
+    /// If the shapes reach other nodes through paths or shape references, or need incoming arcs,
+    /// a disk index is built in a first pass and the neighbourhood also contains the incoming arcs
+    /// of the subject and the outgoing arcs of the nodes it references, as far as the shapes need
+    /// or up to the `reference_depth` of the stream configuration, which is required if the shapes
+    /// have no bound, see [`ShaclSchema::reference_depth`].
+    /// Focus nodes that are not subjects in the dump are not validated.
+    ///
+    /// `on_result` is called with the report of each subject that has validation results and
+    /// `on_progress` every `progress_every` subjects and when the dump has been read
+    pub fn validate_shacl_stream<F, P>(
+        &mut self,
+        mode: &ShaclValidationMode,
+        path: &Path,
+        format: &RDFFormat,
+        mut on_result: F,
+        mut on_progress: P,
+    ) -> Result<StreamProgress>
+    where
+        F: FnMut(ValidationReport) -> Result<()>,
+        P: FnMut(&StreamProgress),
+    {
+        let stream_config = self.config.stream_config();
+        let shacl_schema = match &self.shacl_schema {
+            None => return Err(RudofError::NoShaclSchema),
+            Some(schema) => schema,
+        };
+        let compiled_schema =
+            shacl_schema
+                .clone()
+                .try_into()
+                .map_err(|e| RudofError::SHACLCompilationError {
+                    error: format!("{e}"),
+                    schema: Box::new(shacl_schema.clone()),
+                })?;
+        let needs_store =
+            *mode == ShaclValidationMode::Sparql || shacl_schema.has_sparql_constraints();
+        let reach = StreamReach::new(
+            path,
+            &stream_config,
+            shacl_schema.reference_depth(),
+            shacl_schema.uses_incoming_arcs(),
+        )?;
+        let index = stream_index(path, format, &stream_config, &reach)?;
+        let mut progress = StreamProgress::default();
+        let mut blocks = subject_blocks(path, format)?;
+        while let Some(block) = blocks.next() {
+            let block = block.map_err(|e| stream_error(path, e))?;
+            progress.subjects += 1;
+            progress.triples = blocks.triples_read();
+            let node = RdfData::subject_as_term(block.subject());
+            let graph = stream_neighbourhood(path, &block, &index, &reach)?;
+            let mut data = RdfData::from_graph_without_store(graph);
+            if needs_store {
+                data.check_store().map_err(|e| RudofError::StorageError {
+                    error: format!("{e}"),
+                })?;
+            }
+            let validator = GraphValidation::from_graph(Graph::from_data(data), *mode);
+            let report = validator
+                .validate_node(&compiled_schema, &node)
+                .map_err(|e| RudofError::SHACLValidationError {
+                    error: format!("{e}"),
+                    schema: Box::new(shacl_schema.clone()),
+                })?;
+            progress.validated += 1;
+            if !report.results().is_empty() {
+                on_result(self.configure_shacl_report(report))?;
+            }
+            if progress.subjects % stream_config.progress_every() == 0 {
+                on_progress(&progress)
+            }
+        }
+        on_progress(&progress);
+        Ok(progress)
+    }
+
+    /// Validates with ShEx the nodes of the current shapemap fetching their data from an asynchronous RDF source
+    ///
+    /// The nodes of triple patterns are selected with the arcs they read, which are fetched until none is missing,
+    /// and SPARQL selectors are run by the source.
+    /// The neighbourhood of the nodes, i.e. their incoming arcs and the outgoing arcs of the nodes
+    /// and of the nodes they reference up to the `reference_depth` of the asynchronous configuration,
+    /// is fetched in batches with concurrent requests and each node is validated in memory.
+    /// The arcs that the validation of a node reads and were not fetched, like the ones of deeper references
+    /// or of blank nodes, are fetched and only the nodes that read them are validated again until no arc is missing.
+    /// The result contains the statuses of the selected nodes
+    pub async fn validate_shex_async<A>(&mut self, rdf: &A) -> Result<ResultShapeMap>
+    where
+        A: AsyncSRDF<Subject = OxSubject, IRI = OxNamedNode, Term = OxTerm> + Sync,
+    {
+        let async_config = self.config.async_sparql_config();
+        let validator = match self.shex_validator {
+            None => return Err(RudofError::ShExValidatorUndefined {}),
+            Some(ref mut validator) => validator,
+        };
+        let shapemap = match &self.shapemap {
+            None => {
+                return Err(RudofError::NoShapeMap {
+                    schema: format!("{validator:?}"),
+                })
+            }
+            Some(shapemap) => shapemap,
+        };
+        let nodes_prefixmap = shapemap.nodes_prefixmap();
+        let async_error = |e: A::Err| RudofError::AsyncValidation {
+            error: e.to_string(),
+        };
+        let mut neighbourhood = AsyncNeighbourhood::new();
+        let mut data = RdfData::new().with_read_log();
+        let nodes = select_shapemap_nodes(shapemap, rdf, &mut neighbourhood, &mut data).await?;
+        let subjects: Vec<OxSubject> = nodes
+            .iter()
+            .filter_map(|(node, _)| SRDFGraph::object_as_subject(node.as_object()))
+            .collect();
+        neighbourhood
+            .fetch_references(rdf, &subjects, async_config.reference_depth())
+            .await
+            .map_err(async_error)?;
+        let mut results = vec![ResultShapeMap::new(); nodes.len()];
+        let mut pending: Vec<usize> = (0..nodes.len()).collect();
+        loop {
+            add_new_triples(&mut data, &mut neighbourhood)?;
+            let mut missing = NodeArcs::new();
+            let mut incomplete = Vec::new();
+            for idx in pending {
+                let (node, shapes) = &nodes[idx];
+                results[idx] =
+                    validate_shex_async_node(validator, node, shapes, &data).map_err(|e| {
+                        RudofError::ShExValidatorError {
+                            schema: format!("{validator:?}"),
+                            rdf_data: format!("{data:?}"),
+                            query_map: format!("{shapemap:?}"),
+                            error: format!("{e}"),
+                        }
+                    })?;
+                let node_missing = data.take_read_arcs().difference(neighbourhood.fetched());
+                if !node_missing.is_empty() {
+                    missing.extend(node_missing);
+                    incomplete.push(idx)
+                }
+            }
+            if !neighbourhood
+                .fetch_missing(rdf, &missing)
+                .await
+                .map_err(async_error)?
+            {
+                break;
+            }
+            pending = incomplete;
+        }
+        let mut result = ResultShapeMap::new().with_nodes_prefixmap(&nodes_prefixmap);
+        for ((node, _), node_result) in nodes.iter().zip(results) {
+            // The statuses of the referenced nodes depend on the arcs fetched when the node was validated
+            for (_, label, status) in node_result.iter().filter(|(other, _, _)| *other == node) {
+                result
+                    .add_result(node.clone(), label.clone(), status.clone())
+                    .map_err(|e| RudofError::AsyncValidation {
+                        error: e.to_string(),
+                    })?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Validates with SHACL the focus nodes of the current SHACL schema fetching their data from an asynchronous RDF source
+    ///
+    /// The focus nodes are obtained from the targets of the shapes and their neighbourhood,
+    /// i.e. their incoming arcs and the outgoing arcs of the nodes and of the nodes they reference
+    /// up to the `reference_depth` of the asynchronous configuration, is fetched in batches
+    /// with concurrent requests and validated in memory.
+    /// The arcs that the validation reads and were not fetched, like the ones of deeper references
+    /// or of blank nodes, are fetched and the nodes validated again until no arc is missing.
+    /// SPARQL-based constraints only see the fetched arcs
+    pub async fn validate_shacl_async<A>(
+        &mut self,
+        mode: &ShaclValidationMode,
+        rdf: &A,
+    ) -> Result<ValidationReport>
+    where
+        A: AsyncSRDF<Subject = OxSubject, IRI = OxNamedNode, Term = OxTerm> + Sync,
+    {
+        let async_config = self.config.async_sparql_config();
+        let shacl_schema = match &self.shacl_schema {
+            None => return Err(RudofError::NoShaclSchema),
+            Some(schema) => schema,
+        };
+        let compiled_schema =
+            shacl_schema
+                .clone()
+                .try_into()
+                .map_err(|e| RudofError::SHACLCompilationError {
+                    error: format!("{e}"),
+                    schema: Box::new(shacl_schema.clone()),
+                })?;
+        let async_error = |e: A::Err| RudofError::AsyncValidation {
+            error: e.to_string(),
+        };
+        let mut neighbourhood = AsyncNeighbourhood::new();
+        let nodes = shacl_focus_nodes(shacl_schema, rdf, &mut neighbourhood)
+            .await
+            .map_err(async_error)?;
+        neighbourhood
+            .fetch_references(rdf, &nodes, async_config.reference_depth())
+            .await
+            .map_err(async_error)?;
+        let mut data = RdfData::new().with_read_log();
+        loop {
+            add_new_triples(&mut data, &mut neighbourhood)?;
+            if *mode == ShaclValidationMode::Sparql || shacl_schema.has_sparql_constraints() {
+                data.check_store().map_err(|e| RudofError::StorageError {
+                    error: format!("{e}"),
+                })?;
+            }
+            let validator = GraphValidation::from_graph(Graph::from_data(data), *mode);
+            let result = ShaclProcessor::validate(&validator, &compiled_schema).map_err(|e| {
+                RudofError::SHACLValidationError {
+                    error: format!("{e}"),
+                    schema: Box::new(shacl_schema.clone()),
+                }
+            })?;
+            data = validator.into_graph().into_data();
+            if !neighbourhood
+                .fetch_missing(rdf, &data.take_read_arcs())
+                .await
+                .map_err(async_error)?
+            {
+                return Ok(self.configure_shacl_report(result));
+            }
+        }
+    }
+
+    /// Nodes of the current RDF data selected by a node selector, which can be a triple pattern
+    /// like `{FOCUS a :Person}` or a SPARQL query.
+    /// Prefixed names are resolved with the nodes prefixmap, as in the shapemaps read by [`Rudof::read_shapemap`]
+    pub fn select_nodes(&mut self, node_selector: &NodeSelector) -> Result<Vec<Node>> {
+        if let NodeSelector::Sparql { .. } = node_selector {
+            self.rdf_data
+                .check_store()
+                .map_err(|e| RudofError::StorageError {
+                    error: format!("{e}"),
+                })?;
+        }
+        node_selector
+            .select_with_queries(&self.rdf_data, &self.nodes_prefixmap())
+            .map_err(|e| RudofError::NodeSelector {
+                error: format!("{e}"),
+            })
+    }
+
     /// Adds an endpoint to the current RDF data
     pub fn add_endpoint(&mut self, iri: &IriS, prefixmap: &PrefixMap) -> Result<()> {
         let sparql_endpoint =
@@ -542,11 +1070,57 @@ impl Rudof {
                 iri: iri.clone(),
                 error: format!("{e}"),
             })?;
+        self.add_sparql_endpoint(sparql_endpoint)
+    }
+
+    /// Adds an endpoint described in the configuration to the current RDF data,
+    /// using its authentication, headers, timeout and query method
+    pub fn add_endpoint_description(&mut self, description: &EndpointDescription) -> Result<()> {
+        let sparql_endpoint = SRDFSparql::from_endpoint_description(description).map_err(|e| {
+            RudofError::AddingEndpointError {
+                iri: description.query_url().clone(),
+                error: format!("{e}"),
+            }
+        })?;
+        self.add_sparql_endpoint(sparql_endpoint)
+    }
 
-        self.rdf_data.add_endpoint(sparql_endpoint);
+    fn add_sparql_endpoint(&mut self, sparql_endpoint: SRDFSparql) -> Result<()> {
+        match self.config.endpoint_cache_config() {
+            None => self.rdf_data.add_endpoint(sparql_endpoint),
+            Some(cache_config) => self
+                .rdf_data
+                .add_endpoint_with_cache(sparql_endpoint, &cache_config)
+                .map_err(|e| RudofError::EndpointCache {
+                    error: format!("{e}"),
+                })?,
+        }
         Ok(())
     }
 
+    /// Statistics of the caches of the endpoints of the current RDF data, `None` if they have no cache
+    pub fn endpoint_cache_stats(&self) -> Option<CacheStats> {
+        self.rdf_data.endpoint_cache_stats()
+    }
+
+    /// Removes the entries of the caches of the endpoints of the current RDF data and the files where they are persisted
+    pub fn clear_endpoint_caches(&self) -> Result<()> {
+        self.rdf_data
+            .clear_endpoint_caches()
+            .map_err(|e| RudofError::EndpointCache {
+                error: format!("{e}"),
+            })
+    }
+
+    /// Writes the caches of the endpoints that are persisted in the directory of the cache configuration
+    pub fn save_endpoint_caches(&self) -> Result<()> {
+        self.rdf_data
+            .save_endpoint_caches()
+            .map_err(|e| RudofError::EndpointCache {
+                error: format!("{e}"),
+            })
+    }
+
     /// Parses an RDF graph from a reader and merges it with the current graph
     pub fn read_data<R: io::Read>(
         &mut self,
@@ -555,6 +1129,9 @@ impl Rudof {
         base: Option<&str>,
         reader_mode: &ReaderMode,
     ) -> Result<()> {
+        for (iri, document) in self.jsonld_contexts()? {
+            self.rdf_data.add_jsonld_context(&iri, &document);
+        }
         self.rdf_data
             .merge_from_reader(reader, format, base, reader_mode)
             .map_err(|e| RudofError::MergeRDFDataFromReader {
@@ -566,11 +1143,92 @@ impl Rudof {
         Ok(())
     }
 
+    /// Local documents of the JSON-LD remote contexts declared in the configuration
+    fn jsonld_contexts(&self) -> Result<HashMap<String, String>> {
+        self.config
+            .rdf_data_config()
+            .jsonld_context_documents()
+            .map_err(|e| RudofError::ReadingJsonLdContexts {
+                error: format!("{e}"),
+            })
+    }
+
+    /// Selects the graphs of the current RDF data that are validated and queried,
+    /// both in the in-memory dataset and in the endpoints
+    pub fn set_data_graph(&mut self, graph_selector: GraphSelector) {
+        self.rdf_data.set_graph_selector(graph_selector)
+    }
+
     /// Cleans the in-memory graph
     pub fn clean_rdf_graph(&mut self) {
         self.rdf_data.clean_graph();
     }
 
+    /// Opens the local store in `dir` and takes its dataset as the current RDF data keeping the selected graphs.
+    /// The triples of the store are read from disk when they are needed, so later updates of the store are seen by the current RDF data.
+    /// If the directory doesn't exist, an empty store is created
+    pub fn open_store<P: AsRef<Path>>(&mut self, dir: P) -> Result<()> {
+        let store = LocalStore::open(dir.as_ref()).map_err(|e| RudofError::LocalStore {
+            path: dir.as_ref().display().to_string(),
+            error: format!("{e}"),
+        })?;
+        let graph_selector = self.rdf_data.graph_selector().clone();
+        self.rdf_data =
+            RdfData::from_local_store(store.clone()).with_graph_selector(graph_selector);
+        self.store = Some(store);
+        Ok(())
+    }
+
+    /// Closes the current local store, whose dataset remains the current RDF data
+    pub fn close_store(&mut self) {
+        self.store = None
+    }
+
+    /// Get the current local store
+    pub fn get_store(&self) -> Option<&LocalStore> {
+        self.store.as_ref()
+    }
+
+    /// Parses an RDF graph from a reader and adds it to the current local store while it is parsed,
+    /// returning the number of triples of the updated store
+    pub fn load_store<R: io::Read>(
+        &mut self,
+        reader: R,
+        format: &RDFFormat,
+        base: Option<&str>,
+        reader_mode: &ReaderMode,
+    ) -> Result<usize> {
+        let contexts = self.jsonld_contexts()?;
+        let store = self.store.as_ref().ok_or(RudofError::NoLocalStore)?;
+        let len = store
+            .load_from_reader(reader, format, base, reader_mode, &contexts)
+            .map_err(|e| local_store_error(store, e))?;
+        Ok(len)
+    }
+
+    /// Parses an RDF graph from a reader and removes its triples from the current local store while it is parsed,
+    /// returning how many of them were in the store and how many couldn't be removed because they have blank nodes
+    pub fn remove_from_store<R: io::Read>(
+        &mut self,
+        reader: R,
+        format: &RDFFormat,
+        base: Option<&str>,
+        reader_mode: &ReaderMode,
+    ) -> Result<Removal> {
+        let contexts = self.jsonld_contexts()?;
+        let store = self.store.as_ref().ok_or(RudofError::NoLocalStore)?;
+        let removal = store
+            .remove_from_reader(reader, format, base, reader_mode, &contexts)
+            .map_err(|e| local_store_error(store, e))?;
+        Ok(removal)
+    }
+
+    /// Removes all the triples of the current local store
+    pub fn clear_store(&mut self) -> Result<()> {
+        let store = self.store.as_ref().ok_or(RudofError::NoLocalStore)?;
+        store.clear().map_err(|e| local_store_error(store, e))
+    }
+
     /// Add a pair of node selector and shape selector to the current shapemap
     pub fn shapemap_add_node_shape_selectors(&mut self, node: NodeSelector, shape: ShapeSelector) {
         match &mut self.shapemap {
@@ -613,7 +1271,16 @@ impl Rudof {
                 })?;
                 Ok(shapemap)
             }
-            ShapeMapFormat::JSON => todo!(),
+            ShapeMapFormat::JSON => QueryShapeMap::from_json_str(s.as_str()).map_err(|e| {
+                RudofError::ShapeMapParseError {
+                    str: s.to_string(),
+                    error: format!("{e}"),
+                }
+            }),
+            ShapeMapFormat::Internal => Err(RudofError::ShapeMapParseError {
+                str: s.to_string(),
+                error: "The internal representation of shapemaps can't be read".to_string(),
+            }),
         }?;
         self.shapemap = Some(shapemap);
         Ok(())
@@ -667,39 +1334,365 @@ impl Rudof {
     }
 }
 
-fn shacl_schema_from_data<RDF: FocusRDF + Debug>(rdf_data: RDF) -> Result<ShaclSchema> {
-    let schema = ShaclParser::new(rdf_data)
-        .parse()
-        .map_err(|e| RudofError::SHACLParseError {
-            error: format!("{e}"),
-        })?;
-    Ok(schema)
+/// Subjects of the node selectors of a shapemap that are IRIs
+fn shapemap_subjects(shapemap: &QueryShapeMap) -> Vec<OxSubject> {
+    let nodes_prefixmap = shapemap.nodes_prefixmap();
+    shapemap
+        .iter()
+        .filter_map(|association| match &association.node_selector {
+            NodeSelector::Node(ObjectValue::IriRef(iri_ref)) => {
+                nodes_prefixmap.resolve_iriref(iri_ref).ok()
+            }
+            _ => None,
+        })
+        .map(|iri| SRDFGraph::iri_s2subject(&iri))
+        .collect()
 }
 
-fn shacl_format2rdf_format(shacl_format: &ShaclFormat) -> Result<RDFFormat> {
-    match shacl_format {
-        ShaclFormat::N3 => Ok(RDFFormat::N3),
-        ShaclFormat::NQuads => Ok(RDFFormat::NQuads),
-        ShaclFormat::NTriples => Ok(RDFFormat::NTriples),
-        ShaclFormat::RDFXML => Ok(RDFFormat::RDFXML),
-        ShaclFormat::TriG => Ok(RDFFormat::TriG),
-        ShaclFormat::Turtle => Ok(RDFFormat::Turtle),
-        ShaclFormat::Internal => Err(RudofError::NoInternalFormatForRDF),
+/// Nodes of a shapemap indexed by IRI, which is the only kind of node supported by the streaming validation
+fn shapemap_iri_nodes(shapemap: &QueryShapeMap) -> Result<HashMap<IriS, Vec<ShapeSelector>>> {
+    let mut nodes: HashMap<IriS, Vec<ShapeSelector>> = HashMap::new();
+    for association in shapemap.iter() {
+        let iri = match &association.node_selector {
+            NodeSelector::Node(ObjectValue::IriRef(IriRef::Iri(iri))) => iri.clone(),
+            NodeSelector::Node(ObjectValue::IriRef(IriRef::Prefixed { prefix, local })) => shapemap
+                .nodes_prefixmap()
+                .resolve_prefix_local(prefix, local)
+                .map_err(|e| RudofError::UnsupportedNodeSelector {
+                    selector: format!("{prefix}:{local} ({e})"),
+                })?,
+            other => {
+                return Err(RudofError::UnsupportedNodeSelector {
+                    selector: format!("{other:?}"),
+                })
+            }
+        };
+        nodes
+            .entry(iri)
+            .or_default()
+            .push(association.shape_selector.clone());
     }
+    Ok(nodes)
 }
 
-#[cfg(test)]
-mod tests {
-    use iri_s::iri;
-    use shacl_ast::ShaclFormat;
-    use shacl_validation::shacl_processor::ShaclValidationMode;
-    use shapemap::ShapeMapFormat;
-    use shex_ast::{compiled::shape_label::ShapeLabel, Node};
+/// Focus nodes selected by the targets of the shapes of a SHACL schema in an asynchronous RDF source.
+///
+/// The triples that select the focus nodes of `sh:targetSubjectsOf` and `sh:targetObjectsOf`
+/// targets are also returned so that the validator can find them
+async fn shacl_focus_nodes<A>(
+    schema: &ShaclSchema,
+    rdf: &A,
+    neighbourhood: &mut AsyncNeighbourhood,
+) -> result::Result<Vec<OxSubject>, A::Err>
+where
+    A: AsyncSRDF<Subject = OxSubject, IRI = OxNamedNode, Term = OxTerm> + Sync,
+{
+    let prefixmap = schema.prefix_map();
+    let resolve = |iri_ref: &IriRef| prefixmap.resolve_iriref(iri_ref).ok();
+    let mut nodes = HashSet::new();
+    let mut triples = Vec::new();
+    for (_, shape) in schema.iter() {
+        for target in shape.targets() {
+            match target {
+                Target::TargetNode(node) => {
+                    if let Some(subject) = SRDFGraph::object_as_subject(node) {
+                        nodes.insert(subject);
+                    }
+                }
+                Target::TargetClass(class) => {
+                    // Classes that can't be represented as terms have no instances
+                    let Ok(class) = SRDFGraph::object_as_term(class) else {
+                        continue;
+                    };
+                    let rdf_type = SRDFGraph::iri_s2iri(&RDF_TYPE);
+                    let subjects = rdf
+                        .get_subjects_for_object_predicate(&class, &rdf_type)
+                        .await?;
+                    nodes.extend(subjects.iter().cloned());
+                    neighbourhood.add_subjects_for_object_predicate(&class, &rdf_type, subjects)
+                }
+                Target::TargetSubjectsOf(pred) | Target::TargetObjectsOf(pred) => {
+                    let Some(pred) = resolve(pred) else { continue };
+                    let subjects_of = matches!(target, Target::TargetSubjectsOf(_));
+                    for (subject, pred, object) in rdf
+                        .get_triples_with_predicate(&SRDFGraph::iri_s2iri(&pred))
+                        .await?
+                    {
+                        let node = if subjects_of {
+                            Some(subject.clone())
+                        } else {
+                            SRDFGraph::term_as_subject(&object)
+                        };
+                        nodes.extend(node);
+                        triples.push(OxTriple::new(subject, pred, object))
+                    }
+                }
+            }
+        }
+    }
+    neighbourhood.add_triples(triples);
+    Ok(nodes.into_iter().collect())
+}
+
+/// Nodes selected by the node selectors of a shapemap in an asynchronous RDF source with their shape selectors,
+/// which are grouped by node.
+///
+/// SPARQL selectors are run by the source and the other selectors select the nodes in `data`,
+/// which is extended with the arcs that they read from the source until none is missing
+async fn select_shapemap_nodes<A>(
+    shapemap: &QueryShapeMap,
+    rdf: &A,
+    neighbourhood: &mut AsyncNeighbourhood,
+    data: &mut RdfData,
+) -> Result<Vec<(Node, Vec<ShapeSelector>)>>
+where
+    A: AsyncSRDF<Subject = OxSubject, IRI = OxNamedNode, Term = OxTerm> + Sync,
+{
+    let async_error = |e: A::Err| RudofError::AsyncValidation {
+        error: e.to_string(),
+    };
+    let prefixmap = shapemap.nodes_prefixmap();
+    let mut query_nodes = HashMap::new();
+    for (idx, association) in shapemap.iter().enumerate() {
+        if let NodeSelector::Sparql { query } = &association.node_selector {
+            let terms = rdf.get_nodes_for_query(query).await.map_err(async_error)?;
+            let nodes: Vec<Node> = terms
+                .iter()
+                .map(|term| Node::from(SRDFGraph::term_as_object(term)))
+                .collect();
+            query_nodes.insert(idx, nodes);
+        }
+    }
+    loop {
+        add_new_triples(data, neighbourhood)?;
+        let mut selected: Vec<(Node, Vec<ShapeSelector>)> = Vec::new();
+        for (idx, association) in shapemap.iter().enumerate() {
+            let nodes = match query_nodes.get(&idx) {
+                Some(nodes) => nodes.clone(),
+                None => association
+                    .node_selector
+                    .select(data, &prefixmap)
+                    .map_err(|e| RudofError::NodeSelector {
+                        error: format!("{e}"),
+                    })?,
+            };
+            for node in nodes {
+                let shapes = match selected.iter_mut().find(|(other, _)| *other == node) {
+                    Some((_, shapes)) => shapes,
+                    None => {
+                        selected.push((node, Vec::new()));
+                        &mut selected.last_mut().unwrap().1
+                    }
+                };
+                if !shapes.contains(&association.shape_selector) {
+                    shapes.push(association.shape_selector.clone())
+                }
+            }
+        }
+        if !neighbourhood
+            .fetch_missing(rdf, &data.take_read_arcs())
+            .await
+            .map_err(async_error)?
+        {
+            return Ok(selected);
+        }
+    }
+}
+
+/// Adds the triples of the neighbourhood that are not in the data yet
+fn add_new_triples(data: &mut RdfData, neighbourhood: &mut AsyncNeighbourhood) -> Result<()> {
+    data.add_triples(neighbourhood.take_new_triples())
+        .map_err(|e| RudofError::StorageError {
+            error: format!("{e}"),
+        })
+}
+
+fn validate_shex_async_node(
+    validator: &mut ShExValidator,
+    node: &Node,
+    shapes: &[ShapeSelector],
+    data: &RdfData,
+) -> result::Result<ResultShapeMap, ValidatorError> {
+    let result = shapes
+        .iter()
+        .try_for_each(|shapes| validator.validate_node_shapes(node, shapes, data))
+        .and_then(|_| validator.result_map(None));
+    validator.reset_result_map();
+    result
+}
+
+fn validate_shex_stream_node(
+    validator: &mut ShExValidator,
+    iri: &IriS,
+    shapes: Vec<ShapeSelector>,
+    graph: &SRDFGraph,
+    nodes_prefixmap: &PrefixMap,
+) -> result::Result<ResultShapeMap, ValidatorError> {
+    let mut shapemap = QueryShapeMap::new();
+    for shape in shapes {
+        shapemap.add_association(NodeSelector::Node(ObjectValue::iri(iri.clone())), shape);
+    }
+    let result = validator
+        .validate_shapemap(&shapemap, graph)
+        .and_then(|_| validator.result_map(Some(nodes_prefixmap.clone())));
+    validator.reset_result_map();
+    result
+}
+
+fn subject_blocks(path: &Path, format: &RDFFormat) -> Result<SubjectBlocks<BufReader<File>>> {
+    let file = File::open(path).map_err(|e| stream_error(path, e))?;
+    SubjectBlocks::new(BufReader::new(file), format).map_err(|e| stream_error(path, e))
+}
+
+/// Part of a dump around each subject that the streaming validation needs
+struct StreamReach {
+    /// Number of hops of references whose outgoing arcs are needed
+    depth: usize,
+
+    /// Whether the arcs are also followed backwards from the referenced nodes
+    inverse: bool,
+}
+
+impl StreamReach {
+    /// The `reference_depth` of the stream configuration overrides the depth required by the shapes,
+    /// which is `None` if they have no bound. As following unbounded references could load
+    /// the whole dump for each subject, a `reference_depth` must be set for those shapes
+    fn new(
+        path: &Path,
+        stream_config: &StreamConfig,
+        depth: Option<usize>,
+        inverse: bool,
+    ) -> Result<StreamReach> {
+        match stream_config.reference_depth.or(depth) {
+            Some(depth) => Ok(StreamReach { depth, inverse }),
+            None => Err(stream_error(
+                path,
+                "the shapes follow references without bound, as they are recursive, \
+                 repeat paths, check classes or have SPARQL constraints. \
+                 Set a reference depth (--reference-depth) to validate them in streaming mode",
+            )),
+        }
+    }
+
+    fn needs_index(&self) -> bool {
+        self.depth != 0 || self.inverse
+    }
+}
+
+/// Builds the disk index of a dump if the validation needs arcs that are not in the block of each subject
+fn stream_index(
+    path: &Path,
+    format: &RDFFormat,
+    stream_config: &StreamConfig,
+    reach: &StreamReach,
+) -> Result<Option<DiskIndex>> {
+    if !reach.needs_index() {
+        return Ok(None);
+    }
+    let blocks = subject_blocks(path, format)?;
+    let index = match &stream_config.index_dir {
+        Some(dir) => DiskIndex::build_in(dir, blocks),
+        None => DiskIndex::build(blocks),
+    }
+    .map_err(|e| stream_error(path, e))?;
+    Ok(Some(index))
+}
+
+/// Graph with the triples of a subject and, if there is a disk index, its neighbourhood
+fn stream_neighbourhood(
+    path: &Path,
+    block: &SubjectBlock,
+    index: &Option<DiskIndex>,
+    reach: &StreamReach,
+) -> Result<SRDFGraph> {
+    let mut triples = block.triples().to_vec();
+    if let Some(index) = index {
+        triples.extend(
+            index
+                .neighbourhood(block, reach.depth, reach.inverse)
+                .map_err(|e| stream_error(path, e))?,
+        );
+    }
+    Ok(SRDFGraph::from_triples(triples))
+}
+
+fn stream_error<E: std::fmt::Display>(path: &Path, error: E) -> RudofError {
+    RudofError::StreamingValidation {
+        path: path.display().to_string(),
+        error: format!("{error}"),
+    }
+}
+
+fn local_store_error(store: &LocalStore, error: RdfDataError) -> RudofError {
+    RudofError::LocalStore {
+        path: store.dir().display().to_string(),
+        error: format!("{error}"),
+    }
+}
+
+fn shacl_schema_from_data<RDF: FocusRDF + Debug>(rdf_data: RDF) -> Result<ShaclSchema> {
+    let schema = ShaclParser::new(rdf_data)
+        .parse()
+        .map_err(|e| RudofError::SHACLParseError {
+            error: format!("{e}"),
+        })?;
+    Ok(schema)
+}
+
+fn shex_format2rdf_format(shex_format: &ShExFormat) -> Result<RDFFormat> {
+    shex_format
+        .rdf_format()
+        .ok_or_else(|| RudofError::NoRDFFormatForShEx {
+            format: format!("{shex_format:?}"),
+        })
+}
+
+fn shacl_format2rdf_format(shacl_format: &ShaclFormat) -> Result<RDFFormat> {
+    match shacl_format {
+        ShaclFormat::N3 => Ok(RDFFormat::N3),
+        ShaclFormat::NQuads => Ok(RDFFormat::NQuads),
+        ShaclFormat::NTriples => Ok(RDFFormat::NTriples),
+        ShaclFormat::RDFXML => Ok(RDFFormat::RDFXML),
+        ShaclFormat::TriG => Ok(RDFFormat::TriG),
+        ShaclFormat::Turtle => Ok(RDFFormat::Turtle),
+        ShaclFormat::JsonLd => Ok(RDFFormat::JsonLd),
+        ShaclFormat::Internal => Err(RudofError::NoInternalFormatForRDF),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iri_s::{iri, IriS};
+    use oxigraph::io::RdfFormat;
+    use oxigraph::sparql::results::QueryResultsFormat;
+    use oxigraph::store::Store;
+    use prefixmap::{IriRef, PrefixMap};
+    use shacl_ast::ShaclFormat;
+    use shacl_validation::shacl_config::{SeverityThreshold, ShaclConfig};
+    use shacl_validation::shacl_processor::ShaclValidationMode;
+    use shacl_validation::validation_report::report::ValidationReport;
+    use shacl_validation::validation_report::result::ValidationResult;
+    use shapemap::{
+        NodeSelector, Pattern, QueryShapeMap, ResultShapeMap, ShapeMapFormat, ShapeSelector,
+    };
+    use shex_ast::{compiled::shape_label::ShapeLabel, object_value::ObjectValue, Node};
     use shex_validation::ShExFormat;
+    use srdf::lang::Lang;
+    use srdf::literal::Literal;
+    use srdf::{
+        AsyncSRDFSparql, AsyncSparqlConfig, CacheConfig, EndpointDescription, GraphSelector,
+        Object, QueryMethod, QuerySRDF, SHACLPath, SRDFBasic, SRDFGraph, SRDFSparql,
+        SRDFSparqlError, SparqlConnectionConfig, StreamConfig, RDF_TYPE, SRDF,
+    };
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
 
     use crate::RudofConfig;
 
-    use super::Rudof;
+    use super::{Result, Rudof, RudofError, UmlGenerationMode};
 
     #[test]
     fn test_shex_validation_ok() {
@@ -845,6 +1838,201 @@ mod tests {
         assert!(!result.conforms())
     }
 
+    #[test]
+    fn test_shacl_validation_severity_threshold() {
+        let data = r#"prefix : <http://example.org/>
+        :x :p "hi" .
+        "#;
+        let shacl = r#"prefix :       <http://example.org/>
+            prefix sh:     <http://www.w3.org/ns/shacl#>
+            prefix xsd:    <http://www.w3.org/2001/XMLSchema#>
+
+            :S a sh:NodeShape;
+             sh:targetNode :x ;
+            sh:property [
+                sh:path     :p ;
+                sh:datatype xsd:integer ;
+                sh:severity sh:Warning ;
+                sh:message "{$value} is not an integer"@en, "{$value} no es un entero"@es ;
+            ] .
+             "#;
+        let config = |threshold| {
+            let shacl_config = ShaclConfig {
+                severity_threshold: Some(threshold),
+                preferred_language: Some("es".to_string()),
+                ..ShaclConfig::default()
+            };
+            RudofConfig::default().with_shacl_config(shacl_config)
+        };
+        for (threshold, conforms) in [
+            (SeverityThreshold::Warning, false),
+            (SeverityThreshold::Violation, true),
+        ] {
+            let mut rudof = Rudof::new(&config(threshold));
+            rudof
+                .read_data(
+                    data.as_bytes(),
+                    &srdf::RDFFormat::Turtle,
+                    None,
+                    &srdf::ReaderMode::Strict,
+                )
+                .unwrap();
+            rudof
+                .read_shacl(
+                    shacl.as_bytes(),
+                    &ShaclFormat::Turtle,
+                    None,
+                    &srdf::ReaderMode::Lax,
+                )
+                .unwrap();
+            let result = rudof
+                .validate_shacl(
+                    &ShaclValidationMode::Native,
+                    &crate::ShapesGraphSource::CurrentSchema,
+                )
+                .unwrap();
+            assert_eq!(result.conforms(), conforms);
+            let expected =
+                Object::Literal(Literal::lang_str("hi no es un entero", Lang::new("es")));
+            assert_eq!(result.results()[0].message(), Some(&expected));
+        }
+    }
+
+    #[test]
+    fn test_shacl_report_as_rdf() {
+        let path = SHACLPath::Sequence {
+            paths: vec![
+                SHACLPath::iri(iri!("http://example.org/p")),
+                SHACLPath::Inverse {
+                    path: Box::new(SHACLPath::iri(iri!("http://example.org/q"))),
+                },
+            ],
+        };
+        // The labels of the blank nodes of the data must not clash with the nodes of the report
+        let result = ValidationResult::new(
+            Object::BlankNode("report".to_string()),
+            Object::iri(iri!(
+                "http://www.w3.org/ns/shacl#MinCountConstraintComponent"
+            )),
+            Object::iri(iri!("http://www.w3.org/ns/shacl#Violation")),
+        )
+        .with_path(Some(path.clone()));
+        let report = ValidationReport::new().with_results(vec![result]);
+        let rdf: SRDFGraph = report.as_rdf().unwrap();
+        let reports = rdf
+            .subjects_with_predicate_object(
+                &SRDFGraph::iri_s2iri(&RDF_TYPE),
+                &SRDFGraph::iri_s2term(&shacl_ast::SH_VALIDATION_REPORT),
+            )
+            .unwrap();
+        assert_eq!(reports.len(), 1);
+        let report_node = SRDFGraph::subject_as_term(reports.iter().next().unwrap());
+        let parsed = ValidationReport::parse(&rdf, report_node).unwrap();
+        assert_eq!(parsed.results().len(), 1);
+        assert_eq!(parsed.results()[0].path(), Some(&path));
+    }
+
+    #[test]
+    fn test_shacl_sparql_validation_ko() {
+        let data = r#"prefix : <http://example.org/>
+        :x :p 23 .
+        :y :p 5 .
+        "#;
+        let shacl = r#"prefix :       <http://example.org/>
+            prefix sh:     <http://www.w3.org/ns/shacl#>
+
+            :S a sh:NodeShape;
+               sh:targetNode :x, :y ;
+               sh:sparql [
+                 sh:select """
+                   SELECT $this ?value WHERE {
+                     $this <http://example.org/p> ?value .
+                     FILTER (?value > 10)
+                   }
+                 """
+               ] .
+             "#;
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof
+            .read_data(
+                data.as_bytes(),
+                &srdf::RDFFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+
+        rudof
+            .read_shacl(
+                shacl.as_bytes(),
+                &ShaclFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Lax,
+            )
+            .unwrap();
+        let result = rudof
+            .validate_shacl(
+                &ShaclValidationMode::Native,
+                &crate::ShapesGraphSource::CurrentSchema,
+            )
+            .unwrap();
+        assert_eq!(result.results().len(), 1)
+    }
+
+    fn validate_shacl_sparql(data: &str, select: &str) -> Result<ValidationReport> {
+        let shacl = format!(
+            r#"prefix :       <http://example.org/>
+            prefix sh:     <http://www.w3.org/ns/shacl#>
+
+            :S a sh:NodeShape;
+               sh:targetClass :Person ;
+               sh:sparql [ sh:select """{select}""" ] .
+            "#
+        );
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof
+            .read_data(
+                data.as_bytes(),
+                &srdf::RDFFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        rudof
+            .read_shacl(
+                shacl.as_bytes(),
+                &ShaclFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Lax,
+            )
+            .unwrap();
+        rudof.validate_shacl(
+            &ShaclValidationMode::Native,
+            &crate::ShapesGraphSource::CurrentSchema,
+        )
+    }
+
+    #[test]
+    fn test_shacl_sparql_validation_blank_node_focus() {
+        let data = r#"prefix : <http://example.org/>
+        :bob a :Person ; :age -1 .
+        [] a :Person ; :age -2 .
+        [] a :Person ; :age 5 .
+        "#;
+        let select = "SELECT $this WHERE { $this <http://example.org/age> ?a FILTER (?a < 0) }";
+        let result = validate_shacl_sparql(data, select).unwrap();
+        assert_eq!(result.results().len(), 2)
+    }
+
+    #[test]
+    fn test_shacl_sparql_validation_failure() {
+        let data = r#"prefix : <http://example.org/>
+        :bob a :Person .
+        "#;
+        let select = "SELECT $this ?failure WHERE { BIND (true AS ?failure) }";
+        assert!(validate_shacl_sparql(data, select).is_err())
+    }
+
     #[test]
     fn test_shacl_validation_data_ko() {
         let data = r#"prefix :       <http://example.org/> 
@@ -914,4 +2102,1056 @@ mod tests {
             .unwrap();
         assert!(result.conforms())
     }
+
+    const DATASET: &str = r#"prefix :       <http://example.org/>
+        prefix sh:     <http://www.w3.org/ns/shacl#>
+        prefix xsd:    <http://www.w3.org/2001/XMLSchema#>
+
+        :g1 { :x :p 23 . }
+        :g2 { :x :other 23 . }
+        :shapes {
+            :S a sh:NodeShape;
+               sh:targetNode :x ;
+               sh:property [
+                sh:path     :p ;
+                sh:minCount 1;
+                sh:datatype xsd:integer ;
+            ] ;
+               sh:sparql [
+                 sh:select """
+                   SELECT $this ?value WHERE { $this <http://example.org/other> ?value . }
+                 """
+               ] .
+        }
+    "#;
+
+    fn validate_shacl_named_graphs(data_graph: &str) -> ValidationReport {
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof
+            .read_data(
+                DATASET.as_bytes(),
+                &srdf::RDFFormat::TriG,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        rudof.set_data_graph(GraphSelector::NamedGraph(IriS::new_unchecked(data_graph)));
+        rudof
+            .validate_shacl(
+                &ShaclValidationMode::Native,
+                &crate::ShapesGraphSource::named_graph(iri!("http://example.org/shapes")),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_shacl_validation_named_graphs() {
+        assert!(validate_shacl_named_graphs("http://example.org/g1").conforms());
+        // the sh:sparql constraint is also evaluated in the selected data graph
+        assert_eq!(
+            validate_shacl_named_graphs("http://example.org/g2")
+                .results()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_shex_validation_named_graph() {
+        let shex = r#"<http://example.org/S> { <http://example.org/p> . }"#;
+        let shapemap = r#"<http://example.org/x>@<http://example.org/S>"#;
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof
+            .read_data(
+                DATASET.as_bytes(),
+                &srdf::RDFFormat::TriG,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        rudof.set_data_graph(GraphSelector::NamedGraph(iri!("http://example.org/g1")));
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+        rudof
+            .read_shapemap(shapemap.as_bytes(), &ShapeMapFormat::default())
+            .unwrap();
+        let result = rudof.validate_shex().unwrap();
+        let node = Node::iri(iri!("http://example.org/x"));
+        let shape = ShapeLabel::iri(iri!("http://example.org/S"));
+        assert!(result.get_info(&node, &shape).unwrap().is_conformant())
+    }
+
+    const QUOTED_TRIPLES: &str = r#"prefix :       <http://example.org/>
+        prefix sh:     <http://www.w3.org/ns/shacl#>
+
+        :x :states << :alice :knows :bob >> .
+
+        :S a sh:NodeShape;
+           sh:targetNode :x ;
+           sh:property [
+            sh:path     :states ;
+            sh:minCount 1;
+            sh:nodeKind sh:IRI ;
+        ] .
+    "#;
+
+    fn validate_shex_quoted_triple(shex: &str) -> bool {
+        let shapemap = r#"<http://example.org/x>@<http://example.org/S>"#;
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof
+            .read_data(
+                QUOTED_TRIPLES.as_bytes(),
+                &srdf::RDFFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+        rudof
+            .read_shapemap(shapemap.as_bytes(), &ShapeMapFormat::default())
+            .unwrap();
+        let result = rudof.validate_shex().unwrap();
+        let node = Node::iri(iri!("http://example.org/x"));
+        let shape = ShapeLabel::iri(iri!("http://example.org/S"));
+        result.get_info(&node, &shape).unwrap().is_conformant()
+    }
+
+    #[test]
+    fn test_shex_validation_quoted_triple() {
+        assert!(validate_shex_quoted_triple(
+            r#"<http://example.org/S> { <http://example.org/states> NONLITERAL }"#
+        ));
+        assert!(!validate_shex_quoted_triple(
+            r#"<http://example.org/S> { <http://example.org/states> IRI }"#
+        ));
+        assert!(!validate_shex_quoted_triple(
+            r#"<http://example.org/S> { <http://example.org/states> LITERAL }"#
+        ));
+    }
+
+    #[test]
+    fn test_shacl_validation_quoted_triple() {
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof
+            .read_data(
+                QUOTED_TRIPLES.as_bytes(),
+                &srdf::RDFFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        let result = rudof
+            .validate_shacl(
+                &ShaclValidationMode::Native,
+                &crate::ShapesGraphSource::CurrentData,
+            )
+            .unwrap();
+        // the quoted triple is a value node, so sh:minCount holds but sh:nodeKind sh:IRI fails
+        assert_eq!(result.results().len(), 1);
+    }
+
+    const STREAM_DUMP: &str = r#"<http://example.org/alice> <http://example.org/knows> <http://example.org/bob> .
+<http://example.org/alice> <http://example.org/name> "Alice" .
+<http://example.org/alice> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/Person> .
+<http://example.org/bob> <http://example.org/name> "Bob" .
+<http://example.org/carol> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/Person> .
+"#;
+
+    fn stream_dump(name: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("{name}-{}.nt", std::process::id()));
+        std::fs::write(&path, STREAM_DUMP).unwrap();
+        path
+    }
+
+    fn validate_shex_stream(config: &RudofConfig) -> Result<(ResultShapeMap, usize)> {
+        let shex = r#"prefix : <http://example.org/>
+            :Person { :name . ; :knows @:Person * }"#;
+        let shapemap = r#"<http://example.org/alice>@<http://example.org/Person>,
+            <http://example.org/dave>@<http://example.org/Person>"#;
+        let mut rudof = Rudof::new(config);
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+        rudof
+            .read_shapemap(shapemap.as_bytes(), &ShapeMapFormat::default())
+            .unwrap();
+        let path = stream_dump("shex_stream");
+        let mut result = ResultShapeMap::new();
+        let mut reports = 0;
+        let progress = rudof.validate_shex_stream(
+            &path,
+            &srdf::RDFFormat::NTriples,
+            |map| {
+                for (node, shape, status) in map.iter() {
+                    result
+                        .add_result(node.clone(), shape.clone(), status.clone())
+                        .unwrap()
+                }
+                Ok(())
+            },
+            |_| reports += 1,
+        );
+        std::fs::remove_file(path).unwrap();
+        let progress = progress?;
+        assert_eq!(progress.subjects, 3);
+        assert_eq!(progress.triples, 5);
+        assert_eq!(progress.validated, 2);
+        Ok((result, reports))
+    }
+
+    fn is_conformant(result: &ResultShapeMap, node: &str) -> bool {
+        let node = Node::iri(IriS::new_unchecked(node));
+        let shape = ShapeLabel::iri(iri!("http://example.org/Person"));
+        result.get_info(&node, &shape).unwrap().is_conformant()
+    }
+
+    #[test]
+    fn test_shex_stream_validation() {
+        // The shape is recursive, so its references are only followed up to the configured depth
+        assert!(matches!(
+            validate_shex_stream(&RudofConfig::default()),
+            Err(RudofError::StreamingValidation { .. })
+        ));
+        let config =
+            RudofConfig::default().with_stream_config(StreamConfig::new().with_reference_depth(3));
+        let (result, reports) = validate_shex_stream(&config).unwrap();
+        assert!(is_conformant(&result, "http://example.org/alice"));
+        assert!(!is_conformant(&result, "http://example.org/dave"));
+        assert_eq!(reports, 1)
+    }
+
+    #[test]
+    fn test_shex_stream_validation_without_references() {
+        let config =
+            RudofConfig::default().with_stream_config(StreamConfig::new().with_reference_depth(0));
+        let (result, _) = validate_shex_stream(&config).unwrap();
+        // The neighbourhood of bob is not available
+        assert!(!is_conformant(&result, "http://example.org/alice"));
+        assert!(!is_conformant(&result, "http://example.org/dave"));
+    }
+
+    #[test]
+    fn test_shex_stream_validation_with_index() {
+        let config =
+            RudofConfig::default().with_stream_config(StreamConfig::new().with_reference_depth(2));
+        let (result, _) = validate_shex_stream(&config).unwrap();
+        assert!(is_conformant(&result, "http://example.org/alice"));
+        assert!(!is_conformant(&result, "http://example.org/dave"));
+    }
+
+    fn validate_shacl_stream(shapes: &str) -> Vec<ValidationResult> {
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof
+            .read_shacl(
+                shapes.as_bytes(),
+                &ShaclFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        let path = stream_dump(&format!("shacl_stream-{}", shapes.len()));
+        let mut results = Vec::new();
+        let progress = rudof
+            .validate_shacl_stream(
+                &ShaclValidationMode::Native,
+                &path,
+                &srdf::RDFFormat::NTriples,
+                |report| {
+                    results.extend(report.results().iter().cloned());
+                    Ok(())
+                },
+                |_| {},
+            )
+            .unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(progress.subjects, 3);
+        results
+    }
+
+    #[test]
+    fn test_shacl_stream_validation() {
+        let shapes = r#"prefix :       <http://example.org/>
+            prefix sh:     <http://www.w3.org/ns/shacl#>
+
+            :PersonShape a sh:NodeShape;
+               sh:targetClass :Person ;
+               sh:property [
+                sh:path     :name ;
+                sh:minCount 1;
+            ] .
+        "#;
+        // carol is a person without name
+        assert_eq!(validate_shacl_stream(shapes).len(), 1);
+    }
+
+    #[test]
+    fn test_shacl_stream_validation_references() {
+        let shapes = r#"prefix :       <http://example.org/>
+            prefix sh:     <http://www.w3.org/ns/shacl#>
+
+            :KnowsShape a sh:NodeShape;
+               sh:targetSubjectsOf :knows ;
+               sh:property [
+                sh:path     :knows ;
+                sh:node     :NamedShape ;
+            ] .
+
+            :NamedShape a sh:NodeShape;
+               sh:property [
+                sh:path     :name ;
+                sh:minCount 1;
+            ] .
+        "#;
+        // the name of bob is found in the disk index
+        assert!(validate_shacl_stream(shapes).is_empty());
+    }
+
+    #[test]
+    fn test_shex_validation_local_store() {
+        let dir = std::env::temp_dir().join(format!("rudof-store-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let data = r#"prefix : <http://example.org/>
+            :alice :name "Alice" ; :knows :bob .
+            :bob :name "Bob" .
+        "#;
+        let removed = r#"prefix : <http://example.org/>
+            :bob :name "Bob" .
+        "#;
+        let shex = r#"prefix : <http://example.org/>
+            :Person { :name . ; :knows @:Person * }"#;
+        let shapemap = r#":alice@:Person"#;
+
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof.open_store(&dir).unwrap();
+        let len = rudof
+            .load_store(
+                data.as_bytes(),
+                &srdf::RDFFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        assert_eq!(len, 3);
+
+        // A new instance validates the data persisted in the store
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof.open_store(&dir).unwrap();
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+        rudof
+            .read_shapemap(shapemap.as_bytes(), &ShapeMapFormat::Compact)
+            .unwrap();
+        let result = rudof.validate_shex().unwrap();
+        assert!(is_conformant(&result, "http://example.org/alice"));
+
+        let removal = rudof
+            .remove_from_store(
+                removed.as_bytes(),
+                &srdf::RDFFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        assert_eq!(removal.removed, 1);
+        rudof.reset_validation_results();
+        let result = rudof.validate_shex().unwrap();
+        assert!(!is_conformant(&result, "http://example.org/alice"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_shex_validation_json_shapemap() {
+        let data = r#"prefix : <http://example.org/>
+            :alice :name "Alice" .
+            :bob :knows :alice .
+        "#;
+        let shex = r#"prefix : <http://example.org/>
+            :Person { :name . }"#;
+        let shapemap = r#"[
+            {"node": "http://example.org/alice", "shape": "http://example.org/Person"},
+            {"node": "http://example.org/bob", "shape": "http://example.org/Person"}
+        ]"#;
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof
+            .read_data(
+                data.as_bytes(),
+                &srdf::RDFFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+        rudof
+            .read_shapemap(shapemap.as_bytes(), &ShapeMapFormat::JSON)
+            .unwrap();
+        let result = rudof.validate_shex().unwrap();
+        assert!(is_conformant(&result, "http://example.org/alice"));
+        assert!(!is_conformant(&result, "http://example.org/bob"));
+
+        // The results serialized as JSON can be used again as a shapemap
+        let json = result.as_json_string().unwrap();
+        rudof.reset_validation_results();
+        rudof
+            .read_shapemap(json.as_bytes(), &ShapeMapFormat::JSON)
+            .unwrap();
+        let mut output = Vec::new();
+        rudof
+            .serialize_shapemap(
+                &ShapeMapFormat::JSON,
+                &super::ShapeMapFormatter::default(),
+                &mut output,
+            )
+            .unwrap();
+        let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(
+            output,
+            serde_json::json!([
+                {"node": "http://example.org/alice", "shape": "http://example.org/Person"},
+                {"node": "http://example.org/bob", "shape": "http://example.org/Person"}
+            ])
+        );
+        assert_eq!(rudof.validate_shex().unwrap(), result);
+    }
+
+    #[test]
+    fn test_shex_validation_node_selectors() {
+        let data = r#"prefix : <http://example.org/>
+            :alice a :Person ; :name "Alice" ; :knows :bob .
+            :bob a :Person ; :name "Bob" .
+            :carol a :Person .
+            :dave :name "Dave" .
+        "#;
+        let shex = r#"prefix : <http://example.org/>
+            :Person { :name . }"#;
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof
+            .read_data(
+                data.as_bytes(),
+                &srdf::RDFFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+
+        rudof
+            .read_shapemap(
+                "{FOCUS a :Person}@:Person".as_bytes(),
+                &ShapeMapFormat::Compact,
+            )
+            .unwrap();
+        let result = rudof.validate_shex().unwrap();
+        assert!(is_conformant(&result, "http://example.org/alice"));
+        assert!(is_conformant(&result, "http://example.org/bob"));
+        assert!(!is_conformant(&result, "http://example.org/carol"));
+        assert_eq!(result.iter().count(), 3);
+
+        rudof.reset_validation_results();
+        rudof
+            .read_shapemap(
+                r#"SPARQL "select ?x where { ?x <http://example.org/name> ?n }"@:Person"#
+                    .as_bytes(),
+                &ShapeMapFormat::Compact,
+            )
+            .unwrap();
+        let result = rudof.validate_shex().unwrap();
+        assert!(is_conformant(&result, "http://example.org/dave"));
+        assert_eq!(result.iter().count(), 3);
+
+        let selector = NodeSelector::TriplePattern {
+            subject: Pattern::Node(ObjectValue::prefixed("", "alice")),
+            pred: IriRef::prefixed("", "knows"),
+            object: Pattern::Focus,
+        };
+        let nodes = rudof.select_nodes(&selector).unwrap();
+        assert_eq!(
+            nodes,
+            vec![Node::iri(IriS::new_unchecked("http://example.org/bob"))]
+        );
+    }
+
+    #[test]
+    fn test_shex_validation_resolves_nodes_with_shapemap_prefixmap() {
+        let data = r#"prefix : <http://example.org/>
+            :alice :name "Alice" .
+        "#;
+        let shex = r#"prefix : <http://example.org/>
+            :Person { :name . }"#;
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof
+            .read_data(
+                data.as_bytes(),
+                &srdf::RDFFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+        // The prefix ex is declared by the shapemap but not by the RDF data
+        let mut nodes_prefixmap = PrefixMap::new();
+        nodes_prefixmap
+            .insert("ex", &iri!("http://example.org/"))
+            .unwrap();
+        let mut shapemap = QueryShapeMap::new().with_nodes_prefixmap(&nodes_prefixmap);
+        shapemap.add_association(
+            NodeSelector::prefixed("ex", "alice"),
+            ShapeSelector::iri_unchecked("http://example.org/Person"),
+        );
+        rudof.shapemap = Some(shapemap);
+        let result = rudof.validate_shex().unwrap();
+        assert!(is_conformant(&result, "http://example.org/alice"));
+    }
+
+    #[test]
+    fn test_shex_validation_resolves_cli_nodes_with_data_prefixmap() {
+        let data = r#"prefix : <http://example.org/>
+            :a :name "Alice" .
+        "#;
+        let shex = r#"prefix : <http://example.org/>
+            :Person { :name . }"#;
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof
+            .read_data(
+                data.as_bytes(),
+                &srdf::RDFFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+        // Like `-n :a -l :Person`, whose shapemap has no prefixmap
+        rudof.shapemap_add_node_shape_selectors(
+            NodeSelector::prefixed("", "a"),
+            ShapeSelector::prefixed("", "Person"),
+        );
+        let result = rudof.validate_shex().unwrap();
+        assert!(is_conformant(&result, "http://example.org/a"));
+    }
+
+    /// Stand-in SPARQL endpoint that answers GET requests with an in-memory store.
+    /// The first `failures` requests are answered with `503 Service Unavailable`
+    async fn sparql_server(data: &str, failures: usize) -> (IriS, Arc<AtomicUsize>) {
+        let server = SparqlServer::start(data, failures, None).await;
+        (server.endpoint, server.requests)
+    }
+
+    /// Stand-in SPARQL endpoint that answers GET and URL-encoded POST queries with an in-memory store
+    struct SparqlServer {
+        endpoint: IriS,
+        requests: Arc<AtomicUsize>,
+
+        /// HTTP methods of the requests that have been answered
+        methods: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl SparqlServer {
+        /// Requests whose `Authorization` header differs from `authorization`, when it is set,
+        /// are answered with `401 Unauthorized`
+        async fn start(data: &str, failures: usize, authorization: Option<&str>) -> SparqlServer {
+            let store = Store::new().unwrap();
+            store
+                .load_from_reader(RdfFormat::Turtle, data.as_bytes())
+                .unwrap();
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let endpoint = IriS::new_unchecked(
+                format!("http://{}/sparql", listener.local_addr().unwrap()).as_str(),
+            );
+            let requests = Arc::new(AtomicUsize::new(0));
+            let methods = Arc::new(Mutex::new(Vec::new()));
+            let counter = requests.clone();
+            let method_log = methods.clone();
+            let authorization = authorization.map(|a| a.to_string());
+            tokio::spawn(async move {
+                loop {
+                    let (mut socket, _) = listener.accept().await.unwrap();
+                    let request = read_request(&mut socket).await;
+                    let (head, body) = request.split_once("\r\n\r\n").unwrap();
+                    let mut lines = head.lines();
+                    let mut request_line = lines.next().unwrap().split_whitespace();
+                    let method = request_line.next().unwrap().to_string();
+                    let target = request_line.next().unwrap();
+                    let header = |name: &str| {
+                        head.lines().find_map(|line| {
+                            line.split_once(':')
+                                .filter(|(n, _)| n.eq_ignore_ascii_case(name))
+                                .map(|(_, v)| v.trim().to_string())
+                        })
+                    };
+                    let params = if method == "POST" {
+                        body.to_string()
+                    } else {
+                        url::Url::parse(&format!("http://localhost{target}"))
+                            .unwrap()
+                            .query()
+                            .unwrap_or_default()
+                            .to_string()
+                    };
+                    let query = url::form_urlencoded::parse(params.as_bytes())
+                        .find(|(name, _)| name == "query")
+                        .unwrap()
+                        .1
+                        .to_string();
+                    let authorized =
+                        authorization.is_none() || header("authorization") == authorization;
+                    let response = if !authorized {
+                        "HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+                    } else if counter.fetch_add(1, Ordering::SeqCst) < failures {
+                        "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+                    } else {
+                        method_log.lock().unwrap().push(method);
+                        let results = store.query(query.as_str()).unwrap();
+                        let body = results.write(Vec::new(), QueryResultsFormat::Json).unwrap();
+                        let body = String::from_utf8(body).unwrap();
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/sparql-results+json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                            body.len()
+                        )
+                    };
+                    socket.write_all(response.as_bytes()).await.unwrap();
+                    socket.shutdown().await.unwrap();
+                }
+            });
+            SparqlServer {
+                endpoint,
+                requests,
+                methods,
+            }
+        }
+    }
+
+    /// Reads the head of a request and its body, whose length is given by `Content-Length`
+    async fn read_request(socket: &mut tokio::net::TcpStream) -> String {
+        let mut request = Vec::new();
+        let mut buffer = [0; 4096];
+        loop {
+            let n = socket.read(&mut buffer).await.unwrap();
+            request.extend_from_slice(&buffer[..n]);
+            let text = String::from_utf8_lossy(&request);
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|line| {
+                        line.split_once(':')
+                            .filter(|(n, _)| n.eq_ignore_ascii_case("content-length"))
+                            .map(|(_, v)| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if body.len() >= length || n == 0 {
+                    return text.to_string();
+                }
+            }
+        }
+    }
+
+    const ASYNC_DATA: &str = r#"prefix : <http://example.org/>
+        :alice a :Person ; :name "Alice" ; :knows :bob .
+        :bob a :Person ; :name "Bob" ; :knows :carol .
+        :carol a :Person .
+    "#;
+
+    fn async_config() -> RudofConfig {
+        RudofConfig::default().with_async_sparql_config(
+            AsyncSparqlConfig::new()
+                .with_batch_size(1)
+                .with_concurrency(2)
+                .with_retries(2, 10)
+                .with_reference_depth(2),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_shex_validation_async_endpoint() {
+        let (endpoint, requests) = sparql_server(ASYNC_DATA, 1).await;
+        let shex = r#"prefix : <http://example.org/>
+            :Person { :name . ; :knows @:Person * }"#;
+        let shapemap = r#"<http://example.org/alice>@<http://example.org/Person>,
+            <http://example.org/carol>@<http://example.org/Person>"#;
+        let mut rudof = Rudof::new(&async_config());
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+        rudof
+            .read_shapemap(shapemap.as_bytes(), &ShapeMapFormat::Compact)
+            .unwrap();
+        let sparql = AsyncSRDFSparql::new(&endpoint, &PrefixMap::new())
+            .unwrap()
+            .with_config(rudof.config().async_sparql_config());
+        let result = rudof.validate_shex_async(&sparql).await.unwrap();
+        // alice is not conformant because carol, who is known by bob, has no name
+        assert!(!is_conformant(&result, "http://example.org/alice"));
+        assert!(!is_conformant(&result, "http://example.org/carol"));
+        // The first request failed and was retried
+        assert!(requests.load(Ordering::SeqCst) > 1);
+    }
+
+    #[tokio::test]
+    async fn test_shex_validation_async_fetches_missing_arcs() {
+        let data = r#"prefix : <http://example.org/>
+            :alice :knows :bob .
+            :bob :knows :carol .
+            :carol :address [ :city "Oviedo" ] .
+            :erin :knows :frank .
+            :frank :knows :grace .
+            :grace :address [ :zip "33003" ] .
+        "#;
+        let (endpoint, _) = sparql_server(data, 0).await;
+        let shex = r#"prefix : <http://example.org/>
+            :Person { :knows @:Friend }
+            :Friend { :knows @:Located }
+            :Located { :address { :city . } }"#;
+        let shapemap = r#"<http://example.org/alice>@<http://example.org/Person>,
+            <http://example.org/erin>@<http://example.org/Person>"#;
+        let config = RudofConfig::default()
+            .with_async_sparql_config(AsyncSparqlConfig::new().with_reference_depth(0));
+        let mut rudof = Rudof::new(&config);
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+        rudof
+            .read_shapemap(shapemap.as_bytes(), &ShapeMapFormat::Compact)
+            .unwrap();
+        let sparql = AsyncSRDFSparql::new(&endpoint, &PrefixMap::new()).unwrap();
+        let result = rudof.validate_shex_async(&sparql).await.unwrap();
+        // The arcs of bob, carol and the blank node of the address of carol are not prefetched
+        // and are fetched when the validation needs them
+        assert!(is_conformant(&result, "http://example.org/alice"));
+        // The address of grace has no city
+        assert!(!is_conformant(&result, "http://example.org/erin"));
+    }
+
+    #[tokio::test]
+    async fn test_shex_validation_async_selectors() {
+        let (endpoint, _) = sparql_server(ASYNC_DATA, 0).await;
+        let shex = r#"prefix : <http://example.org/>
+            :Person { :name . }"#;
+        let shapemap = r#"{FOCUS <http://example.org/knows> _}@<http://example.org/Person>,
+            SPARQL "select ?x where { ?x a <http://example.org/Person> }"@<http://example.org/Person>,
+            "Alice"@<http://example.org/Person>"#;
+        let config = RudofConfig::default()
+            .with_async_sparql_config(AsyncSparqlConfig::new().with_reference_depth(0));
+        let mut rudof = Rudof::new(&config);
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+        rudof
+            .read_shapemap(shapemap.as_bytes(), &ShapeMapFormat::Compact)
+            .unwrap();
+        let sparql = AsyncSRDFSparql::new(&endpoint, &PrefixMap::new()).unwrap();
+        let result = rudof.validate_shex_async(&sparql).await.unwrap();
+        // alice and bob are selected by the triple pattern and the query, carol only by the query
+        assert!(is_conformant(&result, "http://example.org/alice"));
+        assert!(is_conformant(&result, "http://example.org/bob"));
+        assert!(!is_conformant(&result, "http://example.org/carol"));
+        let alice = Node::literal(Literal::str("Alice"));
+        let shape = ShapeLabel::iri(iri!("http://example.org/Person"));
+        assert!(!result.get_info(&alice, &shape).unwrap().is_conformant());
+        assert_eq!(result.iter().count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_shacl_validation_async_fetches_missing_arcs() {
+        let data = r#"prefix : <http://example.org/>
+            :alice :knows :bob, :dave .
+            :bob :name "Bob" .
+            :dave :knows :bob .
+        "#;
+        let (endpoint, _) = sparql_server(data, 0).await;
+        let shapes = r#"prefix :       <http://example.org/>
+            prefix sh:     <http://www.w3.org/ns/shacl#>
+
+            :FriendShape a sh:NodeShape;
+               sh:targetNode :alice ;
+               sh:property [
+                sh:path     :knows ;
+                sh:minCount 2 ;
+                sh:node     :NamedShape ;
+            ] .
+            :NamedShape a sh:NodeShape;
+               sh:property [
+                sh:path     :name ;
+                sh:minCount 1;
+            ] .
+        "#;
+        let config = RudofConfig::default()
+            .with_async_sparql_config(AsyncSparqlConfig::new().with_reference_depth(0));
+        let mut rudof = Rudof::new(&config);
+        rudof
+            .read_shacl(
+                shapes.as_bytes(),
+                &ShaclFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        let sparql = AsyncSRDFSparql::new(&endpoint, &PrefixMap::new()).unwrap();
+        let report = rudof
+            .validate_shacl_async(&ShaclValidationMode::Native, &sparql)
+            .await
+            .unwrap();
+        // The arcs of bob and dave are not prefetched and are fetched when the validation needs
+        // them, and dave has no name
+        assert_eq!(report.results().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shacl_validation_async_endpoint() {
+        let (endpoint, _) = sparql_server(ASYNC_DATA, 0).await;
+        let shapes = r#"prefix :       <http://example.org/>
+            prefix sh:     <http://www.w3.org/ns/shacl#>
+
+            :PersonShape a sh:NodeShape;
+               sh:targetClass :Person ;
+               sh:property [
+                sh:path     :name ;
+                sh:minCount 1;
+            ] .
+        "#;
+        let mut rudof = Rudof::new(&async_config());
+        rudof
+            .read_shacl(
+                shapes.as_bytes(),
+                &ShaclFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        let sparql = AsyncSRDFSparql::new(&endpoint, &PrefixMap::new()).unwrap();
+        let report = rudof
+            .validate_shacl_async(&ShaclValidationMode::Native, &sparql)
+            .await
+            .unwrap();
+        // carol is a person without name
+        assert_eq!(report.results().len(), 1);
+    }
+
+    #[test]
+    fn test_shex_validation_cached_endpoint() {
+        // The server runs in its own runtime because the SPARQL endpoint client is blocking
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (endpoint, requests) = runtime.block_on(sparql_server(ASYNC_DATA, 0));
+        let shex = r#"prefix : <http://example.org/>
+            :Person { :name . ; :knows @:Person * }"#;
+        let shapemap = r#"<http://example.org/alice>@<http://example.org/Person>,
+            <http://example.org/carol>@<http://example.org/Person>"#;
+        let config = RudofConfig::default().with_endpoint_cache_config(CacheConfig::new());
+        let mut rudof = Rudof::new(&config);
+        rudof.add_endpoint(&endpoint, &PrefixMap::new()).unwrap();
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+        rudof
+            .read_shapemap(shapemap.as_bytes(), &ShapeMapFormat::Compact)
+            .unwrap();
+        let result = rudof.validate_shex().unwrap();
+        assert!(!is_conformant(&result, "http://example.org/alice"));
+        let stats = rudof.endpoint_cache_stats().unwrap();
+        assert_eq!(stats.prefetched, 2);
+        assert!(stats.hits > 0);
+
+        // A second validation is answered from the cache
+        let sent = requests.load(Ordering::SeqCst);
+        rudof.reset_validation_results();
+        rudof.validate_shex().unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), sent);
+    }
+    #[test]
+    fn test_shex_validation_endpoint_nested_blank_nodes() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let data = r#"prefix : <http://example.org/>
+            :alice :address [ :street "Main" ; :geo [ :lat 1 ; :source [ :name "GPS" ] ] ] .
+            :bob :address [ :street "Second" ; :geo [ :lat 2 ; :source [ ] ] ] .
+        "#;
+        let (endpoint, _) = runtime.block_on(sparql_server(data, 0));
+        let shex = r#"prefix : <http://example.org/>
+            :Person { :address { :street . ; :geo { :lat . ; :source { :name . } } } }"#;
+        let shapemap = r#"<http://example.org/alice>@<http://example.org/Person>,
+            <http://example.org/bob>@<http://example.org/Person>"#;
+        // The chains of blank nodes are longer than the ones obtained by the first query
+        for config in [
+            RudofConfig::default(),
+            RudofConfig::default().with_endpoint_cache_config(CacheConfig::new()),
+        ] {
+            let mut rudof = Rudof::new(&config);
+            rudof.add_endpoint(&endpoint, &PrefixMap::new()).unwrap();
+            rudof
+                .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+                .unwrap();
+            rudof
+                .read_shapemap(shapemap.as_bytes(), &ShapeMapFormat::Compact)
+                .unwrap();
+            let result = rudof.validate_shex().unwrap();
+            assert!(is_conformant(&result, "http://example.org/alice"));
+            assert!(!is_conformant(&result, "http://example.org/bob"));
+        }
+    }
+
+    #[test]
+    fn test_shex_validation_endpoint_with_auth_and_post() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = runtime.block_on(SparqlServer::start(
+            ASYNC_DATA,
+            0,
+            Some("Bearer secret-token"),
+        ));
+        let shex = r#"prefix : <http://example.org/>
+            :Person { :name . }"#;
+        let shapemap = r#"<http://example.org/bob>@<http://example.org/Person>"#;
+
+        // Requests without credentials are rejected by the endpoint
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof
+            .add_endpoint(&server.endpoint, &PrefixMap::new())
+            .unwrap();
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+        rudof
+            .read_shapemap(shapemap.as_bytes(), &ShapeMapFormat::Compact)
+            .unwrap();
+        let err = rudof.validate_shex().unwrap_err();
+        assert!(err.to_string().contains("401"));
+        assert!(server.methods.lock().unwrap().is_empty());
+
+        std::env::set_var("RUDOF_TEST_ENDPOINT_TOKEN", "secret-token");
+        let description = EndpointDescription::from_str(server.endpoint.as_str())
+            .unwrap()
+            .with_connection(
+                SparqlConnectionConfig::new()
+                    .with_bearer_token("${RUDOF_TEST_ENDPOINT_TOKEN}")
+                    .with_timeout_secs(10)
+                    .with_query_method(QueryMethod::Post),
+            );
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof.add_endpoint_description(&description).unwrap();
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+        rudof
+            .read_shapemap(shapemap.as_bytes(), &ShapeMapFormat::Compact)
+            .unwrap();
+        let result = rudof.validate_shex().unwrap();
+        assert!(is_conformant(&result, "http://example.org/bob"));
+        let methods = server.methods.lock().unwrap();
+        assert!(!methods.is_empty());
+        assert!(methods.iter().all(|m| m == "POST"));
+    }
+
+    #[test]
+    fn test_shacl_sparql_ask_endpoint() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let data = r#"prefix : <http://example.org/>
+            :spain a :Country ; :englishLabel "Spain"@en .
+            :germany a :Country ; :englishLabel "Deutschland"@de .
+        "#;
+        let server = runtime.block_on(SparqlServer::start(data, 0, None));
+        let shacl = r#"prefix :   <http://example.org/>
+            prefix sh:  <http://www.w3.org/ns/shacl#>
+
+            :LanguageConstraintComponent a sh:ConstraintComponent ;
+              sh:parameter [ sh:path :lang ] ;
+              sh:propertyValidator [
+                a sh:SPARQLAskValidator ;
+                sh:ask "ASK { FILTER (isLiteral($value) && langMatches(lang($value), $lang)) }"
+              ] .
+
+            :EnglishLabelShape a sh:NodeShape ;
+              sh:targetNode :spain, :germany ;
+              sh:property [ sh:path :englishLabel ; :lang "en" ] .
+        "#;
+        let endpoint = SRDFSparql::new(&server.endpoint, &PrefixMap::new()).unwrap();
+        assert!(endpoint
+            .query_ask("ASK { <http://example.org/spain> ?p ?o }")
+            .unwrap());
+        assert!(matches!(
+            endpoint.query_ask("SELECT * WHERE { ?s ?p ?o }"),
+            Err(SRDFSparqlError::ExpectedBoolean { .. })
+        ));
+
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        rudof
+            .add_endpoint(&server.endpoint, &PrefixMap::new())
+            .unwrap();
+        rudof
+            .read_shacl(
+                shacl.as_bytes(),
+                &ShaclFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Lax,
+            )
+            .unwrap();
+        let result = rudof
+            .validate_shacl(
+                &ShaclValidationMode::Native,
+                &crate::ShapesGraphSource::CurrentSchema,
+            )
+            .unwrap();
+        assert_eq!(result.results().len(), 1);
+        assert_eq!(
+            result.results()[0].focus_node(),
+            &Object::iri(iri!("http://example.org/germany"))
+        );
+    }
+
+    #[test]
+    fn test_schema_diagrams() {
+        let shex = r#"prefix : <http://example.org/>
+        prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+        :Person { :name xsd:string ; :knows @:Person * }
+        "#;
+        let shacl = r#"prefix :   <http://example.org/>
+            prefix sh:  <http://www.w3.org/ns/shacl#>
+            prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+
+            :Person a sh:NodeShape ;
+              sh:property [ sh:path :name ; sh:datatype xsd:string ; sh:minCount 1 ; sh:maxCount 1 ] ;
+              sh:property [ sh:path :knows ; sh:node :Person ] .
+             "#;
+        let mut rudof = Rudof::new(&RudofConfig::default());
+        assert!(rudof
+            .shacl2mermaid(&UmlGenerationMode::all(), &mut Vec::new())
+            .is_err());
+        rudof
+            .read_shex(shex.as_bytes(), &ShExFormat::ShExC, None)
+            .unwrap();
+        rudof
+            .read_shacl(
+                shacl.as_bytes(),
+                &ShaclFormat::Turtle,
+                None,
+                &srdf::ReaderMode::Strict,
+            )
+            .unwrap();
+        let mut shex_output = Vec::new();
+        rudof
+            .shex2mermaid(&UmlGenerationMode::all(), &mut shex_output)
+            .unwrap();
+        let mut shacl_output = Vec::new();
+        rudof
+            .shacl2mermaid(&UmlGenerationMode::all(), &mut shacl_output)
+            .unwrap();
+        for output in [shex_output, shacl_output] {
+            let mermaid = String::from_utf8(output).unwrap();
+            assert!(mermaid.contains(":name : xsd:string\n"));
+            assert!(mermaid.contains("n1 --> \"0..*\" n1 : :knows\n"));
+        }
+        let mut output = Vec::new();
+        rudof
+            .shex2dot(&UmlGenerationMode::neighs(":Person"), &mut output)
+            .unwrap();
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .contains("n1 -> n1 [label=\":knows\", headlabel=\"*\"];"));
+    }
 }
@@ -2,12 +2,13 @@ use std::path::Path;
 
 use dctap::TapConfig;
 use serde_derive::{Deserialize, Serialize};
+use shacl_validation::shacl_config::ShaclConfig;
 use shapes_converter::{
     ShEx2HtmlConfig, ShEx2SparqlConfig, ShEx2UmlConfig, Shacl2ShExConfig, Tap2ShExConfig,
 };
 use shex_validation::{ShExConfig, ValidatorConfig};
 use sparql_service::ServiceConfig;
-use srdf::RdfDataConfig;
+use srdf::{AsyncSparqlConfig, CacheConfig, RdfDataConfig, StreamConfig};
 
 use crate::RudofError;
 
@@ -20,11 +21,16 @@ pub struct RudofConfig {
     shex_validator: Option<ValidatorConfig>,
     shex2uml: Option<ShEx2UmlConfig>,
     shex2html: Option<ShEx2HtmlConfig>,
+    shacl2html: Option<ShEx2HtmlConfig>,
+    shacl: Option<ShaclConfig>,
     shacl2shex: Option<Shacl2ShExConfig>,
     tap2shex: Option<Tap2ShExConfig>,
     tap: Option<TapConfig>,
     shex2sparql: Option<ShEx2SparqlConfig>,
     service: Option<ServiceConfig>,
+    stream: Option<StreamConfig>,
+    async_sparql: Option<AsyncSparqlConfig>,
+    endpoint_cache: Option<CacheConfig>,
 }
 
 impl RudofConfig {
@@ -42,11 +48,37 @@ impl RudofConfig {
         self
     }
 
+    pub fn with_shacl_config(mut self, shacl_config: ShaclConfig) -> Self {
+        self.shacl = Some(shacl_config);
+        self
+    }
+
     pub fn with_shex_config(mut self, shex_config: ShExConfig) -> Self {
         self.shex = Some(shex_config);
         self
     }
 
+    pub fn with_stream_config(mut self, stream_config: StreamConfig) -> Self {
+        self.stream = Some(stream_config);
+        self
+    }
+
+    pub fn with_async_sparql_config(mut self, async_sparql_config: AsyncSparqlConfig) -> Self {
+        self.async_sparql = Some(async_sparql_config);
+        self
+    }
+
+    pub fn with_endpoint_cache_config(mut self, cache_config: CacheConfig) -> Self {
+        self.endpoint_cache = Some(cache_config);
+        self
+    }
+
+    /// Disables the cache of the endpoints
+    pub fn without_endpoint_cache(mut self) -> Self {
+        self.endpoint_cache = None;
+        self
+    }
+
     /// Obtain a DCTapConfig from a path file in YAML
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<RudofConfig, RudofError> {
         let path_name = path.as_ref().display().to_string();
@@ -108,14 +140,39 @@ impl RudofConfig {
         self.shex2html.clone().unwrap_or_default()
     }
 
+    /// The configuration of the SHACL to HTML converter, which uses the same options as the ShEx one
+    pub fn shacl2html_config(&self) -> ShEx2HtmlConfig {
+        self.shacl2html.clone().unwrap_or_else(|| ShEx2HtmlConfig {
+            title: "SHACL shapes graph".to_string(),
+            ..Default::default()
+        })
+    }
+
     pub fn service_config(&self) -> ServiceConfig {
         self.service.clone().unwrap_or_default()
     }
 
+    pub fn stream_config(&self) -> StreamConfig {
+        self.stream.clone().unwrap_or_default()
+    }
+
+    pub fn async_sparql_config(&self) -> AsyncSparqlConfig {
+        self.async_sparql.clone().unwrap_or_default()
+    }
+
+    /// Configuration of the cache of the endpoints, `None` if their answers are not cached
+    pub fn endpoint_cache_config(&self) -> Option<CacheConfig> {
+        self.endpoint_cache.clone()
+    }
+
     pub fn shex2sparql_config(&self) -> ShEx2SparqlConfig {
         self.shex2sparql.clone().unwrap_or_default()
     }
 
+    pub fn shacl_config(&self) -> ShaclConfig {
+        self.shacl.clone().unwrap_or_default()
+    }
+
     pub fn shacl2shex_config(&self) -> Shacl2ShExConfig {
         self.shacl2shex.clone().unwrap_or_default()
     }
@@ -31,6 +31,9 @@ pub enum RudofError {
     #[error("ShEx JSON parser error: {error}")]
     ShExJParserError { error: String },
 
+    #[error("ShEx RDF parser error: {error}")]
+    ShExRParserError { error: String },
+
     #[error("Compiling schema error: {error}")]
     CompilingSchemaError { error: String },
 
@@ -72,6 +75,9 @@ pub enum RudofError {
     #[error("Read error: {error} ")]
     ReadError { error: String },
 
+    #[error("Reading JSON-LD contexts from configuration: {error}")]
+    ReadingJsonLdContexts { error: String },
+
     #[error("AddingEndpoint: {iri} ")]
     AddingEndpointError { iri: IriS, error: String },
 
@@ -97,7 +103,10 @@ pub enum RudofError {
     SHACLValidationError { error: String, schema: Box<Schema> },
 
     #[error("Creating Endpoint validation for SHACL from endpoint {endpoint:?}. error: {error}")]
-    SHACLEndpointValidationCreation { error: String, endpoint: SRDFSparql },
+    SHACLEndpointValidationCreation {
+        error: String,
+        endpoint: Box<SRDFSparql>,
+    },
 
     #[error("Parsing RDF data error: {error}")]
     ParsingRDFDataReader { error: String },
@@ -123,6 +132,15 @@ pub enum RudofError {
     #[error("ShEx2PlantUML Error when generating PlantUML: {error}")]
     ShEx2PlantUmlErrorAsPlantUML { error: String },
 
+    #[error("SHACL2UML: No SHACL schema")]
+    Shacl2UmlWithoutShacl,
+
+    #[error("SHACL2UML Error: {error}")]
+    Shacl2UmlError { error: String },
+
+    #[error("Error generating {format} diagram: {error}")]
+    UmlDiagramError { format: String, error: String },
+
     #[error("Reading ShEx Schema from path: {path}: {error}")]
     ReadingShExPath { path: String, error: String },
 
@@ -147,6 +165,9 @@ pub enum RudofError {
     #[error("Converting SHACLFormat with value Internal to RDFFormat")]
     NoInternalFormatForRDF,
 
+    #[error("Converting ShExFormat with value {format} to RDFFormat")]
+    NoRDFFormatForShEx { format: String },
+
     #[error("Serializing SHACL to internal representation: {error}")]
     SerializingSHACLInternal { error: String },
 
@@ -159,6 +180,9 @@ pub enum RudofError {
     #[error("Serializing ShEx: {error}")]
     SerializingShEx { error: String },
 
+    #[error("Writing ShEx {schema} as RDF: {error}")]
+    WritingShExR { schema: String, error: String },
+
     #[error("Serializing RDF data: {error}")]
     SerializingData { error: String },
 
@@ -186,4 +210,29 @@ pub enum RudofError {
 
     #[error("Error converting DCTAP to ShEx")]
     DCTap2ShEx { error: String },
+
+    #[error("Streaming validation of {path}: {error}")]
+    StreamingValidation { path: String, error: String },
+
+    #[error("Selecting nodes: {error}")]
+    NodeSelector { error: String },
+
+    #[error(
+        "Streaming validation only supports shape maps whose nodes are IRIs, found {selector}"
+    )]
+    UnsupportedNodeSelector { selector: String },
+
+    #[error("Fetching data for asynchronous validation: {error}")]
+    AsyncValidation { error: String },
+
+    #[error("Endpoint cache: {error}")]
+    EndpointCache { error: String },
+
+    #[error("Local store at {path}: {error}")]
+    LocalStore { path: String, error: String },
+
+    #[error(
+        "No local store has been opened. A store must be opened with open_store before updating it"
+    )]
+    NoLocalStore,
 }
@@ -1,8 +1,13 @@
+use iri_s::IriS;
+
 #[derive(Debug, PartialEq, Clone, Default)]
 pub enum ShapesGraphSource {
     #[default]
     CurrentData,
     CurrentSchema,
+
+    /// Named graph of the current data that contains the shapes
+    NamedGraph(IriS),
 }
 
 impl ShapesGraphSource {
@@ -17,4 +22,8 @@ impl ShapesGraphSource {
     pub fn current_data() -> ShapesGraphSource {
         ShapesGraphSource::CurrentData
     }
+
+    pub fn named_graph(name: IriS) -> ShapesGraphSource {
+        ShapesGraphSource::NamedGraph(name)
+    }
 }
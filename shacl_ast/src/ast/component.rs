@@ -1,11 +1,15 @@
 use crate::{
-    node_kind::NodeKind, value::Value, SH_AND_STR, SH_CLASS_STR, SH_CLOSED_STR, SH_DATATYPE_STR,
-    SH_DISJOINT_STR, SH_EQUALS_STR, SH_FLAGS_STR, SH_HAS_VALUE_STR, SH_IGNORED_PROPERTIES_STR,
-    SH_IN_STR, SH_IRI_STR, SH_LANGUAGE_IN_STR, SH_LESS_THAN_OR_EQUALS_STR, SH_LESS_THAN_STR,
-    SH_MAX_COUNT_STR, SH_MAX_EXCLUSIVE_STR, SH_MAX_INCLUSIVE_STR, SH_MAX_LENGTH_STR,
+    node_kind::NodeKind,
+    sparql_constraint::{SparqlConstraint, SparqlConstraintComponent},
+    value::Value,
+    SH_AND_STR, SH_CLASS_STR, SH_CLOSED_STR, SH_DATATYPE_STR, SH_DISJOINT_STR, SH_EQUALS_STR,
+    SH_FLAGS_STR, SH_HAS_VALUE_STR, SH_IGNORED_PROPERTIES_STR, SH_IN_STR, SH_IRI_STR,
+    SH_LANGUAGE_IN_STR, SH_LESS_THAN_OR_EQUALS_STR, SH_LESS_THAN_STR, SH_MAX_COUNT_STR,
+    SH_MAX_EXCLUSIVE_STR, SH_MAX_INCLUSIVE_STR, SH_MAX_LENGTH_STR, SH_MESSAGE_STR,
     SH_MIN_COUNT_STR, SH_MIN_EXCLUSIVE_STR, SH_MIN_INCLUSIVE_STR, SH_MIN_LENGTH_STR, SH_NODE_STR,
     SH_NOT_STR, SH_OR_STR, SH_PATTERN_STR, SH_QUALIFIED_MAX_COUNT_STR, SH_QUALIFIED_MIN_COUNT_STR,
-    SH_QUALIFIED_VALUE_SHAPE_STR, SH_UNIQUE_LANG_STR, SH_XONE_STR,
+    SH_QUALIFIED_VALUE_SHAPE_STR, SH_SELECT_STR, SH_SPARQL_CONSTRAINT_COMPONENT_STR, SH_SPARQL_STR,
+    SH_UNIQUE_LANG_STR, SH_XONE_STR,
 };
 use iri_s::{iri, IriS};
 use itertools::Itertools;
@@ -13,6 +17,7 @@ use oxrdf::{Literal as OxLiteral, NamedNode, Term as OxTerm};
 use prefixmap::IriRef;
 use srdf::{lang::Lang, literal::Literal, RDFNode, SRDFBuilder, XSD_INTEGER_STR};
 use std::fmt::Display;
+use std::hash::{DefaultHasher, Hash, Hasher};
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum Component {
@@ -70,6 +75,11 @@ pub enum Component {
         qualified_max_count: Option<isize>,
         qualified_value_shapes_disjoint: Option<bool>,
     },
+    Sparql(Box<SparqlConstraint>),
+    SparqlComponent {
+        component: Box<SparqlConstraintComponent>,
+        parameter_values: Vec<(IriS, RDFNode)>,
+    },
 }
 
 impl Component {
@@ -79,7 +89,7 @@ impl Component {
     {
         match self {
             Self::Class(rdf_node) => {
-                Self::write_term(&RDF::object_as_term(rdf_node), SH_CLASS_STR, rdf_node, rdf)?;
+                Self::write_term(&RDF::object_as_term(rdf_node)?, SH_CLASS_STR, rdf_node, rdf)?;
             }
             Self::Datatype(iri) => {
                 Self::write_iri(iri, SH_DATATYPE_STR, rdf_node, rdf)?;
@@ -150,20 +160,20 @@ impl Component {
             }
             Self::Or { shapes } => {
                 shapes.iter().try_for_each(|shape| {
-                    Self::write_term(&RDF::object_as_term(shape), SH_OR_STR, rdf_node, rdf)
+                    Self::write_term(&RDF::object_as_term(shape)?, SH_OR_STR, rdf_node, rdf)
                 })?;
             }
             Self::And { shapes } => {
                 shapes.iter().try_for_each(|shape| {
-                    Self::write_term(&RDF::object_as_term(shape), SH_AND_STR, rdf_node, rdf)
+                    Self::write_term(&RDF::object_as_term(shape)?, SH_AND_STR, rdf_node, rdf)
                 })?;
             }
             Self::Not { shape } => {
-                Self::write_term(&RDF::object_as_term(shape), SH_PATTERN_STR, rdf_node, rdf)?;
+                Self::write_term(&RDF::object_as_term(shape)?, SH_PATTERN_STR, rdf_node, rdf)?;
             }
             Self::Xone { shapes } => {
                 shapes.iter().try_for_each(|shape| {
-                    Self::write_term(&RDF::object_as_term(shape), SH_XONE_STR, rdf_node, rdf)
+                    Self::write_term(&RDF::object_as_term(shape)?, SH_XONE_STR, rdf_node, rdf)
                 })?;
             }
             Self::Closed {
@@ -177,7 +187,7 @@ impl Component {
                 })?;
             }
             Self::Node { shape } => {
-                Self::write_term(&RDF::object_as_term(shape), SH_NODE_STR, rdf_node, rdf)?;
+                Self::write_term(&RDF::object_as_term(shape)?, SH_NODE_STR, rdf_node, rdf)?;
             }
             Self::HasValue { value } => match value {
                 Value::Iri(iri) => {
@@ -210,7 +220,7 @@ impl Component {
                 qualified_value_shapes_disjoint,
             } => {
                 Self::write_term(
-                    &RDF::object_as_term(shape),
+                    &RDF::object_as_term(shape)?,
                     SH_QUALIFIED_VALUE_SHAPE_STR,
                     rdf_node,
                     rdf,
@@ -228,6 +238,23 @@ impl Component {
                     Self::write_boolean(*value, SH_QUALIFIED_MAX_COUNT_STR, rdf_node, rdf)?;
                 }
             }
+            Self::Sparql(sparql) => {
+                let mut hasher = DefaultHasher::new();
+                (rdf_node, sparql).hash(&mut hasher);
+                let id = RDFNode::BlankNode(format!("sparql{:x}", hasher.finish()));
+                Self::write_term(&RDF::object_as_term(&id)?, SH_SPARQL_STR, rdf_node, rdf)?;
+                Self::write_literal(&Literal::str(sparql.select()), SH_SELECT_STR, &id, rdf)?;
+                if let Some(message) = sparql.message() {
+                    Self::write_literal(&Literal::str(message), SH_MESSAGE_STR, &id, rdf)?;
+                }
+            }
+            Self::SparqlComponent {
+                parameter_values, ..
+            } => {
+                parameter_values.iter().try_for_each(|(param, value)| {
+                    Self::write_term(&RDF::object_as_term(value)?, param.as_str(), rdf_node, rdf)
+                })?;
+            }
         }
         Ok(())
     }
@@ -360,6 +387,17 @@ impl Display for Component {
                 write!(f, "In [{str}]")
             }
             Component::QualifiedValueShape { .. } => todo!(),
+            Component::Sparql(sparql) => write!(f, "{sparql}"),
+            Component::SparqlComponent {
+                component,
+                parameter_values,
+            } => {
+                let str = parameter_values
+                    .iter()
+                    .map(|(param, value)| format!("{param}={value}"))
+                    .join(" ");
+                write!(f, "{}({str})", component.id())
+            }
         }
     }
 }
@@ -396,6 +434,8 @@ impl From<Component> for IriS {
             Component::QualifiedValueShape { .. } => {
                 IriS::new_unchecked(SH_QUALIFIED_VALUE_SHAPE_STR)
             }
+            Component::Sparql(_) => IriS::new_unchecked(SH_SPARQL_CONSTRAINT_COMPONENT_STR),
+            Component::SparqlComponent { component, .. } => component.id().clone(),
         }
     }
 }
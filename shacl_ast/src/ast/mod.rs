@@ -2,11 +2,13 @@ pub mod component;
 pub mod message_map;
 pub mod node_kind;
 pub mod node_shape;
+pub mod property_group;
 pub mod property_shape;
 pub mod schema;
 pub mod severity;
 pub mod shacl_error;
 pub mod shape;
+pub mod sparql_constraint;
 pub mod target;
 pub mod value;
 
@@ -1,7 +1,8 @@
 use crate::{
     component::Component, message_map::MessageMap, severity::Severity, target::Target,
-    SH_CLOSED_STR, SH_DEACTIVATED_STR, SH_DESCRIPTION_STR, SH_GROUP_STR, SH_INFO_STR, SH_NAME_STR,
-    SH_NODE_SHAPE, SH_PROPERTY_STR, SH_SEVERITY_STR, SH_VIOLATION_STR, SH_WARNING_STR,
+    SH_CLOSED_STR, SH_DEACTIVATED_STR, SH_DESCRIPTION_STR, SH_GROUP_STR, SH_INFO_STR,
+    SH_MESSAGE_STR, SH_NAME_STR, SH_NODE_SHAPE, SH_PROPERTY_STR, SH_SEVERITY_STR, SH_VIOLATION_STR,
+    SH_WARNING_STR,
 };
 use iri_s::iri;
 use oxrdf::{Literal as OxLiteral, Term as OxTerm};
@@ -17,7 +18,7 @@ pub struct NodeShape {
     closed: bool,
     // ignored_properties: Vec<IriRef>,
     deactivated: bool,
-    // message: MessageMap,
+    message: MessageMap,
     severity: Option<Severity>,
     name: MessageMap,
     description: MessageMap,
@@ -35,7 +36,7 @@ impl NodeShape {
             closed: false,
             // ignored_properties: Vec::new(),
             deactivated: false,
-            // message: MessageMap::new(),
+            message: MessageMap::new(),
             severity: None,
             name: MessageMap::new(),
             description: MessageMap::new(),
@@ -63,11 +64,35 @@ impl NodeShape {
         self
     }
 
+    pub fn add_components(&mut self, components: Vec<Component>) {
+        self.components.extend(components);
+    }
+
+    pub fn with_severity(mut self, severity: Option<Severity>) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn with_message(mut self, message: MessageMap) -> Self {
+        self.message = message;
+        self
+    }
+
     pub fn with_closed(mut self, closed: bool) -> Self {
         self.closed = closed;
         self
     }
 
+    pub fn with_name(mut self, name: MessageMap) -> Self {
+        self.name = name;
+        self
+    }
+
+    pub fn with_description(mut self, description: MessageMap) -> Self {
+        self.description = description;
+        self
+    }
+
     pub fn id(&self) -> &RDFNode {
         &self.id
     }
@@ -84,6 +109,18 @@ impl NodeShape {
         self.severity.to_owned()
     }
 
+    pub fn message(&self) -> &MessageMap {
+        &self.message
+    }
+
+    pub fn name(&self) -> &MessageMap {
+        &self.name
+    }
+
+    pub fn description(&self) -> &MessageMap {
+        &self.description
+    }
+
     pub fn components(&self) -> &Vec<Component> {
         &self.components
     }
@@ -118,6 +155,14 @@ impl NodeShape {
             )
         })?;
 
+        self.message.to_term_iter().try_for_each(|term| {
+            rdf.add_triple(
+                &RDF::object_as_subject(&self.id).unwrap(),
+                &RDF::iri_s2iri(&iri!(SH_MESSAGE_STR)),
+                &RDF::term_s2term(&term),
+            )
+        })?;
+
         self.components
             .iter()
             .try_for_each(|component| component.write(&self.id, rdf))?;
@@ -130,7 +175,7 @@ impl NodeShape {
             rdf.add_triple(
                 &RDF::object_as_subject(&self.id).unwrap(),
                 &RDF::iri_s2iri(&iri!(SH_PROPERTY_STR)),
-                &RDF::object_as_term(property_shape),
+                &RDF::object_as_term(property_shape)?,
             )
         })?;
 
@@ -148,7 +193,7 @@ impl NodeShape {
             rdf.add_triple(
                 &RDF::object_as_subject(&self.id).unwrap(),
                 &RDF::iri_s2iri(&iri!(SH_GROUP_STR)),
-                &RDF::object_as_term(group),
+                &RDF::object_as_term(group)?,
             )?;
         }
 
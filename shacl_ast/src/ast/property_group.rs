@@ -0,0 +1,78 @@
+use iri_s::iri;
+use oxrdf::{Literal as OxLiteral, NamedNode, Term as OxTerm};
+use srdf::{
+    numeric_literal::NumericLiteral, RDFNode, SRDFBuilder, RDFS_LABEL_STR, XSD_DECIMAL_STR,
+};
+
+use crate::{message_map::MessageMap, SH_ORDER_STR, SH_PROPERTY_GROUP};
+
+/// Group of property shapes declared with `sh:PropertyGroup` and referenced by `sh:group`,
+/// which is used to arrange the properties of a shape in forms and documentation
+#[derive(Debug, Clone)]
+pub struct PropertyGroup {
+    id: RDFNode,
+    order: Option<NumericLiteral>,
+    label: MessageMap,
+}
+
+impl PropertyGroup {
+    pub fn new(id: RDFNode) -> Self {
+        PropertyGroup {
+            id,
+            order: None,
+            label: MessageMap::new(),
+        }
+    }
+
+    pub fn with_order(mut self, order: Option<NumericLiteral>) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn with_label(mut self, label: MessageMap) -> Self {
+        self.label = label;
+        self
+    }
+
+    pub fn id(&self) -> &RDFNode {
+        &self.id
+    }
+
+    pub fn order(&self) -> Option<&NumericLiteral> {
+        self.order.as_ref()
+    }
+
+    pub fn label(&self) -> &MessageMap {
+        &self.label
+    }
+
+    pub fn write<RDF>(&self, rdf: &mut RDF) -> Result<(), RDF::Err>
+    where
+        RDF: SRDFBuilder,
+    {
+        rdf.add_type(&self.id, RDF::iri_s2term(&SH_PROPERTY_GROUP))?;
+
+        if let Some(order) = &self.order {
+            let decimal_type = NamedNode::new(XSD_DECIMAL_STR).unwrap();
+
+            let term = OxTerm::Literal(OxLiteral::new_typed_literal(
+                order.to_string(),
+                decimal_type,
+            ));
+
+            rdf.add_triple(
+                &RDF::object_as_subject(&self.id).unwrap(),
+                &RDF::iri_s2iri(&iri!(SH_ORDER_STR)),
+                &RDF::term_s2term(&term),
+            )?;
+        }
+
+        self.label.to_term_iter().try_for_each(|term| {
+            rdf.add_triple(
+                &RDF::object_as_subject(&self.id).unwrap(),
+                &RDF::iri_s2iri(&iri!(RDFS_LABEL_STR)),
+                &RDF::term_s2term(&term),
+            )
+        })
+    }
+}
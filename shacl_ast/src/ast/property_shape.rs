@@ -1,15 +1,16 @@
-use iri_s::{iri, IriS};
-use oxrdf::{BlankNode, Literal as OxLiteral, NamedNode, Subject, Term as OxTerm};
+use iri_s::iri;
+use oxrdf::{Literal as OxLiteral, NamedNode, Term as OxTerm};
 use srdf::{
-    numeric_literal::NumericLiteral, RDFNode, SHACLPath, SRDFBuilder, SRDFGraph, SRDF,
+    numeric_literal::NumericLiteral, RDFNode, SHACLPath, SRDFBasic, SRDFBuilder, SRDFGraph, SRDF,
     XSD_DECIMAL_STR,
 };
 use std::{collections::HashSet, fmt::Display};
 
 use crate::{
     component::Component, message_map::MessageMap, severity::Severity, target::Target,
-    SH_DEACTIVATED_STR, SH_DESCRIPTION_STR, SH_GROUP_STR, SH_INFO_STR, SH_NAME_STR, SH_ORDER_STR,
-    SH_PATH_STR, SH_PROPERTY_SHAPE, SH_SEVERITY_STR, SH_VIOLATION_STR, SH_WARNING_STR,
+    SH_DEACTIVATED_STR, SH_DESCRIPTION_STR, SH_GROUP_STR, SH_INFO_STR, SH_MESSAGE_STR, SH_NAME_STR,
+    SH_ORDER_STR, SH_PATH_STR, SH_PROPERTY_SHAPE, SH_SEVERITY_STR, SH_VIOLATION_STR,
+    SH_WARNING_STR,
 };
 
 #[derive(Debug, Clone)]
@@ -22,7 +23,7 @@ pub struct PropertyShape {
     closed: bool,
     // ignored_properties: Vec<IriRef>,
     deactivated: bool,
-    // message: MessageMap,
+    message: MessageMap,
     severity: Option<Severity>,
     name: MessageMap,
     description: MessageMap,
@@ -43,7 +44,7 @@ impl PropertyShape {
             closed: false,
             // ignored_properties: Vec::new(),
             deactivated: false,
-            // message: MessageMap::new(),
+            message: MessageMap::new(),
             severity: None,
             name: MessageMap::new(),
             description: MessageMap::new(),
@@ -88,6 +89,10 @@ impl PropertyShape {
         self
     }
 
+    pub fn add_components(&mut self, components: Vec<Component>) {
+        self.components.extend(components);
+    }
+
     pub fn with_closed(mut self, closed: bool) -> Self {
         self.closed = closed;
         self
@@ -98,6 +103,11 @@ impl PropertyShape {
         self
     }
 
+    pub fn with_message(mut self, message: MessageMap) -> Self {
+        self.message = message;
+        self
+    }
+
     pub fn id(&self) -> &RDFNode {
         &self.id
     }
@@ -114,6 +124,14 @@ impl PropertyShape {
         &self.description
     }
 
+    pub fn order(&self) -> Option<&NumericLiteral> {
+        self.order.as_ref()
+    }
+
+    pub fn group(&self) -> Option<&RDFNode> {
+        self.group.as_ref()
+    }
+
     pub fn is_closed(&self) -> &bool {
         &self.closed
     }
@@ -126,6 +144,10 @@ impl PropertyShape {
         self.severity.to_owned()
     }
 
+    pub fn message(&self) -> &MessageMap {
+        &self.message
+    }
+
     pub fn components(&self) -> &Vec<Component> {
         &self.components
     }
@@ -138,8 +160,6 @@ impl PropertyShape {
         &self.property_shapes
     }
 
-    // I added the following because there seems to be an unreachable pattern in object with rdf-star and Triple
-    #[allow(unreachable_patterns)]
     pub fn get_value_nodes(
         &self,
         data_graph: &SRDFGraph,
@@ -148,26 +168,16 @@ impl PropertyShape {
     ) -> HashSet<RDFNode> {
         match path {
             SHACLPath::Predicate { pred } => {
-                let subject = match focus_node {
-                    RDFNode::Iri(iri_s) => Subject::NamedNode(iri_s.as_named_node().to_owned()),
-                    RDFNode::BlankNode(id) => Subject::BlankNode(BlankNode::new_unchecked(id)),
-                    RDFNode::Literal(_) => todo!(),
+                // Literals can't have outgoing arcs, so they have no value nodes
+                let Some(subject) = SRDFGraph::object_as_subject(focus_node) else {
+                    return HashSet::new();
                 };
                 if let Ok(objects) =
                     data_graph.objects_for_subject_predicate(&subject, pred.as_named_node())
                 {
                     objects
-                        .into_iter()
-                        .map(|object| match object {
-                            OxTerm::NamedNode(node) => {
-                                RDFNode::iri(IriS::new_unchecked(node.as_str()))
-                            }
-                            OxTerm::BlankNode(node) => RDFNode::bnode(node.to_string()),
-                            OxTerm::Literal(literal) => RDFNode::literal(literal.into()),
-                            #[cfg(feature = "rdf-star")]
-                            OxTerm::Triple(_) => unimplemented!(),
-                            _ => unimplemented!(),
-                        })
+                        .iter()
+                        .map(SRDFGraph::term_as_object)
                         .collect::<HashSet<RDFNode>>()
                 } else {
                     HashSet::new()
@@ -199,6 +209,14 @@ impl PropertyShape {
             )
         })?;
 
+        self.message.to_term_iter().try_for_each(|term| {
+            rdf.add_triple(
+                &RDF::object_as_subject(&self.id).unwrap(),
+                &RDF::iri_s2iri(&iri!(SH_MESSAGE_STR)),
+                &RDF::term_s2term(&term),
+            )
+        })?;
+
         if let Some(order) = &self.order {
             let decimal_type = NamedNode::new(XSD_DECIMAL_STR).unwrap();
 
@@ -218,7 +236,7 @@ impl PropertyShape {
             rdf.add_triple(
                 &RDF::object_as_subject(&self.id).unwrap(),
                 &RDF::iri_s2iri(&iri!(SH_GROUP_STR)),
-                &RDF::object_as_term(group),
+                &RDF::object_as_term(group)?,
             )?;
         }
 
@@ -1,17 +1,22 @@
 use std::{collections::HashMap, fmt::Display};
 
+use crate::component::Component;
+use crate::property_group::PropertyGroup;
 use crate::shape::Shape;
+use crate::target::Target;
 use iri_s::IriS;
 use prefixmap::PrefixMap;
-use srdf::RDFNode;
+use srdf::{RDFNode, SHACLPath};
 
 #[derive(Debug, Clone, Default)]
 pub struct Schema {
     // imports: Vec<IriS>,
     // entailments: Vec<IriS>,
     shapes: HashMap<RDFNode, Shape>,
+    property_groups: HashMap<RDFNode, PropertyGroup>,
     prefixmap: PrefixMap,
     base: Option<IriS>,
+    shapes_graph: Option<IriS>,
 }
 
 impl Schema {
@@ -29,6 +34,21 @@ impl Schema {
         self
     }
 
+    pub fn with_property_groups(
+        mut self,
+        property_groups: HashMap<RDFNode, PropertyGroup>,
+    ) -> Self {
+        self.property_groups = property_groups;
+        self
+    }
+
+    /// Sets the IRI of the graph that contains the shapes, which is pre-bound
+    /// to `$shapesGraph` in SPARQL-based constraints
+    pub fn with_shapes_graph(mut self, shapes_graph: Option<IriS>) -> Self {
+        self.shapes_graph = shapes_graph;
+        self
+    }
+
     pub fn prefix_map(&self) -> PrefixMap {
         self.prefixmap.clone()
     }
@@ -37,13 +57,157 @@ impl Schema {
         self.base.clone()
     }
 
+    pub fn shapes_graph(&self) -> Option<IriS> {
+        self.shapes_graph.clone()
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (&RDFNode, &Shape)> {
         self.shapes.iter()
     }
 
+    /// Checks if some shape has SPARQL-based constraints, which require the
+    /// data to be queryable
+    pub fn has_sparql_constraints(&self) -> bool {
+        self.shapes.values().any(|shape| {
+            shape.components().iter().any(|component| {
+                matches!(
+                    component,
+                    Component::Sparql(_) | Component::SparqlComponent { .. }
+                )
+            })
+        })
+    }
+
     pub fn get_shape(&self, sref: &RDFNode) -> Option<&Shape> {
         self.shapes.get(sref)
     }
+
+    /// Property groups referenced by the `sh:group` of the property shapes
+    pub fn property_groups(&self) -> impl Iterator<Item = (&RDFNode, &PropertyGroup)> {
+        self.property_groups.iter()
+    }
+
+    pub fn get_property_group(&self, group: &RDFNode) -> Option<&PropertyGroup> {
+        self.property_groups.get(group)
+    }
+
+    /// Number of arcs that validation may follow from a focus node to reach the nodes whose
+    /// outgoing arcs are checked, through property paths and shape references.
+    ///
+    /// It is `None` when there is no bound: shape references are recursive, paths are repeated
+    /// with `sh:zeroOrMorePath` or `sh:oneOrMorePath`, some referenced shape is not declared,
+    /// some constraint is SPARQL-based or `sh:class` follows `rdfs:subClassOf` chains
+    pub fn reference_depth(&self) -> Option<usize> {
+        let mut reach = 0;
+        for node in self.shapes.keys() {
+            reach = reach.max(self.shape_reach(node, &mut Vec::new())?);
+        }
+        // The outgoing arcs of the focus node are always checked
+        Some(reach.saturating_sub(1))
+    }
+
+    /// Checks if validation needs incoming arcs, because some path is inverse
+    /// or some shape targets the objects of a predicate
+    pub fn uses_incoming_arcs(&self) -> bool {
+        self.shapes.values().any(|shape| {
+            shape
+                .targets()
+                .iter()
+                .any(|target| matches!(target, Target::TargetObjectsOf(_)))
+                || matches!(shape, Shape::PropertyShape(ps) if path_has_inverse(ps.path()))
+        })
+    }
+
+    /// Number of nodes in a chain of arcs from a node whose outgoing arcs are checked by a shape,
+    /// where `visiting` contains the shapes whose references are being followed
+    fn shape_reach(&self, node: &RDFNode, visiting: &mut Vec<RDFNode>) -> Option<usize> {
+        if visiting.contains(node) {
+            return None;
+        }
+        let shape = self.get_shape(node)?;
+        visiting.push(node.clone());
+        let mut reach = 0;
+        for component in shape.components() {
+            reach = reach.max(self.component_reach(component, visiting)?);
+        }
+        let reach = match shape {
+            Shape::NodeShape(ns) => {
+                for property_shape in ns.property_shapes() {
+                    reach = reach.max(self.shape_reach(property_shape, visiting)?);
+                }
+                reach
+            }
+            Shape::PropertyShape(ps) => {
+                for property_shape in ps.property_shapes() {
+                    reach = reach.max(self.shape_reach(property_shape, visiting)?);
+                }
+                // The components are checked on the value nodes at the end of the path
+                path_reach(ps.path())? + reach
+            }
+        };
+        visiting.pop();
+        Some(reach)
+    }
+
+    fn component_reach(&self, component: &Component, visiting: &mut Vec<RDFNode>) -> Option<usize> {
+        match component {
+            // The types of the node and the chain of their superclasses, which has no bound
+            Component::Class(_) => None,
+            Component::Equals(_)
+            | Component::Disjoint(_)
+            | Component::LessThan(_)
+            | Component::LessThanOrEquals(_)
+            | Component::Closed { .. } => Some(1),
+            Component::Node { shape }
+            | Component::Not { shape }
+            | Component::QualifiedValueShape { shape, .. } => self.shape_reach(shape, visiting),
+            Component::And { shapes } | Component::Or { shapes } | Component::Xone { shapes } => {
+                let mut reach = 0;
+                for shape in shapes {
+                    reach = reach.max(self.shape_reach(shape, visiting)?);
+                }
+                Some(reach)
+            }
+            Component::Sparql(_) | Component::SparqlComponent { .. } => None,
+            _ => Some(0),
+        }
+    }
+}
+
+/// Number of arcs between a node and the values of `path`
+fn path_reach(path: &SHACLPath) -> Option<usize> {
+    match path {
+        SHACLPath::Predicate { .. } => Some(1),
+        SHACLPath::Alternative { paths } => {
+            let mut reach = 0;
+            for path in paths {
+                reach = reach.max(path_reach(path)?);
+            }
+            Some(reach)
+        }
+        SHACLPath::Sequence { paths } => {
+            let mut reach = 0;
+            for path in paths {
+                reach += path_reach(path)?;
+            }
+            Some(reach)
+        }
+        SHACLPath::Inverse { path } | SHACLPath::ZeroOrOne { path } => path_reach(path),
+        SHACLPath::ZeroOrMore { .. } | SHACLPath::OneOrMore { .. } => None,
+    }
+}
+
+fn path_has_inverse(path: &SHACLPath) -> bool {
+    match path {
+        SHACLPath::Predicate { .. } => false,
+        SHACLPath::Alternative { paths } | SHACLPath::Sequence { paths } => {
+            paths.iter().any(path_has_inverse)
+        }
+        SHACLPath::Inverse { .. } => true,
+        SHACLPath::ZeroOrMore { path }
+        | SHACLPath::OneOrMore { path }
+        | SHACLPath::ZeroOrOne { path } => path_has_inverse(path),
+    }
 }
 
 /*impl Display for Schema {
@@ -1,7 +1,9 @@
 use srdf::SRDFBuilder;
 use std::fmt::Display;
 
-use crate::{node_shape::NodeShape, property_shape::PropertyShape};
+use crate::{
+    component::Component, node_shape::NodeShape, property_shape::PropertyShape, target::Target,
+};
 
 #[derive(Debug, Clone)]
 pub enum Shape {
@@ -10,6 +12,27 @@ pub enum Shape {
 }
 
 impl Shape {
+    pub fn components(&self) -> &Vec<Component> {
+        match self {
+            Shape::NodeShape(ns) => ns.components(),
+            Shape::PropertyShape(ps) => ps.components(),
+        }
+    }
+
+    pub fn targets(&self) -> &Vec<Target> {
+        match self {
+            Shape::NodeShape(ns) => ns.targets(),
+            Shape::PropertyShape(ps) => ps.targets(),
+        }
+    }
+
+    pub fn add_components(&mut self, components: Vec<Component>) {
+        match self {
+            Shape::NodeShape(ns) => ns.add_components(components),
+            Shape::PropertyShape(ps) => ps.add_components(components),
+        }
+    }
+
     pub fn write<RDF>(&self, rdf: &mut RDF) -> Result<(), RDF::Err>
     where
         RDF: SRDFBuilder,
@@ -0,0 +1,234 @@
+use iri_s::IriS;
+use itertools::Itertools;
+use std::fmt::Display;
+
+/// A SPARQL-based constraint declared with `sh:sparql`.
+///
+/// https://www.w3.org/TR/shacl/#sparql-constraints
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SparqlConstraint {
+    select: String,
+    prefixes: Vec<(String, IriS)>,
+    message: Option<String>,
+    deactivated: bool,
+}
+
+impl SparqlConstraint {
+    pub fn new(select: String) -> Self {
+        SparqlConstraint {
+            select,
+            prefixes: Vec::new(),
+            message: None,
+            deactivated: false,
+        }
+    }
+
+    pub fn with_prefixes(mut self, prefixes: Vec<(String, IriS)>) -> Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    pub fn with_message(mut self, message: Option<String>) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn with_deactivated(mut self, deactivated: bool) -> Self {
+        self.deactivated = deactivated;
+        self
+    }
+
+    pub fn select(&self) -> &String {
+        &self.select
+    }
+
+    pub fn prefixes(&self) -> &Vec<(String, IriS)> {
+        &self.prefixes
+    }
+
+    pub fn message(&self) -> &Option<String> {
+        &self.message
+    }
+
+    pub fn is_deactivated(&self) -> bool {
+        self.deactivated
+    }
+
+    /// The SELECT query preceded by the `PREFIX` declarations obtained from
+    /// `sh:prefixes`
+    pub fn query(&self) -> String {
+        format!("{}{}", prefix_declarations(&self.prefixes), self.select)
+    }
+}
+
+impl Display for SparqlConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "sparql({})", self.select.trim())
+    }
+}
+
+/// Query of a SPARQL-based validator, which can be either a SELECT or an ASK
+/// query.
+///
+/// https://www.w3.org/TR/shacl/#SPARQLSelectValidator
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum SparqlQuery {
+    Select(String),
+    Ask(String),
+}
+
+/// Validator of a SPARQL-based constraint component, i.e. the value of
+/// `sh:validator`, `sh:nodeValidator` or `sh:propertyValidator`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SparqlValidator {
+    query: SparqlQuery,
+    prefixes: Vec<(String, IriS)>,
+    message: Option<String>,
+}
+
+impl SparqlValidator {
+    pub fn new(query: SparqlQuery) -> Self {
+        SparqlValidator {
+            query,
+            prefixes: Vec::new(),
+            message: None,
+        }
+    }
+
+    pub fn with_prefixes(mut self, prefixes: Vec<(String, IriS)>) -> Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    pub fn with_message(mut self, message: Option<String>) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn sparql_query(&self) -> &SparqlQuery {
+        &self.query
+    }
+
+    pub fn prefixes(&self) -> &Vec<(String, IriS)> {
+        &self.prefixes
+    }
+
+    pub fn message(&self) -> &Option<String> {
+        &self.message
+    }
+
+    /// The query preceded by the `PREFIX` declarations obtained from
+    /// `sh:prefixes`
+    pub fn query(&self) -> String {
+        let query = match &self.query {
+            SparqlQuery::Select(query) => query,
+            SparqlQuery::Ask(query) => query,
+        };
+        format!("{}{}", prefix_declarations(&self.prefixes), query)
+    }
+}
+
+/// Parameter of a constraint component declared with `sh:parameter`.
+///
+/// https://www.w3.org/TR/shacl/#constraint-components-parameters
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Parameter {
+    path: IriS,
+    optional: bool,
+}
+
+impl Parameter {
+    pub fn new(path: IriS, optional: bool) -> Self {
+        Parameter { path, optional }
+    }
+
+    pub fn path(&self) -> &IriS {
+        &self.path
+    }
+
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    /// Name of the SPARQL variable pre-bound to the value of the parameter,
+    /// i.e. the local name of its path
+    pub fn var_name(&self) -> String {
+        let str = self.path.as_str();
+        let start = str.rfind(['#', '/', ':']).map(|n| n + 1).unwrap_or(0);
+        str[start..].to_string()
+    }
+}
+
+/// A custom constraint component declared as an instance of
+/// `sh:ConstraintComponent` with SPARQL-based validators.
+///
+/// https://www.w3.org/TR/shacl/#sparql-constraint-components
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SparqlConstraintComponent {
+    id: IriS,
+    parameters: Vec<Parameter>,
+    validator: Option<SparqlValidator>,
+    node_validator: Option<SparqlValidator>,
+    property_validator: Option<SparqlValidator>,
+}
+
+impl SparqlConstraintComponent {
+    pub fn new(id: IriS, parameters: Vec<Parameter>) -> Self {
+        SparqlConstraintComponent {
+            id,
+            parameters,
+            validator: None,
+            node_validator: None,
+            property_validator: None,
+        }
+    }
+
+    pub fn with_validator(mut self, validator: Option<SparqlValidator>) -> Self {
+        self.validator = validator;
+        self
+    }
+
+    pub fn with_node_validator(mut self, validator: Option<SparqlValidator>) -> Self {
+        self.node_validator = validator;
+        self
+    }
+
+    pub fn with_property_validator(mut self, validator: Option<SparqlValidator>) -> Self {
+        self.property_validator = validator;
+        self
+    }
+
+    pub fn id(&self) -> &IriS {
+        &self.id
+    }
+
+    pub fn parameters(&self) -> &Vec<Parameter> {
+        &self.parameters
+    }
+
+    pub fn validator(&self) -> &Option<SparqlValidator> {
+        &self.validator
+    }
+
+    pub fn node_validator(&self) -> &Option<SparqlValidator> {
+        &self.node_validator
+    }
+
+    pub fn property_validator(&self) -> &Option<SparqlValidator> {
+        &self.property_validator
+    }
+}
+
+impl Display for SparqlConstraintComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params = self.parameters.iter().map(|p| p.path()).join(" ");
+        write!(f, "{}({params})", self.id)
+    }
+}
+
+fn prefix_declarations(prefixes: &[(String, IriS)]) -> String {
+    prefixes
+        .iter()
+        .map(|(prefix, namespace)| format!("PREFIX {prefix}: <{}>\n", namespace.as_str()))
+        .collect()
+}
@@ -22,12 +22,12 @@ impl Target {
             Self::TargetNode(target_rdf_node) => rdf.add_triple(
                 &RDF::object_as_subject(rdf_node).unwrap(),
                 &RDF::iri_s2iri(&iri!(SH_TARGET_NODE_STR)),
-                &RDF::object_as_term(target_rdf_node),
+                &RDF::object_as_term(target_rdf_node)?,
             ),
             Self::TargetClass(target_rdf_node) => rdf.add_triple(
                 &RDF::object_as_subject(rdf_node).unwrap(),
                 &RDF::iri_s2iri(&iri!(SH_TARGET_CLASS_STR)),
-                &RDF::object_as_term(target_rdf_node),
+                &RDF::object_as_term(target_rdf_node)?,
             ),
             Self::TargetSubjectsOf(iri_ref) => rdf.add_triple(
                 &RDF::object_as_subject(rdf_node).unwrap(),
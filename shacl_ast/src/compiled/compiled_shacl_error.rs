@@ -8,4 +8,6 @@ pub enum CompiledShaclError {
     ShapeNotFound,
     #[error("Could not convert to Literal")]
     LiteralConversion,
+    #[error("Could not convert {object} to a term: {error}")]
+    ObjectConversion { object: String, error: String },
 }
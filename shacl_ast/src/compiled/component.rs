@@ -7,11 +7,14 @@ use super::compile_shapes;
 use super::compiled_shacl_error::CompiledShaclError;
 use super::convert_iri_ref;
 use super::convert_lang;
+use super::convert_object;
 use super::convert_value;
 use super::shape::CompiledShape;
 use iri_s::iri;
 use iri_s::IriS;
 use node_kind::NodeKind;
+use sparql_constraint::SparqlConstraintComponent;
+use sparql_constraint::SparqlValidator;
 use srdf::RDFNode;
 use srdf::SRDFBasic;
 
@@ -44,13 +47,15 @@ pub enum CompiledComponent<S: SRDFBasic> {
     HasValue(HasValue<S>),
     In(In<S>),
     QualifiedValueShape(QualifiedValueShape<S>),
+    Sparql(Sparql),
+    SparqlComponent(SparqlComponent),
 }
 
 impl<S: SRDFBasic> CompiledComponent<S> {
     pub fn compile(component: Component, schema: &Schema) -> Result<Self, CompiledShaclError> {
         let component = match component {
             Component::Class(object) => {
-                let class_rule = S::object_as_term(&object);
+                let class_rule = convert_object::<S>(&object)?;
                 CompiledComponent::Class(Class::new(class_rule))
             }
             Component::Datatype(iri_ref) => {
@@ -61,19 +66,19 @@ impl<S: SRDFBasic> CompiledComponent<S> {
             Component::MinCount(count) => CompiledComponent::MinCount(MinCount::new(count)),
             Component::MaxCount(count) => CompiledComponent::MaxCount(MaxCount::new(count)),
             Component::MinExclusive(literal) => {
-                let term = S::object_as_term(&RDFNode::literal(literal));
+                let term = convert_object::<S>(&RDFNode::literal(literal))?;
                 CompiledComponent::MinExclusive(MinExclusive::new(term))
             }
             Component::MaxExclusive(literal) => {
-                let term = S::object_as_term(&RDFNode::literal(literal));
+                let term = convert_object::<S>(&RDFNode::literal(literal))?;
                 CompiledComponent::MaxExclusive(MaxExclusive::new(term))
             }
             Component::MinInclusive(literal) => {
-                let term = S::object_as_term(&RDFNode::literal(literal));
+                let term = convert_object::<S>(&RDFNode::literal(literal))?;
                 CompiledComponent::MinInclusive(MinInclusive::new(term))
             }
             Component::MaxInclusive(literal) => {
-                let term = S::object_as_term(&RDFNode::literal(literal));
+                let term = convert_object::<S>(&RDFNode::literal(literal))?;
                 CompiledComponent::MaxInclusive(MaxInclusive::new(term))
             }
             Component::MinLength(length) => CompiledComponent::MinLength(MinLength::new(length)),
@@ -157,6 +162,32 @@ impl<S: SRDFBasic> CompiledComponent<S> {
                     qualified_value_shapes_disjoint,
                 ))
             }
+            Component::Sparql(sparql) => CompiledComponent::Sparql(Sparql::new(
+                sparql.query(),
+                sparql.message().clone(),
+                sparql.is_deactivated(),
+                schema.shapes_graph(),
+            )),
+            Component::SparqlComponent {
+                component,
+                parameter_values,
+            } => {
+                let bindings = parameter_values
+                    .into_iter()
+                    .filter_map(|(path, value)| {
+                        component
+                            .parameters()
+                            .iter()
+                            .find(|param| param.path() == &path)
+                            .map(|param| (param.var_name(), value))
+                    })
+                    .collect();
+                CompiledComponent::SparqlComponent(SparqlComponent::new(
+                    *component,
+                    bindings,
+                    schema.shapes_graph(),
+                ))
+            }
         };
 
         Ok(component)
@@ -741,6 +772,100 @@ impl<S: SRDFBasic> MinInclusive<S> {
     }
 }
 
+/// sh:sparql declares a SPARQL-based constraint: each solution of its SELECT
+/// query is a validation result.
+///
+/// https://www.w3.org/TR/shacl/#SPARQLConstraintComponent
+#[derive(Debug)]
+pub struct Sparql {
+    query: String,
+    message: Option<String>,
+    deactivated: bool,
+    shapes_graph: Option<IriS>,
+}
+
+impl Sparql {
+    pub fn new(
+        query: String,
+        message: Option<String>,
+        deactivated: bool,
+        shapes_graph: Option<IriS>,
+    ) -> Self {
+        Sparql {
+            query,
+            message,
+            deactivated,
+            shapes_graph,
+        }
+    }
+
+    pub fn query(&self) -> &String {
+        &self.query
+    }
+
+    pub fn message(&self) -> &Option<String> {
+        &self.message
+    }
+
+    pub fn is_deactivated(&self) -> bool {
+        self.deactivated
+    }
+
+    pub fn shapes_graph(&self) -> &Option<IriS> {
+        &self.shapes_graph
+    }
+}
+
+/// A custom constraint component whose validators are SPARQL queries. The
+/// values of its parameters in the shape are pre-bound to the variables named
+/// after the local names of the parameter paths.
+///
+/// https://www.w3.org/TR/shacl/#sparql-constraint-components
+#[derive(Debug)]
+pub struct SparqlComponent {
+    component: SparqlConstraintComponent,
+    bindings: Vec<(String, RDFNode)>,
+    shapes_graph: Option<IriS>,
+}
+
+impl SparqlComponent {
+    pub fn new(
+        component: SparqlConstraintComponent,
+        bindings: Vec<(String, RDFNode)>,
+        shapes_graph: Option<IriS>,
+    ) -> Self {
+        SparqlComponent {
+            component,
+            bindings,
+            shapes_graph,
+        }
+    }
+
+    pub fn iri(&self) -> &IriS {
+        self.component.id()
+    }
+
+    pub fn bindings(&self) -> &Vec<(String, RDFNode)> {
+        &self.bindings
+    }
+
+    pub fn shapes_graph(&self) -> &Option<IriS> {
+        &self.shapes_graph
+    }
+
+    /// The validator that applies to a node shape or to a property shape:
+    /// `sh:nodeValidator` or `sh:propertyValidator` if declared, falling back
+    /// to `sh:validator`
+    pub fn validator(&self, is_property_shape: bool) -> Option<&SparqlValidator> {
+        let specific = if is_property_shape {
+            self.component.property_validator()
+        } else {
+            self.component.node_validator()
+        };
+        specific.as_ref().or(self.component.validator().as_ref())
+    }
+}
+
 impl<S: SRDFBasic> From<&CompiledComponent<S>> for IriS {
     fn from(value: &CompiledComponent<S>) -> Self {
         match value {
@@ -775,6 +900,8 @@ impl<S: SRDFBasic> From<&CompiledComponent<S>> for IriS {
             CompiledComponent::QualifiedValueShape { .. } => {
                 iri!(SH_QUALIFIED_VALUE_SHAPE_STR)
             }
+            CompiledComponent::Sparql(_) => iri!(SH_SPARQL_CONSTRAINT_COMPONENT_STR),
+            CompiledComponent::SparqlComponent(component) => component.iri().clone(),
         }
     }
 }
@@ -26,9 +26,16 @@ fn convert_iri_ref<S: SRDFBasic>(iri_ref: IriRef) -> Result<S::IRI, CompiledShac
     Ok(S::iri_s2iri(&iri_s))
 }
 
+fn convert_object<S: SRDFBasic>(object: &Object) -> Result<S::Term, CompiledShaclError> {
+    S::object_as_term(object).map_err(|e| CompiledShaclError::ObjectConversion {
+        object: object.to_string(),
+        error: e.to_string(),
+    })
+}
+
 fn convert_lang<S: SRDFBasic>(lang: Lang) -> Result<S::Literal, CompiledShaclError> {
     let object = RDFNode::literal(Literal::str(&lang.value()));
-    let term = S::object_as_term(&object);
+    let term = convert_object::<S>(&object)?;
     S::term_as_literal(&term).ok_or(CompiledShaclError::LiteralConversion)
 }
 
@@ -59,7 +66,7 @@ fn convert_value<S: SRDFBasic>(value: Value) -> Result<S::Term, CompiledShaclErr
             let iri_ref = convert_iri_ref::<S>(iri_ref)?;
             S::iri_as_term(iri_ref)
         }
-        Value::Literal(literal) => S::object_as_term(&RDFNode::literal(literal)),
+        Value::Literal(literal) => convert_object::<S>(&RDFNode::literal(literal))?,
     };
     Ok(ans)
 }
@@ -2,12 +2,14 @@ use std::collections::HashSet;
 
 use srdf::SRDFBasic;
 
+use crate::message_map::MessageMap;
 use crate::node_shape::NodeShape;
 use crate::Schema;
 
 use super::compile_shape;
 use super::compiled_shacl_error::CompiledShaclError;
 use super::component::CompiledComponent;
+use super::convert_object;
 use super::severity::CompiledSeverity;
 use super::shape::CompiledShape;
 use super::target::CompiledTarget;
@@ -21,7 +23,7 @@ pub struct CompiledNodeShape<S: SRDFBasic> {
     closed: bool,
     // ignored_properties: Vec<S::IRI>,
     deactivated: bool,
-    // message: MessageMap,
+    message: MessageMap,
     severity: Option<CompiledSeverity<S>>,
     // name: MessageMap,
     // description: MessageMap,
@@ -46,10 +48,16 @@ impl<S: SRDFBasic> CompiledNodeShape<S> {
             property_shapes,
             closed,
             deactivated,
+            message: MessageMap::new(),
             severity,
         }
     }
 
+    pub fn with_message(mut self, message: MessageMap) -> Self {
+        self.message = message;
+        self
+    }
+
     pub fn id(&self) -> &S::Term {
         &self.id
     }
@@ -58,6 +66,10 @@ impl<S: SRDFBasic> CompiledNodeShape<S> {
         &self.deactivated
     }
 
+    pub fn message(&self) -> &MessageMap {
+        &self.message
+    }
+
     pub fn severity(&self) -> &CompiledSeverity<S> {
         match &self.severity {
             Some(severity) => severity,
@@ -84,7 +96,7 @@ impl<S: SRDFBasic> CompiledNodeShape<S> {
 
 impl<S: SRDFBasic> CompiledNodeShape<S> {
     pub fn compile(shape: Box<NodeShape>, schema: &Schema) -> Result<Self, CompiledShaclError> {
-        let id = S::object_as_term(shape.id());
+        let id = convert_object::<S>(shape.id())?;
         let closed = shape.is_closed().to_owned();
         let deactivated = shape.is_deactivated().to_owned();
         let severity = CompiledSeverity::compile(shape.severity())?;
@@ -116,7 +128,8 @@ impl<S: SRDFBasic> CompiledNodeShape<S> {
             closed,
             deactivated,
             severity,
-        );
+        )
+        .with_message(shape.message().clone());
 
         Ok(compiled_node_shape)
     }
@@ -3,12 +3,14 @@ use std::collections::HashSet;
 use srdf::SHACLPath;
 use srdf::SRDFBasic;
 
+use crate::message_map::MessageMap;
 use crate::property_shape::PropertyShape;
 use crate::Schema;
 
 use super::compile_shape;
 use super::compiled_shacl_error::CompiledShaclError;
 use super::component::CompiledComponent;
+use super::convert_object;
 use super::severity::CompiledSeverity;
 use super::shape::CompiledShape;
 use super::target::CompiledTarget;
@@ -23,7 +25,7 @@ pub struct CompiledPropertyShape<S: SRDFBasic> {
     closed: bool,
     // ignored_properties: Vec<S::IRI>,
     deactivated: bool,
-    // message: MessageMap,
+    message: MessageMap,
     severity: Option<CompiledSeverity<S>>,
     // name: MessageMap,
     // description: MessageMap,
@@ -53,10 +55,16 @@ impl<S: SRDFBasic> CompiledPropertyShape<S> {
             property_shapes,
             closed,
             deactivated,
+            message: MessageMap::new(),
             severity,
         }
     }
 
+    pub fn with_message(mut self, message: MessageMap) -> Self {
+        self.message = message;
+        self
+    }
+
     pub fn id(&self) -> &S::Term {
         &self.id
     }
@@ -73,6 +81,10 @@ impl<S: SRDFBasic> CompiledPropertyShape<S> {
         &self.deactivated
     }
 
+    pub fn message(&self) -> &MessageMap {
+        &self.message
+    }
+
     pub fn severity(&self) -> &CompiledSeverity<S> {
         match &self.severity {
             Some(severity) => severity,
@@ -95,7 +107,7 @@ impl<S: SRDFBasic> CompiledPropertyShape<S> {
 
 impl<S: SRDFBasic> CompiledPropertyShape<S> {
     pub fn compile(shape: PropertyShape, schema: &Schema) -> Result<Self, CompiledShaclError> {
-        let id = S::object_as_term(shape.id());
+        let id = convert_object::<S>(shape.id())?;
         let path = shape.path().to_owned();
         let closed = shape.is_closed().to_owned();
         let deactivated = shape.is_deactivated().to_owned();
@@ -129,7 +141,8 @@ impl<S: SRDFBasic> CompiledPropertyShape<S> {
             closed,
             deactivated,
             severity,
-        );
+        )
+        .with_message(shape.message().clone());
 
         Ok(compiled_property_shape)
     }
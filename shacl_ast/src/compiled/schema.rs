@@ -6,6 +6,7 @@ use srdf::SRDFBasic;
 use crate::Schema;
 
 use super::compiled_shacl_error::CompiledShaclError;
+use super::convert_object;
 use super::shape::CompiledShape;
 
 #[derive(Debug)]
@@ -54,7 +55,7 @@ impl<S: SRDFBasic> TryFrom<Schema> for CompiledSchema<S> {
         let mut shapes = HashMap::default();
 
         for (rdf_node, shape) in schema.iter() {
-            let term = S::object_as_term(rdf_node);
+            let term = convert_object::<S>(rdf_node)?;
             let shape = CompiledShape::compile(shape.to_owned(), &schema)?;
             shapes.insert(term, shape);
         }
@@ -1,5 +1,6 @@
 use srdf::SRDFBasic;
 
+use crate::message_map::MessageMap;
 use crate::shape::Shape;
 use crate::Schema;
 
@@ -58,6 +59,13 @@ impl<S: SRDFBasic> CompiledShape<S> {
         }
     }
 
+    pub fn message(&self) -> &MessageMap {
+        match self {
+            CompiledShape::NodeShape(ns) => ns.message(),
+            CompiledShape::PropertyShape(ps) => ps.message(),
+        }
+    }
+
     pub fn severity(&self) -> S::Term {
         let iri_s = match self {
             CompiledShape::NodeShape(ns) => ns.severity().into(),
@@ -4,6 +4,7 @@ use crate::target::Target;
 
 use super::compiled_shacl_error::CompiledShaclError;
 use super::convert_iri_ref;
+use super::convert_object;
 
 #[derive(Debug)]
 pub enum CompiledTarget<S: SRDFBasic> {
@@ -16,8 +17,10 @@ pub enum CompiledTarget<S: SRDFBasic> {
 impl<S: SRDFBasic> CompiledTarget<S> {
     pub fn compile(target: Target) -> Result<Self, CompiledShaclError> {
         let ans = match target {
-            Target::TargetNode(object) => CompiledTarget::TargetNode(S::object_as_term(&object)),
-            Target::TargetClass(object) => CompiledTarget::TargetClass(S::object_as_term(&object)),
+            Target::TargetNode(object) => CompiledTarget::TargetNode(convert_object::<S>(&object)?),
+            Target::TargetClass(object) => {
+                CompiledTarget::TargetClass(convert_object::<S>(&object)?)
+            }
             Target::TargetSubjectsOf(iri_ref) => {
                 CompiledTarget::TargetSubjectsOf(convert_iri_ref::<S>(iri_ref)?)
             }
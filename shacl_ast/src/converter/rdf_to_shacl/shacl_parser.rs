@@ -1,17 +1,33 @@
 use prefixmap::{IriRef, PrefixMap};
 use srdf::{
-    combine_parsers, combine_vec, has_type, not, ok, optional, parse_nodes, property_bool,
-    property_value, property_values, property_values_int, property_values_iri,
-    property_values_non_empty, rdf_list, term, FocusRDF, Object, PResult, RDFNode, RDFNodeParse,
-    RDFParseError, RDFParser, SHACLPath, SRDFBasic, Triple, RDF_TYPE,
+    combine_parsers, combine_vec, has_type, literal::Literal, not, numeric_literal::NumericLiteral,
+    ok, optional, parse_nodes, property_bool, property_value, property_values, property_values_int,
+    property_values_iri, property_values_non_empty, rdf_list, term, FocusRDF, Object, PResult,
+    RDFNode, RDFNodeParse, RDFParseError, RDFParser, SHACLPath, SRDFBasic, Triple, RDFS_LABEL,
+    RDF_TYPE,
 };
 use std::collections::{HashMap, HashSet};
 
 use crate::{
-    component::Component, node_kind::NodeKind, node_shape::NodeShape,
-    property_shape::PropertyShape, schema::Schema, shape::Shape, target::Target, value::Value, *,
+    component::Component,
+    message_map::MessageMap,
+    node_kind::NodeKind,
+    node_shape::NodeShape,
+    property_group::PropertyGroup,
+    property_shape::PropertyShape,
+    schema::Schema,
+    severity::Severity,
+    shape::Shape,
+    sparql_constraint::{
+        Parameter, SparqlConstraint, SparqlConstraintComponent, SparqlQuery, SparqlValidator,
+    },
+    target::Target,
+    value::Value,
+    *,
 };
+use iri_s::IriS;
 use std::fmt::Debug;
+use std::str::FromStr;
 
 use super::shacl_parser_error::ShaclParserError;
 
@@ -56,7 +72,12 @@ where
         let mut state = State::from(self.shapes_candidates()?);
         while let Some(node) = state.pop_pending() {
             if let std::collections::hash_map::Entry::Vacant(e) = self.shapes.entry(node.clone()) {
-                let term = RDF::object_as_term(&node);
+                let term = RDF::object_as_term(&node).map_err(|e| {
+                    ShaclParserError::ShapeNodeConversion {
+                        node: node.to_string(),
+                        error: e.to_string(),
+                    }
+                })?;
                 self.rdf_parser.rdf.set_focus(&term);
                 let shape = Self::shape(&mut state)
                     .parse_impl(&mut self.rdf_parser.rdf)
@@ -65,9 +86,250 @@ where
             }
         }
 
+        self.add_sparql_components()?;
+        let property_groups = self.property_groups()?;
+
         Ok(Schema::new()
             .with_prefixmap(prefixmap)
-            .with_shapes(self.shapes.clone()))
+            .with_shapes(self.shapes.clone())
+            .with_property_groups(property_groups))
+    }
+
+    /// Parses the `sh:order` and `rdfs:label` of the groups referenced by the property shapes
+    fn property_groups(&mut self) -> Result<HashMap<RDFNode, PropertyGroup>> {
+        let groups: HashSet<RDFNode> = self
+            .shapes
+            .values()
+            .filter_map(|shape| match shape {
+                Shape::PropertyShape(ps) => ps.group().cloned(),
+                Shape::NodeShape(_) => None,
+            })
+            .collect();
+        let mut property_groups = HashMap::new();
+        for group in groups {
+            let term =
+                RDF::object_as_term(&group).map_err(|e| ShaclParserError::ShapeNodeConversion {
+                    node: group.to_string(),
+                    error: e.to_string(),
+                })?;
+            self.rdf_parser.rdf.set_focus(&term);
+            let property_group = order()
+                .and(message_map(&RDFS_LABEL))
+                .map(|(order, label)| {
+                    PropertyGroup::new(group.clone())
+                        .with_order(order)
+                        .with_label(label)
+                })
+                .parse_impl(&mut self.rdf_parser.rdf)
+                .map_err(|e| ShaclParserError::RDFParseError { err: e })?;
+            property_groups.insert(group, property_group);
+        }
+        Ok(property_groups)
+    }
+
+    /// Adds to each shape its SPARQL-based constraints (`sh:sparql`) and the
+    /// SPARQL-based constraint components whose mandatory parameters it declares
+    fn add_sparql_components(&mut self) -> Result<()> {
+        let constraint_components = self.sparql_constraint_components()?;
+        let mut components = Vec::new();
+        for node in self.shapes.keys() {
+            let Some(subject) = RDF::object_as_subject(node) else {
+                continue;
+            };
+            let mut cs = Vec::new();
+            for term in self.objects(&subject, &SH_SPARQL)? {
+                cs.push(Component::Sparql(Box::new(self.sparql_constraint(&term)?)));
+            }
+            for component in constraint_components.iter() {
+                for parameter_values in self.parameter_values(&subject, component)? {
+                    cs.push(Component::SparqlComponent {
+                        component: Box::new(component.clone()),
+                        parameter_values,
+                    });
+                }
+            }
+            if !cs.is_empty() {
+                components.push((node.clone(), cs));
+            }
+        }
+        for (node, cs) in components {
+            if let Some(shape) = self.shapes.get_mut(&node) {
+                shape.add_components(cs);
+            }
+        }
+        Ok(())
+    }
+
+    fn sparql_constraint(&self, term: &RDF::Term) -> Result<SparqlConstraint> {
+        let subject = Self::term_to_subject(term)?;
+        let select = self.string_value(&subject, &SH_SELECT)?.ok_or_else(|| {
+            ShaclParserError::SparqlConstraintWithoutSelect {
+                term: format!("{term}"),
+            }
+        })?;
+        let deactivated = self
+            .objects(&subject, &SH_DEACTIVATED)?
+            .iter()
+            .any(|t| RDF::term_as_boolean(t) == Some(true));
+        Ok(SparqlConstraint::new(select)
+            .with_prefixes(self.prefixes(&subject)?)
+            .with_message(self.string_value(&subject, &SH_MESSAGE)?)
+            .with_deactivated(deactivated))
+    }
+
+    /// Instances of `sh:ConstraintComponent` that declare SPARQL-based validators
+    fn sparql_constraint_components(&self) -> Result<Vec<SparqlConstraintComponent>> {
+        let instances = self
+            .rdf_parser
+            .rdf
+            .subjects_with_predicate_object(
+                &Self::rdf_type(),
+                &RDF::iri_s2term(&SH_CONSTRAINT_COMPONENT),
+            )
+            .map_err(|e| ShaclParserError::Custom {
+                msg: format!("Error obtaining values with type sh:ConstraintComponent: {e}"),
+            })?;
+        let mut components = Vec::new();
+        for subject in instances {
+            let Some(iri) = RDF::subject_as_iri(&subject) else {
+                continue;
+            };
+            let mut parameters = Vec::new();
+            for param in self.objects(&subject, &SH_PARAMETER)? {
+                let param_subject = Self::term_to_subject(&param)?;
+                let path = self
+                    .objects(&param_subject, &SH_PATH)?
+                    .iter()
+                    .find_map(RDF::term_as_iri_s)
+                    .ok_or_else(|| ShaclParserError::ParameterWithoutPath {
+                        term: format!("{param}"),
+                    })?;
+                let optional = self
+                    .objects(&param_subject, &SH_OPTIONAL)?
+                    .iter()
+                    .any(|t| RDF::term_as_boolean(t) == Some(true));
+                parameters.push(Parameter::new(path, optional));
+            }
+            parameters.sort_by(|p1, p2| p1.path().as_str().cmp(p2.path().as_str()));
+            let component = SparqlConstraintComponent::new(RDF::iri2iri_s(&iri), parameters)
+                .with_validator(self.sparql_validator(&subject, &SH_VALIDATOR)?)
+                .with_node_validator(self.sparql_validator(&subject, &SH_NODE_VALIDATOR)?)
+                .with_property_validator(self.sparql_validator(&subject, &SH_PROPERTY_VALIDATOR)?);
+            components.push(component);
+        }
+        Ok(components)
+    }
+
+    fn sparql_validator(
+        &self,
+        subject: &RDF::Subject,
+        pred: &IriS,
+    ) -> Result<Option<SparqlValidator>> {
+        let Some(term) = self.objects(subject, pred)?.into_iter().next() else {
+            return Ok(None);
+        };
+        let validator_subject = Self::term_to_subject(&term)?;
+        let query = match self.string_value(&validator_subject, &SH_SELECT)? {
+            Some(select) => SparqlQuery::Select(select),
+            None => match self.string_value(&validator_subject, &SH_ASK)? {
+                Some(ask) => SparqlQuery::Ask(ask),
+                None => {
+                    return Err(ShaclParserError::SparqlValidatorWithoutQuery {
+                        term: format!("{term}"),
+                    })
+                }
+            },
+        };
+        Ok(Some(
+            SparqlValidator::new(query)
+                .with_prefixes(self.prefixes(&validator_subject)?)
+                .with_message(self.string_value(&validator_subject, &SH_MESSAGE)?),
+        ))
+    }
+
+    /// Each combination of values that a shape gives to the parameters of a
+    /// constraint component. It is empty if some mandatory parameter has no value
+    fn parameter_values(
+        &self,
+        subject: &RDF::Subject,
+        component: &SparqlConstraintComponent,
+    ) -> Result<Vec<Vec<(IriS, RDFNode)>>> {
+        let mut combinations: Vec<Vec<(IriS, RDFNode)>> = vec![Vec::new()];
+        for param in component.parameters() {
+            let values = self.objects(subject, param.path())?;
+            if values.is_empty() {
+                if param.is_optional() {
+                    continue;
+                }
+                return Ok(Vec::new());
+            }
+            combinations = combinations
+                .into_iter()
+                .flat_map(|combination| {
+                    values.iter().map(move |value| {
+                        let mut combination = combination.clone();
+                        combination.push((param.path().clone(), RDF::term_as_object(value)));
+                        combination
+                    })
+                })
+                .collect();
+        }
+        Ok(combinations)
+    }
+
+    /// Prefix declarations reachable through `sh:prefixes`/`sh:declare`
+    fn prefixes(&self, subject: &RDF::Subject) -> Result<Vec<(String, IriS)>> {
+        let mut prefixes = Vec::new();
+        for ontology in self.objects(subject, &SH_PREFIXES)? {
+            let ontology = Self::term_to_subject(&ontology)?;
+            for declaration in self.objects(&ontology, &SH_DECLARE)? {
+                let declaration_subject = Self::term_to_subject(&declaration)?;
+                let prefix = self.string_value(&declaration_subject, &SH_PREFIX)?;
+                let namespace = self
+                    .objects(&declaration_subject, &SH_NAMESPACE)?
+                    .iter()
+                    .find_map(|t| {
+                        RDF::term_as_iri_s(t).or_else(|| {
+                            RDF::term_as_string(t).and_then(|str| IriS::from_str(&str).ok())
+                        })
+                    });
+                match (prefix, namespace) {
+                    (Some(prefix), Some(namespace)) => prefixes.push((prefix, namespace)),
+                    _ => {
+                        return Err(ShaclParserError::PrefixDeclarationNamespace {
+                            term: format!("{declaration}"),
+                        })
+                    }
+                }
+            }
+        }
+        prefixes.sort_by(|(p1, _), (p2, _)| p1.cmp(p2));
+        prefixes.dedup();
+        Ok(prefixes)
+    }
+
+    fn objects(&self, subject: &RDF::Subject, pred: &IriS) -> Result<Vec<RDF::Term>> {
+        let objects = self
+            .rdf_parser
+            .rdf
+            .objects_for_subject_predicate(subject, &RDF::iri_s2iri(pred))
+            .map_err(|e| ShaclParserError::Custom {
+                msg: format!("Error obtaining values of {pred}: {e}"),
+            })?;
+        Ok(objects.into_iter().collect())
+    }
+
+    fn string_value(&self, subject: &RDF::Subject, pred: &IriS) -> Result<Option<String>> {
+        Ok(self
+            .objects(subject, pred)?
+            .iter()
+            .find_map(RDF::term_as_string))
+    }
+
+    fn term_to_subject(term: &RDF::Term) -> Result<RDF::Subject> {
+        RDF::term_as_subject(term).ok_or_else(|| ShaclParserError::Custom {
+            msg: format!("Expected term to act as a subject: {term}"),
+        })
     }
 
     fn shapes_candidates(&mut self) -> Result<Vec<RDFNode>> {
@@ -304,6 +566,14 @@ where
                 .then(move |(id, path)| ok(&PropertyShape::new(id, path))),
         )
         .then(|ps| targets().flat_map(move |ts| Ok(ps.clone().with_targets(ts))))
+        .then(|ps| severity().flat_map(move |sev| Ok(ps.clone().with_severity(sev))))
+        .then(|ps| message().flat_map(move |msg| Ok(ps.clone().with_message(msg))))
+        .then(|ps| name().flat_map(move |name| Ok(ps.clone().with_name(name))))
+        .then(|ps| {
+            description().flat_map(move |description| Ok(ps.clone().with_description(description)))
+        })
+        .then(|ps| order().flat_map(move |order| Ok(ps.clone().with_order(order))))
+        .then(|ps| group().flat_map(move |group| Ok(ps.clone().with_group(group))))
         .then(|ps| {
             optional(closed()).flat_map(move |c| {
                 if let Some(true) = c {
@@ -340,6 +610,13 @@ where
                 ok(&NodeShape::new(id))
             })
             .then(|ns| targets().flat_map(move |ts| Ok(ns.clone().with_targets(ts))))
+            .then(|ns| severity().flat_map(move |sev| Ok(ns.clone().with_severity(sev))))
+            .then(|ns| message().flat_map(move |msg| Ok(ns.clone().with_message(msg))))
+            .then(|ns| name().flat_map(move |name| Ok(ns.clone().with_name(name))))
+            .then(|ns| {
+                description()
+                    .flat_map(move |description| Ok(ns.clone().with_description(description)))
+            })
             .then(|ps| {
                 optional(closed()).flat_map(move |c| {
                     if let Some(true) = c {
@@ -447,11 +724,16 @@ where
     RDF: FocusRDF,
 {
     let obj = RDF::term_as_object(&term);
-    match obj {
-        Object::Iri(iri) => ok(&SHACLPath::iri(iri)),
-        Object::BlankNode(_) => todo!(),
-        Object::Literal(_) => todo!(),
-    }
+    ok(&obj).flat_map(|obj| match obj {
+        Object::Iri(iri) => Ok(SHACLPath::iri(iri)),
+        Object::BlankNode(bnode) => Err(RDFParseError::Custom {
+            msg: format!("SHACL paths that are not IRIs are not supported yet, found _:{bnode}"),
+        }),
+        Object::Literal(lit) => Err(RDFParseError::ExpectedIRIFoundLiteral { lit }),
+        Object::Triple { .. } => Err(RDFParseError::ExpectedIRIFoundTriple {
+            triple: obj.to_string(),
+        }),
+    })
 }
 
 fn targets<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Target>>
@@ -461,6 +743,87 @@ where
     combine_vec(targets_class(), targets_node())
 }
 
+/// Parses the value of `sh:severity`, which defaults to `sh:Violation` when absent
+fn severity<RDF>() -> impl RDFNodeParse<RDF, Output = Option<Severity>>
+where
+    RDF: FocusRDF,
+{
+    property_values_iri(&SH_SEVERITY).map(|iris| iris.first().map(iri_to_severity))
+}
+
+fn iri_to_severity(iri: &IriS) -> Severity {
+    match iri.as_str() {
+        SH_VIOLATION_STR => Severity::Violation,
+        SH_WARNING_STR => Severity::Warning,
+        SH_INFO_STR => Severity::Info,
+        _ => Severity::Generic(IriRef::iri(iri.clone())),
+    }
+}
+
+/// Parses the values of `sh:message`, which can be language-tagged
+fn message<RDF>() -> impl RDFNodeParse<RDF, Output = MessageMap>
+where
+    RDF: FocusRDF,
+{
+    message_map(&SH_MESSAGE)
+}
+
+/// Parses the values of `sh:name`, which can be language-tagged
+fn name<RDF>() -> impl RDFNodeParse<RDF, Output = MessageMap>
+where
+    RDF: FocusRDF,
+{
+    message_map(&SH_NAME)
+}
+
+/// Parses the values of `sh:description`, which can be language-tagged
+fn description<RDF>() -> impl RDFNodeParse<RDF, Output = MessageMap>
+where
+    RDF: FocusRDF,
+{
+    message_map(&SH_DESCRIPTION)
+}
+
+/// Parses the numeric value of `sh:order`
+fn order<RDF>() -> impl RDFNodeParse<RDF, Output = Option<NumericLiteral>>
+where
+    RDF: FocusRDF,
+{
+    property_values(&SH_ORDER).map(|terms| {
+        terms
+            .iter()
+            .find_map(|term| match RDF::term_as_object(term) {
+                Object::Literal(literal) => literal.numeric_value(),
+                _ => None,
+            })
+    })
+}
+
+/// Parses the value of `sh:group`
+fn group<RDF>() -> impl RDFNodeParse<RDF, Output = Option<RDFNode>>
+where
+    RDF: FocusRDF,
+{
+    property_values(&SH_GROUP).map(|terms| terms.iter().next().map(RDF::term_as_object))
+}
+
+fn message_map<RDF>(property: &IriS) -> impl RDFNodeParse<RDF, Output = MessageMap>
+where
+    RDF: FocusRDF,
+{
+    property_values(property).map(|terms| {
+        terms.iter().fold(MessageMap::new(), |map, term| {
+            match RDF::term_as_object(term) {
+                Object::Literal(Literal::StringLiteral { lexical_form, lang }) => {
+                    map.with_message(lang, lexical_form)
+                }
+                Object::Literal(literal) => map.with_message(None, literal.lexical_form()),
+                _ => map,
+            }
+        })
+    })
+}
+
 fn closed<RDF>() -> impl RDFNodeParse<RDF, Output = bool>
 where
     RDF: FocusRDF,
@@ -590,6 +953,9 @@ where
             bnode: bn.to_string(),
         }),
         Object::Literal(lit) => Ok(Value::Literal(lit)),
+        triple @ Object::Triple { .. } => Err(RDFParseError::UnexpectedTriple {
+            term: triple.to_string(),
+        }),
     }
 }
 
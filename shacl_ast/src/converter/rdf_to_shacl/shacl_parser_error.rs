@@ -28,12 +28,27 @@ pub enum ShaclParserError {
     #[error("Unknown NodeKind, found: {term}")]
     UnknownNodeKind { term: String },
 
+    #[error("Expected `sh:select` in SPARQL-based constraint {term}")]
+    SparqlConstraintWithoutSelect { term: String },
+
+    #[error("Expected `sh:select` or `sh:ask` in SPARQL-based validator {term}")]
+    SparqlValidatorWithoutQuery { term: String },
+
+    #[error("Expected IRI as `sh:path` of parameter {term}")]
+    ParameterWithoutPath { term: String },
+
+    #[error("Expected IRI as value of `sh:namespace` in prefix declaration {term}")]
+    PrefixDeclarationNamespace { term: String },
+
     #[error("SHACL error: {err}")]
     ShaclError {
         #[from]
         err: ShaclError,
     },
 
+    #[error("Shape node {node} can't be converted to a term: {error}")]
+    ShapeNodeConversion { node: String, error: String },
+
     #[error("Custom error: {msg}")]
     Custom { msg: String },
 }
@@ -32,6 +32,10 @@ where
             .iter()
             .try_for_each(|(_, shape)| shape.write(&mut self.rdf))?;
 
+        schema
+            .property_groups()
+            .try_for_each(|(_, group)| group.write(&mut self.rdf))?;
+
         Ok(())
     }
 
@@ -27,4 +27,5 @@ pub enum ShaclFormat {
     TriG,
     N3,
     NQuads,
+    JsonLd,
 }
@@ -12,12 +12,14 @@ pub const SH_IRI_OR_LITERAL_STR: &str = concatcp!(SH_STR, "IRIOrLiteral");
 pub const SH_LITERAL_STR: &str = concatcp!(SH_STR, "Literal");
 pub const SH_NODE_SHAPE_STR: &str = concatcp!(SH_STR, "NodeShape");
 pub const SH_PROPERTY_SHAPE_STR: &str = concatcp!(SH_STR, "PropertyShape");
+pub const SH_PROPERTY_GROUP_STR: &str = concatcp!(SH_STR, "PropertyGroup");
 pub const SH_SHAPE_STR: &str = concatcp!(SH_STR, "Shape");
 pub const SH_SCHEMA_STR: &str = concatcp!(SH_STR, "Schema");
 pub const SH_VALIDATION_REPORT_STR: &str = concatcp!(SH_STR, "ValidationReport");
 pub const SH_VALIDATION_RESULT_STR: &str = concatcp!(SH_STR, "ValidationResult");
 pub const SH_VIOLATION_STR: &str = concatcp!(SH_STR, "Violation");
 pub const SH_WARNING_STR: &str = concatcp!(SH_STR, "Warning");
+pub const SH_ALTERNATIVE_PATH_STR: &str = concatcp!(SH_STR, "alternativePath");
 pub const SH_AND_STR: &str = concatcp!(SH_STR, "and");
 pub const SH_CLASS_STR: &str = concatcp!(SH_STR, "class");
 pub const SH_CLOSED_STR: &str = concatcp!(SH_STR, "closed");
@@ -25,6 +27,7 @@ pub const SH_CONFORMS_STR: &str = concatcp!(SH_STR, "conforms");
 pub const SH_DATATYPE_STR: &str = concatcp!(SH_STR, "datatype");
 pub const SH_DEACTIVATED_STR: &str = concatcp!(SH_STR, "deactivated");
 pub const SH_DESCRIPTION_STR: &str = concatcp!(SH_STR, "description");
+pub const SH_DETAIL_STR: &str = concatcp!(SH_STR, "detail");
 pub const SH_DISJOINT_STR: &str = concatcp!(SH_STR, "disjoint");
 pub const SH_EQUALS_STR: &str = concatcp!(SH_STR, "equals");
 pub const SH_ENTAILMENT_STR: &str = concatcp!(SH_STR, "entailment");
@@ -34,6 +37,7 @@ pub const SH_GROUP_STR: &str = concatcp!(SH_STR, "group");
 pub const SH_HAS_VALUE_STR: &str = concatcp!(SH_STR, "hasValue");
 pub const SH_IGNORED_PROPERTIES_STR: &str = concatcp!(SH_STR, "ignoredProperties");
 pub const SH_IN_STR: &str = concatcp!(SH_STR, "in");
+pub const SH_INVERSE_PATH_STR: &str = concatcp!(SH_STR, "inversePath");
 pub const SH_LANGUAGE_IN_STR: &str = concatcp!(SH_STR, "languageIn");
 pub const SH_LESS_THAN_STR: &str = concatcp!(SH_STR, "lessThan");
 pub const SH_LESS_THAN_OR_EQUALS_STR: &str = concatcp!(SH_STR, "lessThanOrEquals");
@@ -47,6 +51,7 @@ pub const SH_MIN_LENGTH_STR: &str = concatcp!(SH_STR, "minLength");
 pub const SH_MAX_LENGTH_STR: &str = concatcp!(SH_STR, "maxLength");
 pub const SH_MESSAGE_STR: &str = concatcp!(SH_STR, "message");
 pub const SH_NAME_STR: &str = concatcp!(SH_STR, "name");
+pub const SH_ONE_OR_MORE_PATH_STR: &str = concatcp!(SH_STR, "oneOrMorePath");
 pub const SH_NODE_KIND_STR: &str = concatcp!(SH_STR, "nodeKind");
 pub const SH_NODE_STR: &str = concatcp!(SH_STR, "node");
 pub const SH_NOT_STR: &str = concatcp!(SH_STR, "not");
@@ -76,7 +81,23 @@ pub const SH_TARGET_OBJECTS_OF_STR: &str = concatcp!(SH_STR, "targetObjectsOf");
 pub const SH_TEXT_STR: &str = concatcp!(SH_STR, "text");
 pub const SH_UNIQUE_LANG_STR: &str = concatcp!(SH_STR, "uniqueLang");
 pub const SH_XONE_STR: &str = concatcp!(SH_STR, "xone");
+pub const SH_ZERO_OR_MORE_PATH_STR: &str = concatcp!(SH_STR, "zeroOrMorePath");
+pub const SH_ZERO_OR_ONE_PATH_STR: &str = concatcp!(SH_STR, "zeroOrOnePath");
 pub const SH_SOURCE_CONSTRAINT_STR: &str = concatcp!(SH_STR, "sourceConstraint");
+pub const SH_ASK_STR: &str = concatcp!(SH_STR, "ask");
+pub const SH_CONSTRAINT_COMPONENT_STR: &str = concatcp!(SH_STR, "ConstraintComponent");
+pub const SH_DECLARE_STR: &str = concatcp!(SH_STR, "declare");
+pub const SH_NAMESPACE_STR: &str = concatcp!(SH_STR, "namespace");
+pub const SH_NODE_VALIDATOR_STR: &str = concatcp!(SH_STR, "nodeValidator");
+pub const SH_OPTIONAL_STR: &str = concatcp!(SH_STR, "optional");
+pub const SH_PARAMETER_STR: &str = concatcp!(SH_STR, "parameter");
+pub const SH_PREFIX_STR: &str = concatcp!(SH_STR, "prefix");
+pub const SH_PREFIXES_STR: &str = concatcp!(SH_STR, "prefixes");
+pub const SH_PROPERTY_VALIDATOR_STR: &str = concatcp!(SH_STR, "propertyValidator");
+pub const SH_SELECT_STR: &str = concatcp!(SH_STR, "select");
+pub const SH_SPARQL_STR: &str = concatcp!(SH_STR, "sparql");
+pub const SH_SPARQL_CONSTRAINT_COMPONENT_STR: &str = concatcp!(SH_STR, "SPARQLConstraintComponent");
+pub const SH_VALIDATOR_STR: &str = concatcp!(SH_STR, "validator");
 
 lazy_static! {
     pub static ref SH: IriS = IriS::new_unchecked(SH_STR);
@@ -90,12 +111,14 @@ lazy_static! {
     pub static ref SH_LITERAL: IriS = IriS::new_unchecked(SH_LITERAL_STR);
     pub static ref SH_NODE_SHAPE: IriS = IriS::new_unchecked(SH_NODE_SHAPE_STR);
     pub static ref SH_PROPERTY_SHAPE: IriS = IriS::new_unchecked(SH_PROPERTY_SHAPE_STR);
+    pub static ref SH_PROPERTY_GROUP: IriS = IriS::new_unchecked(SH_PROPERTY_GROUP_STR);
     pub static ref SH_SHAPE: IriS = IriS::new_unchecked(SH_SHAPE_STR);
     pub static ref SH_SCHEMA: IriS = IriS::new_unchecked(SH_SCHEMA_STR);
     pub static ref SH_VALIDATION_REPORT: IriS = IriS::new_unchecked(SH_VALIDATION_REPORT_STR);
     pub static ref SH_VALIDATION_RESULT: IriS = IriS::new_unchecked(SH_VALIDATION_RESULT_STR);
     pub static ref SH_VIOLATION: IriS = IriS::new_unchecked(SH_VIOLATION_STR);
     pub static ref SH_WARNING: IriS = IriS::new_unchecked(SH_WARNING_STR);
+    pub static ref SH_ALTERNATIVE_PATH: IriS = IriS::new_unchecked(SH_ALTERNATIVE_PATH_STR);
     pub static ref SH_AND: IriS = IriS::new_unchecked(SH_AND_STR);
     pub static ref SH_CLASS: IriS = IriS::new_unchecked(SH_CLASS_STR);
     pub static ref SH_CLOSED: IriS = IriS::new_unchecked(SH_CLOSED_STR);
@@ -103,6 +126,7 @@ lazy_static! {
     pub static ref SH_DATATYPE: IriS = IriS::new_unchecked(SH_DATATYPE_STR);
     pub static ref SH_DEACTIVATED: IriS = IriS::new_unchecked(SH_DEACTIVATED_STR);
     pub static ref SH_DESCRIPTION: IriS = IriS::new_unchecked(SH_DESCRIPTION_STR);
+    pub static ref SH_DETAIL: IriS = IriS::new_unchecked(SH_DETAIL_STR);
     pub static ref SH_DISJOINT: IriS = IriS::new_unchecked(SH_DISJOINT_STR);
     pub static ref SH_EQUALS: IriS = IriS::new_unchecked(SH_EQUALS_STR);
     pub static ref SH_ENTAILMENT: IriS = IriS::new_unchecked(SH_ENTAILMENT_STR);
@@ -112,6 +136,7 @@ lazy_static! {
     pub static ref SH_HAS_VALUE: IriS = IriS::new_unchecked(SH_HAS_VALUE_STR);
     pub static ref SH_IGNORED_PROPERTIES: IriS = IriS::new_unchecked(SH_IGNORED_PROPERTIES_STR);
     pub static ref SH_IN: IriS = IriS::new_unchecked(SH_IN_STR);
+    pub static ref SH_INVERSE_PATH: IriS = IriS::new_unchecked(SH_INVERSE_PATH_STR);
     pub static ref SH_LANGUAGE_IN: IriS = IriS::new_unchecked(SH_LANGUAGE_IN_STR);
     pub static ref SH_LESS_THAN: IriS = IriS::new_unchecked(SH_LESS_THAN_STR);
     pub static ref SH_LESS_THAN_OR_EQUALS: IriS = IriS::new_unchecked(SH_LESS_THAN_OR_EQUALS_STR);
@@ -125,6 +150,7 @@ lazy_static! {
     pub static ref SH_MAX_LENGTH: IriS = IriS::new_unchecked(SH_MAX_LENGTH_STR);
     pub static ref SH_MESSAGE: IriS = IriS::new_unchecked(SH_MESSAGE_STR);
     pub static ref SH_NAME: IriS = IriS::new_unchecked(SH_NAME_STR);
+    pub static ref SH_ONE_OR_MORE_PATH: IriS = IriS::new_unchecked(SH_ONE_OR_MORE_PATH_STR);
     pub static ref SH_NODE_KIND: IriS = IriS::new_unchecked(SH_NODE_KIND_STR);
     pub static ref SH_NODE: IriS = IriS::new_unchecked(SH_NODE_STR);
     pub static ref SH_NOT: IriS = IriS::new_unchecked(SH_NOT_STR);
@@ -156,5 +182,22 @@ lazy_static! {
     pub static ref SH_TEXT: IriS = IriS::new_unchecked(SH_TEXT_STR);
     pub static ref SH_UNIQUE_LANG: IriS = IriS::new_unchecked(SH_UNIQUE_LANG_STR);
     pub static ref SH_XONE: IriS = IriS::new_unchecked(SH_XONE_STR);
+    pub static ref SH_ZERO_OR_MORE_PATH: IriS = IriS::new_unchecked(SH_ZERO_OR_MORE_PATH_STR);
+    pub static ref SH_ZERO_OR_ONE_PATH: IriS = IriS::new_unchecked(SH_ZERO_OR_ONE_PATH_STR);
     pub static ref SH_SOURCE_CONSTRAINT: IriS = IriS::new_unchecked(SH_SOURCE_CONSTRAINT_STR);
+    pub static ref SH_ASK: IriS = IriS::new_unchecked(SH_ASK_STR);
+    pub static ref SH_CONSTRAINT_COMPONENT: IriS = IriS::new_unchecked(SH_CONSTRAINT_COMPONENT_STR);
+    pub static ref SH_DECLARE: IriS = IriS::new_unchecked(SH_DECLARE_STR);
+    pub static ref SH_NAMESPACE: IriS = IriS::new_unchecked(SH_NAMESPACE_STR);
+    pub static ref SH_NODE_VALIDATOR: IriS = IriS::new_unchecked(SH_NODE_VALIDATOR_STR);
+    pub static ref SH_OPTIONAL: IriS = IriS::new_unchecked(SH_OPTIONAL_STR);
+    pub static ref SH_PARAMETER: IriS = IriS::new_unchecked(SH_PARAMETER_STR);
+    pub static ref SH_PREFIX: IriS = IriS::new_unchecked(SH_PREFIX_STR);
+    pub static ref SH_PREFIXES: IriS = IriS::new_unchecked(SH_PREFIXES_STR);
+    pub static ref SH_PROPERTY_VALIDATOR: IriS = IriS::new_unchecked(SH_PROPERTY_VALIDATOR_STR);
+    pub static ref SH_SELECT: IriS = IriS::new_unchecked(SH_SELECT_STR);
+    pub static ref SH_SPARQL: IriS = IriS::new_unchecked(SH_SPARQL_STR);
+    pub static ref SH_SPARQL_CONSTRAINT_COMPONENT: IriS =
+        IriS::new_unchecked(SH_SPARQL_CONSTRAINT_COMPONENT_STR);
+    pub static ref SH_VALIDATOR: IriS = IriS::new_unchecked(SH_VALIDATOR_STR);
 }
@@ -10,4 +10,7 @@ pub enum ConstraintError {
 
     #[error("Expected IRI but found {term}")]
     ExpectedIri { term: String },
+
+    #[error("Value {value} of a parameter can't be converted to a term: {error}")]
+    ParameterValue { value: String, error: String },
 }
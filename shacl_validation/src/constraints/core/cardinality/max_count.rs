@@ -33,7 +33,7 @@ impl<S: SRDFBasic + Debug> Validator<S> for MaxCount {
     }
 }
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for MaxCount {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for MaxCount {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -37,7 +37,7 @@ impl<S: SRDFBasic + Debug> Validator<S> for MinCount {
     }
 }
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for MinCount {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for MinCount {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -47,7 +47,7 @@ impl<S: SRDFBasic + Debug> Validator<S> for And<S> {
     }
 }
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for And<S> {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for And<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -38,7 +38,7 @@ impl<S: SRDFBasic + Debug> Validator<S> for Not<S> {
     }
 }
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for Not<S> {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for Not<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
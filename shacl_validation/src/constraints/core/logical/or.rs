@@ -50,7 +50,7 @@ impl<S: SRDFBasic + Debug> Validator<S> for Or<S> {
     }
 }
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for Or<S> {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for Or<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -47,7 +47,7 @@ impl<S: SRDFBasic + Debug> Validator<S> for Xone<S> {
     }
 }
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for Xone<S> {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for Xone<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -28,7 +28,7 @@ impl<S: SRDFBasic + Debug> Validator<S> for Closed<S> {
     }
 }
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for Closed<S> {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for Closed<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
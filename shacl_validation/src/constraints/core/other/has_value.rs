@@ -33,7 +33,7 @@ impl<S: SRDFBasic + Debug> Validator<S> for HasValue<S> {
     }
 }
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for HasValue<S> {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for HasValue<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -30,7 +30,7 @@ impl<S: SRDFBasic + Debug> Validator<S> for In<S> {
     }
 }
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for In<S> {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for In<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
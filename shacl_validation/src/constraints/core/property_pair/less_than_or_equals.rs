@@ -10,7 +10,7 @@ use srdf::QuerySRDF;
 use srdf::SRDF;
 use std::fmt::Debug;
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for LessThanOrEquals<S> {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for LessThanOrEquals<S> {
     fn validate_native(
         &self,
         _component: &CompiledComponent<S>,
@@ -38,7 +38,7 @@ impl<S: SRDFBasic + Debug> Validator<S> for Node<S> {
     }
 }
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for Node<S> {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for Node<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -30,7 +30,7 @@ impl<S: SRDFBasic + Debug> Validator<S> for QualifiedValueShape<S> {
     }
 }
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for QualifiedValueShape<S> {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for QualifiedValueShape<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
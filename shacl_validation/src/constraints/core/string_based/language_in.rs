@@ -46,7 +46,7 @@ impl<S: SRDFBasic + Debug> Validator<S> for LanguageIn<S> {
     }
 }
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for LanguageIn<S> {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for LanguageIn<S> {
     fn validate_native<'a>(
         &self,
         component: &CompiledComponent<S>,
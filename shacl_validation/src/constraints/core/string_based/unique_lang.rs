@@ -60,7 +60,7 @@ impl<S: SRDFBasic + Debug> Validator<S> for UniqueLang {
     }
 }
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for UniqueLang {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for UniqueLang {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
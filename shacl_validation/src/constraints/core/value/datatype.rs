@@ -37,7 +37,7 @@ impl<S: SRDFBasic + Debug> Validator<S> for Datatype<S> {
     }
 }
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for Datatype<S> {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for Datatype<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
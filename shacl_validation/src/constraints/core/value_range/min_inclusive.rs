@@ -12,7 +12,7 @@ use srdf::QuerySRDF;
 use srdf::SRDF;
 use std::fmt::Debug;
 
-impl<S: SRDF + Debug + 'static> NativeValidator<S> for MinInclusive<S> {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for MinInclusive<S> {
     fn validate_native(
         &self,
         _component: &CompiledComponent<S>,
@@ -12,6 +12,7 @@ use crate::value_nodes::ValueNodes;
 
 pub mod constraint_error;
 pub mod core;
+pub mod sparql_based;
 
 pub trait Validator<S: SRDFBasic + Debug> {
     fn validate(
@@ -60,7 +61,7 @@ pub trait NativeDeref {
     fn deref(&self) -> &Self::Target;
 }
 
-impl<S: SRDF + Debug + 'static> NativeDeref for CompiledComponent<S> {
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeDeref for CompiledComponent<S> {
     type Target = dyn NativeValidator<S>;
 
     generate_deref_fn!(
@@ -91,7 +92,9 @@ impl<S: SRDF + Debug + 'static> NativeDeref for CompiledComponent<S> {
         Node,
         HasValue,
         In,
-        QualifiedValueShape
+        QualifiedValueShape,
+        Sparql,
+        SparqlComponent
     );
 }
 
@@ -132,6 +135,8 @@ impl<S: QuerySRDF + Debug + 'static> SparqlDeref for CompiledComponent<S> {
         Node,
         HasValue,
         In,
-        QualifiedValueShape
+        QualifiedValueShape,
+        Sparql,
+        SparqlComponent
     );
 }
@@ -0,0 +1,2 @@
+pub(crate) mod sparql_constraint;
+pub(crate) mod sparql_constraint_component;
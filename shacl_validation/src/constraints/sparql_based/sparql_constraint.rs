@@ -0,0 +1,71 @@
+use crate::constraints::constraint_error::ConstraintError;
+use crate::constraints::NativeValidator;
+use crate::constraints::SparqlValidator;
+use crate::constraints::Validator;
+use crate::engine::native::NativeEngine;
+use crate::engine::sparql::SparqlEngine;
+use crate::engine::Engine;
+use crate::helpers::constraint::validate_select_with;
+use crate::helpers::sparql::sparql_bindings;
+use crate::helpers::sparql::substitute_path;
+use crate::validation_report::result::ValidationResult;
+use crate::value_nodes::ValueNodes;
+use shacl_ast::compiled::component::CompiledComponent;
+use shacl_ast::compiled::component::Sparql;
+use shacl_ast::compiled::shape::CompiledShape;
+use srdf::QuerySRDF;
+use srdf::SRDF;
+use std::fmt::Debug;
+
+impl<S: QuerySRDF + Debug> Validator<S> for Sparql {
+    fn validate(
+        &self,
+        component: &CompiledComponent<S>,
+        shape: &CompiledShape<S>,
+        store: &S,
+        _: impl Engine<S>,
+        value_nodes: &ValueNodes<S>,
+    ) -> Result<Vec<ValidationResult>, ConstraintError> {
+        if self.is_deactivated() {
+            return Ok(Vec::new());
+        }
+
+        let query = substitute_path(self.query(), shape);
+        let bindings =
+            |focus_node: &S::Term| sparql_bindings(shape, self.shapes_graph(), focus_node);
+
+        validate_select_with(
+            component,
+            shape,
+            store,
+            value_nodes,
+            self.message(),
+            &query,
+            bindings,
+        )
+    }
+}
+
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for Sparql {
+    fn validate_native(
+        &self,
+        component: &CompiledComponent<S>,
+        shape: &CompiledShape<S>,
+        store: &S,
+        value_nodes: &ValueNodes<S>,
+    ) -> Result<Vec<ValidationResult>, ConstraintError> {
+        self.validate(component, shape, store, NativeEngine, value_nodes)
+    }
+}
+
+impl<S: QuerySRDF + Debug + 'static> SparqlValidator<S> for Sparql {
+    fn validate_sparql(
+        &self,
+        component: &CompiledComponent<S>,
+        shape: &CompiledShape<S>,
+        store: &S,
+        value_nodes: &ValueNodes<S>,
+    ) -> Result<Vec<ValidationResult>, ConstraintError> {
+        self.validate(component, shape, store, SparqlEngine, value_nodes)
+    }
+}
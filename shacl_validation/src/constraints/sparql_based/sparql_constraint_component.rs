@@ -0,0 +1,115 @@
+use crate::constraints::constraint_error::ConstraintError;
+use crate::constraints::NativeValidator;
+use crate::constraints::SparqlValidator;
+use crate::constraints::Validator;
+use crate::engine::native::NativeEngine;
+use crate::engine::sparql::SparqlEngine;
+use crate::engine::Engine;
+use crate::helpers::constraint::instantiate_message;
+use crate::helpers::constraint::validate_select_with;
+use crate::helpers::constraint::validate_sparql_ask_with;
+use crate::helpers::sparql::sparql_bindings;
+use crate::helpers::sparql::substitute_path;
+use crate::validation_report::result::ValidationResult;
+use crate::value_nodes::ValueNodes;
+use shacl_ast::compiled::component::CompiledComponent;
+use shacl_ast::compiled::component::SparqlComponent;
+use shacl_ast::compiled::shape::CompiledShape;
+use shacl_ast::sparql_constraint::SparqlQuery;
+use srdf::QuerySRDF;
+use srdf::VarName;
+use srdf::SRDF;
+use std::fmt::Debug;
+
+impl<S: QuerySRDF + Debug> Validator<S> for SparqlComponent {
+    fn validate(
+        &self,
+        component: &CompiledComponent<S>,
+        shape: &CompiledShape<S>,
+        store: &S,
+        _: impl Engine<S>,
+        value_nodes: &ValueNodes<S>,
+    ) -> Result<Vec<ValidationResult>, ConstraintError> {
+        let is_property_shape = matches!(shape, CompiledShape::PropertyShape(_));
+        let validator = match self.validator(is_property_shape) {
+            Some(validator) => validator,
+            // constraint components without a suitable validator are ignored
+            None => return Ok(Vec::new()),
+        };
+
+        let query = substitute_path(&validator.query(), shape);
+        let message = validator
+            .message()
+            .as_ref()
+            .map(|message| instantiate_message(message, self.bindings()));
+        let parameters = self
+            .bindings()
+            .iter()
+            .map(|(var, value)| {
+                S::object_as_term(value)
+                    .map(|term| (VarName::new(var), term))
+                    .map_err(|e| ConstraintError::ParameterValue {
+                        value: value.to_string(),
+                        error: e.to_string(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let bindings = |focus_node: &S::Term| {
+            let mut bindings = sparql_bindings(shape, self.shapes_graph(), focus_node);
+            bindings.extend(parameters.iter().cloned());
+            bindings
+        };
+
+        match validator.sparql_query() {
+            SparqlQuery::Select(_) => validate_select_with(
+                component,
+                shape,
+                store,
+                value_nodes,
+                &message,
+                &query,
+                bindings,
+            ),
+            SparqlQuery::Ask(_) => {
+                let bindings = |focus_node: &S::Term, value_node: &S::Term| {
+                    let mut bindings = bindings(focus_node);
+                    bindings.push((VarName::new("value"), value_node.clone()));
+                    bindings
+                };
+                validate_sparql_ask_with(
+                    component,
+                    shape,
+                    store,
+                    value_nodes,
+                    &message,
+                    &query,
+                    bindings,
+                )
+            }
+        }
+    }
+}
+
+impl<S: SRDF + QuerySRDF + Debug + 'static> NativeValidator<S> for SparqlComponent {
+    fn validate_native(
+        &self,
+        component: &CompiledComponent<S>,
+        shape: &CompiledShape<S>,
+        store: &S,
+        value_nodes: &ValueNodes<S>,
+    ) -> Result<Vec<ValidationResult>, ConstraintError> {
+        self.validate(component, shape, store, NativeEngine, value_nodes)
+    }
+}
+
+impl<S: QuerySRDF + Debug + 'static> SparqlValidator<S> for SparqlComponent {
+    fn validate_sparql(
+        &self,
+        component: &CompiledComponent<S>,
+        shape: &CompiledShape<S>,
+        store: &S,
+        value_nodes: &ValueNodes<S>,
+    ) -> Result<Vec<ValidationResult>, ConstraintError> {
+        self.validate(component, shape, store, SparqlEngine, value_nodes)
+    }
+}
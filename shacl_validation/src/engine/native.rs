@@ -1,6 +1,7 @@
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::property_shape::CompiledPropertyShape;
 use shacl_ast::compiled::shape::CompiledShape;
+use srdf::QuerySRDF;
 use srdf::SHACLPath;
 use srdf::RDFS_CLASS;
 use srdf::RDFS_SUBCLASS_OF;
@@ -19,7 +20,7 @@ use std::fmt::Debug;
 
 pub struct NativeEngine;
 
-impl<S: SRDF + Debug + 'static> Engine<S> for NativeEngine {
+impl<S: SRDF + QuerySRDF + Debug + 'static> Engine<S> for NativeEngine {
     fn evaluate(
         &self,
         store: &S,
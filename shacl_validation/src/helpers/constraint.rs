@@ -1,11 +1,16 @@
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::shape::CompiledShape;
+use srdf::literal::Literal;
 use srdf::Object;
 use srdf::QuerySRDF;
+use srdf::SHACLPath;
 use srdf::SRDFBasic;
+use srdf::VarName;
 
 use crate::constraints::constraint_error::ConstraintError;
+use crate::helpers::sparql::sparql_path;
 use crate::validation_report::result::ValidationResult;
+use crate::value_nodes::FocusNodeIteration;
 use crate::value_nodes::IterationStrategy;
 use crate::value_nodes::ValueNodeIteration;
 use crate::value_nodes::ValueNodes;
@@ -23,11 +28,20 @@ fn apply<S: SRDFBasic, I: IterationStrategy<S>>(
             if let Ok(condition) = evaluator(item) {
                 if condition {
                     let focus = S::term_as_object(focus_node);
+                    let value = iteration_strategy
+                        .to_value(item)
+                        .map(|value| S::term_as_object(&value));
+                    let path = shape_path(shape);
+                    let messages = shape_messages(shape, &focus, value.as_ref(), path.as_ref());
                     let component = Object::iri(component.into());
                     let severity = S::term_as_object(&shape.severity());
                     let source = Some(S::term_as_object(&shape.id().to_owned()));
                     return Some(
-                        ValidationResult::new(focus, component, severity).with_source(source),
+                        ValidationResult::new(focus, component, severity)
+                            .with_source(source)
+                            .with_value(value)
+                            .with_path(path)
+                            .with_messages(messages),
                     );
                 }
             }
@@ -72,3 +86,196 @@ pub fn validate_ask_with<S: QuerySRDF>(
         },
     )
 }
+
+/// Evaluates the SELECT query of a SPARQL-based constraint for each focus node, pre-binding its `bindings`:
+/// every solution is a validation result, unless `?failure` is bound to true,
+/// which signals a failure of the validation.
+pub fn validate_select_with<S: QuerySRDF>(
+    component: &CompiledComponent<S>,
+    shape: &CompiledShape<S>,
+    store: &S,
+    value_nodes: &ValueNodes<S>,
+    message: &Option<String>,
+    query: &str,
+    bindings: impl Fn(&S::Term) -> Vec<(VarName, S::Term)>,
+) -> Result<Vec<ValidationResult>, ConstraintError> {
+    let mut results = Vec::new();
+    for (focus_node, _) in FocusNodeIteration.iterate(value_nodes) {
+        let solutions = store
+            .query_select_with_bindings(query, &bindings(focus_node))
+            .map_err(|err| {
+                ConstraintError::Query(format!("SELECT query failed for {focus_node}: {err}"))
+            })?;
+        for solution in solutions.iter() {
+            if let Some(true) = solution
+                .find_solution("failure")
+                .and_then(S::term_as_boolean)
+            {
+                return Err(ConstraintError::Query(format!(
+                    "SPARQL-based constraint reported a failure for {focus_node}"
+                )));
+            }
+            let focus = solution.find_solution("this").unwrap_or(focus_node);
+            let value = match solution.find_solution("value") {
+                Some(value) => Some(S::term_as_object(value)),
+                None if matches!(shape, CompiledShape::NodeShape(_)) => {
+                    Some(S::term_as_object(focus))
+                }
+                None => None,
+            };
+            let path = solution
+                .find_solution("path")
+                .and_then(S::term_as_iri_s)
+                .map(SHACLPath::iri)
+                .or(shape_path(shape));
+            let bindings = solution
+                .variables()
+                .enumerate()
+                .filter_map(|(n, var)| {
+                    let value = solution.find_solution(n)?;
+                    Some((var.as_str().to_string(), S::term_as_object(value)))
+                })
+                .collect::<Vec<_>>();
+            let messages = match message {
+                Some(message) => vec![Object::Literal(Literal::str(&instantiate_message(
+                    message, &bindings,
+                )))],
+                None => shape_messages(
+                    shape,
+                    &S::term_as_object(focus),
+                    value.as_ref(),
+                    path.as_ref(),
+                ),
+            };
+            results.push(
+                sparql_result(component, shape, focus)
+                    .with_value(value)
+                    .with_path(path)
+                    .with_messages(messages),
+            );
+        }
+    }
+    Ok(results)
+}
+
+/// Evaluates the ASK query of a SPARQL-based validator for each value node,
+/// reporting a validation result when the answer is false.
+pub fn validate_sparql_ask_with<S: QuerySRDF>(
+    component: &CompiledComponent<S>,
+    shape: &CompiledShape<S>,
+    store: &S,
+    value_nodes: &ValueNodes<S>,
+    message: &Option<String>,
+    query: &str,
+    bindings: impl Fn(&S::Term, &S::Term) -> Vec<(VarName, S::Term)>,
+) -> Result<Vec<ValidationResult>, ConstraintError> {
+    let mut results = Vec::new();
+    for (focus_node, value_node) in ValueNodeIteration.iterate(value_nodes) {
+        let ask = store
+            .query_ask_with_bindings(query, &bindings(focus_node, value_node))
+            .map_err(|err| {
+                ConstraintError::Query(format!("ASK query failed for {focus_node}: {err}"))
+            })?;
+        if !ask {
+            let focus = S::term_as_object(focus_node);
+            let value = S::term_as_object(value_node);
+            let path = shape_path(shape);
+            let messages = match message {
+                Some(message) => {
+                    let mut bindings = vec![
+                        ("this".to_string(), focus),
+                        ("value".to_string(), value.clone()),
+                    ];
+                    bindings.extend(
+                        path.as_ref()
+                            .map(|path| ("path".to_string(), path_object(path))),
+                    );
+                    vec![Object::Literal(Literal::str(&instantiate_message(
+                        message, &bindings,
+                    )))]
+                }
+                None => shape_messages(shape, &focus, Some(&value), path.as_ref()),
+            };
+            results.push(
+                sparql_result(component, shape, focus_node)
+                    .with_value(Some(value))
+                    .with_path(path)
+                    .with_messages(messages),
+            );
+        }
+    }
+    Ok(results)
+}
+
+fn sparql_result<S: SRDFBasic>(
+    component: &CompiledComponent<S>,
+    shape: &CompiledShape<S>,
+    focus_node: &S::Term,
+) -> ValidationResult {
+    let focus = S::term_as_object(focus_node);
+    let component = Object::iri(component.into());
+    let severity = S::term_as_object(&shape.severity());
+    let source = Some(S::term_as_object(shape.id()));
+    ValidationResult::new(focus, component, severity).with_source(source)
+}
+
+fn shape_path<S: SRDFBasic>(shape: &CompiledShape<S>) -> Option<SHACLPath> {
+    match shape {
+        CompiledShape::PropertyShape(ps) => Some(ps.path().clone()),
+        CompiledShape::NodeShape(_) => None,
+    }
+}
+
+/// Value of `{$path}` in messages: the IRI of a predicate path, or else the
+/// path with the SPARQL property path syntax
+fn path_object(path: &SHACLPath) -> Object {
+    match path {
+        SHACLPath::Predicate { pred } => Object::iri(pred.clone()),
+        _ => Object::Literal(Literal::str(&sparql_path(path))),
+    }
+}
+
+/// Instantiates the `sh:message` values of a shape, one per language, for a
+/// validation result with the given focus node, value and path.
+fn shape_messages<S: SRDFBasic>(
+    shape: &CompiledShape<S>,
+    focus: &Object,
+    value: Option<&Object>,
+    path: Option<&SHACLPath>,
+) -> Vec<Object> {
+    let mut bindings = vec![("this".to_string(), focus.clone())];
+    bindings.extend(value.map(|value| ("value".to_string(), value.clone())));
+    bindings.extend(path.map(|path| ("path".to_string(), path_object(path))));
+    let mut messages = shape.message().messages().iter().collect::<Vec<_>>();
+    messages.sort_by_key(|(lang, _)| lang.as_ref().map(|lang| lang.value()));
+    messages
+        .into_iter()
+        .map(|(lang, message)| {
+            let message = instantiate_message(message, &bindings);
+            match lang {
+                Some(lang) => Object::Literal(Literal::lang_str(&message, lang.clone())),
+                None => Object::Literal(Literal::str(&message)),
+            }
+        })
+        .collect()
+}
+
+/// Replaces the `{$var}` and `{?var}` placeholders of a message template by
+/// the values bound to those variables, leaving unknown ones untouched.
+///
+/// https://www.w3.org/TR/shacl/#message-templates
+pub(crate) fn instantiate_message(template: &str, bindings: &[(String, Object)]) -> String {
+    bindings
+        .iter()
+        .fold(template.to_string(), |message, (var, value)| {
+            let value = match value {
+                Object::Iri(iri) => iri.as_str().to_string(),
+                Object::BlankNode(id) => format!("_:{id}"),
+                Object::Literal(literal) => literal.lexical_form(),
+                Object::Triple { .. } => value.to_string(),
+            };
+            message
+                .replace(&format!("{{${var}}}"), &value)
+                .replace(&format!("{{?{var}}}"), &value)
+        })
+}
@@ -1,6 +1,11 @@
 use std::collections::HashSet;
 
+use iri_s::IriS;
+use shacl_ast::compiled::shape::CompiledShape;
 use srdf::QuerySRDF;
+use srdf::SHACLPath;
+use srdf::SRDFBasic;
+use srdf::VarName;
 
 use super::helper_error::SPARQLError;
 
@@ -26,3 +31,49 @@ pub fn select<S: QuerySRDF>(
     }
     Ok(ans)
 }
+
+/// Serializes a SHACL path using the SPARQL property path syntax, which is the
+/// value that replaces `$PATH` in SPARQL-based constraints
+pub fn sparql_path(path: &SHACLPath) -> String {
+    match path {
+        SHACLPath::Predicate { pred } => format!("<{}>", pred.as_str()),
+        SHACLPath::Alternative { paths } => {
+            let paths: Vec<_> = paths.iter().map(sparql_path).collect();
+            format!("({})", paths.join("|"))
+        }
+        SHACLPath::Sequence { paths } => {
+            let paths: Vec<_> = paths.iter().map(sparql_path).collect();
+            format!("({})", paths.join("/"))
+        }
+        SHACLPath::Inverse { path } => format!("^{}", sparql_path(path)),
+        SHACLPath::ZeroOrMore { path } => format!("{}*", sparql_path(path)),
+        SHACLPath::OneOrMore { path } => format!("{}+", sparql_path(path)),
+        SHACLPath::ZeroOrOne { path } => format!("{}?", sparql_path(path)),
+    }
+}
+
+/// Bindings shared by every SPARQL-based constraint: `$this`, `$currentShape`
+/// (unless the shape is a blank node) and `$shapesGraph` (if known).
+/// They are pre-bound by the store, so focus nodes can be blank nodes of the data
+pub fn sparql_bindings<S: SRDFBasic>(
+    shape: &CompiledShape<S>,
+    shapes_graph: &Option<IriS>,
+    focus_node: &S::Term,
+) -> Vec<(VarName, S::Term)> {
+    let mut bindings = vec![(VarName::new("this"), focus_node.clone())];
+    if !S::term_is_bnode(shape.id()) {
+        bindings.push((VarName::new("currentShape"), shape.id().clone()));
+    }
+    if let Some(shapes_graph) = shapes_graph {
+        bindings.push((VarName::new("shapesGraph"), S::iri_s2term(shapes_graph)));
+    }
+    bindings
+}
+
+/// Replaces `$PATH` by the path of the property shape
+pub fn substitute_path<S: SRDFBasic>(query: &str, shape: &CompiledShape<S>) -> String {
+    match shape {
+        CompiledShape::PropertyShape(ps) => query.replace("$PATH", &sparql_path(ps.path())),
+        CompiledShape::NodeShape(_) => query.to_string(),
+    }
+}
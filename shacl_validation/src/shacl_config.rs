@@ -1,6 +1,7 @@
 use std::{io, path::Path};
 
-use srdf::RdfDataConfig;
+use shacl_ast::{SH_INFO, SH_WARNING};
+use srdf::{Object, RdfDataConfig};
 use thiserror::Error;
 
 use serde::{Deserialize, Serialize};
@@ -9,12 +10,21 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct ShaclConfig {
     pub data: Option<RdfDataConfig>,
+
+    /// Minimum severity of the results that break conformance. If not set,
+    /// every result breaks conformance
+    pub severity_threshold: Option<SeverityThreshold>,
+
+    /// Preferred language of the messages of the results
+    pub preferred_language: Option<String>,
 }
 
 impl ShaclConfig {
     pub fn new() -> ShaclConfig {
         Self {
             data: Some(RdfDataConfig::default()),
+            severity_threshold: None,
+            preferred_language: None,
         }
     }
 
@@ -40,6 +50,26 @@ impl Default for ShaclConfig {
     }
 }
 
+/// Severity levels of SHACL results, ordered from the least to the most severe
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum SeverityThreshold {
+    Info,
+    Warning,
+    Violation,
+}
+
+impl SeverityThreshold {
+    /// Severity level of a `sh:severity` value. Unknown severities are
+    /// considered violations
+    pub fn from_severity(severity: &Object) -> SeverityThreshold {
+        match severity {
+            Object::Iri(iri) if *iri == *SH_INFO => SeverityThreshold::Info,
+            Object::Iri(iri) if *iri == *SH_WARNING => SeverityThreshold::Warning,
+            _ => SeverityThreshold::Violation,
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum ShaclConfigError {
     #[error("Reading SHACL Config path {path_name:?} error: {error:?}")]
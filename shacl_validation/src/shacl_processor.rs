@@ -11,6 +11,8 @@ use std::path::Path;
 use crate::engine::native::NativeEngine;
 use crate::engine::sparql::SparqlEngine;
 use crate::engine::Engine;
+use crate::focus_nodes::FocusNodes;
+use crate::shape::FocusNodesOps;
 use crate::shape::Validate;
 use crate::store::graph::Graph;
 use crate::store::sparql::Endpoint;
@@ -66,6 +68,40 @@ pub trait ShaclProcessor<S: SRDFBasic + Debug> {
             .with_results(validation_results)
             .with_prefixmap(shapes_graph.prefix_map())) // return the possibly empty validation report
     }
+
+    /// Executes the Validation of the shapes graph restricted to the focus
+    /// nodes that are equal to `node`. It is used to validate the
+    /// neighbourhood of a node when the rest of the graph is not available,
+    /// as in the streaming validation of large dumps.
+    ///
+    /// # Arguments
+    ///
+    /// * `shapes_graph` - A compiled SHACL shapes graph
+    /// * `node` - The only focus node that is validated
+    fn validate_node(
+        &self,
+        shapes_graph: &CompiledSchema<S>,
+        node: &S::Term,
+    ) -> Result<ValidationReport, ValidateError> {
+        let mut validation_results = Vec::new();
+
+        for (_, shape) in shapes_graph.iter() {
+            let focus_nodes = FocusNodes::new(
+                shape
+                    .focus_nodes(self.store(), self.runner())
+                    .into_iter()
+                    .filter(|focus_node| focus_node == node),
+            );
+            if focus_nodes.len() > 0 {
+                let results = shape.validate(self.store(), self.runner(), Some(&focus_nodes))?;
+                validation_results.extend(results);
+            }
+        }
+
+        Ok(ValidationReport::new()
+            .with_results(validation_results)
+            .with_prefixmap(shapes_graph.prefix_map()))
+    }
 }
 
 /// The In-Memory Graph Validation algorithm.
@@ -144,6 +180,11 @@ impl GraphValidation {
     pub fn from_graph(graph: Graph, mode: ShaclValidationMode) -> GraphValidation {
         GraphValidation { store: graph, mode }
     }
+
+    /// Returns the graph, so that it can be extended and validated again
+    pub fn into_graph(self) -> Graph {
+        self.store
+    }
 }
 
 impl ShaclProcessor<RdfData> for GraphValidation {
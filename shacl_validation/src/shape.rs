@@ -1,3 +1,4 @@
+use crate::constraints::constraint_error::ConstraintError;
 use crate::engine::Engine;
 use crate::focus_nodes::FocusNodes;
 use crate::validate_error::ValidateError;
@@ -43,27 +44,26 @@ impl<S: SRDFBasic + Debug> Validate<S> for CompiledShape<S> {
         //    nodes is obtained from the set of focus nodes
         let value_nodes = self.value_nodes(store, &focus_nodes, runner);
 
-        // 3.
-        let component_validation_results = self
-            .components()
-            .iter()
-            .flat_map(move |component| runner.evaluate(store, self, component, &value_nodes));
+        // 3. Errors of the components are propagated, except for the
+        //    components that are not implemented yet, which are skipped
+        let mut validation_results = Vec::new();
+        for component in self.components() {
+            match runner.evaluate(store, self, component, &value_nodes) {
+                Ok(results) => validation_results.extend(results),
+                Err(ValidateError::NotImplemented { .. })
+                | Err(ValidateError::Constraint(ConstraintError::NotImplemented(_))) => {}
+                Err(err) => return Err(err),
+            }
+        }
 
         // 4. After validating the constraints that are defined in the current
         //    Shape, it is important to also perform the validation over those
         //    nested PropertyShapes. The thing is that the validation needs to
         //    occur over the focus_nodes that have been computed for the current
         //    shape
-        let property_shapes_validation_results = self
-            .property_shapes()
-            .iter()
-            .flat_map(|shape| shape.validate(store, runner, Some(&focus_nodes)));
-
-        // 5.
-        let validation_results = component_validation_results
-            .chain(property_shapes_validation_results)
-            .flatten()
-            .collect();
+        for shape in self.property_shapes() {
+            validation_results.extend(shape.validate(store, runner, Some(&focus_nodes))?);
+        }
 
         Ok(validation_results)
     }
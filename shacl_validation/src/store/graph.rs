@@ -51,6 +51,10 @@ impl Graph {
     pub fn from_data(data: RdfData) -> Graph {
         Graph { store: data }
     }
+
+    pub fn into_data(self) -> RdfData {
+        self.store
+    }
 }
 
 impl Store<RdfData> for Graph {
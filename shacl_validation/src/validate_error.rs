@@ -22,7 +22,7 @@ pub enum ValidateError {
     Graph(#[from] SRDFGraphError),
     #[error("Error when parsing the SHACL Graph, {}", ._0)] // TODO: move to store
     ShaclParser(#[from] ShaclParserError),
-    #[error("Error during the constraint evaluation")]
+    #[error("Error during the constraint evaluation: {}", ._0)]
     Constraint(#[from] ConstraintError),
     // #[error("Error parsing the IRI")]
     //IriParse(#[from] IriParseError),
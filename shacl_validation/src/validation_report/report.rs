@@ -1,10 +1,17 @@
 use std::fmt::{Debug, Display};
+use std::io::Write;
 
 use colored::*;
+use iri_s::IriS;
 use prefixmap::PrefixMap;
-use srdf::{Object, SRDF};
+use serde_json::{Map, Value};
+use shacl_ast::{SH_CONFORMS, SH_RESULT, SH_STR, SH_VALIDATION_REPORT};
+use srdf::lang::Lang;
+use srdf::literal::Literal;
+use srdf::{Object, RDFFormat, SRDFBuilder, SRDFGraph, RDF, SRDF, XSD};
 
 use crate::helpers::srdf::get_objects_for;
+use crate::shacl_config::SeverityThreshold;
 
 use super::result::ValidationResult;
 use super::validation_report_error::ReportError;
@@ -17,6 +24,7 @@ pub struct ValidationReport {
     ok_color: Option<Color>,
     fail_color: Option<Color>,
     display_with_colors: bool,
+    severity_threshold: Option<SeverityThreshold>,
 }
 
 impl ValidationReport {
@@ -64,6 +72,21 @@ impl ValidationReport {
         self
     }
 
+    /// Sets the minimum severity of the results that break conformance
+    pub fn with_severity_threshold(mut self, threshold: Option<SeverityThreshold>) -> Self {
+        self.severity_threshold = threshold;
+        self
+    }
+
+    /// Selects the message of every result in the preferred language, if
+    /// available
+    pub fn with_preferred_language(mut self, lang: Option<Lang>) -> Self {
+        for result in self.results.iter_mut() {
+            result.select_message(lang.as_ref());
+        }
+        self
+    }
+
     pub fn results(&self) -> &Vec<ValidationResult> {
         &self.results
     }
@@ -78,8 +101,94 @@ impl ValidationReport {
         Ok(ValidationReport::new().with_results(results))
     }
 
+    /// A report conforms if it has no results with a severity at or above the
+    /// severity threshold. Without threshold, any result breaks conformance
     pub fn conforms(&self) -> bool {
-        self.results.is_empty()
+        match self.severity_threshold {
+            None => self.results.is_empty(),
+            Some(threshold) => self
+                .results
+                .iter()
+                .all(|result| SeverityThreshold::from_severity(result.severity()) < threshold),
+        }
+    }
+}
+
+impl ValidationReport {
+    /// Returns an RDF graph with the report as a `sh:ValidationReport` node
+    /// and one `sh:ValidationResult` node per result, as defined in the SHACL spec
+    pub fn as_rdf<RDF>(&self) -> Result<RDF, RDF::Err>
+    where
+        RDF: SRDFBuilder,
+    {
+        let mut rdf = RDF::empty();
+        rdf.add_prefix_map(self.rdf_prefixmap())?;
+        let report = RDF::bnode_as_subject(rdf.add_bnode()?);
+        rdf.add_type(
+            &RDF::subject_as_object(&report),
+            RDF::iri_s2term(&SH_VALIDATION_REPORT),
+        )?;
+        rdf.add_triple(
+            &report,
+            &RDF::iri_s2iri(&SH_CONFORMS),
+            &RDF::object_as_term(&Object::Literal(Literal::boolean(self.conforms())))?,
+        )?;
+        for result in self.results.iter() {
+            let result_node = RDF::bnode_as_subject(rdf.add_bnode()?);
+            rdf.add_triple(
+                &report,
+                &RDF::iri_s2iri(&SH_RESULT),
+                &RDF::subject_as_term(&result_node),
+            )?;
+            result.write(&mut rdf, &result_node)?;
+        }
+        Ok(rdf)
+    }
+
+    /// Serializes the report as RDF in the given format (see [`ValidationReport::as_rdf`])
+    pub fn serialize_rdf<W: Write>(
+        &self,
+        format: &RDFFormat,
+        writer: &mut W,
+    ) -> Result<(), ReportError> {
+        let rdf_error = |e: srdf::SRDFGraphError| ReportError::RDFSerialization(e.to_string());
+        let rdf: SRDFGraph = self.as_rdf().map_err(rdf_error)?;
+        rdf.serialize(format, writer).map_err(rdf_error)
+    }
+
+    /// Returns the report as a JSON object with the fields `conforms` and `results`
+    pub fn as_json(&self) -> Value {
+        let mut report = Map::new();
+        report.insert("conforms".to_string(), Value::Bool(self.conforms()));
+        report.insert(
+            "results".to_string(),
+            Value::Array(self.results.iter().map(|r| r.as_json()).collect()),
+        );
+        Value::Object(report)
+    }
+
+    /// Serializes the report as a pretty printed JSON string
+    pub fn as_json_string(&self) -> Result<String, ReportError> {
+        serde_json::to_string_pretty(&self.as_json())
+            .map_err(|e| ReportError::JSONSerialization(e.to_string()))
+    }
+
+    fn rdf_prefixmap(&self) -> PrefixMap {
+        let mut prefixmap = self.nodes_prefixmap.clone();
+        let basic = [("sh", SH_STR), ("rdf", RDF), ("xsd", XSD)];
+        let basic = basic
+            .iter()
+            .map(|(alias, iri)| (alias.to_string(), IriS::new_unchecked(iri)));
+        let shapes = self
+            .shapes_prefixmap
+            .iter()
+            .map(|(alias, iri)| (alias.clone(), iri.clone()));
+        for (alias, iri) in shapes.chain(basic) {
+            if !prefixmap.map.contains_key(&alias) {
+                let _ = prefixmap.insert(&alias, &iri);
+            }
+        }
+        prefixmap
     }
 }
 
@@ -92,6 +201,7 @@ impl Default for ValidationReport {
             ok_color: Some(Color::Green),
             fail_color: Some(Color::Red),
             display_with_colors: true,
+            severity_threshold: None,
         }
     }
 }
@@ -123,9 +233,14 @@ impl Display for ValidationReport {
             Ok(())
         } else {
             let str = format!("{} errors found", self.results.len());
+            let color = if self.conforms() {
+                self.ok_color
+            } else {
+                self.fail_color
+            };
             if self.display_with_colors {
-                if let Some(fail_color) = self.fail_color {
-                    writeln!(f, "{}", str.color(fail_color))?;
+                if let Some(color) = color {
+                    writeln!(f, "{}", str.color(color))?;
                 } else {
                     writeln!(f, "{str}")?;
                 }
@@ -140,13 +255,19 @@ impl Display for ValidationReport {
                     .without_default_colors()
             };
             for result in self.results.iter() {
-                writeln!(
+                write!(
                     f,
                     "Focus node {}, Component: {}, severity: {}",
                     show_node(result.focus_node(), &self.nodes_prefixmap),
                     show_component(result.component(), &shacl_prefixmap),
                     show_severity(result.severity(), &shacl_prefixmap)
                 )?;
+                match result.message() {
+                    Some(Object::Literal(message)) => {
+                        writeln!(f, ", message: {}", message.lexical_form())?
+                    }
+                    _ => writeln!(f)?,
+                }
             }
             Ok(())
         }
@@ -158,6 +279,7 @@ fn show_node(node: &Object, prefixmap: &PrefixMap) -> String {
         Object::Iri(iri_s) => prefixmap.qualify(iri_s),
         Object::BlankNode(node) => format!("_:{node}"),
         Object::Literal(literal) => format!("{literal}"),
+        Object::Triple { .. } => format!("{node}"),
     }
 }
 
@@ -166,6 +288,7 @@ fn show_component(component: &Object, shacl_prefixmap: &PrefixMap) -> String {
         Object::Iri(iri_s) => shacl_prefixmap.qualify(iri_s),
         Object::BlankNode(node) => format!("_:{node}"),
         Object::Literal(literal) => format!("{literal}"),
+        Object::Triple { .. } => format!("{component}"),
     }
 }
 
@@ -174,5 +297,6 @@ fn show_severity(severity: &Object, shacl_prefixmap: &PrefixMap) -> String {
         Object::Iri(iri_s) => shacl_prefixmap.qualify(iri_s),
         Object::BlankNode(node) => format!("_:{node}"),
         Object::Literal(literal) => format!("{literal}"),
+        Object::Triple { .. } => format!("{severity}"),
     }
 }
@@ -1,18 +1,24 @@
 use super::validation_report_error::ResultError;
-use crate::helpers::srdf::get_object_for;
+use crate::helpers::sparql::sparql_path;
+use crate::helpers::srdf::{get_object_for, get_objects_for};
+use serde_json::{Map, Value};
 use shacl_ast::*;
-use srdf::{Object, RDFNode, SRDF};
+use srdf::lang::Lang;
+use srdf::literal::Literal;
+use srdf::{Object, RDFNode, SHACLPath, SRDFBuilder, RDF_FIRST, RDF_NIL, RDF_REST, SRDF};
+use std::collections::HashSet;
 use std::fmt::Debug;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ValidationResult {
     focus_node: RDFNode,           // required
-    path: Option<RDFNode>,         // optional
+    path: Option<SHACLPath>,       // optional
     value: Option<RDFNode>,        // optional
     source: Option<RDFNode>,       // optional
     constraint_component: RDFNode, // required
     details: Option<Vec<RDFNode>>, // optional
     message: Option<RDFNode>,      // optional
+    messages: Vec<RDFNode>,        // candidate messages, one per language
     severity: RDFNode,             // required (TODO: Replace by Severity?)
 }
 
@@ -27,11 +33,12 @@ impl ValidationResult {
             constraint_component,
             details: None,
             message: None,
+            messages: Vec::new(),
             severity,
         }
     }
 
-    pub fn with_path(mut self, path: Option<Object>) -> Self {
+    pub fn with_path(mut self, path: Option<SHACLPath>) -> Self {
         self.path = path;
         self
     }
@@ -56,6 +63,37 @@ impl ValidationResult {
         self
     }
 
+    /// Sets the candidate messages of the result, usually one per language,
+    /// selecting the one without language tag (or else the first one)
+    pub fn with_messages(mut self, messages: Vec<Object>) -> Self {
+        self.messages = messages;
+        self.select_message(None);
+        self
+    }
+
+    /// Selects the candidate message whose language tag matches `lang`,
+    /// falling back to the message without language tag or the first one
+    pub fn select_message(&mut self, lang: Option<&Lang>) {
+        let lang_of = |message: &Object| match message {
+            Object::Literal(Literal::StringLiteral { lang, .. }) => lang.clone(),
+            _ => None,
+        };
+        let preferred = lang.and_then(|lang| {
+            self.messages.iter().find(|message| {
+                lang_of(message).is_some_and(|tag| {
+                    let (tag, lang) = (tag.value(), lang.value());
+                    tag == lang || tag.starts_with(&format!("{lang}-"))
+                })
+            })
+        });
+        let selected = preferred
+            .or_else(|| self.messages.iter().find(|m| lang_of(m).is_none()))
+            .or_else(|| self.messages.first());
+        if let Some(message) = selected {
+            self.message = Some(message.clone());
+        }
+    }
+
     pub fn focus_node(&self) -> &Object {
         &self.focus_node
     }
@@ -67,6 +105,145 @@ impl ValidationResult {
     pub fn severity(&self) -> &Object {
         &self.severity
     }
+
+    pub fn path(&self) -> Option<&SHACLPath> {
+        self.path.as_ref()
+    }
+
+    pub fn value(&self) -> Option<&Object> {
+        self.value.as_ref()
+    }
+
+    pub fn source(&self) -> Option<&Object> {
+        self.source.as_ref()
+    }
+
+    pub fn message(&self) -> Option<&Object> {
+        self.message.as_ref()
+    }
+
+    pub fn messages(&self) -> &Vec<Object> {
+        &self.messages
+    }
+
+    pub fn details(&self) -> Option<&Vec<Object>> {
+        self.details.as_ref()
+    }
+}
+
+impl ValidationResult {
+    /// Adds the triples of the result to `rdf`, using `id` as the node of the `sh:ValidationResult`
+    pub fn write<RDF>(&self, rdf: &mut RDF, id: &RDF::Subject) -> Result<(), RDF::Err>
+    where
+        RDF: SRDFBuilder,
+    {
+        rdf.add_type(
+            &RDF::subject_as_object(id),
+            RDF::iri_s2term(&SH_VALIDATION_RESULT),
+        )?;
+        if let Some(path) = &self.path {
+            let path = write_path(rdf, path)?;
+            rdf.add_triple(id, &RDF::iri_s2iri(&SH_RESULT_PATH), &path)?;
+        }
+        let mut add = |pred: &iri_s::IriS, object: &Object| {
+            rdf.add_triple(id, &RDF::iri_s2iri(pred), &RDF::object_as_term(object)?)
+        };
+        add(&SH_FOCUS_NODE, &self.focus_node)?;
+        add(&SH_RESULT_SEVERITY, &self.severity)?;
+        add(&SH_SOURCE_CONSTRAINT_COMPONENT, &self.constraint_component)?;
+        if let Some(value) = &self.value {
+            add(&SH_VALUE, value)?;
+        }
+        if let Some(source) = &self.source {
+            add(&SH_SOURCE_SHAPE, source)?;
+        }
+        if let Some(message) = &self.message {
+            add(&SH_RESULT_MESSAGE, message)?;
+        }
+        for detail in self.details.iter().flatten() {
+            add(&SH_DETAIL, detail)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the result as a JSON object whose keys are the local names of the SHACL properties
+    pub fn as_json(&self) -> Value {
+        let mut result = Map::new();
+        result.insert("focusNode".to_string(), json_node(&self.focus_node));
+        result.insert("resultSeverity".to_string(), json_node(&self.severity));
+        result.insert(
+            "sourceConstraintComponent".to_string(),
+            json_node(&self.constraint_component),
+        );
+        if let Some(path) = &self.path {
+            result.insert("resultPath".to_string(), json_path(path));
+        }
+        if let Some(value) = &self.value {
+            result.insert("value".to_string(), json_node(value));
+        }
+        if let Some(source) = &self.source {
+            result.insert("sourceShape".to_string(), json_node(source));
+        }
+        if let Some(message) = &self.message {
+            result.insert("resultMessage".to_string(), json_node(message));
+        }
+        if let Some(details) = &self.details {
+            result.insert(
+                "detail".to_string(),
+                Value::Array(details.iter().map(json_node).collect()),
+            );
+        }
+        Value::Object(result)
+    }
+}
+
+/// Adds the triples of a SHACL path to `rdf` and returns its node: the IRI of
+/// a predicate path, an RDF list for a sequence path or a fresh blank node
+/// for the other paths
+fn write_path<RDF: SRDFBuilder>(rdf: &mut RDF, path: &SHACLPath) -> Result<RDF::Term, RDF::Err> {
+    let (pred, object) = match path {
+        SHACLPath::Predicate { pred } => return Ok(RDF::iri_s2term(pred)),
+        SHACLPath::Sequence { paths } => return write_path_list(rdf, paths),
+        SHACLPath::Alternative { paths } => (&*SH_ALTERNATIVE_PATH, write_path_list(rdf, paths)?),
+        SHACLPath::Inverse { path } => (&*SH_INVERSE_PATH, write_path(rdf, path)?),
+        SHACLPath::ZeroOrMore { path } => (&*SH_ZERO_OR_MORE_PATH, write_path(rdf, path)?),
+        SHACLPath::OneOrMore { path } => (&*SH_ONE_OR_MORE_PATH, write_path(rdf, path)?),
+        SHACLPath::ZeroOrOne { path } => (&*SH_ZERO_OR_ONE_PATH, write_path(rdf, path)?),
+    };
+    let node = RDF::bnode_as_subject(rdf.add_bnode()?);
+    rdf.add_triple(&node, &RDF::iri_s2iri(pred), &object)?;
+    Ok(RDF::subject_as_term(&node))
+}
+
+fn write_path_list<RDF: SRDFBuilder>(
+    rdf: &mut RDF,
+    paths: &[SHACLPath],
+) -> Result<RDF::Term, RDF::Err> {
+    let mut list = RDF::iri_s2term(&RDF_NIL);
+    for path in paths.iter().rev() {
+        let first = write_path(rdf, path)?;
+        let node = RDF::bnode_as_subject(rdf.add_bnode()?);
+        rdf.add_triple(&node, &RDF::iri_s2iri(&RDF_FIRST), &first)?;
+        rdf.add_triple(&node, &RDF::iri_s2iri(&RDF_REST), &list)?;
+        list = RDF::subject_as_term(&node);
+    }
+    Ok(list)
+}
+
+/// Predicate paths are represented by their IRI and other paths with the SPARQL property path syntax
+fn json_path(path: &SHACLPath) -> Value {
+    match path {
+        SHACLPath::Predicate { pred } => Value::String(pred.as_str().to_string()),
+        _ => Value::String(sparql_path(path)),
+    }
+}
+
+/// IRIs are represented by their string, blank nodes and literals in Turtle notation
+fn json_node(node: &Object) -> Value {
+    match node {
+        Object::Iri(iri) => Value::String(iri.as_str().to_string()),
+        _ => Value::String(node.to_string()),
+    }
 }
 
 impl ValidationResult {
@@ -100,7 +277,13 @@ impl ValidationResult {
         };
 
         // 2. Second, we must process the optional fields
-        let path = get_object_for(store, validation_result, &S::iri_s2iri(&SH_RESULT_PATH))?;
+        let path = match get_objects_for(store, validation_result, &S::iri_s2iri(&SH_RESULT_PATH))?
+            .into_iter()
+            .next()
+        {
+            Some(path) => Some(parse_path(store, &path)?),
+            None => None,
+        };
         let source = get_object_for(store, validation_result, &S::iri_s2iri(&SH_SOURCE_SHAPE))?;
         let value = get_object_for(store, validation_result, &S::iri_s2iri(&SH_VALUE))?;
 
@@ -113,3 +296,59 @@ impl ValidationResult {
         )
     }
 }
+
+/// Builds a SHACL path from the path it modifies
+type UnaryPath = fn(Box<SHACLPath>) -> SHACLPath;
+
+/// Parses the SHACL path whose node is `term`
+fn parse_path<S: SRDF>(store: &S, term: &S::Term) -> Result<SHACLPath, ResultError> {
+    if let Some(pred) = S::term_as_iri_s(term) {
+        return Ok(SHACLPath::iri(pred));
+    }
+    let object = |pred: &iri_s::IriS| -> Result<Option<S::Term>, ResultError> {
+        Ok(get_objects_for(store, term, &S::iri_s2iri(pred))?
+            .into_iter()
+            .next())
+    };
+    if object(&RDF_FIRST)?.is_some() {
+        let paths = parse_path_list(store, term)?;
+        return Ok(SHACLPath::Sequence { paths });
+    }
+    if let Some(list) = object(&SH_ALTERNATIVE_PATH)? {
+        let paths = parse_path_list(store, &list)?;
+        return Ok(SHACLPath::Alternative { paths });
+    }
+    let unary_paths: [(&iri_s::IriS, UnaryPath); 4] = [
+        (&SH_INVERSE_PATH, |path| SHACLPath::Inverse { path }),
+        (&SH_ZERO_OR_MORE_PATH, |path| SHACLPath::ZeroOrMore { path }),
+        (&SH_ONE_OR_MORE_PATH, |path| SHACLPath::OneOrMore { path }),
+        (&SH_ZERO_OR_ONE_PATH, |path| SHACLPath::ZeroOrOne { path }),
+    ];
+    for (pred, unary_path) in unary_paths {
+        if let Some(path) = object(pred)? {
+            return Ok(unary_path(Box::new(parse_path(store, &path)?)));
+        }
+    }
+    Err(ResultError::WrongPath(term.to_string()))
+}
+
+fn parse_path_list<S: SRDF>(store: &S, list: &S::Term) -> Result<Vec<SHACLPath>, ResultError> {
+    let mut paths = Vec::new();
+    let mut visited = HashSet::new();
+    let mut current = list.clone();
+    while S::term_as_iri_s(&current).as_ref() != Some(&*RDF_NIL) {
+        if !visited.insert(current.clone()) {
+            return Err(ResultError::WrongPath(list.to_string()));
+        }
+        let first = get_objects_for(store, &current, &S::iri_s2iri(&RDF_FIRST))?;
+        let rest = get_objects_for(store, &current, &S::iri_s2iri(&RDF_REST))?;
+        match (first.into_iter().next(), rest.into_iter().next()) {
+            (Some(first), Some(rest)) => {
+                paths.push(parse_path(store, &first)?);
+                current = rest;
+            }
+            _ => return Err(ResultError::WrongPath(list.to_string())),
+        }
+    }
+    Ok(paths)
+}
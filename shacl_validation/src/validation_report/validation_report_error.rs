@@ -8,12 +8,18 @@ pub enum ReportError {
     Srdf(#[from] SRDFError),
     #[error(transparent)]
     Result(#[from] ResultError),
+    #[error("Error serializing the ValidationReport to RDF, {}", _0)]
+    RDFSerialization(String),
+    #[error("Error serializing the ValidationReport to JSON, {}", _0)]
+    JSONSerialization(String),
 }
 
 #[derive(Error, Debug)]
 pub enum ResultError {
     #[error("Error parsing the ValidationResult, the {} field is missing", _0)]
     MissingRequiredField(String),
+    #[error("Error parsing the ValidationResult, {} is not a SHACL path", _0)]
+    WrongPath(String),
     #[error("Error parsing the ValidationResult, {}", _0)]
     Srdf(#[from] SRDFError),
 }
@@ -19,6 +19,11 @@ pub trait IterationStrategy<S: SRDFBasic> {
         &'a self,
         value_nodes: &'a ValueNodes<S>,
     ) -> Box<dyn Iterator<Item = (&'a S::Term, &'a Self::Item)> + 'a>;
+
+    /// The value node reported in a validation result for an item, if any
+    fn to_value(&self, _item: &Self::Item) -> Option<S::Term> {
+        None
+    }
 }
 
 pub struct FocusNodeIteration;
@@ -49,4 +54,8 @@ impl<S: SRDFBasic> IterationStrategy<S> for ValueNodeIteration {
                 .map(move |value_node| (focus_node, value_node))
         }))
     }
+
+    fn to_value(&self, item: &Self::Item) -> Option<S::Term> {
+        Some(item.clone())
+    }
 }
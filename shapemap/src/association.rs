@@ -19,6 +19,8 @@ impl Association {
         }
     }
 
+    /// Pairs of the node values contained explicitly in the node selector and the shape labels.
+    /// Triple patterns and queries don't contain node values, see [`NodeSelector::select`]
     pub fn iter_node_shape<S>(
         &self,
         rdf: &S,
@@ -12,7 +12,9 @@ pub mod shape_selector;
 pub mod shapemap;
 pub mod shapemap_config;
 pub mod shapemap_error;
+pub(crate) mod shapemap_json;
 pub mod shapemap_state;
+pub mod shapemap_vocab;
 pub mod validation_status;
 
 pub use association::*;
@@ -24,6 +26,7 @@ pub use shapemap::*;
 pub use shapemap_config::*;
 pub use shapemap_error::*;
 pub use shapemap_state::*;
+pub use shapemap_vocab::*;
 pub use validation_status::*;
 
 /// Format of Shapemap files
@@ -31,5 +34,10 @@ pub use validation_status::*;
 pub enum ShapeMapFormat {
     #[default]
     Compact,
+
+    /// [JSON representation](https://shexspec.github.io/shape-map/#json-representation) of ShapeMaps
     JSON,
+
+    /// Serialization of the internal representation, which can't be read back
+    Internal,
 }
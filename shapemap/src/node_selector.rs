@@ -1,10 +1,11 @@
 use iri_s::IriS;
-use prefixmap::IriRef;
+use prefixmap::{IriRef, PrefixMap};
 use serde_derive::Serialize;
 use shex_ast::{object_value::ObjectValue, Node};
 use srdf::literal::Literal;
 use srdf::shacl_path::SHACLPath;
-use srdf::SRDF;
+use srdf::{Object, QuerySRDF, SRDF};
+use std::collections::HashSet;
 use thiserror::Error;
 
 /// A NodeSelector following [ShapeMap spec](https://shexspec.github.io/shape-map/#shapemap-structure) can be used to select RDF Nodes
@@ -14,7 +15,7 @@ pub enum NodeSelector {
     Node(ObjectValue),
     TriplePattern {
         subject: Pattern,
-        pred: IriS,
+        pred: IriRef,
         object: Pattern,
     },
     TriplePatternPath {
@@ -48,57 +49,335 @@ impl NodeSelector {
         NodeSelector::Node(ObjectValue::prefixed(alias, local))
     }
 
+    pub fn sparql(query: &str) -> NodeSelector {
+        NodeSelector::Sparql {
+            query: query.to_string(),
+        }
+    }
+
+    /// Node values that this selector contains explicitly, which are only the ones of [`NodeSelector::Node`].
+    /// The nodes of triple patterns and queries depend on the RDF data, see [`NodeSelector::select`]
     pub fn iter_node<S>(&self, _rdf: &S) -> impl Iterator<Item = &ObjectValue>
     where
         S: SRDF,
     {
         match self {
-            NodeSelector::Node(value) => std::iter::once(value),
-            _ => todo!(),
+            NodeSelector::Node(value) => Some(value),
+            _ => None,
         }
+        .into_iter()
     }
-}
 
-#[derive(Debug, Error)]
-pub enum NodeSelectorError {}
-
-impl NodeSelect for NodeSelector {
-    fn select<S>(&self, _rdf: S) -> Result<Vec<S::Term>, NodeSelectorError>
+    /// Nodes selected by this selector in `rdf`.
+    /// Prefixed names are resolved with `prefixmap`, which is usually the nodes prefixmap of the shapemap,
+    /// and with the prefixmap of `rdf` when `prefixmap` doesn't contain their prefix.
+    /// SPARQL selectors need RDF data that can run queries, see [`NodeSelector::select_with_queries`]
+    pub fn select<S>(&self, rdf: &S, prefixmap: &PrefixMap) -> Result<Vec<Node>, NodeSelectorError>
     where
         S: SRDF,
     {
-        match self {
-            NodeSelector::Node(_node) => {
-                todo!()
+        let terms = match self {
+            NodeSelector::Node(ObjectValue::IriRef(iri_ref)) => {
+                return Ok(vec![Node::iri(resolve_iri_ref(iri_ref, prefixmap, rdf)?)])
+            }
+            NodeSelector::Node(ObjectValue::Literal(lit)) => {
+                return Ok(vec![Node::literal(lit.clone())])
             }
             NodeSelector::TriplePattern {
                 subject,
                 pred,
                 object,
-            } => match (subject, pred, object) {
-                (Pattern::Focus, _pred, Pattern::Wildcard) => todo!(),
-                (Pattern::Focus, _pred, Pattern::Node(_node)) => todo!(),
-                (Pattern::Wildcard, _pred, Pattern::Focus) => todo!(),
-                (Pattern::Node(_n), _pred, Pattern::Focus) => todo!(),
-                (_, _, _) => todo!(),
+            } => {
+                let pred = resolve_iri_ref(pred, prefixmap, rdf)?;
+                select_triple_pattern(subject, &S::iri_s2iri(&pred), object, rdf, prefixmap)?
+            }
+            NodeSelector::TriplePatternPath {
+                subject,
+                pred: SHACLPath::Predicate { pred },
+                object,
+            } => select_triple_pattern(subject, &S::iri_s2iri(pred), object, rdf, prefixmap)?,
+            NodeSelector::TriplePatternPath {
+                subject,
+                pred,
+                object,
+            } => match (subject, object) {
+                (Pattern::Node(value), Pattern::Focus) => {
+                    let start = object_value_as_term(value, prefixmap, rdf)?;
+                    path_nodes(vec![start], pred, false, rdf)?
+                }
+                (Pattern::Focus, Pattern::Node(value)) => {
+                    let start = object_value_as_term(value, prefixmap, rdf)?;
+                    path_nodes(vec![start], pred, true, rdf)?
+                }
+                (Pattern::Focus, Pattern::Wildcard) | (Pattern::Wildcard, Pattern::Focus) => {
+                    return Err(NodeSelectorError::WildcardPath {
+                        path: pred.to_string(),
+                    })
+                }
+                _ => return Err(NodeSelectorError::NoFocus),
             },
-            NodeSelector::TriplePatternPath { .. } => todo!(),
-            NodeSelector::Sparql { .. } => todo!(),
-            NodeSelector::Generic { .. } => todo!(),
+            NodeSelector::Sparql { query } => {
+                return Err(NodeSelectorError::QueriesNotSupported {
+                    query: query.clone(),
+                })
+            }
+            NodeSelector::Generic { iri, .. } => {
+                return Err(NodeSelectorError::UnknownSelector { iri: iri.clone() })
+            }
+        };
+        Ok(terms_as_nodes::<S>(terms))
+    }
+
+    /// Nodes selected by this selector in `rdf`, running the SPARQL queries of SPARQL selectors.
+    /// The nodes selected by a query are the values of its first variable
+    pub fn select_with_queries<S>(
+        &self,
+        rdf: &S,
+        prefixmap: &PrefixMap,
+    ) -> Result<Vec<Node>, NodeSelectorError>
+    where
+        S: SRDF + QuerySRDF,
+    {
+        match self {
+            NodeSelector::Sparql { query } => {
+                let solutions = rdf
+                    .query_select(query)
+                    .map_err(|e| NodeSelectorError::Query {
+                        query: query.clone(),
+                        error: e.to_string(),
+                    })?;
+                let terms = solutions
+                    .iter()
+                    .filter_map(|solution| solution.find_solution(0).cloned())
+                    .collect();
+                Ok(terms_as_nodes::<S>(terms))
+            }
+            _ => self.select(rdf, prefixmap),
         }
     }
 }
-#[derive(Debug, PartialEq, Clone, Serialize)]
 
+#[derive(Debug, Error)]
+pub enum NodeSelectorError {
+    #[error("Error resolving prefixed name {prefix}:{local}: {error}")]
+    ResolvingPrefixedName {
+        prefix: String,
+        local: String,
+        error: String,
+    },
+
+    #[error("Error obtaining the nodes selected by a triple pattern: {error}")]
+    RDFError { error: String },
+
+    #[error("Triple patterns must have FOCUS as subject or object")]
+    NoFocus,
+
+    #[error("Triple patterns with a wildcard and the SHACL path {path} are not supported")]
+    WildcardPath { path: String },
+
+    #[error("SPARQL node selectors need RDF data that can run queries. Query: {query}")]
+    QueriesNotSupported { query: String },
+
+    #[error("Error running SPARQL query to select nodes: {error}\nQuery: {query}")]
+    Query { query: String, error: String },
+
+    #[error("Unknown node selector {iri}")]
+    UnknownSelector { iri: IriS },
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
 pub enum Pattern {
-    Node(Node),
+    Node(ObjectValue),
     Wildcard,
     Focus,
 }
 
-#[allow(dead_code)]
-trait NodeSelect {
-    fn select<S>(&self, rdf: S) -> Result<Vec<S::Term>, NodeSelectorError>
-    where
-        S: SRDF;
+fn rdf_error<E: std::fmt::Display>(e: E) -> NodeSelectorError {
+    NodeSelectorError::RDFError {
+        error: e.to_string(),
+    }
+}
+
+/// Resolves a prefixed name with `prefixmap`, falling back to the prefixmap of the RDF data,
+/// so nodes like `:a` given without a shapemap prefixmap use the prefixes declared by the data
+fn resolve_iri_ref<S: SRDF>(
+    iri_ref: &IriRef,
+    prefixmap: &PrefixMap,
+    rdf: &S,
+) -> Result<IriS, NodeSelectorError> {
+    match iri_ref {
+        IriRef::Iri(iri) => Ok(iri.clone()),
+        IriRef::Prefixed { prefix, local } => prefixmap
+            .resolve_prefix_local(prefix, local)
+            .or_else(|e| rdf.resolve_prefix_local(prefix, local).map_err(|_| e))
+            .map_err(|e| NodeSelectorError::ResolvingPrefixedName {
+                prefix: prefix.clone(),
+                local: local.clone(),
+                error: e.to_string(),
+            }),
+    }
+}
+
+fn object_value_as_term<S: SRDF>(
+    value: &ObjectValue,
+    prefixmap: &PrefixMap,
+    rdf: &S,
+) -> Result<S::Term, NodeSelectorError> {
+    match value {
+        ObjectValue::IriRef(iri_ref) => {
+            Ok(S::iri_s2term(&resolve_iri_ref(iri_ref, prefixmap, rdf)?))
+        }
+        ObjectValue::Literal(lit) => {
+            S::object_as_term(&Object::literal(lit.clone())).map_err(|e| {
+                NodeSelectorError::RDFError {
+                    error: e.to_string(),
+                }
+            })
+        }
+    }
+}
+
+/// Nodes that match `FOCUS` in a triple pattern, which must appear once as subject or object
+fn select_triple_pattern<S: SRDF>(
+    subject: &Pattern,
+    pred: &S::IRI,
+    object: &Pattern,
+    rdf: &S,
+    prefixmap: &PrefixMap,
+) -> Result<Vec<S::Term>, NodeSelectorError> {
+    match (subject, object) {
+        (Pattern::Focus, Pattern::Wildcard) => {
+            let triples = rdf.triples_with_predicate(pred).map_err(rdf_error)?;
+            Ok(triples
+                .iter()
+                .map(|triple| S::subject_as_term(&triple.subj()))
+                .collect())
+        }
+        (Pattern::Focus, Pattern::Node(value)) => {
+            let object = object_value_as_term(value, prefixmap, rdf)?;
+            let subjects = rdf
+                .subjects_with_predicate_object(pred, &object)
+                .map_err(rdf_error)?;
+            Ok(subjects.iter().map(S::subject_as_term).collect())
+        }
+        (Pattern::Wildcard, Pattern::Focus) => {
+            let triples = rdf.triples_with_predicate(pred).map_err(rdf_error)?;
+            Ok(triples.iter().map(|triple| triple.obj()).collect())
+        }
+        (Pattern::Node(value), Pattern::Focus) => {
+            let subject = object_value_as_term(value, prefixmap, rdf)?;
+            match S::term_as_subject(&subject) {
+                // Literals can't be subjects so they don't select any node
+                None => Ok(Vec::new()),
+                Some(subject) => {
+                    let objects = rdf
+                        .objects_for_subject_predicate(&subject, pred)
+                        .map_err(rdf_error)?;
+                    Ok(objects.into_iter().collect())
+                }
+            }
+        }
+        _ => Err(NodeSelectorError::NoFocus),
+    }
+}
+
+/// Nodes reached from `nodes` following `path`, or following it backwards if `inverse` is true
+fn path_nodes<S: SRDF>(
+    nodes: Vec<S::Term>,
+    path: &SHACLPath,
+    inverse: bool,
+    rdf: &S,
+) -> Result<Vec<S::Term>, NodeSelectorError> {
+    match path {
+        SHACLPath::Predicate { pred } => {
+            let pred = S::iri_s2iri(pred);
+            let mut result = Vec::new();
+            for node in nodes {
+                if inverse {
+                    let subjects = rdf
+                        .subjects_with_predicate_object(&pred, &node)
+                        .map_err(rdf_error)?;
+                    result.extend(subjects.iter().map(S::subject_as_term))
+                } else if let Some(subject) = S::term_as_subject(&node) {
+                    let objects = rdf
+                        .objects_for_subject_predicate(&subject, &pred)
+                        .map_err(rdf_error)?;
+                    result.extend(objects)
+                }
+            }
+            Ok(dedup(result))
+        }
+        SHACLPath::Inverse { path } => path_nodes(nodes, path, !inverse, rdf),
+        SHACLPath::Sequence { paths } => {
+            let mut current = nodes;
+            let steps: Vec<&SHACLPath> = if inverse {
+                paths.iter().rev().collect()
+            } else {
+                paths.iter().collect()
+            };
+            for step in steps {
+                current = path_nodes(current, step, inverse, rdf)?;
+            }
+            Ok(current)
+        }
+        SHACLPath::Alternative { paths } => {
+            let mut result = Vec::new();
+            for alternative in paths {
+                result.extend(path_nodes(nodes.clone(), alternative, inverse, rdf)?)
+            }
+            Ok(dedup(result))
+        }
+        SHACLPath::ZeroOrOne { path } => {
+            let mut result = nodes.clone();
+            result.extend(path_nodes(nodes, path, inverse, rdf)?);
+            Ok(dedup(result))
+        }
+        SHACLPath::ZeroOrMore { path } => {
+            let mut result = nodes.clone();
+            result.extend(path_closure(nodes, path, inverse, rdf)?);
+            Ok(dedup(result))
+        }
+        SHACLPath::OneOrMore { path } => path_closure(nodes, path, inverse, rdf),
+    }
+}
+
+/// Nodes reached from `nodes` following `path` one or more times
+fn path_closure<S: SRDF>(
+    nodes: Vec<S::Term>,
+    path: &SHACLPath,
+    inverse: bool,
+    rdf: &S,
+) -> Result<Vec<S::Term>, NodeSelectorError> {
+    let mut visited = HashSet::new();
+    let mut result = Vec::new();
+    let mut pending = path_nodes(nodes, path, inverse, rdf)?;
+    while !pending.is_empty() {
+        let new_nodes: Vec<S::Term> = pending
+            .into_iter()
+            .filter(|node| visited.insert(node.clone()))
+            .collect();
+        result.extend(new_nodes.iter().cloned());
+        pending = if new_nodes.is_empty() {
+            Vec::new()
+        } else {
+            path_nodes(new_nodes, path, inverse, rdf)?
+        };
+    }
+    Ok(result)
+}
+
+/// Removes the repeated nodes keeping the order in which they were found
+fn dedup<T: Clone + Eq + std::hash::Hash>(nodes: Vec<T>) -> Vec<T> {
+    let mut seen = HashSet::new();
+    nodes
+        .into_iter()
+        .filter(|node| seen.insert(node.clone()))
+        .collect()
+}
+
+fn terms_as_nodes<S: SRDF>(terms: Vec<S::Term>) -> Vec<Node> {
+    dedup(terms)
+        .iter()
+        .map(|term| Node::from(S::term_as_object(term)))
+        .collect()
 }
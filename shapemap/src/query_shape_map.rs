@@ -1,9 +1,13 @@
 use std::fmt::Display;
 
-use crate::{Association, NodeSelector, ShapeSelector};
-use prefixmap::PrefixMap;
+use crate::shapemap_json::*;
+use crate::{Association, NodeSelector, ShapeSelector, ShapemapError};
+use prefixmap::{IriRef, PrefixMap};
 use serde_derive::Serialize;
-use shex_ast::{object_value::ObjectValue, ShapeExprLabel};
+use serde_json::{Map, Value};
+use shex_ast::object_value::ObjectValue;
+use shex_ast::{Node, ShapeExprLabel};
+use srdf::literal::Literal;
 use srdf::SRDF;
 
 #[derive(Debug, Default, PartialEq, Clone, Serialize)]
@@ -45,15 +49,109 @@ impl QueryShapeMap {
         self.associations.iter()
     }
 
+    /// Pairs of the node values and shape labels of the associations whose node selectors contain node values explicitly,
+    /// see [`Association::iter_node_shape`]
     pub fn iter_node_shape<'a, S>(
         &'a self,
         rdf: &'a S,
-    ) -> impl Iterator<Item = (&ObjectValue, &ShapeExprLabel)> + 'a
+    ) -> impl Iterator<Item = (&'a ObjectValue, &'a ShapeExprLabel)> + 'a
     where
         S: SRDF,
     {
         self.iter().flat_map(|assoc| assoc.iter_node_shape(rdf))
     }
+
+    /// Checks if some node selector is a SPARQL query, which needs RDF data that can run queries
+    pub fn has_sparql_selectors(&self) -> bool {
+        self.associations
+            .iter()
+            .any(|association| matches!(association.node_selector, NodeSelector::Sparql { .. }))
+    }
+
+    /// Reads a shapemap from its [JSON representation](https://shexspec.github.io/shape-map/#json-representation).
+    ///
+    /// The status, reason and application information of the associations are ignored,
+    /// so the results of a validation serialized as JSON can be used as a query shapemap
+    pub fn from_json(value: &Value) -> Result<QueryShapeMap, ShapemapError> {
+        let mut shapemap = QueryShapeMap::new();
+        for association in json_associations(value)? {
+            let node = parse_node(association.node)?;
+            let shape_selector = parse_shape_selector(association.shape)?;
+            shapemap.add_association(
+                NodeSelector::Node(node_as_object_value(node)?),
+                shape_selector,
+            );
+        }
+        Ok(shapemap)
+    }
+
+    /// Reads a shapemap from a string with its JSON representation (see [`QueryShapeMap::from_json`])
+    pub fn from_json_str(str: &str) -> Result<QueryShapeMap, ShapemapError> {
+        let value = serde_json::from_str(str).map_err(|e| parse_error(e.to_string()))?;
+        Self::from_json(&value)
+    }
+
+    /// Returns the shapemap following its [JSON representation](https://shexspec.github.io/shape-map/#json-representation).
+    ///
+    /// Prefixed names are resolved with the prefixmaps of the shapemap.
+    /// Only node selectors that are single nodes can be represented in JSON
+    pub fn as_json(&self) -> Result<Value, ShapemapError> {
+        let associations = self
+            .associations
+            .iter()
+            .map(|association| {
+                let node = match &association.node_selector {
+                    NodeSelector::Node(ObjectValue::IriRef(iri_ref)) => Value::String(
+                        resolve_iri_ref(iri_ref, &self.nodes_prefixmap)?
+                            .as_str()
+                            .to_string(),
+                    ),
+                    NodeSelector::Node(ObjectValue::Literal(Literal::DatatypeLiteral {
+                        lexical_form,
+                        datatype,
+                    })) => json_literal(&Literal::datatype(
+                        lexical_form,
+                        &IriRef::iri(resolve_iri_ref(datatype, &self.nodes_prefixmap)?),
+                    )),
+                    NodeSelector::Node(ObjectValue::Literal(lit)) => json_literal(lit),
+                    other => {
+                        return Err(ShapemapError::JSONSerialization {
+                            error: format!(
+                                "Node selector {other:?} can't be represented in JSON shapemaps"
+                            ),
+                        })
+                    }
+                };
+                let mut map = Map::new();
+                map.insert("node".to_string(), node);
+                map.insert(
+                    "shape".to_string(),
+                    json_shape_selector(&association.shape_selector, &self.shapes_prefixmap)?,
+                );
+                Ok(Value::Object(map))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Value::Array(associations))
+    }
+
+    /// Serializes the shapemap as a pretty printed JSON string (see [`QueryShapeMap::as_json`])
+    pub fn as_json_string(&self) -> Result<String, ShapemapError> {
+        serde_json::to_string_pretty(&self.as_json()?).map_err(|e| {
+            ShapemapError::JSONSerialization {
+                error: e.to_string(),
+            }
+        })
+    }
+}
+
+fn node_as_object_value(node: Node) -> Result<ObjectValue, ShapemapError> {
+    match node.as_object() {
+        srdf::Object::Iri(iri) => Ok(ObjectValue::iri(iri.clone())),
+        srdf::Object::Literal(lit) => Ok(ObjectValue::literal(lit.clone())),
+        other => Err(parse_error(format!(
+            "Node selectors of shapemaps can't be {other}, only IRIs and literals"
+        ))),
+    }
 }
 
 impl Display for QueryShapeMap {
@@ -65,3 +163,66 @@ impl Display for QueryShapeMap {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iri_s::IriS;
+    use serde_json::json;
+    use srdf::lang::Lang;
+
+    #[test]
+    fn test_query_shape_map_json() {
+        let input = json!([
+            {"node": "http://example.org/alice", "shape": "http://example.org/Person"},
+            {"node": {"value": "1", "type": "http://www.w3.org/2001/XMLSchema#integer"}, "shape": "START"},
+            {"node": {"@value": "hi", "@language": "en"}, "shape": "_:S", "status": "conformant", "reason": "ok"}
+        ]);
+        let shapemap = QueryShapeMap::from_json(&input).unwrap();
+        let mut expected = QueryShapeMap::new();
+        expected.add_association(
+            NodeSelector::iri_unchecked("http://example.org/alice"),
+            ShapeSelector::iri_unchecked("http://example.org/Person"),
+        );
+        expected.add_association(
+            NodeSelector::literal(Literal::integer(1)),
+            ShapeSelector::Start,
+        );
+        expected.add_association(
+            NodeSelector::literal(Literal::lang_str("hi", Lang::new("en"))),
+            ShapeSelector::Label(shex_ast::ShapeExprLabel::bnode(shex_ast::BNode::new("S"))),
+        );
+        assert_eq!(shapemap, expected);
+
+        let output = json!([
+            {"node": "http://example.org/alice", "shape": "http://example.org/Person"},
+            {"node": {"value": "1", "type": "http://www.w3.org/2001/XMLSchema#integer"}, "shape": "START"},
+            {"node": {"value": "hi", "language": "en"}, "shape": "_:S"}
+        ]);
+        assert_eq!(shapemap.as_json().unwrap(), output);
+    }
+
+    #[test]
+    fn test_query_shape_map_json_prefixed() {
+        let mut prefixmap = PrefixMap::new();
+        prefixmap
+            .insert("", &IriS::new_unchecked("http://example.org/"))
+            .unwrap();
+        let mut shapemap = QueryShapeMap::new()
+            .with_nodes_prefixmap(&prefixmap)
+            .with_shapes_prefixmap(&prefixmap);
+        shapemap.add_association(
+            NodeSelector::prefixed("", "alice"),
+            ShapeSelector::prefixed("", "Person"),
+        );
+        assert_eq!(
+            shapemap.as_json().unwrap(),
+            json!([{"node": "http://example.org/alice", "shape": "http://example.org/Person"}])
+        );
+        shapemap.add_association(NodeSelector::sparql("select ?x {}"), ShapeSelector::Start);
+        assert!(matches!(
+            shapemap.as_json(),
+            Err(ShapemapError::JSONSerialization { .. })
+        ));
+    }
+}
@@ -1,6 +1,11 @@
 use colored::*;
-use srdf::Object;
+use iri_s::IriS;
+use serde_json::{Map, Value};
+use srdf::literal::Literal;
+use srdf::{Object, RDFFormat, SRDFBuilder, SRDFGraph};
 
+use crate::shapemap_json::*;
+use crate::shapemap_vocab::*;
 use crate::ShapemapConfig;
 use crate::ShapemapError;
 use crate::ValidationStatus;
@@ -10,6 +15,7 @@ use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::io::Write;
 
 /// Contains a map of the results obtained after applying ShEx validation
 #[derive(Debug, PartialEq, Default, Clone)]
@@ -65,128 +71,46 @@ impl ResultShapeMap {
         self
     }
 
+    /// Adds the status of a node and a shape.
+    ///
+    /// If the map already contains a status for them, a pending status is replaced by the new one
+    /// and conformant statuses are merged, but conflicting statuses are an error
     pub fn add_result(
         &mut self,
         node: Node,
         shape_label: ShapeLabel,
         status: ValidationStatus,
     ) -> Result<(), ShapemapError> {
-        let _cn = node.clone();
-        let _sl = shape_label.clone();
-        match self.result.entry(node) {
+        let map = self.result.entry(node.clone()).or_default();
+        match map.entry(shape_label.clone()) {
             Entry::Occupied(mut c) => {
-                let map = c.get_mut();
-                match map.entry(shape_label) {
-                    Entry::Occupied(mut c) => {
-                        let cell_status = c.get_mut();
-                        match (cell_status.clone(), status) {
-                            (
-                                ValidationStatus::Conformant(conformant_info),
-                                ValidationStatus::Conformant(conformant_info2),
-                            ) => {
-                                *cell_status = ValidationStatus::Conformant(
-                                    conformant_info.merge(conformant_info2),
-                                )
-                            }
-                            (
-                                ValidationStatus::Conformant(_conformant_info),
-                                ValidationStatus::NonConformant(_non_conformant_info),
-                            ) => todo!(),
-                            (
-                                ValidationStatus::Conformant(_conformant_info),
-                                ValidationStatus::Pending,
-                            ) => {}
-                            (
-                                ValidationStatus::Conformant(_conformant_info),
-                                ValidationStatus::Inconsistent(
-                                    _conformant_info2,
-                                    _non_conformant_info2,
-                                ),
-                            ) => todo!(),
-                            (
-                                ValidationStatus::NonConformant(_non_conformant_info),
-                                ValidationStatus::Conformant(_conformant_info),
-                            ) => todo!(),
-                            (
-                                ValidationStatus::NonConformant(_non_conformant_info),
-                                ValidationStatus::NonConformant(_non_conformant_info2),
-                            ) => {}
-                            (
-                                ValidationStatus::NonConformant(_non_conformant_info),
-                                ValidationStatus::Pending,
-                            ) => {}
-                            (
-                                ValidationStatus::NonConformant(_non_conformant_info),
-                                ValidationStatus::Inconsistent(
-                                    _conformant_info2,
-                                    _non_conformant_info2,
-                                ),
-                            ) => todo!(),
-                            (
-                                ValidationStatus::Pending,
-                                ValidationStatus::Conformant(conformant_info),
-                            ) => *cell_status = ValidationStatus::Conformant(conformant_info),
-                            (
-                                ValidationStatus::Pending,
-                                ValidationStatus::NonConformant(non_conformant_info),
-                            ) => {
-                                *cell_status = ValidationStatus::NonConformant(non_conformant_info)
-                            }
-                            (ValidationStatus::Pending, ValidationStatus::Pending) => {}
-                            (
-                                ValidationStatus::Pending,
-                                ValidationStatus::Inconsistent(
-                                    _conformant_info,
-                                    _non_conformant_info,
-                                ),
-                            ) => todo!(),
-                            (
-                                ValidationStatus::Inconsistent(
-                                    _conformant_info,
-                                    _non_conformant_info,
-                                ),
-                                ValidationStatus::Conformant(_conformant_info2),
-                            ) => todo!(),
-                            (
-                                ValidationStatus::Inconsistent(
-                                    _conformant_info,
-                                    _non_conformant_info,
-                                ),
-                                ValidationStatus::NonConformant(_non_conformant_info2),
-                            ) => todo!(),
-                            (
-                                ValidationStatus::Inconsistent(
-                                    _conformant_info,
-                                    _non_conformant_info,
-                                ),
-                                ValidationStatus::Pending,
-                            ) => todo!(),
-                            (
-                                ValidationStatus::Inconsistent(
-                                    _conformant_info,
-                                    _non_conformant_info,
-                                ),
-                                ValidationStatus::Inconsistent(
-                                    _conformant_info2,
-                                    _non_conformant_info2,
-                                ),
-                            ) => todo!(),
-                        };
-                        Ok(())
+                let cell_status = c.get_mut();
+                match (cell_status.clone(), status) {
+                    (
+                        ValidationStatus::Conformant(conformant_info),
+                        ValidationStatus::Conformant(conformant_info2),
+                    ) => {
+                        *cell_status =
+                            ValidationStatus::Conformant(conformant_info.merge(conformant_info2))
                     }
-                    Entry::Vacant(v) => {
-                        v.insert(status);
-                        Ok(())
+                    (ValidationStatus::Pending, status) => *cell_status = status,
+                    (_, ValidationStatus::Pending) => {}
+                    (ValidationStatus::NonConformant(_), ValidationStatus::NonConformant(_)) => {}
+                    (old_status, new_status) if old_status == new_status => {}
+                    (old_status, new_status) => {
+                        return Err(ShapemapError::InconsistentStatus {
+                            node: Box::new(node),
+                            label: Box::new(shape_label),
+                            old_status: Box::new(old_status),
+                            new_status: Box::new(new_status),
+                        })
                     }
                 }
             }
             Entry::Vacant(v) => {
-                let mut map = HashMap::new();
-                map.insert(shape_label, status);
-                v.insert(map);
-                Ok(())
+                v.insert(status);
             }
-        }?;
+        }
         Ok(())
     }
 
@@ -204,6 +128,197 @@ impl ResultShapeMap {
                 .map(move |(shape, status)| (node, shape, status))
         })
     }
+
+    /// Iterates over the results sorted by node and shape label,
+    /// so the serializations do not depend on the order of the internal map
+    fn sorted_iter(&self) -> Vec<(&Node, &ShapeLabel, &ValidationStatus)> {
+        let mut results: Vec<_> = self.iter().collect();
+        results.sort_by_key(|(node, label, _)| (node.to_string(), label.to_string()));
+        results
+    }
+
+    /// Returns the results following the [JSON representation](https://shexspec.github.io/shape-map/#json-representation)
+    /// of result shape maps
+    pub fn as_json(&self) -> Value {
+        let associations = self
+            .sorted_iter()
+            .into_iter()
+            .map(|(node, label, status)| {
+                let mut association = Map::new();
+                association.insert("node".to_string(), json_node(node));
+                association.insert("shape".to_string(), json_shape_label(label));
+                association.insert(
+                    "status".to_string(),
+                    Value::String(status.code().to_string()),
+                );
+                if let Some(reason) = status.reason() {
+                    association.insert("reason".to_string(), Value::String(reason));
+                }
+                if let Some(app_info) = status.app_info() {
+                    association.insert("appinfo".to_string(), app_info);
+                }
+                Value::Object(association)
+            })
+            .collect();
+        Value::Array(associations)
+    }
+
+    /// Serializes the results as a pretty printed JSON string
+    pub fn as_json_string(&self) -> Result<String, ShapemapError> {
+        serde_json::to_string_pretty(&self.as_json()).map_err(|e| {
+            ShapemapError::JSONSerialization {
+                error: e.to_string(),
+            }
+        })
+    }
+
+    /// Reads the results from their [JSON representation](https://shexspec.github.io/shape-map/#json-representation).
+    ///
+    /// Associations without status are considered conformant, as in fixed shape maps
+    pub fn from_json(value: &Value) -> Result<ResultShapeMap, ShapemapError> {
+        let mut result = ResultShapeMap::new();
+        for association in json_associations(value)? {
+            let node = parse_node(association.node)?;
+            let label = parse_shape_label(association.shape)?;
+            let reason = association.reason.unwrap_or_default().to_string();
+            let app_info = association.app_info.cloned().unwrap_or(Value::Null);
+            let status = match association.status.unwrap_or("conformant") {
+                "conformant" => ValidationStatus::conformant(reason, app_info),
+                "nonconformant" => ValidationStatus::non_conformant(reason, app_info),
+                "pending" => ValidationStatus::pending(),
+                "inconsistent" => {
+                    // The JSON representation keeps the application information of both results
+                    let (conformant_value, non_conformant_value) = match app_info {
+                        Value::Array(values) if values.len() == 2 => {
+                            (values[0].clone(), values[1].clone())
+                        }
+                        other => (other.clone(), other),
+                    };
+                    ValidationStatus::inconsistent(
+                        reason.clone(),
+                        conformant_value,
+                        reason,
+                        non_conformant_value,
+                    )
+                }
+                other => {
+                    return Err(parse_error(format!(
+                        "Unknown status {other} for node {node} and shape {label}"
+                    )))
+                }
+            };
+            result.add_result(node, label, status)?;
+        }
+        Ok(result)
+    }
+
+    /// Reads the results from a string with their JSON representation (see [`ResultShapeMap::from_json`])
+    pub fn from_json_str(str: &str) -> Result<ResultShapeMap, ShapemapError> {
+        let value = serde_json::from_str(str).map_err(|e| parse_error(e.to_string()))?;
+        Self::from_json(&value)
+    }
+
+    /// Returns an RDF graph with the results.
+    ///
+    /// The graph contains a node of type `sm:ResultShapeMap` linked by `sm:association` to
+    /// one node per association with its `sm:node`, `sm:shape`, `sm:status`, `sm:reason` and `sm:appinfo`
+    pub fn as_rdf<RDF>(&self) -> Result<RDF, RDF::Err>
+    where
+        RDF: SRDFBuilder,
+    {
+        let mut rdf = RDF::empty();
+        rdf.add_prefix_map(self.rdf_prefixmap())?;
+        let result_subject = RDF::bnode_as_subject(rdf.add_bnode()?);
+        rdf.add_type(
+            &RDF::subject_as_object(&result_subject),
+            RDF::iri_s2term(&IriS::new_unchecked(SHAPEMAP_RESULT_SHAPE_MAP_STR)),
+        )?;
+        for (node, label, status) in self.sorted_iter() {
+            let association = RDF::bnode_as_subject(rdf.add_bnode()?);
+            rdf.add_triple(
+                &result_subject,
+                &RDF::iri_s2iri(&IriS::new_unchecked(SHAPEMAP_HAS_ASSOCIATION_STR)),
+                &RDF::subject_as_term(&association),
+            )?;
+            rdf.add_type(
+                &RDF::subject_as_object(&association),
+                RDF::iri_s2term(&IriS::new_unchecked(SHAPEMAP_ASSOCIATION_STR)),
+            )?;
+            rdf.add_triple(
+                &association,
+                &RDF::iri_s2iri(&IriS::new_unchecked(SHAPEMAP_NODE_STR)),
+                &RDF::object_as_term(node.as_object())?,
+            )?;
+            rdf.add_triple(
+                &association,
+                &RDF::iri_s2iri(&IriS::new_unchecked(SHAPEMAP_SHAPE_STR)),
+                &RDF::object_as_term(&rdf_shape_label(label))?,
+            )?;
+            rdf.add_triple(
+                &association,
+                &RDF::iri_s2iri(&IriS::new_unchecked(SHAPEMAP_STATUS_STR)),
+                &RDF::iri_s2term(&rdf_status(status)),
+            )?;
+            if let Some(reason) = status.reason() {
+                rdf.add_triple(
+                    &association,
+                    &RDF::iri_s2iri(&IriS::new_unchecked(SHAPEMAP_REASON_STR)),
+                    &RDF::object_as_term(&Object::Literal(Literal::str(&reason)))?,
+                )?;
+            }
+            if let Some(app_info) = status.app_info() {
+                rdf.add_triple(
+                    &association,
+                    &RDF::iri_s2iri(&IriS::new_unchecked(SHAPEMAP_APPINFO_STR)),
+                    &RDF::object_as_term(&Object::Literal(Literal::str(&app_info.to_string())))?,
+                )?;
+            }
+        }
+        Ok(rdf)
+    }
+
+    /// Serializes the results as RDF in the given format (see [`ResultShapeMap::as_rdf`])
+    pub fn serialize_rdf<W: Write>(
+        &self,
+        format: &RDFFormat,
+        writer: &mut W,
+    ) -> Result<(), ShapemapError> {
+        let rdf_error = |e: srdf::SRDFGraphError| ShapemapError::RDFSerialization {
+            error: e.to_string(),
+        };
+        let rdf: SRDFGraph = self.as_rdf().map_err(rdf_error)?;
+        rdf.serialize(format, writer).map_err(rdf_error)
+    }
+
+    fn rdf_prefixmap(&self) -> PrefixMap {
+        let mut prefixmap = self.nodes_prefixmap();
+        let shapes_prefixmap = self.shapes_prefixmap();
+        let sm = IriS::new_unchecked(SHAPEMAP_STR);
+        for (alias, iri) in shapes_prefixmap.iter().chain([(&"sm".to_string(), &sm)]) {
+            if !prefixmap.map.contains_key(alias) {
+                let _ = prefixmap.insert(alias, iri);
+            }
+        }
+        prefixmap
+    }
+}
+
+fn rdf_shape_label(label: &ShapeLabel) -> Object {
+    match label {
+        ShapeLabel::Iri(iri) => Object::Iri(iri.clone()),
+        ShapeLabel::BNode(bnode) => Object::BlankNode(bnode.value().to_string()),
+        ShapeLabel::Start => Object::Iri(IriS::new_unchecked(SHAPEMAP_START_STR)),
+    }
+}
+
+fn rdf_status(status: &ValidationStatus) -> IriS {
+    let iri = match status {
+        ValidationStatus::Conformant(_) => SHAPEMAP_CONFORMANT_STR,
+        ValidationStatus::NonConformant(_) => SHAPEMAP_NONCONFORMANT_STR,
+        ValidationStatus::Pending => SHAPEMAP_PENDING_STR,
+        ValidationStatus::Inconsistent(_, _) => SHAPEMAP_INCONSISTENT_STR,
+    };
+    IriS::new_unchecked(iri)
 }
 
 fn show_node(node: &Node, prefixmap: &PrefixMap) -> String {
@@ -263,3 +378,133 @@ impl Display for ResultShapeMap {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iri_s::iri;
+    use serde_json::json;
+    use srdf::{SRDFBasic, SRDF};
+
+    fn example_result() -> ResultShapeMap {
+        let mut result = ResultShapeMap::new();
+        result
+            .add_result(
+                Node::iri(iri!("http://example.org/alice")),
+                ShapeLabel::iri(iri!("http://example.org/Person")),
+                ValidationStatus::conformant("Shape passed".to_string(), json!({"n": 1})),
+            )
+            .unwrap();
+        result
+            .add_result(
+                Node::iri(iri!("http://example.org/bob")),
+                ShapeLabel::Start,
+                ValidationStatus::non_conformant("Missing name".to_string(), Value::Null),
+            )
+            .unwrap();
+        result
+    }
+
+    #[test]
+    fn test_result_shape_map_as_json() {
+        let expected = json!([
+            {
+                "node": "http://example.org/alice",
+                "shape": "http://example.org/Person",
+                "status": "conformant",
+                "reason": "Shape passed",
+                "appinfo": {"n": 1}
+            },
+            {
+                "node": "http://example.org/bob",
+                "shape": "START",
+                "status": "nonconformant",
+                "reason": "Missing name"
+            }
+        ]);
+        assert_eq!(example_result().as_json(), expected)
+    }
+
+    #[test]
+    fn test_result_shape_map_from_json() {
+        let result = example_result();
+        let json = result.as_json_string().unwrap();
+        assert_eq!(ResultShapeMap::from_json_str(&json).unwrap(), result);
+
+        let err = ResultShapeMap::from_json(&json!([
+            {"node": "http://example.org/alice", "shape": "START", "status": "unknown"}
+        ]));
+        assert!(matches!(err, Err(ShapemapError::JSONParsing { .. })));
+    }
+
+    #[test]
+    fn test_result_shape_map_from_json_conflicting_statuses() {
+        let err = ResultShapeMap::from_json(&json!([
+            {"node": "http://example.org/a", "shape": "http://example.org/S", "status": "conformant"},
+            {"node": "http://example.org/a", "shape": "http://example.org/S", "status": "nonconformant"}
+        ]));
+        assert!(matches!(err, Err(ShapemapError::InconsistentStatus { .. })));
+    }
+
+    #[test]
+    fn test_result_shape_map_from_json_inconsistent() {
+        let result = ResultShapeMap::from_json(&json!([
+            {"node": "http://example.org/a", "shape": "http://example.org/S", "status": "pending"},
+            {"node": "http://example.org/a", "shape": "http://example.org/S", "status": "inconsistent"}
+        ]))
+        .unwrap();
+        let status = result
+            .get_info(
+                &Node::iri(iri!("http://example.org/a")),
+                &ShapeLabel::iri(iri!("http://example.org/S")),
+            )
+            .unwrap();
+        assert_eq!(status.code(), "inconsistent");
+
+        let err = ResultShapeMap::from_json(&json!([
+            {"node": "http://example.org/a", "shape": "http://example.org/S", "status": "conformant"},
+            {"node": "http://example.org/a", "shape": "http://example.org/S", "status": "inconsistent"}
+        ]));
+        assert!(matches!(err, Err(ShapemapError::InconsistentStatus { .. })));
+    }
+
+    #[test]
+    fn test_result_shape_map_as_rdf() {
+        let mut output = Vec::new();
+        example_result()
+            .serialize_rdf(&RDFFormat::NTriples, &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), 14);
+        assert!(output.contains("<http://example.org/alice> ."));
+        assert!(output.contains(&format!(
+            "<{SHAPEMAP_STATUS_STR}> <{SHAPEMAP_NONCONFORMANT_STR}> ."
+        )));
+        assert!(output.contains(&format!("<{SHAPEMAP_SHAPE_STR}> <{SHAPEMAP_START_STR}> .")));
+    }
+
+    #[test]
+    fn test_result_shape_map_as_rdf_blank_nodes() {
+        // The labels of the blank nodes of the results must not clash with the nodes of the graph
+        let mut result = ResultShapeMap::new();
+        for label in ["result", "a0"] {
+            result
+                .add_result(
+                    Node::bnode(label),
+                    ShapeLabel::Start,
+                    ValidationStatus::conformant("Shape passed".to_string(), Value::Null),
+                )
+                .unwrap();
+        }
+        let rdf: SRDFGraph = result.as_rdf().unwrap();
+        let associations = rdf
+            .subjects_with_predicate_object(
+                &SRDFGraph::iri_s2iri(&IriS::new_unchecked(SHAPEMAP_NODE_STR)),
+                &SRDFGraph::bnode_id2term("result"),
+            )
+            .unwrap();
+        assert_eq!(associations.len(), 1);
+        assert!(!associations.contains(&SRDFGraph::bnode_id2subject("result")));
+        assert!(!associations.contains(&SRDFGraph::bnode_id2subject("a0")));
+    }
+}
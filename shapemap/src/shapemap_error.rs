@@ -7,9 +7,18 @@ use crate::ValidationStatus;
 pub enum ShapemapError {
     #[error("Trying to create an inconsistent status on node {node} and shape {label}. Old status: {old_status}, new status: {new_status}")]
     InconsistentStatus {
-        node: Node,
-        label: ShapeLabel,
-        old_status: ValidationStatus,
-        new_status: ValidationStatus,
+        node: Box<Node>,
+        label: Box<ShapeLabel>,
+        old_status: Box<ValidationStatus>,
+        new_status: Box<ValidationStatus>,
     },
+
+    #[error("Error serializing shape map to JSON: {error}")]
+    JSONSerialization { error: String },
+
+    #[error("Error parsing JSON shape map: {error}")]
+    JSONParsing { error: String },
+
+    #[error("Error serializing result shape map to RDF: {error}")]
+    RDFSerialization { error: String },
 }
@@ -0,0 +1,240 @@
+//! Conversion of nodes and shapes from and to the [JSON representation](https://shexspec.github.io/shape-map/#json-representation)
+//! of ShapeMaps.
+//!
+//! IRIs are represented as strings, blank nodes as strings starting with `_:` and
+//! literals as objects with a `value` and an optional `type` or `language`,
+//! like the object literals of ShExJ. The start shape is represented as `START`.
+use iri_s::IriS;
+use prefixmap::{IriRef, PrefixMap};
+use serde_json::{Map, Value};
+use shex_ast::{compiled::shape_label::ShapeLabel, BNode, Node, ShapeExprLabel};
+use srdf::lang::Lang;
+use srdf::literal::Literal;
+use srdf::numeric_literal::NumericLiteral;
+use srdf::{Object, XSD_BOOLEAN_STR, XSD_DECIMAL_STR, XSD_DOUBLE_STR, XSD_INTEGER_STR};
+use std::str::FromStr;
+
+use crate::{ShapeSelector, ShapemapError};
+
+const START: &str = "START";
+
+/// Fields of an association of a JSON ShapeMap
+pub(crate) struct JsonAssociation<'a> {
+    pub node: &'a Value,
+    pub shape: &'a Value,
+    pub status: Option<&'a str>,
+    pub reason: Option<&'a str>,
+    pub app_info: Option<&'a Value>,
+}
+
+/// Parses a JSON ShapeMap, which must be an array of associations
+pub(crate) fn json_associations(value: &Value) -> Result<Vec<JsonAssociation<'_>>, ShapemapError> {
+    let associations = value
+        .as_array()
+        .ok_or_else(|| parse_error(format!("Expected an array of associations, found {value}")))?;
+    associations
+        .iter()
+        .map(|association| {
+            let fields = association.as_object().ok_or_else(|| {
+                parse_error(format!(
+                    "Expected an association object, found {association}"
+                ))
+            })?;
+            let field = |name: &str| {
+                fields.get(name).ok_or_else(|| {
+                    parse_error(format!("Association without {name}: {association}"))
+                })
+            };
+            let optional_str = |name: &str| match fields.get(name) {
+                None | Some(Value::Null) => Ok(None),
+                Some(Value::String(s)) => Ok(Some(s.as_str())),
+                Some(other) => Err(parse_error(format!(
+                    "Expected a string as {name}, found {other}"
+                ))),
+            };
+            Ok(JsonAssociation {
+                node: field("node")?,
+                shape: field("shape")?,
+                status: optional_str("status")?,
+                reason: optional_str("reason")?,
+                app_info: fields.get("appinfo"),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn parse_error(error: String) -> ShapemapError {
+    ShapemapError::JSONParsing { error }
+}
+
+pub(crate) fn json_node(node: &Node) -> Value {
+    json_object(node.as_object())
+}
+
+fn json_object(object: &Object) -> Value {
+    match object {
+        Object::Iri(iri) => Value::String(iri.as_str().to_string()),
+        Object::Literal(lit) => json_literal(lit),
+        other => Value::String(other.to_string()),
+    }
+}
+
+pub(crate) fn json_literal(lit: &Literal) -> Value {
+    let mut map = Map::new();
+    map.insert("value".to_string(), Value::String(lit.lexical_form()));
+    match lit {
+        Literal::StringLiteral { lang: None, .. } => {}
+        Literal::StringLiteral {
+            lang: Some(lang), ..
+        } => {
+            map.insert("language".to_string(), Value::String(lang.value()));
+        }
+        Literal::DatatypeLiteral { datatype, .. } => {
+            map.insert("type".to_string(), Value::String(datatype.to_string()));
+        }
+        Literal::NumericLiteral(n) => {
+            let datatype = match n {
+                NumericLiteral::Integer(_) => XSD_INTEGER_STR,
+                NumericLiteral::Decimal(_) => XSD_DECIMAL_STR,
+                NumericLiteral::Double(_) => XSD_DOUBLE_STR,
+            };
+            map.insert("type".to_string(), Value::String(datatype.to_string()));
+        }
+        Literal::BooleanLiteral(_) => {
+            map.insert(
+                "type".to_string(),
+                Value::String(XSD_BOOLEAN_STR.to_string()),
+            );
+        }
+    }
+    Value::Object(map)
+}
+
+pub(crate) fn parse_node(value: &Value) -> Result<Node, ShapemapError> {
+    match value {
+        Value::String(str) => match str.strip_prefix("_:") {
+            Some(id) => Ok(Node::bnode(id)),
+            None => Ok(Node::iri(parse_iri(str)?)),
+        },
+        Value::Object(_) => Ok(Node::literal(parse_literal(value)?)),
+        _ => Err(parse_error(format!(
+            "Expected an IRI, a blank node or a literal as node, found {value}"
+        ))),
+    }
+}
+
+/// Parses a literal object, accepting also the JSON-LD keys `@value`, `@type` and `@language`
+fn parse_literal(value: &Value) -> Result<Literal, ShapemapError> {
+    let key = |name: &str| {
+        value
+            .get(name)
+            .or_else(|| value.get(format!("@{name}")))
+            .map(|v| match v {
+                Value::String(s) => Ok(s.clone()),
+                Value::Null => Err(parse_error(format!("Null {name} in literal {value}"))),
+                other => Ok(other.to_string()),
+            })
+            .transpose()
+    };
+    let lexical_form =
+        key("value")?.ok_or_else(|| parse_error(format!("Literal without value: {value}")))?;
+    match (key("type")?, key("language")?) {
+        (Some(_), Some(_)) => Err(parse_error(format!(
+            "Literal with both type and language: {value}"
+        ))),
+        (None, Some(lang)) => Ok(Literal::lang_str(&lexical_form, Lang::new(&lang))),
+        (None, None) => Ok(Literal::str(&lexical_form)),
+        (Some(datatype), None) => {
+            if datatype == XSD_BOOLEAN_STR {
+                match lexical_form.as_str() {
+                    "true" | "1" => return Ok(Literal::BooleanLiteral(true)),
+                    "false" | "0" => return Ok(Literal::BooleanLiteral(false)),
+                    _ => {}
+                }
+            }
+            match NumericLiteral::parse(&lexical_form, &datatype) {
+                Some(n)
+                    if matches!(
+                        datatype.as_str(),
+                        XSD_INTEGER_STR | XSD_DECIMAL_STR | XSD_DOUBLE_STR
+                    ) =>
+                {
+                    Ok(Literal::NumericLiteral(n))
+                }
+                _ => Ok(Literal::datatype(
+                    &lexical_form,
+                    &IriRef::iri(parse_iri(&datatype)?),
+                )),
+            }
+        }
+    }
+}
+
+fn parse_iri(str: &str) -> Result<IriS, ShapemapError> {
+    IriS::from_str(str).map_err(|e| parse_error(format!("Invalid IRI {str}: {e}")))
+}
+
+fn parse_shape_str(value: &Value) -> Result<&str, ShapemapError> {
+    value
+        .as_str()
+        .ok_or_else(|| parse_error(format!("Expected a shape label, found {value}")))
+}
+
+pub(crate) fn parse_shape_label(value: &Value) -> Result<ShapeLabel, ShapemapError> {
+    let str = parse_shape_str(value)?;
+    if str == START {
+        Ok(ShapeLabel::Start)
+    } else if let Some(id) = str.strip_prefix("_:") {
+        Ok(ShapeLabel::BNode(BNode::new(id)))
+    } else {
+        Ok(ShapeLabel::iri(parse_iri(str)?))
+    }
+}
+
+pub(crate) fn parse_shape_selector(value: &Value) -> Result<ShapeSelector, ShapemapError> {
+    let str = parse_shape_str(value)?;
+    if str == START {
+        Ok(ShapeSelector::Start)
+    } else if let Some(id) = str.strip_prefix("_:") {
+        Ok(ShapeSelector::Label(ShapeExprLabel::bnode(BNode::new(id))))
+    } else {
+        Ok(ShapeSelector::Label(ShapeExprLabel::iri(parse_iri(str)?)))
+    }
+}
+
+pub(crate) fn json_shape_label(label: &ShapeLabel) -> Value {
+    let str = match label {
+        ShapeLabel::Iri(iri) => iri.as_str().to_string(),
+        ShapeLabel::BNode(bnode) => bnode.to_string(),
+        ShapeLabel::Start => START.to_string(),
+    };
+    Value::String(str)
+}
+
+pub(crate) fn json_shape_selector(
+    selector: &ShapeSelector,
+    prefixmap: &PrefixMap,
+) -> Result<Value, ShapemapError> {
+    let str = match selector {
+        ShapeSelector::Start | ShapeSelector::Label(ShapeExprLabel::Start) => START.to_string(),
+        ShapeSelector::Label(ShapeExprLabel::BNode { value }) => value.to_string(),
+        ShapeSelector::Label(ShapeExprLabel::IriRef { value: iri_ref }) => {
+            resolve_iri_ref(iri_ref, prefixmap)?.as_str().to_string()
+        }
+    };
+    Ok(Value::String(str))
+}
+
+pub(crate) fn resolve_iri_ref(
+    iri_ref: &IriRef,
+    prefixmap: &PrefixMap,
+) -> Result<IriS, ShapemapError> {
+    match iri_ref {
+        IriRef::Iri(iri) => Ok(iri.clone()),
+        IriRef::Prefixed { prefix, local } => prefixmap
+            .resolve_prefix_local(prefix, local)
+            .map_err(|e| ShapemapError::JSONSerialization {
+                error: format!("Can't resolve {prefix}:{local}: {e}"),
+            }),
+    }
+}
@@ -0,0 +1,16 @@
+//! Vocabulary used to represent result shape maps as RDF
+
+pub const SHAPEMAP_STR: &str = "http://shex.io/ns/shapemap#";
+pub const SHAPEMAP_RESULT_SHAPE_MAP_STR: &str = "http://shex.io/ns/shapemap#ResultShapeMap";
+pub const SHAPEMAP_ASSOCIATION_STR: &str = "http://shex.io/ns/shapemap#Association";
+pub const SHAPEMAP_HAS_ASSOCIATION_STR: &str = "http://shex.io/ns/shapemap#association";
+pub const SHAPEMAP_NODE_STR: &str = "http://shex.io/ns/shapemap#node";
+pub const SHAPEMAP_SHAPE_STR: &str = "http://shex.io/ns/shapemap#shape";
+pub const SHAPEMAP_STATUS_STR: &str = "http://shex.io/ns/shapemap#status";
+pub const SHAPEMAP_REASON_STR: &str = "http://shex.io/ns/shapemap#reason";
+pub const SHAPEMAP_APPINFO_STR: &str = "http://shex.io/ns/shapemap#appinfo";
+pub const SHAPEMAP_START_STR: &str = "http://shex.io/ns/shapemap#Start";
+pub const SHAPEMAP_CONFORMANT_STR: &str = "http://shex.io/ns/shapemap#Conformant";
+pub const SHAPEMAP_NONCONFORMANT_STR: &str = "http://shex.io/ns/shapemap#NonConformant";
+pub const SHAPEMAP_PENDING_STR: &str = "http://shex.io/ns/shapemap#Pending";
+pub const SHAPEMAP_INCONSISTENT_STR: &str = "http://shex.io/ns/shapemap#Inconsistent";
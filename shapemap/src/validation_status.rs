@@ -39,9 +39,68 @@ impl ValidationStatus {
         })
     }
 
+    pub fn inconsistent(
+        conformant_reason: String,
+        conformant_value: Value,
+        non_conformant_reason: String,
+        non_conformant_value: Value,
+    ) -> ValidationStatus {
+        ValidationStatus::Inconsistent(
+            ConformantInfo {
+                reason: conformant_reason,
+                app_info: conformant_value,
+            },
+            NonConformantInfo {
+                reason: non_conformant_reason,
+                app_info: non_conformant_value,
+            },
+        )
+    }
+
     pub fn pending() -> ValidationStatus {
         ValidationStatus::Pending
     }
+
+    /// Name of the status as used in the ShapeMap JSON results
+    pub fn code(&self) -> &'static str {
+        match self {
+            ValidationStatus::Conformant(_) => "conformant",
+            ValidationStatus::NonConformant(_) => "nonconformant",
+            ValidationStatus::Pending => "pending",
+            ValidationStatus::Inconsistent(_, _) => "inconsistent",
+        }
+    }
+
+    /// Reason of the status, `None` if the status is pending
+    pub fn reason(&self) -> Option<String> {
+        match self {
+            ValidationStatus::Conformant(info) => Some(info.reason.clone()),
+            ValidationStatus::NonConformant(info) => Some(info.reason.clone()),
+            ValidationStatus::Pending => None,
+            ValidationStatus::Inconsistent(conformant, non_conformant) => Some(format!(
+                "conformant: {}, non-conformant: {}",
+                conformant.reason, non_conformant.reason
+            )),
+        }
+    }
+
+    /// Application specific information attached to the status, `None` if there is no information
+    pub fn app_info(&self) -> Option<Value> {
+        let app_info = match self {
+            ValidationStatus::Conformant(info) => info.app_info.clone(),
+            ValidationStatus::NonConformant(info) => info.app_info.clone(),
+            ValidationStatus::Pending => Value::Null,
+            ValidationStatus::Inconsistent(conformant, non_conformant) => Value::Array(vec![
+                conformant.app_info.clone(),
+                non_conformant.app_info.clone(),
+            ]),
+        };
+        if app_info.is_null() {
+            None
+        } else {
+            Some(app_info)
+        }
+    }
 }
 
 impl Display for ValidationStatus {
@@ -72,6 +131,14 @@ pub struct ConformantInfo {
 }
 
 impl ConformantInfo {
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    pub fn app_info(&self) -> &Value {
+        &self.app_info
+    }
+
     pub fn merge(&self, other: ConformantInfo) -> ConformantInfo {
         let merged_reason = format!("{}\n{}", self.reason, other.reason);
         ConformantInfo {
@@ -93,6 +160,16 @@ pub struct NonConformantInfo {
     app_info: Value,
 }
 
+impl NonConformantInfo {
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    pub fn app_info(&self) -> &Value {
+        &self.app_info
+    }
+}
+
 impl Display for NonConformantInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.reason)
@@ -4,7 +4,9 @@
 pub mod converter_config;
 pub mod converter_error;
 pub mod landing_html_template;
+pub mod shacl_to_html;
 pub mod shacl_to_shex;
+pub mod shacl_to_uml;
 pub mod shex_to_html;
 pub mod shex_to_sparql;
 pub mod shex_to_uml;
@@ -18,9 +20,13 @@ use shex_ast::ObjectValue;
 
 pub use crate::converter_config::*;
 pub use crate::converter_error::*;
+pub use crate::shacl_to_html::shacl2html::*;
+pub use crate::shacl_to_html::shacl2html_error::*;
 pub use crate::shacl_to_shex::shacl2shex::*;
 pub use crate::shacl_to_shex::shacl2shex_config::*;
 pub use crate::shacl_to_shex::shacl2shex_error::*;
+pub use crate::shacl_to_uml::shacl2uml::*;
+pub use crate::shacl_to_uml::shacl2uml_error::*;
 pub use crate::shex_to_html::shex2html::*;
 pub use crate::shex_to_html::shex2html_config::*;
 pub use crate::shex_to_html::shex2html_error::*;
@@ -0,0 +1,8 @@
+//! SHACL to HTML
+//!
+//!
+pub mod shacl2html;
+pub mod shacl2html_error;
+
+pub use shacl2html::*;
+pub use shacl2html_error::*;
@@ -0,0 +1,450 @@
+use std::cmp::Ordering;
+
+use prefixmap::{IriRef, PrefixMap};
+use shacl_ast::{
+    component::Component, node_shape::NodeShape, property_shape::PropertyShape,
+    shape::Shape as ShaclShape, value::Value as ShaclValue, Schema as ShaclSchema,
+};
+use srdf::{numeric_literal::NumericLiteral, Object, RDFNode, SHACLPath};
+
+use super::Shacl2HtmlError;
+use crate::shacl_to_uml::{message2label, node_kind2str, path2str};
+use crate::shex_to_html::shex2html::{export_html_schema, landing_page2name};
+use crate::shex_to_html::{
+    Cardinality, HtmlSchema, HtmlShape, Name, ShEx2HtmlConfig, ShapeTemplateEntry, Value,
+    ValueConstraint,
+};
+use crate::{ImageFormat, Shacl2Uml, UmlGenerationMode};
+
+/// Converts SHACL schemas to HTML pages with the same templates as the ShEx to HTML converter.
+///
+/// Each node shape generates a page whose property table is ordered by `sh:order`,
+/// grouped by `sh:group` and labelled by `sh:name`.
+/// The groups are ordered and labelled by the `sh:order` and `rdfs:label` of their `sh:PropertyGroup`
+pub struct Shacl2Html {
+    config: ShEx2HtmlConfig,
+    current_html: HtmlSchema,
+    current_uml_converter: Shacl2Uml,
+    current_prefixmap: PrefixMap,
+}
+
+impl Shacl2Html {
+    pub fn new(config: ShEx2HtmlConfig) -> Shacl2Html {
+        let uml_config = config.shex2uml_config();
+        Shacl2Html {
+            config,
+            current_html: HtmlSchema::new(),
+            current_uml_converter: Shacl2Uml::new(&uml_config),
+            current_prefixmap: PrefixMap::new(),
+        }
+    }
+
+    pub fn current_html(&self) -> &HtmlSchema {
+        &self.current_html
+    }
+
+    pub fn convert(&mut self, schema: &ShaclSchema) -> Result<(), Shacl2HtmlError> {
+        self.current_prefixmap = schema.prefix_map().without_rich_qualifying();
+        self.current_html = HtmlSchema::new().with_prefixmap(self.current_prefixmap.clone());
+        let parent = landing_page2name(&self.config);
+        if self.config.embed_svg_schema || self.config.embed_svg_shape {
+            self.current_uml_converter.convert(schema)?;
+        }
+        let mut node_shapes: Vec<&NodeShape> = schema
+            .iter()
+            .filter_map(|(_, shape)| match shape {
+                ShaclShape::NodeShape(ns) => Some(ns.as_ref()),
+                ShaclShape::PropertyShape(_) => None,
+            })
+            .collect();
+        node_shapes.sort_by_key(|ns| ns.id().to_string());
+        for node_shape in node_shapes {
+            let mut name = self.rdfnode2name(node_shape.id())?;
+            if let Some(label) = message2label(node_shape.name()) {
+                name.add_label(label.as_str())
+            }
+            let (node_id, _found) = self.current_html.get_node_adding_label(&name.name());
+            let component = self.node_shape2htmlshape(name, node_shape, schema, &parent)?;
+            self.current_html.add_component(node_id, component)?;
+        }
+
+        if self.config.embed_svg_shape {
+            for shape in self.current_html.shapes_mut() {
+                let str = create_svg(
+                    &self.current_uml_converter,
+                    &UmlGenerationMode::neighs(&shape.name().name()),
+                )?;
+                shape.set_svg_shape(str.as_str());
+            }
+        }
+
+        if self.config.embed_svg_schema {
+            let str = create_svg(&self.current_uml_converter, &UmlGenerationMode::all())?;
+            self.current_html.set_svg_schema(str.as_str())
+        }
+        Ok(())
+    }
+
+    pub fn export_schema(&self) -> Result<(), Shacl2HtmlError> {
+        export_html_schema(&self.current_html, &self.config)?;
+        Ok(())
+    }
+
+    fn node_shape2htmlshape(
+        &mut self,
+        name: Name,
+        node_shape: &NodeShape,
+        schema: &ShaclSchema,
+        parent: &Name,
+    ) -> Result<HtmlShape, Shacl2HtmlError> {
+        let mut html_shape = HtmlShape::new(name, parent.clone());
+        if let Some(description) = message2label(node_shape.description()) {
+            html_shape.set_description(description.as_str())
+        }
+        for component in node_shape.components() {
+            match component {
+                Component::Node { shape } => {
+                    let extended_name = self.shape_ref2name(shape, parent)?;
+                    html_shape.add_extends(&extended_name)
+                }
+                Component::And { shapes } => {
+                    for shape in shapes {
+                        let extended_name = self.shape_ref2name(shape, parent)?;
+                        html_shape.add_extends(&extended_name)
+                    }
+                }
+                _ => {}
+            }
+        }
+        let mut property_shapes = Vec::new();
+        for property in node_shape.property_shapes() {
+            match schema.get_shape(property) {
+                Some(ShaclShape::PropertyShape(ps)) => property_shapes.push(ps),
+                Some(ShaclShape::NodeShape(ns)) => {
+                    return Err(Shacl2HtmlError::NotExpectedNodeShape {
+                        node_shape: ns.clone(),
+                    })
+                }
+                None => {
+                    return Err(Shacl2HtmlError::ShapeNotFound {
+                        node: property.clone(),
+                    })
+                }
+            }
+        }
+        // Property shapes without sh:order go last, sorted by path so the pages are always the same
+        property_shapes.sort_by(|ps1, ps2| {
+            compare_order(ps1.order(), ps2.order()).then_with(|| {
+                path2str(ps1.path(), &self.current_prefixmap)
+                    .cmp(&path2str(ps2.path(), &self.current_prefixmap))
+            })
+        });
+        let mut groups: Vec<(&RDFNode, Vec<ShapeTemplateEntry>)> = Vec::new();
+        for property_shape in property_shapes {
+            let entries = self.property_shape2entries(property_shape, parent)?;
+            match property_shape.group() {
+                Some(group) => match groups.iter_mut().find(|(g, _)| *g == group) {
+                    Some((_, group_entries)) => group_entries.extend(entries),
+                    None => groups.push((group, entries)),
+                },
+                None => entries
+                    .into_iter()
+                    .for_each(|entry| html_shape.add_entry(entry)),
+            }
+        }
+        // Groups are ordered by the sh:order of the sh:PropertyGroup and labelled by its rdfs:label.
+        // Groups without sh:order go last, in the order of their first property shape
+        groups.sort_by(|(g1, _), (g2, _)| {
+            let order = |group| schema.get_property_group(group).and_then(|g| g.order());
+            compare_order(order(g1), order(g2))
+        });
+        for (group, entries) in groups {
+            let mut name = self.rdfnode2name(group)?;
+            if let Some(label) = schema
+                .get_property_group(group)
+                .and_then(|g| message2label(g.label()))
+            {
+                name.add_label(label.as_str())
+            }
+            for entry in entries {
+                html_shape.add_group_entry(&name, entry)
+            }
+        }
+        Ok(html_shape)
+    }
+
+    /// Converts a property shape to entries of the shape.
+    /// Qualified value shapes generate an entry with their own cardinality
+    fn property_shape2entries(
+        &mut self,
+        property_shape: &PropertyShape,
+        parent: &Name,
+    ) -> Result<Vec<ShapeTemplateEntry>, Shacl2HtmlError> {
+        let mut name = self.path2name(property_shape.path());
+        if let Some(label) = message2label(property_shape.name()) {
+            name.add_label(label.as_str())
+        }
+        let description = message2label(property_shape.description());
+        let mut min = None;
+        let mut max = None;
+        for component in property_shape.components() {
+            match component {
+                Component::MinCount(n) => min = Some(*n),
+                Component::MaxCount(n) => max = Some(*n),
+                _ => {}
+            }
+        }
+        let mut value_constraint = ValueConstraint::Any;
+        let mut qualified_entries = Vec::new();
+        for component in property_shape.components() {
+            match component {
+                Component::Datatype(datatype) => {
+                    value_constraint = ValueConstraint::datatype(self.iri_ref2name(datatype)?)
+                }
+                Component::NodeKind(node_kind) if value_constraint == ValueConstraint::Any => {
+                    value_constraint = ValueConstraint::datatype(Name::new(
+                        node_kind2str(node_kind),
+                        None,
+                        self.config.target_folder().as_path(),
+                    ))
+                }
+                Component::Class(class) => {
+                    value_constraint = ValueConstraint::Class(self.rdfnode2name(class)?)
+                }
+                Component::Node { shape } => {
+                    value_constraint = ValueConstraint::Ref(self.shape_ref2name(shape, parent)?)
+                }
+                Component::In { values } => {
+                    let values = values
+                        .iter()
+                        .map(|value| self.shacl_value2value(value))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    value_constraint = ValueConstraint::ValueSet(values)
+                }
+                Component::HasValue { value } => {
+                    value_constraint =
+                        ValueConstraint::ValueSet(vec![self.shacl_value2value(value)?])
+                }
+                Component::QualifiedValueShape {
+                    shape,
+                    qualified_min_count,
+                    qualified_max_count,
+                    qualified_value_shapes_disjoint: _,
+                } => {
+                    let entry = ShapeTemplateEntry::new(
+                        name.clone(),
+                        ValueConstraint::Ref(self.shape_ref2name(shape, parent)?),
+                        mk_card(*qualified_min_count, *qualified_max_count),
+                    );
+                    qualified_entries.push(entry.with_description(description.clone()))
+                }
+                _ => {}
+            }
+        }
+        let mut entries = Vec::new();
+        if value_constraint != ValueConstraint::Any || qualified_entries.is_empty() {
+            entries.push(
+                ShapeTemplateEntry::new(name, value_constraint, mk_card(min, max))
+                    .with_description(description),
+            )
+        }
+        entries.append(&mut qualified_entries);
+        Ok(entries)
+    }
+
+    /// Name of a referenced shape, which gets a page even if it is not declared in the schema
+    fn shape_ref2name(&mut self, shape: &RDFNode, parent: &Name) -> Result<Name, Shacl2HtmlError> {
+        let ref_name = self.rdfnode2name(shape)?;
+        let (node, found) = self.current_html.get_node_adding_label(&ref_name.name());
+        if !found {
+            self.current_html
+                .add_component(node, HtmlShape::new(ref_name.clone(), parent.clone()))?
+        }
+        Ok(ref_name)
+    }
+
+    fn rdfnode2name(&self, node: &RDFNode) -> Result<Name, Shacl2HtmlError> {
+        let target_folder = self.config.target_folder();
+        match node {
+            Object::Iri(iri) => Ok(Name::new(
+                self.current_prefixmap.qualify(iri).as_str(),
+                Some(iri.as_str()),
+                target_folder.as_path(),
+            )),
+            Object::BlankNode(bnode) => Ok(Name::new(
+                format!("_:{bnode}").as_str(),
+                None,
+                target_folder.as_path(),
+            )),
+            Object::Literal(lit) => Err(Shacl2HtmlError::RDFNode2NameLiteral {
+                literal: lit.clone(),
+            }),
+            Object::Triple { .. } => Err(Shacl2HtmlError::not_implemented(
+                format!("Quoted triple as shape identifier: {node}").as_str(),
+            )),
+        }
+    }
+
+    fn iri_ref2name(&self, iri_ref: &IriRef) -> Result<Name, Shacl2HtmlError> {
+        let target_folder = self.config.target_folder();
+        match iri_ref {
+            IriRef::Iri(iri) => Ok(Name::new(
+                self.current_prefixmap.qualify(iri).as_str(),
+                Some(iri.as_str()),
+                target_folder.as_path(),
+            )),
+            IriRef::Prefixed { prefix, local } => {
+                let iri = self.current_prefixmap.resolve_prefix_local(prefix, local)?;
+                Ok(Name::new(
+                    format!("{prefix}:{local}").as_str(),
+                    Some(iri.as_str()),
+                    target_folder.as_path(),
+                ))
+            }
+        }
+    }
+
+    fn shacl_value2value(&self, value: &ShaclValue) -> Result<Value, Shacl2HtmlError> {
+        match value {
+            ShaclValue::Iri(iri_ref) => Ok(Value::Str(self.iri_ref2name(iri_ref)?.name())),
+            ShaclValue::Literal(lit) => Ok(Value::Str(lit.to_string())),
+        }
+    }
+
+    fn path2name(&self, path: &SHACLPath) -> Name {
+        let target_folder = self.config.target_folder();
+        match path {
+            SHACLPath::Predicate { pred } => Name::new(
+                self.current_prefixmap.qualify(pred).as_str(),
+                Some(pred.as_str()),
+                target_folder.as_path(),
+            ),
+            _ => Name::new(
+                path2str(path, &self.current_prefixmap).as_str(),
+                None,
+                target_folder.as_path(),
+            ),
+        }
+    }
+}
+
+/// Compares the values of `sh:order`, where the property shapes without order go last
+fn compare_order(order1: Option<&NumericLiteral>, order2: Option<&NumericLiteral>) -> Ordering {
+    match (order1, order2) {
+        (Some(o1), Some(o2)) => o1.compare(o2).unwrap_or(Ordering::Equal),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Cardinality of a property shape, where SHACL's default is `{0,*}`
+fn mk_card(min: Option<isize>, max: Option<isize>) -> Cardinality {
+    let min = min.unwrap_or(0).max(0) as i32;
+    let max = max.map(|n| n as i32).unwrap_or(-1);
+    match (min, max) {
+        (1, 1) => Cardinality::OneOne,
+        (0, -1) => Cardinality::Star,
+        (0, 1) => Cardinality::Optional,
+        (1, -1) => Cardinality::Plus,
+        (m, n) if n == m => Cardinality::Fixed(m),
+        (m, n) => Cardinality::Range(m, n),
+    }
+}
+
+fn create_svg(converter: &Shacl2Uml, mode: &UmlGenerationMode) -> Result<String, Shacl2HtmlError> {
+    let mut svg = Vec::new();
+    converter.as_image(&mut svg, ImageFormat::SVG, mode)?;
+    Ok(String::from_utf8(svg)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shacl_ast::ShaclParser;
+    use srdf::{RDFFormat, SRDFGraph};
+
+    #[test]
+    fn test_shacl2html() {
+        let shacl_str = r#"prefix : <http://example.org/>
+prefix sh: <http://www.w3.org/ns/shacl#>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#>
+
+:Person a sh:NodeShape ;
+  sh:name "Person" ;
+  sh:description "A human being" ;
+  sh:property [
+    sh:path :email ; sh:name "E-mail" ; sh:order 3 ; sh:group :Contact ; sh:datatype xsd:string
+  ] ;
+  sh:property [
+    sh:path :name ; sh:name "Full name"@en ; sh:order 1 ; sh:datatype xsd:string ;
+    sh:minCount 1 ; sh:maxCount 1
+  ] ;
+  sh:property [
+    sh:path :phone ; sh:order 2 ; sh:group :Contact ; sh:minCount 2 ; sh:maxCount 3
+  ] ;
+  sh:property [
+    sh:path :knows ; sh:node :Person
+  ] ;
+  sh:property [
+    sh:path :birthDate ; sh:order 0.5 ; sh:datatype xsd:date ; sh:description "Date of birth"
+  ] ;
+  sh:property [
+    sh:path :homepage ; sh:order 1 ; sh:group :Online ; sh:nodeKind sh:IRI
+  ] .
+
+:Contact a sh:PropertyGroup ; sh:order 2 ; rdfs:label "Contact details" .
+:Online a sh:PropertyGroup ; sh:order 1 ; rdfs:label "Online presence" .
+"#;
+        let rdf =
+            SRDFGraph::from_str(shacl_str, &RDFFormat::Turtle, None, &Default::default()).unwrap();
+        let schema = ShaclParser::new(rdf).parse().unwrap();
+        let config = ShEx2HtmlConfig {
+            embed_svg_schema: false,
+            embed_svg_shape: false,
+            ..Default::default()
+        };
+        let mut converter = Shacl2Html::new(config);
+        converter.convert(&schema).unwrap();
+        let shapes: Vec<&HtmlShape> = converter.current_html().shapes().collect();
+        assert_eq!(shapes.len(), 1);
+        let person = shapes[0];
+        assert_eq!(person.name().name(), ":Person");
+        assert_eq!(person.name().label(), Some("Person".to_string()));
+        assert_eq!(person.description(), Some("A human being".to_string()));
+
+        let entries: Vec<(String, Option<String>)> = person
+            .entries()
+            .map(|e| (e.name.name(), e.name.label()))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                (":birthDate".to_string(), None),
+                (":name".to_string(), Some("Full name".to_string())),
+                (":knows".to_string(), None),
+            ]
+        );
+        let birth_date = person.entries().next().unwrap();
+        assert_eq!(birth_date.description, Some("Date of birth".to_string()));
+        assert_eq!(birth_date.card, Cardinality::Star);
+
+        // The groups are sorted by their own sh:order, although :Contact has the first property
+        let groups: Vec<(String, Option<String>)> = person
+            .groups()
+            .map(|g| (g.name.name(), g.name.label()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                (":Online".to_string(), Some("Online presence".to_string())),
+                (":Contact".to_string(), Some("Contact details".to_string())),
+            ]
+        );
+        let contact = person.groups().nth(1).unwrap();
+        let group_entries: Vec<String> = contact.entries.iter().map(|e| e.name.name()).collect();
+        assert_eq!(group_entries, vec![":phone", ":email"]);
+        assert_eq!(contact.entries[0].card, Cardinality::Range(2, 3));
+    }
+}
@@ -0,0 +1,58 @@
+use std::string::FromUtf8Error;
+
+use prefixmap::PrefixMapError;
+use shacl_ast::node_shape::NodeShape;
+use srdf::{literal::Literal, RDFNode};
+use thiserror::Error;
+
+use crate::{ShEx2HtmlError, Shacl2UmlError};
+
+#[derive(Error, Debug)]
+pub enum Shacl2HtmlError {
+    #[error("Shacl2Html error: Feature not implemented: {msg}")]
+    NotImplemented { msg: String },
+
+    #[error("Unexpected literal as shape identifier: {literal}")]
+    RDFNode2NameLiteral { literal: Literal },
+
+    #[error("Shape {node} not found in SHACL schema")]
+    ShapeNotFound { node: RDFNode },
+
+    #[error("Not expected node shape as property shape: {node_shape:?}")]
+    NotExpectedNodeShape { node_shape: Box<NodeShape> },
+
+    #[error(transparent)]
+    ShEx2HtmlError { err: Box<ShEx2HtmlError> },
+
+    #[error(transparent)]
+    Shacl2UmlError {
+        #[from]
+        err: Shacl2UmlError,
+    },
+
+    #[error(transparent)]
+    PrefixMapError {
+        #[from]
+        err: PrefixMapError,
+    },
+
+    #[error(transparent)]
+    Utf8Error {
+        #[from]
+        err: FromUtf8Error,
+    },
+}
+
+impl Shacl2HtmlError {
+    pub fn not_implemented(msg: &str) -> Shacl2HtmlError {
+        Shacl2HtmlError::NotImplemented {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<ShEx2HtmlError> for Shacl2HtmlError {
+    fn from(err: ShEx2HtmlError) -> Self {
+        Shacl2HtmlError::ShEx2HtmlError { err: Box::new(err) }
+    }
+}
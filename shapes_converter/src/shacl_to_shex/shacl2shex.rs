@@ -65,6 +65,9 @@ impl Shacl2ShEx {
             srdf::Object::Literal(lit) => Err(Shacl2ShExError::RDFNode2LabelLiteral {
                 literal: lit.clone(),
             }),
+            srdf::Object::Triple { .. } => Err(Shacl2ShExError::not_implemented(
+                format!("Quoted triple as shape label: {node}").as_str(),
+            )),
         }
     }
 
@@ -145,6 +148,9 @@ impl Shacl2ShEx {
                     Object::Literal(lit) => Err(Shacl2ShExError::UnexpectedLiteralForTargetClass {
                         literal: lit.clone(),
                     }),
+                    Object::Triple { .. } => Err(Shacl2ShExError::not_implemented(
+                        format!("Quoted triple in target class declaration: {cls}").as_str(),
+                    )),
                 }?;
                 let value_cls = ShapeExpr::node_constraint(
                     NodeConstraint::new().with_values(vec![value_set_value]),
@@ -328,8 +334,21 @@ impl Shacl2ShEx {
         let rdf_type = IriRef::iri(IriS::rdf_type());
         let value = match cls {
             Object::Iri(iri) => ValueSetValue::iri(IriRef::iri(iri.clone())),
-            Object::BlankNode(_) => todo!(),
-            Object::Literal(_) => todo!(),
+            Object::BlankNode(bnode) => {
+                return Err(Shacl2ShExError::UnexpectedBlankNodeForClass {
+                    bnode: bnode.to_string(),
+                })
+            }
+            Object::Literal(literal) => {
+                return Err(Shacl2ShExError::UnexpectedLiteralForClass {
+                    literal: literal.clone(),
+                })
+            }
+            Object::Triple { .. } => {
+                return Err(Shacl2ShExError::UnexpectedTripleForClass {
+                    triple: cls.to_string(),
+                })
+            }
         };
         let cls = NodeConstraint::new().with_values(vec![value]);
         let te = TripleExpr::triple_constraint(
@@ -396,6 +415,10 @@ impl Shacl2ShEx {
                 qualified_max_count: _,
                 qualified_value_shapes_disjoint: _,
             } => todo!(),
+            Component::Sparql(_) | Component::SparqlComponent { .. } => {
+                debug!("SPARQL-based constraints have no ShEx counterpart");
+                Ok(ShapeExpr::empty_shape())
+            }
         }
     }
 
@@ -18,6 +18,15 @@ pub enum Shacl2ShExError {
 
     #[error("Unexpected literal in target class declaration: {literal:?}")]
     UnexpectedLiteralForTargetClass { literal: Literal },
+
+    #[error("Unexpected blank node in class constraint: {bnode:?}")]
+    UnexpectedBlankNodeForClass { bnode: String },
+
+    #[error("Unexpected literal in class constraint: {literal:?}")]
+    UnexpectedLiteralForClass { literal: Literal },
+
+    #[error("Unexpected quoted triple in class constraint: {triple}")]
+    UnexpectedTripleForClass { triple: String },
 }
 
 impl Shacl2ShExError {
@@ -0,0 +1,8 @@
+//! SHACL to UML converter
+//!
+//!
+pub mod shacl2uml;
+pub mod shacl2uml_error;
+
+pub use shacl2uml::*;
+pub use shacl2uml_error::*;
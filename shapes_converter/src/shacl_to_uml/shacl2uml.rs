@@ -0,0 +1,461 @@
+use std::io::Write;
+
+use prefixmap::{IriRef, PrefixMap};
+use shacl_ast::{
+    component::Component, message_map::MessageMap, node_kind::NodeKind, node_shape::NodeShape,
+    property_shape::PropertyShape, shape::Shape as ShaclShape, value::Value, Schema as ShaclSchema,
+};
+use srdf::{Object, RDFNode, SHACLPath};
+
+use super::Shacl2UmlError;
+use crate::shex_to_uml::shex2uml::{plantuml2image, ImageFormat, UmlGenerationMode};
+use crate::shex_to_uml::{
+    Name, NodeId, ShEx2UmlConfig, ShEx2UmlError, Uml, UmlCardinality, UmlClass, UmlComponent,
+    UmlEntry, UmlRenderer, ValueConstraint,
+};
+
+/// Converts SHACL schemas to the UML model used by the ShEx to UML converter,
+/// so they can be shown with the same kinds of diagrams.
+///
+/// Node shapes are represented as classes and their property shapes as entries.
+/// Property shapes with `sh:node`, `sh:class` or `sh:qualifiedValueShape` are represented as links
+/// and `sh:node` or `sh:and` in node shapes are represented as extends
+pub struct Shacl2Uml {
+    config: ShEx2UmlConfig,
+    current_uml: Uml,
+    current_prefixmap: PrefixMap,
+}
+
+impl Shacl2Uml {
+    pub fn new(config: &ShEx2UmlConfig) -> Shacl2Uml {
+        Shacl2Uml {
+            config: config.clone(),
+            current_uml: Uml::new(),
+            current_prefixmap: PrefixMap::new(),
+        }
+    }
+
+    pub fn convert(&mut self, schema: &ShaclSchema) -> Result<(), Shacl2UmlError> {
+        self.current_prefixmap = schema.prefix_map().without_rich_qualifying();
+        self.current_uml = Uml::new();
+        // Node shapes are sorted so the same schema always generates the same diagram
+        let mut node_shapes: Vec<&NodeShape> = schema
+            .iter()
+            .filter_map(|(_, shape)| match shape {
+                ShaclShape::NodeShape(ns) => Some(ns.as_ref()),
+                ShaclShape::PropertyShape(_) => None,
+            })
+            .collect();
+        node_shapes.sort_by_key(|ns| ns.id().to_string());
+        for node_shape in node_shapes {
+            let name = self.rdfnode2name(node_shape.id())?;
+            let (node_id, _found) = self.current_uml.get_node_adding_label(&name.name());
+            let class = self.node_shape2class(name, node_shape, &node_id, schema)?;
+            self.current_uml
+                .update_component(node_id, UmlComponent::class(class))?;
+        }
+        Ok(())
+    }
+
+    pub fn as_plantuml<W: Write>(
+        &self,
+        writer: &mut W,
+        mode: &UmlGenerationMode,
+    ) -> Result<(), Shacl2UmlError> {
+        match self.target_node(mode)? {
+            None => self.current_uml.as_plantuml_all(&self.config, writer)?,
+            Some(node) => self
+                .current_uml
+                .as_plantuml_neighs(&self.config, writer, &node)?,
+        }
+        Ok(())
+    }
+
+    /// Converts the current UML to an SVG image without external tools
+    pub fn as_svg<W: Write>(
+        &self,
+        writer: &mut W,
+        mode: &UmlGenerationMode,
+    ) -> Result<(), Shacl2UmlError> {
+        match self.target_node(mode)? {
+            None => self.current_uml.as_svg_all(&self.config, writer)?,
+            Some(node) => self
+                .current_uml
+                .as_svg_neighs(&self.config, writer, &node)?,
+        }
+        Ok(())
+    }
+
+    /// Converts the current UML to a [Mermaid](https://mermaid.js.org/) class diagram
+    pub fn as_mermaid<W: Write>(
+        &self,
+        writer: &mut W,
+        mode: &UmlGenerationMode,
+    ) -> Result<(), Shacl2UmlError> {
+        match self.target_node(mode)? {
+            None => self.current_uml.as_mermaid_all(&self.config, writer)?,
+            Some(node) => self
+                .current_uml
+                .as_mermaid_neighs(&self.config, writer, &node)?,
+        }
+        Ok(())
+    }
+
+    /// Converts the current UML to a [Graphviz](https://graphviz.org/) DOT graph
+    pub fn as_dot<W: Write>(
+        &self,
+        writer: &mut W,
+        mode: &UmlGenerationMode,
+    ) -> Result<(), Shacl2UmlError> {
+        match self.target_node(mode)? {
+            None => self.current_uml.as_dot_all(&self.config, writer)?,
+            Some(node) => self
+                .current_uml
+                .as_dot_neighs(&self.config, writer, &node)?,
+        }
+        Ok(())
+    }
+
+    /// Converts the current UML to an image.
+    ///
+    /// SVG images are generated with the renderer selected in the configuration, PNG images with PlantUML
+    pub fn as_image<W: Write>(
+        &self,
+        writer: &mut W,
+        image_format: ImageFormat,
+        mode: &UmlGenerationMode,
+    ) -> Result<(), Shacl2UmlError> {
+        match (&image_format, self.config.renderer()) {
+            (ImageFormat::SVG, UmlRenderer::Native) => self.as_svg(writer, mode),
+            _ => {
+                let target_node = self.target_node(mode)?;
+                plantuml2image(&self.config, writer, image_format, |tempfile| {
+                    match target_node {
+                        None => self.current_uml.as_plantuml_all(&self.config, tempfile)?,
+                        Some(node) => {
+                            self.current_uml
+                                .as_plantuml_neighs(&self.config, tempfile, &node)?
+                        }
+                    }
+                    Ok::<(), ShEx2UmlError>(())
+                })?;
+                Ok(())
+            }
+        }
+    }
+
+    fn target_node(&self, mode: &UmlGenerationMode) -> Result<Option<NodeId>, Shacl2UmlError> {
+        match mode {
+            UmlGenerationMode::AllNodes => Ok(None),
+            UmlGenerationMode::Neighs(str) => match self.current_uml.get_node(str) {
+                Some(node) => Ok(Some(node)),
+                None => Err(Shacl2UmlError::NotFoundLabel { name: str.clone() }),
+            },
+        }
+    }
+
+    fn node_shape2class(
+        &mut self,
+        name: Name,
+        node_shape: &NodeShape,
+        current_node_id: &NodeId,
+        schema: &ShaclSchema,
+    ) -> Result<UmlClass, Shacl2UmlError> {
+        let mut uml_class = UmlClass::new(name);
+        for component in node_shape.components() {
+            match component {
+                Component::Node { shape } => {
+                    self.add_extends(&mut uml_class, current_node_id, shape)?
+                }
+                Component::And { shapes } => {
+                    for shape in shapes {
+                        self.add_extends(&mut uml_class, current_node_id, shape)?
+                    }
+                }
+                _ => {}
+            }
+        }
+        for property in node_shape.property_shapes() {
+            match schema.get_shape(property) {
+                Some(ShaclShape::PropertyShape(ps)) => {
+                    if let Some(entry) = self.property_shape2entry(ps, current_node_id)? {
+                        uml_class.add_entry(entry)
+                    }
+                }
+                Some(ShaclShape::NodeShape(ns)) => {
+                    return Err(Shacl2UmlError::NotExpectedNodeShape {
+                        node_shape: ns.clone(),
+                    })
+                }
+                None => {
+                    return Err(Shacl2UmlError::ShapeNotFound {
+                        node: property.clone(),
+                    })
+                }
+            }
+        }
+        Ok(uml_class)
+    }
+
+    fn add_extends(
+        &mut self,
+        uml_class: &mut UmlClass,
+        current_node_id: &NodeId,
+        shape: &RDFNode,
+    ) -> Result<(), Shacl2UmlError> {
+        let extended_name = self.rdfnode2name(shape)?;
+        let (extended_node, found) = self
+            .current_uml
+            .get_node_adding_label(&extended_name.name());
+        self.current_uml
+            .add_extends(current_node_id, &extended_node);
+        uml_class.add_extends(&extended_node);
+        if !found {
+            self.current_uml.add_component(
+                extended_node,
+                UmlComponent::class(UmlClass::new(extended_name)),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Converts a property shape to an entry of the class.
+    /// Property shapes which are only represented by links don't generate entries
+    fn property_shape2entry(
+        &mut self,
+        property_shape: &PropertyShape,
+        current_node_id: &NodeId,
+    ) -> Result<Option<UmlEntry>, Shacl2UmlError> {
+        let mut name = self.path2name(property_shape.path());
+        if let Some(label) = message2label(property_shape.name()) {
+            name.add_label(label.as_str())
+        }
+        let mut min = None;
+        let mut max = None;
+        for component in property_shape.components() {
+            match component {
+                Component::MinCount(n) => min = Some(*n),
+                Component::MaxCount(n) => max = Some(*n),
+                _ => {}
+            }
+        }
+        let card = mk_card(min, max);
+        let mut value_constraint = None;
+        let mut has_links = false;
+        for component in property_shape.components() {
+            match component {
+                Component::Datatype(datatype) => {
+                    value_constraint = Some(ValueConstraint::datatype(self.iri_ref2name(datatype)?))
+                }
+                Component::NodeKind(node_kind) if value_constraint.is_none() => {
+                    value_constraint = Some(ValueConstraint::datatype(Name::new(
+                        node_kind2str(node_kind),
+                        None,
+                    )))
+                }
+                Component::In { values } => {
+                    let values = values
+                        .iter()
+                        .map(|value| self.value2name(value))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    value_constraint = Some(ValueConstraint::ValueSet(values))
+                }
+                Component::HasValue { value } => {
+                    value_constraint =
+                        Some(ValueConstraint::ValueSet(vec![self.value2name(value)?]))
+                }
+                Component::Node { shape } | Component::Class(shape) => {
+                    let target = self.rdfnode2name(shape)?;
+                    self.current_uml.add_link(
+                        *current_node_id,
+                        target,
+                        name.clone(),
+                        card.clone(),
+                    )?;
+                    has_links = true;
+                }
+                Component::QualifiedValueShape {
+                    shape,
+                    qualified_min_count,
+                    qualified_max_count,
+                    qualified_value_shapes_disjoint: _,
+                } => {
+                    let target = self.rdfnode2name(shape)?;
+                    let card = mk_card(*qualified_min_count, *qualified_max_count);
+                    self.current_uml
+                        .add_link(*current_node_id, target, name.clone(), card)?;
+                    has_links = true;
+                }
+                _ => {}
+            }
+        }
+        match (value_constraint, has_links) {
+            (Some(value_constraint), _) => Ok(Some(UmlEntry::new(name, value_constraint, card))),
+            (None, true) => Ok(None),
+            (None, false) => Ok(Some(UmlEntry::new(name, ValueConstraint::Any, card))),
+        }
+    }
+
+    fn rdfnode2name(&self, node: &RDFNode) -> Result<Name, Shacl2UmlError> {
+        match node {
+            Object::Iri(iri) => Ok(Name::new(
+                self.current_prefixmap.qualify(iri).as_str(),
+                Some(iri.as_str()),
+            )),
+            Object::BlankNode(bnode) => Ok(Name::new(format!("_:{bnode}").as_str(), None)),
+            Object::Literal(lit) => Err(Shacl2UmlError::RDFNode2NameLiteral {
+                literal: lit.clone(),
+            }),
+            Object::Triple { .. } => Err(Shacl2UmlError::not_implemented(
+                format!("Quoted triple as shape identifier: {node}").as_str(),
+            )),
+        }
+    }
+
+    fn iri_ref2name(&self, iri_ref: &IriRef) -> Result<Name, Shacl2UmlError> {
+        match iri_ref {
+            IriRef::Iri(iri) => Ok(Name::new(
+                self.current_prefixmap.qualify(iri).as_str(),
+                Some(iri.as_str()),
+            )),
+            IriRef::Prefixed { prefix, local } => {
+                let iri = self.current_prefixmap.resolve_prefix_local(prefix, local)?;
+                Ok(Name::new(
+                    format!("{prefix}:{local}").as_str(),
+                    Some(iri.as_str()),
+                ))
+            }
+        }
+    }
+
+    fn value2name(&self, value: &Value) -> Result<Name, Shacl2UmlError> {
+        match value {
+            Value::Iri(iri_ref) => self.iri_ref2name(iri_ref),
+            Value::Literal(lit) => Ok(Name::new(lit.to_string().as_str(), None)),
+        }
+    }
+
+    fn path2name(&self, path: &SHACLPath) -> Name {
+        match path {
+            SHACLPath::Predicate { pred } => Name::new(
+                self.current_prefixmap.qualify(pred).as_str(),
+                Some(pred.as_str()),
+            ),
+            _ => Name::new(path2str(path, &self.current_prefixmap).as_str(), None),
+        }
+    }
+}
+
+/// Cardinality of a property shape, where SHACL's default is `{0,*}`
+fn mk_card(min: Option<isize>, max: Option<isize>) -> UmlCardinality {
+    let min = min.unwrap_or(0).max(0) as i32;
+    let max = max.map(|n| n as i32).unwrap_or(-1);
+    match (min, max) {
+        (1, 1) => UmlCardinality::OneOne,
+        (0, -1) => UmlCardinality::Star,
+        (0, 1) => UmlCardinality::Optional,
+        (1, -1) => UmlCardinality::Plus,
+        (m, n) if n == m => UmlCardinality::Fixed(m),
+        (m, n) => UmlCardinality::Range(m, n),
+    }
+}
+
+/// Paths with SPARQL property path syntax, qualifying the predicates
+pub(crate) fn path2str(path: &SHACLPath, prefixmap: &PrefixMap) -> String {
+    let paths2str = |paths: &[SHACLPath], separator: &str| {
+        let paths: Vec<String> = paths.iter().map(|p| path2str(p, prefixmap)).collect();
+        format!("({})", paths.join(separator))
+    };
+    match path {
+        SHACLPath::Predicate { pred } => prefixmap.qualify(pred),
+        SHACLPath::Alternative { paths } => paths2str(paths, " | "),
+        SHACLPath::Sequence { paths } => paths2str(paths, " / "),
+        SHACLPath::Inverse { path } => format!("^{}", path2str(path, prefixmap)),
+        SHACLPath::ZeroOrMore { path } => format!("({})*", path2str(path, prefixmap)),
+        SHACLPath::OneOrMore { path } => format!("({})+", path2str(path, prefixmap)),
+        SHACLPath::ZeroOrOne { path } => format!("({})?", path2str(path, prefixmap)),
+    }
+}
+
+pub(crate) fn node_kind2str(node_kind: &NodeKind) -> &'static str {
+    match node_kind {
+        NodeKind::Iri => "IRI",
+        NodeKind::Literal => "Literal",
+        NodeKind::BlankNode => "BlankNode",
+        NodeKind::BlankNodeOrIri => "BlankNodeOrIRI",
+        NodeKind::BlankNodeOrLiteral => "BlankNodeOrLiteral",
+        NodeKind::IRIOrLiteral => "IRIOrLiteral",
+    }
+}
+
+/// Message without language or, if there is none, the one with the first language
+pub(crate) fn message2label(messages: &MessageMap) -> Option<String> {
+    messages
+        .messages()
+        .iter()
+        .min_by_key(|(lang, _)| lang.as_ref().map(|lang| lang.value()))
+        .map(|(_, message)| message.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shacl_ast::ShaclParser;
+    use srdf::{RDFFormat, SRDFGraph};
+
+    #[test]
+    fn test_shacl2uml() {
+        let shacl_str = r#"prefix : <http://example.org/>
+prefix sh: <http://www.w3.org/ns/shacl#>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+
+:Person a sh:NodeShape ;
+  sh:property [
+    sh:path :name ; sh:datatype xsd:string ; sh:minCount 1 ; sh:maxCount 1
+  ] ;
+  sh:property [
+    sh:path :knows ; sh:node :Person
+  ] ;
+  sh:property [
+    sh:path :status ; sh:in (:Active :Inactive) ; sh:maxCount 1
+  ] .
+
+:Student a sh:NodeShape ;
+  sh:node :Person ;
+  sh:property [
+    sh:path :course ; sh:class :Course ; sh:minCount 1 ; sh:maxCount 10
+  ] .
+"#;
+        let rdf =
+            SRDFGraph::from_str(shacl_str, &RDFFormat::Turtle, None, &Default::default()).unwrap();
+        let schema = ShaclParser::new(rdf).parse().unwrap();
+        let mut converter = Shacl2Uml::new(&ShEx2UmlConfig::new());
+        converter.convert(&schema).unwrap();
+
+        let mut output = Vec::new();
+        converter
+            .as_mermaid(&mut output, &UmlGenerationMode::all())
+            .unwrap();
+        let mermaid = String::from_utf8(output).unwrap();
+        assert!(mermaid.starts_with("classDiagram\n"));
+        assert!(mermaid.contains(":name : xsd:string\n"));
+        assert!(mermaid.contains(":status : [ :Active :Inactive ] [0..1]\n"));
+        assert!(mermaid.contains(" --> \"0..*\" n1 : :knows\n"));
+        assert!(mermaid.contains(" --> \"1..10\" "));
+        assert!(mermaid.contains("n1 <|-- "));
+
+        let mut output = Vec::new();
+        converter
+            .as_dot(&mut output, &UmlGenerationMode::neighs(":Student"))
+            .unwrap();
+        let dot = String::from_utf8(output).unwrap();
+        assert!(dot.starts_with("digraph uml {"));
+        assert!(dot.contains("[arrowhead=empty];"));
+        assert!(dot.contains("[label=\":course\", headlabel=\"{1,10}\"];"));
+        assert!(!dot.contains(":knows"));
+
+        assert!(converter
+            .as_svg(&mut Vec::new(), &UmlGenerationMode::neighs(":Unknown"))
+            .is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use prefixmap::PrefixMapError;
+use shacl_ast::node_shape::NodeShape;
+use srdf::{literal::Literal, RDFNode};
+use thiserror::Error;
+
+use crate::shex_to_uml::{ShEx2UmlError, UmlError};
+
+#[derive(Error, Debug)]
+pub enum Shacl2UmlError {
+    #[error("Shacl2Uml error: Feature not implemented: {msg}")]
+    NotImplemented { msg: String },
+
+    #[error("Unexpected literal as shape identifier: {literal}")]
+    RDFNode2NameLiteral { literal: Literal },
+
+    #[error("Shape {node} not found in SHACL schema")]
+    ShapeNotFound { node: RDFNode },
+
+    #[error("Not expected node shape as property shape: {node_shape:?}")]
+    NotExpectedNodeShape { node_shape: Box<NodeShape> },
+
+    #[error("Not found label: {name}")]
+    NotFoundLabel { name: String },
+
+    #[error(transparent)]
+    UmlError {
+        #[from]
+        err: UmlError,
+    },
+
+    #[error(transparent)]
+    ShEx2UmlError { err: Box<ShEx2UmlError> },
+
+    #[error(transparent)]
+    PrefixMapError {
+        #[from]
+        err: PrefixMapError,
+    },
+}
+
+impl Shacl2UmlError {
+    pub fn not_implemented(msg: &str) -> Shacl2UmlError {
+        Shacl2UmlError::NotImplemented {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<ShEx2UmlError> for Shacl2UmlError {
+    fn from(err: ShEx2UmlError) -> Self {
+        Shacl2UmlError::ShEx2UmlError { err: Box::new(err) }
+    }
+}
@@ -23,8 +23,9 @@ impl Serialize for Cardinality {
             Cardinality::Star => serializer.serialize_str("Zero or more"),
             Cardinality::Plus => serializer.serialize_str("One or more"),
             Cardinality::Optional => serializer.serialize_str("Zero or one (optional)"),
-            Cardinality::Range(_, _) => todo!(),
-            Cardinality::Fixed(_) => todo!(),
+            Cardinality::Range(m, -1) => serializer.serialize_str(&format!("{m} or more")),
+            Cardinality::Range(m, n) => serializer.serialize_str(&format!("Between {m} and {n}")),
+            Cardinality::Fixed(m) => serializer.serialize_str(&format!("Exactly {m}")),
         }
     }
 }
@@ -8,6 +8,7 @@ pub struct ShapeTemplateEntry {
     pub name: Name,
     pub value_constraint: ValueConstraint,
     pub card: Cardinality,
+    pub description: Option<String>,
 }
 
 impl ShapeTemplateEntry {
@@ -20,6 +21,12 @@ impl ShapeTemplateEntry {
             name,
             value_constraint,
             card,
+            description: None,
         }
     }
+
+    pub fn with_description(mut self, description: Option<String>) -> Self {
+        self.description = description;
+        self
+    }
 }
@@ -0,0 +1,23 @@
+use serde::Serialize;
+
+use super::{Name, ShapeTemplateEntry};
+
+/// Represents a group of entries of a shape which are shown together, like the properties of a SHACL `sh:PropertyGroup`
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct EntryGroup {
+    pub name: Name,
+    pub entries: Vec<ShapeTemplateEntry>,
+}
+
+impl EntryGroup {
+    pub fn new(name: Name) -> EntryGroup {
+        EntryGroup {
+            name,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn add_entry(&mut self, entry: ShapeTemplateEntry) {
+        self.entries.push(entry)
+    }
+}
@@ -75,7 +75,9 @@ impl HtmlSchema {
 
     pub fn to_landing_html_schema(&self, config: &ShEx2HtmlConfig) -> LandingHtmlTemplate {
         let mut shapes = Vec::new();
-        for shape in self.shapes.values() {
+        let mut html_shapes: Vec<&HtmlShape> = self.shapes.values().collect();
+        html_shapes.sort_by_key(|shape| shape.name().name());
+        for shape in html_shapes {
             shapes.push(ShapeRef::new(
                 shape.name().name().as_str(),
                 shape.name().as_relative_href().unwrap_or_default().as_str(),
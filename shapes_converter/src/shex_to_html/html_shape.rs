@@ -1,15 +1,21 @@
 use serde::Serialize;
 
-use super::{Name, ShapeTemplateEntry};
+use super::{EntryGroup, Name, ShapeTemplateEntry};
 
 #[derive(Serialize, Debug, PartialEq, Clone)]
 pub struct HtmlShape {
     /// Name of this shape
     name: Name,
 
-    /// Sequence of entries
+    /// Description of this shape
+    description: Option<String>,
+
+    /// Sequence of entries which don't belong to a group
     entries: Vec<ShapeTemplateEntry>,
 
+    /// Sequence of groups of entries
+    groups: Vec<EntryGroup>,
+
     /// Sequence of shape expressions that this shape extends
     extends: Vec<Name>,
 
@@ -27,7 +33,9 @@ impl HtmlShape {
     pub fn new(name: Name, parent: Name) -> HtmlShape {
         HtmlShape {
             name,
+            description: None,
             entries: Vec::new(),
+            groups: Vec::new(),
             extends: Vec::new(),
             parent,
             children: Vec::new(),
@@ -47,6 +55,30 @@ impl HtmlShape {
         self.entries.iter()
     }
 
+    /// Adds an entry to the group with that name, creating the group if it doesn't exist
+    pub fn add_group_entry(&mut self, group: &Name, entry: ShapeTemplateEntry) {
+        match self.groups.iter_mut().find(|g| g.name == *group) {
+            Some(g) => g.add_entry(entry),
+            None => {
+                let mut g = EntryGroup::new(group.clone());
+                g.add_entry(entry);
+                self.groups.push(g)
+            }
+        }
+    }
+
+    pub fn groups(&self) -> impl Iterator<Item = &EntryGroup> {
+        self.groups.iter()
+    }
+
+    pub fn description(&self) -> Option<String> {
+        self.description.clone()
+    }
+
+    pub fn set_description(&mut self, description: &str) {
+        self.description = Some(description.to_string());
+    }
+
     pub fn add_extends(&mut self, name: &Name) {
         self.extends.push(name.clone())
     }
@@ -59,9 +91,17 @@ impl HtmlShape {
         for entry in other.entries() {
             self.add_entry(entry.clone())
         }
+        for group in other.groups() {
+            for entry in group.entries.iter() {
+                self.add_group_entry(&group.name, entry.clone())
+            }
+        }
         for extend in other.extends() {
             self.add_extends(extend)
         }
+        if self.description.is_none() {
+            self.description.clone_from(&other.description)
+        }
         match &self.svg_shape {
             Some(_svg) => {
                 // If the current shape has an svg, let it go
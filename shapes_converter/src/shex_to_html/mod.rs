@@ -3,6 +3,7 @@
 //!
 mod cardinality;
 mod entry;
+mod entry_group;
 mod html_schema;
 mod html_shape;
 mod name;
@@ -15,6 +16,7 @@ mod value_constraint;
 
 pub use cardinality::*;
 pub use entry::*;
+pub use entry_group::*;
 pub use html_schema::*;
 pub use html_shape::*;
 pub use name::*;
@@ -94,39 +94,11 @@ impl ShEx2Html {
     }
 
     pub fn create_name_for_schema(&self, _shex: &Schema) -> Name {
-        let path_name = &self.config.landing_page();
-        let os_str = path_name.file_stem().unwrap_or_else(|| OsStr::new("kiko"));
-        let str = os_str
-            .to_os_string()
-            .into_string()
-            .unwrap_or_else(|_| panic!("Creating name for schema. Should be a string {os_str:?}"));
-        Name::new(&str, None, self.config.landing_page())
+        landing_page2name(&self.config)
     }
 
     pub fn export_schema(&self) -> Result<(), ShEx2HtmlError> {
-        let environment = create_env();
-        let landing_page = self.config.landing_page();
-        let template = environment.get_template(self.config.landing_page_name.as_str())?;
-        let landing_page_name = self.config.landing_page_name();
-        let out = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .create(true)
-            .open(landing_page)
-            .map_err(|e| ShEx2HtmlError::ErrorCreatingLandingPage {
-                name: landing_page_name,
-                error: e,
-            })?;
-
-        /* Generate landing page */
-        template.render_to_write(self.current_html.to_landing_html_schema(&self.config), out)?;
-
-        /* Generate a page for each shape */
-        let shape_template = environment.get_template(self.config.shape_template_name.as_str())?;
-        for shape in self.current_html.shapes() {
-            generate_shape_page(shape, &shape_template, &self.config)?;
-        }
-        Ok(())
+        export_html_schema(&self.current_html, &self.config)
     }
 
     fn shape_label2name(
@@ -377,6 +349,47 @@ fn iri_ref2name(
     Ok(name)
 }
 
+/// Name of the landing page, which is the parent of the shapes
+pub(crate) fn landing_page2name(config: &ShEx2HtmlConfig) -> Name {
+    let path_name = &config.landing_page();
+    let os_str = path_name.file_stem().unwrap_or_else(|| OsStr::new("kiko"));
+    let str = os_str
+        .to_os_string()
+        .into_string()
+        .unwrap_or_else(|_| panic!("Creating name for schema. Should be a string {os_str:?}"));
+    Name::new(&str, None, config.landing_page())
+}
+
+/// Generates the landing page and a page for each shape of an HTML schema
+pub(crate) fn export_html_schema(
+    html_schema: &HtmlSchema,
+    config: &ShEx2HtmlConfig,
+) -> Result<(), ShEx2HtmlError> {
+    let environment = create_env();
+    let landing_page = config.landing_page();
+    let template = environment.get_template(config.landing_page_name.as_str())?;
+    let landing_page_name = config.landing_page_name();
+    let out = OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .create(true)
+        .open(landing_page)
+        .map_err(|e| ShEx2HtmlError::ErrorCreatingLandingPage {
+            name: landing_page_name,
+            error: e,
+        })?;
+
+    /* Generate landing page */
+    template.render_to_write(html_schema.to_landing_html_schema(config), out)?;
+
+    /* Generate a page for each shape */
+    let shape_template = environment.get_template(config.shape_template_name.as_str())?;
+    for shape in html_schema.shapes() {
+        generate_shape_page(shape, &shape_template, config)?;
+    }
+    Ok(())
+}
+
 pub fn create_env() -> Environment<'static> {
     let mut env = Environment::new();
     env.set_loader(path_loader("shapes_converter/default_templates"));
@@ -391,8 +404,8 @@ fn mk_card(min: &Option<i32>, max: &Option<i32>) -> Result<Cardinality, ShEx2Htm
         (0, -1) => Ok(Cardinality::Star),
         (0, 1) => Ok(Cardinality::Optional),
         (1, -1) => Ok(Cardinality::Plus),
-        (m, n) if m >= 0 && n > m => Ok(Cardinality::Fixed(m)),
-        (m, n) if m >= 0 && n > m => Ok(Cardinality::Range(m, n)),
+        (m, n) if m >= 0 && n == m => Ok(Cardinality::Fixed(m)),
+        (m, n) if m >= 0 && (n > m || n == -1) => Ok(Cardinality::Range(m, n)),
         _ => Err(ShEx2HtmlError::WrongCardinality { min, max }),
     }
 }
@@ -9,6 +9,7 @@ pub enum ValueConstraint {
     Any,
     Datatype(Name),
     Ref(Name),
+    Class(Name),
     ValueSet(Vec<Value>),
     None,
 }
@@ -10,9 +10,12 @@ mod uml;
 mod uml_cardinality;
 mod uml_class;
 mod uml_component;
+mod uml_dot;
 mod uml_entry;
 mod uml_error;
 mod uml_link;
+mod uml_mermaid;
+mod uml_svg;
 mod value_constraint;
 
 pub use name::*;
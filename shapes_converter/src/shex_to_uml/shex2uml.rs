@@ -16,7 +16,8 @@ use crate::{
 };
 
 use super::{
-    Name, NodeId, UmlCardinality, UmlClass, UmlComponent, UmlEntry, ValueConstraint, PLANTUML,
+    Name, NodeId, UmlCardinality, UmlClass, UmlComponent, UmlEntry, UmlRenderer, ValueConstraint,
+    PLANTUML,
 };
 use tempfile::TempDir;
 
@@ -57,69 +58,102 @@ impl ShEx2Uml {
         }
     }
 
-    /// Converts the current UML to an image
-    pub fn as_image<W: Write>(
+    /// Converts the current UML to an SVG image without external tools
+    pub fn as_svg<W: Write>(
         &self,
         writer: &mut W,
-        image_format: ImageFormat,
         mode: &UmlGenerationMode,
     ) -> Result<(), ShEx2UmlError> {
-        let tempdir = TempDir::new().map_err(|e| ShEx2UmlError::TempFileError { err: e })?;
-        let tempdir_path = tempdir.path();
-        let tempfile_path = tempdir_path.join("temp.uml");
-        let tempfile_name = tempfile_path.display().to_string();
-        let mut tempfile =
-            File::create(tempfile_path).map_err(|e| ShEx2UmlError::CreatingTempUMLFile {
-                tempfile_name: tempfile_name.clone(),
-                error: e,
-            })?;
-        self.as_plantuml(&mut tempfile, mode)?;
-        /*self.current_uml
-        .as_plantuml(&self.config, &mut tempfile, mode)?;*/
-        debug!("ShEx contents stored in temporary file:{}", tempfile_name);
-
-        let (out_param, out_file_name) = match image_format {
-            ImageFormat::PNG => ("-png", tempdir_path.join("temp.png")),
-            ImageFormat::SVG => ("-svg", tempdir_path.join("temp.svg")),
-        };
-        if let Some(plantuml_path) = &self.config.plantuml_path {
-            let mut command = Command::new("java");
-            command
-                .arg("-jar")
-                .arg(plantuml_path)
-                .arg("-o")
-                .arg(tempdir_path.to_string_lossy().to_string())
-                .arg(out_param)
-                .arg(tempfile_name);
-            let command_name = format!("{:?}", &command);
-            debug!("PLANTUML COMMAND:\n{command_name}");
-            let result = command.output();
-            match result {
-                Ok(_) => {
-                    let mut temp_file = File::open(out_file_name.as_path()).map_err(|e| {
-                        ShEx2UmlError::CantOpenGeneratedTempFile {
-                            generated_name: out_file_name.display().to_string(),
-                            error: e,
-                        }
-                    })?;
-                    copy(&mut temp_file, writer).map_err(|e| ShEx2UmlError::CopyingTempFile {
-                        temp_name: out_file_name.display().to_string(),
-                        error: e,
-                    })?;
+        match mode {
+            UmlGenerationMode::AllNodes => {
+                self.current_uml.as_svg_all(&self.config, writer)?;
+                Ok(())
+            }
+            UmlGenerationMode::Neighs(str) => {
+                if let Some(node_id) = self.current_uml.get_node(str) {
+                    self.current_uml
+                        .as_svg_neighs(&self.config, writer, &node_id)?;
                     Ok(())
+                } else {
+                    Err(ShEx2UmlError::NotFoundLabel { name: str.clone() })
+                }
+            }
+        }
+    }
+
+    /// Converts the current UML to a [Mermaid](https://mermaid.js.org/) class diagram
+    pub fn as_mermaid<W: Write>(
+        &self,
+        writer: &mut W,
+        mode: &UmlGenerationMode,
+    ) -> Result<(), ShEx2UmlError> {
+        match mode {
+            UmlGenerationMode::AllNodes => {
+                self.current_uml.as_mermaid_all(&self.config, writer)?;
+                Ok(())
+            }
+            UmlGenerationMode::Neighs(str) => {
+                if let Some(node_id) = self.current_uml.get_node(str) {
+                    self.current_uml
+                        .as_mermaid_neighs(&self.config, writer, &node_id)?;
+                    Ok(())
+                } else {
+                    Err(ShEx2UmlError::NotFoundLabel { name: str.clone() })
                 }
-                Err(e) => Err(ShEx2UmlError::PlantUMLCommandError {
-                    command: command_name,
-                    error: e,
-                }),
             }
-        } else {
-            Err(ShEx2UmlError::NoPlantUMLPath {
-                env_name: PLANTUML.to_string(),
-            })
         }
     }
 
+    /// Converts the current UML to a [Graphviz](https://graphviz.org/) DOT graph
+    pub fn as_dot<W: Write>(
+        &self,
+        writer: &mut W,
+        mode: &UmlGenerationMode,
+    ) -> Result<(), ShEx2UmlError> {
+        match mode {
+            UmlGenerationMode::AllNodes => {
+                self.current_uml.as_dot_all(&self.config, writer)?;
+                Ok(())
+            }
+            UmlGenerationMode::Neighs(str) => {
+                if let Some(node_id) = self.current_uml.get_node(str) {
+                    self.current_uml
+                        .as_dot_neighs(&self.config, writer, &node_id)?;
+                    Ok(())
+                } else {
+                    Err(ShEx2UmlError::NotFoundLabel { name: str.clone() })
+                }
+            }
+        }
+    }
+
+    /// Converts the current UML to an image.
+    ///
+    /// SVG images are generated with the renderer selected in the configuration, PNG images with PlantUML
+    pub fn as_image<W: Write>(
+        &self,
+        writer: &mut W,
+        image_format: ImageFormat,
+        mode: &UmlGenerationMode,
+    ) -> Result<(), ShEx2UmlError> {
+        match (&image_format, self.config.renderer()) {
+            (ImageFormat::SVG, UmlRenderer::Native) => self.as_svg(writer, mode),
+            _ => self.as_plantuml_image(writer, image_format, mode),
+        }
+    }
+
+    /// Converts the current UML to an image calling PlantUML
+    pub fn as_plantuml_image<W: Write>(
+        &self,
+        writer: &mut W,
+        image_format: ImageFormat,
+        mode: &UmlGenerationMode,
+    ) -> Result<(), ShEx2UmlError> {
+        plantuml2image(&self.config, writer, image_format, |tempfile| {
+            self.as_plantuml(tempfile, mode)
+        })
+    }
+
     pub fn convert(&mut self, shex: &Schema) -> Result<(), ShEx2UmlError> {
         self.current_prefixmap = shex.prefixmap().unwrap_or_default();
         if let Some(shapes) = shex.shapes() {
@@ -360,6 +394,71 @@ fn value_set2value_constraint(
     Ok(result)
 }
 
+/// Generates an image calling PlantUML with the contents written by `write_plantuml` in a temporary file
+pub(crate) fn plantuml2image<W, F>(
+    config: &ShEx2UmlConfig,
+    writer: &mut W,
+    image_format: ImageFormat,
+    write_plantuml: F,
+) -> Result<(), ShEx2UmlError>
+where
+    W: Write,
+    F: FnOnce(&mut File) -> Result<(), ShEx2UmlError>,
+{
+    let tempdir = TempDir::new().map_err(|e| ShEx2UmlError::TempFileError { err: e })?;
+    let tempdir_path = tempdir.path();
+    let tempfile_path = tempdir_path.join("temp.uml");
+    let tempfile_name = tempfile_path.display().to_string();
+    let mut tempfile =
+        File::create(tempfile_path).map_err(|e| ShEx2UmlError::CreatingTempUMLFile {
+            tempfile_name: tempfile_name.clone(),
+            error: e,
+        })?;
+    write_plantuml(&mut tempfile)?;
+    debug!("ShEx contents stored in temporary file:{}", tempfile_name);
+
+    let (out_param, out_file_name) = match image_format {
+        ImageFormat::PNG => ("-png", tempdir_path.join("temp.png")),
+        ImageFormat::SVG => ("-svg", tempdir_path.join("temp.svg")),
+    };
+    if let Some(plantuml_path) = &config.plantuml_path {
+        let mut command = Command::new("java");
+        command
+            .arg("-jar")
+            .arg(plantuml_path)
+            .arg("-o")
+            .arg(tempdir_path.to_string_lossy().to_string())
+            .arg(out_param)
+            .arg(tempfile_name);
+        let command_name = format!("{:?}", &command);
+        debug!("PLANTUML COMMAND:\n{command_name}");
+        let result = command.output();
+        match result {
+            Ok(_) => {
+                let mut temp_file = File::open(out_file_name.as_path()).map_err(|e| {
+                    ShEx2UmlError::CantOpenGeneratedTempFile {
+                        generated_name: out_file_name.display().to_string(),
+                        error: e,
+                    }
+                })?;
+                copy(&mut temp_file, writer).map_err(|e| ShEx2UmlError::CopyingTempFile {
+                    temp_name: out_file_name.display().to_string(),
+                    error: e,
+                })?;
+                Ok(())
+            }
+            Err(e) => Err(ShEx2UmlError::PlantUMLCommandError {
+                command: command_name,
+                error: e,
+            }),
+        }
+    } else {
+        Err(ShEx2UmlError::NoPlantUMLPath {
+            env_name: PLANTUML.to_string(),
+        })
+    }
+}
+
 fn iri_ref2name(
     iri_ref: &IriRef,
     _config: &ShEx2UmlConfig,
@@ -387,8 +486,8 @@ fn mk_card(min: &Option<i32>, max: &Option<i32>) -> Result<UmlCardinality, ShEx2
         (0, -1) => Ok(UmlCardinality::Star),
         (0, 1) => Ok(UmlCardinality::Optional),
         (1, -1) => Ok(UmlCardinality::Plus),
-        (m, n) if m >= 0 && n > m => Ok(UmlCardinality::Fixed(m)),
-        (m, n) if m >= 0 && n > m => Ok(UmlCardinality::Range(m, n)),
+        (m, n) if m >= 0 && n == m => Ok(UmlCardinality::Fixed(m)),
+        (m, n) if m >= 0 && (n > m || n == -1) => Ok(UmlCardinality::Range(m, n)),
         _ => Err(ShEx2UmlError::WrongCardinality { min, max }),
     }
 }
@@ -449,8 +548,66 @@ impl UmlGenerationMode {
 
 #[cfg(test)]
 mod tests {
-    // use super::*;
-    // use shex_compact::ShExParser;
+    use super::*;
+    use shex_compact::ShExParser;
+
+    #[test]
+    fn test_native_svg() {
+        let shex_str = r#"prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+
+:Person {
+  :name xsd:string ;
+  :knows @:Person * ;
+  :worksFor @:Company ?
+}
+
+:Student extends @:Person {
+  :course @:Course +
+}
+
+:Company {
+  :name xsd:string ;
+  :employee @:Person {1,10}
+}
+"#;
+        let shex = ShExParser::parse(shex_str, None).unwrap();
+        // No PlantUML is needed even if the JAR can't be found
+        let config = ShEx2UmlConfig::new().with_plantuml_path("/non-existent/plantuml.jar");
+        let mut converter = ShEx2Uml::new(&config);
+        converter.convert(&shex).unwrap();
+
+        let mut output = Vec::new();
+        converter
+            .as_image(&mut output, ImageFormat::SVG, &UmlGenerationMode::all())
+            .unwrap();
+        let svg = String::from_utf8(output).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<g id=\"uml-node-").count(), 4);
+        assert_eq!(svg.matches("<g ").count(), svg.matches("</g>").count());
+        assert!(svg.contains(">:Person</text>"));
+        assert!(svg.contains(":name : xsd:string</text>"));
+        assert!(svg.contains(">:knows *</text>"));
+        assert!(svg.contains(">:employee {1,10}</text>"));
+        assert_eq!(svg.matches("url(#uml-extends)").count(), 1);
+        assert_eq!(svg.matches("url(#uml-arrow)").count(), 4);
+
+        let mut output = Vec::new();
+        converter
+            .as_svg(&mut output, &UmlGenerationMode::neighs(":Company"))
+            .unwrap();
+        let svg = String::from_utf8(output).unwrap();
+        assert_eq!(svg.matches("<g id=\"uml-node-").count(), 2);
+        assert_eq!(svg.matches("url(#uml-arrow)").count(), 2);
+
+        let config = config.with_renderer(UmlRenderer::PlantUml);
+        let mut converter = ShEx2Uml::new(&config);
+        converter.convert(&shex).unwrap();
+        assert!(converter
+            .as_image(&mut Vec::new(), ImageFormat::SVG, &UmlGenerationMode::all())
+            .is_err());
+    }
 
     /*    #[test]
         fn test_simple() {
@@ -15,12 +15,28 @@ pub const PLANTUML: &str = "PLANTUML";
 
 pub const DEFAULT_REPLACE_IRI_BY_LABEL: bool = true;
 
+/// Tool used to generate the SVG images of UML diagrams
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UmlRenderer {
+    /// Layout and rendering implemented in Rust, which doesn't need external tools
+    #[default]
+    Native,
+
+    /// [PlantUML](https://plantuml.com/), which needs Java and the JAR file pointed by the `PLANTUML` environment variable
+    PlantUml,
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct ShEx2UmlConfig {
     pub plantuml_path: Option<PathBuf>,
     pub annotation_label: Vec<IriS>,
     pub replace_iri_by_label: Option<bool>,
     pub shex: Option<ShExConfig>,
+
+    /// Tool used to generate SVG images, PNG images always need PlantUML
+    #[serde(default)]
+    pub renderer: Option<UmlRenderer>,
 }
 
 impl ShEx2UmlConfig {
@@ -34,9 +50,14 @@ impl ShEx2UmlConfig {
             annotation_label: vec![IriS::new_unchecked(RDFS_LABEL_STR)],
             replace_iri_by_label: None,
             shex: Some(ShExConfig::default()),
+            renderer: None,
         }
     }
 
+    pub fn renderer(&self) -> UmlRenderer {
+        self.renderer.unwrap_or_default()
+    }
+
     pub fn shex_config(&self) -> ShExConfig {
         match &self.shex {
             None => ShExConfig::default(),
@@ -67,6 +88,11 @@ impl ShEx2UmlConfig {
         self.plantuml_path = Some(path.as_ref().to_owned());
         self
     }
+
+    pub fn with_renderer(mut self, renderer: UmlRenderer) -> Self {
+        self.renderer = Some(renderer);
+        self
+    }
 }
 
 #[derive(Error, Debug)]
@@ -1,7 +1,11 @@
+use super::uml_dot::uml2dot;
+use super::uml_mermaid::uml2mermaid;
+use super::uml_svg::uml2svg;
 use super::Name;
 use super::NodeId;
 use super::ShEx2UmlConfig;
 use super::UmlCardinality;
+use super::UmlClass;
 use super::UmlComponent;
 use super::UmlEntry;
 use super::UmlError;
@@ -195,6 +199,202 @@ impl Uml {
         writeln!(writer, "@enduml")?;
         Ok(())
     }
+
+    /// Writes all the components as an SVG image without external tools
+    pub fn as_svg_all<W: Write>(
+        &self,
+        config: &ShEx2UmlConfig,
+        writer: &mut W,
+    ) -> Result<(), UmlError> {
+        let (classes, links, extends) = self.diagram(config, None);
+        uml2svg(&classes, &links, &extends, config, writer)
+    }
+
+    /// Writes the component of `target_node` and its neighbours as an SVG image without external tools
+    pub fn as_svg_neighs<W: Write>(
+        &self,
+        config: &ShEx2UmlConfig,
+        writer: &mut W,
+        target_node: &NodeId,
+    ) -> Result<(), UmlError> {
+        let (classes, links, extends) = self.diagram(config, Some(target_node));
+        uml2svg(&classes, &links, &extends, config, writer)
+    }
+
+    /// Writes all the components as a [Mermaid](https://mermaid.js.org/syntax/classDiagram.html) class diagram
+    pub fn as_mermaid_all<W: Write>(
+        &self,
+        config: &ShEx2UmlConfig,
+        writer: &mut W,
+    ) -> Result<(), UmlError> {
+        let (classes, links, extends) = self.diagram(config, None);
+        uml2mermaid(&classes, &links, &extends, config, writer)
+    }
+
+    /// Writes the component of `target_node` and its neighbours as a Mermaid class diagram
+    pub fn as_mermaid_neighs<W: Write>(
+        &self,
+        config: &ShEx2UmlConfig,
+        writer: &mut W,
+        target_node: &NodeId,
+    ) -> Result<(), UmlError> {
+        let (classes, links, extends) = self.diagram(config, Some(target_node));
+        uml2mermaid(&classes, &links, &extends, config, writer)
+    }
+
+    /// Writes all the components as a [Graphviz DOT](https://graphviz.org/doc/info/lang.html) graph
+    pub fn as_dot_all<W: Write>(
+        &self,
+        config: &ShEx2UmlConfig,
+        writer: &mut W,
+    ) -> Result<(), UmlError> {
+        let (classes, links, extends) = self.diagram(config, None);
+        uml2dot(&classes, &links, &extends, config, writer)
+    }
+
+    /// Writes the component of `target_node` and its neighbours as a Graphviz DOT graph
+    pub fn as_dot_neighs<W: Write>(
+        &self,
+        config: &ShEx2UmlConfig,
+        writer: &mut W,
+        target_node: &NodeId,
+    ) -> Result<(), UmlError> {
+        let (classes, links, extends) = self.diagram(config, Some(target_node));
+        uml2dot(&classes, &links, &extends, config, writer)
+    }
+
+    /// Classes, links and extends to draw, either all of them or only the neighbours of `target_node`.
+    ///
+    /// Classes are sorted by node. Nodes without component, like references to shapes that are not declared,
+    /// only show their label
+    fn diagram(
+        &self,
+        config: &ShEx2UmlConfig,
+        target_node: Option<&NodeId>,
+    ) -> (Vec<DiagramClass<'_>>, Vec<&UmlLink>, Vec<(NodeId, NodeId)>) {
+        let is_selected = |n1: &NodeId, n2: &NodeId| match target_node {
+            None => true,
+            Some(target) => n1 == target || n2 == target,
+        };
+        let links: Vec<&UmlLink> = self
+            .links
+            .iter()
+            .filter(|link| is_selected(&link.source, &link.target))
+            .collect();
+        let extends: Vec<(NodeId, NodeId)> = self
+            .extends()
+            .filter(|(n1, n2)| is_selected(n1, n2))
+            .map(|(n1, n2)| (*n1, *n2))
+            .collect();
+        let mut nodes: HashSet<NodeId> = match target_node {
+            None => self.components.keys().copied().collect(),
+            Some(target) => HashSet::from([*target]),
+        };
+        nodes.extend(links.iter().flat_map(|link| [link.source, link.target]));
+        nodes.extend(extends.iter().flat_map(|(n1, n2)| [*n1, *n2]));
+        let mut nodes: Vec<NodeId> = nodes.into_iter().collect();
+        nodes.sort();
+        let classes = nodes
+            .into_iter()
+            .map(|node| match self.components.get(&node) {
+                Some(UmlComponent::UmlClass(class)) => DiagramClass {
+                    id: node,
+                    name: class_name(class, config),
+                    href: class.href(),
+                    entries: class.entries().collect(),
+                },
+                None => DiagramClass {
+                    id: node,
+                    name: self
+                        .labels
+                        .iter()
+                        .find(|(_, n)| **n == node)
+                        .map(|(label, _)| label.clone())
+                        .unwrap_or_else(|| node.to_string()),
+                    href: None,
+                    entries: Vec::new(),
+                },
+            })
+            .collect();
+        (classes, links, extends)
+    }
+}
+
+/// A class to be drawn in a diagram
+pub(crate) struct DiagramClass<'a> {
+    pub id: NodeId,
+    pub name: String,
+    pub href: Option<String>,
+    pub entries: Vec<&'a UmlEntry>,
+}
+
+fn class_name(class: &UmlClass, config: &ShEx2UmlConfig) -> String {
+    if config.replace_iri_by_label() {
+        class.label().unwrap_or_else(|| class.name())
+    } else {
+        class.name()
+    }
+}
+
+/// Text of an entry in diagrams other than PlantUML
+pub(crate) fn entry2text(entry: &UmlEntry, config: &ShEx2UmlConfig) -> String {
+    let line = format!(
+        "{} : {} {}",
+        name2text(&entry.name, config),
+        value_constraint2text(&entry.value_constraint, config),
+        card2text(&entry.card)
+    );
+    line.trim_end().to_string()
+}
+
+pub(crate) fn name2text(name: &Name, config: &ShEx2UmlConfig) -> String {
+    if config.replace_iri_by_label() {
+        name.label().unwrap_or_else(|| name.name())
+    } else {
+        name.name()
+    }
+}
+
+pub(crate) fn value_constraint2text(vc: &ValueConstraint, config: &ShEx2UmlConfig) -> String {
+    match vc {
+        ValueConstraint::Any => ".".to_string(),
+        ValueConstraint::Datatype(dt) => name2text(dt, config),
+        ValueConstraint::Ref(r) => format!("@{}", name2text(r, config)),
+        ValueConstraint::None => "".to_string(),
+        ValueConstraint::ValueSet(values) => {
+            let values: Vec<String> = values.iter().map(|v| name2text(v, config)).collect();
+            format!("[ {} ]", values.join(" "))
+        }
+    }
+}
+
+pub(crate) fn card2text(card: &UmlCardinality) -> String {
+    match card {
+        UmlCardinality::OneOne => "".to_string(),
+        UmlCardinality::Star => "*".to_string(),
+        UmlCardinality::Plus => "+".to_string(),
+        UmlCardinality::Optional => "?".to_string(),
+        UmlCardinality::Range(m, -1) => format!("{{{m},*}}"),
+        UmlCardinality::Range(m, n) => format!("{{{m},{n}}}"),
+        UmlCardinality::Fixed(m) => format!("{{{m}}}"),
+    }
+}
+
+/// Escapes the characters that have a special meaning in XML, which is used by SVG diagrams and DOT HTML-like labels.
+/// The apostrophe is written as a numeric reference because Graphviz doesn't know `&apos;`
+pub(crate) fn escape_xml(str: &str) -> String {
+    let mut result = String::with_capacity(str.len());
+    for c in str.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            _ => result.push(c),
+        }
+    }
+    result
 }
 
 fn component2plantuml<W: Write>(
@@ -302,6 +502,7 @@ fn card2plantuml(card: &UmlCardinality) -> String {
         UmlCardinality::Star => "*".to_string(),
         UmlCardinality::Plus => "+".to_string(),
         UmlCardinality::Optional => "?".to_string(),
+        UmlCardinality::Range(m, -1) => format!("{m}-*"),
         UmlCardinality::Range(m, n) => format!("{m}-{n}"),
         UmlCardinality::Fixed(m) => format!("{{{m}}}"),
     }
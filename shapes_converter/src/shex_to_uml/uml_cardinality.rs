@@ -6,6 +6,7 @@ pub enum UmlCardinality {
     Star,
     Plus,
     Optional,
+    /// Range with a minimum and a maximum, which is `-1` when unbounded
     Range(i32, i32),
     Fixed(i32),
 }
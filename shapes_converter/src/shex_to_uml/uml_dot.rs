@@ -0,0 +1,99 @@
+//! [Graphviz DOT](https://graphviz.org/doc/info/lang.html) rendering of UML diagrams.
+//!
+//! Classes are drawn with HTML-like labels: a table with the name of the class and its entries.
+use std::io::Write;
+
+use super::{card2text, entry2text, escape_xml, name2text, DiagramClass};
+use super::{NodeId, ShEx2UmlConfig, UmlError, UmlLink};
+
+const HEADER_COLOR: &str = "#FEFECE";
+
+/// Writes the classes, links and extends as a DOT directed graph
+pub(crate) fn uml2dot<W: Write>(
+    classes: &[DiagramClass],
+    links: &[&UmlLink],
+    extends: &[(NodeId, NodeId)],
+    config: &ShEx2UmlConfig,
+    writer: &mut W,
+) -> Result<(), UmlError> {
+    writeln!(writer, "digraph uml {{")?;
+    writeln!(
+        writer,
+        "  node [shape=plaintext, fontname=\"Helvetica\", fontsize=12];"
+    )?;
+    writeln!(writer, "  edge [fontname=\"Helvetica\", fontsize=10];")?;
+    for class in classes {
+        let entries: String = class
+            .entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{}<br align=\"left\"/>",
+                    escape_xml(&entry2text(entry, config))
+                )
+            })
+            .collect();
+        let url = class
+            .href
+            .as_ref()
+            .map(|href| format!(", URL={}", quote_dot(href)))
+            .unwrap_or_default();
+        writeln!(
+            writer,
+            "  {} [label=<<table border=\"0\" cellborder=\"1\" cellspacing=\"0\" cellpadding=\"4\">\
+<tr><td bgcolor=\"{HEADER_COLOR}\"><b>{}</b></td></tr>\
+<tr><td align=\"left\" balign=\"left\">{entries}</td></tr></table>>{url}];",
+            node2dot(&class.id),
+            escape_xml(&class.name)
+        )?;
+    }
+    for link in links {
+        let card = card2text(&link.card);
+        let card = if card.is_empty() {
+            "".to_string()
+        } else {
+            format!(", headlabel={}", quote_dot(&card))
+        };
+        writeln!(
+            writer,
+            "  {} -> {} [label={}{card}];",
+            node2dot(&link.source),
+            node2dot(&link.target),
+            quote_dot(&name2text(&link.name, config))
+        )?;
+    }
+    for (child, parent) in extends {
+        writeln!(
+            writer,
+            "  {} -> {} [arrowhead=empty];",
+            node2dot(child),
+            node2dot(parent)
+        )?;
+    }
+    writeln!(writer, "}}")?;
+    Ok(())
+}
+
+fn node2dot(node: &NodeId) -> String {
+    format!("n{node}")
+}
+
+/// Quoted DOT string
+fn quote_dot(str: &str) -> String {
+    format!("\"{}\"", str.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Escapes text inside HTML-like labels
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_dot() {
+        assert_eq!(quote_dot("a \"b\" \\c"), r#""a \"b\" \\c""#);
+        assert_eq!(
+            escape_xml("<a> & \"b\" 'c'"),
+            "&lt;a&gt; &amp; &quot;b&quot; &#39;c&#39;"
+        );
+    }
+}
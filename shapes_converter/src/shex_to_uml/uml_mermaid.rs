@@ -0,0 +1,133 @@
+//! [Mermaid](https://mermaid.js.org/syntax/classDiagram.html) rendering of UML diagrams.
+//!
+//! Cardinalities are written as UML multiplicities, like `[0..*]`, because Mermaid
+//! takes a trailing `*` of a member as a classifier and braces delimit the body of classes.
+use std::io::Write;
+
+use super::{name2text, value_constraint2text, DiagramClass};
+use super::{NodeId, ShEx2UmlConfig, UmlCardinality, UmlEntry, UmlError, UmlLink};
+
+/// Writes the classes, links and extends as a Mermaid class diagram
+pub(crate) fn uml2mermaid<W: Write>(
+    classes: &[DiagramClass],
+    links: &[&UmlLink],
+    extends: &[(NodeId, NodeId)],
+    config: &ShEx2UmlConfig,
+    writer: &mut W,
+) -> Result<(), UmlError> {
+    writeln!(writer, "classDiagram")?;
+    for class in classes {
+        let id = node2mermaid(&class.id);
+        let name = escape_mermaid(&class.name);
+        if class.entries.is_empty() {
+            writeln!(writer, "  class {id}[\"{name}\"]")?;
+        } else {
+            writeln!(writer, "  class {id}[\"{name}\"] {{")?;
+            for entry in class.entries.iter() {
+                writeln!(
+                    writer,
+                    "    {}",
+                    escape_mermaid(&entry2mermaid(entry, config))
+                )?;
+            }
+            writeln!(writer, "  }}")?;
+        }
+    }
+    for link in links {
+        let card = multiplicity(&link.card);
+        let card = if card.is_empty() {
+            "".to_string()
+        } else {
+            format!("\"{card}\" ")
+        };
+        writeln!(
+            writer,
+            "  {} --> {card}{} : {}",
+            node2mermaid(&link.source),
+            node2mermaid(&link.target),
+            escape_mermaid(&name2text(&link.name, config))
+        )?;
+    }
+    for (child, parent) in extends {
+        writeln!(
+            writer,
+            "  {} <|-- {}",
+            node2mermaid(parent),
+            node2mermaid(child)
+        )?;
+    }
+    for class in classes {
+        if let Some(href) = &class.href {
+            writeln!(
+                writer,
+                "  click {} href \"{}\"",
+                node2mermaid(&class.id),
+                escape_mermaid(href)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn node2mermaid(node: &NodeId) -> String {
+    format!("n{node}")
+}
+
+fn entry2mermaid(entry: &UmlEntry, config: &ShEx2UmlConfig) -> String {
+    let card = multiplicity(&entry.card);
+    let card = if card.is_empty() {
+        card
+    } else {
+        format!("[{card}]")
+    };
+    let line = format!(
+        "{} : {} {}",
+        name2text(&entry.name, config),
+        value_constraint2text(&entry.value_constraint, config),
+        card
+    );
+    line.trim_end().to_string()
+}
+
+/// UML multiplicity of a cardinality, which is empty for exactly one
+fn multiplicity(card: &UmlCardinality) -> String {
+    match card {
+        UmlCardinality::OneOne => "".to_string(),
+        UmlCardinality::Star => "0..*".to_string(),
+        UmlCardinality::Plus => "1..*".to_string(),
+        UmlCardinality::Optional => "0..1".to_string(),
+        UmlCardinality::Range(m, -1) => format!("{m}..*"),
+        UmlCardinality::Range(m, n) => format!("{m}..{n}"),
+        UmlCardinality::Fixed(m) => format!("{m}"),
+    }
+}
+
+/// Replaces the characters with a meaning in Mermaid class diagrams by entity codes
+fn escape_mermaid(str: &str) -> String {
+    let mut result = String::with_capacity(str.len());
+    for c in str.chars() {
+        match c {
+            '"' => result.push_str("#quot;"),
+            '{' => result.push_str("#123;"),
+            '}' => result.push_str("#125;"),
+            '~' => result.push_str("#126;"),
+            '\n' => result.push(' '),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_mermaid() {
+        assert_eq!(
+            escape_mermaid("\"a\" {b} ~c"),
+            "#quot;a#quot; #123;b#125; #126;c"
+        );
+        assert_eq!(multiplicity(&UmlCardinality::Range(1, 10)), "1..10");
+    }
+}
@@ -0,0 +1,495 @@
+//! SVG rendering of UML diagrams.
+//!
+//! The classes are placed in layers following a simple layered graph layout:
+//! the targets of links and the children of extends are put in lower layers than their sources,
+//! ignoring the arcs that close cycles, and the classes of each layer are ordered by
+//! the barycenter of their predecessors to reduce crossings.
+use std::collections::HashMap;
+use std::io::Write;
+
+use super::{card2text, entry2text, escape_xml, name2text, DiagramClass};
+use super::{NodeId, ShEx2UmlConfig, UmlError, UmlLink};
+
+const FONT_SIZE: f64 = 12.0;
+const CHAR_WIDTH: f64 = 7.0;
+const LINE_HEIGHT: f64 = 18.0;
+const HEADER_HEIGHT: f64 = 26.0;
+const PADDING: f64 = 8.0;
+const ICON_WIDTH: f64 = 22.0;
+const MIN_CLASS_WIDTH: f64 = 80.0;
+const H_GAP: f64 = 50.0;
+const V_GAP: f64 = 70.0;
+const MARGIN: f64 = 20.0;
+const PARALLEL_OFFSET: f64 = 14.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Rect {
+    fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    /// Point of the border of the rectangle in the direction of `to` from its center
+    fn border_point(&self, to: (f64, f64)) -> (f64, f64) {
+        let (cx, cy) = self.center();
+        let (dx, dy) = (to.0 - cx, to.1 - cy);
+        if dx == 0.0 && dy == 0.0 {
+            return (cx, cy);
+        }
+        let tx = if dx == 0.0 {
+            f64::INFINITY
+        } else {
+            (self.width / 2.0) / dx.abs()
+        };
+        let ty = if dy == 0.0 {
+            f64::INFINITY
+        } else {
+            (self.height / 2.0) / dy.abs()
+        };
+        let t = tx.min(ty);
+        (cx + t * dx, cy + t * dy)
+    }
+}
+
+/// Writes the classes, links and extends as an SVG image
+pub(crate) fn uml2svg<W: Write>(
+    classes: &[DiagramClass],
+    links: &[&UmlLink],
+    extends: &[(NodeId, NodeId)],
+    config: &ShEx2UmlConfig,
+    writer: &mut W,
+) -> Result<(), UmlError> {
+    let lines: HashMap<NodeId, Vec<String>> = classes
+        .iter()
+        .map(|class| {
+            let lines = class
+                .entries
+                .iter()
+                .map(|entry| entry2text(entry, config))
+                .collect();
+            (class.id, lines)
+        })
+        .collect();
+    let sizes: HashMap<NodeId, (f64, f64)> = classes
+        .iter()
+        .map(|class| (class.id, class_size(&class.name, &lines[&class.id])))
+        .collect();
+
+    let nodes: Vec<NodeId> = classes.iter().map(|class| class.id).collect();
+    // Parents of extends are placed above their children
+    let edges: Vec<(NodeId, NodeId)> = links
+        .iter()
+        .map(|link| (link.source, link.target))
+        .chain(extends.iter().map(|(child, parent)| (*parent, *child)))
+        .collect();
+    let (positions, width, height) = layout(&nodes, &edges, &sizes);
+
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.0}" height="{height:.0}" viewBox="0 0 {width:.0} {height:.0}">"#
+    )?;
+    writeln!(writer, "<defs>")?;
+    writeln!(
+        writer,
+        r##"<marker id="uml-arrow" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="8" markerHeight="8" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10" fill="none" stroke="#A80036"/></marker>"##
+    )?;
+    writeln!(
+        writer,
+        r##"<marker id="uml-extends" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="12" markerHeight="12" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z" fill="#FFFFFF" stroke="#A80036"/></marker>"##
+    )?;
+    writeln!(writer, "</defs>")?;
+    writeln!(
+        writer,
+        "<style>text {{ font-family: sans-serif; font-size: {FONT_SIZE}px; fill: #000000; }} .uml-class {{ fill: #FEFECE; stroke: #A80036; stroke-width: 1.5; }} .uml-edge {{ fill: none; stroke: #A80036; stroke-width: 1; }} .uml-name {{ font-weight: bold; }}</style>"
+    )?;
+
+    for class in classes {
+        class2svg(class, &positions[&class.id], &lines[&class.id], writer)?;
+    }
+
+    let mut parallel: HashMap<(NodeId, NodeId), usize> = HashMap::new();
+    let mut pair_counts: HashMap<(NodeId, NodeId), usize> = HashMap::new();
+    for (source, target) in links
+        .iter()
+        .map(|link| (link.source, link.target))
+        .chain(extends.iter().copied())
+    {
+        *pair_counts.entry(pair_key(source, target)).or_default() += 1;
+    }
+    let mut next_offset = |source: NodeId, target: NodeId| {
+        let key = pair_key(source, target);
+        let index = parallel.entry(key).or_default();
+        let total = pair_counts[&key] as f64;
+        let offset = (*index as f64 - (total - 1.0) / 2.0) * PARALLEL_OFFSET;
+        *index += 1;
+        // The offset must not depend on the direction of the edge
+        if (source, target) == key {
+            offset
+        } else {
+            -offset
+        }
+    };
+
+    for link in links {
+        let offset = next_offset(link.source, link.target);
+        let label = format!(
+            "{} {}",
+            name2text(&link.name, config),
+            card2text(&link.card)
+        );
+        edge2svg(
+            &positions[&link.source],
+            &positions[&link.target],
+            offset,
+            "uml-arrow",
+            Some((label.trim(), link.name.href())),
+            writer,
+        )?;
+    }
+    for (child, parent) in extends {
+        let offset = next_offset(*child, *parent);
+        edge2svg(
+            &positions[child],
+            &positions[parent],
+            offset,
+            "uml-extends",
+            None,
+            writer,
+        )?;
+    }
+    writeln!(writer, "</svg>")?;
+    Ok(())
+}
+
+fn pair_key(a: NodeId, b: NodeId) -> (NodeId, NodeId) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+fn text_width(str: &str) -> f64 {
+    str.chars().count() as f64 * CHAR_WIDTH
+}
+
+fn class_size(name: &str, lines: &[String]) -> (f64, f64) {
+    let header_width = ICON_WIDTH + text_width(name);
+    let width = lines
+        .iter()
+        .map(|line| text_width(line))
+        .fold(header_width, f64::max)
+        + 2.0 * PADDING;
+    let height = HEADER_HEIGHT + lines.len() as f64 * LINE_HEIGHT + PADDING;
+    (width.max(MIN_CLASS_WIDTH), height)
+}
+
+/// Assigns a layer to each node, so the sources of the edges are in upper layers than the targets.
+/// Edges that close a cycle are ignored
+fn layers(nodes: &[NodeId], edges: &[(NodeId, NodeId)]) -> HashMap<NodeId, usize> {
+    let mut successors: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    for (source, target) in edges {
+        if source != target {
+            successors.entry(*source).or_default().push(*target);
+        }
+    }
+    for targets in successors.values_mut() {
+        targets.sort();
+        targets.dedup();
+    }
+
+    // Depth first search that keeps the edges that don't point to a node in the current path
+    #[derive(Clone, Copy, PartialEq)]
+    enum State {
+        InPath,
+        Done,
+    }
+    let mut state: HashMap<NodeId, State> = HashMap::new();
+    let mut order = Vec::new();
+    let mut acyclic: Vec<(NodeId, NodeId)> = Vec::new();
+    for start in nodes {
+        if state.contains_key(start) {
+            continue;
+        }
+        let mut stack = vec![(*start, 0)];
+        state.insert(*start, State::InPath);
+        while let Some((node, next)) = stack.pop() {
+            let targets = successors.get(&node).map(|ts| ts.as_slice()).unwrap_or(&[]);
+            if let Some(target) = targets.get(next) {
+                stack.push((node, next + 1));
+                match state.get(target) {
+                    None => {
+                        acyclic.push((node, *target));
+                        state.insert(*target, State::InPath);
+                        stack.push((*target, 0));
+                    }
+                    Some(State::Done) => acyclic.push((node, *target)),
+                    Some(State::InPath) => {}
+                }
+            } else {
+                state.insert(node, State::Done);
+                order.push(node);
+            }
+        }
+    }
+
+    // Longest path from the sources following a topological order
+    let mut predecessors: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    for (source, target) in acyclic {
+        predecessors.entry(target).or_default().push(source);
+    }
+    let mut layer: HashMap<NodeId, usize> = HashMap::new();
+    for node in order.iter().rev() {
+        let value = predecessors
+            .get(node)
+            .into_iter()
+            .flatten()
+            .filter_map(|pred| layer.get(pred).map(|l| l + 1))
+            .max()
+            .unwrap_or(0);
+        layer.insert(*node, value);
+    }
+    layer
+}
+
+/// Returns the position of each node and the size of the diagram
+fn layout(
+    nodes: &[NodeId],
+    edges: &[(NodeId, NodeId)],
+    sizes: &HashMap<NodeId, (f64, f64)>,
+) -> (HashMap<NodeId, Rect>, f64, f64) {
+    let layer = layers(nodes, edges);
+    let depth = layer.values().max().map(|max| max + 1).unwrap_or(0);
+    let mut rows: Vec<Vec<NodeId>> = vec![Vec::new(); depth];
+    let mut sorted_nodes = nodes.to_vec();
+    sorted_nodes.sort();
+    for node in sorted_nodes {
+        rows[layer[&node]].push(node);
+    }
+
+    // Orders each layer by the mean position of the predecessors in the upper layers
+    let mut index: HashMap<NodeId, usize> = HashMap::new();
+    for row in rows.iter_mut() {
+        let mut keyed: Vec<(f64, NodeId)> = row
+            .iter()
+            .map(|node| {
+                let positions: Vec<f64> = edges
+                    .iter()
+                    .filter(|(_, target)| target == node)
+                    .filter_map(|(source, _)| index.get(source).map(|i| *i as f64))
+                    .collect();
+                let barycenter = if positions.is_empty() {
+                    f64::MAX
+                } else {
+                    positions.iter().sum::<f64>() / positions.len() as f64
+                };
+                (barycenter, *node)
+            })
+            .collect();
+        keyed.sort_by(|(b1, n1), (b2, n2)| b1.total_cmp(b2).then(n1.cmp(n2)));
+        *row = keyed.into_iter().map(|(_, node)| node).collect();
+        for (i, node) in row.iter().enumerate() {
+            index.insert(*node, i);
+        }
+    }
+
+    let row_width = |row: &Vec<NodeId>| {
+        row.iter().map(|node| sizes[node].0).sum::<f64>()
+            + H_GAP * row.len().saturating_sub(1) as f64
+    };
+    let max_width = rows.iter().map(row_width).fold(0.0, f64::max);
+    let mut positions = HashMap::new();
+    let mut y = MARGIN;
+    for row in rows.iter() {
+        let mut x = MARGIN + (max_width - row_width(row)) / 2.0;
+        let row_height = row.iter().map(|node| sizes[node].1).fold(0.0, f64::max);
+        for node in row {
+            let (width, height) = sizes[node];
+            positions.insert(
+                *node,
+                Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                },
+            );
+            x += width + H_GAP;
+        }
+        y += row_height + V_GAP;
+    }
+    let height = if rows.is_empty() {
+        2.0 * MARGIN
+    } else {
+        y - V_GAP + MARGIN
+    };
+    (positions, max_width + 2.0 * MARGIN, height)
+}
+
+fn class2svg<W: Write>(
+    class: &DiagramClass,
+    rect: &Rect,
+    lines: &[String],
+    writer: &mut W,
+) -> Result<(), UmlError> {
+    let Rect {
+        x,
+        y,
+        width,
+        height,
+    } = *rect;
+    writeln!(writer, r#"<g id="uml-node-{}">"#, class.id)?;
+    writeln!(
+        writer,
+        r#"<rect class="uml-class" x="{x:.1}" y="{y:.1}" width="{width:.1}" height="{height:.1}" rx="3"/>"#
+    )?;
+    let icon_x = x + PADDING + 7.0;
+    let text_y = y + HEADER_HEIGHT / 2.0 + FONT_SIZE / 3.0;
+    writeln!(
+        writer,
+        r##"<circle cx="{icon_x:.1}" cy="{:.1}" r="8" fill="#FF7700" stroke="#A80036"/><text x="{icon_x:.1}" y="{text_y:.1}" text-anchor="middle">S</text>"##,
+        y + HEADER_HEIGHT / 2.0
+    )?;
+    let name = text2svg(
+        &class.name,
+        x + PADDING + ICON_WIDTH,
+        text_y,
+        Some("uml-name"),
+        class.href.as_deref(),
+    );
+    writeln!(writer, "{name}")?;
+    if !lines.is_empty() {
+        let line_y = y + HEADER_HEIGHT;
+        writeln!(
+            writer,
+            r##"<line x1="{x:.1}" y1="{line_y:.1}" x2="{:.1}" y2="{line_y:.1}" stroke="#A80036"/>"##,
+            x + width
+        )?;
+    }
+    for (n, (line, entry)) in lines.iter().zip(class.entries.iter()).enumerate() {
+        let line_y = y + HEADER_HEIGHT + (n as f64 + 1.0) * LINE_HEIGHT - FONT_SIZE / 3.0;
+        let href = entry.name.href();
+        writeln!(
+            writer,
+            "{}",
+            text2svg(line, x + PADDING, line_y, None, href.as_deref())
+        )?;
+    }
+    writeln!(writer, "</g>")?;
+    Ok(())
+}
+
+fn edge2svg<W: Write>(
+    source: &Rect,
+    target: &Rect,
+    offset: f64,
+    marker: &str,
+    label: Option<(&str, Option<String>)>,
+    writer: &mut W,
+) -> Result<(), UmlError> {
+    let (path, label_point) = if source == target {
+        // Self references are drawn as a loop on the right side of the class
+        let (x, y) = (source.x + source.width, source.y + source.height / 2.0);
+        let shift = offset.abs() + 20.0;
+        (
+            format!(
+                "M {x:.1} {:.1} C {:.1} {:.1} {:.1} {:.1} {x:.1} {:.1}",
+                y - 10.0,
+                x + 2.0 * shift,
+                y - 30.0,
+                x + 2.0 * shift,
+                y + 30.0,
+                y + 10.0
+            ),
+            (x + 2.0 * shift, y),
+        )
+    } else {
+        let (sx, sy) = source.center();
+        let (tx, ty) = target.center();
+        let length = ((tx - sx).powi(2) + (ty - sy).powi(2)).sqrt();
+        let (px, py) = (-(ty - sy) / length * offset, (tx - sx) / length * offset);
+        let start = source.border_point((tx + px, ty + py));
+        let end = target.border_point((sx + px, sy + py));
+        let (start, end) = ((start.0 + px, start.1 + py), (end.0 + px, end.1 + py));
+        (
+            format!(
+                "M {:.1} {:.1} L {:.1} {:.1}",
+                start.0, start.1, end.0, end.1
+            ),
+            ((start.0 + end.0) / 2.0, (start.1 + end.1) / 2.0),
+        )
+    };
+    writeln!(
+        writer,
+        r#"<path class="uml-edge" d="{path}" marker-end="url(#{marker})"/>"#
+    )?;
+    if let Some((label, href)) = label {
+        if !label.is_empty() {
+            writeln!(
+                writer,
+                "{}",
+                text2svg(
+                    label,
+                    label_point.0 + 4.0,
+                    label_point.1 - 4.0,
+                    None,
+                    href.as_deref()
+                )
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn text2svg(str: &str, x: f64, y: f64, class: Option<&str>, href: Option<&str>) -> String {
+    let class = class
+        .map(|c| format!(r#" class="{c}""#))
+        .unwrap_or_default();
+    let text = format!(
+        r#"<text x="{x:.1}" y="{y:.1}"{class}>{}</text>"#,
+        escape_xml(str)
+    );
+    match href {
+        Some(href) => format!(r#"<a href="{}">{text}</a>"#, escape_xml(href)),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layers_with_cycle() {
+        let (a, b, c, d) = (
+            NodeId::new(1),
+            NodeId::new(2),
+            NodeId::new(3),
+            NodeId::new(4),
+        );
+        let edges = vec![(a, b), (b, c), (c, a), (a, c), (d, d)];
+        let layer = layers(&[a, b, c, d], &edges);
+        assert_eq!(layer[&a], 0);
+        assert_eq!(layer[&b], 1);
+        assert_eq!(layer[&c], 2);
+        assert_eq!(layer[&d], 0);
+    }
+
+    #[test]
+    fn test_border_point() {
+        let rect = Rect {
+            x: 0.0,
+            y: 0.0,
+            width: 100.0,
+            height: 40.0,
+        };
+        assert_eq!(rect.border_point((50.0, 200.0)), (50.0, 40.0));
+        assert_eq!(rect.border_point((300.0, 20.0)), (100.0, 20.0));
+    }
+}
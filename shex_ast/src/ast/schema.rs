@@ -8,7 +8,7 @@ use std::path::{Path, PathBuf};
 use std::{fs, io};
 use tracing::debug;
 
-use super::{IriOrStr, SemAct, ShapeDecl, ShapeExpr};
+use super::{IriOrStr, SemAct, ShapeDecl, ShapeExpr, TripleExpr};
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 pub struct Schema {
@@ -270,6 +270,139 @@ impl Schema {
             Ok(None)
         }
     }
+
+    /// Number of arcs that validation may follow from a focus node to reach the nodes whose
+    /// outgoing arcs are checked by nested shapes or shape references.
+    ///
+    /// It is `None` when there is no bound: shape references are recursive or reach
+    /// shapes or triple expressions that are not declared in the schema
+    pub fn reference_depth(&self) -> Option<usize> {
+        let mut reach = 0;
+        for shape_decl in self.shapes().unwrap_or_default() {
+            reach = reach.max(self.shape_expr_reach(&shape_decl.shape_expr, &mut Vec::new())?);
+        }
+        if let Some(start) = &self.start {
+            reach = reach.max(self.shape_expr_reach(start, &mut Vec::new())?);
+        }
+        // The outgoing arcs of the focus node are always checked
+        Some(reach.saturating_sub(1))
+    }
+
+    /// Checks if some triple constraint is inverse, so validation needs the incoming arcs of the nodes
+    pub fn has_inverse_triple_constraints(&self) -> bool {
+        self.shapes()
+            .unwrap_or_default()
+            .iter()
+            .map(|shape_decl| &shape_decl.shape_expr)
+            .chain(self.start.iter())
+            .any(shape_expr_has_inverse)
+    }
+
+    /// Number of nodes in a chain of arcs from a node whose outgoing arcs are checked by `se`,
+    /// where `visiting` contains the shapes whose references are being followed
+    fn shape_expr_reach(
+        &self,
+        se: &ShapeExpr,
+        visiting: &mut Vec<ShapeExprLabel>,
+    ) -> Option<usize> {
+        match se {
+            ShapeExpr::ShapeOr { shape_exprs } | ShapeExpr::ShapeAnd { shape_exprs } => {
+                let mut reach = 0;
+                for wrapper in shape_exprs {
+                    reach = reach.max(self.shape_expr_reach(&wrapper.se, visiting)?);
+                }
+                Some(reach)
+            }
+            ShapeExpr::ShapeNot { shape_expr } => self.shape_expr_reach(&shape_expr.se, visiting),
+            ShapeExpr::NodeConstraint(_) | ShapeExpr::External => Some(0),
+            ShapeExpr::Shape(shape) => {
+                let mut reach = match &shape.expression {
+                    Some(wrapper) => 1 + self.triple_expr_reach(&wrapper.te, visiting)?,
+                    None => 1,
+                };
+                for label in shape.extends.iter().flatten() {
+                    reach = reach.max(self.label_reach(label, visiting)?);
+                }
+                Some(reach)
+            }
+            ShapeExpr::Ref(label) => self.label_reach(label, visiting),
+        }
+    }
+
+    fn triple_expr_reach(
+        &self,
+        te: &TripleExpr,
+        visiting: &mut Vec<ShapeExprLabel>,
+    ) -> Option<usize> {
+        match te {
+            TripleExpr::EachOf { expressions, .. } | TripleExpr::OneOf { expressions, .. } => {
+                let mut reach = 0;
+                for wrapper in expressions {
+                    reach = reach.max(self.triple_expr_reach(&wrapper.te, visiting)?);
+                }
+                Some(reach)
+            }
+            TripleExpr::TripleConstraint { value_expr, .. } => match value_expr {
+                Some(se) => self.shape_expr_reach(se, visiting),
+                None => Some(0),
+            },
+            TripleExpr::TripleExprRef(_) => None,
+        }
+    }
+
+    fn label_reach(
+        &self,
+        label: &ShapeExprLabel,
+        visiting: &mut Vec<ShapeExprLabel>,
+    ) -> Option<usize> {
+        if visiting.contains(label) {
+            return None;
+        }
+        let se = match label {
+            ShapeExprLabel::IriRef { value } => self.find_shape_by_iri_ref(value).ok()??,
+            ShapeExprLabel::BNode { .. } => {
+                self.shapes()?
+                    .into_iter()
+                    .find(|shape_decl| shape_decl.id == *label)?
+                    .shape_expr
+            }
+            ShapeExprLabel::Start => self.start()?,
+        };
+        visiting.push(label.clone());
+        let reach = self.shape_expr_reach(&se, visiting);
+        visiting.pop();
+        reach
+    }
+}
+
+fn shape_expr_has_inverse(se: &ShapeExpr) -> bool {
+    match se {
+        ShapeExpr::ShapeOr { shape_exprs } | ShapeExpr::ShapeAnd { shape_exprs } => shape_exprs
+            .iter()
+            .any(|wrapper| shape_expr_has_inverse(&wrapper.se)),
+        ShapeExpr::ShapeNot { shape_expr } => shape_expr_has_inverse(&shape_expr.se),
+        ShapeExpr::Shape(shape) => shape
+            .expression
+            .iter()
+            .any(|wrapper| triple_expr_has_inverse(&wrapper.te)),
+        ShapeExpr::NodeConstraint(_) | ShapeExpr::External | ShapeExpr::Ref(_) => false,
+    }
+}
+
+fn triple_expr_has_inverse(te: &TripleExpr) -> bool {
+    match te {
+        TripleExpr::EachOf { expressions, .. } | TripleExpr::OneOf { expressions, .. } => {
+            expressions
+                .iter()
+                .any(|wrapper| triple_expr_has_inverse(&wrapper.te))
+        }
+        TripleExpr::TripleConstraint {
+            inverse,
+            value_expr,
+            ..
+        } => *inverse == Some(true) || value_expr.iter().any(|se| shape_expr_has_inverse(se)),
+        TripleExpr::TripleExprRef(_) => false,
+    }
 }
 
 impl Default for Schema {
@@ -1,12 +1,13 @@
 use crate::{
-    ast::Schema as SchemaJson, compiled::schema_json_compiler::SchemaJsonCompiler, CResult,
+    ast::Schema as SchemaJson, compiled::schema_json_compiler::SchemaJsonCompiler, BNode, CResult,
     CompiledSchemaError, ShapeExprLabel, ShapeLabelIdx,
 };
 use iri_s::IriS;
 use prefixmap::{IriRef, PrefixMap};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 
+use super::sem_act::SemAct;
 use super::shape_expr::ShapeExpr;
 use super::shape_label::ShapeLabel;
 
@@ -18,6 +19,10 @@ pub struct CompiledSchema {
     shapes: HashMap<ShapeLabelIdx, (ShapeLabel, ShapeExpr)>,
     shape_label_counter: ShapeLabelIdx,
     prefixmap: PrefixMap,
+    internal_shapes: HashSet<ShapeLabelIdx>,
+    abstract_shapes: HashSet<ShapeLabelIdx>,
+    descendants: HashMap<ShapeLabelIdx, Vec<ShapeLabelIdx>>,
+    start_acts: Vec<SemAct>,
 }
 
 impl CompiledSchema {
@@ -27,6 +32,10 @@ impl CompiledSchema {
             shape_label_counter: ShapeLabelIdx::default(),
             shapes: HashMap::new(),
             prefixmap: PrefixMap::new(),
+            internal_shapes: HashSet::new(),
+            abstract_shapes: HashSet::new(),
+            descendants: HashMap::new(),
+            start_acts: Vec::new(),
         }
     }
 
@@ -38,6 +47,15 @@ impl CompiledSchema {
         self.prefixmap.clone()
     }
 
+    pub fn set_start_acts(&mut self, start_acts: Vec<SemAct>) {
+        self.start_acts = start_acts;
+    }
+
+    /// Semantic actions that are run before starting the validation
+    pub fn start_acts(&self) -> &[SemAct] {
+        &self.start_acts
+    }
+
     pub fn add_shape(&mut self, shape_label: ShapeLabel, se: ShapeExpr) {
         let idx = self.shape_label_counter;
         self.shape_labels_map.insert(shape_label.clone(), idx);
@@ -45,6 +63,81 @@ impl CompiledSchema {
         self.shape_label_counter.incr()
     }
 
+    /// Adds a placeholder for a shape expression which is not declared in the schema
+    /// but appears nested inside another one, like the value expression of a triple constraint.
+    ///
+    /// The placeholder must later be filled with `replace_shape`
+    pub fn add_internal_shape(&mut self) -> ShapeLabelIdx {
+        let idx = self.shape_label_counter;
+        let label = ShapeLabel::BNode(BNode::new(format!("internal{idx}").as_str()));
+        self.shapes.insert(idx, (label, ShapeExpr::Empty));
+        self.internal_shapes.insert(idx);
+        self.shape_label_counter.incr();
+        idx
+    }
+
+    /// Returns `true` if the shape expression has been generated by the compiler
+    /// and doesn't correspond to a shape declaration of the schema
+    pub fn is_internal(&self, idx: &ShapeLabelIdx) -> bool {
+        self.internal_shapes.contains(idx)
+    }
+
+    pub fn set_abstract(&mut self, idx: &ShapeLabelIdx) {
+        self.abstract_shapes.insert(*idx);
+    }
+
+    /// Returns `true` if the shape has been declared as `ABSTRACT`
+    pub fn is_abstract(&self, idx: &ShapeLabelIdx) -> bool {
+        self.abstract_shapes.contains(idx)
+    }
+
+    /// Shapes that extend directly or indirectly the shape with index `idx`
+    pub fn descendants(&self, idx: &ShapeLabelIdx) -> &[ShapeLabelIdx] {
+        match self.descendants.get(idx) {
+            Some(ds) => ds.as_slice(),
+            None => &[],
+        }
+    }
+
+    /// Shapes that are directly extended by the shape declaration with index `idx`
+    ///
+    /// Extensions are declared in the shape or in the shapes that appear in a top level `AND`
+    pub fn parents(&self, idx: &ShapeLabelIdx) -> Vec<ShapeLabelIdx> {
+        match self.shapes.get(idx) {
+            Some((_, ShapeExpr::Shape(shape))) => shape.extends().clone(),
+            Some((_, ShapeExpr::ShapeAnd { exprs, .. })) => exprs
+                .iter()
+                .flat_map(|e| match e {
+                    ShapeExpr::Shape(shape) => shape.extends().clone(),
+                    _ => Vec::new(),
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Recalculates the descendants of each shape from the `EXTENDS` declarations
+    pub fn update_descendants(&mut self) -> Result<()> {
+        let mut descendants: HashMap<ShapeLabelIdx, Vec<ShapeLabelIdx>> = HashMap::new();
+        for idx in self.shapes.keys() {
+            let mut visited = HashSet::new();
+            let mut pending = self.parents(idx);
+            while let Some(parent) = pending.pop() {
+                if parent == *idx {
+                    return Err(CompiledSchemaError::CyclicExtends {
+                        shape_label: self.shapes[idx].0.clone(),
+                    });
+                }
+                if visited.insert(parent) {
+                    descendants.entry(parent).or_default().push(*idx);
+                    pending.extend(self.parents(&parent));
+                }
+            }
+        }
+        self.descendants = descendants;
+        Ok(())
+    }
+
     pub fn get_shape_expr(&self, shape_label: &ShapeLabel) -> Option<&ShapeExpr> {
         if let Some(idx) = self.find_shape_label_idx(shape_label) {
             self.shapes.get(idx).map(|(_label, se)| se)
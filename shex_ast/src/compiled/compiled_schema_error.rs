@@ -19,6 +19,14 @@ pub enum CompiledSchemaError {
     #[error("SchemaJson Error")]
     SchemaJsonError(#[from] ast::SchemaJsonError),
 
+    #[error("Shape {shape_label} extends itself")]
+    CyclicExtends { shape_label: ShapeLabel },
+
+    #[error(
+        "Shape {shape_label} has a negated reference to itself, directly or through other shapes"
+    )]
+    NegatedReferenceCycle { shape_label: ShapeLabel },
+
     #[error("Duplicated triple expression label in schema: {label:?}")]
     DuplicatedTripleExprLabel { label: TripleExprLabel },
 
@@ -91,9 +99,85 @@ pub enum CompiledSchemaError {
         node: String,
     },
 
+    #[error("NumericValue of {node} = {found} doesn't match minExclusive of {expected}")]
+    MinExclusiveError {
+        expected: NumericLiteral,
+        found: NumericLiteral,
+        node: String,
+    },
+
+    #[error("NumericValue of {node} = {found} doesn't match maxInclusive of {expected}")]
+    MaxInclusiveError {
+        expected: NumericLiteral,
+        found: NumericLiteral,
+        node: String,
+    },
+
+    #[error("NumericValue of {node} = {found} doesn't match maxExclusive of {expected}")]
+    MaxExclusiveError {
+        expected: NumericLiteral,
+        found: NumericLiteral,
+        node: String,
+    },
+
+    #[error("TotalDigits of {node} = {found} doesn't match {expected}")]
+    TotalDigitsError {
+        expected: usize,
+        found: usize,
+        node: String,
+    },
+
+    #[error("FractionDigits of {node} = {found} doesn't match {expected}")]
+    FractionDigitsError {
+        expected: usize,
+        found: usize,
+        node: String,
+    },
+
+    #[error("Node {node} is not a decimal value, so it can't be checked against {facet}")]
+    NonDecimal { node: String, facet: String },
+
+    #[error("Node {node} is not in value set {value_set}")]
+    NotInValueSet { node: String, value_set: String },
+
+    #[error("Node {node} matches {value} but it is excluded by {exclusion}")]
+    ValueSetExclusion {
+        node: String,
+        value: String,
+        exclusion: String,
+    },
+
     #[error("Node {node} is not a numeric literal")]
     NonNumeric { node: String },
 
+    #[error("Node {node} doesn't match pattern /{regex}/{flags}")]
+    PatternError {
+        regex: String,
+        flags: String,
+        node: String,
+    },
+
+    #[error("Blank node {node} can't be checked against pattern /{regex}/{flags}")]
+    PatternBNode {
+        regex: String,
+        flags: String,
+        node: String,
+    },
+
+    #[error("Invalid regular expression /{regex}/{flags}: {error}")]
+    InvalidRegex {
+        regex: String,
+        flags: String,
+        error: String,
+    },
+
+    #[error("Unknown flag {flag} in regular expression /{regex}/{flags}")]
+    UnknownRegexFlag {
+        regex: String,
+        flags: String,
+        flag: char,
+    },
+
     #[error("Shape label not found {shape_label}")]
     ShapeLabelNotFound { shape_label: ShapeLabel },
 
@@ -1,7 +1,10 @@
 use std::fmt::Display;
 
 use iri_s::IriS;
+use prefixmap::IriRef;
+use srdf::numeric_literal::NumericLiteral;
 use srdf::{lang::Lang, literal::Literal, Object};
+use srdf::{XSD_BOOLEAN, XSD_DECIMAL, XSD_DOUBLE, XSD_INTEGER, XSD_STRING};
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum ObjectValue {
@@ -9,25 +12,39 @@ pub enum ObjectValue {
     ObjectLiteral {
         value: String,
         language: Option<Lang>,
-        // type_: Option<String>,
+        /// Datatype of the literal. `None` represents `xsd:string` or `rdf:langString`
+        type_: Option<IriS>,
     },
 }
 
 impl ObjectValue {
+    /// Creates an object value from a literal.
+    /// Returns `None` if the datatype of the literal is a prefixed name that has not been resolved
+    pub fn literal(lit: &Literal) -> Option<ObjectValue> {
+        let (value, language, type_) = literal_parts(lit)?;
+        Some(ObjectValue::ObjectLiteral {
+            value,
+            language,
+            type_,
+        })
+    }
+
     pub(crate) fn match_value(&self, object: &Object) -> bool {
         match self {
             ObjectValue::IriRef(iri_expected) => match object {
                 Object::Iri(iri) => iri == iri_expected,
                 _ => false,
             },
-            ObjectValue::ObjectLiteral { value, language } => match object {
-                Object::Literal(lit) => match lit {
-                    Literal::StringLiteral { lexical_form, lang } => {
-                        value == lexical_form && language == lang
+            ObjectValue::ObjectLiteral {
+                value,
+                language,
+                type_,
+            } => match object {
+                Object::Literal(lit) => match literal_parts(lit) {
+                    Some((lexical_form, lang, datatype)) => {
+                        *value == lexical_form && *language == lang && *type_ == datatype
                     }
-                    Literal::DatatypeLiteral { .. } => todo!(),
-                    Literal::BooleanLiteral(_) => todo!(),
-                    Literal::NumericLiteral(_) => todo!(),
+                    None => false,
                 },
                 _ => false,
             },
@@ -35,6 +52,33 @@ impl ObjectValue {
     }
 }
 
+/// Splits a literal in its lexical form, language tag and datatype
+/// so literals can be compared as RDF terms independently of their representation
+pub(crate) fn literal_parts(lit: &Literal) -> Option<(String, Option<Lang>, Option<IriS>)> {
+    match lit {
+        Literal::StringLiteral { lexical_form, lang } => {
+            Some((lexical_form.clone(), lang.clone(), None))
+        }
+        Literal::DatatypeLiteral {
+            lexical_form,
+            datatype,
+        } => match datatype {
+            IriRef::Iri(iri) if *iri == *XSD_STRING => Some((lexical_form.clone(), None, None)),
+            IriRef::Iri(iri) => Some((lexical_form.clone(), None, Some(iri.clone()))),
+            IriRef::Prefixed { .. } => None,
+        },
+        Literal::NumericLiteral(n) => {
+            let datatype = match n {
+                NumericLiteral::Integer(_) => XSD_INTEGER.clone(),
+                NumericLiteral::Decimal(_) => XSD_DECIMAL.clone(),
+                NumericLiteral::Double(_) => XSD_DOUBLE.clone(),
+            };
+            Some((n.lexical_form(), None, Some(datatype)))
+        }
+        Literal::BooleanLiteral(_) => Some((lit.lexical_form(), None, Some(XSD_BOOLEAN.clone()))),
+    }
+}
+
 impl Display for ObjectValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -42,14 +86,16 @@ impl Display for ObjectValue {
                 write!(f, "{iri}")?;
                 Ok(())
             }
-            ObjectValue::ObjectLiteral { value, language } => {
+            ObjectValue::ObjectLiteral {
+                value,
+                language,
+                type_,
+            } => {
                 write!(f, "\"{value}\"")?;
-                match language {
-                    None => Ok(()),
-                    Some(lang) => {
-                        write!(f, "@{lang}")?;
-                        Ok(())
-                    }
+                match (language, type_) {
+                    (Some(lang), _) => write!(f, "{lang}"),
+                    (None, Some(dt)) => write!(f, "^^<{dt}>"),
+                    (None, None) => Ok(()),
                 }
             }
         }
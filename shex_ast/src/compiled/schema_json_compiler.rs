@@ -1,12 +1,15 @@
 use crate::compiled::annotation::Annotation;
 use crate::compiled::compiled_schema::CompiledSchema;
 use crate::compiled::object_value::ObjectValue;
-use crate::compiled::sem_act::SemAct;
+use crate::compiled::sem_act::{SemAct, TripleConstraintSemActs};
 use crate::compiled::shape::Shape;
 use crate::compiled::shape_expr::ShapeExpr;
 use crate::compiled::shape_label::ShapeLabel;
 use crate::compiled::value_set::ValueSet;
-use crate::compiled::value_set_value::ValueSetValue;
+use crate::compiled::value_set_value::{
+    IriExclusion, IriRefOrWildcard, LangOrWildcard, LanguageExclusion, LiteralExclusion,
+    StringOrWildcard, ValueSetValue,
+};
 use crate::ShapeExprLabel;
 use crate::{ast, ast::Schema as SchemaJson, CompiledSchemaError, ShapeLabelIdx};
 use crate::{CResult, Cond, Node, Pred};
@@ -15,8 +18,12 @@ use lazy_static::lazy_static;
 use prefixmap::IriRef;
 use rbe::{rbe::Rbe, Component, MatchCond, Max, Min, RbeTable};
 use rbe::{Cardinality, Pending, RbeError, SingleCond};
+use regex::{Regex, RegexBuilder};
 use srdf::literal::Literal;
+use srdf::numeric_literal::NumericLiteral;
 use srdf::Object;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use tracing::debug;
 
 use super::node_constraint::NodeConstraint;
@@ -47,8 +54,125 @@ impl SchemaJsonCompiler {
     ) -> CResult<()> {
         debug!("Compiling schema_json: {compiled_schema:?}");
         compiled_schema.set_prefixmap(schema_json.prefixmap());
+        let start_acts = Self::cnv_sem_acts(&schema_json.start_actions(), compiled_schema)?;
+        compiled_schema.set_start_acts(start_acts);
         self.collect_shape_labels(schema_json, compiled_schema)?;
         self.collect_shape_exprs(schema_json, compiled_schema)?;
+        compiled_schema.update_descendants()?;
+        self.check_negated_references(schema_json, compiled_schema)?;
+        Ok(())
+    }
+
+    /// Checks that no shape has a negated reference to itself, directly or through other shapes,
+    /// because the validation of such schemas is not well defined
+    fn check_negated_references(
+        &self,
+        schema_json: &SchemaJson,
+        compiled_schema: &mut CompiledSchema,
+    ) -> CResult<()> {
+        let mut labels = Vec::new();
+        let mut refs: HashMap<ShapeLabelIdx, Vec<(ShapeLabelIdx, bool)>> = HashMap::new();
+        for sd in schema_json.shapes().iter().flatten() {
+            let idx = self.get_shape_label_idx(&sd.id, compiled_schema)?;
+            let mut shape_refs = Vec::new();
+            self.shape_expr_refs(&sd.shape_expr, false, compiled_schema, &mut shape_refs)?;
+            refs.insert(idx, shape_refs);
+            labels.push((idx, self.shape_expr_label_to_shape_label(&sd.id)?));
+        }
+        for (idx, label) in labels {
+            let negated_refs = refs[&idx].iter().filter(|(_, negated)| *negated);
+            for (referenced, _) in negated_refs {
+                if Self::reaches(referenced, &idx, &refs) {
+                    return Err(CompiledSchemaError::NegatedReferenceCycle { shape_label: label });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks if the shape `to` can be reached from the shape `from` following references
+    fn reaches(
+        from: &ShapeLabelIdx,
+        to: &ShapeLabelIdx,
+        refs: &HashMap<ShapeLabelIdx, Vec<(ShapeLabelIdx, bool)>>,
+    ) -> bool {
+        let mut visited = HashSet::new();
+        let mut pending = vec![*from];
+        while let Some(idx) = pending.pop() {
+            if idx == *to {
+                return true;
+            }
+            if visited.insert(idx) {
+                pending.extend(
+                    refs.get(&idx)
+                        .iter()
+                        .flat_map(|rs| rs.iter().map(|(r, _)| *r)),
+                );
+            }
+        }
+        false
+    }
+
+    /// Collects the shape labels referenced by a shape expression and if the references are negated,
+    /// which happens inside `NOT` and in the value expressions of triple constraints whose predicate is `EXTRA`
+    fn shape_expr_refs(
+        &self,
+        se: &ast::ShapeExpr,
+        negated: bool,
+        compiled_schema: &mut CompiledSchema,
+        refs: &mut Vec<(ShapeLabelIdx, bool)>,
+    ) -> CResult<()> {
+        match se {
+            ast::ShapeExpr::Ref(se_ref) => {
+                refs.push((self.ref2idx(se_ref, compiled_schema)?, negated))
+            }
+            ast::ShapeExpr::ShapeOr { shape_exprs } | ast::ShapeExpr::ShapeAnd { shape_exprs } => {
+                for sew in shape_exprs {
+                    self.shape_expr_refs(&sew.se, negated, compiled_schema, refs)?;
+                }
+            }
+            ast::ShapeExpr::ShapeNot { shape_expr } => {
+                self.shape_expr_refs(&shape_expr.se, true, compiled_schema, refs)?
+            }
+            ast::ShapeExpr::Shape(shape) => {
+                for e in shape.extends.iter().flatten() {
+                    refs.push((self.ref2idx(e, compiled_schema)?, negated));
+                }
+                let extra: Vec<IriS> = shape.extra.iter().flatten().map(iri_ref2iri_s).collect();
+                if let Some(tew) = &shape.expression {
+                    self.triple_expr_refs(&tew.te, negated, &extra, compiled_schema, refs)?;
+                }
+            }
+            ast::ShapeExpr::NodeConstraint(_) | ast::ShapeExpr::External => {}
+        }
+        Ok(())
+    }
+
+    fn triple_expr_refs(
+        &self,
+        te: &ast::TripleExpr,
+        negated: bool,
+        extra: &[IriS],
+        compiled_schema: &mut CompiledSchema,
+        refs: &mut Vec<(ShapeLabelIdx, bool)>,
+    ) -> CResult<()> {
+        match te {
+            ast::TripleExpr::EachOf { expressions, .. }
+            | ast::TripleExpr::OneOf { expressions, .. } => {
+                for e in expressions {
+                    self.triple_expr_refs(&e.te, negated, extra, compiled_schema, refs)?;
+                }
+            }
+            ast::TripleExpr::TripleConstraint {
+                predicate,
+                value_expr: Some(ve),
+                ..
+            } => {
+                let negated = negated || extra.contains(&iri_ref2iri_s(predicate));
+                self.shape_expr_refs(ve, negated, compiled_schema, refs)?;
+            }
+            ast::TripleExpr::TripleConstraint { .. } | ast::TripleExpr::TripleExprRef(_) => {}
+        }
         Ok(())
     }
 
@@ -62,7 +186,11 @@ impl SchemaJsonCompiler {
             Some(sds) => {
                 for sd in sds {
                     let label = self.shape_expr_label_to_shape_label(&sd.id)?;
-                    compiled_schema.add_shape(label, ShapeExpr::Empty);
+                    compiled_schema.add_shape(label.clone(), ShapeExpr::Empty);
+                    if sd.is_abstract {
+                        let idx = compiled_schema.get_shape_label_idx(&label)?;
+                        compiled_schema.set_abstract(&idx);
+                    }
                     self.shape_decls_counter += 1;
                 }
                 Ok(())
@@ -181,16 +309,26 @@ impl SchemaJsonCompiler {
             }
             ast::ShapeExpr::Shape(shape) => {
                 let new_extra = self.cnv_extra(&shape.extra)?;
+                let mut triple_sem_acts = Vec::new();
                 let rbe_table = match &shape.expression {
                     None => RbeTable::new(),
                     Some(tew) => {
                         let mut table = RbeTable::new();
-                        let rbe = self.triple_expr2rbe(&tew.te, compiled_schema, &mut table)?;
+                        let rbe = self.triple_expr2rbe(
+                            &tew.te,
+                            compiled_schema,
+                            &mut table,
+                            &mut triple_sem_acts,
+                        )?;
                         table.with_rbe(rbe);
                         table
                     }
                 };
                 let preds = Self::get_preds_shape(shape);
+                let mut extends = Vec::new();
+                for e in shape.extends.iter().flatten() {
+                    extends.push(self.ref2idx(e, compiled_schema)?);
+                }
 
                 let display = match compiled_schema.find_shape_idx(idx) {
                     None => "internal".to_string(),
@@ -201,11 +339,13 @@ impl SchemaJsonCompiler {
                     Self::cnv_closed(&shape.closed),
                     new_extra,
                     rbe_table,
-                    Self::cnv_sem_acts(&shape.sem_acts),
+                    Self::cnv_sem_acts(&shape.sem_acts, compiled_schema)?,
                     Self::cnv_annotations(&shape.annotations),
                     preds,
                     display,
-                );
+                )
+                .with_extends(extends)
+                .with_triple_sem_acts(triple_sem_acts);
                 Ok(ShapeExpr::Shape(shape))
             }
             ast::ShapeExpr::NodeConstraint(nc) => {
@@ -268,13 +408,26 @@ impl SchemaJsonCompiler {
         }
     }
 
-    fn cnv_sem_acts(sem_acts: &Option<Vec<ast::SemAct>>) -> Vec<SemAct> {
-        if let Some(_vs) = sem_acts {
-            // TODO
-            Vec::new()
-        } else {
-            Vec::new()
+    fn cnv_sem_acts(
+        sem_acts: &Option<Vec<ast::SemAct>>,
+        compiled_schema: &CompiledSchema,
+    ) -> CResult<Vec<SemAct>> {
+        let mut vs = Vec::new();
+        for sem_act in sem_acts.iter().flatten() {
+            let name = match sem_act.name() {
+                IriRef::Iri(iri) => iri,
+                IriRef::Prefixed { prefix, local } => compiled_schema
+                    .prefixmap()
+                    .resolve_prefix_local(&prefix, &local)
+                    .map_err(|err| CompiledSchemaError::PrefixedNotFound {
+                        prefix: prefix.clone(),
+                        local: local.clone(),
+                        err: Box::new(err),
+                    })?,
+            };
+            vs.push(SemAct::new(name, sem_act.code()));
         }
+        Ok(vs)
     }
 
     fn cnv_annotations(annotations: &Option<Vec<ast::Annotation>>) -> Vec<Annotation> {
@@ -291,6 +444,7 @@ impl SchemaJsonCompiler {
         triple_expr: &ast::TripleExpr,
         compiled_schema: &mut CompiledSchema,
         current_table: &mut RbeTable<Pred, Node, ShapeLabelIdx>,
+        triple_sem_acts: &mut Vec<TripleConstraintSemActs>,
     ) -> CResult<Rbe<Component>> {
         match triple_expr {
             ast::TripleExpr::EachOf {
@@ -303,7 +457,12 @@ impl SchemaJsonCompiler {
             } => {
                 let mut cs = Vec::new();
                 for e in expressions {
-                    let c = self.triple_expr2rbe(&e.te, compiled_schema, current_table)?;
+                    let c = self.triple_expr2rbe(
+                        &e.te,
+                        compiled_schema,
+                        current_table,
+                        triple_sem_acts,
+                    )?;
                     cs.push(c)
                 }
                 let card = self.cnv_min_max(min, max)?;
@@ -319,7 +478,12 @@ impl SchemaJsonCompiler {
             } => {
                 let mut cs = Vec::new();
                 for e in expressions {
-                    let c = self.triple_expr2rbe(&e.te, compiled_schema, current_table)?;
+                    let c = self.triple_expr2rbe(
+                        &e.te,
+                        compiled_schema,
+                        current_table,
+                        triple_sem_acts,
+                    )?;
                     cs.push(c)
                 }
                 let card = self.cnv_min_max(min, max)?;
@@ -333,13 +497,21 @@ impl SchemaJsonCompiler {
                 value_expr,
                 min,
                 max,
-                sem_acts: _,
+                sem_acts,
                 annotations: _,
             } => {
                 let min = self.cnv_min(min)?;
                 let max = self.cnv_max(max)?;
                 let iri = Self::cnv_predicate(predicate)?;
                 let cond = self.value_expr2match_cond(value_expr, compiled_schema)?;
+                let sem_acts = Self::cnv_sem_acts(sem_acts, compiled_schema)?;
+                if !sem_acts.is_empty() {
+                    triple_sem_acts.push(TripleConstraintSemActs::new(
+                        iri.clone(),
+                        cond.clone(),
+                        sem_acts,
+                    ));
+                }
                 let c = current_table.add_component(iri, &cond);
                 Ok(Rbe::symbol(c, min.value, max))
             }
@@ -417,11 +589,14 @@ impl SchemaJsonCompiler {
                     let idx = self.ref2idx(sref, compiled_schema)?;
                     Ok(mk_cond_ref(idx))
                 }
-                ast::ShapeExpr::Shape { .. } => todo("value_expr2match_cond: Shape"),
-                ast::ShapeExpr::ShapeAnd { .. } => todo("value_expr2match_cond: ShapeOr"),
-                ast::ShapeExpr::ShapeOr { .. } => todo("value_expr2match_cond: ShapeOr"),
-                ast::ShapeExpr::ShapeNot { .. } => todo("value_expr2match_cond: ShapeNot"),
-                ast::ShapeExpr::External => todo("value_expr2match_cond: ShapeExternal"),
+                // Nested shape expressions are compiled as internal shapes which are
+                // checked by the validator as if they were references
+                _ => {
+                    let idx = compiled_schema.add_internal_shape();
+                    let se = self.compile_shape_expr(se, &idx, compiled_schema)?;
+                    compiled_schema.replace_shape(&idx, se);
+                    Ok(mk_cond_ref(idx))
+                }
             }
         } else {
             Ok(MatchCond::single(SingleCond::new().with_name(".")))
@@ -480,7 +655,7 @@ fn node_constraint2match_cond(
 ) -> CResult<Cond> {
     let c1: Option<Cond> = node_kind.as_ref().map(node_kind2match_cond);
     let c2 = datatype.as_ref().map(datatype2match_cond).transpose()?;
-    let c3 = xs_facet.as_ref().map(xs_facets2match_cond);
+    let c3 = xs_facet.as_ref().map(xs_facets2match_cond).transpose()?;
     let c4 = values.as_ref().map(|vs| valueset2match_cond(vs.clone()));
     let os = vec![c1, c2, c3, c4];
     Ok(options2match_cond(os))
@@ -495,38 +670,38 @@ fn datatype2match_cond(datatype: &IriRef) -> CResult<Cond> {
     Ok(mk_cond_datatype(datatype))
 }
 
-fn xs_facets2match_cond(xs_facets: &Vec<ast::XsFacet>) -> Cond {
+fn xs_facets2match_cond(xs_facets: &Vec<ast::XsFacet>) -> CResult<Cond> {
     let mut conds = Vec::new();
     for xs_facet in xs_facets {
-        conds.push(xs_facet2match_cond(xs_facet))
+        conds.push(xs_facet2match_cond(xs_facet)?)
     }
-    MatchCond::And(conds)
+    Ok(MatchCond::And(conds))
 }
 
-fn xs_facet2match_cond(xs_facet: &ast::XsFacet) -> Cond {
+fn xs_facet2match_cond(xs_facet: &ast::XsFacet) -> CResult<Cond> {
     match xs_facet {
         ast::XsFacet::StringFacet(sf) => string_facet_to_match_cond(sf),
-        ast::XsFacet::NumericFacet(nf) => numeric_facet_to_match_cond(nf),
+        ast::XsFacet::NumericFacet(nf) => Ok(numeric_facet_to_match_cond(nf)),
     }
 }
 
-fn string_facet_to_match_cond(sf: &ast::StringFacet) -> Cond {
+fn string_facet_to_match_cond(sf: &ast::StringFacet) -> CResult<Cond> {
     match sf {
-        ast::StringFacet::Length(len) => mk_cond_length(*len),
-        ast::StringFacet::MinLength(len) => mk_cond_min_length(*len),
-        ast::StringFacet::MaxLength(len) => mk_cond_max_length(*len),
-        ast::StringFacet::Pattern(_) => todo!(),
+        ast::StringFacet::Length(len) => Ok(mk_cond_length(*len)),
+        ast::StringFacet::MinLength(len) => Ok(mk_cond_min_length(*len)),
+        ast::StringFacet::MaxLength(len) => Ok(mk_cond_max_length(*len)),
+        ast::StringFacet::Pattern(pattern) => mk_cond_pattern(pattern),
     }
 }
 
 fn numeric_facet_to_match_cond(nf: &ast::NumericFacet) -> Cond {
     match nf {
-        ast::NumericFacet::MinInclusive(_min) => todo!(),
-        ast::NumericFacet::MinExclusive(_) => todo!(),
-        ast::NumericFacet::MaxInclusive(_) => todo!(),
-        ast::NumericFacet::MaxExclusive(_) => todo!(),
-        ast::NumericFacet::TotalDigits(_) => todo!(),
-        ast::NumericFacet::FractionDigits(_) => todo!(),
+        ast::NumericFacet::MinInclusive(min) => mk_cond_min_inclusive(min.clone()),
+        ast::NumericFacet::MinExclusive(min) => mk_cond_min_exclusive(min.clone()),
+        ast::NumericFacet::MaxInclusive(max) => mk_cond_max_inclusive(max.clone()),
+        ast::NumericFacet::MaxExclusive(max) => mk_cond_max_exclusive(max.clone()),
+        ast::NumericFacet::TotalDigits(n) => mk_cond_total_digits(*n),
+        ast::NumericFacet::FractionDigits(n) => mk_cond_fraction_digits(*n),
     }
 }
 
@@ -607,6 +782,133 @@ fn mk_cond_max_length(len: usize) -> Cond {
     })
 }
 
+fn mk_cond_min_inclusive(min: NumericLiteral) -> Cond {
+    MatchCond::simple(
+        format!("minInclusive{min}").as_str(),
+        move |value: &Node| match check_node_min_inclusive(value, &min) {
+            Ok(_) => Ok(Pending::new()),
+            Err(err) => Err(RbeError::MsgError {
+                msg: format!("MinInclusive error: {err}"),
+            }),
+        },
+    )
+}
+
+fn mk_cond_min_exclusive(min: NumericLiteral) -> Cond {
+    MatchCond::simple(
+        format!("minExclusive{min}").as_str(),
+        move |value: &Node| match check_node_min_exclusive(value, &min) {
+            Ok(_) => Ok(Pending::new()),
+            Err(err) => Err(RbeError::MsgError {
+                msg: format!("MinExclusive error: {err}"),
+            }),
+        },
+    )
+}
+
+fn mk_cond_max_inclusive(max: NumericLiteral) -> Cond {
+    MatchCond::simple(
+        format!("maxInclusive{max}").as_str(),
+        move |value: &Node| match check_node_max_inclusive(value, &max) {
+            Ok(_) => Ok(Pending::new()),
+            Err(err) => Err(RbeError::MsgError {
+                msg: format!("MaxInclusive error: {err}"),
+            }),
+        },
+    )
+}
+
+fn mk_cond_max_exclusive(max: NumericLiteral) -> Cond {
+    MatchCond::simple(
+        format!("maxExclusive{max}").as_str(),
+        move |value: &Node| match check_node_max_exclusive(value, &max) {
+            Ok(_) => Ok(Pending::new()),
+            Err(err) => Err(RbeError::MsgError {
+                msg: format!("MaxExclusive error: {err}"),
+            }),
+        },
+    )
+}
+
+fn mk_cond_total_digits(n: usize) -> Cond {
+    MatchCond::simple(format!("totalDigits{n}").as_str(), move |value: &Node| {
+        match check_node_total_digits(value, n) {
+            Ok(_) => Ok(Pending::new()),
+            Err(err) => Err(RbeError::MsgError {
+                msg: format!("TotalDigits error: {err}"),
+            }),
+        }
+    })
+}
+
+fn mk_cond_fraction_digits(n: usize) -> Cond {
+    MatchCond::simple(
+        format!("fractionDigits{n}").as_str(),
+        move |value: &Node| match check_node_fraction_digits(value, n) {
+            Ok(_) => Ok(Pending::new()),
+            Err(err) => Err(RbeError::MsgError {
+                msg: format!("FractionDigits error: {err}"),
+            }),
+        },
+    )
+}
+
+fn mk_cond_pattern(pattern: &ast::Pattern) -> CResult<Cond> {
+    let flags = pattern.flags.clone().unwrap_or_default();
+    let regex = mk_regex(&pattern.str, &flags)?;
+    let str = pattern.str.clone();
+    Ok(MatchCond::simple(
+        format!("pattern/{str}/{flags}").as_str(),
+        move |value: &Node| match check_node_pattern(value, &regex, &str, &flags) {
+            Ok(_) => Ok(Pending::new()),
+            Err(err) => Err(RbeError::MsgError {
+                msg: format!("Pattern error: {err}"),
+            }),
+        },
+    ))
+}
+
+/// Builds a regular expression from a pattern facet using the flags defined by
+/// SPARQL's `REGEX` function (`i`, `m`, `s`, `x` and `q`)
+fn mk_regex(str: &str, flags: &str) -> CResult<Regex> {
+    let mut literal = false;
+    let mut builder_flags = Vec::new();
+    for flag in flags.chars() {
+        match flag {
+            'i' | 'm' | 's' | 'x' => builder_flags.push(flag),
+            'q' => literal = true,
+            _ => {
+                return Err(CompiledSchemaError::UnknownRegexFlag {
+                    regex: str.to_string(),
+                    flags: flags.to_string(),
+                    flag,
+                })
+            }
+        }
+    }
+    let source = if literal {
+        regex::escape(str)
+    } else {
+        str.to_string()
+    };
+    let mut builder = RegexBuilder::new(source.as_str());
+    for flag in builder_flags {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'm' => builder.multi_line(true),
+            's' => builder.dot_matches_new_line(true),
+            _ => builder.ignore_whitespace(true),
+        };
+    }
+    builder
+        .build()
+        .map_err(|e| CompiledSchemaError::InvalidRegex {
+            regex: str.to_string(),
+            flags: flags.to_string(),
+            error: e.to_string(),
+        })
+}
+
 fn mk_cond_nodekind(nodekind: ast::NodeKind) -> Cond {
     MatchCond::single(
         SingleCond::new()
@@ -636,14 +938,11 @@ fn mk_cond_value_set(value_set: ValueSet) -> Cond {
     MatchCond::single(
         SingleCond::new()
             .with_name(format!("{}", value_set).as_str())
-            .with_cond(move |node: &Node| {
-                if value_set.check_value(node.as_object()) {
-                    Ok(Pending::empty())
-                } else {
-                    Err(RbeError::MsgError {
-                        msg: format!("Values failed: {node} not in {value_set}"),
-                    })
-                }
+            .with_cond(move |node: &Node| match value_set.check_node(node) {
+                Ok(_) => Ok(Pending::empty()),
+                Err(err) => Err(RbeError::MsgError {
+                    msg: format!("Values failed: {err}"),
+                }),
             }),
     )
 }
@@ -663,120 +962,100 @@ fn cnv_value(v: &ast::ValueSetValue) -> CResult<ValueSetValue> {
             let cnv_stem = cnv_iri_ref(stem)?;
             Ok(ValueSetValue::IriStem { stem: cnv_stem })
         }
+        ast::ValueSetValue::IriStemRange { stem, exclusions } => {
+            let stem = match stem {
+                ast::IriRefOrWildcard::IriRef(iri) => IriRefOrWildcard::IriRef(cnv_iri_ref(iri)?),
+                ast::IriRefOrWildcard::Wildcard => IriRefOrWildcard::Wildcard,
+            };
+            let exclusions = cnv_opt_vec(exclusions, cnv_iri_exclusion)?;
+            Ok(ValueSetValue::IriStemRange { stem, exclusions })
+        }
         ast::ValueSetValue::ObjectValue(ovw) => {
             let ov = cnv_object_value(ovw)?;
             Ok(ValueSetValue::ObjectValue(ov))
         }
         ast::ValueSetValue::Language { language_tag, .. } => Ok(ValueSetValue::Language {
-            language_tag: language_tag.to_string(),
+            language_tag: language_tag.clone(),
         }),
+        ast::ValueSetValue::LanguageStem { stem } => {
+            Ok(ValueSetValue::LanguageStem { stem: stem.clone() })
+        }
+        ast::ValueSetValue::LanguageStemRange { stem, exclusions } => {
+            let stem = match stem {
+                ast::LangOrWildcard::Lang(lang) => LangOrWildcard::Lang(lang.clone()),
+                ast::LangOrWildcard::Wildcard => LangOrWildcard::Wildcard,
+            };
+            let exclusions = cnv_opt_vec(exclusions, cnv_language_exclusion)?;
+            Ok(ValueSetValue::LanguageStemRange { stem, exclusions })
+        }
         ast::ValueSetValue::LiteralStem { stem, .. } => Ok(ValueSetValue::LiteralStem {
             stem: stem.to_string(),
         }),
-        ast::ValueSetValue::LiteralStemRange { .. } => {
-            todo!()
-            /*let stem = cnv_string_or_wildcard(&stem)?;
-            let exclusions = cnv_opt_vec(&exclusions, cnv_string_or_literalstem)?;
-            Ok(ValueSetValue::LiteralStemRange { stem, exclusions })*/
+        ast::ValueSetValue::LiteralStemRange { stem, exclusions } => {
+            let stem = cnv_string_or_wildcard(stem);
+            let exclusions = cnv_opt_vec(exclusions, cnv_literal_exclusion)?;
+            Ok(ValueSetValue::LiteralStemRange { stem, exclusions })
         }
-        _ => todo!(),
     }
 }
 
-/*
-fn cnv_node_kind(_nk: &ast::NodeKind) -> CResult<NodeKind> {
-    todo!()
+fn cnv_string_or_wildcard(sw: &ast::StringOrWildcard) -> StringOrWildcard {
+    match sw {
+        ast::StringOrWildcard::String(s) => StringOrWildcard::String(s.clone()),
+        ast::StringOrWildcard::Wildcard => StringOrWildcard::Wildcard,
+    }
 }
 
-fn cnv_xs_facet(_xsf: &ast::XsFacet) -> CResult<XsFacet> {
-    todo!()
+fn cnv_iri_exclusion(exc: &ast::IriExclusion) -> CResult<IriExclusion> {
+    match exc {
+        ast::IriExclusion::Iri(iri) => Ok(IriExclusion::Iri(cnv_iri_ref(iri)?)),
+        ast::IriExclusion::IriStem(stem) => Ok(IriExclusion::IriStem(cnv_iri_ref(stem)?)),
+    }
 }
 
-fn cnv_vec<A, B, F>(vs: Vec<A>, func: F) -> CResult<Vec<B>>
-where
-    F: Fn(&A) -> CResult<B>,
-{
-    let mut rs = Vec::new();
-    for v in vs {
-        let b = func(&v)?;
-        rs.push(b);
+fn cnv_literal_exclusion(exc: &ast::LiteralExclusion) -> CResult<LiteralExclusion> {
+    match exc {
+        ast::LiteralExclusion::Literal(s) => Ok(LiteralExclusion::Literal(s.clone())),
+        ast::LiteralExclusion::LiteralStem(s) => Ok(LiteralExclusion::LiteralStem(s.clone())),
     }
-    Ok(rs)
 }
 
-fn cnv_opt_vec<A, B, F>(maybe_vs: &Option<Vec<A>>, func: F) -> CResult<Option<Vec<B>>>
-where
-    F: Fn(&A) -> CResult<B>,
-{
-    match maybe_vs {
-        None => Ok(None),
-        Some(vs) => {
-            let mut rs = Vec::new();
-            for v in vs {
-                match func(v) {
-                    Err(err) => return Err(err),
-                    Ok(result) => {
-                        rs.push(result);
-                    }
-                }
-            }
-            Ok(Some(rs))
+fn cnv_language_exclusion(exc: &ast::LanguageExclusion) -> CResult<LanguageExclusion> {
+    match exc {
+        ast::LanguageExclusion::Language(lang) => Ok(LanguageExclusion::Language(lang.clone())),
+        ast::LanguageExclusion::LanguageStem(stem) => {
+            Ok(LanguageExclusion::LanguageStem(stem.clone()))
         }
     }
 }
 
-fn cnv_opt<A, B, F>(maybe_vs: &Option<A>, func: F) -> CResult<Option<B>>
+fn cnv_opt_vec<A, B, F>(maybe_vs: &Option<Vec<A>>, func: F) -> CResult<Option<Vec<B>>>
 where
     F: Fn(&A) -> CResult<B>,
 {
     match maybe_vs {
         None => Ok(None),
-        Some(vs) => match func(vs) {
-            Err(err) => Err(err),
-            Ok(v) => Ok(Some(v)),
-        },
+        Some(vs) => {
+            let rs = vs.iter().map(func).collect::<CResult<Vec<_>>>()?;
+            Ok(Some(rs))
+        }
     }
 }
 
-fn cnv_string_or_wildcard(_sw: &ast::StringOrWildcard) -> CResult<StringOrWildcard> {
-    todo!()
-}
-
-fn cnv_string_or_literalstem(
-    _sl: &ast::StringOrLiteralStemWrapper,
-) -> CResult<StringOrLiteralStem> {
-    todo!()
-}
-*/
-
 fn cnv_object_value(ov: &ast::ObjectValue) -> CResult<ObjectValue> {
     match ov {
         ast::ObjectValue::IriRef(ir) => {
             let iri = cnv_iri_ref(ir)?;
             Ok(ObjectValue::IriRef(iri))
         }
-        ast::ObjectValue::Literal(_n) => {
-            todo!()
-        } /*ast::ObjectValue::ObjectLiteral {
-              value, language, ..
-          } => Ok(ObjectValue::ObjectLiteral {
-              value: value.to_string(),
-              language: language.clone(),
-          })*/
+        ast::ObjectValue::Literal(lit) => {
+            ObjectValue::literal(lit).ok_or_else(|| CompiledSchemaError::Internal {
+                msg: format!("Cannot convert literal {lit} with unresolved datatype"),
+            })
+        }
     }
 }
 
-/*fn cnv_lang(lang: &String) -> CResult<Lang> {
-    Ok(Lang::new(lang.as_str()))
-}*/
-
-/*fn check_node_maybe_node_kind(node: &Node, nodekind: &Option<ast::NodeKind>) -> CResult<()> {
-    match nodekind {
-        None => Ok(()),
-        Some(nk) => check_node_node_kind(node, &nk),
-    }
-}*/
-
 fn check_node_node_kind(node: &Node, nk: &ast::NodeKind) -> CResult<()> {
     match (nk, node.as_object()) {
         (ast::NodeKind::Iri, Object::Iri { .. }) => Ok(()),
@@ -789,6 +1068,8 @@ fn check_node_node_kind(node: &Node, nk: &ast::NodeKind) -> CResult<()> {
         }
         (ast::NodeKind::NonLiteral, Object::BlankNode(_)) => Ok(()),
         (ast::NodeKind::NonLiteral, Object::Iri { .. }) => Ok(()),
+        // Experimental: quoted triples are not literals, so they only match NONLITERAL
+        (ast::NodeKind::NonLiteral, Object::Triple { .. }) => Ok(()),
         (ast::NodeKind::NonLiteral, _) => {
             Err(CompiledSchemaError::NodeKindNonLiteral { node: node.clone() })
         }
@@ -900,37 +1181,128 @@ fn check_node_max_length(node: &Node, len: usize) -> CResult<()> {
     }
 }
 
-/*
+fn node_numeric_value(node: &Node) -> CResult<NumericLiteral> {
+    node.numeric_value()
+        .ok_or_else(|| CompiledSchemaError::NonNumeric {
+            node: format!("{node}"),
+        })
+}
+
 fn check_node_min_inclusive(node: &Node, min: &NumericLiteral) -> CResult<()> {
     debug!("check_node_min_inclusive: {node:?} min: {min}");
-    if let Some(node_number) = node.numeric_value() {
-        if node_number.less_than(min) {
-            Ok(())
-        } else {
-            Err(CompiledSchemaError::MinInclusiveError {
-                expected: min.clone(),
-                found: node_number,
+    let node_number = node_numeric_value(node)?;
+    match node_number.compare(min) {
+        Some(Ordering::Greater | Ordering::Equal) => Ok(()),
+        _ => Err(CompiledSchemaError::MinInclusiveError {
+            expected: min.clone(),
+            found: node_number,
+            node: format!("{node}"),
+        }),
+    }
+}
+
+fn check_node_min_exclusive(node: &Node, min: &NumericLiteral) -> CResult<()> {
+    debug!("check_node_min_exclusive: {node:?} min: {min}");
+    let node_number = node_numeric_value(node)?;
+    match node_number.compare(min) {
+        Some(Ordering::Greater) => Ok(()),
+        _ => Err(CompiledSchemaError::MinExclusiveError {
+            expected: min.clone(),
+            found: node_number,
+            node: format!("{node}"),
+        }),
+    }
+}
+
+fn check_node_max_inclusive(node: &Node, max: &NumericLiteral) -> CResult<()> {
+    debug!("check_node_max_inclusive: {node:?} max: {max}");
+    let node_number = node_numeric_value(node)?;
+    match node_number.compare(max) {
+        Some(Ordering::Less | Ordering::Equal) => Ok(()),
+        _ => Err(CompiledSchemaError::MaxInclusiveError {
+            expected: max.clone(),
+            found: node_number,
+            node: format!("{node}"),
+        }),
+    }
+}
+
+fn check_node_max_exclusive(node: &Node, max: &NumericLiteral) -> CResult<()> {
+    debug!("check_node_max_exclusive: {node:?} max: {max}");
+    let node_number = node_numeric_value(node)?;
+    match node_number.compare(max) {
+        Some(Ordering::Less) => Ok(()),
+        _ => Err(CompiledSchemaError::MaxExclusiveError {
+            expected: max.clone(),
+            found: node_number,
+            node: format!("{node}"),
+        }),
+    }
+}
+
+fn check_node_total_digits(node: &Node, total_digits: usize) -> CResult<()> {
+    debug!("check_node_total_digits: {node:?} total_digits: {total_digits}");
+    let node_number = node_numeric_value(node)?;
+    match node_number.total_digits() {
+        Some(found) if found <= total_digits => Ok(()),
+        Some(found) => Err(CompiledSchemaError::TotalDigitsError {
+            expected: total_digits,
+            found,
+            node: format!("{node}"),
+        }),
+        None => Err(CompiledSchemaError::NonDecimal {
+            node: format!("{node}"),
+            facet: format!("totalDigits {total_digits}"),
+        }),
+    }
+}
+
+fn check_node_fraction_digits(node: &Node, fraction_digits: usize) -> CResult<()> {
+    debug!("check_node_fraction_digits: {node:?} fraction_digits: {fraction_digits}");
+    let node_number = node_numeric_value(node)?;
+    match node_number.fraction_digits() {
+        Some(found) if found <= fraction_digits => Ok(()),
+        Some(found) => Err(CompiledSchemaError::FractionDigitsError {
+            expected: fraction_digits,
+            found,
+            node: format!("{node}"),
+        }),
+        None => Err(CompiledSchemaError::NonDecimal {
+            node: format!("{node}"),
+            facet: format!("fractionDigits {fraction_digits}"),
+        }),
+    }
+}
+
+fn check_node_pattern(node: &Node, regex: &Regex, str: &str, flags: &str) -> CResult<()> {
+    debug!("check_node_pattern: {node:?} pattern: /{str}/{flags}");
+    let lexical_form = match node.as_object() {
+        Object::Iri(iri) => iri.as_str().to_string(),
+        Object::Literal(lit) => lit.lexical_form(),
+        Object::BlankNode(_) => {
+            return Err(CompiledSchemaError::PatternBNode {
+                regex: str.to_string(),
+                flags: flags.to_string(),
                 node: format!("{node}"),
             })
         }
+        Object::Triple { .. } => node.as_object().to_string(),
+    };
+    if regex.is_match(lexical_form.as_str()) {
+        Ok(())
     } else {
-        Err(CompiledSchemaError::NonNumeric {
+        Err(CompiledSchemaError::PatternError {
+            regex: str.to_string(),
+            flags: flags.to_string(),
             node: format!("{node}"),
         })
     }
 }
-*/
 
 /*fn check_node_xs_facets(node: &Object, xs_facets: &Vec<XsFacet>) -> CResult<()> {
     Ok(()) // todo!()
 }*/
 
-fn todo<A>(str: &str) -> CResult<A> {
-    Err(CompiledSchemaError::Todo {
-        msg: str.to_string(),
-    })
-}
-
 fn cnv_iri_ref(iri: &IriRef) -> Result<IriS, CompiledSchemaError> {
     match iri {
         IriRef::Iri(iri) => Ok(iri.clone()),
@@ -939,3 +1311,26 @@ fn cnv_iri_ref(iri: &IriRef) -> Result<IriS, CompiledSchemaError> {
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regex_flags() {
+        assert!(mk_regex("^a.b$", "s").unwrap().is_match("a\nb"));
+        assert!(mk_regex("^b$", "m").unwrap().is_match("a\nb"));
+        assert!(mk_regex("^a b$", "x").unwrap().is_match("ab"));
+        assert!(mk_regex("a.b", "q").unwrap().is_match("xa.by"));
+        assert!(!mk_regex("a.b", "q").unwrap().is_match("axb"));
+        assert!(mk_regex("^AB$", "i").unwrap().is_match("ab"));
+        assert!(matches!(
+            mk_regex("a", "u"),
+            Err(CompiledSchemaError::UnknownRegexFlag { flag: 'u', .. })
+        ));
+        assert!(matches!(
+            mk_regex("(a", ""),
+            Err(CompiledSchemaError::InvalidRegex { .. })
+        ));
+    }
+}
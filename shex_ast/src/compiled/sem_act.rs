@@ -1,8 +1,68 @@
 use iri_s::IriS;
 use serde_derive::Serialize;
+use std::fmt::Display;
+
+use crate::{Cond, Pred};
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize)]
 pub struct SemAct {
     name: IriS,
     code: Option<String>,
 }
+
+impl SemAct {
+    pub fn new(name: IriS, code: Option<String>) -> SemAct {
+        SemAct { name, code }
+    }
+
+    /// IRI of the extension that handles this semantic action
+    pub fn name(&self) -> &IriS {
+        &self.name
+    }
+
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+}
+
+impl Display for SemAct {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.code {
+            Some(code) => write!(f, "%<{}>{{{code}%}}", self.name),
+            None => write!(f, "%<{}>%", self.name),
+        }
+    }
+}
+
+/// Semantic actions attached to a triple constraint of a shape.
+///
+/// They are run for every triple of the neighbourhood whose predicate and value
+/// match the triple constraint
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TripleConstraintSemActs {
+    pred: Pred,
+    cond: Cond,
+    sem_acts: Vec<SemAct>,
+}
+
+impl TripleConstraintSemActs {
+    pub fn new(pred: Pred, cond: Cond, sem_acts: Vec<SemAct>) -> Self {
+        TripleConstraintSemActs {
+            pred,
+            cond,
+            sem_acts,
+        }
+    }
+
+    pub fn pred(&self) -> &Pred {
+        &self.pred
+    }
+
+    pub fn cond(&self) -> &Cond {
+        &self.cond
+    }
+
+    pub fn sem_acts(&self) -> &[SemAct] {
+        &self.sem_acts
+    }
+}
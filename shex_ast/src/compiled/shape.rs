@@ -2,7 +2,10 @@ use iri_s::IriS;
 use rbe::RbeTable;
 use std::fmt::Display;
 
-use super::{annotation::Annotation, sem_act::SemAct};
+use super::{
+    annotation::Annotation,
+    sem_act::{SemAct, TripleConstraintSemActs},
+};
 use crate::{Node, Pred, ShapeLabelIdx};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -11,8 +14,10 @@ pub struct Shape {
     extra: Vec<IriS>,
     rbe_table: RbeTable<Pred, Node, ShapeLabelIdx>,
     sem_acts: Vec<SemAct>,
+    triple_sem_acts: Vec<TripleConstraintSemActs>,
     annotations: Vec<Annotation>,
     preds: Vec<IriS>,
+    extends: Vec<ShapeLabelIdx>,
     display: String,
 }
 
@@ -31,12 +36,39 @@ impl Shape {
             extra,
             rbe_table,
             sem_acts,
+            triple_sem_acts: Vec::new(),
             annotations,
             preds,
+            extends: Vec::new(),
             display,
         }
     }
 
+    pub fn with_extends(mut self, extends: Vec<ShapeLabelIdx>) -> Self {
+        self.extends = extends;
+        self
+    }
+
+    pub fn with_triple_sem_acts(mut self, triple_sem_acts: Vec<TripleConstraintSemActs>) -> Self {
+        self.triple_sem_acts = triple_sem_acts;
+        self
+    }
+
+    /// Semantic actions declared on the shape
+    pub fn sem_acts(&self) -> &[SemAct] {
+        &self.sem_acts
+    }
+
+    /// Semantic actions declared on the triple constraints of the shape
+    pub fn triple_sem_acts(&self) -> &[TripleConstraintSemActs] {
+        &self.triple_sem_acts
+    }
+
+    /// Shapes that this shape extends
+    pub fn extends(&self) -> &Vec<ShapeLabelIdx> {
+        &self.extends
+    }
+
     pub fn preds(&self) -> Vec<IriS> {
         self.preds.clone()
     }
@@ -40,7 +40,7 @@ impl Display for ShapeExpr {
             ShapeExpr::NodeConstraint(nc) => write!(f, "{nc}"),
             ShapeExpr::Shape(shape) => write!(f, "{shape}"),
             ShapeExpr::External {} => write!(f, "External"),
-            ShapeExpr::Ref { .. } => todo!(),
+            ShapeExpr::Ref { idx } => write!(f, "@{idx}"),
             ShapeExpr::Empty => write!(f, "<Empty>"),
         }
     }
@@ -1,7 +1,8 @@
 use srdf::Object;
 use std::fmt::Display;
 
-use super::value_set_value::ValueSetValue;
+use super::value_set_value::{ValueSetMatch, ValueSetValue};
+use crate::{CResult, CompiledSchemaError, Node};
 
 #[derive(Clone, Debug, Default)]
 pub struct ValueSet {
@@ -20,6 +21,32 @@ impl ValueSet {
     pub fn check_value(&self, object: &Object) -> bool {
         self.values.iter().any(|vsv| vsv.match_value(object))
     }
+
+    /// Checks that a node belongs to the value set.
+    /// If it doesn't, the error reports the exclusion that removed it, if any
+    pub fn check_node(&self, node: &Node) -> CResult<()> {
+        let mut excluded = None;
+        for value in &self.values {
+            match value.check_value(node.as_object()) {
+                ValueSetMatch::Matches => return Ok(()),
+                ValueSetMatch::Excluded { exclusion } => {
+                    excluded.get_or_insert((value, exclusion));
+                }
+                ValueSetMatch::NoMatch => {}
+            }
+        }
+        match excluded {
+            Some((value, exclusion)) => Err(CompiledSchemaError::ValueSetExclusion {
+                node: format!("{node}"),
+                value: format!("{value}"),
+                exclusion,
+            }),
+            None => Err(CompiledSchemaError::NotInValueSet {
+                node: format!("{node}"),
+                value_set: format!("{self}"),
+            }),
+        }
+    }
 }
 
 impl Display for ValueSet {
@@ -32,3 +59,33 @@ impl Display for ValueSet {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiled::value_set_value::{IriExclusion, IriRefOrWildcard};
+    use iri_s::IriS;
+
+    #[test]
+    fn test_check_node_reports_exclusion() {
+        let mut vs = ValueSet::new();
+        vs.add_value(ValueSetValue::IriStemRange {
+            stem: IriRefOrWildcard::IriRef(IriS::new_unchecked("http://example.org/")),
+            exclusions: Some(vec![IriExclusion::Iri(IriS::new_unchecked(
+                "http://example.org/x",
+            ))]),
+        });
+        let ok = Node::iri(IriS::new_unchecked("http://example.org/y"));
+        let excluded = Node::iri(IriS::new_unchecked("http://example.org/x"));
+        let other = Node::iri(IriS::new_unchecked("http://other.org/x"));
+        assert!(vs.check_node(&ok).is_ok());
+        assert!(matches!(
+            vs.check_node(&excluded),
+            Err(CompiledSchemaError::ValueSetExclusion { .. })
+        ));
+        assert!(matches!(
+            vs.check_node(&other),
+            Err(CompiledSchemaError::NotInValueSet { .. })
+        ));
+    }
+}
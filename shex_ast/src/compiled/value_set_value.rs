@@ -1,5 +1,7 @@
-use super::object_value::ObjectValue;
+use super::object_value::{literal_parts, ObjectValue};
 use iri_s::IriS;
+use srdf::lang::Lang;
+use srdf::literal::Literal;
 use srdf::Object;
 use std::fmt::Display;
 
@@ -10,73 +12,284 @@ pub enum ValueSetValue {
     },
     IriStemRange {
         stem: IriRefOrWildcard,
-        exclusions: Option<Vec<StringOrIriStem>>,
+        exclusions: Option<Vec<IriExclusion>>,
     },
     LiteralStem {
         stem: String,
     },
     LiteralStemRange {
         stem: StringOrWildcard,
-        exclusions: Option<Vec<StringOrLiteralStem>>,
+        exclusions: Option<Vec<LiteralExclusion>>,
     },
     Language {
-        language_tag: String,
+        language_tag: Lang,
+    },
+    LanguageStem {
+        stem: Lang,
+    },
+    LanguageStemRange {
+        stem: LangOrWildcard,
+        exclusions: Option<Vec<LanguageExclusion>>,
     },
-    LanguageStem,
-    LanguageStemRange,
     ObjectValue(ObjectValue),
 }
 
-#[derive(PartialEq, Eq, Clone, Debug)]
-pub enum StringOrLiteralStem {
-    String(String),
-    LiteralStem { stem: String },
-}
-
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum IriRefOrWildcard {
     IriRef(IriS),
-    Wildcard { type_: String },
+    Wildcard,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum StringOrWildcard {
     String(String),
-    Wildcard { type_: String },
+    Wildcard,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
-pub enum StringOrIriStem {
-    String(String),
-    IriStem { stem: String },
+pub enum LangOrWildcard {
+    Lang(Lang),
+    Wildcard,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum IriExclusion {
+    Iri(IriS),
+    IriStem(IriS),
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum LiteralExclusion {
+    Literal(String),
+    LiteralStem(String),
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum LanguageExclusion {
+    Language(Lang),
+    LanguageStem(Lang),
+}
+
+/// Result of checking a node against a value set value
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ValueSetMatch {
+    Matches,
+    NoMatch,
+    /// The node matches the stem of a range but it is removed by one of its exclusions
+    Excluded {
+        exclusion: String,
+    },
 }
 
 impl ValueSetValue {
     pub fn match_value(&self, object: &Object) -> bool {
+        self.check_value(object) == ValueSetMatch::Matches
+    }
+
+    pub fn check_value(&self, object: &Object) -> ValueSetMatch {
+        match self {
+            ValueSetValue::IriStem { stem } => match object {
+                Object::Iri(iri) => matches_bool(iri.as_str().starts_with(stem.as_str())),
+                _ => ValueSetMatch::NoMatch,
+            },
+            ValueSetValue::IriStemRange { stem, exclusions } => match object {
+                Object::Iri(iri) => {
+                    let matches_stem = match stem {
+                        IriRefOrWildcard::IriRef(stem) => iri.as_str().starts_with(stem.as_str()),
+                        IriRefOrWildcard::Wildcard => true,
+                    };
+                    if matches_stem {
+                        check_exclusions(exclusions, |e| e.excludes(iri))
+                    } else {
+                        ValueSetMatch::NoMatch
+                    }
+                }
+                _ => ValueSetMatch::NoMatch,
+            },
+            ValueSetValue::LiteralStem { stem } => match object {
+                Object::Literal(lit) => matches_bool(lit.lexical_form().starts_with(stem)),
+                _ => ValueSetMatch::NoMatch,
+            },
+            ValueSetValue::LiteralStemRange { stem, exclusions } => match object {
+                Object::Literal(lit) => {
+                    let lexical_form = lit.lexical_form();
+                    let matches_stem = match stem {
+                        StringOrWildcard::String(stem) => lexical_form.starts_with(stem),
+                        StringOrWildcard::Wildcard => true,
+                    };
+                    if matches_stem {
+                        check_exclusions(exclusions, |e| e.excludes(&lexical_form))
+                    } else {
+                        ValueSetMatch::NoMatch
+                    }
+                }
+                _ => ValueSetMatch::NoMatch,
+            },
+            ValueSetValue::Language { language_tag } => match object_lang(object) {
+                Some(lang) => matches_bool(lang == *language_tag),
+                None => ValueSetMatch::NoMatch,
+            },
+            ValueSetValue::LanguageStem { stem } => match object_lang(object) {
+                Some(lang) => matches_bool(lang_matches_stem(&lang, stem)),
+                None => ValueSetMatch::NoMatch,
+            },
+            ValueSetValue::LanguageStemRange { stem, exclusions } => match object_lang(object) {
+                Some(lang) => {
+                    let matches_stem = match stem {
+                        LangOrWildcard::Lang(stem) => lang_matches_stem(&lang, stem),
+                        LangOrWildcard::Wildcard => true,
+                    };
+                    if matches_stem {
+                        check_exclusions(exclusions, |e| e.excludes(&lang))
+                    } else {
+                        ValueSetMatch::NoMatch
+                    }
+                }
+                None => ValueSetMatch::NoMatch,
+            },
+            ValueSetValue::ObjectValue(v) => matches_bool(v.match_value(object)),
+        }
+    }
+}
+
+impl IriExclusion {
+    fn excludes(&self, iri: &IriS) -> bool {
+        match self {
+            IriExclusion::Iri(e) => iri == e,
+            IriExclusion::IriStem(stem) => iri.as_str().starts_with(stem.as_str()),
+        }
+    }
+}
+
+impl LiteralExclusion {
+    fn excludes(&self, lexical_form: &str) -> bool {
+        match self {
+            LiteralExclusion::Literal(e) => lexical_form == e,
+            LiteralExclusion::LiteralStem(stem) => lexical_form.starts_with(stem.as_str()),
+        }
+    }
+}
+
+impl LanguageExclusion {
+    fn excludes(&self, lang: &Lang) -> bool {
         match self {
-            ValueSetValue::IriStem { .. } => todo!(),
-            ValueSetValue::IriStemRange { .. } => todo!(),
-            ValueSetValue::LiteralStem { .. } => todo!(),
-            ValueSetValue::LiteralStemRange { .. } => todo!(),
-            ValueSetValue::Language { .. } => todo!(),
-            ValueSetValue::LanguageStem => todo!(),
-            ValueSetValue::LanguageStemRange => todo!(),
-            ValueSetValue::ObjectValue(v) => v.match_value(object),
+            LanguageExclusion::Language(e) => lang == e,
+            LanguageExclusion::LanguageStem(stem) => lang_matches_stem(lang, stem),
+        }
+    }
+}
+
+fn matches_bool(b: bool) -> ValueSetMatch {
+    if b {
+        ValueSetMatch::Matches
+    } else {
+        ValueSetMatch::NoMatch
+    }
+}
+
+fn check_exclusions<E: Display>(
+    exclusions: &Option<Vec<E>>,
+    excludes: impl Fn(&E) -> bool,
+) -> ValueSetMatch {
+    let found = exclusions
+        .iter()
+        .flatten()
+        .find(|exclusion| excludes(exclusion));
+    match found {
+        Some(exclusion) => ValueSetMatch::Excluded {
+            exclusion: exclusion.to_string(),
+        },
+        None => ValueSetMatch::Matches,
+    }
+}
+
+fn object_lang(object: &Object) -> Option<Lang> {
+    match object {
+        Object::Literal(lit @ Literal::StringLiteral { .. }) => {
+            literal_parts(lit).and_then(|(_, lang, _)| lang)
         }
+        _ => None,
     }
 }
 
+/// A language tag matches a stem if it is equal to it or if it starts by the stem followed by `-`.
+/// The empty stem matches any language tag
+fn lang_matches_stem(lang: &Lang, stem: &Lang) -> bool {
+    let lang = lang.value();
+    let stem = stem.value();
+    stem.is_empty()
+        || lang == stem
+        || lang
+            .strip_prefix(stem.as_str())
+            .is_some_and(|rest| rest.starts_with('-'))
+}
+
 impl Display for ValueSetValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ValueSetValue::IriStem { .. } => todo!(),
-            ValueSetValue::IriStemRange { .. } => todo!(),
-            ValueSetValue::LiteralStem { .. } => todo!(),
-            ValueSetValue::LiteralStemRange { .. } => todo!(),
-            ValueSetValue::Language { .. } => todo!(),
-            ValueSetValue::LanguageStem => todo!(),
-            ValueSetValue::LanguageStemRange => todo!(),
+            ValueSetValue::IriStem { stem } => write!(f, "<{stem}>~"),
+            ValueSetValue::IriStemRange { stem, exclusions } => {
+                match stem {
+                    IriRefOrWildcard::IriRef(stem) => write!(f, "<{stem}>~")?,
+                    IriRefOrWildcard::Wildcard => write!(f, ".")?,
+                }
+                write_exclusions(f, exclusions)
+            }
+            ValueSetValue::LiteralStem { stem } => write!(f, "\"{stem}\"~"),
+            ValueSetValue::LiteralStemRange { stem, exclusions } => {
+                match stem {
+                    StringOrWildcard::String(stem) => write!(f, "\"{stem}\"~")?,
+                    StringOrWildcard::Wildcard => write!(f, ".")?,
+                }
+                write_exclusions(f, exclusions)
+            }
+            ValueSetValue::Language { language_tag } => write!(f, "{language_tag}"),
+            ValueSetValue::LanguageStem { stem } => write!(f, "{stem}~"),
+            ValueSetValue::LanguageStemRange { stem, exclusions } => {
+                match stem {
+                    LangOrWildcard::Lang(stem) => write!(f, "{stem}~")?,
+                    LangOrWildcard::Wildcard => write!(f, ".")?,
+                }
+                write_exclusions(f, exclusions)
+            }
             ValueSetValue::ObjectValue(ov) => write!(f, "{ov}"),
         }
     }
 }
+
+fn write_exclusions<E: Display>(
+    f: &mut std::fmt::Formatter<'_>,
+    exclusions: &Option<Vec<E>>,
+) -> std::fmt::Result {
+    for exclusion in exclusions.iter().flatten() {
+        write!(f, " - {exclusion}")?;
+    }
+    Ok(())
+}
+
+impl Display for IriExclusion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IriExclusion::Iri(iri) => write!(f, "<{iri}>"),
+            IriExclusion::IriStem(stem) => write!(f, "<{stem}>~"),
+        }
+    }
+}
+
+impl Display for LiteralExclusion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LiteralExclusion::Literal(str) => write!(f, "\"{str}\""),
+            LiteralExclusion::LiteralStem(stem) => write!(f, "\"{stem}\"~"),
+        }
+    }
+}
+
+impl Display for LanguageExclusion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LanguageExclusion::Language(lang) => write!(f, "{lang}"),
+            LanguageExclusion::LanguageStem(stem) => write!(f, "{stem}~"),
+        }
+    }
+}
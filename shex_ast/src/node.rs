@@ -1,6 +1,7 @@
 use iri_s::IriS;
 use rbe::Value;
 use serde_derive::Serialize;
+use srdf::literal::Literal;
 use srdf::numeric_literal::NumericLiteral;
 use srdf::Object;
 use std::fmt::Display;
@@ -20,6 +21,20 @@ impl Node {
         }
     }
 
+    /// Creates a node from a blank node identifier
+    pub fn bnode(id: &str) -> Node {
+        Node {
+            node: Object::bnode(id.to_string()),
+        }
+    }
+
+    /// Creates a node from a [`Literal`]
+    pub fn literal(lit: Literal) -> Node {
+        Node {
+            node: Object::literal(lit),
+        }
+    }
+
     /// Returns the length of the RDF Node
     pub fn length(&self) -> usize {
         self.node.length()
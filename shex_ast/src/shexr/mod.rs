@@ -1,5 +1,6 @@
 pub mod shexr_error;
 pub mod shexr_parser;
 pub mod shexr_vocab;
+pub mod shexr_writer;
 
 pub use shexr_vocab::*;
@@ -24,6 +24,31 @@ pub enum ShExRError {
 
     #[error("Unexpected value for nodeKind: {iri}")]
     UnexpectedNodeKind { iri: IriS },
+
+    #[error("Error obtaining RDF data: {err}")]
+    SRDFError { err: String },
+
+    #[error("Node {node} has no value for property {pred}")]
+    NoValue { node: String, pred: IriS },
+
+    #[error("Node {node} has more than one value for property {pred}")]
+    MoreThanOneValue { node: String, pred: IriS },
+
+    #[error("Node {node} was expected to be {expected}")]
+    UnexpectedNode { node: String, expected: String },
+
+    #[error("Node {node} is not a valid RDF list")]
+    NoList { node: String },
+}
+
+/// Errors writing a schema as ShExR
+#[derive(Debug, Error)]
+pub enum ShExRWriterError {
+    #[error("Error building RDF data: {err}")]
+    SRDFError { err: String },
+
+    #[error("Node {node} can't be the subject of a triple")]
+    NoSubject { node: String },
 }
 
 #[derive(Debug)]
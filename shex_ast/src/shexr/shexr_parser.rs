@@ -1,24 +1,34 @@
-use super::shexr_error::ShExRError;
+use super::shexr_error::{Nodes, ShExRError};
 use super::*;
 use crate::{
-    BNode, NodeConstraint, NodeKind, ObjectValue, Schema, Shape, ShapeDecl, ShapeExpr,
-    ShapeExprLabel, ValueSetValue,
+    Annotation, BNode, IriExclusion, IriOrStr, LangOrWildcard, LanguageExclusion, LiteralExclusion,
+    Node, NodeConstraint, NodeKind, NumericFacet, ObjectValue, Pattern, Schema, SemAct, Shape,
+    ShapeDecl, ShapeExpr, ShapeExprLabel, StringFacet, TripleExpr, TripleExprLabel, ValueSetValue,
+    XsFacet,
 };
 use iri_s::IriS;
 use prefixmap::IriRef;
-use srdf::rdf_parser;
-use srdf::srdf_parser::*;
-use srdf::FocusRDF;
-use srdf::RDFParseError;
-use srdf::{Object, RDFParser};
+use srdf::lang::Lang;
+use srdf::literal::Literal;
+use srdf::numeric_literal::NumericLiteral;
+use srdf::{FocusRDF, Object, RDFParser, RDF_FIRST, RDF_NIL, RDF_TYPE, XSD_BOOLEAN_STR};
+use std::collections::HashSet;
+
+use crate::ast::iri_ref_or_wildcard::IriRefOrWildcard;
+use crate::ast::string_or_wildcard::StringOrWildcard;
 
 type Result<A> = std::result::Result<A, ShExRError>;
 
+/// Parses a ShEx schema from its RDF representation (ShExR)
+///
+/// Triple expressions with an IRI as identifier are parsed the first time they
+/// are found and become references to that identifier afterwards.
 pub struct ShExRParser<RDF>
 where
     RDF: FocusRDF,
 {
     rdf_parser: RDFParser<RDF>,
+    triple_expr_labels: HashSet<TripleExprLabel>,
 }
 
 impl<RDF> ShExRParser<RDF>
@@ -28,267 +38,650 @@ where
     pub fn new(rdf: RDF) -> ShExRParser<RDF> {
         ShExRParser {
             rdf_parser: RDFParser::new(rdf),
+            triple_expr_labels: HashSet::new(),
         }
     }
 
     pub fn parse(&mut self) -> Result<Schema> {
-        let schema = Self::schema_parser()
-            .parse_impl(&mut self.rdf_parser.rdf)
-            .map_err(|e| ShExRError::RDFParseError { err: e })?;
+        let schema_type = RDF::iri_s2term(&ShExRVocab::sx_schema());
+        let mut schema_nodes = self
+            .rdf_parser
+            .instances_of(&schema_type)?
+            .map(|subject| RDF::term_as_object(&RDF::subject_as_term(&subject)))
+            .collect::<Vec<_>>();
+        let schema_node = match schema_nodes.len() {
+            0 => Err(ShExRError::NoSchemaNodes),
+            1 => Ok(schema_nodes.remove(0)),
+            _ => Err(ShExRError::MoreThanOneSchema {
+                nodes: Nodes::new(schema_nodes.into_iter().map(Node::from).collect()),
+            }),
+        }?;
+        let schema = self.schema(&schema_node)?;
         let prefixmap = self.rdf_parser.prefixmap();
         Ok(schema.with_prefixmap(prefixmap))
     }
 
-    pub fn schema_parser() -> impl RDFNodeParse<RDF, Output = Schema> {
-        instance_of(&ShExRVocab::sx_schema())
-            .then(|node| set_focus_subject(node).then(|_| Self::schema()))
+    fn schema(&mut self, node: &Object) -> Result<Schema> {
+        let mut schema = Schema::new();
+        for import in self.values_or_list(node, SX_IMPORTS)? {
+            let iri = Self::iri(&import)?;
+            schema = schema.with_import(IriOrStr::new(iri.as_str()));
+        }
+        let start_acts = self.sem_acts(node, SX_STARTACTS)?;
+        let start = match self.value(node, SX_START)? {
+            Some(start) => Some(self.shape_expr(&start)?),
+            None => None,
+        };
+        let shapes = match self.value(node, SX_SHAPES)? {
+            Some(shapes) => {
+                let mut decls = Vec::new();
+                for decl in self.list(&shapes)? {
+                    decls.push(self.shape_decl(&decl)?);
+                }
+                Some(decls)
+            }
+            None => None,
+        };
+        Ok(schema
+            .with_start_actions(start_acts)
+            .with_start(start)
+            .with_shapes(shapes))
     }
 
-    fn schema() -> impl RDFNodeParse<RDF, Output = Schema> {
-        property_value(&sx_shapes()).then(|ref node| {
-            set_focus(node)
-                .and(parse_rdf_list::<RDF, _>(Self::shape_decl()))
-                .map(|(_, vs)| Schema::new().with_shapes(Some(vs)))
-        })
+    fn shape_decl(&mut self, node: &Object) -> Result<ShapeDecl> {
+        let label = Self::shape_label(node)?;
+        let is_abstract = self.bool_value(node, SX_ABSTRACT)?.unwrap_or(false);
+        let shape_expr = match self.value(node, SX_SHAPE_EXPR)? {
+            Some(se) => self.shape_expr(&se)?,
+            // Shape declarations without sx:shapeExpr are shape expressions themselves
+            None => match self.shape_expr_def(node)? {
+                Some(se) => se,
+                None => Err(ShExRError::UnexpectedNode {
+                    node: node.to_string(),
+                    expected: "a shape declaration".to_string(),
+                })?,
+            },
+        };
+        Ok(ShapeDecl::new(label, shape_expr, is_abstract))
     }
 
-    fn term_to_shape_label(term: &RDF::Term) -> Result<ShapeExprLabel> {
-        let object = RDF::term_as_object(term);
-        match object {
-            Object::Iri(iri) => Ok(ShapeExprLabel::iri(iri)),
-            Object::BlankNode(bnode) => Ok(ShapeExprLabel::bnode(BNode::new(bnode.as_str()))),
-            Object::Literal(lit) => Err(ShExRError::ShapeExprLabelLiteral { lit }),
+    fn shape_expr(&mut self, node: &Object) -> Result<ShapeExpr> {
+        if *node == Object::iri(IriS::new_unchecked(SX_START)) {
+            return Ok(ShapeExpr::Ref(ShapeExprLabel::Start));
+        }
+        match self.shape_expr_def(node)? {
+            Some(se) => Ok(se),
+            None => Ok(ShapeExpr::Ref(Self::shape_label(node)?)),
         }
     }
 
-    fn shape_decl() -> impl RDFNodeParse<RDF, Output = ShapeDecl> {
-        (term().flat_map(move |ref t| {
-            let label = Self::term_to_shape_label(t).map_err(|e| RDFParseError::Custom {
-                msg: format!("Expected term to be a label: {t}: {e}"),
-            })?;
-            Ok(label)
-        }))
-        .and(Self::parse_shape_expr())
-        .map(|(label, se)| ShapeDecl::new(label, se, false))
+    /// Parses a node whose `rdf:type` is one of the shape expression classes,
+    /// returning `None` for other nodes, which are shape references
+    fn shape_expr_def(&mut self, node: &Object) -> Result<Option<ShapeExpr>> {
+        let types = self.types(node)?;
+        let has_type = |t: &str| types.iter().any(|iri| iri.as_str() == t);
+        if has_type(SX_SHAPE_OR) || has_type(SX_SHAPE_AND) {
+            let list = self.required_value(node, SX_SHAPE_EXPRS)?;
+            let mut ses = Vec::new();
+            for se in self.list(&list)? {
+                ses.push(self.shape_expr(&se)?);
+            }
+            if has_type(SX_SHAPE_OR) {
+                Ok(Some(ShapeExpr::or(ses)))
+            } else {
+                Ok(Some(ShapeExpr::and(ses)))
+            }
+        } else if has_type(SX_SHAPE_NOT) {
+            let se = self.required_value(node, SX_SHAPE_EXPR)?;
+            Ok(Some(ShapeExpr::shape_not(self.shape_expr(&se)?)))
+        } else if has_type(SX_NODECONSTRAINT) {
+            Ok(Some(ShapeExpr::node_constraint(
+                self.node_constraint(node)?,
+            )))
+        } else if has_type(SX_SHAPE) {
+            Ok(Some(ShapeExpr::shape(self.shape(node)?)))
+        } else if has_type(SX_SHAPE_EXTERNAL) {
+            Ok(Some(ShapeExpr::external()))
+        } else {
+            Ok(None)
+        }
     }
 
-    fn parse_shape_expr() -> impl RDFNodeParse<RDF, Output = ShapeExpr> {
-        property_value(&sx_shape_expr()).then(|ref node| set_focus(node).then(|_| shape_expr()))
+    fn shape(&mut self, node: &Object) -> Result<Shape> {
+        let closed = self.bool_value(node, SX_CLOSED)?;
+        let extra = self
+            .values_or_list(node, SX_EXTRA)?
+            .iter()
+            .map(|e| Self::iri(e).map(IriRef::Iri))
+            .collect::<Result<Vec<_>>>()?;
+        let extends = self
+            .values_or_list(node, SX_EXTENDS)?
+            .iter()
+            .map(Self::shape_label)
+            .collect::<Result<Vec<_>>>()?;
+        let expression = match self.value(node, SX_EXPRESSION)? {
+            Some(te) => Some(self.triple_expr(&te)?),
+            None => None,
+        };
+        Ok(Shape::new(closed, non_empty(extra), expression)
+            .with_extends(non_empty(extends))
+            .with_sem_acts(self.sem_acts(node, SX_SEMACTS)?)
+            .with_annotations(self.annotations(node)?))
     }
-}
 
-fn shape_expr_<RDF>() -> impl RDFNodeParse<RDF, Output = ShapeExpr>
-where
-    RDF: FocusRDF,
-{
-    // I would like the following code to work...but it doesn't yet...
-    /*parse_by_type(vec![
-     (sx_shape_and(), shape_and()),
-     . . .
-     (sx_node_constraint(), node_constraint())
-    ], default) */
-
-    shape_and().or(shape_or()).or(shape()).or(node_constraint())
-}
+    fn triple_expr(&mut self, node: &Object) -> Result<TripleExpr> {
+        let types = self.types(node)?;
+        let has_type = |t: &str| types.iter().any(|iri| iri.as_str() == t);
+        let id = match node {
+            Object::Iri(iri) => Some(TripleExprLabel::IriRef {
+                value: IriRef::Iri(iri.clone()),
+            }),
+            _ => None,
+        };
+        let is_definition =
+            has_type(SX_TRIPLECONSTRAINT) || has_type(SX_EACHOF) || has_type(SX_ONEOF);
+        if !is_definition {
+            return Ok(TripleExpr::TripleExprRef(Self::triple_expr_label(node)?));
+        }
+        if let Some(id) = &id {
+            if !self.triple_expr_labels.insert(id.clone()) {
+                return Ok(TripleExpr::TripleExprRef(id.clone()));
+            }
+        }
+        let min = self.int_value(node, SX_MIN)?.map(|n| n as i32);
+        let max = self.int_value(node, SX_MAX)?.map(|n| n as i32);
+        let sem_acts = self.sem_acts(node, SX_SEMACTS)?;
+        let annotations = self.annotations(node)?;
+        if has_type(SX_TRIPLECONSTRAINT) {
+            let predicate = IriRef::Iri(Self::iri(&self.required_value(node, SX_PREDICATE)?)?);
+            let value_expr = match self.value(node, SX_VALUEEXPR)? {
+                Some(se) => Some(Box::new(self.shape_expr(&se)?)),
+                None => None,
+            };
+            Ok(TripleExpr::TripleConstraint {
+                id,
+                negated: self.bool_value(node, SX_NEGATED)?,
+                inverse: self.bool_value(node, SX_INVERSE)?,
+                predicate,
+                value_expr,
+                min,
+                max,
+                sem_acts,
+                annotations,
+            })
+        } else {
+            let list = self.required_value(node, SX_EXPRESSIONS)?;
+            let mut expressions = Vec::new();
+            for te in self.list(&list)? {
+                expressions.push(self.triple_expr(&te)?.into());
+            }
+            if has_type(SX_EACHOF) {
+                Ok(TripleExpr::EachOf {
+                    id,
+                    expressions,
+                    min,
+                    max,
+                    sem_acts,
+                    annotations,
+                })
+            } else {
+                Ok(TripleExpr::OneOf {
+                    id,
+                    expressions,
+                    min,
+                    max,
+                    sem_acts,
+                    annotations,
+                })
+            }
+        }
+    }
 
-fn shape<RDF>() -> impl RDFNodeParse<RDF, Output = ShapeExpr>
-where
-    RDF: FocusRDF,
-{
-    has_type(sx_shape()).with({
-        closed().then(|maybe_closed| {
-            let extra = None; // TODO
-            let expression = None; // TODO
-            ok(&ShapeExpr::shape(Shape::new(
-                maybe_closed,
-                extra,
-                expression,
-            )))
-        })
-    })
-}
+    fn node_constraint(&mut self, node: &Object) -> Result<NodeConstraint> {
+        let mut nc = NodeConstraint::new();
+        if let Some(nk) = self.value(node, SX_NODEKIND)? {
+            nc = nc.with_node_kind(node_kind(&Self::iri(&nk)?)?);
+        }
+        if let Some(dt) = self.value(node, SX_DATATYPE)? {
+            nc = nc.with_datatype(IriRef::Iri(Self::iri(&dt)?));
+        }
+        nc = nc.with_xsfacets(self.xs_facets(node)?);
+        if let Some(values) = self.value(node, SX_VALUES)? {
+            let mut vs = Vec::new();
+            for value in self.list(&values)? {
+                vs.push(self.value_set_value(&value)?);
+            }
+            nc = nc.with_values(vs);
+        }
+        Ok(nc)
+    }
 
-fn closed<RDF>() -> impl RDFNodeParse<RDF, Output = Option<bool>>
-where
-    RDF: FocusRDF,
-{
-    optional(property_bool(&sx_closed()))
-}
+    /// Facets are returned in the same order as the ShExJ deserializer
+    fn xs_facets(&mut self, node: &Object) -> Result<Vec<XsFacet>> {
+        let mut facets = Vec::new();
+        if let Some(pattern) = self.string_value(node, SX_PATTERN)? {
+            let pattern = match self.string_value(node, SX_FLAGS)? {
+                Some(flags) => Pattern::new_flags(&pattern, &flags),
+                None => Pattern::new(&pattern),
+            };
+            facets.push(XsFacet::StringFacet(StringFacet::Pattern(pattern)));
+        }
+        let string_facets = [
+            (SX_LENGTH, StringFacet::Length as fn(usize) -> StringFacet),
+            (SX_MINLENGTH, StringFacet::MinLength),
+            (SX_MAXLENGTH, StringFacet::MaxLength),
+        ];
+        for (pred, facet) in string_facets {
+            if let Some(n) = self.int_value(node, pred)? {
+                facets.push(XsFacet::StringFacet(facet(n as usize)));
+            }
+        }
+        let numeric_facets = [
+            (
+                SX_MININCLUSIVE,
+                NumericFacet::MinInclusive as fn(NumericLiteral) -> NumericFacet,
+            ),
+            (SX_MAXINCLUSIVE, NumericFacet::MaxInclusive),
+            (SX_MINEXCLUSIVE, NumericFacet::MinExclusive),
+            (SX_MAXEXCLUSIVE, NumericFacet::MaxExclusive),
+        ];
+        for (pred, facet) in numeric_facets {
+            if let Some(value) = self.value(node, pred)? {
+                match Self::literal(&value)? {
+                    Literal::NumericLiteral(n) => facets.push(XsFacet::NumericFacet(facet(n))),
+                    _ => Err(ShExRError::UnexpectedNode {
+                        node: value.to_string(),
+                        expected: "a numeric literal".to_string(),
+                    })?,
+                }
+            }
+        }
+        if let Some(n) = self.int_value(node, SX_TOTALDIGITS)? {
+            facets.push(XsFacet::NumericFacet(NumericFacet::TotalDigits(n as usize)));
+        }
+        if let Some(n) = self.int_value(node, SX_FRACTIONDIGITS)? {
+            facets.push(XsFacet::NumericFacet(NumericFacet::FractionDigits(
+                n as usize,
+            )));
+        }
+        Ok(facets)
+    }
 
-rdf_parser! {
-    pub fn shape_and[RDF]()(RDF) -> ShapeExpr where [] {
-        has_type(sx_shape_and()).with(
-        property_value(&sx_shape_exprs()).then(|ref node| {
-            set_focus(node).and(
-                   parse_rdf_list::<RDF, _>(shape_expr())
-                 ).map(|(_,vs)| { ShapeExpr::and(vs) })
-           }))
+    fn value_set_value(&mut self, node: &Object) -> Result<ValueSetValue> {
+        match node {
+            Object::Iri(iri) => Ok(ValueSetValue::iri(IriRef::Iri(iri.clone()))),
+            Object::Literal(_) => Ok(ValueSetValue::ObjectValue(ObjectValue::Literal(
+                Self::literal(node)?,
+            ))),
+            Object::BlankNode(_) => {
+                let types = self.types(node)?;
+                let has_type = |t: &str| types.iter().any(|iri| iri.as_str() == t);
+                if has_type(SX_LANGUAGE) {
+                    let tag = self.required_string(node, SX_LANGUAGE_TAG)?;
+                    Ok(ValueSetValue::language(Lang::new(&tag)))
+                } else if has_type(SX_IRISTEM) {
+                    let stem = self.required_value(node, SX_STEM)?;
+                    Ok(ValueSetValue::IriStem {
+                        stem: IriRef::Iri(Self::stem_iri(&stem)?),
+                    })
+                } else if has_type(SX_LITERALSTEM) {
+                    let stem = self.required_string(node, SX_STEM)?;
+                    Ok(ValueSetValue::literal_stem(stem))
+                } else if has_type(SX_LANGUAGESTEM) {
+                    let stem = self.required_string(node, SX_STEM)?;
+                    Ok(ValueSetValue::language_stem(Lang::new(&stem)))
+                } else if has_type(SX_IRISTEMRANGE) {
+                    let stem = match self.stem_range(node)? {
+                        Some(stem) => IriRefOrWildcard::IriRef(IriRef::Iri(Self::stem_iri(&stem)?)),
+                        None => IriRefOrWildcard::Wildcard,
+                    };
+                    let exclusions = self.exclusions(node, |parser, exc, is_stem| {
+                        let iri = if is_stem {
+                            let stem = parser.required_value(exc, SX_STEM)?;
+                            IriRef::Iri(Self::stem_iri(&stem)?)
+                        } else {
+                            IriRef::Iri(Self::iri(exc)?)
+                        };
+                        Ok(if is_stem {
+                            IriExclusion::IriStem(iri)
+                        } else {
+                            IriExclusion::Iri(iri)
+                        })
+                    })?;
+                    Ok(ValueSetValue::IriStemRange { stem, exclusions })
+                } else if has_type(SX_LITERALSTEMRANGE) {
+                    let stem = match self.stem_range(node)? {
+                        Some(stem) => StringOrWildcard::String(Self::string(&stem)?),
+                        None => StringOrWildcard::Wildcard,
+                    };
+                    let exclusions = self.exclusions(node, |parser, exc, is_stem| {
+                        if is_stem {
+                            let stem = parser.required_string(exc, SX_STEM)?;
+                            Ok(LiteralExclusion::LiteralStem(stem))
+                        } else {
+                            Ok(LiteralExclusion::Literal(Self::string(exc)?))
+                        }
+                    })?;
+                    Ok(ValueSetValue::LiteralStemRange { stem, exclusions })
+                } else if has_type(SX_LANGUAGESTEMRANGE) {
+                    let stem = match self.stem_range(node)? {
+                        Some(stem) => LangOrWildcard::Lang(Lang::new(&Self::string(&stem)?)),
+                        None => LangOrWildcard::Wildcard,
+                    };
+                    let exclusions = self.exclusions(node, |parser, exc, is_stem| {
+                        if is_stem {
+                            let stem = parser.required_string(exc, SX_STEM)?;
+                            Ok(LanguageExclusion::LanguageStem(Lang::new(&stem)))
+                        } else {
+                            Ok(LanguageExclusion::Language(Lang::new(&Self::string(exc)?)))
+                        }
+                    })?;
+                    Ok(ValueSetValue::LanguageStemRange { stem, exclusions })
+                } else {
+                    Err(ShExRError::UnexpectedNode {
+                        node: node.to_string(),
+                        expected: "a value set value".to_string(),
+                    })
+                }
+            }
+            Object::Triple { .. } => Err(ShExRError::UnexpectedNode {
+                node: node.to_string(),
+                expected: "a value set value".to_string(),
+            }),
+        }
     }
-}
 
-rdf_parser! {
-    pub fn shape_or[RDF]()(RDF) -> ShapeExpr where [] {
-        has_type(sx_shape_or()).with(
-        property_value(&sx_shape_exprs()).then(|ref node| {
-            set_focus(node).and(
-                   parse_rdf_list::<RDF, _>(shape_expr())
-                 ).map(|(_,vs)| { ShapeExpr::or(vs) })
-           }))
+    /// Stem of a stem range, `None` if it is a wildcard
+    fn stem_range(&mut self, node: &Object) -> Result<Option<Object>> {
+        let stem = self.required_value(node, SX_STEM)?;
+        let is_wildcard = self.types(&stem)?.iter().any(|t| t.as_str() == SX_WILDCARD);
+        Ok(if is_wildcard { None } else { Some(stem) })
     }
-}
 
-rdf_parser! {
-    pub fn shape_expr[RDF]()(RDF) -> ShapeExpr where [] {
-       shape_expr_()
+    /// Parses the exclusions of a stem range, passing to `parse_exclusion`
+    /// whether each exclusion is a stem or a single value
+    fn exclusions<E>(
+        &mut self,
+        node: &Object,
+        parse_exclusion: impl Fn(&mut Self, &Object, bool) -> Result<E>,
+    ) -> Result<Option<Vec<E>>> {
+        if self.value(node, SX_EXCLUSION)?.is_none() {
+            return Ok(None);
+        }
+        let mut exclusions = Vec::new();
+        for exc in self.values_or_list(node, SX_EXCLUSION)? {
+            let is_stem = matches!(exc, Object::BlankNode(_));
+            exclusions.push(parse_exclusion(self, &exc, is_stem)?);
+        }
+        Ok(Some(exclusions))
     }
-}
 
-/*
-#[inline]
-fn sx_schema<RDF>() -> RDF::Term
-where
-    RDF: FocusRDF,
-{
-    RDFParser::<RDF>::term_iri_unchecked(SX_SCHEMA)
-}
-*/
+    fn sem_acts(&mut self, node: &Object, pred: &str) -> Result<Option<Vec<SemAct>>> {
+        if self.value(node, pred)?.is_none() {
+            return Ok(None);
+        }
+        let mut sem_acts = Vec::new();
+        for sem_act in self.values_or_list(node, pred)? {
+            let name = Self::iri(&self.required_value(&sem_act, SX_NAME)?)?;
+            let code = self.string_value(&sem_act, SX_CODE)?;
+            sem_acts.push(SemAct::new(IriRef::Iri(name), code));
+        }
+        Ok(Some(sem_acts))
+    }
 
-#[inline]
-fn sx_shapes() -> IriS {
-    IriS::new_unchecked(SX_SHAPES)
-}
+    fn annotations(&mut self, node: &Object) -> Result<Option<Vec<Annotation>>> {
+        if self.value(node, SX_ANNOTATION_PROP)?.is_none() {
+            return Ok(None);
+        }
+        let mut annotations = Vec::new();
+        for annotation in self.values_or_list(node, SX_ANNOTATION_PROP)? {
+            let predicate = Self::iri(&self.required_value(&annotation, SX_PREDICATE)?)?;
+            let object = match self.required_value(&annotation, SX_OBJECT)? {
+                Object::Iri(iri) => ObjectValue::iri(iri),
+                lit @ Object::Literal(_) => ObjectValue::Literal(Self::literal(&lit)?),
+                bnode => Err(ShExRError::UnexpectedNode {
+                    node: bnode.to_string(),
+                    expected: "an IRI or a literal".to_string(),
+                })?,
+            };
+            annotations.push(Annotation::new(IriRef::Iri(predicate), object));
+        }
+        Ok(Some(annotations))
+    }
 
-fn sx_shape_expr() -> IriS {
-    IriS::new_unchecked(SX_SHAPE_EXPR)
-}
+    fn types(&self, node: &Object) -> Result<Vec<IriS>> {
+        Ok(self
+            .values(node, RDF_TYPE.as_str())?
+            .into_iter()
+            .filter_map(|t| match t {
+                Object::Iri(iri) => Some(iri),
+                _ => None,
+            })
+            .collect())
+    }
 
-fn sx_closed() -> IriS {
-    IriS::new_unchecked(SX_CLOSED)
-}
+    fn values(&self, node: &Object, pred: &str) -> Result<Vec<Object>> {
+        let subject = match RDF::object_as_subject(node) {
+            Some(subject) => subject,
+            None => return Ok(Vec::new()),
+        };
+        let pred = RDF::iri_s2iri(&IriS::new_unchecked(pred));
+        let mut values = self
+            .rdf_parser
+            .rdf
+            .objects_for_subject_predicate(&subject, &pred)
+            .map_err(|e| ShExRError::SRDFError { err: e.to_string() })?
+            .iter()
+            .map(RDF::term_as_object)
+            .collect::<Vec<_>>();
+        values.sort_by_key(|value| value.to_string());
+        Ok(values)
+    }
 
-#[inline]
-fn sx_values() -> IriS {
-    IriS::new_unchecked(SX_VALUES)
-}
+    fn value(&self, node: &Object, pred: &str) -> Result<Option<Object>> {
+        let mut values = self.values(node, pred)?;
+        match values.len() {
+            0 => Ok(None),
+            1 => Ok(values.pop()),
+            _ => Err(ShExRError::MoreThanOneValue {
+                node: node.to_string(),
+                pred: IriS::new_unchecked(pred),
+            }),
+        }
+    }
 
-#[inline]
-fn sx_shape() -> IriS {
-    IriS::new_unchecked(SX_SHAPE)
-}
+    fn required_value(&self, node: &Object, pred: &str) -> Result<Object> {
+        self.value(node, pred)?.ok_or_else(|| ShExRError::NoValue {
+            node: node.to_string(),
+            pred: IriS::new_unchecked(pred),
+        })
+    }
 
-#[inline]
-fn sx_shape_exprs() -> IriS {
-    IriS::new_unchecked(SX_SHAPE_EXPRS)
-}
+    fn bool_value(&self, node: &Object, pred: &str) -> Result<Option<bool>> {
+        match self.value(node, pred)? {
+            Some(value) => match Self::literal(&value)? {
+                Literal::BooleanLiteral(b) => Ok(Some(b)),
+                _ => Err(ShExRError::UnexpectedNode {
+                    node: value.to_string(),
+                    expected: "a boolean".to_string(),
+                }),
+            },
+            None => Ok(None),
+        }
+    }
 
-#[inline]
-fn sx_node_kind() -> IriS {
-    IriS::new_unchecked(SX_NODEKIND)
-}
+    fn int_value(&self, node: &Object, pred: &str) -> Result<Option<isize>> {
+        match self.value(node, pred)? {
+            Some(value) => match Self::literal(&value)?.lexical_form().parse() {
+                Ok(n) => Ok(Some(n)),
+                Err(_) => Err(ShExRError::UnexpectedNode {
+                    node: value.to_string(),
+                    expected: "an integer".to_string(),
+                }),
+            },
+            None => Ok(None),
+        }
+    }
 
-fn sx_shape_and() -> IriS {
-    IriS::new_unchecked(SX_SHAPE_AND)
-}
+    fn string_value(&self, node: &Object, pred: &str) -> Result<Option<String>> {
+        match self.value(node, pred)? {
+            Some(value) => Ok(Some(Self::string(&value)?)),
+            None => Ok(None),
+        }
+    }
 
-fn sx_shape_or() -> IriS {
-    IriS::new_unchecked(SX_SHAPE_OR)
-}
+    fn required_string(&self, node: &Object, pred: &str) -> Result<String> {
+        Self::string(&self.required_value(node, pred)?)
+    }
 
-/*
-fn sx_node_constraint() -> IriS {
-    IriS::new_unchecked(SX_NODECONSTRAINT)
-}
-*/
-
-rdf_parser! {
- fn node_constraint[RDF]()(RDF) -> ShapeExpr
- where [] {
-    parse_nodekind().then(|maybe_nodekind|
-        parse_value_set().then(move |maybe_valueset| {
-           let mut nc = NodeConstraint::new();
-           if let Some(nk) = &maybe_nodekind {
-              nc = nc.with_node_kind(nk.clone());
-           }
-           if let Some(vs) = &maybe_valueset {
-              nc = nc.with_values(vs.clone())
-           }
-           ok(&ShapeExpr::node_constraint(nc))
-         }
+    /// Values of a property which can be either repeated or an RDF list
+    fn values_or_list(&self, node: &Object, pred: &str) -> Result<Vec<Object>> {
+        let mut result = Vec::new();
+        for value in self.values(node, pred)? {
+            if self.is_list(&value)? {
+                result.extend(self.list(&value)?)
+            } else {
+                result.push(value)
+            }
+        }
+        Ok(result)
+    }
+
+    fn is_list(&self, node: &Object) -> Result<bool> {
+        Ok(
+            *node == Object::iri(RDF_NIL.clone())
+                || self.value(node, RDF_FIRST.as_str())?.is_some(),
         )
-    )
-  }
-}
+    }
 
-fn parse_nodekind<RDF>() -> impl RDFNodeParse<RDF, Output = Option<NodeKind>>
-where
-    RDF: FocusRDF,
-{
-    optional(
-        property_value(&sx_node_kind())
-            .then(|ref node| set_focus(node).and(nodekind()).map(|(_, vs)| vs)),
-    )
-}
+    fn list(&self, node: &Object) -> Result<Vec<Object>> {
+        let mut items = Vec::new();
+        let mut visited = HashSet::new();
+        let mut current = node.clone();
+        while current != Object::iri(RDF_NIL.clone()) {
+            if !visited.insert(current.clone()) {
+                return Err(ShExRError::NoList {
+                    node: node.to_string(),
+                });
+            }
+            let first = self.value(&current, RDF_FIRST.as_str())?;
+            let rest = self.value(&current, srdf::RDF_REST.as_str())?;
+            match (first, rest) {
+                (Some(first), Some(rest)) => {
+                    items.push(first);
+                    current = rest;
+                }
+                _ => {
+                    return Err(ShExRError::NoList {
+                        node: node.to_string(),
+                    })
+                }
+            }
+        }
+        Ok(items)
+    }
 
-fn nodekind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
-where
-    RDF: FocusRDF,
-{
-    iri_kind()
-        .or(literal_kind())
-        .or(bnode_kind())
-        .or(nonliteral_kind())
-}
+    fn shape_label(node: &Object) -> Result<ShapeExprLabel> {
+        match node {
+            Object::Iri(iri) => Ok(ShapeExprLabel::iri(iri.clone())),
+            Object::BlankNode(bnode) => Ok(ShapeExprLabel::bnode(BNode::new(bnode.as_str()))),
+            Object::Literal(lit) => Err(ShExRError::ShapeExprLabelLiteral { lit: lit.clone() }),
+            Object::Triple { .. } => Err(ShExRError::UnexpectedNode {
+                node: node.to_string(),
+                expected: "a shape expression label".to_string(),
+            }),
+        }
+    }
 
-fn iri_kind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
-where
-    RDF: FocusRDF,
-{
-    is_iri(ShExRVocab::sx_iri()).map(|_| NodeKind::Iri)
-}
+    fn triple_expr_label(node: &Object) -> Result<TripleExprLabel> {
+        match node {
+            Object::Iri(iri) => Ok(TripleExprLabel::IriRef {
+                value: IriRef::Iri(iri.clone()),
+            }),
+            Object::BlankNode(bnode) => Ok(TripleExprLabel::BNode {
+                value: BNode::new(bnode.as_str()),
+            }),
+            Object::Literal(_) | Object::Triple { .. } => Err(ShExRError::UnexpectedNode {
+                node: node.to_string(),
+                expected: "a triple expression".to_string(),
+            }),
+        }
+    }
 
-fn literal_kind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
-where
-    RDF: FocusRDF,
-{
-    is_iri(ShExRVocab::sx_literal()).map(|_| NodeKind::Literal)
-}
+    fn iri(node: &Object) -> Result<IriS> {
+        match node {
+            Object::Iri(iri) => Ok(iri.clone()),
+            _ => Err(ShExRError::UnexpectedNode {
+                node: node.to_string(),
+                expected: "an IRI".to_string(),
+            }),
+        }
+    }
 
-fn bnode_kind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
-where
-    RDF: FocusRDF,
-{
-    is_iri(ShExRVocab::sx_bnode()).map(|_| NodeKind::BNode)
-}
+    /// IRI stems are represented as `xsd:anyURI` literals, but IRIs are also accepted
+    fn stem_iri(node: &Object) -> Result<IriS> {
+        match node {
+            Object::Iri(iri) => Ok(iri.clone()),
+            _ => Ok(IriS::new_unchecked(&Self::string(node)?)),
+        }
+    }
 
-fn nonliteral_kind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
-where
-    RDF: FocusRDF,
-{
-    is_iri(ShExRVocab::sx_nonliteral()).map(|_| NodeKind::NonLiteral)
-}
+    fn string(node: &Object) -> Result<String> {
+        match node {
+            Object::Literal(lit) => Ok(lit.lexical_form()),
+            _ => Err(ShExRError::UnexpectedNode {
+                node: node.to_string(),
+                expected: "a literal".to_string(),
+            }),
+        }
+    }
 
-fn parse_value_set<RDF>() -> impl RDFNodeParse<RDF, Output = Option<Vec<ValueSetValue>>>
-where
-    RDF: FocusRDF,
-{
-    optional(property_value(&sx_values()).then(|ref node| {
-        set_focus(node)
-            .and(parse_rdf_list::<RDF, _>(parse_value()))
-            .map(|(_, vs)| vs)
-    }))
+    /// Literals with boolean or numeric datatypes are converted to the
+    /// corresponding literal values, as done when parsing ShExJ
+    fn literal(node: &Object) -> Result<Literal> {
+        match node {
+            Object::Literal(Literal::DatatypeLiteral {
+                lexical_form,
+                datatype: IriRef::Iri(datatype),
+            }) => {
+                if datatype.as_str() == XSD_BOOLEAN_STR {
+                    match lexical_form.as_str() {
+                        "true" | "1" => return Ok(Literal::boolean(true)),
+                        "false" | "0" => return Ok(Literal::boolean(false)),
+                        _ => {}
+                    }
+                }
+                match NumericLiteral::parse(lexical_form, datatype.as_str()) {
+                    Some(n) => Ok(Literal::NumericLiteral(n)),
+                    None => Ok(Literal::datatype(
+                        lexical_form,
+                        &IriRef::Iri(datatype.clone()),
+                    )),
+                }
+            }
+            Object::Literal(lit) => Ok(lit.clone()),
+            _ => Err(ShExRError::UnexpectedNode {
+                node: node.to_string(),
+                expected: "a literal".to_string(),
+            }),
+        }
+    }
 }
 
-fn parse_value<RDF>() -> impl RDFNodeParse<RDF, Output = ValueSetValue>
-where
-    RDF: FocusRDF,
-{
-    //firstOf(objectValue, )
-    object_value().map(ValueSetValue::ObjectValue)
+fn node_kind(iri: &IriS) -> Result<NodeKind> {
+    match iri.as_str() {
+        SX_IRI => Ok(NodeKind::Iri),
+        SX_BNODE => Ok(NodeKind::BNode),
+        SX_LITERAL => Ok(NodeKind::Literal),
+        SX_NONLITERAL => Ok(NodeKind::NonLiteral),
+        _ => Err(ShExRError::UnexpectedNodeKind { iri: iri.clone() }),
+    }
 }
 
-fn object_value<RDF>() -> impl RDFNodeParse<RDF, Output = ObjectValue>
-where
-    RDF: FocusRDF,
-{
-    iri().map(|ref iri| ObjectValue::IriRef(IriRef::Iri(iri.clone())))
+fn non_empty<A>(values: Vec<A>) -> Option<Vec<A>> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values)
+    }
 }
@@ -0,0 +1,765 @@
+use super::*;
+use crate::{
+    Annotation, IriExclusion, LangOrWildcard, LanguageExclusion, LiteralExclusion, NodeConstraint,
+    NodeKind, NumericFacet, ObjectValue, Schema, SemAct, Shape, ShapeDecl, ShapeExpr,
+    ShapeExprLabel, StringFacet, TripleExpr, TripleExprLabel, ValueSetValue, XsFacet,
+};
+use iri_s::IriS;
+use prefixmap::IriRef;
+use srdf::literal::Literal;
+use srdf::{
+    Object, RDFFormat, SRDFBuilder, RDF, RDF_FIRST_STR, RDF_NIL_STR, RDF_REST_STR, RDF_TYPE_STR,
+    XSD,
+};
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::io::Write;
+
+use super::shexr_error::ShExRWriterError;
+
+use crate::ast::iri_ref_or_wildcard::IriRefOrWildcard;
+use crate::ast::string_or_wildcard::StringOrWildcard;
+
+const XSD_ANY_URI: &str = "http://www.w3.org/2001/XMLSchema#anyURI";
+
+/// Writes a ShEx schema as an RDF graph following the ShExR vocabulary
+pub struct ShExRWriter<RDF>
+where
+    RDF: SRDFBuilder,
+{
+    rdf: RDF,
+    bnode_counter: usize,
+    bnode_labels: HashSet<String>,
+}
+
+impl<RDF> ShExRWriter<RDF>
+where
+    RDF: SRDFBuilder,
+{
+    pub fn new() -> Self {
+        Self {
+            rdf: RDF::empty(),
+            bnode_counter: 0,
+            bnode_labels: HashSet::new(),
+        }
+    }
+
+    pub fn write(&mut self, schema: &Schema) -> Result<(), ShExRWriterError> {
+        let mut prefix_map = schema.prefixmap().unwrap_or_default();
+        let _ = prefix_map.insert("rdf", &IriS::new_unchecked(RDF));
+        let _ = prefix_map.insert("xsd", &IriS::new_unchecked(XSD));
+        let _ = prefix_map.insert("sx", &IriS::new_unchecked(SX));
+        self.rdf.add_prefix_map(prefix_map).map_err(rdf_error)?;
+        self.rdf.add_base(&schema.base()).map_err(rdf_error)?;
+
+        // Blank nodes created by the writer must not clash with the labels of the schema
+        self.bnode_labels = schema
+            .shapes()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|decl| match &decl.id {
+                ShapeExprLabel::BNode { value } => Some(value.value().to_string()),
+                _ => None,
+            })
+            .collect();
+
+        let node = self.bnode();
+        self.add_type(&node, SX_SCHEMA)?;
+        let imports = schema
+            .imports()
+            .iter()
+            .map(|import| Object::iri(IriS::new_unchecked(&import.to_string())))
+            .collect();
+        self.add_list_if_some(&node, SX_IMPORTS, non_empty(imports))?;
+        if let Some(start_acts) = schema.start_actions() {
+            self.add_sem_acts(&node, SX_STARTACTS, &start_acts)?;
+        }
+        if let Some(start) = schema.start() {
+            let start = self.shape_expr(schema, &start)?;
+            self.add(&node, SX_START, &start)?;
+        }
+        if let Some(shapes) = schema.shapes() {
+            let mut decls = Vec::new();
+            for decl in shapes.iter() {
+                decls.push(self.shape_decl(schema, decl)?);
+            }
+            let list = self.list(decls)?;
+            self.add(&node, SX_SHAPES, &list)?;
+        }
+        Ok(())
+    }
+
+    pub fn serialize<W: Write>(
+        &self,
+        format: &RDFFormat,
+        writer: &mut W,
+    ) -> Result<(), ShExRWriterError> {
+        self.rdf.serialize(format, writer).map_err(rdf_error)
+    }
+
+    fn shape_decl(
+        &mut self,
+        schema: &Schema,
+        decl: &ShapeDecl,
+    ) -> Result<Object, ShExRWriterError> {
+        let node = shape_label(schema, &decl.id);
+        self.add_type(&node, SX_SHAPE_DECL)?;
+        if decl.is_abstract {
+            self.add(&node, SX_ABSTRACT, &Object::Literal(Literal::boolean(true)))?;
+        }
+        let se = self.shape_expr(schema, &decl.shape_expr)?;
+        self.add(&node, SX_SHAPE_EXPR, &se)?;
+        Ok(node)
+    }
+
+    fn shape_expr(&mut self, schema: &Schema, se: &ShapeExpr) -> Result<Object, ShExRWriterError> {
+        match se {
+            ShapeExpr::Ref(label) => Ok(shape_label(schema, label)),
+            ShapeExpr::ShapeOr { shape_exprs } | ShapeExpr::ShapeAnd { shape_exprs } => {
+                let node = self.bnode();
+                let type_ = match se {
+                    ShapeExpr::ShapeOr { .. } => SX_SHAPE_OR,
+                    _ => SX_SHAPE_AND,
+                };
+                self.add_type(&node, type_)?;
+                let mut ses = Vec::new();
+                for se in shape_exprs {
+                    ses.push(self.shape_expr(schema, &se.se)?);
+                }
+                let list = self.list(ses)?;
+                self.add(&node, SX_SHAPE_EXPRS, &list)?;
+                Ok(node)
+            }
+            ShapeExpr::ShapeNot { shape_expr } => {
+                let node = self.bnode();
+                self.add_type(&node, SX_SHAPE_NOT)?;
+                let se = self.shape_expr(schema, &shape_expr.se)?;
+                self.add(&node, SX_SHAPE_EXPR, &se)?;
+                Ok(node)
+            }
+            ShapeExpr::NodeConstraint(nc) => self.node_constraint(schema, nc),
+            ShapeExpr::Shape(shape) => self.shape(schema, shape),
+            ShapeExpr::External => {
+                let node = self.bnode();
+                self.add_type(&node, SX_SHAPE_EXTERNAL)?;
+                Ok(node)
+            }
+        }
+    }
+
+    fn shape(&mut self, schema: &Schema, shape: &Shape) -> Result<Object, ShExRWriterError> {
+        let node = self.bnode();
+        self.add_type(&node, SX_SHAPE)?;
+        if let Some(closed) = shape.closed {
+            self.add(&node, SX_CLOSED, &Object::Literal(Literal::boolean(closed)))?;
+        }
+        for extra in shape.extra.iter().flatten() {
+            self.add(&node, SX_EXTRA, &iri_ref(schema, extra))?;
+        }
+        for extends in shape.extends.iter().flatten() {
+            self.add(&node, SX_EXTENDS, &shape_label(schema, extends))?;
+        }
+        if let Some(te) = shape.triple_expr() {
+            let te = self.triple_expr(schema, &te)?;
+            self.add(&node, SX_EXPRESSION, &te)?;
+        }
+        if let Some(sem_acts) = &shape.sem_acts {
+            self.add_sem_acts(&node, SX_SEMACTS, sem_acts)?;
+        }
+        if let Some(annotations) = &shape.annotations {
+            self.add_annotations(schema, &node, annotations)?;
+        }
+        Ok(node)
+    }
+
+    fn triple_expr(
+        &mut self,
+        schema: &Schema,
+        te: &TripleExpr,
+    ) -> Result<Object, ShExRWriterError> {
+        let (id, min, max, sem_acts, annotations) = match te {
+            TripleExpr::TripleExprRef(label) => return Ok(triple_expr_label(schema, label)),
+            TripleExpr::EachOf {
+                id,
+                min,
+                max,
+                sem_acts,
+                annotations,
+                ..
+            }
+            | TripleExpr::OneOf {
+                id,
+                min,
+                max,
+                sem_acts,
+                annotations,
+                ..
+            }
+            | TripleExpr::TripleConstraint {
+                id,
+                min,
+                max,
+                sem_acts,
+                annotations,
+                ..
+            } => (id, min, max, sem_acts, annotations),
+        };
+        let node = match id {
+            Some(id) => triple_expr_label(schema, id),
+            None => self.bnode(),
+        };
+        match te {
+            TripleExpr::EachOf { expressions, .. } | TripleExpr::OneOf { expressions, .. } => {
+                let type_ = match te {
+                    TripleExpr::EachOf { .. } => SX_EACHOF,
+                    _ => SX_ONEOF,
+                };
+                self.add_type(&node, type_)?;
+                let mut tes = Vec::new();
+                for te in expressions {
+                    tes.push(self.triple_expr(schema, &te.te)?);
+                }
+                let list = self.list(tes)?;
+                self.add(&node, SX_EXPRESSIONS, &list)?;
+            }
+            TripleExpr::TripleConstraint {
+                negated,
+                inverse,
+                predicate,
+                value_expr,
+                ..
+            } => {
+                self.add_type(&node, SX_TRIPLECONSTRAINT)?;
+                if let Some(inverse) = inverse {
+                    self.add(
+                        &node,
+                        SX_INVERSE,
+                        &Object::Literal(Literal::boolean(*inverse)),
+                    )?;
+                }
+                if let Some(negated) = negated {
+                    self.add(
+                        &node,
+                        SX_NEGATED,
+                        &Object::Literal(Literal::boolean(*negated)),
+                    )?;
+                }
+                self.add(&node, SX_PREDICATE, &iri_ref(schema, predicate))?;
+                if let Some(se) = value_expr {
+                    let se = self.shape_expr(schema, se)?;
+                    self.add(&node, SX_VALUEEXPR, &se)?;
+                }
+            }
+            TripleExpr::TripleExprRef(_) => {}
+        }
+        if let Some(min) = min {
+            self.add(&node, SX_MIN, &integer(*min as isize))?;
+        }
+        if let Some(max) = max {
+            self.add(&node, SX_MAX, &integer(*max as isize))?;
+        }
+        if let Some(sem_acts) = sem_acts {
+            self.add_sem_acts(&node, SX_SEMACTS, sem_acts)?;
+        }
+        if let Some(annotations) = annotations {
+            self.add_annotations(schema, &node, annotations)?;
+        }
+        Ok(node)
+    }
+
+    fn node_constraint(
+        &mut self,
+        schema: &Schema,
+        nc: &NodeConstraint,
+    ) -> Result<Object, ShExRWriterError> {
+        let node = self.bnode();
+        self.add_type(&node, SX_NODECONSTRAINT)?;
+        if let Some(nk) = nc.node_kind() {
+            let nk = match nk {
+                NodeKind::Iri => SX_IRI,
+                NodeKind::BNode => SX_BNODE,
+                NodeKind::NonLiteral => SX_NONLITERAL,
+                NodeKind::Literal => SX_LITERAL,
+            };
+            self.add(&node, SX_NODEKIND, &Object::iri(IriS::new_unchecked(nk)))?;
+        }
+        if let Some(dt) = nc.datatype() {
+            self.add(&node, SX_DATATYPE, &iri_ref(schema, &dt))?;
+        }
+        for facet in nc.xs_facet().unwrap_or_default() {
+            match facet {
+                XsFacet::StringFacet(StringFacet::Length(n)) => {
+                    self.add(&node, SX_LENGTH, &integer(n as isize))?
+                }
+                XsFacet::StringFacet(StringFacet::MinLength(n)) => {
+                    self.add(&node, SX_MINLENGTH, &integer(n as isize))?
+                }
+                XsFacet::StringFacet(StringFacet::MaxLength(n)) => {
+                    self.add(&node, SX_MAXLENGTH, &integer(n as isize))?
+                }
+                XsFacet::StringFacet(StringFacet::Pattern(pattern)) => {
+                    self.add(&node, SX_PATTERN, &string(&pattern.str))?;
+                    if let Some(flags) = &pattern.flags {
+                        self.add(&node, SX_FLAGS, &string(flags))?;
+                    }
+                }
+                XsFacet::NumericFacet(facet) => {
+                    let (pred, value) = match facet {
+                        NumericFacet::MinInclusive(n) => {
+                            (SX_MININCLUSIVE, Literal::NumericLiteral(n))
+                        }
+                        NumericFacet::MinExclusive(n) => {
+                            (SX_MINEXCLUSIVE, Literal::NumericLiteral(n))
+                        }
+                        NumericFacet::MaxInclusive(n) => {
+                            (SX_MAXINCLUSIVE, Literal::NumericLiteral(n))
+                        }
+                        NumericFacet::MaxExclusive(n) => {
+                            (SX_MAXEXCLUSIVE, Literal::NumericLiteral(n))
+                        }
+                        NumericFacet::TotalDigits(n) => {
+                            (SX_TOTALDIGITS, Literal::integer(n as isize))
+                        }
+                        NumericFacet::FractionDigits(n) => {
+                            (SX_FRACTIONDIGITS, Literal::integer(n as isize))
+                        }
+                    };
+                    self.add(&node, pred, &Object::Literal(value))?
+                }
+            }
+        }
+        if let Some(values) = nc.values() {
+            let mut vs = Vec::new();
+            for value in values.iter() {
+                vs.push(self.value_set_value(schema, value)?);
+            }
+            let list = self.list(vs)?;
+            self.add(&node, SX_VALUES, &list)?;
+        }
+        Ok(node)
+    }
+
+    fn value_set_value(
+        &mut self,
+        schema: &Schema,
+        value: &ValueSetValue,
+    ) -> Result<Object, ShExRWriterError> {
+        match value {
+            ValueSetValue::ObjectValue(ov) => Ok(object_value(schema, ov)),
+            ValueSetValue::Language { language_tag } => {
+                let node = self.typed_bnode(SX_LANGUAGE)?;
+                self.add(&node, SX_LANGUAGE_TAG, &string(&language_tag.value()))?;
+                Ok(node)
+            }
+            ValueSetValue::IriStem { stem } => self.stem(SX_IRISTEM, &iri_stem(schema, stem)),
+            ValueSetValue::LiteralStem { stem } => self.stem(SX_LITERALSTEM, &string(stem)),
+            ValueSetValue::LanguageStem { stem } => {
+                self.stem(SX_LANGUAGESTEM, &string(&stem.value()))
+            }
+            ValueSetValue::IriStemRange { stem, exclusions } => {
+                let stem = match stem {
+                    IriRefOrWildcard::IriRef(iri) => iri_stem(schema, iri),
+                    IriRefOrWildcard::Wildcard => self.typed_bnode(SX_WILDCARD)?,
+                };
+                let node = self.stem(SX_IRISTEMRANGE, &stem)?;
+                let mut excs = Vec::new();
+                for exc in exclusions.iter().flatten() {
+                    excs.push(match exc {
+                        IriExclusion::Iri(iri) => iri_ref(schema, iri),
+                        IriExclusion::IriStem(iri) => {
+                            self.stem(SX_IRISTEM, &iri_stem(schema, iri))?
+                        }
+                    });
+                }
+                let excs = exclusions.as_ref().map(|_| excs);
+                self.add_list_if_some(&node, SX_EXCLUSION, excs)?;
+                Ok(node)
+            }
+            ValueSetValue::LiteralStemRange { stem, exclusions } => {
+                let stem = match stem {
+                    StringOrWildcard::String(str) => string(str),
+                    StringOrWildcard::Wildcard => self.typed_bnode(SX_WILDCARD)?,
+                };
+                let node = self.stem(SX_LITERALSTEMRANGE, &stem)?;
+                let mut excs = Vec::new();
+                for exc in exclusions.iter().flatten() {
+                    excs.push(match exc {
+                        LiteralExclusion::Literal(str) => string(str),
+                        LiteralExclusion::LiteralStem(str) => {
+                            self.stem(SX_LITERALSTEM, &string(str))?
+                        }
+                    });
+                }
+                let excs = exclusions.as_ref().map(|_| excs);
+                self.add_list_if_some(&node, SX_EXCLUSION, excs)?;
+                Ok(node)
+            }
+            ValueSetValue::LanguageStemRange { stem, exclusions } => {
+                let stem = match stem {
+                    LangOrWildcard::Lang(lang) => string(&lang.value()),
+                    LangOrWildcard::Wildcard => self.typed_bnode(SX_WILDCARD)?,
+                };
+                let node = self.stem(SX_LANGUAGESTEMRANGE, &stem)?;
+                let mut excs = Vec::new();
+                for exc in exclusions.iter().flatten() {
+                    excs.push(match exc {
+                        LanguageExclusion::Language(lang) => string(&lang.value()),
+                        LanguageExclusion::LanguageStem(lang) => {
+                            self.stem(SX_LANGUAGESTEM, &string(&lang.value()))?
+                        }
+                    });
+                }
+                let excs = exclusions.as_ref().map(|_| excs);
+                self.add_list_if_some(&node, SX_EXCLUSION, excs)?;
+                Ok(node)
+            }
+        }
+    }
+
+    fn stem(&mut self, type_: &str, stem: &Object) -> Result<Object, ShExRWriterError> {
+        let node = self.typed_bnode(type_)?;
+        self.add(&node, SX_STEM, stem)?;
+        Ok(node)
+    }
+
+    fn add_sem_acts(
+        &mut self,
+        node: &Object,
+        pred: &str,
+        sem_acts: &[SemAct],
+    ) -> Result<(), ShExRWriterError> {
+        let mut nodes = Vec::new();
+        for sem_act in sem_acts {
+            let sem_act_node = self.typed_bnode(SX_SEMACT)?;
+            self.add(
+                &sem_act_node,
+                SX_NAME,
+                &Object::iri(IriS::new_unchecked(&sem_act.name().to_string())),
+            )?;
+            if let Some(code) = sem_act.code() {
+                self.add(&sem_act_node, SX_CODE, &string(&code))?;
+            }
+            nodes.push(sem_act_node);
+        }
+        let list = self.list(nodes)?;
+        self.add(node, pred, &list)
+    }
+
+    fn add_annotations(
+        &mut self,
+        schema: &Schema,
+        node: &Object,
+        annotations: &[Annotation],
+    ) -> Result<(), ShExRWriterError> {
+        let mut nodes = Vec::new();
+        for annotation in annotations {
+            let annotation_node = self.typed_bnode(SX_ANNOTATION)?;
+            self.add(
+                &annotation_node,
+                SX_PREDICATE,
+                &iri_ref(schema, &annotation.predicate()),
+            )?;
+            self.add(
+                &annotation_node,
+                SX_OBJECT,
+                &object_value(schema, &annotation.object()),
+            )?;
+            nodes.push(annotation_node);
+        }
+        let list = self.list(nodes)?;
+        self.add(node, SX_ANNOTATION_PROP, &list)
+    }
+
+    fn add_list_if_some(
+        &mut self,
+        node: &Object,
+        pred: &str,
+        values: Option<Vec<Object>>,
+    ) -> Result<(), ShExRWriterError> {
+        if let Some(values) = values {
+            let list = self.list(values)?;
+            self.add(node, pred, &list)?;
+        }
+        Ok(())
+    }
+
+    /// Adds an RDF list with the values and returns its head
+    fn list(&mut self, values: Vec<Object>) -> Result<Object, ShExRWriterError> {
+        let mut head = Object::iri(IriS::new_unchecked(RDF_NIL_STR));
+        for value in values.into_iter().rev() {
+            let node = self.bnode();
+            self.add(&node, RDF_FIRST_STR, &value)?;
+            self.add(&node, RDF_REST_STR, &head)?;
+            head = node;
+        }
+        Ok(head)
+    }
+
+    fn typed_bnode(&mut self, type_: &str) -> Result<Object, ShExRWriterError> {
+        let node = self.bnode();
+        self.add_type(&node, type_)?;
+        Ok(node)
+    }
+
+    fn bnode(&mut self) -> Object {
+        loop {
+            let label = format!("b{}", self.bnode_counter);
+            self.bnode_counter += 1;
+            if !self.bnode_labels.contains(&label) {
+                return Object::BlankNode(label);
+            }
+        }
+    }
+
+    fn add_type(&mut self, node: &Object, type_: &str) -> Result<(), ShExRWriterError> {
+        self.add(node, RDF_TYPE_STR, &Object::iri(IriS::new_unchecked(type_)))
+    }
+
+    fn add(&mut self, subj: &Object, pred: &str, obj: &Object) -> Result<(), ShExRWriterError> {
+        let subj = RDF::object_as_subject(subj).ok_or_else(|| ShExRWriterError::NoSubject {
+            node: subj.to_string(),
+        })?;
+        let obj = RDF::object_as_term(obj).map_err(rdf_error)?;
+        self.rdf
+            .add_triple(&subj, &RDF::iri_s2iri(&IriS::new_unchecked(pred)), &obj)
+            .map_err(rdf_error)
+    }
+}
+
+impl<RDF> Default for ShExRWriter<RDF>
+where
+    RDF: SRDFBuilder,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn rdf_error<E: Display>(err: E) -> ShExRWriterError {
+    ShExRWriterError::SRDFError {
+        err: err.to_string(),
+    }
+}
+
+fn shape_label(schema: &Schema, label: &ShapeExprLabel) -> Object {
+    match label {
+        ShapeExprLabel::IriRef { value } => iri_ref(schema, value),
+        ShapeExprLabel::BNode { value } => Object::BlankNode(value.value().to_string()),
+        ShapeExprLabel::Start => Object::iri(IriS::new_unchecked(SX_START)),
+    }
+}
+
+fn triple_expr_label(schema: &Schema, label: &TripleExprLabel) -> Object {
+    match label {
+        TripleExprLabel::IriRef { value } => iri_ref(schema, value),
+        TripleExprLabel::BNode { value } => Object::BlankNode(value.value().to_string()),
+    }
+}
+
+fn iri_ref(schema: &Schema, iri: &IriRef) -> Object {
+    Object::iri(schema.resolve_iriref(iri))
+}
+
+fn iri_stem(schema: &Schema, iri: &IriRef) -> Object {
+    Object::Literal(Literal::datatype(
+        schema.resolve_iriref(iri).as_str(),
+        &IriRef::Iri(IriS::new_unchecked(XSD_ANY_URI)),
+    ))
+}
+
+fn object_value(schema: &Schema, value: &ObjectValue) -> Object {
+    match value {
+        ObjectValue::IriRef(iri) => iri_ref(schema, iri),
+        ObjectValue::Literal(lit) => Object::Literal(lit.clone()),
+    }
+}
+
+fn string(str: &str) -> Object {
+    Object::Literal(Literal::str(str))
+}
+
+fn integer(n: isize) -> Object {
+    Object::Literal(Literal::integer(n))
+}
+
+fn non_empty(values: Vec<Object>) -> Option<Vec<Object>> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shexr::shexr_parser::ShExRParser;
+    use srdf::{ReaderMode, SRDFGraph};
+
+    fn round_trip(shexj: &str) {
+        let schema: Schema = serde_json::from_str(shexj).unwrap();
+        let mut writer = ShExRWriter::<SRDFGraph>::new();
+        writer.write(&schema).unwrap();
+        let mut buf = Vec::new();
+        writer.serialize(&RDFFormat::Turtle, &mut buf).unwrap();
+        let turtle = String::from_utf8(buf).unwrap();
+        let rdf =
+            SRDFGraph::from_str(&turtle, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let mut parsed = ShExRParser::new(rdf).parse().unwrap().with_prefixmap(None);
+        parsed.with_source_iri(&schema.source_iri());
+        assert_eq!(parsed, schema, "ShExR:\n{turtle}");
+    }
+
+    #[test]
+    fn test_round_trip_shape() {
+        round_trip(
+            r#"{
+  "@context": "http://www.w3.org/ns/shex.jsonld",
+  "type": "Schema",
+  "start": "http://example.org/S",
+  "shapes": [
+    {
+      "type": "ShapeDecl",
+      "id": "http://example.org/S",
+      "shapeExpr": {
+        "type": "Shape",
+        "closed": true,
+        "extra": [ "http://example.org/p" ],
+        "expression": {
+          "type": "EachOf",
+          "expressions": [
+            {
+              "type": "TripleConstraint",
+              "predicate": "http://example.org/p",
+              "valueExpr": {
+                "type": "NodeConstraint",
+                "datatype": "http://www.w3.org/2001/XMLSchema#string",
+                "minlength": 2,
+                "pattern": "^a",
+                "flags": "i"
+              },
+              "min": 0,
+              "max": -1
+            },
+            {
+              "type": "TripleConstraint",
+              "inverse": true,
+              "predicate": "http://example.org/q",
+              "valueExpr": "http://example.org/T"
+            }
+          ]
+        },
+        "semActs": [ { "type": "SemAct", "name": "http://example.org/ext", "code": " print(s) " } ],
+        "annotations": [
+          {
+            "type": "Annotation",
+            "predicate": "http://www.w3.org/2000/01/rdf-schema#label",
+            "object": { "value": "S shape" }
+          }
+        ]
+      }
+    },
+    {
+      "type": "ShapeDecl",
+      "id": "http://example.org/T",
+      "abstract": true,
+      "shapeExpr": {
+        "type": "ShapeOr",
+        "shapeExprs": [
+          { "type": "NodeConstraint", "nodeKind": "iri" },
+          {
+            "type": "ShapeNot",
+            "shapeExpr": { "type": "NodeConstraint", "nodeKind": "literal" }
+          }
+        ]
+      }
+    }
+  ]
+}"#,
+        )
+    }
+
+    #[test]
+    fn test_round_trip_value_set() {
+        round_trip(
+            r#"{
+  "@context": "http://www.w3.org/ns/shex.jsonld",
+  "type": "Schema",
+  "startActs": [ { "type": "SemAct", "name": "http://example.org/ext" } ],
+  "shapes": [
+    {
+      "type": "ShapeDecl",
+      "id": "http://example.org/S",
+      "shapeExpr": {
+        "type": "NodeConstraint",
+        "values": [
+          "http://example.org/a",
+          { "value": "hi", "language": "en" },
+          { "value": "ab" },
+          { "type": "Language", "languageTag": "fr" },
+          { "type": "IriStem", "stem": "http://example.org/s" },
+          { "type": "LiteralStem", "stem": "x" },
+          {
+            "type": "IriStemRange",
+            "stem": { "type": "Wildcard" },
+            "exclusions": [
+              "http://example.org/b",
+              { "type": "IriStem", "stem": "http://example.org/c" }
+            ]
+          },
+          {
+            "type": "LanguageStemRange",
+            "stem": "es",
+            "exclusions": [ "es-ES" ]
+          }
+        ]
+      }
+    }
+  ]
+}"#,
+        )
+    }
+
+    #[test]
+    fn test_round_trip_imports_extends_refs() {
+        round_trip(
+            r#"{
+  "@context": "http://www.w3.org/ns/shex.jsonld",
+  "type": "Schema",
+  "imports": [ "http://example.org/other" ],
+  "shapes": [
+    {
+      "type": "ShapeDecl",
+      "id": "http://example.org/S",
+      "shapeExpr": {
+        "type": "Shape",
+        "extends": [ "http://example.org/T" ],
+        "expression": {
+          "type": "OneOf",
+          "expressions": [
+            {
+              "type": "TripleConstraint",
+              "id": "http://example.org/tc",
+              "predicate": "http://example.org/p",
+              "valueExpr": {
+                "type": "NodeConstraint",
+                "datatype": "http://www.w3.org/2001/XMLSchema#integer",
+                "mininclusive": 1,
+                "maxexclusive": 10
+              }
+            },
+            "http://example.org/tc"
+          ]
+        }
+      }
+    },
+    {
+      "type": "ShapeDecl",
+      "id": "http://example.org/T",
+      "shapeExpr": { "type": "ShapeExternal" }
+    }
+  ]
+}"#,
+        )
+    }
+}
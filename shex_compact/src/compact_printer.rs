@@ -53,7 +53,7 @@ fn pp_numeric_literal<'a, A>(
     }
 }
 
-fn pp_iri_ref<'a, A>(
+pub(crate) fn pp_iri_ref<'a, A>(
     value: &IriRef,
     doc: &'a Arena<'a, A>,
     prefixmap: &PrefixMap,
@@ -1,8 +1,8 @@
-use crate::{keyword, pp_label, pp_object_value};
+use crate::{keyword, pp_iri_ref, pp_label, pp_object_value};
 use colored::*;
 use prefixmap::PrefixMap;
 use pretty::{Arena, DocAllocator, DocBuilder};
-use shapemap::{query_shape_map::QueryShapeMap, Association, NodeSelector, ShapeSelector};
+use shapemap::{query_shape_map::QueryShapeMap, Association, NodeSelector, Pattern, ShapeSelector};
 use std::marker::PhantomData;
 
 /// Struct that can be used to pretty print Shapemaps
@@ -177,10 +177,52 @@ where
     fn pp_node_selector(&self, ns: &NodeSelector) -> DocBuilder<'a, Arena<'a, A>, A> {
         match ns {
             NodeSelector::Node(v) => pp_object_value(v, self.doc, &self.nodes_prefixmap),
-            NodeSelector::TriplePattern { .. } => todo!(),
-            NodeSelector::TriplePatternPath { .. } => todo!(),
-            NodeSelector::Sparql { .. } => todo!(),
-            NodeSelector::Generic { .. } => todo!(),
+            NodeSelector::TriplePattern {
+                subject,
+                pred,
+                object,
+            } => self.pp_triple_pattern(
+                subject,
+                pp_iri_ref(pred, self.doc, &self.nodes_prefixmap),
+                object,
+            ),
+            NodeSelector::TriplePatternPath {
+                subject,
+                pred,
+                object,
+            } => self.pp_triple_pattern(subject, self.doc.text(pred.to_string()), object),
+            NodeSelector::Sparql { query } => keyword("SPARQL", self.doc, self.keyword_color)
+                .append(self.doc.space())
+                .append(self.doc.text(quote_string(query))),
+            NodeSelector::Generic { iri, param } => self
+                .doc
+                .text(iri.to_string())
+                .append(self.doc.space())
+                .append(self.doc.text(quote_string(param))),
+        }
+    }
+
+    fn pp_triple_pattern(
+        &self,
+        subject: &Pattern,
+        pred: DocBuilder<'a, Arena<'a, A>, A>,
+        object: &Pattern,
+    ) -> DocBuilder<'a, Arena<'a, A>, A> {
+        self.doc
+            .text("{")
+            .append(self.pp_pattern(subject))
+            .append(self.doc.space())
+            .append(pred)
+            .append(self.doc.space())
+            .append(self.pp_pattern(object))
+            .append(self.doc.text("}"))
+    }
+
+    fn pp_pattern(&self, pattern: &Pattern) -> DocBuilder<'a, Arena<'a, A>, A> {
+        match pattern {
+            Pattern::Node(v) => pp_object_value(v, self.doc, &self.nodes_prefixmap),
+            Pattern::Wildcard => self.doc.text("_"),
+            Pattern::Focus => keyword("FOCUS", self.doc, self.keyword_color),
         }
     }
 
@@ -194,5 +236,16 @@ where
     }
 }
 
+/// Writes a string literal with double quotes, escaping the characters that need it
+fn quote_string(str: &str) -> String {
+    let escaped = str
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("\"{escaped}\"")
+}
+
 #[cfg(test)]
 mod tests {}
@@ -1,7 +1,7 @@
 use crate::{
     grammar::{map_error, tag_no_case_tws, token_tws, traced, tws0},
     iri, literal,
-    shex_grammar::shape_expr_label,
+    shex_grammar::{iri_ref, predicate, shape_expr_label, string},
     IRes, ParseError, Span,
 };
 use nom::{
@@ -9,9 +9,10 @@ use nom::{
     character::complete::char,
     combinator::{all_consuming, map, opt},
     multi::many0,
-    sequence::tuple,
+    sequence::{delimited, tuple},
 };
-use shapemap::{NodeSelector, ShapeSelector};
+use shapemap::{NodeSelector, Pattern, ShapeSelector};
+use shex_ast::object_value::ObjectValue;
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum ShapeMapStatement {
@@ -79,10 +80,86 @@ pub(crate) fn shape_spec<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, ShapeSelector
 pub(crate) fn node_selector<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, NodeSelector> {
     traced(
         "node_selector",
-        map_error(move |i| object_term(i), || ParseError::ExpectedNodeSpec),
+        map_error(
+            move |i| alt((triple_pattern, extended, object_term))(i),
+            || ParseError::ExpectedNodeSpec,
+        ),
     )
 }
 
+/// triplePattern    : '{' FOCUS predicate (objectTerm | '_') '}'
+///                  | '{' (subjectTerm | '_') predicate FOCUS '}' ;
+fn triple_pattern(i: Span) -> IRes<NodeSelector> {
+    delimited(
+        token_tws("{"),
+        alt((focus_subject_pattern, focus_object_pattern)),
+        token_tws("}"),
+    )(i)
+}
+
+fn focus_subject_pattern(i: Span) -> IRes<NodeSelector> {
+    let (i, (_, pred, _, object)) = tuple((
+        tag_no_case_tws("FOCUS"),
+        predicate,
+        tws0,
+        alt((map(object_value, Pattern::Node), wildcard)),
+    ))(i)?;
+    Ok((
+        i,
+        NodeSelector::TriplePattern {
+            subject: Pattern::Focus,
+            pred,
+            object,
+        },
+    ))
+}
+
+fn focus_object_pattern(i: Span) -> IRes<NodeSelector> {
+    let (i, (subject, _, pred, _)) = tuple((
+        alt((
+            map(iri, |iri| Pattern::Node(ObjectValue::iri_ref(iri))),
+            wildcard,
+        )),
+        tws0,
+        predicate,
+        tag_no_case_tws("FOCUS"),
+    ))(i)?;
+    Ok((
+        i,
+        NodeSelector::TriplePattern {
+            subject,
+            pred,
+            object: Pattern::Focus,
+        },
+    ))
+}
+
+fn wildcard(i: Span) -> IRes<Pattern> {
+    map(token_tws("_"), |_| Pattern::Wildcard)(i)
+}
+
+/// extended         : SPARQL string | nodeIri string ;
+fn extended(i: Span) -> IRes<NodeSelector> {
+    alt((sparql_selector, generic_selector))(i)
+}
+
+fn sparql_selector(i: Span) -> IRes<NodeSelector> {
+    let (i, (_, query)) = tuple((tag_no_case_tws("SPARQL"), string()))(i)?;
+    Ok((i, NodeSelector::Sparql { query }))
+}
+
+fn generic_selector(i: Span) -> IRes<NodeSelector> {
+    let (i, (iri, _, param)) = tuple((iri_ref, tws0, string()))(i)?;
+    Ok((i, NodeSelector::Generic { iri, param }))
+}
+
+fn object_value(i: Span) -> IRes<ObjectValue> {
+    alt((
+        map(iri, ObjectValue::iri_ref),
+        map(literal(), ObjectValue::literal),
+    ))(i)
+}
+
 fn object_term(i: Span) -> IRes<NodeSelector> {
     alt((subject_term, literal_selector))(i)
 }
@@ -99,6 +176,8 @@ fn literal_selector(i: Span) -> IRes<NodeSelector> {
 
 #[cfg(test)]
 mod tests {
+    use iri_s::IriS;
+    use prefixmap::IriRef;
     use shapemap::ShapeSelector;
 
     use super::*;
@@ -114,6 +193,50 @@ mod tests {
         assert_eq!(shape_map, expected);
     }
 
+    #[test]
+    fn triple_pattern_selectors() {
+        let input = Span::new("{FOCUS a :Person}@:S");
+        let (_, shape_map) = association(input).unwrap();
+        let expected = ShapeMapStatement::Association {
+            node_selector: NodeSelector::TriplePattern {
+                subject: Pattern::Focus,
+                pred: IriRef::iri(IriS::new_unchecked(
+                    "http://www.w3.org/1999/02/22-rdf-syntax-ns#type",
+                )),
+                object: Pattern::Node(ObjectValue::prefixed("", "Person")),
+            },
+            shape_selector: ShapeSelector::prefixed("", "S"),
+        };
+        assert_eq!(shape_map, expected);
+
+        let (_, selector) = node_selector()(Span::new("{ :alice :knows focus }")).unwrap();
+        let expected = NodeSelector::TriplePattern {
+            subject: Pattern::Node(ObjectValue::prefixed("", "alice")),
+            pred: IriRef::prefixed("", "knows"),
+            object: Pattern::Focus,
+        };
+        assert_eq!(selector, expected);
+
+        let (_, selector) = node_selector()(Span::new("{FOCUS :name _}")).unwrap();
+        let expected = NodeSelector::TriplePattern {
+            subject: Pattern::Focus,
+            pred: IriRef::prefixed("", "name"),
+            object: Pattern::Wildcard,
+        };
+        assert_eq!(selector, expected);
+    }
+
+    #[test]
+    fn sparql_selector() {
+        let input = Span::new(r#"SPARQL "select ?x where { ?x a :Person }"@:S"#);
+        let (_, shape_map) = association(input).unwrap();
+        let expected = ShapeMapStatement::Association {
+            node_selector: NodeSelector::sparql("select ?x where { ?x a :Person }"),
+            shape_selector: ShapeSelector::prefixed("", "S"),
+        };
+        assert_eq!(shape_map, expected);
+    }
+
     /*    #[test_log::test]
     fn example_shapemap_failed () {
         let input = Span::new("\n @START \n # Comment \n@STRT\n");
@@ -1536,7 +1536,7 @@ fn datatype_iri(i: Span) -> IRes<IriRef> {
 
 /// `[135s] string ::= STRING_LITERAL1 | STRING_LITERAL_LONG1`
 /// `                  | STRING_LITERAL2 | STRING_LITERAL_LONG2`
-fn string<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, String> {
+pub(crate) fn string<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, String> {
     traced(
         "string",
         map_error(
@@ -1686,7 +1686,7 @@ fn lang_tag(i: Span) -> IRes<Lang> {
 }
 
 /// `[61] predicate ::= iri | RDF_TYPE`
-fn predicate(i: Span) -> IRes<IriRef> {
+pub(crate) fn predicate(i: Span) -> IRes<IriRef> {
     alt((iri, rdf_type))(i)
 }
 
@@ -2186,7 +2186,7 @@ fn is_hex(c: char) -> bool {
 
 /// `[18t] <IRIREF> ::= "<" ([^#0000- <>\"{}|^`\\] | UCHAR)* ">"`
 /// iri_chars = ([^#0000- <>\"{}|^`\\] | UCHAR)*
-fn iri_ref(i: Span) -> IRes<IriS> {
+pub(crate) fn iri_ref(i: Span) -> IRes<IriS> {
     let (i, str) = delimited(
         char('<'),
         // take_while(is_iri_ref),
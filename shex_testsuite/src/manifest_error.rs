@@ -1,4 +1,5 @@
 use iri_s::IriSError;
+use shex_ast::shexr::shexr_error::{ShExRError, ShExRWriterError};
 use shex_ast::{ast::SchemaJsonError, CompiledSchemaError, Schema};
 use shex_compact::ParseError;
 use shex_validation::{ResultValue, ValidatorError};
@@ -29,6 +30,34 @@ pub enum ManifestError {
         entry_name: String,
     },
 
+    #[error("Error parsing Schema as ShExR: error {error}, entry: {entry_name:?}")]
+    ShExRError {
+        error: Box<ShExRError>,
+        entry_name: String,
+    },
+
+    #[error(
+        "Error |ShExRSchemaDifferent| ShEx Schema parsed from ShExR is different from schema parsed from JSON\nSchema JSON Parsed:\n{json_schema_parsed:?}\nSchema parsed from ShExR:\n{shexr_schema_parsed:?}"
+    )]
+    ShExRSchemaDifferent {
+        json_schema_parsed: Schema,
+        shexr_schema_parsed: Schema,
+    },
+
+    #[error("Error writing Schema as ShExR: error {error}, entry: {entry_name:?}")]
+    ShExRWriterError {
+        error: Box<ShExRWriterError>,
+        entry_name: String,
+    },
+
+    #[error(
+        "Error |ShExRRoundTripDifferent| ShEx Schema written as ShExR and parsed back is different from schema parsed from JSON\nSchema JSON Parsed:\n{json_schema_parsed:?}\nSchema parsed back from ShExR:\n{shexr_schema_parsed:?}"
+    )]
+    ShExRRoundTripDifferent {
+        json_schema_parsed: Schema,
+        shexr_schema_parsed: Schema,
+    },
+
     #[error("not found entry: {name:?}")]
     NotFoundEntry { name: String },
 
@@ -62,6 +91,13 @@ pub enum ManifestError {
     #[error("Expected OK but obtained {value} for {entry}")]
     ExpectedOkButObtained { value: ResultValue, entry: String },
 
+    #[error("Values printed by the Test extension in {entry}: {printed:?} are different from the expected ones: {expected:?}")]
+    ExtensionResultsDifferent {
+        entry: String,
+        expected: Vec<String>,
+        printed: Vec<String>,
+    },
+
     #[error("Schema parsed is different to schema serialized after parsing\nSchema parsed from JSON\n{schema_parsed:?}\nSchema serialized after parsing:\n{schema_parsed_after_serialization:?}\nSchema serialized: {schema_serialized}\nSchema serialized after: {schema_serialized_after}")]
     SchemasDifferent {
         schema_parsed: Schema,
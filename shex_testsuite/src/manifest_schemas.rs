@@ -10,7 +10,10 @@ use serde::de::{self};
 use serde::{Deserialize, Deserializer, Serialize};
 // use serde_derive::{Serialize};
 use shex_ast::ast::Schema as SchemaJson;
+use shex_ast::shexr::shexr_parser::ShExRParser;
+use shex_ast::shexr::shexr_writer::ShExRWriter;
 use shex_compact::ShExParser;
+use srdf::{RDFFormat, ReaderMode, SRDFGraph};
 use tracing::debug;
 use url::Url;
 
@@ -209,7 +212,7 @@ impl SchemasEntry {
             shexc_schema_parsed = shexc_schema_parsed.with_base(None).with_prefixmap(None);
             if schema_parsed == shexc_schema_parsed {
                 debug!("Schema JSON parsed == Schema ShExC parsed");
-                Ok(())
+                self.check_shexr(base, &schema_parsed)
             } else {
                 Err(ManifestError::ShExSchemaDifferent {
                     json_schema_parsed: schema_parsed,
@@ -229,6 +232,68 @@ impl SchemasEntry {
     }
 }
 
+impl SchemasEntry {
+    /// Checks that the ShExR representation of the entry is equal to the ShExJ one
+    /// and that the ShExJ schema is read back unchanged after writing it as ShExR
+    fn check_shexr(
+        &self,
+        base: &Path,
+        json_schema_parsed: &SchemaJson,
+    ) -> Result<(), ManifestError> {
+        let parse_shexr = |graph| {
+            let mut shexr_schema_parsed = ShExRParser::new(graph)
+                .parse()?
+                .with_base(None)
+                .with_prefixmap(None);
+            shexr_schema_parsed.with_source_iri(&json_schema_parsed.source_iri());
+            Ok(shexr_schema_parsed)
+        };
+        let shexr_error = |e| ManifestError::ShExRError {
+            entry_name: self.name.to_string(),
+            error: Box::new(e),
+        };
+        let graph =
+            SRDFGraph::parse_data(&self.ttl, &RDFFormat::Turtle, base, &ReaderMode::Strict)?;
+        let shexr_schema_parsed = parse_shexr(graph).map_err(shexr_error)?;
+        if *json_schema_parsed != shexr_schema_parsed {
+            return Err(ManifestError::ShExRSchemaDifferent {
+                json_schema_parsed: json_schema_parsed.clone(),
+                shexr_schema_parsed,
+            });
+        }
+        debug!("Schema JSON parsed == Schema ShExR parsed");
+
+        let writer_error = |e| ManifestError::ShExRWriterError {
+            entry_name: self.name.to_string(),
+            error: Box::new(e),
+        };
+        let mut shexr_writer: ShExRWriter<SRDFGraph> = ShExRWriter::new();
+        shexr_writer
+            .write(json_schema_parsed)
+            .map_err(writer_error)?;
+        let mut shexr = Vec::new();
+        shexr_writer
+            .serialize(&RDFFormat::Turtle, &mut shexr)
+            .map_err(writer_error)?;
+        let graph = SRDFGraph::from_reader(
+            shexr.as_slice(),
+            &RDFFormat::Turtle,
+            None,
+            &ReaderMode::Strict,
+        )?;
+        let shexr_schema_parsed = parse_shexr(graph).map_err(shexr_error)?;
+        if *json_schema_parsed == shexr_schema_parsed {
+            debug!("Schema JSON parsed == Schema written as ShExR and parsed back");
+            Ok(())
+        } else {
+            Err(ManifestError::ShExRRoundTripDifferent {
+                json_schema_parsed: json_schema_parsed.clone(),
+                shexr_schema_parsed,
+            })
+        }
+    }
+}
+
 impl Manifest for ManifestSchemas {
     fn len(&self) -> usize {
         self.entry_names.len()
@@ -268,6 +333,20 @@ mod tests {
         assert_eq!(manifest.len(), 2);
     }
 
+    #[test]
+    fn check_local_shexr_entries() {
+        let base = Path::new("localTest/schemas");
+        let manifest_path = base.join("manifest.jsonld");
+        let manifest = {
+            let manifest_str = fs::read_to_string(manifest_path).unwrap();
+            serde_json::from_str::<ManifestSchemas>(&manifest_str).unwrap()
+        };
+        for entry in manifest.map.values() {
+            let schema = SchemaJson::parse_schema_name(&entry.json, base).unwrap();
+            entry.check_shexr(base, &schema).unwrap();
+        }
+    }
+
     #[test]
     fn count_schema_entries() {
         let manifest_path = Path::new("shexTest/schemas/manifest.jsonld");
@@ -10,7 +10,8 @@ use shex_ast::compiled::shape_label::ShapeLabel;
 use shex_ast::{
     ast::Schema as SchemaJson, compiled::schema_json_compiler::SchemaJsonCompiler, Node,
 };
-use shex_validation::Validator;
+use shex_compact::ShExParser;
+use shex_validation::{test_extension, TestSemActHandler, Validator};
 use shex_validation::{ResultValue, ValidatorConfig};
 use srdf::literal::Literal;
 use srdf::srdf_graph::SRDFGraph;
@@ -20,7 +21,9 @@ use std::collections::HashMap;
 use std::fmt;
 use std::path::Path;
 use std::str::FromStr;
+use std::sync::Arc;
 use tracing::debug;
+use url::Url;
 use ValidationType::*;
 
 #[derive(Deserialize, Debug)]
@@ -156,6 +159,7 @@ fn change_extension(name: String, old_extension: String, new_extension: String)
     }
 }
 
+/// Parses the schema of an entry from its ShExJ version, or from its ShExC version when there is no ShExJ file
 fn parse_schema(
     schema: &String,
     base: &Path,
@@ -165,6 +169,9 @@ fn parse_schema(
         change_extension(schema.to_string(), ".shex".to_string(), ".json".to_string());
 
     debug!("schema: {}, new_schema_name: {}", schema, new_schema_name);
+    if schema.ends_with(".shex") && !base.join(&new_schema_name).exists() {
+        return parse_schema_shexc(schema, base);
+    }
     SchemaJson::parse_schema_name(&new_schema_name, base).map_err(|e| {
         ManifestError::SchemaJsonError {
             error: Box::new(e),
@@ -173,6 +180,24 @@ fn parse_schema(
     })
 }
 
+/// Parses a schema in ShExC taking the location of the file as base IRI
+fn parse_schema_shexc(schema: &str, base: &Path) -> Result<SchemaJson, ManifestError> {
+    let schema_path = base.join(schema);
+    let schema_absolute =
+        schema_path
+            .canonicalize()
+            .map_err(|err| ManifestError::AbsolutePathError {
+                base: schema_path.as_os_str().to_os_string(),
+                error: err,
+            })?;
+    let schema_url =
+        Url::from_file_path(&schema_absolute).map_err(|_| ManifestError::BasePathError {
+            base: schema_absolute.as_os_str().to_os_string(),
+        })?;
+    let base_iri = IriS::new_unchecked(schema_url.as_str());
+    Ok(ShExParser::parse_buf(&schema_absolute, Some(base_iri))?)
+}
+
 impl ValidationEntry {
     pub fn run(&self, base: &Path) -> Result<(), ManifestError> {
         let graph = SRDFGraph::parse_data(
@@ -197,7 +222,9 @@ impl ValidationEntry {
         compiler
             .compile(&schema, &mut compiled_schema)
             .map_err(Box::new)?;
-        let mut validator = Validator::new(compiled_schema, &ValidatorConfig::default());
+        let test_handler = Arc::new(TestSemActHandler::new());
+        let mut validator = Validator::new(compiled_schema, &ValidatorConfig::default())
+            .with_sem_act_handler(test_extension(), test_handler.clone());
         validator.validate_node_shape(&node, &shape, &graph)?;
         let type_ = parse_type(&self.type_)?;
         let result = validator.get_result(&node, &shape)?;
@@ -218,6 +245,30 @@ impl ValidationEntry {
                     entry: self.name.clone(),
                 })
             }
+        }?;
+        self.check_extension_results(test_handler.printed())
+    }
+
+    /// Checks that the values printed by the Test extension are the ones expected by the entry, in the same order
+    fn check_extension_results(&self, printed: Vec<String>) -> Result<(), ManifestError> {
+        let expected: Vec<String> = self
+            .extension_results
+            .iter()
+            .filter(|r| r.extension == test_extension().as_str())
+            .map(|r| r.prints.clone())
+            .collect();
+        if printed == expected {
+            Ok(())
+        } else {
+            debug!(
+                "Printed by Test extension: {:?}, expected: {:?}",
+                printed, expected
+            );
+            Err(ManifestError::ExtensionResultsDifferent {
+                entry: self.name.clone(),
+                expected,
+                printed,
+            })
         }
     }
 }
@@ -246,10 +297,13 @@ fn parse_maybe_focus(maybe_focus: &Option<Focus>, entry: &str) -> Result<Node, M
 
 fn parse_focus(focus: &Focus) -> Result<Node, ManifestError> {
     match focus {
-        Focus::Single(str) => {
-            let iri = IriS::from_str(str.as_str())?;
-            Ok(iri.into())
-        }
+        Focus::Single(str) => match str.strip_prefix("_:") {
+            Some(label) => Ok(Object::bnode(label.to_string()).into()),
+            None => {
+                let iri = IriS::from_str(str.as_str())?;
+                Ok(iri.into())
+            }
+        },
         Focus::Typed(str, str_type) => {
             let datatype = IriS::from_str(str_type.as_str())?;
             Ok(Object::Literal(Literal::datatype(str, &IriRef::Iri(datatype))).into())
@@ -303,9 +357,40 @@ impl Manifest for ManifestValidation {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::manifest_run_mode::ManifestRunMode;
     use std::fs;
     use std::path::Path;
 
+    #[test]
+    fn run_local_validation_entries() {
+        let base = Path::new("localTest/validation");
+        let manifest = {
+            let manifest_str = fs::read_to_string(base.join("manifest.jsonld")).unwrap();
+            serde_json::from_str::<ManifestValidation>(&manifest_str).unwrap()
+        };
+        let result = manifest.run(base, ManifestRunMode::CollectErrors, Vec::new(), None, None);
+        let panicked: Vec<&String> = result.panicked.iter().map(|(name, _)| name).collect();
+        assert!(result.failed.is_empty(), "Failed: {:?}", result.failed);
+        assert!(panicked.is_empty(), "Panicked: {panicked:?}");
+        assert_eq!(result.passed.len(), manifest.len());
+    }
+
+    #[test]
+    fn local_entry_with_different_extension_results_fails() {
+        let base = Path::new("localTest/validation");
+        let mut manifest = {
+            let manifest_str = fs::read_to_string(base.join("manifest.jsonld")).unwrap();
+            serde_json::from_str::<ManifestValidation>(&manifest_str).unwrap()
+        };
+        let entry = manifest.map.get_mut("1dotPrint_pass").unwrap();
+        entry.run(base).unwrap();
+        entry.extension_results.pop();
+        assert!(matches!(
+            entry.run(base),
+            Err(ManifestError::ExtensionResultsDifferent { .. })
+        ));
+    }
+
     #[test]
     fn count_validation_entries() {
         let manifest_path = Path::new("shexTest/validation/manifest.jsonld");
@@ -8,6 +8,7 @@ pub mod reason;
 pub mod rule;
 pub mod schema_without_imports;
 pub mod schema_without_imports_error;
+pub mod sem_act;
 pub mod shex_config;
 pub mod shex_format;
 pub mod solver;
@@ -22,6 +23,7 @@ pub use crate::result_value::*;
 pub use crate::rule::*;
 pub use crate::schema_without_imports::*;
 pub use crate::schema_without_imports_error::*;
+pub use crate::sem_act::*;
 pub use crate::shex_config::*;
 pub use crate::shex_format::*;
 pub use crate::validator::*;
@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use shex_ast::{
     compiled::{node_constraint::NodeConstraint, shape::Shape, shape_expr::ShapeExpr},
-    Node,
+    Node, ShapeLabelIdx,
 };
 
 /// Reason represents justifications about why a node conforms to some shape
@@ -10,7 +10,10 @@ use shex_ast::{
 pub enum Reason {
     NodeConstraintPassed { node: Node, nc: NodeConstraint },
     ShapeAndPassed { node: Node, se: ShapeExpr },
+    ShapeOrPassed { node: Node, se: ShapeExpr },
+    ShapeNotPassed { node: Node, se: ShapeExpr },
     ShapePassed { node: Node, shape: Shape },
+    ShapeRefPassed { node: Node, idx: ShapeLabelIdx },
 }
 
 impl Display for Reason {
@@ -22,9 +25,41 @@ impl Display for Reason {
             Reason::ShapeAndPassed { node, se } => {
                 write!(f, "AND passed. Node {node}, and: {se}")
             }
+            Reason::ShapeOrPassed { node, se } => {
+                write!(f, "OR passed. Node {node}, branch: {se}")
+            }
+            Reason::ShapeNotPassed { node, se } => {
+                write!(f, "NOT passed. Node {node}, not: {se}")
+            }
             Reason::ShapePassed { node, shape } => {
                 write!(f, "Shape passed. Node {node}, shape: {shape}")
             }
+            Reason::ShapeRefPassed { node, idx } => {
+                write!(
+                    f,
+                    "Shape reference passed. Node {node}, shape {idx} assumed while being processed"
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Reasons {
+    reasons: Vec<Reason>,
+}
+
+impl Reasons {
+    pub fn new(reasons: Vec<Reason>) -> Reasons {
+        Reasons { reasons }
+    }
+}
+
+impl Display for Reasons {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for reason in self.reasons.iter() {
+            writeln!(f, "  {reason}")?;
         }
+        Ok(())
     }
 }
@@ -1,8 +1,10 @@
 use iri_s::IriS;
 use prefixmap::IriRef;
 use serde_derive::{Deserialize, Serialize};
+use shex_ast::shexr::shexr_parser::ShExRParser;
 use shex_ast::{IriOrStr, Schema, SchemaJsonError, Shape, ShapeDecl, ShapeExpr, ShapeExprLabel};
 use shex_compact::ShExParser;
+use srdf::{ReaderMode, SRDFGraph};
 use std::collections::{hash_map::Entry, HashMap};
 use tracing::debug;
 use url::Url;
@@ -245,8 +247,28 @@ pub fn get_schema_from_iri(
                 })?;
             Ok(schema)
         }
-        ShExFormat::Turtle => {
-            todo!()
+        _ => {
+            let content =
+                iri.dereference(base)
+                    .map_err(|e| SchemaWithoutImportsError::DereferencingIri {
+                        iri: iri.clone(),
+                        error: format!("{e}"),
+                    })?;
+            // All the remaining formats are RDF serializations of ShExR
+            let rdf_format = format.rdf_format().unwrap();
+            let base = base.as_ref().map(|base| base.as_str());
+            let rdf = SRDFGraph::from_str(&content, &rdf_format, base, &ReaderMode::default())
+                .map_err(|e| SchemaWithoutImportsError::ShExRError {
+                    iri: iri.clone(),
+                    error: format!("{e}"),
+                })?;
+            let schema = ShExRParser::new(rdf).parse().map_err(|e| {
+                SchemaWithoutImportsError::ShExRError {
+                    iri: iri.clone(),
+                    error: format!("{e}"),
+                }
+            })?;
+            Ok(schema)
         }
     }
 }
@@ -16,6 +16,9 @@ pub enum SchemaWithoutImportsError {
     #[error("ShExJ error at IRI: {iri}. Error: {error}")]
     ShExJError { iri: IriS, error: String },
 
+    #[error("ShExR error at IRI: {iri}. Error: {error}")]
+    ShExRError { iri: IriS, error: String },
+
     #[error("Duplicated declaration for shape expr with label {label}\nPrevious shape expr from {imported_from:?}\n{old_shape_expr:?}\nShape Expr2 {shape_expr2:?}")]
     DuplicatedShapeDecl {
         label: ShapeExprLabel,
@@ -0,0 +1,186 @@
+use std::fmt::{Debug, Display};
+use std::sync::Mutex;
+
+use iri_s::IriS;
+use shex_ast::{Node, Pred};
+use thiserror::Error;
+use tracing::info;
+
+/// IRI of the standard ShEx `Test` extension
+pub const TEST_EXTENSION: &str = "http://shex.io/extensions/Test/";
+
+/// Returns the IRI of the standard `Test` extension
+pub fn test_extension() -> IriS {
+    IriS::new_unchecked(TEST_EXTENSION)
+}
+
+/// Context in which a semantic action is run
+#[derive(Debug, Clone)]
+pub enum SemActContext<'a> {
+    /// Start actions of the schema, run before the validation
+    Start,
+
+    /// Actions of a shape, run when a node conforms to the shape
+    Shape { node: &'a Node },
+
+    /// Actions of a triple constraint, run for each triple that matches it
+    TripleConstraint {
+        subject: &'a Node,
+        predicate: &'a Pred,
+        object: &'a Node,
+    },
+}
+
+impl Display for SemActContext<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SemActContext::Start => write!(f, "start"),
+            SemActContext::Shape { node } => write!(f, "shape with node {node}"),
+            SemActContext::TripleConstraint {
+                subject,
+                predicate,
+                object,
+            } => write!(f, "triple {subject} {predicate} {object}"),
+        }
+    }
+}
+
+/// Handler of the semantic actions of an extension.
+///
+/// Handlers are registered in the [`crate::Validator`] with the IRI of the extension they handle.
+/// Semantic actions of extensions without a registered handler are ignored
+pub trait SemActHandler: Debug + Send + Sync {
+    /// Runs the code of a semantic action. Returning an error makes the validation fail
+    fn run(&self, code: Option<&str>, context: &SemActContext<'_>) -> Result<(), SemActError>;
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum SemActError {
+    #[error("Semantic action failed: {msg}")]
+    Failed { msg: String },
+
+    #[error("Error parsing code of semantic action {code:?}: {msg}")]
+    CodeParse { code: String, msg: String },
+
+    #[error("Variable {var} is not bound when running semantic action in {context}")]
+    UnboundVariable { var: char, context: String },
+}
+
+/// Implementation of the standard `Test` extension (`http://shex.io/extensions/Test/`)
+/// used by the ShEx test suite.
+///
+/// The code of the actions must be `print(arg)` or `fail(arg)` where `arg` is a string literal
+/// or one of the variables `s`, `p` and `o`, bound to the subject, predicate and object of the
+/// current triple (or `s` to the focus node in shape actions).
+/// Printed values are logged and collected so they can be inspected after the validation
+#[derive(Debug, Default)]
+pub struct TestSemActHandler {
+    printed: Mutex<Vec<String>>,
+}
+
+impl TestSemActHandler {
+    pub fn new() -> TestSemActHandler {
+        TestSemActHandler::default()
+    }
+
+    /// Values printed so far by `print` actions
+    pub fn printed(&self) -> Vec<String> {
+        self.printed.lock().map(|p| p.clone()).unwrap_or_default()
+    }
+}
+
+impl SemActHandler for TestSemActHandler {
+    fn run(&self, code: Option<&str>, context: &SemActContext<'_>) -> Result<(), SemActError> {
+        let code = match code {
+            None => return Ok(()),
+            Some(code) => code,
+        };
+        let (command, arg) = parse_test_code(code)?;
+        let value = match arg {
+            TestArg::Str(str) => str,
+            TestArg::Var(var) => var_value(var, context)?,
+        };
+        match command {
+            TestCommand::Print => {
+                info!("Test extension prints: {value}");
+                if let Ok(mut printed) = self.printed.lock() {
+                    printed.push(value);
+                }
+                Ok(())
+            }
+            TestCommand::Fail => Err(SemActError::Failed { msg: value }),
+        }
+    }
+}
+
+enum TestCommand {
+    Print,
+    Fail,
+}
+
+enum TestArg {
+    Str(String),
+    Var(char),
+}
+
+fn parse_test_code(code: &str) -> Result<(TestCommand, TestArg), SemActError> {
+    let err = |msg: &str| SemActError::CodeParse {
+        code: code.to_string(),
+        msg: msg.to_string(),
+    };
+    let trimmed = code.trim();
+    let (command, rest) = if let Some(rest) = trimmed.strip_prefix("print") {
+        (TestCommand::Print, rest)
+    } else if let Some(rest) = trimmed.strip_prefix("fail") {
+        (TestCommand::Fail, rest)
+    } else {
+        return Err(err("expected print or fail"));
+    };
+    let arg = rest
+        .trim()
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+        .ok_or_else(|| err("expected argument between parenthesis"))?
+        .trim();
+    match arg {
+        "s" | "p" | "o" => Ok((command, TestArg::Var(arg.chars().next().unwrap()))),
+        _ => {
+            let str = arg
+                .strip_prefix('"')
+                .and_then(|arg| arg.strip_suffix('"'))
+                .ok_or_else(|| err("expected s, p, o or a string literal"))?;
+            Ok((command, TestArg::Str(unescape(str))))
+        }
+    }
+}
+
+fn unescape(str: &str) -> String {
+    let mut result = String::new();
+    let mut chars = str.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('t') => result.push('\t'),
+                Some(other) => result.push(other),
+                None => result.push('\\'),
+            },
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+fn var_value(var: char, context: &SemActContext<'_>) -> Result<String, SemActError> {
+    let value = match (var, context) {
+        ('s', SemActContext::Shape { node }) => Some(node.to_string()),
+        ('s', SemActContext::TripleConstraint { subject, .. }) => Some(subject.to_string()),
+        ('p', SemActContext::TripleConstraint { predicate, .. }) => Some(predicate.to_string()),
+        ('o', SemActContext::TripleConstraint { object, .. }) => Some(object.to_string()),
+        _ => None,
+    };
+    value.ok_or_else(|| SemActError::UnboundVariable {
+        var,
+        context: context.to_string(),
+    })
+}
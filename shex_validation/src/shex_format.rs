@@ -1,4 +1,5 @@
 use serde_derive::{Deserialize, Serialize};
+use srdf::RDFFormat;
 
 /// Contains possible ShEx formats
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
@@ -7,4 +8,24 @@ pub enum ShExFormat {
     ShExC,
     ShExJ,
     Turtle,
+    NTriples,
+    RDFXML,
+    TriG,
+    N3,
+    NQuads,
+}
+
+impl ShExFormat {
+    /// Returns the RDF format of the ShExR representations, `None` for ShExC and ShExJ
+    pub fn rdf_format(&self) -> Option<RDFFormat> {
+        match self {
+            ShExFormat::ShExC | ShExFormat::ShExJ => None,
+            ShExFormat::Turtle => Some(RDFFormat::Turtle),
+            ShExFormat::NTriples => Some(RDFFormat::NTriples),
+            ShExFormat::RDFXML => Some(RDFFormat::RDFXML),
+            ShExFormat::TriG => Some(RDFFormat::TriG),
+            ShExFormat::N3 => Some(RDFFormat::N3),
+            ShExFormat::NQuads => Some(RDFFormat::NQuads),
+        }
+    }
 }
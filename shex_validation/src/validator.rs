@@ -3,23 +3,29 @@ use crate::validator_error::*;
 use crate::validator_runner::Engine;
 use crate::PosAtom;
 use crate::Reason;
+use crate::Reasons;
 use crate::ResultValue;
+use crate::SemActContext;
+use crate::SemActHandler;
 use crate::ValidatorConfig;
 use either::Either;
-use prefixmap::IriRef;
+use iri_s::IriS;
 use prefixmap::PrefixMap;
 use serde_json::Value;
 use shapemap::query_shape_map::QueryShapeMap;
+use shapemap::NodeSelector;
+use shapemap::NodeSelectorError;
 use shapemap::ResultShapeMap;
+use shapemap::ShapeSelector;
 use shapemap::ValidationStatus;
 use shex_ast::compiled::compiled_schema::CompiledSchema;
 use shex_ast::compiled::shape_expr::ShapeExpr;
 use shex_ast::compiled::shape_label::ShapeLabel;
-use shex_ast::object_value::ObjectValue;
 use shex_ast::Node;
 use shex_ast::ShapeExprLabel;
 use shex_ast::ShapeLabelIdx;
-use srdf::SRDF;
+use srdf::{QuerySRDF, SRDF};
+use std::sync::Arc;
 use tracing::debug;
 
 type Result<T> = std::result::Result<T, ValidatorError>;
@@ -43,18 +49,53 @@ impl Validator {
         self.runner.reset()
     }
 
+    /// Registers the handler of the semantic actions of an extension
+    pub fn add_sem_act_handler(&mut self, extension: IriS, handler: Arc<dyn SemActHandler>) {
+        self.runner.add_sem_act_handler(extension, handler)
+    }
+
+    pub fn with_sem_act_handler(
+        mut self,
+        extension: IriS,
+        handler: Arc<dyn SemActHandler>,
+    ) -> Self {
+        self.add_sem_act_handler(extension, handler);
+        self
+    }
+
     /// validate a node against a shape label
     pub fn validate_node_shape<S>(&mut self, node: &Node, shape: &ShapeLabel, rdf: &S) -> Result<()>
     where
         S: SRDF,
     {
         let idx = self.get_idx(shape)?;
-        self.runner.add_pending(node.clone(), idx);
+        let start_acts = self.check_start_acts();
+        self.add_target(node.clone(), idx, &start_acts)?;
         debug!("Before while loop: ${}@{}", node, idx);
         self.loop_validating(rdf)?;
         Ok(())
     }
 
+    /// Validates a node against the shapes of a shape selector.
+    /// It is used when the nodes of a shapemap have been selected beforehand, like in asynchronous validations
+    pub fn validate_node_shapes<S>(
+        &mut self,
+        node: &Node,
+        shapes: &ShapeSelector,
+        rdf: &S,
+    ) -> Result<()>
+    where
+        S: SRDF,
+    {
+        let start_acts = self.check_start_acts();
+        for label in shapes.iter_shape() {
+            let idx = self.get_shape_expr_label(label)?;
+            self.add_target(node.clone(), idx, &start_acts)?;
+        }
+        self.loop_validating(rdf)?;
+        Ok(())
+    }
+
     fn get_shape_expr_label(&mut self, label: &ShapeExprLabel) -> Result<ShapeLabelIdx> {
         self.schema
             .find_ref(label)
@@ -64,39 +105,84 @@ impl Validator {
             })
     }
 
+    /// Validates the nodes selected by a shapemap.
+    /// Shapemaps with SPARQL node selectors need [`Validator::validate_shapemap_with_queries`]
     pub fn validate_shapemap<S>(&mut self, shapemap: &QueryShapeMap, rdf: &S) -> Result<()>
     where
         S: SRDF,
     {
-        self.fill_pending(shapemap, rdf)?;
+        self.fill_pending(shapemap, |selector, prefixmap| {
+            selector.select(rdf, prefixmap)
+        })?;
         self.loop_validating(rdf)?;
         Ok(())
     }
 
-    fn fill_pending<S>(&mut self, shapemap: &QueryShapeMap, rdf: &S) -> Result<()>
+    /// Validates the nodes selected by a shapemap whose node selectors can be SPARQL queries,
+    /// which are run in `rdf`
+    pub fn validate_shapemap_with_queries<S>(
+        &mut self,
+        shapemap: &QueryShapeMap,
+        rdf: &S,
+    ) -> Result<()>
     where
-        S: SRDF,
+        S: SRDF + QuerySRDF,
     {
-        for (node_value, label) in shapemap.iter_node_shape(rdf) {
-            let idx = self.get_shape_expr_label(label)?;
-            let node = self.node_from_object_value(node_value, rdf)?;
-            self.runner.add_pending(node.clone(), idx);
-        }
+        self.fill_pending(shapemap, |selector, prefixmap| {
+            selector.select_with_queries(rdf, prefixmap)
+        })?;
+        self.loop_validating(rdf)?;
         Ok(())
     }
 
-    fn node_from_object_value<S>(&mut self, value: &ObjectValue, rdf: &S) -> Result<Node>
+    fn fill_pending<F>(&mut self, shapemap: &QueryShapeMap, select: F) -> Result<()>
     where
-        S: SRDF,
+        F: Fn(&NodeSelector, &PrefixMap) -> std::result::Result<Vec<Node>, NodeSelectorError>,
     {
-        match value {
-            ObjectValue::IriRef(IriRef::Iri(iri)) => Ok(Node::iri(iri.clone())),
-            ObjectValue::IriRef(IriRef::Prefixed { prefix, local }) => {
-                let iri = rdf.resolve_prefix_local(prefix, local)?;
-                Ok(Node::iri(iri.clone()))
+        let start_acts = self.check_start_acts();
+        // Prefixed names of the nodes are resolved with the prefixmap of the shapemap or the one of the RDF data
+        let prefixmap = shapemap.nodes_prefixmap();
+        for association in shapemap.iter() {
+            let nodes = select(&association.node_selector, &prefixmap).map_err(|e| {
+                ValidatorError::NodeSelectorError {
+                    error: e.to_string(),
+                }
+            })?;
+            for label in association.shape_selector.iter_shape() {
+                let idx = self.get_shape_expr_label(label)?;
+                for node in nodes.iter() {
+                    self.add_target(node.clone(), idx, &start_acts)?;
+                }
             }
-            ObjectValue::Literal(_lit) => todo!(),
         }
+        Ok(())
+    }
+
+    /// Runs the start semantic actions of the schema
+    fn check_start_acts(&self) -> std::result::Result<(), ValidatorError> {
+        self.runner
+            .check_sem_acts(self.schema.start_acts(), &SemActContext::Start)
+    }
+
+    /// Adds a node/shape pair to be validated.
+    /// Abstract shapes can't be used as validation targets so they fail directly,
+    /// as well as every target when the start semantic actions fail
+    fn add_target(
+        &mut self,
+        node: Node,
+        idx: ShapeLabelIdx,
+        start_acts: &std::result::Result<(), ValidatorError>,
+    ) -> Result<()> {
+        if let Err(err) = start_acts {
+            self.runner.add_failed(node, idx, err.clone());
+        } else if self.schema.is_abstract(&idx) {
+            let label = self.get_shape_label(&idx)?.clone();
+            self.runner
+                .add_failed(node, idx, ValidatorError::AbstractShapeTarget { label });
+        } else {
+            self.runner.add_pending(node, idx);
+        }
+        Ok(())
     }
 
     fn loop_validating<S>(&mut self, rdf: &S) -> Result<()>
@@ -107,17 +193,8 @@ impl Validator {
             self.runner.new_step();
             let atom = self.runner.pop_pending().unwrap();
             debug!("Processing atom: ${atom:?}");
-            self.runner.add_processing(&atom);
-            let passed = self.check_node_atom(&atom, rdf)?;
-            self.runner.remove_processing(&atom);
-            match passed {
-                Either::Right(reasons) => {
-                    self.runner.add_checked_pos(atom, reasons);
-                }
-                Either::Left(errors) => {
-                    self.runner.add_checked_neg(atom.negated(), errors);
-                }
-            }
+            // The result is recorded in the checked atoms of the runner
+            self.check_node_atom(&atom, rdf)?;
         }
         Ok(())
     }
@@ -131,13 +208,21 @@ impl Validator {
         S: SRDF,
     {
         let (node, idx) = atom.get_value();
-        let se = find_shape_idx(idx, &self.schema);
+        let result = self.runner.check_node_idx(node, idx, &self.schema, rdf)?;
         match atom {
-            Atom::Pos { .. } => self.runner.check_node_shape_expr(node, se, rdf),
-            Atom::Neg { .. } => {
-                // Check if a node doesn't conform to a shape expr
-                todo!()
-            }
+            Atom::Pos { .. } => Ok(result),
+            // A node conforms to a negative atom when it doesn't conform to the shape
+            Atom::Neg { .. } => match result {
+                Either::Left(_errors) => Ok(Either::Right(Vec::new())),
+                Either::Right(reasons) => {
+                    let se = find_shape_idx(idx, &self.schema).clone();
+                    Ok(Either::Left(vec![ValidatorError::ShapeNotError {
+                        shape_expr: Box::new(se),
+                        node: node.clone(),
+                        reasons: Reasons::new(reasons),
+                    }]))
+                }
+            },
         }
     }
 
@@ -179,6 +264,9 @@ impl Validator {
         };
         for atom in &self.runner.checked() {
             let (node, idx) = atom.get_value();
+            if self.schema.is_internal(idx) {
+                continue;
+            }
             let label = self.get_shape_label(idx)?;
             match atom {
                 Atom::Pos(pa) => {
@@ -264,4 +352,465 @@ fn show_reasons(reasons: &[Reason]) -> String {
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use iri_s::IriS;
+    use shex_ast::CompiledSchemaError;
+    use shex_compact::ShExParser;
+    use srdf::literal::Literal;
+    use srdf::{srdf_graph::SRDFGraph, RDFFormat, ReaderMode};
+
+    fn validate(schema: &str, data: &str, node: &str, shape: &str) -> ResultValue {
+        validate_node(schema, data, &Node::iri(IriS::new_unchecked(node)), shape)
+    }
+
+    fn validate_node(schema: &str, data: &str, node: &Node, shape: &str) -> ResultValue {
+        let schema = ShExParser::parse(schema, None).unwrap();
+        let mut compiled_schema = CompiledSchema::new();
+        compiled_schema.from_schema_json(&schema).unwrap();
+        let rdf = SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let shape = ShapeLabel::iri(IriS::new_unchecked(shape));
+        let mut validator = Validator::new(compiled_schema, &ValidatorConfig::default());
+        validator.validate_node_shape(node, &shape, &rdf).unwrap();
+        validator.get_result(node, &shape).unwrap()
+    }
+
+    const SCHEMA: &str = r#"prefix : <http://example.org/>
+    prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+    :Int xsd:integer
+    :Str xsd:string
+    :IntOrStr @:Int OR @:Str
+    :NotInt NOT @:Int
+    :Person { :name @:Str ; :knows @:Person * }
+    :Named { :name . } AND NOT { :age . }
+    :Value { :value @:Int OR @:Str }
+    :WithAddress { :address { :street @:Str ; :geo { :lat xsd:decimal } } }
+    "#;
+
+    const DATA: &str = r#"prefix : <http://example.org/>
+    :alice :name "Alice" ; :knows :bob .
+    :bob :name "Bob" ; :knows :alice .
+    :carol :name "Carol" ; :knows :dave .
+    :dave :name 23 .
+    :eve :name "Eve" ; :age 23 .
+    :v1 :value 1 .
+    :v2 :value "one" .
+    :v3 :value :one .
+    :frank :address [ :street "Main" ; :geo [ :lat 4.5 ] ] .
+    :grace :address [ :street "Main" ; :geo [ :lat "none" ] ] .
+    "#;
+
+    #[test]
+    fn test_or() {
+        assert_eq!(
+            validate(
+                SCHEMA,
+                DATA,
+                "http://example.org/v1",
+                "http://example.org/Value"
+            ),
+            ResultValue::Ok
+        );
+        assert_eq!(
+            validate(
+                SCHEMA,
+                DATA,
+                "http://example.org/v2",
+                "http://example.org/Value"
+            ),
+            ResultValue::Ok
+        );
+        assert_eq!(
+            validate(
+                SCHEMA,
+                DATA,
+                "http://example.org/v3",
+                "http://example.org/Value"
+            ),
+            ResultValue::Failed
+        );
+    }
+
+    #[test]
+    fn test_not() {
+        assert_eq!(
+            validate(
+                SCHEMA,
+                DATA,
+                "http://example.org/alice",
+                "http://example.org/Named"
+            ),
+            ResultValue::Ok
+        );
+        assert_eq!(
+            validate(
+                SCHEMA,
+                DATA,
+                "http://example.org/eve",
+                "http://example.org/Named"
+            ),
+            ResultValue::Failed
+        );
+        assert_eq!(
+            validate(
+                SCHEMA,
+                DATA,
+                "http://example.org/alice",
+                "http://example.org/NotInt"
+            ),
+            ResultValue::Ok
+        );
+    }
+
+    #[test]
+    fn test_recursive_refs() {
+        assert_eq!(
+            validate(
+                SCHEMA,
+                DATA,
+                "http://example.org/alice",
+                "http://example.org/Person"
+            ),
+            ResultValue::Ok
+        );
+        assert_eq!(
+            validate(
+                SCHEMA,
+                DATA,
+                "http://example.org/carol",
+                "http://example.org/Person"
+            ),
+            ResultValue::Failed
+        );
+    }
+
+    #[test]
+    fn test_negated_reference_cycle() {
+        // :n@:S would conform if :n@:X didn't, and :n@:X would conform if :n@:S didn't
+        let schema = ShExParser::parse(
+            r#"prefix : <http://example.org/>
+            :S { :p @:X }
+            :X NOT @:S
+            "#,
+            None,
+        )
+        .unwrap();
+        let mut compiled_schema = CompiledSchema::new();
+        assert!(matches!(
+            compiled_schema.from_schema_json(&schema),
+            Err(CompiledSchemaError::NegatedReferenceCycle { .. })
+        ));
+    }
+
+    /// Data where `n` people know each other, and everyone has a name except the last one if `unnamed`
+    fn complete_graph(n: usize, unnamed: bool) -> String {
+        let mut data = "prefix : <http://example.org/>\n".to_string();
+        for i in 0..n {
+            if i < n - 1 || !unnamed {
+                data.push_str(&format!(":p{i} :name \"P{i}\" .\n"));
+            }
+            for j in 0..n {
+                data.push_str(&format!(":p{i} :knows :p{j} .\n"));
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn test_recursive_refs_complete_graph() {
+        let schema = ShExParser::parse(SCHEMA, None).unwrap();
+        let person = ShapeLabel::iri(IriS::new_unchecked("http://example.org/Person"));
+        for (unnamed, expected) in [(false, ResultValue::Ok), (true, ResultValue::Failed)] {
+            let mut compiled_schema = CompiledSchema::new();
+            compiled_schema.from_schema_json(&schema).unwrap();
+            let rdf = SRDFGraph::from_str(
+                &complete_graph(12, unnamed),
+                &RDFFormat::Turtle,
+                None,
+                &ReaderMode::Strict,
+            )
+            .unwrap();
+            let mut validator = Validator::new(compiled_schema, &ValidatorConfig::default());
+            for i in 0..12 {
+                let node = Node::iri(IriS::new_unchecked(&format!("http://example.org/p{i}")));
+                validator.validate_node_shape(&node, &person, &rdf).unwrap();
+                assert_eq!(validator.get_result(&node, &person).unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nested_blank_nodes() {
+        assert_eq!(
+            validate(
+                SCHEMA,
+                DATA,
+                "http://example.org/frank",
+                "http://example.org/WithAddress"
+            ),
+            ResultValue::Ok
+        );
+        assert_eq!(
+            validate(
+                SCHEMA,
+                DATA,
+                "http://example.org/grace",
+                "http://example.org/WithAddress"
+            ),
+            ResultValue::Failed
+        );
+    }
+
+    #[test]
+    fn test_literal_focus_node() {
+        let str = Node::literal(Literal::str("abc"));
+        assert_eq!(
+            validate_node(SCHEMA, DATA, &str, "http://example.org/Str"),
+            ResultValue::Ok
+        );
+        assert_eq!(
+            validate_node(SCHEMA, DATA, &str, "http://example.org/Int"),
+            ResultValue::Failed
+        );
+        assert_eq!(
+            validate_node(SCHEMA, DATA, &str, "http://example.org/Person"),
+            ResultValue::Failed
+        );
+    }
+
+    #[test]
+    fn test_empty_shape() {
+        let schema = r#"prefix : <http://example.org/>
+        :Empty {}
+        :Opt { :p . ? }"#;
+        let data = r#"prefix : <http://example.org/>
+        :x :q 1 ."#;
+        assert_eq!(
+            validate(
+                schema,
+                data,
+                "http://example.org/x",
+                "http://example.org/Empty"
+            ),
+            ResultValue::Ok
+        );
+        assert_eq!(
+            validate(
+                schema,
+                data,
+                "http://example.org/x",
+                "http://example.org/Opt"
+            ),
+            ResultValue::Ok
+        );
+    }
+
+    const FACETS_SCHEMA: &str = r#"prefix : <http://example.org/>
+    prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+    :Age { :age xsd:integer MININCLUSIVE 0 MAXEXCLUSIVE 150 }
+    :Positive { :value MINEXCLUSIVE 0 }
+    :Price { :price TOTALDIGITS 4 FRACTIONDIGITS 2 }
+    :Country { :code /^[A-Z]{2}$/ }
+    :CountryCI { :code /^[a-z]{2}$/i }
+    "#;
+
+    const FACETS_DATA: &str = r#"prefix : <http://example.org/>
+    prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+    :a1 :age 0 .
+    :a2 :age -1 .
+    :a3 :age 150 .
+    :a4 :age "23" .
+    :p1 :value 0.5 .
+    :p2 :value "1.0e-3"^^xsd:double .
+    :p3 :value 0.0 .
+    :p4 :value "0"^^xsd:nonNegativeInteger .
+    :m1 :price 12.50 .
+    :m2 :price 123.45 .
+    :m3 :price 1.255 .
+    :c1 :code "ES" .
+    :c2 :code "es" .
+    :c3 :code "ESP" .
+    "#;
+
+    fn validate_facets(node: &str, shape: &str) -> ResultValue {
+        validate(
+            FACETS_SCHEMA,
+            FACETS_DATA,
+            format!("http://example.org/{node}").as_str(),
+            format!("http://example.org/{shape}").as_str(),
+        )
+    }
+
+    #[test]
+    fn test_numeric_facets() {
+        assert_eq!(validate_facets("a1", "Age"), ResultValue::Ok);
+        assert_eq!(validate_facets("a2", "Age"), ResultValue::Failed);
+        assert_eq!(validate_facets("a3", "Age"), ResultValue::Failed);
+        assert_eq!(validate_facets("a4", "Age"), ResultValue::Failed);
+        assert_eq!(validate_facets("p1", "Positive"), ResultValue::Ok);
+        assert_eq!(validate_facets("p2", "Positive"), ResultValue::Ok);
+        assert_eq!(validate_facets("p3", "Positive"), ResultValue::Failed);
+        assert_eq!(validate_facets("p4", "Positive"), ResultValue::Failed);
+        assert_eq!(validate_facets("m1", "Price"), ResultValue::Ok);
+        assert_eq!(validate_facets("m2", "Price"), ResultValue::Failed);
+        assert_eq!(validate_facets("m3", "Price"), ResultValue::Failed);
+    }
+
+    #[test]
+    fn test_pattern_facets() {
+        assert_eq!(validate_facets("c1", "Country"), ResultValue::Ok);
+        assert_eq!(validate_facets("c2", "Country"), ResultValue::Failed);
+        assert_eq!(validate_facets("c3", "Country"), ResultValue::Failed);
+        assert_eq!(validate_facets("c2", "CountryCI"), ResultValue::Ok);
+        assert_eq!(validate_facets("c3", "CountryCI"), ResultValue::Failed);
+    }
+
+    const VALUES_SCHEMA: &str = r#"prefix : <http://example.org/>
+    prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+    :Color { :color [:red :green "blue" 1 true "2"^^xsd:decimal] }
+    :IriRange { :p [<http://example.org/a/>~ - <http://example.org/a/x> - <http://example.org/a/y/>~] }
+    :AnyIri { :p [. - <http://example.org/a/x>] }
+    :LitRange { :p ["ab"~ - "abc" - "abd"~] }
+    :Lang { :p [@en] }
+    :LangStem { :p [@en~ - @en-gb] }
+    :AnyLang { :p [@~] }
+    "#;
+
+    const VALUES_DATA: &str = r#"prefix : <http://example.org/>
+    prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+    :c1 :color :red .
+    :c2 :color "blue" .
+    :c3 :color 1 .
+    :c4 :color true .
+    :c5 :color "2"^^xsd:decimal .
+    :c6 :color :yellow .
+    :c7 :color "blue"@en .
+    :c8 :color "1" .
+    :i1 :p <http://example.org/a/b> .
+    :i2 :p <http://example.org/a/x> .
+    :i3 :p <http://example.org/a/y/z> .
+    :i4 :p <http://example.org/b> .
+    :l1 :p "abz" .
+    :l2 :p "abc" .
+    :l3 :p "abdef" .
+    :l4 :p "xyz" .
+    :g1 :p "hi"@en .
+    :g2 :p "hi"@en-US .
+    :g3 :p "hi"@en-GB .
+    :g4 :p "hi"@es .
+    :g5 :p "hi" .
+    "#;
+
+    fn validate_values(node: &str, shape: &str) -> ResultValue {
+        validate(
+            VALUES_SCHEMA,
+            VALUES_DATA,
+            format!("http://example.org/{node}").as_str(),
+            format!("http://example.org/{shape}").as_str(),
+        )
+    }
+
+    #[test]
+    fn test_value_set_object_values() {
+        for node in ["c1", "c2", "c3", "c4", "c5"] {
+            assert_eq!(validate_values(node, "Color"), ResultValue::Ok, "{node}");
+        }
+        for node in ["c6", "c7", "c8"] {
+            assert_eq!(
+                validate_values(node, "Color"),
+                ResultValue::Failed,
+                "{node}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_value_set_iri_ranges() {
+        assert_eq!(validate_values("i1", "IriRange"), ResultValue::Ok);
+        assert_eq!(validate_values("i2", "IriRange"), ResultValue::Failed);
+        assert_eq!(validate_values("i3", "IriRange"), ResultValue::Failed);
+        assert_eq!(validate_values("i4", "IriRange"), ResultValue::Failed);
+        assert_eq!(validate_values("i4", "AnyIri"), ResultValue::Ok);
+        assert_eq!(validate_values("i2", "AnyIri"), ResultValue::Failed);
+        assert_eq!(validate_values("l1", "AnyIri"), ResultValue::Failed);
+    }
+
+    #[test]
+    fn test_value_set_literal_ranges() {
+        assert_eq!(validate_values("l1", "LitRange"), ResultValue::Ok);
+        assert_eq!(validate_values("l2", "LitRange"), ResultValue::Failed);
+        assert_eq!(validate_values("l3", "LitRange"), ResultValue::Failed);
+        assert_eq!(validate_values("l4", "LitRange"), ResultValue::Failed);
+    }
+
+    #[test]
+    fn test_value_set_languages() {
+        assert_eq!(validate_values("g1", "Lang"), ResultValue::Ok);
+        assert_eq!(validate_values("g2", "Lang"), ResultValue::Failed);
+        assert_eq!(validate_values("g1", "LangStem"), ResultValue::Ok);
+        assert_eq!(validate_values("g2", "LangStem"), ResultValue::Ok);
+        assert_eq!(validate_values("g3", "LangStem"), ResultValue::Failed);
+        assert_eq!(validate_values("g4", "LangStem"), ResultValue::Failed);
+        assert_eq!(validate_values("g4", "AnyLang"), ResultValue::Ok);
+        assert_eq!(validate_values("g5", "AnyLang"), ResultValue::Failed);
+    }
+
+    const SEM_ACTS_SCHEMA: &str = r#"prefix : <http://example.org/>
+    prefix Test: <http://shex.io/extensions/Test/>
+    %Test:{ print("start") %}
+    :S { :p . %Test:{ print(o) %} } %Test:{ print(s) %}
+    :F { :p . %Test:{ fail("failed") %} }
+    :U { :p . %<http://example.org/Unknown>{ anything %} }
+    "#;
+
+    fn validate_sem_acts(schema: &str, shape: &str) -> (ResultValue, Vec<String>) {
+        let schema = ShExParser::parse(schema, None).unwrap();
+        let mut compiled_schema = CompiledSchema::new();
+        compiled_schema.from_schema_json(&schema).unwrap();
+        let data = r#"prefix : <http://example.org/>
+        :x :p 1 ."#;
+        let rdf = SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let node = Node::iri(IriS::new_unchecked("http://example.org/x"));
+        let shape = ShapeLabel::iri(IriS::new_unchecked(shape));
+        let handler = Arc::new(crate::TestSemActHandler::new());
+        let mut validator = Validator::new(compiled_schema, &ValidatorConfig::default())
+            .with_sem_act_handler(crate::test_extension(), handler.clone());
+        validator.validate_node_shape(&node, &shape, &rdf).unwrap();
+        (
+            validator.get_result(&node, &shape).unwrap(),
+            handler.printed(),
+        )
+    }
+
+    #[test]
+    fn test_sem_acts_print() {
+        let (result, printed) = validate_sem_acts(SEM_ACTS_SCHEMA, "http://example.org/S");
+        assert_eq!(result, ResultValue::Ok);
+        assert_eq!(
+            printed,
+            vec![
+                "start",
+                "\"1\"^^<http://www.w3.org/2001/XMLSchema#integer>",
+                "http://example.org/x"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sem_acts_fail() {
+        let (result, _) = validate_sem_acts(SEM_ACTS_SCHEMA, "http://example.org/F");
+        assert_eq!(result, ResultValue::Failed);
+        let (result, _) = validate_sem_acts(SEM_ACTS_SCHEMA, "http://example.org/U");
+        assert_eq!(result, ResultValue::Ok);
+    }
+
+    #[test]
+    fn test_start_sem_acts_fail() {
+        let schema = r#"prefix : <http://example.org/>
+        prefix Test: <http://shex.io/extensions/Test/>
+        %Test:{ fail("start") %}
+        :S { :p . }"#;
+        let (result, _) = validate_sem_acts(schema, "http://example.org/S");
+        assert_eq!(result, ResultValue::Failed);
+    }
+}
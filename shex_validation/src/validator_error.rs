@@ -2,12 +2,17 @@ use std::fmt::Display;
 
 use prefixmap::PrefixMapError;
 use rbe::RbeError;
+use shex_ast::compiled::node_constraint::NodeConstraint;
 use shex_ast::compiled::preds::Preds;
+use shex_ast::compiled::sem_act::SemAct;
+use shex_ast::compiled::shape::Shape;
 use shex_ast::compiled::shape_expr::ShapeExpr;
 use shex_ast::{compiled::shape_label::ShapeLabel, Node, Pred, ShapeExprLabel, ShapeLabelIdx};
 use srdf::Object;
 use thiserror::Error;
 
+use crate::{Reasons, SemActError};
+
 #[derive(Error, Debug, Clone)]
 pub enum ValidatorError {
     #[error("SRDF Error: {error}")]
@@ -25,6 +30,9 @@ pub enum ValidatorError {
     #[error("Shapemap error: {error}")]
     ShapeMapError { error: String },
 
+    #[error("Selecting the nodes of the shapemap: {error}")]
+    NodeSelectorError { error: String },
+
     #[error("Failed regular expression")]
     RbeFailed(),
 
@@ -45,11 +53,62 @@ pub enum ValidatorError {
 
     #[error("And error: shape expression {shape_expr} failed for node {node}: {errors}")]
     ShapeAndError {
-        shape_expr: ShapeExpr,
+        shape_expr: Box<ShapeExpr>,
         node: Node,
         errors: ValidatorErrors,
     },
 
+    #[error("Node constraint error: node {node} doesn't satisfy {nc}: {error}")]
+    NodeConstraintError {
+        node: Node,
+        nc: Box<NodeConstraint>,
+        error: Box<RbeError<Pred, Node, ShapeLabelIdx>>,
+    },
+
+    #[error("Or error: shape expression {shape_expr} failed for node {node}: all branches failed: {errors}")]
+    ShapeOrError {
+        shape_expr: Box<ShapeExpr>,
+        node: Node,
+        errors: ValidatorErrors,
+    },
+
+    #[error("Not error: node {node} conforms to shape expression {shape_expr} but it should not: {reasons}")]
+    ShapeNotError {
+        shape_expr: Box<ShapeExpr>,
+        node: Node,
+        reasons: Reasons,
+    },
+
+    #[error("Reference error: node {node} doesn't conform to shape {idx}: {errors}")]
+    ReferenceFailed {
+        node: Node,
+        idx: ShapeLabelIdx,
+        errors: ValidatorErrors,
+    },
+
+    #[error("Extended shape error: node {node} failed to match the triple expression of {shape}: {errors}")]
+    ExtendedShapeError {
+        shape: Box<Shape>,
+        node: Node,
+        errors: ValidatorErrors,
+    },
+
+    #[error("Semantic action {sem_act} failed in {context}: {error}")]
+    SemActFailed {
+        sem_act: Box<SemAct>,
+        context: String,
+        error: SemActError,
+    },
+
+    #[error("Shape {idx} is abstract and there are no shapes that extend it")]
+    AbstractShapeWithoutExtensions { idx: ShapeLabelIdx },
+
+    #[error("Shape {label} is abstract and can't be used as a validation target")]
+    AbstractShapeTarget { label: ShapeLabel },
+
+    #[error("Shape expression with index {idx} not found in schema")]
+    ShapeLabelIdxNotFound { idx: ShapeLabelIdx },
+
     #[error("Error reading config file from path {path}: {error}")]
     ValidatorConfigFromPathError { path: String, error: String },
 
@@ -1,21 +1,26 @@
 use crate::atom;
 use crate::validator_error::*;
 use crate::Reason;
+use crate::Reasons;
 use crate::ResultValue;
+use crate::SemActContext;
+use crate::SemActHandler;
 use crate::ValidatorConfig;
 use either::Either;
 use indexmap::IndexSet;
 use iri_s::IriS;
-use rbe::MatchTableIter;
+use shex_ast::compiled::compiled_schema::CompiledSchema;
 use shex_ast::compiled::preds::Preds;
+use shex_ast::compiled::sem_act::SemAct;
 use shex_ast::compiled::shape::Shape;
 use shex_ast::compiled::shape_expr::ShapeExpr;
 use shex_ast::Node;
 use shex_ast::Pred;
 use shex_ast::ShapeLabelIdx;
-use srdf::{Object, SRDF};
+use srdf::SRDF;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::debug;
 
 type Result<T> = std::result::Result<T, ValidatorError>;
@@ -31,12 +36,17 @@ pub struct Engine {
     processing: IndexSet<Atom>,
     pending: IndexSet<Atom>,
     //rules: Vec<Rule>,
-    alternative_match_iterators: Vec<MatchTableIter<Pred, Node, ShapeLabelIdx>>,
+    hypotheses: IndexSet<Atom>,
+
+    /// Conformant results obtained assuming that some atoms that were being processed conform,
+    /// with the reasons of the result and the atoms assumed
+    tentative: HashMap<Atom, (Vec<Reason>, IndexSet<Atom>)>,
     // alternatives: Vec<ResultMap<Node, ShapeLabelIdx>>,
     config: ValidatorConfig,
     step_counter: usize,
     reasons: HashMap<PosAtom, Vec<Reason>>,
     errors: HashMap<NegAtom, Vec<ValidatorError>>,
+    sem_act_handlers: HashMap<IriS, Arc<dyn SemActHandler>>,
 }
 
 impl Default for Engine {
@@ -52,17 +62,72 @@ impl Engine {
             processing: IndexSet::new(),
             pending: IndexSet::new(),
             //rules: Vec::new(),
-            alternative_match_iterators: Vec::new(),
+            hypotheses: IndexSet::new(),
+            tentative: HashMap::new(),
             config: config.clone(),
             step_counter: 0,
             reasons: HashMap::new(),
             errors: HashMap::new(),
+            sem_act_handlers: HashMap::new(),
         }
     }
 
     pub fn reset(&mut self) {
         let config = self.config.clone();
+        let sem_act_handlers = std::mem::take(&mut self.sem_act_handlers);
         *self = Engine::new(&config);
+        self.sem_act_handlers = sem_act_handlers;
+    }
+
+    pub fn add_sem_act_handler(&mut self, extension: IriS, handler: Arc<dyn SemActHandler>) {
+        self.sem_act_handlers.insert(extension, handler);
+    }
+
+    /// Runs some semantic actions with the handlers registered for their extensions.
+    /// Actions of extensions without handler are ignored
+    pub fn check_sem_acts(
+        &self,
+        sem_acts: &[SemAct],
+        context: &SemActContext<'_>,
+    ) -> std::result::Result<(), ValidatorError> {
+        for sem_act in sem_acts {
+            match self.sem_act_handlers.get(sem_act.name()) {
+                Some(handler) => {
+                    handler.run(sem_act.code(), context).map_err(|error| {
+                        ValidatorError::SemActFailed {
+                            sem_act: Box::new(sem_act.clone()),
+                            context: context.to_string(),
+                            error,
+                        }
+                    })?;
+                }
+                None => debug!("No handler for semantic action {sem_act}, ignored"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the semantic actions of a shape and of its triple constraints
+    /// once a node has matched the shape with some values of its neighbourhood
+    fn check_shape_sem_acts(
+        &self,
+        node: &Node,
+        shape: &Shape,
+        values: &[(Pred, Node)],
+    ) -> std::result::Result<(), ValidatorError> {
+        for tc in shape.triple_sem_acts() {
+            for (pred, value) in values {
+                if pred == tc.pred() && tc.cond().matches(value).is_ok() {
+                    let context = SemActContext::TripleConstraint {
+                        subject: node,
+                        predicate: pred,
+                        object: value,
+                    };
+                    self.check_sem_acts(tc.sem_acts(), &context)?;
+                }
+            }
+        }
+        self.check_sem_acts(shape.sem_acts(), &SemActContext::Shape { node })
     }
 
     pub(crate) fn add_processing(&mut self, atom: &Atom) {
@@ -208,10 +273,142 @@ impl Engine {
         }
     }
 
+    /// Checks if a node conforms to the shape expression identified by `idx`.
+    ///
+    /// If the atom is already being processed, it is assumed to conform (co-inductive semantics)
+    /// and the assumption is recorded as a hypothesis.
+    /// Non-conformant results are cached in `checked`, because schemas without negated reference cycles
+    /// are monotonic, so a node that fails assuming that other atoms conform also fails without the assumption.
+    /// Conformant results are cached in `checked` when they don't depend on hypotheses,
+    /// and otherwise they are kept as tentative results until the hypotheses are resolved
+    pub(crate) fn check_node_idx<S>(
+        &mut self,
+        node: &Node,
+        idx: &ShapeLabelIdx,
+        schema: &CompiledSchema,
+        rdf: &S,
+    ) -> Result<Either<Vec<ValidatorError>, Vec<Reason>>>
+    where
+        S: SRDF,
+    {
+        let pos_atom = PosAtom::new((node.clone(), *idx));
+        let atom = Atom::pos(&pos_atom);
+        if self.checked.contains(&atom) {
+            return Ok(Either::Right(self.find_reasons(&pos_atom)));
+        }
+        let neg_atom = NegAtom::new((node.clone(), *idx));
+        if self.checked.contains(&Atom::neg(&neg_atom)) {
+            return Ok(Either::Left(self.find_errors(&neg_atom)));
+        }
+        if let Some((reasons, hypotheses)) = self.tentative.get(&atom) {
+            let reasons = reasons.clone();
+            self.hypotheses.extend(hypotheses.iter().cloned());
+            return Ok(Either::Right(reasons));
+        }
+        if self.is_processing(&atom) {
+            debug!(
+                "Step {}. Assuming {node}@{idx} because it is being processed",
+                self.step_counter
+            );
+            self.hypotheses.insert(atom);
+            return Ok(Either::Right(vec![Reason::ShapeRefPassed {
+                node: node.clone(),
+                idx: *idx,
+            }]));
+        }
+        let outer_hypotheses = std::mem::take(&mut self.hypotheses);
+        self.add_processing(&atom);
+        let result = self.check_node_label(node, idx, schema, rdf);
+        self.remove_processing(&atom);
+        let mut hypotheses = std::mem::replace(&mut self.hypotheses, outer_hypotheses);
+        hypotheses.swap_remove(&atom);
+        let result = result?;
+        self.resolve_hypothesis(&atom, result.is_right());
+        match &result {
+            Either::Right(reasons) if hypotheses.is_empty() => {
+                self.add_checked_pos(atom, reasons.clone())
+            }
+            Either::Right(reasons) => {
+                self.tentative
+                    .insert(atom, (reasons.clone(), hypotheses.clone()));
+            }
+            Either::Left(errors) => self.add_checked_neg(atom.negated(), errors.clone()),
+        }
+        self.hypotheses.extend(hypotheses);
+        Ok(result)
+    }
+
+    /// Updates the tentative results that assumed that `atom` conforms once its result is known.
+    /// If it conforms, the assumption is removed and the results without assumptions are cached,
+    /// otherwise the results are discarded so they are checked again
+    fn resolve_hypothesis(&mut self, atom: &Atom, conforms: bool) {
+        let dependents: Vec<Atom> = self
+            .tentative
+            .iter()
+            .filter(|(_, (_, hypotheses))| hypotheses.contains(atom))
+            .map(|(dependent, _)| dependent.clone())
+            .collect();
+        for dependent in dependents {
+            let resolved = match self.tentative.get_mut(&dependent) {
+                Some((_, hypotheses)) if conforms => {
+                    hypotheses.swap_remove(atom);
+                    hypotheses.is_empty()
+                }
+                _ => true,
+            };
+            if resolved {
+                if let Some((reasons, _)) = self.tentative.remove(&dependent) {
+                    if conforms {
+                        self.add_checked_pos(dependent, reasons)
+                    }
+                }
+            }
+        }
+    }
+
+    /// A node conforms to a shape label if it conforms to its shape expression,
+    /// unless the shape is abstract, or to the shape expression of some shape that extends it
+    fn check_node_label<S>(
+        &mut self,
+        node: &Node,
+        idx: &ShapeLabelIdx,
+        schema: &CompiledSchema,
+        rdf: &S,
+    ) -> Result<Either<Vec<ValidatorError>, Vec<Reason>>>
+    where
+        S: SRDF,
+    {
+        let mut candidates = Vec::new();
+        if !schema.is_abstract(idx) {
+            candidates.push(*idx);
+        }
+        candidates.extend(
+            schema
+                .descendants(idx)
+                .iter()
+                .filter(|d| !schema.is_abstract(d)),
+        );
+        if candidates.is_empty() {
+            return Ok(Either::Left(vec![
+                ValidatorError::AbstractShapeWithoutExtensions { idx: *idx },
+            ]));
+        }
+        let mut errors = Vec::new();
+        for candidate in candidates {
+            let se = find_shape_expr(&candidate, schema)?;
+            match self.check_node_shape_expr(node, se, schema, rdf)? {
+                Either::Right(reasons) => return Ok(Either::Right(reasons)),
+                Either::Left(errs) => errors.extend(errs),
+            }
+        }
+        Ok(Either::Left(errors))
+    }
+
     pub(crate) fn check_node_shape_expr<S>(
         &mut self,
         node: &Node,
         se: &ShapeExpr,
+        schema: &CompiledSchema,
         rdf: &S,
     ) -> Result<Either<Vec<ValidatorError>, Vec<Reason>>>
     where
@@ -224,24 +421,25 @@ impl Engine {
         match se {
             ShapeExpr::NodeConstraint(nc) => match nc.cond().matches(node) {
                 Ok(_pending) => {
-                    // TODO: Add pending to pending nodes
-                    // I think this is not needed because node constraints will not generate pending nodes
+                    // Node constraints don't generate pending nodes
                     Ok(Either::Right(vec![Reason::NodeConstraintPassed {
                         node: node.clone(),
                         nc: nc.clone(),
                     }]))
                 }
-                Err(err) => Ok(Either::Left(vec![ValidatorError::RbeError(err)])),
+                Err(err) => Ok(Either::Left(vec![ValidatorError::NodeConstraintError {
+                    node: node.clone(),
+                    nc: Box::new(nc.clone()),
+                    error: Box::new(err),
+                }])),
             },
-            ShapeExpr::Ref { .. } => {
-                todo!()
-            }
+            ShapeExpr::Ref { idx } => self.check_node_idx(node, idx, schema, rdf),
             ShapeExpr::ShapeAnd { exprs, .. } => {
                 for e in exprs {
-                    let result = self.check_node_shape_expr(node, e, rdf)?;
+                    let result = self.check_node_shape_expr(node, e, schema, rdf)?;
                     if let Some(errors) = result.left() {
                         return Ok(Either::Left(vec![ValidatorError::ShapeAndError {
-                            shape_expr: e.clone(),
+                            shape_expr: Box::new(e.clone()),
                             node: node.clone(),
                             errors: ValidatorErrors::new(errors),
                         }]));
@@ -253,27 +451,42 @@ impl Engine {
                 }]))
             }
             ShapeExpr::ShapeNot { expr, .. } => {
-                let result = self.check_node_shape_expr(node, expr, rdf)?;
+                let result = self.check_node_shape_expr(node, expr, schema, rdf)?;
                 match result {
-                    Either::Left(_errors) => {
-                        todo!()
-                    }
-                    Either::Right(_errors) => {
-                        todo!()
+                    Either::Left(_errors) => Ok(Either::Right(vec![Reason::ShapeNotPassed {
+                        node: node.clone(),
+                        se: se.clone(),
+                    }])),
+                    Either::Right(reasons) => {
+                        Ok(Either::Left(vec![ValidatorError::ShapeNotError {
+                            shape_expr: expr.clone(),
+                            node: node.clone(),
+                            reasons: Reasons::new(reasons),
+                        }]))
                     }
                 }
             }
-            ShapeExpr::ShapeOr { .. } => {
-                todo!()
-                /*for e in exprs {
-                    let result = self.check_node_shape_expr(node, e, rdf)?;
-                    if result {
-                        return Ok(true);
+            ShapeExpr::ShapeOr { exprs, .. } => {
+                let mut errors_collection = Vec::new();
+                for e in exprs {
+                    let result = self.check_node_shape_expr(node, e, schema, rdf)?;
+                    match result {
+                        Either::Left(errors) => errors_collection.extend(errors),
+                        Either::Right(_reasons) => {
+                            return Ok(Either::Right(vec![Reason::ShapeOrPassed {
+                                node: node.clone(),
+                                se: e.clone(),
+                            }]))
+                        }
                     }
                 }
-                Ok(false)*/
+                Ok(Either::Left(vec![ValidatorError::ShapeOrError {
+                    shape_expr: Box::new(se.clone()),
+                    node: node.clone(),
+                    errors: ValidatorErrors::new(errors_collection),
+                }]))
             }
-            ShapeExpr::Shape(shape) => self.check_node_shape(node, shape, rdf),
+            ShapeExpr::Shape(shape) => self.check_node_shape(node, shape, schema, rdf),
             ShapeExpr::Empty => Ok(Either::Right(Vec::new())),
             ShapeExpr::External {} => Ok(Either::Right(Vec::new())),
         }
@@ -283,82 +496,135 @@ impl Engine {
         &mut self,
         node: &Node,
         shape: &Shape,
+        schema: &CompiledSchema,
         rdf: &S,
     ) -> Result<Either<Vec<ValidatorError>, Vec<Reason>>>
     where
         S: SRDF,
     {
-        let (values, remainder) = self.neighs(node, shape.preds(), rdf)?;
+        let mut parts = vec![shape];
+        let mut constraints = Vec::new();
+        for parent in shape.extends() {
+            collect_extended_parts(parent, schema, &mut parts, &mut constraints)?;
+        }
+        let preds: IndexSet<IriS> = parts.iter().flat_map(|part| part.preds()).collect();
+        let (values, remainder) = self.neighs(node, preds.iter().cloned().collect(), rdf)?;
         if shape.is_closed() && !remainder.is_empty() {
             let errs = vec![ValidatorError::ClosedShapeWithRemainderPreds {
                 remainder: Preds::new(remainder),
-                declared: Preds::new(
-                    shape
-                        .preds()
-                        .iter()
-                        .map(|iri| {
-                            let new_iri = iri.clone();
-                            Pred::from(new_iri)
-                        })
-                        .collect(),
-                ),
+                declared: Preds::new(preds.into_iter().map(Pred::from).collect()),
             }];
             return Ok(Either::Left(errs));
         };
         debug!("Neighs of {node}: {values:?}");
-        let mut result_iter = shape.rbe_table().matches(values)?;
-        let mut current_err = None;
-        let counter = self.step_counter;
-        let mut found = false;
-        let mut iter_count = 0;
 
-        // Search for the first result which is not an err
-        while let Some(next_result) = result_iter.next() {
-            iter_count += 1;
+        // Constraints of the extended shapes which are not shapes apply to the whole node
+        for constraint in constraints {
+            if let Either::Left(errs) = self.check_node_shape_expr(node, constraint, schema, rdf)? {
+                return Ok(Either::Left(errs));
+            }
+        }
+
+        if parts.len() == 1 {
+            return match self.check_rbe_table(shape, values.clone(), schema, rdf)? {
+                Either::Left(errors) => {
+                    if errors.is_empty() {
+                        debug!(
+                            "No value found for node/shape where node = {node}, shape = {shape:?}"
+                        );
+                    }
+                    Ok(Either::Left(errors))
+                }
+                Either::Right(()) => match self.check_shape_sem_acts(node, shape, &values) {
+                    Ok(()) => Ok(Either::Right(vec![Reason::ShapePassed {
+                        node: node.clone(),
+                        shape: shape.clone(),
+                    }])),
+                    Err(err) => Ok(Either::Left(vec![err])),
+                },
+            };
+        }
+
+        // With extensions, the neighbourhood is partitioned between the shape and the extended shapes
+        // and each part must match the triple expression of the corresponding shape
+        let mut errors = Vec::new();
+        for partition in partitions(&values, &parts) {
+            let mut partition_errors = Vec::new();
+            for (part, part_values) in parts.iter().zip(partition.iter()) {
+                if let Either::Left(errs) =
+                    self.check_rbe_table(part, part_values.clone(), schema, rdf)?
+                {
+                    partition_errors.push(ValidatorError::ExtendedShapeError {
+                        shape: Box::new((*part).clone()),
+                        node: node.clone(),
+                        errors: ValidatorErrors::new(errs),
+                    });
+                    break;
+                }
+            }
+            if partition_errors.is_empty() {
+                for (part, part_values) in parts.iter().zip(partition.iter()) {
+                    if let Err(err) = self.check_shape_sem_acts(node, part, part_values) {
+                        partition_errors.push(err);
+                        break;
+                    }
+                }
+            }
+            if partition_errors.is_empty() {
+                return Ok(Either::Right(vec![Reason::ShapePassed {
+                    node: node.clone(),
+                    shape: shape.clone(),
+                }]));
+            }
+            errors.extend(partition_errors);
+        }
+        Ok(Either::Left(errors))
+    }
+
+    /// Checks if some values match the triple expression of a shape and if the pending values
+    /// of that matching conform to the corresponding shapes.
+    /// If some pending values fail, the next alternative matching is tried
+    fn check_rbe_table<S>(
+        &mut self,
+        shape: &Shape,
+        values: Vec<(Pred, Node)>,
+        schema: &CompiledSchema,
+        rdf: &S,
+    ) -> Result<Either<Vec<ValidatorError>, ()>>
+    where
+        S: SRDF,
+    {
+        let result_iter = shape.rbe_table().matches(values)?;
+        let mut errors = Vec::new();
+        let counter = self.step_counter;
+        for (iter_count, next_result) in result_iter.enumerate() {
             match next_result {
                 Ok(pending_values) => {
                     debug!("Found result, iteration {iter_count}");
+                    let mut failed_refs = Vec::new();
                     for (p, v) in pending_values.iter() {
                         debug!("Step {counter}: Value in pending: {p}/{v}");
-                        let pos_atom = PosAtom::new(((*p).clone(), *v));
-                        let atom = Atom::pos(&pos_atom);
-                        if self.is_processing(&atom) {
-                            let pred = p.clone();
-                            debug!(
-                                "Step {counter} Adding ok: {}/{v} because it was already processed",
-                                &pred
-                            );
-                            self.add_ok(pred, *v);
-                        } else {
-                            self.insert_pending(&atom);
+                        if let Either::Left(errs) = self.check_node_idx(p, v, schema, rdf)? {
+                            failed_refs.push(ValidatorError::ReferenceFailed {
+                                node: p.clone(),
+                                idx: *v,
+                                errors: ValidatorErrors::new(errs),
+                            });
+                            break;
                         }
                     }
-                    // We keep alternative match iterators which will be recovered in case of failure
-                    self.alternative_match_iterators.push(result_iter);
-                    found = true;
-                    break;
+                    if failed_refs.is_empty() {
+                        return Ok(Either::Right(()));
+                    }
+                    errors.extend(failed_refs);
                 }
                 Err(err) => {
                     debug!("Result with error {err} at iteration {iter_count}");
-                    current_err = Some(err);
+                    errors.push(ValidatorError::RbeError(err));
                 }
             }
         }
-        if !found {
-            let errs = match current_err {
-                Some(rbe_err) => vec![ValidatorError::RbeError(rbe_err)],
-                None => {
-                    debug!("No value found for node/shape where node = {node}, shape = {shape:?}. Current_err = empty");
-                    Vec::new()
-                }
-            };
-            Ok(Either::Left(errs))
-        } else {
-            Ok(Either::Right(vec![Reason::ShapePassed {
-                node: node.clone(),
-                shape: shape.clone(),
-            }]))
-        }
+        Ok(Either::Left(errors))
     }
 
     fn cnv_iri<S>(&self, iri: S::IRI) -> Pred
@@ -381,7 +647,7 @@ impl Engine {
     where
         S: SRDF,
     {
-        let node = self.get_rdf_node(node, rdf);
+        let node = self.get_rdf_node(node, rdf)?;
         let list: Vec<_> = preds.iter().map(|pred| S::iri_s2iri(pred)).collect();
         if let Some(subject) = S::term_as_subject(&node) {
             let (outgoing_arcs, remainder) = rdf
@@ -402,36 +668,147 @@ impl Engine {
             }
             Ok((result, remainder_preds))
         } else {
-            todo!()
+            // Literals can't be subjects of triples so their neighbourhood is empty
+            Ok((Vec::new(), Vec::new()))
         }
     }
 
-    fn cnv_err<S>(&self, _err: S::Err) -> ValidatorError
+    fn cnv_err<S>(&self, err: S::Err) -> ValidatorError
     where
         S: SRDF,
     {
-        todo!()
+        ValidatorError::SRDFError {
+            error: format!("{err}"),
+        }
     }
 
-    fn get_rdf_node<S>(&self, node: &Node, _rdf: &S) -> S::Term
+    fn get_rdf_node<S>(&self, node: &Node, _rdf: &S) -> Result<S::Term>
     where
         S: SRDF,
     {
-        match node.as_object() {
-            Object::Iri(iri) => {
-                let i = S::iri_s2iri(iri);
-                S::iri_as_term(i)
-            }
-            Object::BlankNode(_id) => {
-                todo!()
+        S::object_as_term(node.as_object()).map_err(|e| self.cnv_err::<S>(e))
+    }
+
+    pub fn insert_pending(&mut self, atom: &Atom) {
+        self.pending.insert((*atom).clone());
+    }
+}
+
+fn find_shape_expr<'a>(idx: &ShapeLabelIdx, schema: &'a CompiledSchema) -> Result<&'a ShapeExpr> {
+    match schema.find_shape_idx(idx) {
+        Some((_label, se)) => Ok(se),
+        None => Err(ValidatorError::ShapeLabelIdxNotFound { idx: *idx }),
+    }
+}
+
+/// Collects the shapes whose triple expressions take part in an extension
+/// and the shape expressions that must be checked against the whole node
+fn collect_extended_parts<'a>(
+    idx: &ShapeLabelIdx,
+    schema: &'a CompiledSchema,
+    parts: &mut Vec<&'a Shape>,
+    constraints: &mut Vec<&'a ShapeExpr>,
+) -> Result<()> {
+    match find_shape_expr(idx, schema)? {
+        ShapeExpr::Ref { idx } => collect_extended_parts(idx, schema, parts, constraints),
+        ShapeExpr::ShapeAnd { exprs, .. } => {
+            for e in exprs {
+                collect_extended_se(e, schema, parts, constraints)?;
             }
-            Object::Literal(_lit) => {
-                todo!()
+            Ok(())
+        }
+        se => collect_extended_se(se, schema, parts, constraints),
+    }
+}
+
+fn collect_extended_se<'a>(
+    se: &'a ShapeExpr,
+    schema: &'a CompiledSchema,
+    parts: &mut Vec<&'a Shape>,
+    constraints: &mut Vec<&'a ShapeExpr>,
+) -> Result<()> {
+    match se {
+        ShapeExpr::Shape(shape) => {
+            // A shape inherited through different paths only contributes once
+            if !parts.contains(&shape) {
+                parts.push(shape);
+                for parent in shape.extends() {
+                    collect_extended_parts(parent, schema, parts, constraints)?;
+                }
             }
+            Ok(())
+        }
+        se => {
+            constraints.push(se);
+            Ok(())
         }
     }
+}
 
-    pub fn insert_pending(&mut self, atom: &Atom) {
-        self.pending.insert((*atom).clone());
+/// Obtains the ways in which the values can be distributed between the shapes
+/// so that each value is assigned to a shape that mentions its predicate.
+/// The distributions are generated lazily because their number grows exponentially with the number of values
+fn partitions<'a>(values: &'a [(Pred, Node)], parts: &[&Shape]) -> Partitions<'a> {
+    let candidates: Vec<Vec<usize>> = values
+        .iter()
+        .map(|(pred, _)| {
+            parts
+                .iter()
+                .enumerate()
+                .filter(|(_, part)| part.preds().iter().any(|p| p == pred.iri()))
+                .map(|(n, _)| n)
+                .collect()
+        })
+        .collect();
+    let current = if candidates.iter().any(|c| c.is_empty()) {
+        None
+    } else {
+        Some(vec![0; values.len()])
+    };
+    Partitions {
+        values,
+        parts: parts.len(),
+        candidates,
+        current,
+    }
+}
+
+/// Iterator over the distributions of values between shapes, which are enumerated like the digits of a counter
+struct Partitions<'a> {
+    values: &'a [(Pred, Node)],
+    parts: usize,
+
+    /// Indexes of the shapes that mention the predicate of each value
+    candidates: Vec<Vec<usize>>,
+
+    /// Position in `candidates` of the shape assigned to each value in the next distribution.
+    /// `None` when there are no more distributions
+    current: Option<Vec<usize>>,
+}
+
+impl Iterator for Partitions<'_> {
+    type Item = Vec<Vec<(Pred, Node)>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current.as_mut()?;
+        let mut partition = vec![Vec::new(); self.parts];
+        for ((value, candidates), position) in
+            self.values.iter().zip(&self.candidates).zip(current.iter())
+        {
+            partition[candidates[*position]].push(value.clone());
+        }
+        let mut exhausted = true;
+        for (position, candidates) in current.iter_mut().zip(&self.candidates) {
+            *position += 1;
+            if *position < candidates.len() {
+                exhausted = false;
+                break;
+            }
+            *position = 0;
+        }
+        if exhausted {
+            self.current = None;
+        }
+        Some(partition)
     }
 }
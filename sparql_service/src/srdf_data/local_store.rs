@@ -0,0 +1,1343 @@
+use super::rdf_data::bound_query;
+use super::{RdfData, RdfDataError};
+use iri_s::IriS;
+use lazy_static::lazy_static;
+use oxrdf::{
+    BlankNode as OxBlankNode, GraphName, Literal as OxLiteral, NamedNode as OxNamedNode,
+    Quad as OxQuad, Subject as OxSubject, Term as OxTerm, Triple as OxTriple,
+};
+use prefixmap::{PrefixMap, PrefixMapError};
+use redb::{
+    Database, ReadOnlyTable, ReadableTable, ReadableTableMetadata, StorageError, TableDefinition,
+};
+use spareval::{InternalQuad, QueryEvaluator, QueryResults, QueryableDataset};
+use srdf::{
+    GraphSelector, ListOfIriAndTerms, Object, QuerySRDF, QuerySolution, QuerySolutions, RDFFormat,
+    ReaderMode, SRDFBasic, SRDFGraph, VarName, SRDF,
+};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, Weak};
+
+/// Name of the file that contains the dataset inside the store directory
+pub const STORE_DATA_FILE: &str = "data.redb";
+
+/// Dictionary of the terms of the store in N-Triples, which the quads refer to by their identifiers.
+/// Identifiers start at 1 and terms are removed with the last quad that uses them
+const TERMS: TableDefinition<u64, &str> = TableDefinition::new("terms");
+const TERM_IDS: TableDefinition<&str, u64> = TableDefinition::new("term_ids");
+
+/// Number of places of the quads of the store where each term appears
+const TERM_REFS: TableDefinition<u64, u64> = TableDefinition::new("term_refs");
+
+/// Number of quads of each named graph, keyed by the identifier of its name
+const GRAPHS: TableDefinition<u64, u64> = TableDefinition::new("graphs");
+
+/// Number of quads of a document that are written or removed in each transaction
+const BATCH_SIZE: usize = 100_000;
+
+/// Identifier of the default graph in the keys of the quads
+const DEFAULT_GRAPH_ID: u64 = 0;
+
+/// Identifiers of the terms of a quad, in the order of the index that contains it
+type QuadKey = (u64, u64, u64, u64);
+
+/// Quads keyed by subject, predicate, object and graph, by predicate, object, subject and graph
+/// and by object, subject, predicate and graph, so the quads that match a pattern are a range of one of them
+const SPOG: TableDefinition<QuadKey, ()> = TableDefinition::new("spog");
+const POSG: TableDefinition<QuadKey, ()> = TableDefinition::new("posg");
+const OSPG: TableDefinition<QuadKey, ()> = TableDefinition::new("ospg");
+
+/// Terms decoded in a read that are kept to decode the next quads
+const TERM_CACHE_SIZE: usize = 1024;
+
+/// Prefix declarations of the documents loaded in the store
+const PREFIXES: TableDefinition<&str, &str> = TableDefinition::new("prefixes");
+
+lazy_static! {
+    /// Databases that are open in this process.
+    /// A database file can only be opened once, so stores opened again share the same database
+    static ref OPEN_STORES: Mutex<HashMap<PathBuf, Weak<Database>>> = Mutex::new(HashMap::new());
+}
+
+type QuadIter = Box<dyn Iterator<Item = Result<OxQuad, RdfDataError>>>;
+
+/// RDF dataset persisted in a local directory
+///
+/// The quads are kept in a [redb](https://www.redb.org) database as identifiers of a dictionary of terms
+/// in three indexes with composite keys, so the store is queried in place instead of being read in memory when it is opened.
+/// The documents are parsed once when they are loaded, whatever their format was,
+/// and the blank nodes of each load are renamed so that they don't clash with the ones already in the store.
+/// The quads of a document are written in transactions of [`BATCH_SIZE`] quads while it is parsed,
+/// so an interrupted update keeps the batches that were already written
+#[derive(Debug, Clone)]
+pub struct LocalStore {
+    dir: PathBuf,
+    db: Arc<Database>,
+    graph_selector: GraphSelector,
+}
+
+impl LocalStore {
+    /// Opens the store in `dir`, creating an empty store if the directory doesn't exist
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<LocalStore, RdfDataError> {
+        let dir = dir.as_ref().to_path_buf();
+        let opening_error = |e: &dyn Display| RdfDataError::OpeningStore {
+            path: dir.display().to_string(),
+            error: e.to_string(),
+        };
+        fs::create_dir_all(&dir).map_err(|e| opening_error(&e))?;
+        let key = fs::canonicalize(&dir).map_err(|e| opening_error(&e))?;
+        let db = {
+            let mut open_stores = OPEN_STORES
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            match open_stores.get(&key).and_then(Weak::upgrade) {
+                Some(db) => db,
+                None => {
+                    let db = Arc::new(
+                        Database::create(dir.join(STORE_DATA_FILE))
+                            .map_err(|e| opening_error(&e))?,
+                    );
+                    open_stores.retain(|_, db| db.strong_count() > 0);
+                    open_stores.insert(key, Arc::downgrade(&db));
+                    db
+                }
+            }
+        };
+        let store = LocalStore {
+            dir,
+            db,
+            graph_selector: GraphSelector::default(),
+        };
+        store.update(|_| Ok(()))?;
+        Ok(store)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the file that contains the dataset
+    pub fn data_path(&self) -> PathBuf {
+        self.dir.join(STORE_DATA_FILE)
+    }
+
+    /// Graphs that are used to answer queries
+    pub fn graph_selector(&self) -> &GraphSelector {
+        &self.graph_selector
+    }
+
+    pub fn set_graph_selector(&mut self, graph_selector: GraphSelector) {
+        self.graph_selector = graph_selector
+    }
+
+    pub fn with_graph_selector(mut self, graph_selector: GraphSelector) -> Self {
+        self.set_graph_selector(graph_selector);
+        self
+    }
+
+    /// Number of quads of the store
+    pub fn len(&self) -> Result<usize, RdfDataError> {
+        let txn = self.db.begin_read().map_err(|e| self.reading_error(e))?;
+        let table = txn.open_table(SPOG).map_err(|e| self.reading_error(e))?;
+        let len = table.len().map_err(|e| self.reading_error(e))?;
+        Ok(len as usize)
+    }
+
+    pub fn is_empty(&self) -> Result<bool, RdfDataError> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Names of the graphs of the store
+    pub fn graph_names(&self) -> Result<HashSet<GraphName>, RdfDataError> {
+        let txn = self.db.begin_read().map_err(|e| self.reading_error(e))?;
+        let graphs = txn.open_table(GRAPHS).map_err(|e| self.reading_error(e))?;
+        let mut decoder = TermDecoder {
+            terms: txn.open_table(TERMS).map_err(|e| self.reading_error(e))?,
+            cache: HashMap::new(),
+        };
+        let mut names = HashSet::new();
+        for entry in graphs.range::<u64>(..).map_err(|e| self.reading_error(e))? {
+            let (id, _) = entry.map_err(|e| self.reading_error(e))?;
+            names.insert(
+                decoder
+                    .graph_name(id.value())
+                    .map_err(|e| self.reading_error(e))?,
+            );
+        }
+        Ok(names)
+    }
+
+    /// Prefix declarations of the documents loaded in the store
+    pub fn prefixmap(&self) -> Result<PrefixMap, RdfDataError> {
+        let txn = self.db.begin_read().map_err(|e| self.reading_error(e))?;
+        let table = txn
+            .open_table(PREFIXES)
+            .map_err(|e| self.reading_error(e))?;
+        let mut prefixmap = PrefixMap::new();
+        for entry in table.range::<&str>(..).map_err(|e| self.reading_error(e))? {
+            let (alias, iri) = entry.map_err(|e| self.reading_error(e))?;
+            prefixmap
+                .insert(alias.value(), &IriS::new_unchecked(iri.value()))
+                .map_err(|e| self.reading_error(e))?;
+        }
+        Ok(prefixmap)
+    }
+
+    /// Reads the whole dataset of the store in memory
+    pub fn read_graph(&self) -> Result<SRDFGraph, RdfDataError> {
+        let quads = self
+            .quads_for_pattern(None, None, None)?
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut graph = SRDFGraph::from_quads(quads);
+        graph.merge_prefixes(self.prefixmap()?)?;
+        Ok(graph)
+    }
+
+    /// Adds the triples and prefixes of `graph` to the store returning the number of triples of the updated dataset.
+    /// The blank nodes of `graph` are renamed, so loading a document twice duplicates its blank nodes
+    pub fn add_graph(&self, graph: &SRDFGraph) -> Result<usize, RdfDataError> {
+        self.insert_quads(graph.quads_with_fresh_blank_nodes(), graph.prefixmap())?;
+        self.len()
+    }
+
+    /// Parses a document adding its triples and prefixes to the store, returning the number of triples of the updated dataset.
+    /// The document is written while it is parsed, so it doesn't need to fit in memory.
+    /// The blank nodes of the document are renamed, so loading it twice duplicates its blank nodes
+    pub fn load_from_reader<R: io::Read>(
+        &self,
+        read: R,
+        format: &RDFFormat,
+        base: Option<&str>,
+        reader_mode: &ReaderMode,
+        jsonld_contexts: &HashMap<String, String>,
+    ) -> Result<usize, RdfDataError> {
+        let fresh = FreshBlankNodes::new();
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+        let prefixmap =
+            SRDFGraph::parse_quads(read, format, base, reader_mode, jsonld_contexts, |quad| {
+                batch.push(fresh.quad(quad));
+                if batch.len() >= BATCH_SIZE {
+                    self.insert_quads(batch.drain(..), PrefixMap::new())?;
+                }
+                Ok::<(), RdfDataError>(())
+            })?;
+        self.insert_quads(batch, prefixmap)?;
+        self.len()
+    }
+
+    /// Removes the triples of `graph` from the store
+    pub fn remove_graph(&self, graph: &SRDFGraph) -> Result<Removal, RdfDataError> {
+        let mut removal = Removal::default();
+        let mut batch = Vec::new();
+        for quad in graph.quads() {
+            removal.add_quad(quad, &mut batch)
+        }
+        removal.removed = self.remove_quads(batch)?;
+        Ok(removal)
+    }
+
+    /// Parses a document removing its triples from the store while it is parsed
+    pub fn remove_from_reader<R: io::Read>(
+        &self,
+        read: R,
+        format: &RDFFormat,
+        base: Option<&str>,
+        reader_mode: &ReaderMode,
+        jsonld_contexts: &HashMap<String, String>,
+    ) -> Result<Removal, RdfDataError> {
+        let mut removal = Removal::default();
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+        SRDFGraph::parse_quads(read, format, base, reader_mode, jsonld_contexts, |quad| {
+            removal.add_quad(quad, &mut batch);
+            if batch.len() >= BATCH_SIZE {
+                removal.removed += self.remove_quads(batch.drain(..))?;
+            }
+            Ok::<(), RdfDataError>(())
+        })?;
+        removal.removed += self.remove_quads(batch)?;
+        Ok(removal)
+    }
+
+    /// Removes all the triples of the store, keeping its prefixes
+    pub fn clear(&self) -> Result<(), RdfDataError> {
+        self.update(|txn| {
+            for table in [SPOG, POSG, OSPG] {
+                txn.delete_table(table).map_err(|e| self.writing_error(e))?;
+            }
+            for table in [TERM_REFS, GRAPHS] {
+                txn.delete_table(table).map_err(|e| self.writing_error(e))?;
+            }
+            txn.delete_table(TERMS).map_err(|e| self.writing_error(e))?;
+            txn.delete_table(TERM_IDS)
+                .map_err(|e| self.writing_error(e))?;
+            Ok(())
+        })
+    }
+
+    /// Adds the quads and prefixes in a transaction
+    fn insert_quads<I: IntoIterator<Item = OxQuad>>(
+        &self,
+        quads: I,
+        prefixmap: PrefixMap,
+    ) -> Result<(), RdfDataError> {
+        self.update(|txn| {
+            let mut tables = QuadTables::open(txn).map_err(|e| self.writing_error(e))?;
+            for quad in quads {
+                tables.insert(&quad).map_err(|e| self.writing_error(e))?;
+            }
+            let mut prefixes = txn
+                .open_table(PREFIXES)
+                .map_err(|e| self.writing_error(e))?;
+            for (alias, iri) in prefixmap.iter() {
+                prefixes
+                    .insert(alias.as_str(), iri.as_str())
+                    .map_err(|e| self.writing_error(e))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Removes the quads in a transaction returning how many of them were in the store
+    fn remove_quads<I: IntoIterator<Item = OxQuad>>(
+        &self,
+        quads: I,
+    ) -> Result<usize, RdfDataError> {
+        self.update(|txn| {
+            let mut tables = QuadTables::open(txn).map_err(|e| self.writing_error(e))?;
+            let mut count = 0;
+            for quad in quads {
+                if tables.remove(&quad).map_err(|e| self.writing_error(e))? {
+                    count += 1
+                }
+            }
+            Ok(count)
+        })
+    }
+
+    /// Runs `update` in a write transaction that is committed if it succeeds.
+    /// The tables are created if they don't exist so they can be read afterwards
+    fn update<T, F>(&self, update: F) -> Result<T, RdfDataError>
+    where
+        F: FnOnce(&redb::WriteTransaction) -> Result<T, RdfDataError>,
+    {
+        let txn = self.db.begin_write().map_err(|e| self.writing_error(e))?;
+        let result = update(&txn)?;
+        QuadTables::open(&txn).map_err(|e| self.writing_error(e))?;
+        txn.open_table(PREFIXES)
+            .map_err(|e| self.writing_error(e))?;
+        txn.commit().map_err(|e| self.writing_error(e))?;
+        Ok(result)
+    }
+
+    /// Quads of any graph that match the given terms, which are read from the index whose keys start with them
+    fn quads_for_pattern(
+        &self,
+        subject: Option<&OxTerm>,
+        predicate: Option<&OxTerm>,
+        object: Option<&OxTerm>,
+    ) -> Result<QuadIter, RdfDataError> {
+        let txn = self.db.begin_read().map_err(|e| self.reading_error(e))?;
+        let term_ids = txn
+            .open_table(TERM_IDS)
+            .map_err(|e| self.reading_error(e))?;
+        let mut ids = Vec::new();
+        for term in [subject, predicate, object] {
+            match term {
+                None => ids.push(None),
+                Some(term) => match term_ids
+                    .get(term.to_string().as_str())
+                    .map_err(|e| self.reading_error(e))?
+                {
+                    Some(id) => ids.push(Some(id.value())),
+                    // Terms that are not in the dictionary don't match any quad
+                    None => return Ok(Box::new(std::iter::empty())),
+                },
+            }
+        }
+        let (index, prefix) = match (ids[0], ids[1], ids[2]) {
+            (Some(s), Some(p), Some(o)) => (QuadIndex::Spog, vec![s, p, o]),
+            (Some(s), Some(p), None) => (QuadIndex::Spog, vec![s, p]),
+            (Some(s), None, Some(o)) => (QuadIndex::Ospg, vec![o, s]),
+            (Some(s), None, None) => (QuadIndex::Spog, vec![s]),
+            (None, Some(p), Some(o)) => (QuadIndex::Posg, vec![p, o]),
+            (None, Some(p), None) => (QuadIndex::Posg, vec![p]),
+            (None, None, Some(o)) => (QuadIndex::Ospg, vec![o]),
+            (None, None, None) => (QuadIndex::Spog, Vec::new()),
+        };
+        let table = txn
+            .open_table(index.table())
+            .map_err(|e| self.reading_error(e))?;
+        let keys = table
+            .range(key_range(&prefix))
+            .map_err(|e| self.reading_error(e))?;
+        let mut decoder = TermDecoder {
+            terms: txn.open_table(TERMS).map_err(|e| self.reading_error(e))?,
+            cache: HashMap::new(),
+        };
+        let path = self.data_path().display().to_string();
+        Ok(Box::new(keys.map(move |entry| {
+            let (key, _) = entry.map_err(|e| reading_error(&path, e))?;
+            decoder
+                .quad(index.quad_ids(key.value()))
+                .map_err(|e| reading_error(&path, e))
+        })))
+    }
+
+    /// Triples of the selected graphs that match the given terms
+    fn triples(
+        &self,
+        subject: Option<&OxTerm>,
+        predicate: Option<&OxTerm>,
+        object: Option<&OxTerm>,
+    ) -> Result<Vec<OxTriple>, RdfDataError> {
+        let mut seen = HashSet::new();
+        let mut triples = Vec::new();
+        for quad in self.quads_for_pattern(subject, predicate, object)? {
+            let quad = quad?;
+            if self.is_selected(&quad.graph_name) {
+                let triple = OxTriple::from(quad);
+                if seen.insert(triple.clone()) {
+                    triples.push(triple)
+                }
+            }
+        }
+        Ok(triples)
+    }
+
+    fn is_selected(&self, graph_name: &GraphName) -> bool {
+        match &self.graph_selector {
+            GraphSelector::DefaultGraph => graph_name.is_default_graph(),
+            GraphSelector::NamedGraph(name) => {
+                matches!(graph_name, GraphName::NamedNode(n) if n.as_str() == name.as_str())
+            }
+            GraphSelector::Union => true,
+        }
+    }
+
+    fn reading_error<E: Display>(&self, error: E) -> RdfDataError {
+        reading_error(&self.data_path().display().to_string(), error)
+    }
+
+    fn writing_error<E: Display>(&self, error: E) -> RdfDataError {
+        RdfDataError::WritingStore {
+            path: self.data_path().display().to_string(),
+            error: error.to_string(),
+        }
+    }
+}
+
+/// Outcome of removing the triples of a document from the store
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Removal {
+    /// Triples of the document that were in the store
+    pub removed: usize,
+
+    /// Triples of the document with blank nodes, which are not removed
+    /// because the blank nodes of the store were renamed when they were loaded
+    pub with_blank_nodes: usize,
+}
+
+impl Removal {
+    /// Adds the quad to the batch of quads to remove unless it has blank nodes
+    fn add_quad(&mut self, quad: OxQuad, batch: &mut Vec<OxQuad>) {
+        if has_blank_nodes(&quad) {
+            self.with_blank_nodes += 1
+        } else {
+            batch.push(quad)
+        }
+    }
+}
+
+fn has_blank_nodes(quad: &OxQuad) -> bool {
+    subject_has_blank_nodes(&quad.subject)
+        || term_has_blank_nodes(&quad.object)
+        || quad.graph_name.is_blank_node()
+}
+
+fn subject_has_blank_nodes(subject: &OxSubject) -> bool {
+    match subject {
+        OxSubject::NamedNode(_) => false,
+        OxSubject::BlankNode(_) => true,
+        OxSubject::Triple(triple) => triple_has_blank_nodes(triple),
+    }
+}
+
+fn term_has_blank_nodes(term: &OxTerm) -> bool {
+    match term {
+        OxTerm::BlankNode(_) => true,
+        OxTerm::Triple(triple) => triple_has_blank_nodes(triple),
+        _ => false,
+    }
+}
+
+fn triple_has_blank_nodes(triple: &OxTriple) -> bool {
+    subject_has_blank_nodes(&triple.subject) || term_has_blank_nodes(&triple.object)
+}
+
+/// Renames the blank nodes of a document adding a random prefix to their labels,
+/// so they don't clash with the blank nodes of other documents and the renamed labels don't need to be kept
+struct FreshBlankNodes {
+    prefix: String,
+}
+
+impl FreshBlankNodes {
+    fn new() -> Self {
+        FreshBlankNodes {
+            prefix: OxBlankNode::default().into_string(),
+        }
+    }
+
+    fn blank_node(&self, bnode: &OxBlankNode) -> OxBlankNode {
+        OxBlankNode::new_unchecked(format!("{}_{}", self.prefix, bnode.as_str()))
+    }
+
+    fn subject(&self, subject: OxSubject) -> OxSubject {
+        match subject {
+            OxSubject::BlankNode(bnode) => OxSubject::BlankNode(self.blank_node(&bnode)),
+            OxSubject::Triple(triple) => OxSubject::Triple(Box::new(self.triple(*triple))),
+            subject => subject,
+        }
+    }
+
+    fn term(&self, term: OxTerm) -> OxTerm {
+        match term {
+            OxTerm::BlankNode(bnode) => OxTerm::BlankNode(self.blank_node(&bnode)),
+            OxTerm::Triple(triple) => OxTerm::Triple(Box::new(self.triple(*triple))),
+            term => term,
+        }
+    }
+
+    fn triple(&self, triple: OxTriple) -> OxTriple {
+        OxTriple::new(
+            self.subject(triple.subject),
+            triple.predicate,
+            self.term(triple.object),
+        )
+    }
+
+    fn quad(&self, quad: OxQuad) -> OxQuad {
+        OxQuad {
+            subject: self.subject(quad.subject),
+            predicate: quad.predicate,
+            object: self.term(quad.object),
+            graph_name: match quad.graph_name {
+                GraphName::BlankNode(bnode) => GraphName::BlankNode(self.blank_node(&bnode)),
+                graph_name => graph_name,
+            },
+        }
+    }
+}
+
+/// Orders of the identifiers of a quad in the keys of the indexes
+#[derive(Debug, Clone, Copy)]
+enum QuadIndex {
+    Spog,
+    Posg,
+    Ospg,
+}
+
+impl QuadIndex {
+    fn table(&self) -> TableDefinition<'static, QuadKey, ()> {
+        match self {
+            QuadIndex::Spog => SPOG,
+            QuadIndex::Posg => POSG,
+            QuadIndex::Ospg => OSPG,
+        }
+    }
+
+    /// Key in this index of the quad with identifiers `(s, p, o, g)`
+    fn key(&self, (s, p, o, g): QuadKey) -> QuadKey {
+        match self {
+            QuadIndex::Spog => (s, p, o, g),
+            QuadIndex::Posg => (p, o, s, g),
+            QuadIndex::Ospg => (o, s, p, g),
+        }
+    }
+
+    /// Identifiers `(s, p, o, g)` of the quad with a key of this index
+    fn quad_ids(&self, (a, b, c, g): QuadKey) -> QuadKey {
+        match self {
+            QuadIndex::Spog => (a, b, c, g),
+            QuadIndex::Posg => (c, a, b, g),
+            QuadIndex::Ospg => (b, c, a, g),
+        }
+    }
+}
+
+/// Keys of an index that start with the given identifiers
+fn key_range(prefix: &[u64]) -> RangeInclusive<QuadKey> {
+    let key = |fill| {
+        let mut ids = [fill; 4];
+        ids[..prefix.len()].copy_from_slice(prefix);
+        (ids[0], ids[1], ids[2], ids[3])
+    };
+    key(u64::MIN)..=key(u64::MAX)
+}
+
+/// N-Triples form of the graph of a quad in the dictionary, `None` for the default graph
+fn graph_term(graph_name: &GraphName) -> Option<String> {
+    match graph_name {
+        GraphName::NamedNode(n) => Some(n.to_string()),
+        GraphName::BlankNode(b) => Some(b.to_string()),
+        GraphName::DefaultGraph => None,
+    }
+}
+
+/// Decodes the identifiers of the quads read in a transaction, keeping the last terms that were decoded
+struct TermDecoder {
+    terms: ReadOnlyTable<u64, &'static str>,
+    cache: HashMap<u64, OxTerm>,
+}
+
+impl TermDecoder {
+    fn term(&mut self, id: u64) -> Result<OxTerm, String> {
+        if let Some(term) = self.cache.get(&id) {
+            return Ok(term.clone());
+        }
+        let term = match self.terms.get(id).map_err(|e| e.to_string())? {
+            Some(term) => OxTerm::from_str(term.value()).map_err(|e| e.to_string())?,
+            None => return Err(format!("Term {id} not found in the dictionary")),
+        };
+        if self.cache.len() >= TERM_CACHE_SIZE {
+            self.cache.clear()
+        }
+        self.cache.insert(id, term.clone());
+        Ok(term)
+    }
+
+    fn quad(&mut self, (s, p, o, g): QuadKey) -> Result<OxQuad, String> {
+        let subject = self.term(s)?;
+        let subject = LocalStore::term_as_subject(&subject)
+            .ok_or_else(|| format!("Subject {subject} is not an IRI, a blank node or a triple"))?;
+        let predicate = match self.term(p)? {
+            OxTerm::NamedNode(n) => n,
+            term => return Err(format!("Predicate {term} is not an IRI")),
+        };
+        let object = self.term(o)?;
+        let graph_name = match g {
+            DEFAULT_GRAPH_ID => GraphName::DefaultGraph,
+            g => self.graph_name(g)?,
+        };
+        Ok(OxQuad::new(subject, predicate, object, graph_name))
+    }
+
+    fn graph_name(&mut self, id: u64) -> Result<GraphName, String> {
+        match self.term(id)? {
+            OxTerm::NamedNode(n) => Ok(GraphName::NamedNode(n)),
+            OxTerm::BlankNode(b) => Ok(GraphName::BlankNode(b)),
+            term => Err(format!("Graph {term} is not an IRI or a blank node")),
+        }
+    }
+}
+
+/// Dictionary, counters and indexes of the quads opened in a write transaction
+struct QuadTables<'txn> {
+    terms: redb::Table<'txn, u64, &'static str>,
+    term_ids: redb::Table<'txn, &'static str, u64>,
+    term_refs: redb::Table<'txn, u64, u64>,
+    graphs: redb::Table<'txn, u64, u64>,
+    spog: redb::Table<'txn, QuadKey, ()>,
+    posg: redb::Table<'txn, QuadKey, ()>,
+    ospg: redb::Table<'txn, QuadKey, ()>,
+}
+
+impl<'txn> QuadTables<'txn> {
+    fn open(txn: &'txn redb::WriteTransaction) -> Result<Self, redb::TableError> {
+        Ok(QuadTables {
+            terms: txn.open_table(TERMS)?,
+            term_ids: txn.open_table(TERM_IDS)?,
+            term_refs: txn.open_table(TERM_REFS)?,
+            graphs: txn.open_table(GRAPHS)?,
+            spog: txn.open_table(SPOG)?,
+            posg: txn.open_table(POSG)?,
+            ospg: txn.open_table(OSPG)?,
+        })
+    }
+
+    fn insert(&mut self, quad: &OxQuad) -> Result<(), StorageError> {
+        let graph = match graph_term(&quad.graph_name) {
+            Some(graph) => self.term_id(&graph)?,
+            None => DEFAULT_GRAPH_ID,
+        };
+        let ids = (
+            self.term_id(&quad.subject.to_string())?,
+            self.term_id(&quad.predicate.to_string())?,
+            self.term_id(&quad.object.to_string())?,
+            graph,
+        );
+        if self.spog.insert(QuadIndex::Spog.key(ids), ())?.is_some() {
+            return Ok(());
+        }
+        self.posg.insert(QuadIndex::Posg.key(ids), ())?;
+        self.ospg.insert(QuadIndex::Ospg.key(ids), ())?;
+        let (s, p, o, g) = ids;
+        for id in [s, p, o] {
+            add_to_counter(&mut self.term_refs, id, 1)?;
+        }
+        if g != DEFAULT_GRAPH_ID {
+            add_to_counter(&mut self.term_refs, g, 1)?;
+            add_to_counter(&mut self.graphs, g, 1)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the quad returning if it was in the store
+    fn remove(&mut self, quad: &OxQuad) -> Result<bool, StorageError> {
+        let ids = match self.quad_ids(quad)? {
+            Some(ids) => ids,
+            None => return Ok(false),
+        };
+        if self.spog.remove(QuadIndex::Spog.key(ids))?.is_none() {
+            return Ok(false);
+        }
+        self.posg.remove(QuadIndex::Posg.key(ids))?;
+        self.ospg.remove(QuadIndex::Ospg.key(ids))?;
+        let (s, p, o, g) = ids;
+        if g != DEFAULT_GRAPH_ID {
+            add_to_counter(&mut self.graphs, g, -1)?;
+            self.release_term(g)?;
+        }
+        for id in [s, p, o] {
+            self.release_term(id)?;
+        }
+        Ok(true)
+    }
+
+    /// Removes a place where the term appears, removing the term from the dictionary if it was the last one
+    fn release_term(&mut self, id: u64) -> Result<(), StorageError> {
+        if add_to_counter(&mut self.term_refs, id, -1)? == 0 {
+            let term = self.terms.remove(id)?.map(|term| term.value().to_string());
+            if let Some(term) = term {
+                self.term_ids.remove(term.as_str())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Identifier of a term, which is added to the dictionary if it is new
+    fn term_id(&mut self, term: &str) -> Result<u64, StorageError> {
+        if let Some(id) = self.known_term_id(term)? {
+            return Ok(id);
+        }
+        let id = match self.terms.last()? {
+            Some((id, _)) => id.value() + 1,
+            None => 1,
+        };
+        self.terms.insert(id, term)?;
+        self.term_ids.insert(term, id)?;
+        Ok(id)
+    }
+
+    fn known_term_id(&self, term: &str) -> Result<Option<u64>, StorageError> {
+        Ok(self.term_ids.get(term)?.map(|id| id.value()))
+    }
+
+    /// Identifiers of the terms of a quad, `None` if some of them is not in the dictionary
+    fn quad_ids(&self, quad: &OxQuad) -> Result<Option<QuadKey>, StorageError> {
+        let graph = match graph_term(&quad.graph_name) {
+            Some(graph) => self.known_term_id(&graph)?,
+            None => Some(DEFAULT_GRAPH_ID),
+        };
+        Ok(
+            match (
+                self.known_term_id(&quad.subject.to_string())?,
+                self.known_term_id(&quad.predicate.to_string())?,
+                self.known_term_id(&quad.object.to_string())?,
+                graph,
+            ) {
+                (Some(s), Some(p), Some(o), Some(g)) => Some((s, p, o, g)),
+                _ => None,
+            },
+        )
+    }
+}
+
+/// Adds `delta` to the counter of `id` returning its new value. Counters that reach zero are removed
+fn add_to_counter(
+    table: &mut redb::Table<'_, u64, u64>,
+    id: u64,
+    delta: i64,
+) -> Result<u64, StorageError> {
+    let value = table.get(id)?.map(|value| value.value()).unwrap_or(0);
+    let value = value.saturating_add_signed(delta);
+    if value == 0 {
+        table.remove(id)?;
+    } else {
+        table.insert(id, value)?;
+    }
+    Ok(value)
+}
+
+fn reading_error<E: Display>(path: &str, error: E) -> RdfDataError {
+    RdfDataError::ReadingStore {
+        path: path.to_string(),
+        error: error.to_string(),
+    }
+}
+
+impl SRDFBasic for LocalStore {
+    type IRI = OxNamedNode;
+    type BNode = OxBlankNode;
+    type Literal = OxLiteral;
+    type Subject = OxSubject;
+    type Term = OxTerm;
+    type Err = RdfDataError;
+
+    fn prefixmap(&self) -> Option<PrefixMap> {
+        LocalStore::prefixmap(self).ok()
+    }
+
+    fn subject_as_iri(subject: &Self::Subject) -> Option<Self::IRI> {
+        RdfData::subject_as_iri(subject)
+    }
+
+    fn subject_as_bnode(subject: &Self::Subject) -> Option<Self::BNode> {
+        RdfData::subject_as_bnode(subject)
+    }
+
+    fn subject_is_iri(subject: &Self::Subject) -> bool {
+        RdfData::subject_is_iri(subject)
+    }
+
+    fn subject_is_bnode(subject: &Self::Subject) -> bool {
+        RdfData::subject_is_bnode(subject)
+    }
+
+    fn term_as_iri(object: &Self::Term) -> Option<&Self::IRI> {
+        RdfData::term_as_iri(object)
+    }
+
+    fn term_as_bnode(object: &Self::Term) -> Option<Self::BNode> {
+        RdfData::term_as_bnode(object)
+    }
+
+    fn term_as_literal(object: &Self::Term) -> Option<Self::Literal> {
+        RdfData::term_as_literal(object)
+    }
+
+    fn term_as_object(term: &Self::Term) -> Object {
+        RdfData::term_as_object(term)
+    }
+
+    fn object_as_term(obj: &Object) -> Result<Self::Term, Self::Err> {
+        RdfData::object_as_term(obj)
+    }
+
+    fn term_is_iri(object: &Self::Term) -> bool {
+        RdfData::term_is_iri(object)
+    }
+
+    fn term_is_bnode(object: &Self::Term) -> bool {
+        RdfData::term_is_bnode(object)
+    }
+
+    fn term_is_literal(object: &Self::Term) -> bool {
+        RdfData::term_is_literal(object)
+    }
+
+    fn term_as_subject(object: &Self::Term) -> Option<Self::Subject> {
+        RdfData::term_as_subject(object)
+    }
+
+    fn subject_as_term(subject: &Self::Subject) -> Self::Term {
+        RdfData::subject_as_term(subject)
+    }
+
+    fn lexical_form(literal: &Self::Literal) -> &str {
+        RdfData::lexical_form(literal)
+    }
+
+    fn lang(literal: &Self::Literal) -> Option<String> {
+        RdfData::lang(literal)
+    }
+
+    fn datatype(literal: &Self::Literal) -> Self::IRI {
+        RdfData::datatype(literal)
+    }
+
+    fn iri_s2iri(iri_s: &IriS) -> Self::IRI {
+        RdfData::iri_s2iri(iri_s)
+    }
+
+    fn term_s2term(term: &OxTerm) -> Self::Term {
+        term.clone()
+    }
+
+    fn bnode_id2bnode(id: &str) -> Self::BNode {
+        RdfData::bnode_id2bnode(id)
+    }
+
+    fn iri_as_term(iri: Self::IRI) -> Self::Term {
+        RdfData::iri_as_term(iri)
+    }
+
+    fn iri_as_subject(iri: Self::IRI) -> Self::Subject {
+        RdfData::iri_as_subject(iri)
+    }
+
+    fn bnode_as_term(bnode: Self::BNode) -> Self::Term {
+        RdfData::bnode_as_term(bnode)
+    }
+
+    fn bnode_as_subject(bnode: Self::BNode) -> Self::Subject {
+        RdfData::bnode_as_subject(bnode)
+    }
+
+    fn iri2iri_s(iri: &Self::IRI) -> IriS {
+        RdfData::iri2iri_s(iri)
+    }
+
+    fn qualify_iri(&self, iri: &Self::IRI) -> String {
+        let iri_s = IriS::from_named_node(iri);
+        match LocalStore::prefixmap(self) {
+            Ok(prefixmap) => prefixmap.qualify(&iri_s),
+            Err(_) => format!("<{iri}>"),
+        }
+    }
+
+    fn qualify_subject(&self, subj: &Self::Subject) -> String {
+        match subj {
+            OxSubject::NamedNode(n) => self.qualify_iri(n),
+            _ => subj.to_string(),
+        }
+    }
+
+    fn qualify_term(&self, term: &Self::Term) -> String {
+        match term {
+            OxTerm::NamedNode(n) => self.qualify_iri(n),
+            _ => term.to_string(),
+        }
+    }
+
+    fn resolve_prefix_local(&self, prefix: &str, local: &str) -> Result<IriS, PrefixMapError> {
+        let prefixmap = LocalStore::prefixmap(self).unwrap_or_default();
+        prefixmap.resolve_prefix_local(prefix, local)
+    }
+}
+
+impl SRDF for LocalStore {
+    fn predicates_for_subject(
+        &self,
+        subject: &Self::Subject,
+    ) -> Result<HashSet<Self::IRI>, Self::Err> {
+        let subject = OxTerm::from(subject.clone());
+        let triples = self.triples(Some(&subject), None, None)?;
+        Ok(triples.into_iter().map(|t| t.predicate).collect())
+    }
+
+    fn objects_for_subject_predicate(
+        &self,
+        subject: &Self::Subject,
+        pred: &Self::IRI,
+    ) -> Result<HashSet<Self::Term>, Self::Err> {
+        let subject = OxTerm::from(subject.clone());
+        let pred = OxTerm::from(pred.clone());
+        let triples = self.triples(Some(&subject), Some(&pred), None)?;
+        Ok(triples.into_iter().map(|t| t.object).collect())
+    }
+
+    fn subjects_with_predicate_object(
+        &self,
+        pred: &Self::IRI,
+        object: &Self::Term,
+    ) -> Result<HashSet<Self::Subject>, Self::Err> {
+        let pred = OxTerm::from(pred.clone());
+        let triples = self.triples(None, Some(&pred), Some(object))?;
+        Ok(triples.into_iter().map(|t| t.subject).collect())
+    }
+
+    fn triples_with_predicate(
+        &self,
+        pred: &Self::IRI,
+    ) -> Result<Vec<srdf::Triple<Self>>, Self::Err> {
+        let pred = OxTerm::from(pred.clone());
+        let triples = self.triples(None, Some(&pred), None)?;
+        Ok(triples
+            .into_iter()
+            .map(|t| srdf::Triple::new(t.subject, t.predicate, t.object))
+            .collect())
+    }
+
+    fn outgoing_arcs(
+        &self,
+        subject: &Self::Subject,
+    ) -> Result<HashMap<Self::IRI, HashSet<Self::Term>>, Self::Err> {
+        let subject = OxTerm::from(subject.clone());
+        let mut result: HashMap<_, HashSet<_>> = HashMap::new();
+        for triple in self.triples(Some(&subject), None, None)? {
+            result
+                .entry(triple.predicate)
+                .or_default()
+                .insert(triple.object);
+        }
+        Ok(result)
+    }
+
+    fn incoming_arcs(
+        &self,
+        object: &Self::Term,
+    ) -> Result<HashMap<Self::IRI, HashSet<Self::Subject>>, Self::Err> {
+        let mut result: HashMap<_, HashSet<_>> = HashMap::new();
+        for triple in self.triples(None, None, Some(object))? {
+            result
+                .entry(triple.predicate)
+                .or_default()
+                .insert(triple.subject);
+        }
+        Ok(result)
+    }
+
+    fn outgoing_arcs_from_list(
+        &self,
+        subject: &Self::Subject,
+        preds: &[Self::IRI],
+    ) -> Result<(HashMap<Self::IRI, HashSet<Self::Term>>, Vec<Self::IRI>), Self::Err> {
+        let mut result: HashMap<_, HashSet<_>> = HashMap::new();
+        let mut remainder = Vec::new();
+        for (pred, objects) in self.outgoing_arcs(subject)? {
+            if preds.contains(&pred) {
+                result.entry(pred).or_default().extend(objects);
+            } else {
+                remainder.push(pred)
+            }
+        }
+        Ok((result, remainder))
+    }
+
+    fn neighs(
+        &self,
+        node: &Self::Term,
+    ) -> Result<ListOfIriAndTerms<Self::IRI, Self::Term>, Self::Err> {
+        match Self::term_as_subject(node) {
+            None => Ok(Vec::new()),
+            Some(subject) => Ok(self.outgoing_arcs(&subject)?.into_iter().collect()),
+        }
+    }
+}
+
+impl QuerySRDF for LocalStore {
+    fn query_select(&self, query: &str) -> Result<QuerySolutions<LocalStore>, RdfDataError> {
+        self.query_select_with_bindings(query, &[])
+    }
+
+    fn query_ask(&self, query: &str) -> Result<bool, RdfDataError> {
+        self.query_ask_with_bindings(query, &[])
+    }
+
+    fn query_select_with_bindings(
+        &self,
+        query: &str,
+        bindings: &[(VarName, OxTerm)],
+    ) -> Result<QuerySolutions<LocalStore>, RdfDataError> {
+        let mut sols = QuerySolutions::empty();
+        if let QueryResults::Solutions(solutions) = self.execute(query, bindings)? {
+            let mut results = Vec::new();
+            for solution in solutions {
+                let solution = solution?;
+                let variables = solution
+                    .variables()
+                    .iter()
+                    .map(|v| VarName::new(v.as_str()))
+                    .collect();
+                let values = solution.values().to_vec();
+                results.push(QuerySolution::new(variables, values))
+            }
+            sols.extend(results)
+        }
+        Ok(sols)
+    }
+
+    fn query_ask_with_bindings(
+        &self,
+        query: &str,
+        bindings: &[(VarName, OxTerm)],
+    ) -> Result<bool, RdfDataError> {
+        match self.execute(query, bindings)? {
+            QueryResults::Boolean(value) => Ok(value),
+            _ => Ok(false),
+        }
+    }
+}
+
+impl LocalStore {
+    /// Evaluates a query over the selected graphs of the store, substituting the variables of `bindings`
+    fn execute(
+        &self,
+        query: &str,
+        bindings: &[(VarName, OxTerm)],
+    ) -> Result<QueryResults, RdfDataError> {
+        let (query, substitutions) = bound_query(query, bindings)?;
+        Ok(QueryEvaluator::new().execute_with_substituted_variables(
+            self.clone(),
+            &query,
+            substitutions,
+        )?)
+    }
+}
+
+/// The default graph of the SPARQL queries is made of the selected graphs of the store
+impl QueryableDataset for LocalStore {
+    type InternalTerm = OxTerm;
+    type Error = RdfDataError;
+
+    fn internal_quads_for_pattern(
+        &self,
+        subject: Option<&OxTerm>,
+        predicate: Option<&OxTerm>,
+        object: Option<&OxTerm>,
+        graph_name: Option<Option<&OxTerm>>,
+    ) -> Box<dyn Iterator<Item = Result<InternalQuad<Self>, RdfDataError>>> {
+        let quads = match self.quads_for_pattern(subject, predicate, object) {
+            Ok(quads) => quads,
+            Err(e) => return Box::new(std::iter::once(Err(e))),
+        };
+        let store = self.clone();
+        let graph_name = graph_name.map(|name| name.cloned());
+        Box::new(quads.filter_map(move |quad| {
+            let quad = match quad {
+                Ok(quad) => quad,
+                Err(e) => return Some(Err(e)),
+            };
+            let quad_graph = match &quad.graph_name {
+                GraphName::NamedNode(n) => Some(OxTerm::from(n.clone())),
+                GraphName::BlankNode(b) => Some(OxTerm::from(b.clone())),
+                GraphName::DefaultGraph => None,
+            };
+            let graph = match &graph_name {
+                None => quad_graph,
+                Some(None) if store.is_selected(&quad.graph_name) => None,
+                Some(Some(name)) if quad_graph.as_ref() == Some(name) => quad_graph,
+                _ => return None,
+            };
+            Some(Ok(InternalQuad {
+                subject: quad.subject.into(),
+                predicate: quad.predicate.into(),
+                object: quad.object,
+                graph_name: graph,
+            }))
+        }))
+    }
+
+    fn internalize_term(&self, term: OxTerm) -> Result<OxTerm, RdfDataError> {
+        Ok(term)
+    }
+
+    fn externalize_term(&self, term: OxTerm) -> Result<OxTerm, RdfDataError> {
+        Ok(term)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use srdf::{RDFFormat, ReaderMode};
+
+    fn parse(str: &str, format: &RDFFormat) -> SRDFGraph {
+        SRDFGraph::from_str(str, format, None, &ReaderMode::Strict).unwrap()
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rudof_store_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_local_store_load_and_reopen() {
+        let dir = test_dir("reopen");
+        let store = LocalStore::open(&dir).unwrap();
+        let data = r#"prefix : <http://example.org/>
+        :alice :knows :bob .
+        "#;
+        let trig = r#"prefix : <http://example.org/>
+        :g { :bob :name "Bob" }
+        "#;
+        store.add_graph(&parse(data, &RDFFormat::Turtle)).unwrap();
+        let len = store.add_graph(&parse(trig, &RDFFormat::TriG)).unwrap();
+        assert_eq!(len, 2);
+        drop(store);
+
+        let store = LocalStore::open(&dir).unwrap();
+        let graph = store.read_graph().unwrap();
+        assert_eq!(graph.len(), 2);
+        assert!(graph.prefixmap().find("").is_some());
+        let store = store.with_graph_selector(GraphSelector::NamedGraph(iri_s::iri!(
+            "http://example.org/g"
+        )));
+        let bob = LocalStore::iri_s2subject(&iri_s::iri!("http://example.org/bob"));
+        assert_eq!(store.predicates_for_subject(&bob).unwrap().len(), 1);
+        let alice = LocalStore::iri_s2subject(&iri_s::iri!("http://example.org/alice"));
+        assert!(store.predicates_for_subject(&alice).unwrap().is_empty());
+        drop(store);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_local_store_remove_and_clear() {
+        let dir = test_dir("remove");
+        let store = LocalStore::open(&dir).unwrap();
+        let data = r#"prefix : <http://example.org/>
+        :alice :knows :bob, :carol .
+        "#;
+        let removed = r#"prefix : <http://example.org/>
+        :alice :knows :carol, :dave .
+        "#;
+        store.add_graph(&parse(data, &RDFFormat::Turtle)).unwrap();
+        let removal = store
+            .remove_graph(&parse(removed, &RDFFormat::Turtle))
+            .unwrap();
+        assert_eq!(removal.removed, 1);
+        assert_eq!(store.len().unwrap(), 1);
+        store.clear().unwrap();
+        assert!(store.is_empty().unwrap());
+        assert!(store.read_graph().unwrap().is_empty());
+        drop(store);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_local_store_stream_graphs_and_terms() {
+        let dir = test_dir("stream");
+        let store = LocalStore::open(&dir).unwrap();
+        let trig = r#"prefix : <http://example.org/>
+        :alice :knows :bob .
+        :g1 { :bob :name "Bob" ; :address [ :city "Oviedo" ] }
+        :g2 { :carol :name "Carol" }
+        "#;
+        let contexts = HashMap::new();
+        let len = store
+            .load_from_reader(
+                trig.as_bytes(),
+                &RDFFormat::TriG,
+                None,
+                &ReaderMode::Strict,
+                &contexts,
+            )
+            .unwrap();
+        assert_eq!(len, 5);
+        assert!(store.prefixmap().unwrap().find("").is_some());
+        assert_eq!(store.graph_names().unwrap().len(), 2);
+
+        let removed = r#"prefix : <http://example.org/>
+        :g1 { :bob :name "Bob" ; :address [ :city "Oviedo" ] }
+        :g2 { :carol :name "Carol" }
+        "#;
+        let removal = store
+            .remove_from_reader(
+                removed.as_bytes(),
+                &RDFFormat::TriG,
+                None,
+                &ReaderMode::Strict,
+                &contexts,
+            )
+            .unwrap();
+        assert_eq!(
+            removal,
+            Removal {
+                removed: 2,
+                with_blank_nodes: 2
+            }
+        );
+        let graph_names = store.graph_names().unwrap();
+        assert_eq!(graph_names.len(), 1);
+        assert!(
+            graph_names.contains(&GraphName::NamedNode(OxNamedNode::new_unchecked(
+                "http://example.org/g1"
+            )))
+        );
+
+        // The terms that are no longer used are removed from the dictionary
+        let txn = store.db.begin_read().unwrap();
+        let term_ids = txn.open_table(TERM_IDS).unwrap();
+        assert!(term_ids
+            .get("<http://example.org/carol>")
+            .unwrap()
+            .is_none());
+        assert!(term_ids.get("<http://example.org/g2>").unwrap().is_none());
+        assert!(term_ids.get("<http://example.org/bob>").unwrap().is_some());
+        drop(term_ids);
+        drop(txn);
+        drop(store);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_local_store_arcs_and_queries() {
+        let dir = test_dir("queries");
+        let store = LocalStore::open(&dir).unwrap();
+        let data = r#"prefix : <http://example.org/>
+        :alice :knows :bob ; :name "Alice" .
+        :carol :knows :bob ; :address [ :city "Oviedo" ] .
+        "#;
+        store.add_graph(&parse(data, &RDFFormat::Turtle)).unwrap();
+        // Loading the same document again duplicates its blank node
+        let len = store.add_graph(&parse(data, &RDFFormat::Turtle)).unwrap();
+        assert_eq!(len, 7);
+
+        let bob = LocalStore::iri_s2term(&iri_s::iri!("http://example.org/bob"));
+        let knows = LocalStore::iri_s2iri(&iri_s::iri!("http://example.org/knows"));
+        let incoming = store.incoming_arcs(&bob).unwrap();
+        assert_eq!(incoming.get(&knows).unwrap().len(), 2);
+
+        let sols = store
+            .query_select("SELECT ?x WHERE { ?x <http://example.org/knows> ?y }")
+            .unwrap();
+        assert_eq!(sols.count(), 2);
+        assert!(store
+            .query_ask("ASK { ?x <http://example.org/name> \"Alice\" }")
+            .unwrap());
+        drop(store);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_local_store_patterns() {
+        let dir = test_dir("patterns");
+        let store = LocalStore::open(&dir).unwrap();
+        let data = r#"prefix : <http://example.org/>
+        :alice :knows :bob, :carol ; :name "Alice"@en .
+        :bob :knows :carol ; :likes :carol .
+        << :alice :knows :bob >> :since 2020 .
+        "#;
+        store.add_graph(&parse(data, &RDFFormat::Turtle)).unwrap();
+        let iri = |name: &str| {
+            LocalStore::iri_s2iri(&IriS::new_unchecked(&format!("http://example.org/{name}")))
+        };
+        let alice = LocalStore::iri_as_subject(iri("alice"));
+        let carol = LocalStore::iri_as_term(iri("carol"));
+
+        let known = store
+            .objects_for_subject_predicate(&alice, &iri("knows"))
+            .unwrap();
+        assert_eq!(known.len(), 2);
+        let known_by = store
+            .subjects_with_predicate_object(&iri("knows"), &carol)
+            .unwrap();
+        assert_eq!(known_by.len(), 2);
+        let sols = store
+            .query_select(
+                "SELECT ?p WHERE { <http://example.org/bob> ?p <http://example.org/carol> }",
+            )
+            .unwrap();
+        assert_eq!(sols.count(), 2);
+        let unknown = LocalStore::iri_as_term(iri("dave"));
+        assert!(store.incoming_arcs(&unknown).unwrap().is_empty());
+
+        let names = store
+            .objects_for_subject_predicate(&alice, &iri("name"))
+            .unwrap();
+        let name = OxLiteral::new_language_tagged_literal_unchecked("Alice", "en");
+        assert!(names.contains(&OxTerm::from(name)));
+        let since = store.triples_with_predicate(&iri("since")).unwrap();
+        assert_eq!(since.len(), 1);
+        assert!(matches!(since[0].subj(), OxSubject::Triple(_)));
+        drop(store);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,5 +1,7 @@
+mod local_store;
 mod rdf_data;
 mod rdf_data_error;
 
+pub use local_store::*;
 pub use rdf_data::*;
 pub use rdf_data_error::*;
@@ -1,23 +1,31 @@
-use super::RdfDataError;
+use super::{LocalStore, RdfDataError};
 use colored::*;
 use iri_s::IriS;
 use oxigraph::sparql::Query;
+use oxigraph::sparql::QueryOptions;
 use oxigraph::sparql::QueryResults;
 use oxigraph::store::Store;
 use oxrdf::{
-    BlankNode as OxBlankNode, Literal as OxLiteral, NamedNode as OxNamedNode, Subject as OxSubject,
-    Term as OxTerm,
+    BlankNode as OxBlankNode, GraphName, Literal as OxLiteral, NamedNode as OxNamedNode,
+    Subject as OxSubject, Term as OxTerm, Triple as OxTriple, Variable,
 };
 use oxrdfio::RdfFormat;
 use prefixmap::IriRef;
 use prefixmap::PrefixMap;
 use rust_decimal::Decimal;
 use sparesults::QuerySolution as SparQuerySolution;
+use spargebra::algebra::GraphPattern;
+use spargebra::Query as SparQuery;
 use srdf::lang::Lang;
 use srdf::literal::Literal;
 use srdf::numeric_literal::NumericLiteral;
+use srdf::CacheConfig;
+use srdf::CacheStats;
+use srdf::CachedSRDF;
 use srdf::FocusRDF;
+use srdf::GraphSelector;
 use srdf::ListOfIriAndTerms;
+use srdf::NodeArcs;
 use srdf::Object;
 use srdf::QuerySRDF;
 use srdf::QuerySolution;
@@ -37,22 +45,35 @@ use std::fmt::Debug;
 use std::hash::Hash;
 use std::io;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 
-/// Generic abstraction that represents RDF Data which can be  behind SPARQL endpoints or an in-memory graph or both
-/// The triples in RdfData are taken as the union of the triples of the endpoints and the in-memory graph
+/// Generic abstraction that represents RDF Data which can be  behind SPARQL endpoints, an in-memory graph or a local store
+/// The triples in RdfData are taken as the union of the triples of the endpoints, the in-memory graph and the local store
 #[derive(Clone)]
 pub struct RdfData {
     /// Current focus node used when parsing
     focus: Option<OxTerm>,
 
-    /// List of SPARQL endpoints
-    endpoints: Vec<SRDFSparql>,
+    /// List of SPARQL endpoints, which can cache the neighbourhoods of the nodes they return
+    endpoints: Vec<CachedSRDF<SRDFSparql>>,
 
     /// In-memory graph
     graph: Option<SRDFGraph>,
 
     /// In-memory Store used to access the graph using SPARQL queries
     store: Option<Store>,
+
+    /// Local store whose triples are read from disk when they are needed
+    local_store: Option<LocalStore>,
+
+    /// Local documents of JSON-LD remote contexts used when parsing JSON-LD
+    jsonld_contexts: HashMap<String, String>,
+
+    /// Graphs of the in-memory dataset and the endpoints that are used to answer queries
+    graph_selector: GraphSelector,
+
+    /// Arcs that have been read, if they are recorded
+    read_arcs: Option<Arc<Mutex<NodeArcs>>>,
 }
 
 impl Debug for RdfData {
@@ -60,6 +81,7 @@ impl Debug for RdfData {
         f.debug_struct("RdfData")
             .field("endpoints", &self.endpoints)
             .field("graph", &self.graph)
+            .field("local_store", &self.local_store)
             .finish()
     }
 }
@@ -70,7 +92,11 @@ impl RdfData {
             endpoints: Vec::new(),
             graph: None,
             store: None,
+            local_store: None,
             focus: None,
+            jsonld_contexts: HashMap::new(),
+            graph_selector: GraphSelector::default(),
+            read_arcs: None,
         }
     }
 
@@ -92,18 +118,122 @@ impl RdfData {
     pub fn from_graph(graph: SRDFGraph) -> Result<RdfData, RdfDataError> {
         let store = Store::new()?;
         store.bulk_loader().load_quads(graph.quads())?;
+        let graph_selector = graph.graph_selector().clone();
         Ok(RdfData {
             endpoints: Vec::new(),
             graph: Some(graph),
             store: Some(store),
+            local_store: None,
             focus: None,
+            jsonld_contexts: HashMap::new(),
+            graph_selector,
+            read_arcs: None,
         })
     }
 
-    // Cleans the values of endpoints and graph
+    /// Creates an RdfData from an in-memory RDF Graph without initializing the Store,
+    /// which can be created later with [`RdfData::check_store`] if SPARQL queries are needed
+    pub fn from_graph_without_store(graph: SRDFGraph) -> RdfData {
+        let graph_selector = graph.graph_selector().clone();
+        RdfData {
+            endpoints: Vec::new(),
+            graph: Some(graph),
+            store: None,
+            local_store: None,
+            focus: None,
+            jsonld_contexts: HashMap::new(),
+            graph_selector,
+            read_arcs: None,
+        }
+    }
+
+    /// Creates an RdfData from a local store
+    pub fn from_local_store(local_store: LocalStore) -> RdfData {
+        let graph_selector = local_store.graph_selector().clone();
+        RdfData {
+            local_store: Some(local_store),
+            graph_selector,
+            ..RdfData::new()
+        }
+    }
+
+    // Cleans the values of endpoints, graph and local store
     pub fn clean_all(&mut self) {
         self.endpoints = Vec::new();
-        self.graph = None
+        self.graph = None;
+        self.local_store = None
+    }
+
+    /// Get the local store
+    pub fn local_store(&self) -> Option<&LocalStore> {
+        self.local_store.as_ref()
+    }
+
+    /// Records the nodes whose arcs are read, which can be obtained with [`RdfData::read_arcs`].
+    /// The clones of the RdfData share the record
+    pub fn with_read_log(mut self) -> Self {
+        self.read_arcs = Some(Arc::new(Mutex::new(NodeArcs::new())));
+        self
+    }
+
+    /// Arcs that have been read since the record was enabled with [`RdfData::with_read_log`].
+    /// The answers of SPARQL queries are not recorded
+    pub fn read_arcs(&self) -> NodeArcs {
+        match &self.read_arcs {
+            Some(read_arcs) => read_arcs
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone(),
+            None => NodeArcs::new(),
+        }
+    }
+
+    /// Arcs that have been read since the last call, or since the record was enabled in the first call.
+    /// It is used to know the arcs that each validated node reads
+    pub fn take_read_arcs(&self) -> NodeArcs {
+        match &self.read_arcs {
+            Some(read_arcs) => std::mem::take(
+                &mut *read_arcs
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()),
+            ),
+            None => NodeArcs::new(),
+        }
+    }
+
+    fn log_read<F: FnOnce(&mut NodeArcs)>(&self, log: F) {
+        if let Some(read_arcs) = &self.read_arcs {
+            log(&mut read_arcs
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()))
+        }
+    }
+
+    fn log_outgoing(&self, subject: &OxSubject) {
+        self.log_read(|arcs| {
+            arcs.subjects.insert(subject.clone());
+        })
+    }
+
+    fn log_incoming(&self, object: &OxTerm) {
+        self.log_read(|arcs| {
+            arcs.objects.insert(object.clone());
+        })
+    }
+
+    /// Adds triples to the default graph of the in-memory graph, and to the store if it has been initialized
+    pub fn add_triples<I: IntoIterator<Item = OxTriple>>(
+        &mut self,
+        triples: I,
+    ) -> Result<(), RdfDataError> {
+        let graph = self.graph.get_or_insert_with(SRDFGraph::new);
+        for triple in triples {
+            graph.add_triple(&triple.subject, &triple.predicate, &triple.object)?;
+            if let Some(store) = &self.store {
+                store.insert(&triple.in_graph(GraphName::DefaultGraph))?;
+            }
+        }
+        Ok(())
     }
 
     /// Get the in-memory graph
@@ -124,12 +254,17 @@ impl RdfData {
         base: Option<&str>,
         reader_mode: &ReaderMode,
     ) -> Result<(), RdfDataError> {
+        // the store is rebuilt from the merged graph the next time it is checked
+        self.store = None;
         match &mut self.graph {
             Some(ref mut graph) => graph
                 .merge_from_reader(read, format, base, reader_mode)
                 .map_err(|e| RdfDataError::SRDFGraphError { err: e }),
             None => {
-                let mut graph = SRDFGraph::new();
+                let mut graph = SRDFGraph::new().with_graph_selector(self.graph_selector.clone());
+                for (iri, document) in self.jsonld_contexts.iter() {
+                    graph.add_jsonld_context(iri, document);
+                }
                 graph
                     .merge_from_reader(read, format, base, reader_mode)
                     .map_err(|e| RdfDataError::SRDFGraphError { err: e })?;
@@ -139,28 +274,169 @@ impl RdfData {
         }
     }
 
+    /// Adds a local document for the JSON-LD remote context with that IRI
+    pub fn add_jsonld_context(&mut self, iri: &str, document: &str) {
+        self.jsonld_contexts
+            .insert(iri.to_string(), document.to_string());
+        if let Some(ref mut graph) = &mut self.graph {
+            graph.add_jsonld_context(iri, document)
+        }
+    }
+
     /// Creates an RdfData from an endpoint
     pub fn from_endpoint(endpoint: SRDFSparql) -> RdfData {
         RdfData {
-            endpoints: vec![endpoint],
+            endpoints: vec![CachedSRDF::without_cache(endpoint)],
             graph: None,
             store: None,
+            local_store: None,
             focus: None,
+            jsonld_contexts: HashMap::new(),
+            graph_selector: GraphSelector::default(),
+            read_arcs: None,
         }
     }
 
     /// Adds a new endpoint to the list of endpoints
-    pub fn add_endpoint(&mut self, endpoint: SRDFSparql) {
+    pub fn add_endpoint(&mut self, mut endpoint: SRDFSparql) {
         // TODO: Ensure that there are no repeated endpoints
-        self.endpoints.push(endpoint);
+        if let GraphSelector::NamedGraph(name) = &self.graph_selector {
+            endpoint.set_graph(Some(name.clone()))
+        }
+        self.endpoints.push(CachedSRDF::without_cache(endpoint));
     }
 
-    /// Gets the PrefixMap from the in-memory graph
-    pub fn prefixmap_in_memory(&self) -> PrefixMap {
-        self.graph
+    /// Adds a new endpoint whose answers are cached following `config`.
+    /// If the configuration has a directory, the cache is persisted in a file named after the endpoint and its graph,
+    /// and the entries it contains are loaded unless they were saved for another endpoint, have expired or `refresh` is set
+    pub fn add_endpoint_with_cache(
+        &mut self,
+        mut endpoint: SRDFSparql,
+        config: &CacheConfig,
+    ) -> Result<(), RdfDataError> {
+        if let GraphSelector::NamedGraph(name) = &self.graph_selector {
+            endpoint.set_graph(Some(name.clone()))
+        }
+        let key = endpoint_cache_key(&endpoint);
+        let path = config
+            .dir
             .as_ref()
-            .map(|g| g.prefixmap())
-            .unwrap_or_default()
+            .map(|dir| dir.join(endpoint_cache_file_name(&key)));
+        let mut cached = CachedSRDF::new(endpoint, config.capacity());
+        if let Some(path) = path {
+            cached = cached.with_persistence(&path, &key);
+            if let Some(ttl) = config.ttl() {
+                cached = cached.with_ttl(ttl);
+            }
+            if !config.refresh() {
+                cached.load().map_err(|e| RdfDataError::EndpointCache {
+                    error: e.to_string(),
+                })?;
+            }
+        }
+        self.endpoints.push(cached);
+        Ok(())
+    }
+
+    /// Fetches in advance the outgoing arcs of the subjects from the endpoints that have a cache
+    pub fn prefetch_neighbourhoods(&self, subjects: &[OxSubject]) -> Result<(), RdfDataError> {
+        for endpoint in &self.endpoints {
+            endpoint.prefetch(subjects)?;
+        }
+        Ok(())
+    }
+
+    /// Statistics of the caches of the endpoints, `None` if no endpoint has a cache
+    pub fn endpoint_cache_stats(&self) -> Option<CacheStats> {
+        self.endpoints
+            .iter()
+            .filter(|endpoint| endpoint.is_enabled())
+            .map(|endpoint| endpoint.stats())
+            .reduce(|mut total, stats| {
+                total += stats;
+                total
+            })
+    }
+
+    /// Removes the entries of the caches of the endpoints and the files where they are persisted
+    pub fn clear_endpoint_caches(&self) -> Result<(), RdfDataError> {
+        for endpoint in &self.endpoints {
+            endpoint
+                .clear_persisted()
+                .map_err(|e| RdfDataError::EndpointCache {
+                    error: e.to_string(),
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Writes the caches of the endpoints that are persisted
+    pub fn save_endpoint_caches(&self) -> Result<(), RdfDataError> {
+        for endpoint in &self.endpoints {
+            endpoint.save().map_err(|e| RdfDataError::EndpointCache {
+                error: e.to_string(),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Graphs that are used to answer queries
+    pub fn graph_selector(&self) -> &GraphSelector {
+        &self.graph_selector
+    }
+
+    /// Selects the graphs used to answer queries in the in-memory dataset, the local store and the endpoints.
+    /// A named graph is queried in the endpoints with `GRAPH`,
+    /// while the default graph and the union graph are whatever the endpoints take as default graph
+    pub fn set_graph_selector(&mut self, graph_selector: GraphSelector) {
+        if let Some(graph) = &mut self.graph {
+            graph.set_graph_selector(graph_selector.clone())
+        }
+        if let Some(local_store) = &mut self.local_store {
+            local_store.set_graph_selector(graph_selector.clone())
+        }
+        let endpoint_graph = match &graph_selector {
+            GraphSelector::NamedGraph(name) => Some(name.clone()),
+            GraphSelector::DefaultGraph | GraphSelector::Union => None,
+        };
+        for endpoint in self.endpoints.iter_mut() {
+            if endpoint.inner().graph() != endpoint_graph.as_ref() {
+                endpoint.inner_mut().set_graph(endpoint_graph.clone())
+            }
+        }
+        self.graph_selector = graph_selector;
+    }
+
+    pub fn with_graph_selector(mut self, graph_selector: GraphSelector) -> Self {
+        self.set_graph_selector(graph_selector);
+        self
+    }
+
+    /// Parses a query to be run in the in-memory store taking the selected graphs as default graph
+    fn store_query(&self, query: SparQuery) -> Query {
+        let mut query = Query::from(query);
+        match &self.graph_selector {
+            GraphSelector::DefaultGraph => {}
+            GraphSelector::NamedGraph(name) => query
+                .dataset_mut()
+                .set_default_graph(vec![OxNamedNode::new_unchecked(name.as_str()).into()]),
+            GraphSelector::Union => query.dataset_mut().set_default_graph_as_union(),
+        }
+        query
+    }
+
+    /// Gets the PrefixMap from the in-memory graph or the local store
+    pub fn prefixmap_in_memory(&self) -> PrefixMap {
+        self.prefixmap().unwrap_or_default()
+    }
+
+    /// Prefixes of the in-memory graph, or of the local store if there is no graph
+    fn local_prefixmap(&self) -> Option<PrefixMap> {
+        match (&self.graph, &self.local_store) {
+            (Some(graph), _) => Some(graph.prefixmap()),
+            (None, Some(local_store)) => local_store.prefixmap().ok(),
+            (None, None) => None,
+        }
     }
 
     pub fn show_blanknode(&self, bn: &OxBlankNode) -> String {
@@ -173,6 +449,15 @@ impl RdfData {
         format!("{}", str.red())
     }
 
+    fn qualify_triple(&self, triple: &OxTriple) -> String {
+        format!(
+            "<< {} {} {} >>",
+            self.qualify_subject(&triple.subject),
+            self.qualify_iri(&triple.predicate),
+            self.qualify_term(&triple.object)
+        )
+    }
+
     pub fn serialize<W: io::Write>(
         &self,
         format: &RDFFormat,
@@ -186,8 +471,17 @@ impl RdfData {
                     error: format!("{e}"),
                 })?
         }
+        if let Some(local_store) = &self.local_store {
+            local_store
+                .read_graph()?
+                .serialize(format, writer)
+                .map_err(|e| RdfDataError::Serializing {
+                    format: *format,
+                    error: format!("{e}"),
+                })?
+        }
         for e in self.endpoints.iter() {
-            writeln!(writer, "Endpoint {}", e.iri())?
+            writeln!(writer, "Endpoint {}", e.inner().iri())?
         }
         Ok(())
     }
@@ -208,7 +502,7 @@ impl SRDFBasic for RdfData {
     type Err = RdfDataError;
 
     fn prefixmap(&self) -> std::option::Option<PrefixMap> {
-        self.graph.as_ref().map(|g| g.prefixmap())
+        self.local_prefixmap()
     }
 
     fn subject_as_iri(subject: &Self::Subject) -> Option<Self::IRI> {
@@ -286,12 +580,12 @@ impl SRDFBasic for RdfData {
             }
             OxTerm::NamedNode(iri) => Object::Iri(Self::iri2iri_s(iri)),
             // #[cfg(feature = "rdf-star")]
-            OxTerm::Triple(_) => unimplemented!(),
+            OxTerm::Triple(_) => SRDFGraph::term_as_object(term),
         }
     }
 
-    fn object_as_term(obj: &srdf::Object) -> Self::Term {
-        match obj {
+    fn object_as_term(obj: &srdf::Object) -> Result<Self::Term, Self::Err> {
+        let term = match obj {
             Object::Iri(iri) => Self::iri_s2term(iri),
             Object::BlankNode(bn) => Self::bnode_id2term(bn),
             Object::Literal(lit) => {
@@ -322,7 +616,9 @@ impl SRDFBasic for RdfData {
                 };
                 OxTerm::Literal(literal)
             }
-        }
+            Object::Triple { .. } => SRDFGraph::object_as_term(obj)?,
+        };
+        Ok(term)
     }
 
     fn term_is_iri(object: &Self::Term) -> bool {
@@ -341,6 +637,8 @@ impl SRDFBasic for RdfData {
         match object {
             OxTerm::NamedNode(n) => Some(OxSubject::NamedNode(n.clone())),
             OxTerm::BlankNode(b) => Some(OxSubject::BlankNode(b.clone())),
+            // #[cfg(feature = "rdf-star")]
+            OxTerm::Triple(t) => Some(OxSubject::Triple(t.clone())),
             _ => None,
         }
     }
@@ -350,7 +648,7 @@ impl SRDFBasic for RdfData {
             OxSubject::NamedNode(n) => OxTerm::NamedNode(n.clone()),
             OxSubject::BlankNode(b) => OxTerm::BlankNode(b.clone()),
             // #[cfg(feature = "rdf-star")]
-            OxSubject::Triple(_) => unimplemented!(),
+            OxSubject::Triple(t) => OxTerm::Triple(t.clone()),
         }
     }
 
@@ -400,11 +698,11 @@ impl SRDFBasic for RdfData {
 
     fn qualify_iri(&self, node: &Self::IRI) -> String {
         let iri = IriS::from_str(node.as_str()).unwrap();
-        if let Some(graph) = &self.graph {
-            graph.prefixmap().qualify(&iri)
+        if let Some(prefixmap) = self.local_prefixmap() {
+            prefixmap.qualify(&iri)
         } else {
             for e in self.endpoints.iter() {
-                if let Some(qualified) = e.prefixmap().qualify_optional(&iri) {
+                if let Some(qualified) = e.inner().prefixmap().qualify_optional(&iri) {
                     return qualified;
                 }
             }
@@ -417,7 +715,7 @@ impl SRDFBasic for RdfData {
             OxSubject::BlankNode(bn) => self.show_blanknode(bn),
             OxSubject::NamedNode(n) => self.qualify_iri(n),
             // #[cfg(feature = "rdf-star")]
-            OxSubject::Triple(_) => unimplemented!(),
+            OxSubject::Triple(triple) => self.qualify_triple(triple),
         }
     }
 
@@ -427,7 +725,7 @@ impl SRDFBasic for RdfData {
             OxTerm::Literal(lit) => self.show_literal(lit),
             OxTerm::NamedNode(n) => self.qualify_iri(n),
             // #[cfg(feature = "rdf-star")]
-            OxTerm::Triple(_) => unimplemented!(),
+            OxTerm::Triple(triple) => self.qualify_triple(triple),
         }
     }
 
@@ -436,12 +734,12 @@ impl SRDFBasic for RdfData {
         prefix: &str,
         local: &str,
     ) -> Result<IriS, prefixmap::PrefixMapError> {
-        if let Some(graph) = self.graph() {
-            let iri = graph.prefixmap().resolve_prefix_local(prefix, local)?;
+        if let Some(prefixmap) = self.local_prefixmap() {
+            let iri = prefixmap.resolve_prefix_local(prefix, local)?;
             Ok(iri.clone())
         } else {
             for e in self.endpoints.iter() {
-                if let Ok(iri) = e.prefixmap().resolve_prefix_local(prefix, local) {
+                if let Ok(iri) = e.inner().prefixmap().resolve_prefix_local(prefix, local) {
                     return Ok(iri.clone());
                 }
             }
@@ -455,18 +753,50 @@ impl SRDFBasic for RdfData {
 
 impl QuerySRDF for RdfData {
     fn query_select(&self, query_str: &str) -> Result<QuerySolutions<RdfData>, RdfDataError>
+    where
+        Self: Sized,
+    {
+        self.query_select_with_bindings(query_str, &[])
+    }
+
+    fn query_ask(&self, query_str: &str) -> Result<bool, Self::Err> {
+        self.query_ask_with_bindings(query_str, &[])
+    }
+
+    /// The variables are substituted in the in-memory store and the local store, where the blank nodes
+    /// of the data can be bound, and pre-bound with `VALUES` in the endpoints
+    fn query_select_with_bindings(
+        &self,
+        query_str: &str,
+        bindings: &[(VarName, OxTerm)],
+    ) -> Result<QuerySolutions<RdfData>, RdfDataError>
     where
         Self: Sized,
     {
         let mut sols: QuerySolutions<RdfData> = QuerySolutions::empty();
-        let query = Query::parse(query_str, None)?;
         if let Some(store) = &self.store {
-            let new_sol = store.query(query)?;
+            let (query, substitutions) = bound_query(query_str, bindings)?;
+            let new_sol = store.query_opt_with_substituted_variables(
+                self.store_query(query),
+                QueryOptions::default(),
+                substitutions,
+            )?;
             let sol = cnv_query_results(new_sol)?;
             sols.extend(sol)
         }
+        if let Some(local_store) = &self.local_store {
+            let new_sols = local_store.query_select_with_bindings(query_str, bindings)?;
+            sols.extend(
+                new_sols
+                    .iter()
+                    .map(|sol| sol.convert(|t| t.clone()))
+                    .collect(),
+            )
+        }
         for endpoint in &self.endpoints {
-            let new_sols = endpoint.query_select(query_str)?;
+            let new_sols = endpoint
+                .inner()
+                .query_select_with_bindings(query_str, bindings)?;
             let new_sols_converted: Vec<QuerySolution<RdfData>> =
                 new_sols.iter().map(cnv_sol).collect();
             sols.extend(new_sols_converted)
@@ -474,11 +804,108 @@ impl QuerySRDF for RdfData {
         Ok(sols)
     }
 
-    fn query_ask(&self, _query: &str) -> Result<bool, Self::Err> {
-        todo!()
+    fn query_ask_with_bindings(
+        &self,
+        query_str: &str,
+        bindings: &[(VarName, OxTerm)],
+    ) -> Result<bool, Self::Err> {
+        if let Some(store) = &self.store {
+            let (query, substitutions) = bound_query(query_str, bindings)?;
+            if let QueryResults::Boolean(true) = store.query_opt_with_substituted_variables(
+                self.store_query(query),
+                QueryOptions::default(),
+                substitutions,
+            )? {
+                return Ok(true);
+            }
+        }
+        if let Some(local_store) = &self.local_store {
+            if local_store.query_ask_with_bindings(query_str, bindings)? {
+                return Ok(true);
+            }
+        }
+        for endpoint in &self.endpoints {
+            if endpoint
+                .inner()
+                .query_ask_with_bindings(query_str, bindings)?
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
     }
 }
 
+/// Parses a query whose variables in `bindings` are pre-bound by substitution.
+/// Only the projected variables of a query can be substituted, so the bound variables are added to the projection
+/// of SELECT queries and ASK queries are projected on them, which doesn't change their solutions
+/// because the bound variables have a single value
+pub(crate) fn bound_query(
+    query_str: &str,
+    bindings: &[(VarName, OxTerm)],
+) -> Result<(SparQuery, Vec<(Variable, OxTerm)>), RdfDataError> {
+    let mut query = SparQuery::parse(query_str, None)?;
+    let substitutions: Vec<(Variable, OxTerm)> = bindings
+        .iter()
+        .map(|(var, term)| (Variable::new_unchecked(var.as_str()), term.clone()))
+        .collect();
+    if !substitutions.is_empty() {
+        let variables: Vec<Variable> = substitutions.iter().map(|(var, _)| var.clone()).collect();
+        match &mut query {
+            SparQuery::Select { pattern, .. } => project_variables(pattern, &variables),
+            SparQuery::Ask { pattern, .. } => wrap_projection(pattern, &variables),
+            SparQuery::Construct { .. } | SparQuery::Describe { .. } => {}
+        }
+    }
+    Ok((query, substitutions))
+}
+
+/// Adds `variables` to the projection of a SELECT query, which can be under solution modifiers
+fn project_variables(pattern: &mut GraphPattern, variables: &[Variable]) {
+    match pattern {
+        GraphPattern::Project {
+            variables: projected,
+            ..
+        } => {
+            for var in variables {
+                if !projected.contains(var) {
+                    projected.push(var.clone())
+                }
+            }
+        }
+        GraphPattern::Distinct { inner }
+        | GraphPattern::Reduced { inner }
+        | GraphPattern::Slice { inner, .. }
+        | GraphPattern::OrderBy { inner, .. } => project_variables(inner, variables),
+        _ => wrap_projection(pattern, variables),
+    }
+}
+
+/// Projects a pattern on its variables and `variables`
+fn wrap_projection(pattern: &mut GraphPattern, variables: &[Variable]) {
+    let mut projected = Vec::new();
+    pattern.on_in_scope_variable(|var| {
+        if !projected.contains(var) {
+            projected.push(var.clone())
+        }
+    });
+    for var in variables {
+        if !projected.contains(var) {
+            projected.push(var.clone())
+        }
+    }
+    let inner = std::mem::replace(
+        pattern,
+        GraphPattern::Bgp {
+            patterns: Vec::new(),
+        },
+    );
+    *pattern = GraphPattern::Project {
+        inner: Box::new(inner),
+        variables: projected,
+    };
+}
+
 fn cnv_sol(sol: &QuerySolution<SRDFSparql>) -> QuerySolution<RdfData> {
     sol.convert(|t| t.clone())
 }
@@ -518,6 +945,9 @@ fn _cnv_rdf_format(rdf_format: RDFFormat) -> RdfFormat {
         RDFFormat::TriG => RdfFormat::TriG,
         RDFFormat::N3 => RdfFormat::N3,
         RDFFormat::NQuads => RdfFormat::NQuads,
+        RDFFormat::JsonLd => RdfFormat::JsonLd {
+            profile: oxrdfio::JsonLdProfileSet::empty(),
+        },
     }
 }
 
@@ -536,9 +966,20 @@ fn cnv_decimal(_d: &Decimal) -> oxsdatatypes::Decimal {
 impl SRDF for RdfData {
     fn predicates_for_subject(
         &self,
-        _subject: &Self::Subject,
+        subject: &Self::Subject,
     ) -> Result<std::collections::HashSet<Self::IRI>, Self::Err> {
-        todo!()
+        self.log_outgoing(subject);
+        let mut result = HashSet::new();
+        if let Some(graph) = &self.graph {
+            result.extend(graph.predicates_for_subject(subject)?)
+        }
+        if let Some(local_store) = &self.local_store {
+            result.extend(local_store.predicates_for_subject(subject)?)
+        }
+        for e in &self.endpoints {
+            result.extend(e.predicates_for_subject(subject)?)
+        }
+        Ok(result)
     }
 
     fn objects_for_subject_predicate(
@@ -546,11 +987,16 @@ impl SRDF for RdfData {
         subject: &Self::Subject,
         pred: &Self::IRI,
     ) -> Result<std::collections::HashSet<Self::Term>, Self::Err> {
+        self.log_outgoing(subject);
         let mut result = HashSet::new();
         if let Some(graph) = &self.graph {
             let os = graph.objects_for_subject_predicate(subject, pred)?;
             result.extend(os)
         }
+        if let Some(local_store) = &self.local_store {
+            let os = local_store.objects_for_subject_predicate(subject, pred)?;
+            result.extend(os)
+        }
         for e in &self.endpoints {
             let os = e.objects_for_subject_predicate(subject, pred)?;
             result.extend(os)
@@ -563,11 +1009,19 @@ impl SRDF for RdfData {
         pred: &Self::IRI,
         object: &Self::Term,
     ) -> Result<std::collections::HashSet<Self::Subject>, Self::Err> {
+        self.log_read(|arcs| {
+            arcs.predicate_objects
+                .insert((pred.clone(), object.clone()));
+        });
         let mut result = HashSet::new();
         if let Some(graph) = &self.graph {
             let s = graph.subjects_with_predicate_object(pred, object)?;
             result.extend(s);
         }
+        if let Some(local_store) = &self.local_store {
+            let s = local_store.subjects_with_predicate_object(pred, object)?;
+            result.extend(s);
+        }
         for e in self.endpoints.iter() {
             let s = e.subjects_with_predicate_object(pred, object)?;
             result.extend(s)
@@ -579,12 +1033,20 @@ impl SRDF for RdfData {
         &self,
         pred: &Self::IRI,
     ) -> Result<Vec<srdf::Triple<Self>>, Self::Err> {
+        self.log_read(|arcs| {
+            arcs.predicates.insert(pred.clone());
+        });
         let mut result = Vec::new();
         if let Some(graph) = &self.graph {
             let s = graph.triples_with_predicate(pred)?;
             let t: Vec<srdf::Triple<RdfData>> = s.into_iter().map(|s| s.cnv::<RdfData>()).collect();
             result.extend(t)
         }
+        if let Some(local_store) = &self.local_store {
+            let s = local_store.triples_with_predicate(pred)?;
+            let t: Vec<srdf::Triple<RdfData>> = s.into_iter().map(|s| s.cnv::<RdfData>()).collect();
+            result.extend(t)
+        }
         for e in self.endpoints.iter() {
             let s = e.triples_with_predicate(pred)?;
             let t: Vec<srdf::Triple<RdfData>> = s.into_iter().map(|s| s.cnv::<RdfData>()).collect();
@@ -597,23 +1059,39 @@ impl SRDF for RdfData {
         &self,
         subject: &Self::Subject,
     ) -> Result<HashMap<Self::IRI, HashSet<Self::Term>>, Self::Err> {
+        self.log_outgoing(subject);
         let mut result = HashMap::new();
         if let Some(graph) = &self.graph {
             let arcs = graph.outgoing_arcs(subject)?;
-            result.extend(arcs)
+            merge_arcs(&mut result, arcs)
+        }
+        if let Some(local_store) = &self.local_store {
+            let arcs = local_store.outgoing_arcs(subject)?;
+            merge_arcs(&mut result, arcs)
         }
         for e in &self.endpoints {
             let arcs = e.outgoing_arcs(subject)?;
-            result.extend(arcs)
+            merge_arcs(&mut result, arcs)
         }
         Ok(result)
     }
 
     fn incoming_arcs(
         &self,
-        _object: &Self::Term,
+        object: &Self::Term,
     ) -> Result<HashMap<Self::IRI, HashSet<Self::Subject>>, Self::Err> {
-        todo!()
+        self.log_incoming(object);
+        let mut result = HashMap::new();
+        if let Some(graph) = &self.graph {
+            merge_arcs(&mut result, graph.incoming_arcs(object)?)
+        }
+        if let Some(local_store) = &self.local_store {
+            merge_arcs(&mut result, local_store.incoming_arcs(object)?)
+        }
+        for e in &self.endpoints {
+            merge_arcs(&mut result, e.incoming_arcs(object)?)
+        }
+        Ok(result)
     }
 
     fn outgoing_arcs_from_list(
@@ -621,10 +1099,15 @@ impl SRDF for RdfData {
         subject: &Self::Subject,
         preds: &[Self::IRI],
     ) -> Result<(HashMap<Self::IRI, HashSet<Self::Term>>, Vec<Self::IRI>), Self::Err> {
+        self.log_outgoing(subject);
         let mut result = (HashMap::new(), Vec::new());
         if let Some(graph) = &self.graph {
             merge_outgoing_arcs(&mut result, graph.outgoing_arcs_from_list(subject, preds)?);
         }
+        if let Some(local_store) = &self.local_store {
+            let next = local_store.outgoing_arcs_from_list(subject, preds)?;
+            merge_outgoing_arcs(&mut result, next)
+        }
         for endpoint in &self.endpoints {
             let next = endpoint.outgoing_arcs_from_list(subject, preds)?;
             merge_outgoing_arcs(&mut result, next)
@@ -678,6 +1161,10 @@ impl SRDFBuilder for RdfData {
         todo!()
     }
 
+    fn add_bnode(&mut self) -> Result<Self::BNode, Self::Err> {
+        Ok(OxBlankNode::default())
+    }
+
     fn add_triple(
         &mut self,
         _subj: &Self::Subject,
@@ -707,17 +1194,44 @@ impl SRDFBuilder for RdfData {
     ) -> Result<(), Self::Err> {
         if let Some(graph) = &self.graph {
             graph.serialize(format, writer)?;
-            Ok::<(), Self::Err>(())
-        } else {
-            Ok(())
-        }?;
+        }
+        if let Some(local_store) = &self.local_store {
+            local_store.read_graph()?.serialize(format, writer)?;
+        }
         for endpoint in &self.endpoints {
-            writeln!(writer, "Endpoint {}", endpoint.iri())?;
+            writeln!(writer, "Endpoint {}", endpoint.inner().iri())?;
         }
         Ok(())
     }
 }
 
+/// Identifies the answers of an endpoint in its persisted cache, made of the endpoint IRI and its named graph
+fn endpoint_cache_key(endpoint: &SRDFSparql) -> String {
+    match endpoint.graph() {
+        Some(graph) => format!("{} {}", endpoint.iri(), graph),
+        None => endpoint.iri().to_string(),
+    }
+}
+
+/// Name of the file where the cache of an endpoint is persisted
+fn endpoint_cache_file_name(key: &str) -> String {
+    let name: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{name}.json")
+}
+
+fn merge_arcs<I, T>(current: &mut HashMap<I, HashSet<T>>, next: HashMap<I, HashSet<T>>)
+where
+    I: Eq + Hash,
+    T: Eq + Hash,
+{
+    for (key, values) in next {
+        current.entry(key).or_default().extend(values)
+    }
+}
+
 fn merge_outgoing_arcs<I, T>(
     current: &mut (HashMap<I, HashSet<T>>, Vec<I>),
     next: (HashMap<I, HashSet<T>>, Vec<I>),
@@ -4,6 +4,7 @@ use oxigraph::{
     sparql::{EvaluationError, SparqlSyntaxError},
     store::StorageError,
 };
+use spareval::QueryEvaluationError;
 use thiserror::Error;
 
 use srdf::{RDFFormat, SRDFGraphError, SRDFSparqlError};
@@ -31,6 +32,18 @@ pub enum RdfDataError {
     #[error("Serializing RDF Data as {format}: {error}")]
     Serializing { error: String, format: RDFFormat },
 
+    #[error("Opening local store at {path}: {error}")]
+    OpeningStore { path: String, error: String },
+
+    #[error("Reading local store at {path}: {error}")]
+    ReadingStore { path: String, error: String },
+
+    #[error("Writing local store at {path}: {error}")]
+    WritingStore { path: String, error: String },
+
+    #[error("Endpoint cache: {error}")]
+    EndpointCache { error: String },
+
     #[error(transparent)]
     StorageError {
         #[from]
@@ -48,4 +61,10 @@ pub enum RdfDataError {
         #[from]
         err: EvaluationError,
     },
+
+    #[error(transparent)]
+    QueryEvaluationError {
+        #[from]
+        err: QueryEvaluationError,
+    },
 }
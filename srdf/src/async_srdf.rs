@@ -1,15 +1,16 @@
 use std::{collections::HashSet, fmt::Display};
 
 use async_trait::async_trait;
+use oxrdf::{NamedNode as OxNamedNode, Subject as OxSubject, Term as OxTerm, Triple as OxTriple};
 use std::hash::Hash;
 
 #[async_trait]
 pub trait AsyncSRDF {
-    type Subject: Display + Sync + Send;
-    type IRI: Display + Hash + Eq + Sync + Send;
+    type Subject: Display + Clone + Sync + Send;
+    type IRI: Display + Hash + Eq + Clone + Sync + Send;
     type BNode: Display + Sync + Send;
     type Literal: Display + Sync + Send;
-    type Term: Display + Sync + Send;
+    type Term: Display + Clone + Sync + Send;
     type Err: Display;
 
     async fn get_predicates_subject(
@@ -28,6 +29,268 @@ pub trait AsyncSRDF {
         object: &Self::Term,
         pred: &Self::IRI,
     ) -> Result<HashSet<Self::Subject>, Self::Err>;
+
+    /// Triples whose subject is one of `subjects`.
+    /// Implementations backed by remote services can fetch several subjects with each request
+    async fn get_triples_with_subjects(
+        &self,
+        subjects: &[Self::Subject],
+    ) -> Result<Vec<(Self::Subject, Self::IRI, Self::Term)>, Self::Err>;
+
+    /// Triples whose object is one of `objects`
+    async fn get_triples_with_objects(
+        &self,
+        objects: &[Self::Term],
+    ) -> Result<Vec<(Self::Subject, Self::IRI, Self::Term)>, Self::Err>;
+
+    async fn get_triples_with_predicate(
+        &self,
+        pred: &Self::IRI,
+    ) -> Result<Vec<(Self::Subject, Self::IRI, Self::Term)>, Self::Err>;
+
+    /// Values of the first variable of the solutions of a SPARQL SELECT query.
+    /// Sources that can't run queries return an error
+    async fn get_nodes_for_query(&self, query: &str) -> Result<Vec<Self::Term>, Self::Err>;
+}
+
+/// Fetches the neighbourhood of `nodes` from an asynchronous RDF source so that it can be validated in memory.
+///
+/// The neighbourhood contains the incoming arcs of the nodes and the outgoing arcs of the nodes
+/// and of the nodes that can be reached from them following up to `depth` outgoing arcs.
+/// Each hop is fetched with the batched methods of [`AsyncSRDF`]
+pub async fn fetch_neighbourhood<A>(
+    rdf: &A,
+    nodes: &[OxSubject],
+    depth: usize,
+) -> Result<Vec<OxTriple>, A::Err>
+where
+    A: AsyncSRDF<Subject = OxSubject, IRI = OxNamedNode, Term = OxTerm> + Sync,
+{
+    let mut neighbourhood = AsyncNeighbourhood::new();
+    neighbourhood.fetch_references(rdf, nodes, depth).await?;
+    Ok(neighbourhood.triples().cloned().collect())
+}
+
+/// Arcs of some nodes: the outgoing arcs of `subjects`, the incoming arcs of `objects`,
+/// the incoming arcs of the objects of `predicate_objects` with that predicate
+/// and all the arcs with the predicates of `predicates`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct NodeArcs {
+    pub subjects: HashSet<OxSubject>,
+    pub objects: HashSet<OxTerm>,
+    pub predicate_objects: HashSet<(OxNamedNode, OxTerm)>,
+    pub predicates: HashSet<OxNamedNode>,
+}
+
+impl NodeArcs {
+    pub fn new() -> NodeArcs {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.subjects.is_empty()
+            && self.objects.is_empty()
+            && self.predicate_objects.is_empty()
+            && self.predicates.is_empty()
+    }
+
+    /// Adds the arcs of `other`
+    pub fn extend(&mut self, other: NodeArcs) {
+        self.subjects.extend(other.subjects);
+        self.objects.extend(other.objects);
+        self.predicate_objects.extend(other.predicate_objects);
+        self.predicates.extend(other.predicates);
+    }
+
+    /// Arcs of `self` that are not in `other`.
+    /// The incoming arcs of an object with some predicate are in `other` if all its incoming arcs
+    /// or all the arcs with that predicate are
+    pub fn difference(&self, other: &NodeArcs) -> NodeArcs {
+        let covered = |arcs: &NodeArcs, pred: &OxNamedNode, object: &OxTerm| {
+            arcs.objects.contains(object) || arcs.predicates.contains(pred)
+        };
+        NodeArcs {
+            subjects: self.subjects.difference(&other.subjects).cloned().collect(),
+            objects: self.objects.difference(&other.objects).cloned().collect(),
+            predicate_objects: self
+                .predicate_objects
+                .iter()
+                .filter(|(pred, object)| {
+                    !covered(self, pred, object)
+                        && !covered(other, pred, object)
+                        && !other
+                            .predicate_objects
+                            .contains(&(pred.clone(), object.clone()))
+                })
+                .cloned()
+                .collect(),
+            predicates: self
+                .predicates
+                .difference(&other.predicates)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// Neighbourhood of some nodes fetched from an asynchronous RDF source so that they can be validated in memory.
+///
+/// It remembers which arcs have been fetched, so it can be extended with the arcs that a validation reads
+/// and were not fetched yet until the validation doesn't need more arcs.
+/// The triples that were not taken yet with [`AsyncNeighbourhood::take_new_triples`] are kept apart
+/// so that the data being validated can be extended with them
+#[derive(Debug, Default, Clone)]
+pub struct AsyncNeighbourhood {
+    triples: HashSet<OxTriple>,
+    new_triples: Vec<OxTriple>,
+    fetched: NodeArcs,
+}
+
+impl AsyncNeighbourhood {
+    pub fn new() -> AsyncNeighbourhood {
+        Self::default()
+    }
+
+    pub fn triples(&self) -> impl Iterator<Item = &OxTriple> {
+        self.triples.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.triples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triples.is_empty()
+    }
+
+    /// Arcs that have been fetched
+    pub fn fetched(&self) -> &NodeArcs {
+        &self.fetched
+    }
+
+    /// Triples added since the last call, which are the ones of the neighbourhood in the first call
+    pub fn take_new_triples(&mut self) -> Vec<OxTriple> {
+        std::mem::take(&mut self.new_triples)
+    }
+
+    /// Adds triples that were obtained from the source by other means returning if some of them were new
+    pub fn add_triples<I: IntoIterator<Item = OxTriple>>(&mut self, triples: I) -> bool {
+        let mut added = false;
+        for triple in triples {
+            added |= self.insert(triple)
+        }
+        added
+    }
+
+    fn insert(&mut self, triple: OxTriple) -> bool {
+        if self.triples.contains(&triple) {
+            false
+        } else {
+            self.triples.insert(triple.clone());
+            self.new_triples.push(triple);
+            true
+        }
+    }
+
+    /// Adds the subjects that have an arc to `object` with predicate `pred`,
+    /// which were obtained from the source by other means
+    pub fn add_subjects_for_object_predicate<I: IntoIterator<Item = OxSubject>>(
+        &mut self,
+        object: &OxTerm,
+        pred: &OxNamedNode,
+        subjects: I,
+    ) {
+        self.add_triples(
+            subjects
+                .into_iter()
+                .map(|subject| OxTriple::new(subject, pred.clone(), object.clone())),
+        );
+        self.fetched
+            .predicate_objects
+            .insert((pred.clone(), object.clone()));
+    }
+
+    /// Fetches the incoming arcs of `nodes` and the outgoing arcs of the nodes and of the nodes
+    /// that can be reached from them following up to `depth` outgoing arcs
+    pub async fn fetch_references<A>(
+        &mut self,
+        rdf: &A,
+        nodes: &[OxSubject],
+        depth: usize,
+    ) -> Result<(), A::Err>
+    where
+        A: AsyncSRDF<Subject = OxSubject, IRI = OxNamedNode, Term = OxTerm> + Sync,
+    {
+        let incoming = NodeArcs {
+            objects: nodes.iter().cloned().map(OxTerm::from).collect(),
+            ..NodeArcs::default()
+        };
+        self.fetch_missing(rdf, &incoming).await?;
+        let mut current: HashSet<OxSubject> = nodes.iter().cloned().collect();
+        for hop in 0..=depth {
+            current.retain(|node| !self.fetched.subjects.contains(node));
+            if current.is_empty() {
+                break;
+            }
+            let subjects: Vec<OxSubject> = current.drain().collect();
+            let triples = rdf.get_triples_with_subjects(&subjects).await?;
+            self.fetched.subjects.extend(subjects);
+            for (subject, pred, object) in triples {
+                if hop < depth {
+                    current.extend(reference(&object));
+                }
+                self.insert(OxTriple::new(subject, pred, object));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetches the arcs of `arcs` that haven't been fetched yet returning if some new triple was found.
+    /// The outgoing and incoming arcs are fetched with the batched methods of [`AsyncSRDF`]
+    pub async fn fetch_missing<A>(&mut self, rdf: &A, arcs: &NodeArcs) -> Result<bool, A::Err>
+    where
+        A: AsyncSRDF<Subject = OxSubject, IRI = OxNamedNode, Term = OxTerm> + Sync,
+    {
+        let missing = arcs.difference(&self.fetched);
+        let mut added = false;
+        if !missing.subjects.is_empty() {
+            let subjects: Vec<OxSubject> = missing.subjects.iter().cloned().collect();
+            for (subject, pred, object) in rdf.get_triples_with_subjects(&subjects).await? {
+                added |= self.insert(OxTriple::new(subject, pred, object))
+            }
+        }
+        if !missing.objects.is_empty() {
+            let objects: Vec<OxTerm> = missing.objects.iter().cloned().collect();
+            for (subject, pred, object) in rdf.get_triples_with_objects(&objects).await? {
+                added |= self.insert(OxTriple::new(subject, pred, object))
+            }
+        }
+        for (pred, object) in missing.predicate_objects.iter() {
+            for subject in rdf.get_subjects_for_object_predicate(object, pred).await? {
+                added |= self.insert(OxTriple::new(subject, pred.clone(), object.clone()))
+            }
+        }
+        for pred in missing.predicates.iter() {
+            for (subject, pred, object) in rdf.get_triples_with_predicate(pred).await? {
+                added |= self.insert(OxTriple::new(subject, pred, object))
+            }
+        }
+        self.fetched.subjects.extend(missing.subjects);
+        self.fetched.objects.extend(missing.objects);
+        self.fetched
+            .predicate_objects
+            .extend(missing.predicate_objects);
+        self.fetched.predicates.extend(missing.predicates);
+        Ok(added)
+    }
+}
+
+fn reference(term: &OxTerm) -> Option<OxSubject> {
+    match term {
+        OxTerm::NamedNode(n) => Some(OxSubject::NamedNode(n.clone())),
+        OxTerm::BlankNode(b) => Some(OxSubject::BlankNode(b.clone())),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -35,10 +298,73 @@ mod tests {
     use iri_s::*;
     use std::str::FromStr;
 
+    use super::{fetch_neighbourhood, AsyncNeighbourhood, NodeArcs};
+    use crate::{RDFFormat, ReaderMode, SRDFBasic, SRDFGraph};
+
     #[test]
     fn check_2_iris() {
         let iri1: IriS = IriS::from_str("http://example.org/iri").unwrap();
         let iri2 = IriS::from_str("http://example.org/iri").unwrap();
         assert_eq!(iri1, iri2);
     }
+
+    #[tokio::test]
+    async fn test_fetch_neighbourhood() {
+        let data = r#"prefix : <http://example.org/>
+            :dave :knows :alice .
+            :alice :knows :bob .
+            :bob :knows :carol .
+            :carol :knows :dave .
+        "#;
+        let graph =
+            SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let alice = SRDFGraph::iri_s2subject(&iri!("http://example.org/alice"));
+        let triples = fetch_neighbourhood(&graph, std::slice::from_ref(&alice), 0)
+            .await
+            .unwrap();
+        // :dave :knows :alice and :alice :knows :bob
+        assert_eq!(triples.len(), 2);
+        let triples = fetch_neighbourhood(&graph, &[alice], 1).await.unwrap();
+        assert_eq!(triples.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_missing_arcs() {
+        let data = r#"prefix : <http://example.org/>
+            :alice :knows :bob .
+            :bob :knows :carol .
+            :carol :knows :dave .
+            :dave a :Person .
+        "#;
+        let graph =
+            SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let alice = SRDFGraph::iri_s2subject(&iri!("http://example.org/alice"));
+        let carol = SRDFGraph::iri_s2subject(&iri!("http://example.org/carol"));
+        let person = SRDFGraph::iri_s2term(&iri!("http://example.org/Person"));
+        let rdf_type =
+            SRDFGraph::iri_s2iri(&iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"));
+        let mut neighbourhood = AsyncNeighbourhood::new();
+        neighbourhood
+            .fetch_references(&graph, std::slice::from_ref(&alice), 0)
+            .await
+            .unwrap();
+        assert_eq!(neighbourhood.len(), 1);
+        assert_eq!(neighbourhood.take_new_triples().len(), 1);
+
+        let mut arcs = NodeArcs::new();
+        arcs.subjects.insert(carol);
+        arcs.predicate_objects.insert((rdf_type, person));
+        assert!(neighbourhood.fetch_missing(&graph, &arcs).await.unwrap());
+        assert_eq!(neighbourhood.len(), 3);
+        assert_eq!(neighbourhood.take_new_triples().len(), 2);
+        // The arcs were already fetched
+        assert!(!neighbourhood.fetch_missing(&graph, &arcs).await.unwrap());
+
+        let knows = SRDFGraph::iri_s2iri(&iri!("http://example.org/knows"));
+        let mut arcs = NodeArcs::new();
+        arcs.predicates.insert(knows);
+        assert!(neighbourhood.fetch_missing(&graph, &arcs).await.unwrap());
+        assert_eq!(neighbourhood.len(), 4);
+        assert_eq!(neighbourhood.take_new_triples().len(), 1);
+    }
 }
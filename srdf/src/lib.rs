@@ -20,9 +20,11 @@ pub mod shacl_path;
 pub mod srdf;
 pub mod srdf_basic;
 pub mod srdf_builder;
+pub mod srdf_cache;
 pub mod srdf_graph;
 pub mod srdf_parser;
 pub mod srdf_sparql;
+pub mod srdf_stream;
 pub mod subject;
 pub mod triple;
 pub mod vocab;
@@ -39,9 +41,11 @@ pub use rdf::*;
 pub use rdf_format::*;
 pub use shacl_path::*;
 pub use srdf_builder::*;
+pub use srdf_cache::*;
 pub use srdf_graph::*;
 pub use srdf_parser::*;
 pub use srdf_sparql::*;
+pub use srdf_stream::*;
 pub use subject::*;
 pub use triple::*;
 pub use vocab::*;
@@ -75,6 +75,10 @@ impl Literal {
     pub fn numeric_value(&self) -> Option<NumericLiteral> {
         match self {
             Literal::NumericLiteral(nl) => Some(nl.clone()),
+            Literal::DatatypeLiteral {
+                lexical_form,
+                datatype: IriRef::Iri(iri),
+            } => NumericLiteral::parse(lexical_form, iri.as_str()),
             Literal::StringLiteral { .. }
             | Literal::DatatypeLiteral { .. }
             | Literal::BooleanLiteral(true)
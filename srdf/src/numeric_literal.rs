@@ -1,5 +1,7 @@
 use core::fmt;
+use std::cmp::Ordering;
 use std::fmt::Display;
+use std::str::FromStr;
 
 use rust_decimal::{
     prelude::{FromPrimitive, ToPrimitive},
@@ -8,6 +10,8 @@ use rust_decimal::{
 use serde::{de::Visitor, Deserialize, Serialize, Serializer};
 use std::hash::Hash;
 
+use crate::XSD;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum NumericLiteral {
     Integer(isize),
@@ -99,11 +103,133 @@ impl NumericLiteral {
     }
 
     pub fn less_than(&self, other: &NumericLiteral) -> bool {
+        self.compare(other) == Some(Ordering::Less)
+    }
+
+    /// Parses the lexical form of a literal whose datatype is an XSD numeric type.
+    ///
+    /// Returns `None` if the datatype is not numeric or the lexical form is not
+    /// a valid value of that datatype
+    pub fn parse(lexical_form: &str, datatype: &str) -> Option<NumericLiteral> {
+        let local = datatype.strip_prefix(XSD)?;
+        let str = lexical_form.trim();
+        match local {
+            "decimal" => {
+                if str.contains(['e', 'E']) {
+                    None
+                } else {
+                    Decimal::from_str(str.strip_prefix('+').unwrap_or(str))
+                        .ok()
+                        .map(NumericLiteral::Decimal)
+                }
+            }
+            "double" | "float" => parse_double(str).map(NumericLiteral::Double),
+            _ => {
+                let (min, max) = integer_bounds(local)?;
+                let n = parse_integer(str)?;
+                if min.is_some_and(|min| n < min) || max.is_some_and(|max| n > max) {
+                    None
+                } else {
+                    match isize::try_from(n) {
+                        Ok(n) => Some(NumericLiteral::Integer(n)),
+                        Err(_) => Decimal::from_i128(n).map(NumericLiteral::Decimal),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Compares two numeric literals in the value space following XSD type promotion:
+    /// integers are promoted to decimals and decimals to doubles.
+    ///
+    /// Returns `None` when the values are not comparable (e.g. `NaN`)
+    pub fn compare(&self, other: &NumericLiteral) -> Option<Ordering> {
         match (self, other) {
-            (NumericLiteral::Integer(n1), NumericLiteral::Integer(n2)) => n1 < n2,
-            (v1, v2) => v1.as_decimal() < v2.as_decimal(),
+            (NumericLiteral::Integer(n1), NumericLiteral::Integer(n2)) => Some(n1.cmp(n2)),
+            (NumericLiteral::Double(_), _) | (_, NumericLiteral::Double(_)) => {
+                self.as_f64().partial_cmp(&other.as_f64())
+            }
+            (v1, v2) => Some(v1.as_decimal().cmp(&v2.as_decimal())),
         }
     }
+
+    /// Number of significant digits of a decimal value (`xsd:totalDigits`).
+    /// Returns `None` for doubles, which don't have that facet
+    pub fn total_digits(&self) -> Option<usize> {
+        match self {
+            NumericLiteral::Integer(n) => Some(n.unsigned_abs().to_string().len()),
+            NumericLiteral::Decimal(d) => {
+                let d = d.normalize();
+                let digits = d.mantissa().unsigned_abs().to_string().len();
+                // Values like 0.05 have less mantissa digits than fraction digits
+                Some(digits.max(d.scale() as usize))
+            }
+            NumericLiteral::Double(_) => None,
+        }
+    }
+
+    /// Number of digits after the decimal point of a decimal value (`xsd:fractionDigits`).
+    /// Returns `None` for doubles, which don't have that facet
+    pub fn fraction_digits(&self) -> Option<usize> {
+        match self {
+            NumericLiteral::Integer(_) => Some(0),
+            NumericLiteral::Decimal(d) => Some(d.normalize().scale() as usize),
+            NumericLiteral::Double(_) => None,
+        }
+    }
+
+    fn as_f64(&self) -> f64 {
+        match self {
+            NumericLiteral::Integer(n) => *n as f64,
+            NumericLiteral::Decimal(d) => d.to_f64().unwrap_or(f64::NAN),
+            NumericLiteral::Double(d) => *d,
+        }
+    }
+}
+
+fn parse_double(str: &str) -> Option<f64> {
+    match str {
+        "INF" | "+INF" => Some(f64::INFINITY),
+        "-INF" => Some(f64::NEG_INFINITY),
+        "NaN" => Some(f64::NAN),
+        _ if str
+            .chars()
+            .any(|c| c.is_ascii_alphabetic() && c != 'e' && c != 'E') =>
+        {
+            None
+        }
+        _ => f64::from_str(str).ok(),
+    }
+}
+
+fn parse_integer(str: &str) -> Option<i128> {
+    let digits = str.strip_prefix(['+', '-']).unwrap_or(str);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        None
+    } else {
+        i128::from_str(str).ok()
+    }
+}
+
+/// Bounds of the integer types derived from `xsd:integer`
+fn integer_bounds(local: &str) -> Option<(Option<i128>, Option<i128>)> {
+    let bounds = match local {
+        "integer" => (None, None),
+        "nonNegativeInteger" => (Some(0), None),
+        "positiveInteger" => (Some(1), None),
+        "nonPositiveInteger" => (None, Some(0)),
+        "negativeInteger" => (None, Some(-1)),
+        "long" => (Some(i64::MIN as i128), Some(i64::MAX as i128)),
+        "int" => (Some(i32::MIN as i128), Some(i32::MAX as i128)),
+        "short" => (Some(i16::MIN as i128), Some(i16::MAX as i128)),
+        "byte" => (Some(i8::MIN as i128), Some(i8::MAX as i128)),
+        "unsignedLong" => (Some(0), Some(u64::MAX as i128)),
+        "unsignedInt" => (Some(0), Some(u32::MAX as i128)),
+        "unsignedShort" => (Some(0), Some(u16::MAX as i128)),
+        "unsignedByte" => (Some(0), Some(u8::MAX as i128)),
+        _ => return None,
+    };
+    Some(bounds)
 }
 
 impl Eq for NumericLiteral {}
@@ -257,4 +383,52 @@ mod tests {
         let expected = NumericLiteral::Decimal(dec!(5.35));
         assert_eq!(deser, expected);
     }
+
+    #[test]
+    fn test_parse_xsd_numerics() {
+        let xsd = |local: &str| format!("{XSD}{local}");
+        assert_eq!(
+            NumericLiteral::parse("+23", &xsd("integer")),
+            Some(NumericLiteral::Integer(23))
+        );
+        assert_eq!(
+            NumericLiteral::parse("5.35", &xsd("decimal")),
+            Some(NumericLiteral::Decimal(dec!(5.35)))
+        );
+        assert_eq!(
+            NumericLiteral::parse("-INF", &xsd("double")),
+            Some(NumericLiteral::Double(f64::NEG_INFINITY))
+        );
+        assert_eq!(NumericLiteral::parse("128", &xsd("byte")), None);
+        assert_eq!(
+            NumericLiteral::parse("-1", &xsd("nonNegativeInteger")),
+            None
+        );
+        assert_eq!(NumericLiteral::parse("1.5", &xsd("integer")), None);
+        assert_eq!(NumericLiteral::parse("1", &xsd("string")), None);
+    }
+
+    #[test]
+    fn test_compare_with_promotion() {
+        let one = NumericLiteral::integer(1);
+        let one_and_half = NumericLiteral::decimal(dec!(1.5));
+        let two = NumericLiteral::double(2.0);
+        assert_eq!(one.compare(&one_and_half), Some(Ordering::Less));
+        assert_eq!(two.compare(&one_and_half), Some(Ordering::Greater));
+        assert_eq!(
+            NumericLiteral::decimal(dec!(1.0)).compare(&one),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(NumericLiteral::double(f64::NAN).compare(&one), None);
+    }
+
+    #[test]
+    fn test_digits() {
+        let d = NumericLiteral::decimal(dec!(123.450));
+        assert_eq!(d.total_digits(), Some(5));
+        assert_eq!(d.fraction_digits(), Some(2));
+        assert_eq!(NumericLiteral::decimal(dec!(0.05)).total_digits(), Some(2));
+        assert_eq!(NumericLiteral::integer(-100).total_digits(), Some(3));
+        assert_eq!(NumericLiteral::double(1.5).total_digits(), None);
+    }
 }
@@ -5,14 +5,20 @@ use crate::numeric_literal::NumericLiteral;
 use iri_s::IriS;
 use serde_derive::{Deserialize, Serialize};
 
-/// Concrete representation of RDF objects which can be IRIs, Blank nodes or literals
+/// Concrete representation of RDF objects which can be IRIs, Blank nodes, literals
+/// or quoted triples as in RDF-star
 ///
-/// Note: We plan to support triple terms as in RDF-star in the future
+/// Note: The support for quoted triples is experimental
 #[derive(Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Object {
     Iri(IriS),
     BlankNode(String),
     Literal(Literal),
+    Triple {
+        subject: Box<Object>,
+        predicate: IriS,
+        object: Box<Object>,
+    },
 }
 
 impl Object {
@@ -28,17 +34,30 @@ impl Object {
         Object::Literal(lit)
     }
 
+    pub fn triple(subject: Object, predicate: IriS, object: Object) -> Object {
+        Object::Triple {
+            subject: Box::new(subject),
+            predicate,
+            object: Box::new(object),
+        }
+    }
+
+    pub fn is_triple(&self) -> bool {
+        matches!(self, Object::Triple { .. })
+    }
+
     pub fn length(&self) -> usize {
         match self {
             Object::Iri(iri) => iri.as_str().len(),
             Object::BlankNode(bn) => bn.len(),
             Object::Literal(lit) => lit.lexical_form().len(),
+            Object::Triple { .. } => self.to_string().len(),
         }
     }
 
     pub fn numeric_value(&self) -> Option<NumericLiteral> {
         match self {
-            Object::Iri(_) | Object::BlankNode(_) => None,
+            Object::Iri(_) | Object::BlankNode(_) | Object::Triple { .. } => None,
             Object::Literal(lit) => lit.numeric_value(),
         }
     }
@@ -66,18 +85,41 @@ impl Display for Object {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Object::Iri(iri) => write!(f, "{iri}"),
-            Object::BlankNode(bnode) => write!(f, "_{bnode}"),
+            Object::BlankNode(bnode) => write!(f, "_:{bnode}"),
             Object::Literal(lit) => write!(f, "{lit}"),
+            Object::Triple {
+                subject,
+                predicate,
+                object,
+            } => write!(
+                f,
+                "<< {} <{predicate}> {} >>",
+                show_triple_component(subject),
+                show_triple_component(object)
+            ),
         }
     }
 }
 
+/// IRIs inside quoted triples are shown between angle brackets as in Turtle-star
+fn show_triple_component(obj: &Object) -> String {
+    match obj {
+        Object::Iri(iri) => format!("<{iri}>"),
+        _ => obj.to_string(),
+    }
+}
+
 impl Debug for Object {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Object::Iri(iri) => write!(f, "Iri {{{iri:?}}}"),
             Object::BlankNode(bnode) => write!(f, "Bnode{{{bnode:?}}}"),
             Object::Literal(lit) => write!(f, "Literal{{{lit:?}}}"),
+            Object::Triple {
+                subject,
+                predicate,
+                object,
+            } => write!(f, "Triple {{{subject:?} {predicate:?} {object:?}}}"),
         }
     }
 }
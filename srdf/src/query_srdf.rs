@@ -8,6 +8,23 @@ pub trait QuerySRDF: SRDFBasic {
         Self: Sized;
 
     fn query_ask(&self, query: &str) -> Result<bool, Self::Err>;
+
+    /// Runs a SELECT query whose variables in `bindings` are pre-bound to the given terms,
+    /// which can be blank nodes of the data when the query is evaluated locally
+    fn query_select_with_bindings(
+        &self,
+        query: &str,
+        bindings: &[(VarName, Self::Term)],
+    ) -> Result<QuerySolutions<Self>, Self::Err>
+    where
+        Self: Sized;
+
+    /// Runs an ASK query whose variables in `bindings` are pre-bound to the given terms
+    fn query_ask_with_bindings(
+        &self,
+        query: &str,
+        bindings: &[(VarName, Self::Term)],
+    ) -> Result<bool, Self::Err>;
 }
 
 #[derive(PartialEq, Eq, Debug, Clone, Hash)]
@@ -27,6 +44,10 @@ impl VarName {
             str: str.to_string(),
         }
     }
+
+    pub fn as_str(&self) -> &str {
+        &self.str
+    }
 }
 
 impl From<String> for VarName {
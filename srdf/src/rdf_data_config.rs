@@ -1,8 +1,15 @@
-use std::{collections::HashMap, io, path::Path, str::FromStr};
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use prefixmap::PrefixMap;
 use thiserror::Error;
 
+use crate::SparqlConnectionConfig;
+
 use iri_s::{IriS, IriSError};
 use serde_derive::{Deserialize, Serialize};
 
@@ -12,6 +19,10 @@ pub struct RdfDataConfig {
     /// Default base to resolve relative IRIs, if it is `None` relative IRIs will be marked as errors`
     pub base: Option<IriS>,
     pub endpoints: Option<HashMap<String, EndpointDescription>>,
+
+    /// Local documents for JSON-LD remote contexts, indexed by the IRI of the context.
+    /// Remote contexts are never fetched from the network
+    pub jsonld_contexts: Option<HashMap<String, PathBuf>>,
 }
 
 impl RdfDataConfig {
@@ -19,9 +30,33 @@ impl RdfDataConfig {
         RdfDataConfig {
             base: None,
             endpoints: None,
+            jsonld_contexts: None,
         }
     }
 
+    pub fn with_jsonld_context(mut self, iri: &str, path: &Path) -> Self {
+        self.jsonld_contexts
+            .get_or_insert_with(HashMap::new)
+            .insert(iri.to_string(), path.to_path_buf());
+        self
+    }
+
+    /// Reads the local documents of the JSON-LD contexts
+    pub fn jsonld_context_documents(&self) -> Result<HashMap<String, String>, RdfDataConfigError> {
+        let mut documents = HashMap::new();
+        for (iri, path) in self.jsonld_contexts.iter().flatten() {
+            let document = std::fs::read_to_string(path).map_err(|e| {
+                RdfDataConfigError::ReadingJsonLdContext {
+                    iri: iri.clone(),
+                    path_name: path.display().to_string(),
+                    error: e,
+                }
+            })?;
+            documents.insert(iri.clone(), document);
+        }
+        Ok(documents)
+    }
+
     pub fn with_wikidata(mut self) -> Self {
         let wikidata_name = "wikidata";
         let wikidata_iri = "https://query.wikidata.org/sparql";
@@ -75,7 +110,13 @@ impl Default for RdfDataConfig {
 pub struct EndpointDescription {
     query_url: IriS,
     update_url: Option<IriS>,
+
+    #[serde(default)]
     prefixmap: PrefixMap,
+
+    /// Authentication, headers, timeout and query method of the requests to the endpoint
+    #[serde(flatten)]
+    connection: SparqlConnectionConfig,
 }
 
 impl EndpointDescription {
@@ -84,6 +125,7 @@ impl EndpointDescription {
             query_url: IriS::new_unchecked(str),
             update_url: None,
             prefixmap: PrefixMap::new(),
+            connection: SparqlConnectionConfig::default(),
         }
     }
 
@@ -103,6 +145,15 @@ impl EndpointDescription {
     pub fn add_prefixmap(&mut self, prefixmap: PrefixMap) {
         self.prefixmap = prefixmap;
     }
+
+    pub fn connection(&self) -> &SparqlConnectionConfig {
+        &self.connection
+    }
+
+    pub fn with_connection(mut self, connection: SparqlConnectionConfig) -> Self {
+        self.connection = connection;
+        self
+    }
 }
 
 impl FromStr for EndpointDescription {
@@ -114,6 +165,7 @@ impl FromStr for EndpointDescription {
             query_url: iri,
             update_url: None,
             prefixmap: PrefixMap::new(),
+            connection: SparqlConnectionConfig::default(),
         })
     }
 }
@@ -129,6 +181,59 @@ pub enum RdfDataConfigError {
         error: serde_yml::Error,
     },
 
+    #[error("Reading JSON-LD context {iri} from path {path_name:?} error: {error:?}")]
+    ReadingJsonLdContext {
+        iri: String,
+        path_name: String,
+        error: io::Error,
+    },
+
     #[error("Converting to IRI the string {str}. Error: {error}")]
     ConvertingIriEndpoint { error: String, str: String },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EndpointAuth, QueryMethod};
+
+    #[test]
+    fn test_endpoint_connection_from_yaml() {
+        let yaml = r#"
+endpoints:
+  internal:
+    query_url: https://example.org/sparql
+    auth:
+      type: basic
+      username: reader
+      password: ${READER_PASSWORD}
+    headers:
+      X-Tenant: research
+    timeout_secs: 30
+    query_method: post
+  public:
+    query_url: https://example.org/public/sparql
+"#;
+        let config: RdfDataConfig = serde_yml::from_str(yaml).unwrap();
+        let internal = config.find_endpoint("internal").unwrap().connection();
+        assert_eq!(
+            internal.auth,
+            Some(EndpointAuth::Basic {
+                username: "reader".to_string(),
+                password: Some("${READER_PASSWORD}".to_string())
+            })
+        );
+        assert_eq!(internal.timeout_secs, Some(30));
+        assert_eq!(internal.query_method(), QueryMethod::Post);
+        assert_eq!(
+            internal.headers.as_ref().unwrap().get("X-Tenant"),
+            Some(&"research".to_string())
+        );
+        let public = config.find_endpoint("public").unwrap();
+        assert_eq!(public.connection(), &SparqlConnectionConfig::default());
+        assert_eq!(
+            public.query_url().as_str(),
+            "https://example.org/public/sparql"
+        );
+    }
+}
@@ -13,6 +13,7 @@ pub enum RDFFormat {
     TriG,
     N3,
     NQuads,
+    JsonLd,
 }
 
 impl FromStr for RDFFormat {
@@ -26,6 +27,7 @@ impl FromStr for RDFFormat {
             "trig" => Ok(RDFFormat::TriG),
             "n3" => Ok(RDFFormat::N3),
             "nq" => Ok(RDFFormat::NQuads),
+            "jsonld" => Ok(RDFFormat::JsonLd),
             _ => Err(RDFParseError::SRDFError {
                 err: format!("Format {} not supported", s).to_string(),
             }),
@@ -42,6 +44,7 @@ impl Display for RDFFormat {
             RDFFormat::TriG => write!(f, "TriG"),
             RDFFormat::N3 => write!(f, "N3"),
             RDFFormat::NQuads => write!(f, "NQuads"),
+            RDFFormat::JsonLd => write!(f, "JSON-LD"),
         }
     }
 }
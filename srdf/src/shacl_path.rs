@@ -26,16 +26,26 @@ impl Display for SHACLPath {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             SHACLPath::Predicate { pred } => write!(f, "{pred}"),
-            SHACLPath::Alternative { .. } => todo!(),
-            SHACLPath::Sequence { .. } => todo!(),
-            SHACLPath::Inverse { .. } => todo!(),
-            SHACLPath::ZeroOrMore { .. } => todo!(),
-            SHACLPath::OneOrMore { .. } => todo!(),
-            SHACLPath::ZeroOrOne { .. } => todo!(),
+            SHACLPath::Alternative { paths } => write_paths(f, paths, " | "),
+            SHACLPath::Sequence { paths } => write_paths(f, paths, " / "),
+            SHACLPath::Inverse { path } => write!(f, "^{path}"),
+            SHACLPath::ZeroOrMore { path } => write!(f, "({path})*"),
+            SHACLPath::OneOrMore { path } => write!(f, "({path})+"),
+            SHACLPath::ZeroOrOne { path } => write!(f, "({path})?"),
         }
     }
 }
 
+/// Writes paths with SPARQL property path syntax
+fn write_paths(
+    f: &mut std::fmt::Formatter<'_>,
+    paths: &[SHACLPath],
+    separator: &str,
+) -> std::fmt::Result {
+    let paths: Vec<String> = paths.iter().map(|path| path.to_string()).collect();
+    write!(f, "({})", paths.join(separator))
+}
+
 impl From<SHACLPath> for &str {
     fn from(value: SHACLPath) -> Self {
         match value {
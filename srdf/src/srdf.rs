@@ -8,6 +8,12 @@ pub type HasMapOfIriAndItem<I, T> = HashMap<I, HashSet<T>>;
 
 type OutgoingArcs<I, T> = (HasMapOfIriAndItem<I, T>, Vec<I>);
 
+/// Outgoing arcs of several subjects indexed by subject
+pub type OutgoingArcsOfSubjects<S> = HashMap<
+    <S as SRDFBasic>::Subject,
+    HasMapOfIriAndItem<<S as SRDFBasic>::IRI, <S as SRDFBasic>::Term>,
+>;
+
 /// This trait contains functions to handle Simple RDF graphs, which are basically to get the neighbourhood of RDF nodes
 ///
 /// TODO: Consider alternative names: RDFGraphOps
@@ -78,4 +84,17 @@ pub trait SRDF: SRDFBasic {
         subject: &Self::Subject,
         preds: &[Self::IRI],
     ) -> Result<OutgoingArcs<Self::IRI, Self::Term>, Self::Err>;
+
+    /// Get the outgoing arcs of several subjects.
+    /// Implementations backed by remote services can obtain them with fewer requests than calling `outgoing_arcs` for each subject
+    fn outgoing_arcs_for_subjects(
+        &self,
+        subjects: &[Self::Subject],
+    ) -> Result<OutgoingArcsOfSubjects<Self>, Self::Err> {
+        let mut result = HashMap::new();
+        for subject in subjects {
+            result.insert(subject.clone(), self.outgoing_arcs(subject)?);
+        }
+        Ok(result)
+    }
 }
@@ -52,9 +52,11 @@ pub trait SRDFBasic {
     }
     fn term_as_object(term: &Self::Term) -> Object;
 
-    fn object_as_term(obj: &Object) -> Self::Term;
+    /// Converts an object into a term.
+    /// Fails with quoted triples that can't be represented, like the ones whose subject is a literal
+    fn object_as_term(obj: &Object) -> Result<Self::Term, Self::Err>;
     fn object_as_subject(obj: &Object) -> Option<Self::Subject> {
-        let term = Self::object_as_term(obj);
+        let term = Self::object_as_term(obj).ok()?;
         Self::term_as_subject(&term)
     }
 
@@ -19,6 +19,9 @@ pub trait SRDFBuilder: SRDF {
     /// Adds a prefix map declaration to the current RDF graph
     fn add_prefix_map(&mut self, prefix_map: PrefixMap) -> Result<(), Self::Err>;
 
+    /// Returns a fresh blank node, different from the blank nodes of the current RDF graph
+    fn add_bnode(&mut self) -> Result<Self::BNode, Self::Err>;
+
     /// Adds an RDF triple to the current RDF graph
     fn add_triple(
         &mut self,
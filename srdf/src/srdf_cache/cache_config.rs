@@ -0,0 +1,63 @@
+use serde_derive::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Default number of nodes whose outgoing arcs are kept in the cache
+pub const DEFAULT_CACHE_CAPACITY: usize = 10_000;
+
+/// Configuration of the cache of the neighbourhoods of nodes obtained from SPARQL endpoints
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct CacheConfig {
+    /// Maximum number of nodes whose outgoing arcs are kept in the cache,
+    /// the same number of nodes with incoming arcs is also kept
+    pub capacity: Option<usize>,
+
+    /// Directory where the cache of each endpoint is persisted between runs.
+    /// If it is `None` the cache is only kept in memory
+    pub dir: Option<PathBuf>,
+
+    /// Seconds after which a persisted cache is stale and is not loaded.
+    /// If it is `None` the persisted cache never expires
+    pub ttl_secs: Option<u64>,
+
+    /// Ignores the persisted cache, which is replaced when the cache is saved again
+    pub refresh: Option<bool>,
+}
+
+impl CacheConfig {
+    pub fn new() -> CacheConfig {
+        Self::default()
+    }
+
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    pub fn with_dir(mut self, dir: PathBuf) -> Self {
+        self.dir = Some(dir);
+        self
+    }
+
+    pub fn with_ttl_secs(mut self, secs: u64) -> Self {
+        self.ttl_secs = Some(secs);
+        self
+    }
+
+    pub fn with_refresh(mut self, refresh: bool) -> Self {
+        self.refresh = Some(refresh);
+        self
+    }
+
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl_secs.map(Duration::from_secs)
+    }
+
+    pub fn refresh(&self) -> bool {
+        self.refresh.unwrap_or(false)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity.unwrap_or(DEFAULT_CACHE_CAPACITY)
+    }
+}
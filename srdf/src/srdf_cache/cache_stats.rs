@@ -0,0 +1,53 @@
+use std::fmt::Display;
+use std::ops::AddAssign;
+
+/// Statistics of the use of a [`crate::CachedSRDF`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CacheStats {
+    /// Lookups answered from the cache
+    pub hits: usize,
+
+    /// Lookups that had to reach the backend
+    pub misses: usize,
+
+    /// Nodes whose neighbourhood was fetched in advance
+    pub prefetched: usize,
+
+    /// Nodes removed from the cache to make room for new ones
+    pub evictions: usize,
+}
+
+impl CacheStats {
+    /// Ratio of lookups answered from the cache, 0 if there were no lookups
+    pub fn hit_ratio(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl AddAssign for CacheStats {
+    fn add_assign(&mut self, other: Self) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+        self.prefetched += other.prefetched;
+        self.evictions += other.evictions;
+    }
+}
+
+impl Display for CacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Cache: {} hits, {} misses ({:.1}% hit ratio), {} prefetched, {} evicted",
+            self.hits,
+            self.misses,
+            self.hit_ratio() * 100.0,
+            self.prefetched,
+            self.evictions
+        )
+    }
+}
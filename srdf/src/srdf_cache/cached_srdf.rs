@@ -0,0 +1,697 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::fs::{self, File};
+use std::hash::Hash;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use iri_s::IriS;
+use oxrdf::Term as OxTerm;
+use prefixmap::{PrefixMap, PrefixMapError};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    CacheStats, HasMapOfIriAndItem, LruCache, Object, SRDFBasic, SRDFCacheError, Triple, SRDF,
+};
+
+type OutgoingArcs<S> = HasMapOfIriAndItem<<S as SRDFBasic>::IRI, <S as SRDFBasic>::Term>;
+type IncomingArcs<S> = HasMapOfIriAndItem<<S as SRDFBasic>::IRI, <S as SRDFBasic>::Subject>;
+
+struct CacheEntries<S: SRDFBasic> {
+    outgoing: LruCache<S::Subject, OutgoingArcs<S>>,
+    incoming: LruCache<S::Term, IncomingArcs<S>>,
+    stats: CacheStats,
+}
+
+impl<S: SRDFBasic> CacheEntries<S> {
+    fn new(capacity: usize) -> Self {
+        CacheEntries {
+            outgoing: LruCache::new(capacity),
+            incoming: LruCache::new(capacity),
+            stats: CacheStats::default(),
+        }
+    }
+}
+
+/// Decorator of an [`SRDF`] backend that keeps the neighbourhoods of the most recently used nodes in memory.
+///
+/// The outgoing arcs of a subject are fetched as a whole the first time any of them is needed
+/// and the answers to `predicates_for_subject`, `objects_for_subject_predicate`, `outgoing_arcs`
+/// and `outgoing_arcs_from_list` are obtained from them. Incoming arcs are cached in the same way.
+/// Blank nodes are always passed to the backend because their labels may only be meaningful inside a single response.
+///
+/// Clones share the same cache, so that it can be used from copies of the RDF data.
+/// A cache with capacity 0 passes all the calls to the backend
+#[derive(Clone)]
+pub struct CachedSRDF<S: SRDFBasic> {
+    inner: S,
+    capacity: usize,
+
+    /// File where the cache is persisted
+    path: Option<PathBuf>,
+
+    /// Identifies the backend whose answers are persisted, like the IRI of an endpoint
+    key: String,
+
+    /// Time after which the persisted entries are no longer loaded
+    ttl: Option<Duration>,
+    entries: Arc<Mutex<CacheEntries<S>>>,
+}
+
+/// Arcs of a node as pairs of predicates and the nodes they relate
+type PersistedArcs = Vec<(IriS, Vec<Object>)>;
+
+/// Serializable content of a cache
+#[derive(Serialize, Deserialize, Default)]
+struct PersistedCache {
+    /// Key of the backend whose answers were cached
+    #[serde(default)]
+    key: String,
+
+    /// Seconds since the Unix epoch when the cache was saved
+    #[serde(default)]
+    saved_at: u64,
+    outgoing: Vec<(Object, PersistedArcs)>,
+    incoming: Vec<(Object, PersistedArcs)>,
+}
+
+impl<S: SRDFBasic> CachedSRDF<S> {
+    pub fn new(inner: S, capacity: usize) -> CachedSRDF<S> {
+        CachedSRDF {
+            inner,
+            capacity,
+            path: None,
+            key: String::new(),
+            ttl: None,
+            entries: Arc::new(Mutex::new(CacheEntries::new(capacity))),
+        }
+    }
+
+    /// Wraps a backend without caching anything
+    pub fn without_cache(inner: S) -> CachedSRDF<S> {
+        Self::new(inner, 0)
+    }
+
+    /// Persists the cache in the file at `path` for the backend identified by `key`.
+    /// The entries that the file already contains are obtained with [`CachedSRDF::load`]
+    pub fn with_persistence(mut self, path: &Path, key: &str) -> Self {
+        self.path = Some(path.to_path_buf());
+        self.key = key.to_string();
+        self
+    }
+
+    /// Sets the time after which the persisted entries are stale and are no longer loaded
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Gives mutable access to the backend.
+    /// As the backend may answer differently after it is changed, this instance stops sharing
+    /// the cache with its clones, starts with an empty cache and is no longer persisted
+    pub fn inner_mut(&mut self) -> &mut S {
+        self.entries = Arc::new(Mutex::new(CacheEntries::new(self.capacity)));
+        self.path = None;
+        &mut self.inner
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.entries().stats.clone()
+    }
+
+    /// Removes all the entries of the cache
+    pub fn clear(&self) {
+        let mut entries = self.entries();
+        entries.outgoing.clear();
+        entries.incoming.clear();
+    }
+
+    /// Removes all the entries of the cache and the file where it is persisted
+    pub fn clear_persisted(&self) -> Result<(), SRDFCacheError> {
+        self.clear();
+        match &self.path {
+            Some(path) if path.exists() => {
+                fs::remove_file(path).map_err(|e| SRDFCacheError::Writing {
+                    path: path.display().to_string(),
+                    error: e.to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn entries(&self) -> MutexGuard<'_, CacheEntries<S>> {
+        // A panic while the lock was held can't leave the cache in an inconsistent state
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn caches_subject(&self, subject: &S::Subject) -> bool {
+        self.is_enabled() && !S::subject_is_bnode(subject)
+    }
+
+    fn caches_term(&self, term: &S::Term) -> bool {
+        self.is_enabled() && !S::term_is_bnode(term) && !S::term_is_literal(term)
+    }
+
+    fn cached_outgoing(&self, subject: &S::Subject) -> Option<OutgoingArcs<S>> {
+        let mut entries = self.entries();
+        match entries.outgoing.get(subject).cloned() {
+            Some(arcs) => {
+                entries.stats.hits += 1;
+                Some(arcs)
+            }
+            None => {
+                entries.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn cache_outgoing(&self, subject: S::Subject, arcs: OutgoingArcs<S>) {
+        let mut entries = self.entries();
+        let evicted = entries.outgoing.insert(subject, arcs);
+        entries.stats.evictions += evicted;
+    }
+
+    /// Loads the entries of the file where the cache is persisted.
+    /// Files saved for another key or older than the time to live are ignored, as they may not match the answers of the backend.
+    /// Returns whether the entries were loaded
+    pub fn load(&self) -> Result<bool, SRDFCacheError> {
+        let path = match &self.path {
+            Some(path) if path.exists() => path,
+            _ => return Ok(false),
+        };
+        let read_error = |e: String| SRDFCacheError::Reading {
+            path: path.display().to_string(),
+            error: e,
+        };
+        let file = File::open(path).map_err(|e| read_error(e.to_string()))?;
+        let persisted: PersistedCache =
+            serde_json::from_reader(BufReader::new(file)).map_err(|e| read_error(e.to_string()))?;
+        if persisted.key != self.key || self.is_expired(persisted.saved_at) {
+            return Ok(false);
+        }
+        let mut entries = self.entries();
+        for (subject, arcs) in persisted.outgoing {
+            if let Some(subject) = S::object_as_subject(&subject) {
+                let arcs = arcs_from_objects::<S, _>(arcs, |o| S::object_as_term(o).ok());
+                entries.outgoing.insert(subject, arcs);
+            }
+        }
+        for (object, arcs) in persisted.incoming {
+            if let Ok(object) = S::object_as_term(&object) {
+                let arcs = arcs_from_objects::<S, _>(arcs, S::object_as_subject);
+                entries.incoming.insert(object, arcs);
+            }
+        }
+        Ok(true)
+    }
+
+    fn is_expired(&self, saved_at: u64) -> bool {
+        self.ttl.is_some_and(|ttl| {
+            let saved_at = UNIX_EPOCH + Duration::from_secs(saved_at);
+            SystemTime::now()
+                .duration_since(saved_at)
+                .is_ok_and(|age| age > ttl)
+        })
+    }
+
+    /// Writes the cache to its file if it is persisted.
+    /// The entries are written from the least to the most recently used so that loading them keeps their order
+    pub fn save(&self) -> Result<(), SRDFCacheError> {
+        let path = match &self.path {
+            None => return Ok(()),
+            Some(path) => path,
+        };
+        let write_error = |e: String| SRDFCacheError::Writing {
+            path: path.display().to_string(),
+            error: e,
+        };
+        let persisted = {
+            let entries = self.entries();
+            PersistedCache {
+                key: self.key.clone(),
+                saved_at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |d| d.as_secs()),
+                outgoing: entries
+                    .outgoing
+                    .iter()
+                    .map(|(subject, arcs)| {
+                        (
+                            S::subject_as_object(subject),
+                            arcs_as_objects::<S, _>(arcs, S::term_as_object),
+                        )
+                    })
+                    .collect(),
+                incoming: entries
+                    .incoming
+                    .iter()
+                    .map(|(object, arcs)| {
+                        (
+                            S::term_as_object(object),
+                            arcs_as_objects::<S, _>(arcs, S::subject_as_object),
+                        )
+                    })
+                    .collect(),
+            }
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| write_error(e.to_string()))?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        let file = File::create(&tmp_path).map_err(|e| write_error(e.to_string()))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, &persisted).map_err(|e| write_error(e.to_string()))?;
+        writer.flush().map_err(|e| write_error(e.to_string()))?;
+        fs::rename(&tmp_path, path).map_err(|e| write_error(e.to_string()))?;
+        Ok(())
+    }
+}
+
+impl<S: SRDF> CachedSRDF<S> {
+    /// Fetches in advance the outgoing arcs of the subjects that are not in the cache.
+    /// Backends like SPARQL endpoints obtain them with a single request for many subjects
+    pub fn prefetch(&self, subjects: &[S::Subject]) -> Result<(), S::Err> {
+        let pending: Vec<S::Subject> = {
+            let entries = self.entries();
+            let mut seen = HashSet::new();
+            subjects
+                .iter()
+                .filter(|s| self.caches_subject(s) && !entries.outgoing.contains_key(s))
+                .filter(|s| seen.insert(*s))
+                .cloned()
+                .collect()
+        };
+        if pending.is_empty() {
+            return Ok(());
+        }
+        let mut arcs = self.inner.outgoing_arcs_for_subjects(&pending)?;
+        let mut entries = self.entries();
+        for subject in pending {
+            let subject_arcs = arcs.remove(&subject).unwrap_or_default();
+            let evicted = entries.outgoing.insert(subject, subject_arcs);
+            entries.stats.evictions += evicted;
+            entries.stats.prefetched += 1;
+        }
+        Ok(())
+    }
+}
+
+fn arcs_as_objects<S, N>(
+    arcs: &HasMapOfIriAndItem<S::IRI, N>,
+    as_object: fn(&N) -> Object,
+) -> PersistedArcs
+where
+    S: SRDFBasic,
+{
+    arcs.iter()
+        .map(|(pred, nodes)| (S::iri2iri_s(pred), nodes.iter().map(as_object).collect()))
+        .collect()
+}
+
+fn arcs_from_objects<S, N>(
+    arcs: PersistedArcs,
+    from_object: impl Fn(&Object) -> Option<N>,
+) -> HasMapOfIriAndItem<S::IRI, N>
+where
+    S: SRDFBasic,
+    N: Eq + Hash,
+{
+    arcs.into_iter()
+        .map(|(pred, nodes)| {
+            (
+                S::iri_s2iri(&pred),
+                nodes.iter().filter_map(&from_object).collect(),
+            )
+        })
+        .collect()
+}
+
+impl<S: SRDFBasic + Debug> Debug for CachedSRDF<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedSRDF")
+            .field("inner", &self.inner)
+            .field("capacity", &self.capacity)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl<S: SRDFBasic> SRDFBasic for CachedSRDF<S> {
+    type Subject = S::Subject;
+    type IRI = S::IRI;
+    type BNode = S::BNode;
+    type Literal = S::Literal;
+    type Term = S::Term;
+    type Err = S::Err;
+
+    fn subject_as_iri(subject: &Self::Subject) -> Option<Self::IRI> {
+        S::subject_as_iri(subject)
+    }
+
+    fn subject_as_bnode(subject: &Self::Subject) -> Option<Self::BNode> {
+        S::subject_as_bnode(subject)
+    }
+
+    fn subject_is_iri(subject: &Self::Subject) -> bool {
+        S::subject_is_iri(subject)
+    }
+
+    fn subject_is_bnode(subject: &Self::Subject) -> bool {
+        S::subject_is_bnode(subject)
+    }
+
+    fn term_as_iri(object: &Self::Term) -> Option<&Self::IRI> {
+        S::term_as_iri(object)
+    }
+
+    fn term_as_bnode(object: &Self::Term) -> Option<Self::BNode> {
+        S::term_as_bnode(object)
+    }
+
+    fn term_as_literal(object: &Self::Term) -> Option<Self::Literal> {
+        S::term_as_literal(object)
+    }
+
+    fn term_as_object(term: &Self::Term) -> Object {
+        S::term_as_object(term)
+    }
+
+    fn object_as_term(obj: &Object) -> Result<Self::Term, Self::Err> {
+        S::object_as_term(obj)
+    }
+
+    fn term_is_iri(object: &Self::Term) -> bool {
+        S::term_is_iri(object)
+    }
+
+    fn term_is_bnode(object: &Self::Term) -> bool {
+        S::term_is_bnode(object)
+    }
+
+    fn term_is_literal(object: &Self::Term) -> bool {
+        S::term_is_literal(object)
+    }
+
+    fn term_as_subject(object: &Self::Term) -> Option<Self::Subject> {
+        S::term_as_subject(object)
+    }
+
+    fn subject_as_term(subject: &Self::Subject) -> Self::Term {
+        S::subject_as_term(subject)
+    }
+
+    fn lexical_form(literal: &Self::Literal) -> &str {
+        S::lexical_form(literal)
+    }
+
+    fn lang(literal: &Self::Literal) -> Option<String> {
+        S::lang(literal)
+    }
+
+    fn datatype(literal: &Self::Literal) -> Self::IRI {
+        S::datatype(literal)
+    }
+
+    fn iri_s2iri(iri_s: &IriS) -> Self::IRI {
+        S::iri_s2iri(iri_s)
+    }
+
+    fn term_s2term(term: &OxTerm) -> Self::Term {
+        S::term_s2term(term)
+    }
+
+    fn bnode_id2bnode(id: &str) -> Self::BNode {
+        S::bnode_id2bnode(id)
+    }
+
+    fn iri_as_term(iri: Self::IRI) -> Self::Term {
+        S::iri_as_term(iri)
+    }
+
+    fn iri_as_subject(iri: Self::IRI) -> Self::Subject {
+        S::iri_as_subject(iri)
+    }
+
+    fn bnode_as_term(bnode: Self::BNode) -> Self::Term {
+        S::bnode_as_term(bnode)
+    }
+
+    fn bnode_as_subject(bnode: Self::BNode) -> Self::Subject {
+        S::bnode_as_subject(bnode)
+    }
+
+    fn iri2iri_s(iri: &Self::IRI) -> IriS {
+        S::iri2iri_s(iri)
+    }
+
+    fn qualify_iri(&self, iri: &Self::IRI) -> String {
+        self.inner.qualify_iri(iri)
+    }
+
+    fn qualify_subject(&self, subj: &Self::Subject) -> String {
+        self.inner.qualify_subject(subj)
+    }
+
+    fn qualify_term(&self, term: &Self::Term) -> String {
+        self.inner.qualify_term(term)
+    }
+
+    fn prefixmap(&self) -> Option<PrefixMap> {
+        self.inner.prefixmap()
+    }
+
+    fn resolve_prefix_local(&self, prefix: &str, local: &str) -> Result<IriS, PrefixMapError> {
+        self.inner.resolve_prefix_local(prefix, local)
+    }
+}
+
+impl<S: SRDF> SRDF for CachedSRDF<S> {
+    fn predicates_for_subject(
+        &self,
+        subject: &Self::Subject,
+    ) -> Result<HashSet<Self::IRI>, Self::Err> {
+        if !self.caches_subject(subject) {
+            return self.inner.predicates_for_subject(subject);
+        }
+        let arcs = self.outgoing_arcs(subject)?;
+        Ok(arcs.into_keys().collect())
+    }
+
+    fn objects_for_subject_predicate(
+        &self,
+        subject: &Self::Subject,
+        pred: &Self::IRI,
+    ) -> Result<HashSet<Self::Term>, Self::Err> {
+        if !self.caches_subject(subject) {
+            return self.inner.objects_for_subject_predicate(subject, pred);
+        }
+        let mut arcs = self.outgoing_arcs(subject)?;
+        Ok(arcs.remove(pred).unwrap_or_default())
+    }
+
+    fn subjects_with_predicate_object(
+        &self,
+        pred: &Self::IRI,
+        object: &Self::Term,
+    ) -> Result<HashSet<Self::Subject>, Self::Err> {
+        // Only answered from the cache when the incoming arcs of the object are already there,
+        // fetching all of them to get the subjects of one predicate may be too expensive
+        if self.caches_term(object) {
+            let mut entries = self.entries();
+            if let Some(arcs) = entries.incoming.get(object) {
+                let subjects = arcs.get(pred).cloned().unwrap_or_default();
+                entries.stats.hits += 1;
+                return Ok(subjects);
+            }
+        }
+        self.inner.subjects_with_predicate_object(pred, object)
+    }
+
+    fn triples_with_predicate(&self, pred: &Self::IRI) -> Result<Vec<Triple<Self>>, Self::Err> {
+        let triples = self.inner.triples_with_predicate(pred)?;
+        Ok(triples.into_iter().map(|t| t.cnv::<Self>()).collect())
+    }
+
+    fn outgoing_arcs(
+        &self,
+        subject: &Self::Subject,
+    ) -> Result<HasMapOfIriAndItem<Self::IRI, Self::Term>, Self::Err> {
+        if !self.caches_subject(subject) {
+            return self.inner.outgoing_arcs(subject);
+        }
+        if let Some(arcs) = self.cached_outgoing(subject) {
+            return Ok(arcs);
+        }
+        let arcs = self.inner.outgoing_arcs(subject)?;
+        self.cache_outgoing(subject.clone(), arcs.clone());
+        Ok(arcs)
+    }
+
+    fn incoming_arcs(
+        &self,
+        object: &Self::Term,
+    ) -> Result<HasMapOfIriAndItem<Self::IRI, Self::Subject>, Self::Err> {
+        if !self.caches_term(object) {
+            return self.inner.incoming_arcs(object);
+        }
+        {
+            let mut entries = self.entries();
+            if let Some(arcs) = entries.incoming.get(object).cloned() {
+                entries.stats.hits += 1;
+                return Ok(arcs);
+            }
+            entries.stats.misses += 1;
+        }
+        let arcs = self.inner.incoming_arcs(object)?;
+        let mut entries = self.entries();
+        let evicted = entries.incoming.insert(object.clone(), arcs.clone());
+        entries.stats.evictions += evicted;
+        Ok(arcs)
+    }
+
+    fn outgoing_arcs_from_list(
+        &self,
+        subject: &Self::Subject,
+        preds: &[Self::IRI],
+    ) -> Result<(OutgoingArcs<S>, Vec<Self::IRI>), Self::Err> {
+        if !self.caches_subject(subject) {
+            return self.inner.outgoing_arcs_from_list(subject, preds);
+        }
+        let mut remainder = Vec::new();
+        let mut arcs = self.outgoing_arcs(subject)?;
+        arcs.retain(|pred, _| {
+            let selected = preds.contains(pred);
+            if !selected {
+                remainder.push(pred.clone())
+            }
+            selected
+        });
+        Ok((arcs, remainder))
+    }
+
+    fn outgoing_arcs_for_subjects(
+        &self,
+        subjects: &[Self::Subject],
+    ) -> Result<HashMap<Self::Subject, OutgoingArcs<S>>, Self::Err> {
+        self.prefetch(subjects)?;
+        let mut result = HashMap::new();
+        for subject in subjects {
+            result.insert(subject.clone(), self.outgoing_arcs(subject)?);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RDFFormat, ReaderMode, SRDFGraph};
+    use iri_s::iri;
+
+    fn cached_graph(capacity: usize) -> CachedSRDF<SRDFGraph> {
+        let data = r#"prefix : <http://example.org/>
+            :alice :name "Alice" ; :knows :bob .
+            :bob :name "Bob" .
+        "#;
+        let graph =
+            SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        CachedSRDF::new(graph, capacity)
+    }
+
+    #[test]
+    fn test_cached_srdf_hits_and_misses() {
+        let cached = cached_graph(10);
+        let alice = SRDFGraph::iri_s2subject(&iri!("http://example.org/alice"));
+        let name = SRDFGraph::iri_s2iri(&iri!("http://example.org/name"));
+        assert_eq!(cached.predicates_for_subject(&alice).unwrap().len(), 2);
+        assert_eq!(
+            cached
+                .objects_for_subject_predicate(&alice, &name)
+                .unwrap()
+                .len(),
+            1
+        );
+        let (arcs, remainder) = cached
+            .outgoing_arcs_from_list(&alice, std::slice::from_ref(&name))
+            .unwrap();
+        assert_eq!(arcs.len(), 1);
+        assert_eq!(remainder.len(), 1);
+        let stats = cached.stats();
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.hits, 2);
+    }
+
+    #[test]
+    fn test_cached_srdf_prefetch_and_eviction() {
+        let cached = cached_graph(1);
+        let alice = SRDFGraph::iri_s2subject(&iri!("http://example.org/alice"));
+        let bob = SRDFGraph::iri_s2subject(&iri!("http://example.org/bob"));
+        cached.prefetch(&[alice.clone(), bob.clone()]).unwrap();
+        cached.outgoing_arcs(&bob).unwrap();
+        let stats = cached.stats();
+        assert_eq!(stats.prefetched, 2);
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.hits, 1);
+    }
+
+    #[test]
+    fn test_cached_srdf_persistence() {
+        let path = std::env::temp_dir().join(format!("rudof_cache_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let alice = SRDFGraph::iri_s2subject(&iri!("http://example.org/alice"));
+        let cached = cached_graph(10).with_persistence(&path, "http://example.org/sparql");
+        cached.outgoing_arcs(&alice).unwrap();
+        cached.save().unwrap();
+
+        // A cache over an empty graph answers from the persisted entries
+        let reloaded = CachedSRDF::new(SRDFGraph::new(), 10)
+            .with_persistence(&path, "http://example.org/sparql")
+            .with_ttl(Duration::from_secs(3600));
+        assert!(reloaded.load().unwrap());
+        assert_eq!(reloaded.predicates_for_subject(&alice).unwrap().len(), 2);
+        assert_eq!(reloaded.stats().hits, 1);
+
+        // Entries of another endpoint or that have expired are not loaded
+        let other = CachedSRDF::new(SRDFGraph::new(), 10)
+            .with_persistence(&path, "http://example.org/other");
+        assert!(!other.load().unwrap());
+        let expired = CachedSRDF::new(SRDFGraph::new(), 10)
+            .with_persistence(&path, "http://example.org/sparql")
+            .with_ttl(Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(1100));
+        assert!(!expired.load().unwrap());
+
+        reloaded.clear_persisted().unwrap();
+        assert!(!path.exists());
+    }
+}
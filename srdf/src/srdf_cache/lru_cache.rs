@@ -0,0 +1,105 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Map with a maximum number of entries that evicts the least recently used entry when it is full.
+/// A cache with capacity 0 doesn't keep any entry
+#[derive(Debug, Clone)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, (V, u64)>,
+
+    /// Keys indexed by the time they were last used
+    recency: BTreeMap<u64, K>,
+    clock: u64,
+}
+
+impl<K, V> LruCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn new(capacity: usize) -> LruCache<K, V> {
+        LruCache {
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Gets the value of a key marking it as the most recently used
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.clock += 1;
+        let (value, used) = self.entries.get_mut(key)?;
+        self.recency.remove(used);
+        *used = self.clock;
+        self.recency.insert(self.clock, key.clone());
+        Some(value)
+    }
+
+    /// Inserts a value returning the number of entries that were evicted
+    pub fn insert(&mut self, key: K, value: V) -> usize {
+        if self.capacity == 0 {
+            return 0;
+        }
+        self.clock += 1;
+        if let Some((_, used)) = self.entries.insert(key.clone(), (value, self.clock)) {
+            self.recency.remove(&used);
+        }
+        self.recency.insert(self.clock, key);
+        let mut evicted = 0;
+        while self.entries.len() > self.capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                self.entries.remove(&oldest);
+                evicted += 1;
+            }
+        }
+        evicted
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    /// Iterates over the entries from the least to the most recently used
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.recency.values().filter_map(|key| {
+            self.entries
+                .get_key_value(key)
+                .map(|(key, (value, _))| (key, value))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LruCache;
+
+    #[test]
+    fn test_lru_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.insert("c", 3), 1);
+        assert!(!cache.contains_key(&"b"));
+        let keys: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec!["a", "c"]);
+    }
+}
@@ -0,0 +1,17 @@
+//! Caching of the neighbourhoods of nodes obtained from an [`crate::SRDF`] backend.
+//!
+//! [`CachedSRDF`] decorates any backend, like a SPARQL endpoint, keeping the outgoing and
+//! incoming arcs of the most recently used nodes in memory so that repeated lookups during a
+//! validation don't reach the backend again. The neighbourhoods of many nodes can be prefetched
+//! at once and the cache can be persisted in a file to be reused by later runs.
+pub mod cache_config;
+pub mod cache_stats;
+pub mod cached_srdf;
+pub mod lru_cache;
+pub mod srdf_cache_error;
+
+pub use cache_config::*;
+pub use cache_stats::*;
+pub use cached_srdf::*;
+pub use lru_cache::*;
+pub use srdf_cache_error::*;
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SRDFCacheError {
+    #[error("Reading cache from {path}: {error}")]
+    Reading { path: String, error: String },
+
+    #[error("Writing cache to {path}: {error}")]
+    Writing { path: String, error: String },
+}
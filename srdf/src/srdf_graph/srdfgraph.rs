@@ -7,11 +7,13 @@ use crate::async_srdf::AsyncSRDF;
 use crate::literal::Literal;
 use crate::numeric_literal::NumericLiteral;
 use crate::{FocusRDF, RDFFormat, SRDFBasic, SRDFBuilder, Triple as STriple, RDF_TYPE_STR, SRDF};
-use oxrdfio::{RdfFormat, RdfSerializer};
+use indexmap::IndexMap;
+use oxrdfio::{JsonLdProfileSet, LoadedDocument, RdfFormat, RdfParser, RdfSerializer};
 use oxrdfxml::RdfXmlParser;
 use rust_decimal::Decimal;
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
 use std::fs::File;
 use std::io::{self, BufReader, Write};
 use std::path::{Path, PathBuf};
@@ -22,16 +24,24 @@ use crate::srdfgraph_error::SRDFGraphError;
 use crate::Object;
 use oxrdf::{
     BlankNode as OxBlankNode, Graph, GraphName, Literal as OxLiteral, NamedNode as OxNamedNode,
-    Quad, Subject as OxSubject, Term as OxTerm, Triple as OxTriple, TripleRef,
+    NamedOrBlankNode as OxNamedOrBlankNode, Quad, Subject as OxSubject, Term as OxTerm,
+    Triple as OxTriple, TripleRef,
 };
 use oxsdatatypes::Decimal as OxDecimal;
-use oxttl::{NQuadsParser, NTriplesParser, TurtleParser};
+use oxttl::{NTriplesParser, TurtleParser};
 use prefixmap::{prefixmap::*, IriRef, PrefixMapError};
 
+/// In-memory RDF dataset with a default graph and a set of named graphs
+///
+/// The SRDF queries are answered by the graphs chosen by its [`GraphSelector`],
+/// which is the default graph unless another one is selected.
 #[derive(Debug, Default, Clone)]
 pub struct SRDFGraph {
     focus: Option<OxTerm>,
     graph: Graph,
+    named_graphs: IndexMap<OxNamedOrBlankNode, Graph>,
+    graph_selector: GraphSelector,
+    jsonld_contexts: HashMap<String, String>,
     pm: PrefixMap,
     base: Option<IriS>,
 }
@@ -41,50 +51,217 @@ impl SRDFGraph {
         Self::default()
     }
 
+    /// Creates a graph whose default graph contains `triples`
+    pub fn from_triples<I: IntoIterator<Item = OxTriple>>(triples: I) -> Self {
+        let mut graph = Graph::new();
+        for triple in triples {
+            graph.insert(&triple);
+        }
+        SRDFGraph {
+            graph,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a dataset that contains `quads` in their graphs
+    pub fn from_quads<I: IntoIterator<Item = Quad>>(quads: I) -> Self {
+        let mut graph = SRDFGraph::default();
+        for quad in quads {
+            graph.insert_quad(quad)
+        }
+        graph
+    }
+
+    /// Number of triples in the default graph and all the named graphs
     pub fn len(&self) -> usize {
-        self.graph.len()
+        self.graph.len() + self.named_graphs.values().map(Graph::len).sum::<usize>()
     }
 
+    /// Quads of the default graph and all the named graphs
     pub fn quads(&self) -> impl Iterator<Item = Quad> + '_ {
-        let graph_name = GraphName::DefaultGraph;
-        self.graph
+        let default_quads = self
+            .graph
             .iter()
-            .map(move |t| triple_to_quad(t, graph_name.clone()))
+            .map(|t| triple_to_quad(t, GraphName::DefaultGraph));
+        let named_quads = self.named_graphs.iter().flat_map(|(name, graph)| {
+            graph
+                .iter()
+                .map(move |t| triple_to_quad(t, GraphName::from(name.clone())))
+        });
+        default_quads.chain(named_quads)
     }
 
     pub fn is_empty(&self) -> bool {
-        self.graph.is_empty()
+        self.len() == 0
     }
 
-    pub fn merge_from_reader<R: io::Read>(
-        &mut self,
+    /// Names of the named graphs in the order in which they were added
+    pub fn graph_names(&self) -> impl Iterator<Item = &OxNamedOrBlankNode> {
+        self.named_graphs.keys()
+    }
+
+    /// Checks if there is a named graph with that name
+    pub fn has_named_graph(&self, name: &IriS) -> bool {
+        self.named_graphs
+            .contains_key(&OxNamedOrBlankNode::from(Self::cnv_iri(name.clone())))
+    }
+
+    pub fn graph_selector(&self) -> &GraphSelector {
+        &self.graph_selector
+    }
+
+    /// Selects the graphs used to answer the SRDF queries.
+    /// Triples added with [`SRDFBuilder`] go to the selected named graph or to the default graph otherwise
+    pub fn set_graph_selector(&mut self, graph_selector: GraphSelector) {
+        self.graph_selector = graph_selector
+    }
+
+    pub fn with_graph_selector(mut self, graph_selector: GraphSelector) -> Self {
+        self.graph_selector = graph_selector;
+        self
+    }
+
+    /// Adds a local document for the JSON-LD remote context with that IRI.
+    /// JSON-LD remote contexts are never fetched from the network, so they must be added before parsing
+    pub fn add_jsonld_context(&mut self, iri: &str, document: &str) {
+        self.jsonld_contexts
+            .insert(iri.to_string(), document.to_string());
+    }
+
+    #[cfg(feature = "rdf-star")]
+    fn qualify_triple(&self, triple: &OxTriple) -> String {
+        format!(
+            "<< {} {} {} >>",
+            self.qualify_subject(&triple.subject),
+            self.qualify_iri(&triple.predicate),
+            self.qualify_term(&triple.object)
+        )
+    }
+
+    fn selected_graphs(&self) -> Vec<&Graph> {
+        match &self.graph_selector {
+            GraphSelector::DefaultGraph => vec![&self.graph],
+            GraphSelector::NamedGraph(name) => self
+                .named_graphs
+                .get(&OxNamedOrBlankNode::from(Self::cnv_iri(name.clone())))
+                .into_iter()
+                .collect(),
+            GraphSelector::Union => std::iter::once(&self.graph)
+                .chain(self.named_graphs.values())
+                .collect(),
+        }
+    }
+
+    fn selected_graph_mut(&mut self) -> &mut Graph {
+        match &self.graph_selector {
+            GraphSelector::NamedGraph(name) => self
+                .named_graphs
+                .entry(Self::cnv_iri(name.clone()).into())
+                .or_default(),
+            _ => &mut self.graph,
+        }
+    }
+
+    fn insert_quad(&mut self, quad: Quad) {
+        let graph = match &quad.graph_name {
+            GraphName::DefaultGraph => &mut self.graph,
+            GraphName::NamedNode(name) => self.named_graphs.entry(name.clone().into()).or_default(),
+            GraphName::BlankNode(name) => self.named_graphs.entry(name.clone().into()).or_default(),
+        };
+        graph.insert(&OxTriple::from(quad));
+    }
+
+    /// Quads of the default graph and all the named graphs whose blank nodes are replaced by fresh ones,
+    /// so that they don't clash with the blank nodes of other documents when they are merged
+    pub fn quads_with_fresh_blank_nodes(&self) -> impl Iterator<Item = Quad> + '_ {
+        let mut fresh = HashMap::new();
+        self.quads().map(move |quad| Quad {
+            subject: rename_subject(quad.subject, &mut fresh),
+            predicate: quad.predicate,
+            object: rename_term(quad.object, &mut fresh),
+            graph_name: match quad.graph_name {
+                GraphName::BlankNode(bnode) => {
+                    GraphName::BlankNode(rename_blank_node(&bnode, &mut fresh))
+                }
+                graph_name => graph_name,
+            },
+        })
+    }
+
+    /// Adds the quads and prefixes of `other` to this dataset.
+    /// The blank nodes of `other` are renamed, so they are different from the ones of this dataset
+    pub fn merge_graph(&mut self, other: &SRDFGraph) -> Result<(), SRDFGraphError> {
+        for quad in other.quads_with_fresh_blank_nodes() {
+            self.insert_quad(quad)
+        }
+        self.merge_prefixes(other.prefixmap())
+    }
+
+    /// Removes the quads from their graphs, returning how many of them were in the dataset
+    pub fn remove_quads<I: IntoIterator<Item = Quad>>(&mut self, quads: I) -> usize {
+        let mut removed = 0;
+        for quad in quads {
+            let graph = match &quad.graph_name {
+                GraphName::DefaultGraph => Some(&mut self.graph),
+                GraphName::NamedNode(name) => self
+                    .named_graphs
+                    .get_mut(&OxNamedOrBlankNode::from(name.clone())),
+                GraphName::BlankNode(name) => self
+                    .named_graphs
+                    .get_mut(&OxNamedOrBlankNode::from(name.clone())),
+            };
+            if let Some(graph) = graph {
+                if graph.remove(&OxTriple::from(quad)) {
+                    removed += 1
+                }
+            }
+        }
+        removed
+    }
+
+    /// Removes the triples of the default graph and the named graphs keeping the prefixes
+    pub fn clear(&mut self) {
+        self.graph.clear();
+        self.named_graphs.clear();
+    }
+
+    /// Parses a document calling `on_quad` with each quad as soon as it is read, so the document is never kept in memory,
+    /// and returns the prefixes declared in it. The remote contexts of JSON-LD documents are taken from `jsonld_contexts`
+    pub fn parse_quads<R, F, E>(
         read: R,
         format: &RDFFormat,
         base: Option<&str>,
         reader_mode: &ReaderMode,
-    ) -> Result<(), SRDFGraphError> {
+        jsonld_contexts: &HashMap<String, String>,
+        mut on_quad: F,
+    ) -> Result<PrefixMap, E>
+    where
+        R: io::Read,
+        F: FnMut(Quad) -> Result<(), E>,
+        E: From<SRDFGraphError>,
+    {
         match format {
             RDFFormat::Turtle => {
                 let turtle_parser = match base {
                     None => TurtleParser::new(),
-                    Some(iri) => TurtleParser::new().with_base_iri(iri)?,
+                    Some(iri) => TurtleParser::new()
+                        .with_base_iri(iri)
+                        .map_err(SRDFGraphError::from)?,
                 };
-                // let mut graph = Graph::default();
+                #[cfg(feature = "rdf-star")]
+                let turtle_parser = turtle_parser.with_quoted_triples();
                 let mut reader = turtle_parser.for_reader(read);
                 for triple_result in reader.by_ref() {
-                    self.graph.insert(triple_result?.as_ref());
+                    let triple = triple_result.map_err(SRDFGraphError::from)?;
+                    on_quad(triple.in_graph(GraphName::DefaultGraph))?;
                 }
                 let prefixes: HashMap<&str, &str> = reader.prefixes().collect();
-                self.base = match (&self.base, base) {
-                    (None, None) => None,
-                    (Some(b), None) => Some(b.clone()),
-                    (_, Some(b)) => Some(IriS::new_unchecked(b)),
-                };
-                let pm = PrefixMap::from_hashmap(&prefixes)?;
-                self.merge_prefixes(pm)?;
+                Ok(PrefixMap::from_hashmap(&prefixes).map_err(SRDFGraphError::from)?)
             }
             RDFFormat::NTriples => {
                 let parser = NTriplesParser::new();
+                #[cfg(feature = "rdf-star")]
+                let parser = parser.with_quoted_triples();
                 let mut reader = parser.for_reader(read);
                 for triple_result in reader.by_ref() {
                     match triple_result {
@@ -93,16 +270,16 @@ impl SRDFGraph {
                                 return Err(SRDFGraphError::TurtleError {
                                     data: "Reading n-quads".to_string(),
                                     turtle_error: e,
-                                });
+                                }
+                                .into());
                             } else {
                                 debug!("Error captured: {e:?}")
                             }
                         }
-                        Ok(t) => {
-                            self.graph.insert(t.as_ref());
-                        }
+                        Ok(t) => on_quad(t.in_graph(GraphName::DefaultGraph))?,
                     }
                 }
+                Ok(PrefixMap::new())
             }
             RDFFormat::RDFXML => {
                 let parser = RdfXmlParser::new();
@@ -112,29 +289,111 @@ impl SRDFGraph {
                         Err(e) => {
                             debug!("Error captured: {e:?}")
                         }
-                        Ok(t) => {
-                            self.graph.insert(t.as_ref());
-                        }
+                        Ok(t) => on_quad(t.in_graph(GraphName::DefaultGraph))?,
                     }
                 }
+                Ok(PrefixMap::new())
             }
-            RDFFormat::TriG => todo!(),
-            RDFFormat::N3 => todo!(),
-            RDFFormat::NQuads => {
-                let parser = NQuadsParser::new();
-                let mut reader = parser.for_reader(read);
-                for triple_result in reader.by_ref() {
-                    match triple_result {
-                        Err(e) => {
-                            debug!("Error captured: {e:?}")
-                        }
-                        Ok(t) => {
-                            self.graph.insert(t.as_ref());
-                        }
+            RDFFormat::TriG => Self::parse_dataset_quads(
+                read,
+                RdfFormat::TriG,
+                base,
+                reader_mode,
+                jsonld_contexts,
+                on_quad,
+            ),
+            RDFFormat::N3 => Self::parse_dataset_quads(
+                read,
+                RdfFormat::N3,
+                base,
+                reader_mode,
+                jsonld_contexts,
+                on_quad,
+            ),
+            RDFFormat::NQuads => Self::parse_dataset_quads(
+                read,
+                RdfFormat::NQuads,
+                base,
+                reader_mode,
+                jsonld_contexts,
+                on_quad,
+            ),
+            RDFFormat::JsonLd => Self::parse_dataset_quads(
+                read,
+                JSON_LD,
+                base,
+                reader_mode,
+                jsonld_contexts,
+                on_quad,
+            ),
+        }
+    }
+
+    fn parse_dataset_quads<R, F, E>(
+        read: R,
+        format: RdfFormat,
+        base: Option<&str>,
+        reader_mode: &ReaderMode,
+        jsonld_contexts: &HashMap<String, String>,
+        mut on_quad: F,
+    ) -> Result<PrefixMap, E>
+    where
+        R: io::Read,
+        F: FnMut(Quad) -> Result<(), E>,
+        E: From<SRDFGraphError>,
+    {
+        let parser = match base {
+            None => RdfParser::from_format(format),
+            Some(iri) => RdfParser::from_format(format)
+                .with_base_iri(iri)
+                .map_err(SRDFGraphError::from)?,
+        };
+        let contexts = jsonld_contexts.clone();
+        let mut reader = parser.for_reader(read).with_document_loader(move |iri| {
+            match contexts.get(iri) {
+                Some(document) => Ok(LoadedDocument {
+                    url: iri.to_string(),
+                    content: document.clone().into_bytes(),
+                    format: JSON_LD,
+                }),
+                None => Err(format!(
+                    "JSON-LD context {iri} not found. Remote contexts are not fetched, they must be provided locally"
+                )
+                .into()),
+            }
+        });
+        for quad_result in reader.by_ref() {
+            match quad_result {
+                Err(e) => {
+                    if reader_mode.is_strict() {
+                        return Err(SRDFGraphError::RDFParseError { err: e }.into());
+                    } else {
+                        debug!("Error captured: {e:?}")
                     }
                 }
+                Ok(quad) => on_quad(quad)?,
             }
         }
+        let prefixes: HashMap<&str, &str> = reader.prefixes().collect();
+        Ok(PrefixMap::from_hashmap(&prefixes).map_err(SRDFGraphError::from)?)
+    }
+
+    pub fn merge_from_reader<R: io::Read>(
+        &mut self,
+        read: R,
+        format: &RDFFormat,
+        base: Option<&str>,
+        reader_mode: &ReaderMode,
+    ) -> Result<(), SRDFGraphError> {
+        let contexts = self.jsonld_contexts.clone();
+        let pm = Self::parse_quads(read, format, base, reader_mode, &contexts, |quad| {
+            self.insert_quad(quad);
+            Ok::<(), SRDFGraphError>(())
+        })?;
+        if let Some(b) = base {
+            self.base = Some(IriS::new_unchecked(b))
+        }
+        self.merge_prefixes(pm)?;
         Ok(())
     }
 
@@ -297,7 +556,7 @@ impl SRDFBasic for SRDFGraph {
             OxSubject::NamedNode(n) => OxTerm::NamedNode(n.clone()),
             OxSubject::BlankNode(b) => OxTerm::BlankNode(b.clone()),
             #[cfg(feature = "rdf-star")]
-            OxSubject::Triple(_) => unimplemented!(),
+            OxSubject::Triple(t) => OxTerm::Triple(t.clone()),
         }
     }
 
@@ -305,6 +564,8 @@ impl SRDFBasic for SRDFGraph {
         match object {
             OxTerm::NamedNode(n) => Some(OxSubject::NamedNode(n.clone())),
             OxTerm::BlankNode(b) => Some(OxSubject::BlankNode(b.clone())),
+            #[cfg(feature = "rdf-star")]
+            OxTerm::Triple(t) => Some(OxSubject::Triple(t.clone())),
             _ => None,
         }
     }
@@ -366,7 +627,11 @@ impl SRDFBasic for SRDFGraph {
             }
             OxTerm::NamedNode(iri) => Object::Iri(Self::iri2iri_s(iri)),
             #[cfg(feature = "rdf-star")]
-            OxTerm::Triple(_) => unimplemented!(),
+            OxTerm::Triple(triple) => Object::triple(
+                Self::term_as_object(&OxTerm::from(triple.subject.clone())),
+                Self::iri2iri_s(&triple.predicate),
+                Self::term_as_object(&triple.object),
+            ),
         }
     }
 
@@ -385,7 +650,7 @@ impl SRDFBasic for SRDFGraph {
             OxSubject::BlankNode(bn) => self.show_blanknode(bn),
             OxSubject::NamedNode(n) => self.qualify_iri(n),
             #[cfg(feature = "rdf-star")]
-            OxSubject::Triple(_) => unimplemented!(),
+            OxSubject::Triple(triple) => self.qualify_triple(triple),
         }
     }
 
@@ -395,7 +660,7 @@ impl SRDFBasic for SRDFGraph {
             OxTerm::Literal(lit) => self.show_literal(lit),
             OxTerm::NamedNode(n) => self.qualify_iri(n),
             #[cfg(feature = "rdf-star")]
-            OxTerm::Triple(_) => unimplemented!(),
+            OxTerm::Triple(triple) => self.qualify_triple(triple),
         }
     }
 
@@ -411,39 +676,60 @@ impl SRDFBasic for SRDFGraph {
         OxTerm::BlankNode(bnode)
     }
 
-    fn object_as_term(obj: &Object) -> Self::Term {
-        match obj {
-            Object::Iri(iri) => Self::iri_s2term(iri),
-            Object::BlankNode(bn) => Self::bnode_id2term(bn),
-            Object::Literal(lit) => {
-                let literal: OxLiteral = match lit {
-                    Literal::StringLiteral { lexical_form, lang } => match lang {
-                        Some(lang) => OxLiteral::new_language_tagged_literal_unchecked(
+    fn object_as_term(obj: &Object) -> Result<Self::Term, Self::Err> {
+        let term =
+            match obj {
+                Object::Iri(iri) => Self::iri_s2term(iri),
+                Object::BlankNode(bn) => Self::bnode_id2term(bn),
+                Object::Literal(lit) => {
+                    let literal: OxLiteral = match lit {
+                        Literal::StringLiteral { lexical_form, lang } => match lang {
+                            Some(lang) => OxLiteral::new_language_tagged_literal_unchecked(
+                                lexical_form,
+                                lang.value(),
+                            ),
+                            None => OxLiteral::new_simple_literal(lexical_form),
+                        },
+                        Literal::DatatypeLiteral {
                             lexical_form,
-                            lang.to_string(),
-                        ),
-                        None => OxLiteral::new_simple_literal(lexical_form),
-                    },
-                    Literal::DatatypeLiteral {
-                        lexical_form,
-                        datatype,
-                    } => OxLiteral::new_typed_literal(lexical_form, cnv_iri_ref(datatype)),
-                    Literal::NumericLiteral(n) => match n {
-                        NumericLiteral::Integer(n) => {
-                            let n: i128 = *n as i128;
-                            OxLiteral::from(n)
-                        }
-                        NumericLiteral::Decimal(d) => {
-                            let decimal = cnv_decimal(d);
-                            OxLiteral::from(decimal)
-                        }
-                        NumericLiteral::Double(d) => OxLiteral::from(*d),
-                    },
-                    Literal::BooleanLiteral(b) => OxLiteral::from(*b),
-                };
-                OxTerm::Literal(literal)
-            }
-        }
+                            datatype,
+                        } => OxLiteral::new_typed_literal(lexical_form, cnv_iri_ref(datatype)),
+                        Literal::NumericLiteral(n) => match n {
+                            NumericLiteral::Integer(n) => {
+                                let n: i128 = *n as i128;
+                                OxLiteral::from(n)
+                            }
+                            NumericLiteral::Decimal(d) => cnv_decimal(d),
+                            NumericLiteral::Double(d) => OxLiteral::from(*d),
+                        },
+                        Literal::BooleanLiteral(b) => OxLiteral::from(*b),
+                    };
+                    OxTerm::Literal(literal)
+                }
+                #[cfg(feature = "rdf-star")]
+                Object::Triple {
+                    subject,
+                    predicate,
+                    object,
+                } => {
+                    let subject = Self::term_as_subject(&Self::object_as_term(subject)?)
+                        .ok_or_else(|| SRDFGraphError::QuotedTripleLiteralSubject {
+                            triple: obj.to_string(),
+                        })?;
+                    OxTerm::Triple(Box::new(OxTriple::new(
+                        subject,
+                        Self::iri_s2iri(predicate),
+                        Self::object_as_term(object)?,
+                    )))
+                }
+                #[cfg(not(feature = "rdf-star"))]
+                Object::Triple { .. } => {
+                    return Err(SRDFGraphError::QuotedTripleWithoutRdfStar {
+                        triple: obj.to_string(),
+                    })
+                }
+            };
+        Ok(term)
     }
 
     fn bnode_as_subject(bnode: Self::BNode) -> Self::Subject {
@@ -455,8 +741,46 @@ fn cnv_iri_ref(iri_ref: &IriRef) -> OxNamedNode {
     OxNamedNode::new_unchecked(iri_ref.to_string())
 }
 
-fn cnv_decimal(_d: &Decimal) -> OxDecimal {
-    todo!()
+fn cnv_decimal(d: &Decimal) -> OxLiteral {
+    // Both types share the xsd:decimal lexical form, but OxDecimal has less precision
+    match OxDecimal::from_str(&d.to_string()) {
+        Ok(decimal) => OxLiteral::from(decimal),
+        Err(_) => OxLiteral::new_typed_literal(d.to_string(), oxrdf::vocab::xsd::DECIMAL),
+    }
+}
+
+fn rename_blank_node(
+    bnode: &OxBlankNode,
+    fresh: &mut HashMap<OxBlankNode, OxBlankNode>,
+) -> OxBlankNode {
+    fresh.entry(bnode.clone()).or_default().clone()
+}
+
+fn rename_subject(subject: OxSubject, fresh: &mut HashMap<OxBlankNode, OxBlankNode>) -> OxSubject {
+    match subject {
+        OxSubject::BlankNode(bnode) => OxSubject::BlankNode(rename_blank_node(&bnode, fresh)),
+        #[cfg(feature = "rdf-star")]
+        OxSubject::Triple(triple) => OxSubject::Triple(Box::new(rename_triple(*triple, fresh))),
+        subject => subject,
+    }
+}
+
+fn rename_term(term: OxTerm, fresh: &mut HashMap<OxBlankNode, OxBlankNode>) -> OxTerm {
+    match term {
+        OxTerm::BlankNode(bnode) => OxTerm::BlankNode(rename_blank_node(&bnode, fresh)),
+        #[cfg(feature = "rdf-star")]
+        OxTerm::Triple(triple) => OxTerm::Triple(Box::new(rename_triple(*triple, fresh))),
+        term => term,
+    }
+}
+
+#[cfg(feature = "rdf-star")]
+fn rename_triple(triple: OxTriple, fresh: &mut HashMap<OxBlankNode, OxBlankNode>) -> OxTriple {
+    OxTriple::new(
+        rename_subject(triple.subject, fresh),
+        triple.predicate,
+        rename_term(triple.object, fresh),
+    )
 }
 
 impl SRDF for SRDFGraph {
@@ -465,9 +789,11 @@ impl SRDF for SRDFGraph {
         subject: &Self::Subject,
     ) -> Result<HashSet<Self::IRI>, Self::Err> {
         let mut ps = HashSet::new();
-        for triple in self.graph.triples_for_subject(subject) {
-            let pred = triple.predicate.into_owned();
-            ps.insert(pred);
+        for graph in self.selected_graphs() {
+            for triple in graph.triples_for_subject(subject) {
+                let pred = triple.predicate.into_owned();
+                ps.insert(pred);
+            }
         }
         Ok(ps)
     }
@@ -479,8 +805,10 @@ impl SRDF for SRDFGraph {
     ) -> Result<HashSet<Self::Term>, Self::Err> {
         let predicate = pred.as_ref();
         let mut result = HashSet::new();
-        for o in self.graph.objects_for_subject_predicate(subject, predicate) {
-            result.insert(o.into_owned());
+        for graph in self.selected_graphs() {
+            for o in graph.objects_for_subject_predicate(subject, predicate) {
+                result.insert(o.into_owned());
+            }
         }
         Ok(result)
     }
@@ -491,11 +819,10 @@ impl SRDF for SRDFGraph {
         object: &Self::Term,
     ) -> Result<HashSet<Self::Subject>, Self::Err> {
         let mut result = HashSet::new();
-        for subj in self
-            .graph
-            .subjects_for_predicate_object(pred.as_ref(), object.as_ref())
-        {
-            result.insert(subj.into_owned());
+        for graph in self.selected_graphs() {
+            for subj in graph.subjects_for_predicate_object(pred.as_ref(), object.as_ref()) {
+                result.insert(subj.into_owned());
+            }
         }
         Ok(result)
     }
@@ -505,15 +832,17 @@ impl SRDF for SRDFGraph {
         subject: &Self::Subject,
     ) -> Result<HashMap<Self::IRI, HashSet<Self::Term>>, Self::Err> {
         let mut results: HashMap<Self::IRI, HashSet<Self::Term>> = HashMap::new();
-        for triple in self.graph.triples_for_subject(subject) {
-            let pred = triple.predicate.into_owned();
-            let term = triple.object.into_owned();
-            match results.entry(pred) {
-                Entry::Occupied(mut vs) => {
-                    vs.get_mut().insert(term.clone());
-                }
-                Entry::Vacant(vacant) => {
-                    vacant.insert(HashSet::from([term.clone()]));
+        for graph in self.selected_graphs() {
+            for triple in graph.triples_for_subject(subject) {
+                let pred = triple.predicate.into_owned();
+                let term = triple.object.into_owned();
+                match results.entry(pred) {
+                    Entry::Occupied(mut vs) => {
+                        vs.get_mut().insert(term.clone());
+                    }
+                    Entry::Vacant(vacant) => {
+                        vacant.insert(HashSet::from([term.clone()]));
+                    }
                 }
             }
         }
@@ -525,15 +854,17 @@ impl SRDF for SRDFGraph {
         object: &Self::Term,
     ) -> Result<HashMap<Self::IRI, HashSet<Self::Subject>>, Self::Err> {
         let mut results: HashMap<Self::IRI, HashSet<Self::Subject>> = HashMap::new();
-        for triple in self.graph.triples_for_object(object) {
-            let pred = triple.predicate.into_owned();
-            let subj = triple.subject.into_owned();
-            match results.entry(pred) {
-                Entry::Occupied(mut vs) => {
-                    vs.get_mut().insert(subj.clone());
-                }
-                Entry::Vacant(vacant) => {
-                    vacant.insert(HashSet::from([subj.clone()]));
+        for graph in self.selected_graphs() {
+            for triple in graph.triples_for_object(object) {
+                let pred = triple.predicate.into_owned();
+                let subj = triple.subject.into_owned();
+                match results.entry(pred) {
+                    Entry::Occupied(mut vs) => {
+                        vs.get_mut().insert(subj.clone());
+                    }
+                    Entry::Vacant(vacant) => {
+                        vacant.insert(HashSet::from([subj.clone()]));
+                    }
                 }
             }
         }
@@ -547,20 +878,22 @@ impl SRDF for SRDFGraph {
     ) -> Result<(HashMap<Self::IRI, HashSet<Self::Term>>, Vec<Self::IRI>), Self::Err> {
         let mut results: HashMap<Self::IRI, HashSet<Self::Term>> = HashMap::new();
         let mut remainder = Vec::new();
-        for triple in self.graph.triples_for_subject(subject) {
-            let pred = triple.predicate.into_owned();
-            let term = triple.object.into_owned();
-            if preds.contains(&pred) {
-                match results.entry(pred) {
-                    Entry::Occupied(mut vs) => {
-                        vs.get_mut().insert(term.clone());
-                    }
-                    Entry::Vacant(vacant) => {
-                        vacant.insert(HashSet::from([term.clone()]));
+        for graph in self.selected_graphs() {
+            for triple in graph.triples_for_subject(subject) {
+                let pred = triple.predicate.into_owned();
+                let term = triple.object.into_owned();
+                if preds.contains(&pred) {
+                    match results.entry(pred) {
+                        Entry::Occupied(mut vs) => {
+                            vs.get_mut().insert(term.clone());
+                        }
+                        Entry::Vacant(vacant) => {
+                            vacant.insert(HashSet::from([term.clone()]));
+                        }
                     }
+                } else {
+                    remainder.push(pred)
                 }
-            } else {
-                remainder.push(pred)
             }
         }
         Ok((results, remainder))
@@ -571,11 +904,17 @@ impl SRDF for SRDFGraph {
         pred: &Self::IRI,
     ) -> Result<Vec<crate::Triple<Self>>, Self::Err> {
         let mut result = Vec::new();
-        for triple in self.graph.triples_for_predicate(pred) {
-            let subj = triple.subject.into_owned();
-            let pred = triple.predicate.into_owned();
-            let obj = triple.object.into_owned();
-            result.push(STriple::new(subj, pred, obj))
+        let mut seen = HashSet::new();
+        for graph in self.selected_graphs() {
+            for triple in graph.triples_for_predicate(pred) {
+                // The same triple can be in more than one of the selected graphs
+                if seen.insert(triple) {
+                    let subj = triple.subject.into_owned();
+                    let pred = triple.predicate.into_owned();
+                    let obj = triple.object.into_owned();
+                    result.push(STriple::new(subj, pred, obj))
+                }
+            }
         }
         Ok(result)
     }
@@ -595,9 +934,11 @@ impl AsyncSRDF for SRDFGraph {
         subject: &OxSubject,
     ) -> Result<HashSet<OxNamedNode>, SRDFGraphError> {
         let mut results = HashSet::new();
-        for triple in self.graph.triples_for_subject(subject) {
-            let predicate: OxNamedNode = triple.predicate.to_owned().into();
-            results.insert(predicate);
+        for graph in self.selected_graphs() {
+            for triple in graph.triples_for_subject(subject) {
+                let predicate: OxNamedNode = triple.predicate.to_owned().into();
+                results.insert(predicate);
+            }
         }
         Ok(results)
     }
@@ -608,11 +949,13 @@ impl AsyncSRDF for SRDFGraph {
         pred: &OxNamedNode,
     ) -> Result<HashSet<OxTerm>, SRDFGraphError> {
         let mut results = HashSet::new();
-        for triple in self.graph.triples_for_subject(subject) {
-            let predicate: OxNamedNode = triple.predicate.to_owned().into();
-            if predicate.eq(pred) {
-                let object: OxTerm = triple.object.to_owned().into();
-                results.insert(object);
+        for graph in self.selected_graphs() {
+            for triple in graph.triples_for_subject(subject) {
+                let predicate: OxNamedNode = triple.predicate.to_owned().into();
+                if predicate.eq(pred) {
+                    let object: OxTerm = triple.object.to_owned().into();
+                    results.insert(object);
+                }
             }
         }
         Ok(results)
@@ -624,15 +967,68 @@ impl AsyncSRDF for SRDFGraph {
         pred: &OxNamedNode,
     ) -> Result<HashSet<OxSubject>, SRDFGraphError> {
         let mut results = HashSet::new();
-        for triple in self.graph.triples_for_object(object) {
-            let predicate: OxNamedNode = triple.predicate.to_owned().into();
-            if predicate.eq(pred) {
-                let subject: OxSubject = triple.subject.to_owned().into();
-                results.insert(subject);
+        for graph in self.selected_graphs() {
+            for triple in graph.triples_for_object(object) {
+                let predicate: OxNamedNode = triple.predicate.to_owned().into();
+                if predicate.eq(pred) {
+                    let subject: OxSubject = triple.subject.to_owned().into();
+                    results.insert(subject);
+                }
             }
         }
         Ok(results)
     }
+
+    async fn get_triples_with_subjects(
+        &self,
+        subjects: &[OxSubject],
+    ) -> Result<Vec<(OxSubject, OxNamedNode, OxTerm)>, SRDFGraphError> {
+        let mut results = Vec::new();
+        for graph in self.selected_graphs() {
+            for subject in subjects {
+                results.extend(graph.triples_for_subject(subject).map(owned_triple))
+            }
+        }
+        Ok(results)
+    }
+
+    async fn get_triples_with_objects(
+        &self,
+        objects: &[OxTerm],
+    ) -> Result<Vec<(OxSubject, OxNamedNode, OxTerm)>, SRDFGraphError> {
+        let mut results = Vec::new();
+        for graph in self.selected_graphs() {
+            for object in objects {
+                results.extend(graph.triples_for_object(object).map(owned_triple))
+            }
+        }
+        Ok(results)
+    }
+
+    async fn get_triples_with_predicate(
+        &self,
+        pred: &OxNamedNode,
+    ) -> Result<Vec<(OxSubject, OxNamedNode, OxTerm)>, SRDFGraphError> {
+        let mut results = Vec::new();
+        for graph in self.selected_graphs() {
+            results.extend(graph.triples_for_predicate(pred).map(owned_triple))
+        }
+        Ok(results)
+    }
+
+    async fn get_nodes_for_query(&self, query: &str) -> Result<Vec<OxTerm>, SRDFGraphError> {
+        Err(SRDFGraphError::QueriesNotSupported {
+            query: query.to_string(),
+        })
+    }
+}
+
+fn owned_triple(triple: TripleRef) -> (OxSubject, OxNamedNode, OxTerm) {
+    (
+        triple.subject.into_owned(),
+        triple.predicate.into_owned(),
+        triple.object.into_owned(),
+    )
 }
 
 impl FocusRDF for SRDFGraph {
@@ -661,6 +1057,11 @@ impl SRDFBuilder for SRDFGraph {
         Ok(())
     }
 
+    fn add_bnode(&mut self) -> Result<Self::BNode, Self::Err> {
+        // Blank nodes with random identifiers, as in the parsers of oxigraph
+        Ok(OxBlankNode::default())
+    }
+
     fn add_triple(
         &mut self,
         subj: &Self::Subject,
@@ -668,7 +1069,7 @@ impl SRDFBuilder for SRDFGraph {
         obj: &Self::Term,
     ) -> Result<(), Self::Err> {
         let triple = OxTriple::new(subj.clone(), pred.clone(), obj.clone());
-        self.graph.insert(&triple);
+        self.selected_graph_mut().insert(&triple);
         Ok(())
     }
 
@@ -679,7 +1080,7 @@ impl SRDFBuilder for SRDFGraph {
         obj: &Self::Term,
     ) -> Result<(), Self::Err> {
         let triple = OxTriple::new(subj.clone(), pred.clone(), obj.clone());
-        self.graph.remove(&triple);
+        self.selected_graph_mut().remove(&triple);
         Ok(())
     }
 
@@ -687,7 +1088,7 @@ impl SRDFBuilder for SRDFGraph {
         match Self::object_as_subject(node) {
             Some(subj) => {
                 let triple = OxTriple::new(subj, rdf_type(), r#type.clone());
-                self.graph.insert(&triple);
+                self.selected_graph_mut().insert(&triple);
                 Ok(())
             }
             None => {
@@ -700,21 +1101,45 @@ impl SRDFBuilder for SRDFGraph {
         SRDFGraph {
             focus: None,
             graph: Graph::new(),
+            named_graphs: IndexMap::new(),
+            graph_selector: GraphSelector::default(),
+            jsonld_contexts: HashMap::new(),
             pm: PrefixMap::new(),
             base: None,
         }
     }
 
+    /// Dataset formats (TriG and N-Quads) contain all the graphs, other formats contain the selected graphs
     fn serialize<W: Write>(&self, format: &RDFFormat, write: &mut W) -> Result<(), Self::Err> {
-        let mut serializer = RdfSerializer::from_format(cnv_rdf_format(format));
+        let rdf_format = cnv_rdf_format(format);
+        let mut serializer = RdfSerializer::from_format(rdf_format);
 
         for (prefix, iri) in &self.pm.map {
             serializer = serializer.with_prefix(prefix, iri.as_str()).unwrap();
         }
 
         let mut writer = serializer.for_writer(write);
-        for triple in self.graph.iter() {
-            writer.serialize_triple(triple)?;
+        if rdf_format.supports_datasets() {
+            for quad in self.quads() {
+                writer.serialize_quad(&quad)?;
+            }
+        } else {
+            match self.selected_graphs().as_slice() {
+                [graph] => {
+                    for triple in graph.iter() {
+                        writer.serialize_triple(triple)?;
+                    }
+                }
+                graphs => {
+                    let mut union = Graph::new();
+                    for graph in graphs {
+                        union.extend(graph.iter());
+                    }
+                    for triple in union.iter() {
+                        writer.serialize_triple(triple)?;
+                    }
+                }
+            }
         }
         writer.finish()?;
         Ok(())
@@ -729,9 +1154,14 @@ fn cnv_rdf_format(rdf_format: &RDFFormat) -> RdfFormat {
         RDFFormat::TriG => RdfFormat::TriG,
         RDFFormat::N3 => RdfFormat::N3,
         RDFFormat::NQuads => RdfFormat::NQuads,
+        RDFFormat::JsonLd => JSON_LD,
     }
 }
 
+const JSON_LD: RdfFormat = RdfFormat::JsonLd {
+    profile: JsonLdProfileSet::empty(),
+};
+
 fn rdf_type() -> OxNamedNode {
     OxNamedNode::new_unchecked(RDF_TYPE_STR)
 }
@@ -782,10 +1212,33 @@ mod tests {
         let term = terms.iter().next().unwrap().clone();
         let subject = <SRDFGraph as SRDFBasic>::term_as_subject(&term).unwrap();
         let outgoing = graph.outgoing_arcs(&subject).unwrap();
-        let one = <SRDFGraph as SRDFBasic>::object_as_term(&Object::Literal(int!(1)));
+        let one = <SRDFGraph as SRDFBasic>::object_as_term(&Object::Literal(int!(1))).unwrap();
         assert_eq!(outgoing.get(&p), Some(&HashSet::from([one])))
     }
 
+    #[test]
+    fn test_merge_graph_renames_blank_nodes() {
+        let s = r#"prefix : <http://example.org/>
+        _:b1 :p 1 .
+        "#;
+
+        let other = SRDFGraph::from_str(s, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let mut graph = SRDFGraph::new();
+        graph.merge_graph(&other).unwrap();
+        graph.merge_graph(&other).unwrap();
+        assert_eq!(graph.len(), 2)
+    }
+
+    #[test]
+    fn test_object_as_term_quoted_triple_literal_subject() {
+        let triple = Object::Triple {
+            subject: Box::new(Object::Literal(int!(1))),
+            predicate: iri!("http://example.org/p"),
+            object: Box::new(Object::Literal(int!(2))),
+        };
+        assert!(<SRDFGraph as SRDFBasic>::object_as_term(&triple).is_err())
+    }
+
     #[test]
     fn test_outgoing_arcs_bnode() {
         let s = r#"prefix : <http://example.org/>
@@ -802,7 +1255,7 @@ mod tests {
         let bnode = <SRDFGraph as SRDFBasic>::term_as_bnode(&term).unwrap();
         let subject = <SRDFGraph as SRDFBasic>::bnode_id2subject(bnode.as_str());
         let outgoing = graph.outgoing_arcs(&subject).unwrap();
-        let one = <SRDFGraph as SRDFBasic>::object_as_term(&Object::Literal(int!(1)));
+        let one = <SRDFGraph as SRDFBasic>::object_as_term(&Object::Literal(int!(1))).unwrap();
         assert_eq!(outgoing.get(&p), Some(&HashSet::from([one])))
     }
 
@@ -1013,6 +1466,180 @@ mod tests {
         let result = parser.parse(&x, graph);
         assert!(result.is_ok())
     }
+
+    const TRIG: &str = r#"prefix : <http://example.org/>
+        :alice :name "Alice" .
+        :g1 { :alice :knows :bob . }
+        :g2 { :alice :knows :carol . :carol :name "Carol" . }
+        "#;
+
+    fn knows_from_alice(graph: &SRDFGraph) -> HashSet<OxTerm> {
+        let alice = <SRDFGraph as SRDFBasic>::iri_s2subject(&iri!("http://example.org/alice"));
+        let knows = <SRDFGraph as SRDFBasic>::iri_s2iri(&iri!("http://example.org/knows"));
+        graph.objects_for_subject_predicate(&alice, &knows).unwrap()
+    }
+
+    #[test]
+    fn test_trig_graph_selector() {
+        let mut graph =
+            SRDFGraph::from_str(TRIG, &RDFFormat::TriG, None, &ReaderMode::Strict).unwrap();
+        let bob = <SRDFGraph as SRDFBasic>::iri_s2term(&iri!("http://example.org/bob"));
+        let carol = <SRDFGraph as SRDFBasic>::iri_s2term(&iri!("http://example.org/carol"));
+        assert_eq!(graph.len(), 4);
+        assert!(graph.has_named_graph(&iri!("http://example.org/g1")));
+        assert_eq!(knows_from_alice(&graph), HashSet::new());
+
+        graph.set_graph_selector(GraphSelector::NamedGraph(iri!("http://example.org/g1")));
+        assert_eq!(knows_from_alice(&graph), HashSet::from([bob.clone()]));
+
+        graph.set_graph_selector(GraphSelector::Union);
+        assert_eq!(knows_from_alice(&graph), HashSet::from([bob, carol]));
+    }
+
+    #[test]
+    fn test_nquads_round_trip_keeps_graph_names() {
+        let graph = SRDFGraph::from_str(TRIG, &RDFFormat::TriG, None, &ReaderMode::Strict).unwrap();
+        let mut buf = Vec::new();
+        graph.serialize(&RDFFormat::NQuads, &mut buf).unwrap();
+        let nquads = String::from_utf8(buf).unwrap();
+        let parsed =
+            SRDFGraph::from_str(&nquads, &RDFFormat::NQuads, None, &ReaderMode::Strict).unwrap();
+        assert_eq!(
+            parsed.quads().collect::<HashSet<_>>(),
+            graph.quads().collect::<HashSet<_>>()
+        );
+        let names: Vec<_> = parsed.graph_names().map(|name| name.to_string()).collect();
+        assert_eq!(
+            names,
+            vec!["<http://example.org/g1>", "<http://example.org/g2>"]
+        );
+    }
+
+    #[test]
+    fn test_n3() {
+        let s = r#"@prefix : <http://example.org/> .
+        :alice :knows :bob .
+        "#;
+        let graph = SRDFGraph::from_str(s, &RDFFormat::N3, None, &ReaderMode::Strict).unwrap();
+        let bob = <SRDFGraph as SRDFBasic>::iri_s2term(&iri!("http://example.org/bob"));
+        assert_eq!(knows_from_alice(&graph), HashSet::from([bob]));
+    }
+
+    const JSONLD: &str = r#"{
+        "@context": "http://example.org/context.jsonld",
+        "@id": "ex:alice",
+        "knows": "ex:bob"
+    }"#;
+
+    const CONTEXT: &str = r#"{
+        "@context": {
+            "ex": "http://example.org/",
+            "knows": { "@id": "http://example.org/knows", "@type": "@id" }
+        }
+    }"#;
+
+    #[test]
+    fn test_jsonld_with_local_context() {
+        let mut graph = SRDFGraph::new();
+        graph.add_jsonld_context("http://example.org/context.jsonld", CONTEXT);
+        graph
+            .merge_from_reader(
+                JSONLD.as_bytes(),
+                &RDFFormat::JsonLd,
+                None,
+                &ReaderMode::Strict,
+            )
+            .unwrap();
+        let bob = <SRDFGraph as SRDFBasic>::iri_s2term(&iri!("http://example.org/bob"));
+        assert_eq!(knows_from_alice(&graph), HashSet::from([bob]));
+    }
+
+    #[test]
+    fn test_jsonld_remote_context_is_not_fetched() {
+        let result = SRDFGraph::from_str(JSONLD, &RDFFormat::JsonLd, None, &ReaderMode::Strict);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_jsonld_round_trip() {
+        let graph = SRDFGraph::from_str(TRIG, &RDFFormat::TriG, None, &ReaderMode::Strict).unwrap();
+        let mut buf = Vec::new();
+        graph.serialize(&RDFFormat::JsonLd, &mut buf).unwrap();
+        let jsonld = String::from_utf8(buf).unwrap();
+        let parsed =
+            SRDFGraph::from_str(&jsonld, &RDFFormat::JsonLd, None, &ReaderMode::Strict).unwrap();
+        assert_eq!(
+            parsed.quads().collect::<HashSet<_>>(),
+            graph.quads().collect::<HashSet<_>>()
+        );
+    }
+
+    #[cfg(feature = "rdf-star")]
+    fn quoted_alice_knows_bob() -> Object {
+        Object::triple(
+            Object::iri(iri!("http://example.org/alice")),
+            iri!("http://example.org/knows"),
+            Object::iri(iri!("http://example.org/bob")),
+        )
+    }
+
+    #[cfg(feature = "rdf-star")]
+    #[test]
+    fn test_turtle_star_quoted_triple() {
+        let s = r#"prefix : <http://example.org/>
+                   << :alice :knows :bob >> :source :wikipedia .
+                   :carol :believes << :alice :knows :bob >> .
+        "#;
+        let graph = SRDFGraph::from_str(s, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let source = SRDFGraph::iri_s2iri(&iri!("http://example.org/source"));
+        let triples = graph.triples_with_predicate(&source).unwrap();
+        assert_eq!(triples.len(), 1);
+        let subject = SRDFGraph::subject_as_term(&triples[0].subj());
+        assert_eq!(
+            SRDFGraph::term_as_object(&subject),
+            quoted_alice_knows_bob()
+        );
+
+        let carol = SRDFGraph::iri_s2subject(&iri!("http://example.org/carol"));
+        let believes = SRDFGraph::iri_s2iri(&iri!("http://example.org/believes"));
+        let objects = graph
+            .objects_for_subject_predicate(&carol, &believes)
+            .unwrap();
+        let objects: Vec<Object> = objects.iter().map(SRDFGraph::term_as_object).collect();
+        assert_eq!(objects, vec![quoted_alice_knows_bob()]);
+    }
+
+    #[cfg(feature = "rdf-star")]
+    #[test]
+    fn test_ntriples_star_quoted_triple() {
+        let s = r#"<< <http://example.org/alice> <http://example.org/knows> <http://example.org/bob> >> <http://example.org/source> "wikipedia" .
+        "#;
+        let graph =
+            SRDFGraph::from_str(s, &RDFFormat::NTriples, None, &ReaderMode::Strict).unwrap();
+        assert_eq!(graph.len(), 1);
+        let source = SRDFGraph::iri_s2iri(&iri!("http://example.org/source"));
+        let subjects = graph
+            .subjects_with_predicate_object(&source, &OxTerm::Literal(OxLiteral::from("wikipedia")))
+            .unwrap();
+        let subjects: Vec<Object> = subjects
+            .iter()
+            .map(|s| SRDFGraph::term_as_object(&SRDFGraph::subject_as_term(s)))
+            .collect();
+        assert_eq!(subjects, vec![quoted_alice_knows_bob()]);
+    }
+
+    #[cfg(feature = "rdf-star")]
+    #[test]
+    fn test_quoted_triple_object_round_trip() {
+        let triple = quoted_alice_knows_bob();
+        let term = SRDFGraph::object_as_term(&triple).unwrap();
+        assert!(matches!(term, OxTerm::Triple(_)));
+        assert_eq!(SRDFGraph::term_as_object(&term), triple);
+        assert_eq!(
+            triple.to_string(),
+            "<< <http://example.org/alice> <http://example.org/knows> <http://example.org/bob> >>"
+        );
+    }
 }
 
 #[test]
@@ -1045,6 +1672,30 @@ fn triple_to_quad(t: TripleRef, graph_name: GraphName) -> Quad {
     Quad::new(subj, pred, obj, graph_name)
 }
 
+/// Graphs of an RDF dataset that are used to answer queries
+#[derive(Debug, PartialEq, Clone, Default)]
+pub enum GraphSelector {
+    /// The default graph
+    #[default]
+    DefaultGraph,
+
+    /// The named graph with that name
+    NamedGraph(IriS),
+
+    /// The union of the default graph and all the named graphs
+    Union,
+}
+
+impl Display for GraphSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphSelector::DefaultGraph => write!(f, "default graph"),
+            GraphSelector::NamedGraph(name) => write!(f, "graph <{name}>"),
+            GraphSelector::Union => write!(f, "union graph"),
+        }
+    }
+}
+
 /// Reader mode when parsing RDF data files
 #[derive(Debug, PartialEq, Clone, Default)]
 pub enum ReaderMode {
@@ -1,5 +1,6 @@
 use iri_s::IriSError;
 use oxiri::IriParseError;
+use oxrdfio::RdfParseError;
 use oxttl::TurtleParseError;
 use prefixmap::PrefixMapError;
 use std::io;
@@ -20,6 +21,12 @@ pub enum SRDFGraphError {
         err: TurtleParseError,
     },
 
+    #[error(transparent)]
+    RDFParseError {
+        #[from]
+        err: RdfParseError,
+    },
+
     #[error(transparent)]
     IOError {
         #[from]
@@ -49,4 +56,15 @@ pub enum SRDFGraphError {
         #[from]
         err: PrefixMapError,
     },
+
+    #[error(
+        "Quoted triple {triple} can't be represented because the rdf-star feature is not enabled"
+    )]
+    QuotedTripleWithoutRdfStar { triple: String },
+
+    #[error("Quoted triple {triple} has a literal as subject")]
+    QuotedTripleLiteralSubject { triple: String },
+
+    #[error("In-memory graphs can't run SPARQL queries. Query: {query}")]
+    QueriesNotSupported { query: String },
 }
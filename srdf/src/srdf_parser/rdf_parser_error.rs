@@ -72,9 +72,15 @@ pub enum RDFParseError {
     #[error("Unexpected Blank Node: {term}")]
     UnexpectedBNode { term: String },
 
+    #[error("Unexpected quoted triple: {term}")]
+    UnexpectedTriple { term: String },
+
     #[error("Expected IRI but found Literal {lit}")]
     ExpectedIRIFoundLiteral { lit: Literal },
 
+    #[error("Expected IRI but found quoted triple {triple}")]
+    ExpectedIRIFoundTriple { triple: String },
+
     #[error("Condition {condition_name} failed for node {node}")]
     NodeDoesntSatisfyCondition {
         condition_name: String,
@@ -0,0 +1,98 @@
+use serde_derive::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::{DEFAULT_BATCH_SIZE, DEFAULT_MAX_BLANK_NODE_DEPTH};
+
+/// Default number of requests that are sent at the same time to an endpoint
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Default number of times that a request is retried after a transient error
+pub const DEFAULT_MAX_RETRIES: usize = 3;
+
+/// Default time to wait before the first retry, which is doubled after each retry
+pub const DEFAULT_BACKOFF_MILLIS: u64 = 500;
+
+/// Configuration of the asynchronous access to SPARQL endpoints
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct AsyncSparqlConfig {
+    /// Number of nodes of the `VALUES` block of each request
+    pub batch_size: Option<usize>,
+
+    /// Maximum number of requests that are sent at the same time
+    pub concurrency: Option<usize>,
+
+    /// Number of retries of a request that failed with a timeout, a connection error,
+    /// a `429 Too Many Requests` or a server error
+    pub max_retries: Option<usize>,
+
+    /// Milliseconds to wait before the first retry, the wait is doubled after each retry
+    pub backoff_millis: Option<u64>,
+
+    /// Number of hops of references whose outgoing arcs are prefetched along with the focus nodes.
+    /// The arcs of deeper references are fetched when the validation needs them, so a depth
+    /// close to the levels of references of the shapes saves requests
+    pub reference_depth: Option<usize>,
+
+    /// Maximum length of the chains of blank nodes that are obtained with the triples of a node.
+    /// Longer chains make the requests fail instead of repeating them with greater depths
+    pub max_blank_node_depth: Option<usize>,
+}
+
+impl AsyncSparqlConfig {
+    pub fn new() -> AsyncSparqlConfig {
+        Self::default()
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    pub fn with_retries(mut self, max_retries: usize, backoff_millis: u64) -> Self {
+        self.max_retries = Some(max_retries);
+        self.backoff_millis = Some(backoff_millis);
+        self
+    }
+
+    pub fn with_reference_depth(mut self, depth: usize) -> Self {
+        self.reference_depth = Some(depth);
+        self
+    }
+
+    pub fn with_max_blank_node_depth(mut self, depth: usize) -> Self {
+        self.max_blank_node_depth = Some(depth);
+        self
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1)
+    }
+
+    pub fn concurrency(&self) -> usize {
+        self.concurrency.unwrap_or(DEFAULT_CONCURRENCY).max(1)
+    }
+
+    pub fn max_retries(&self) -> usize {
+        self.max_retries.unwrap_or(DEFAULT_MAX_RETRIES)
+    }
+
+    /// Time to wait before the retry number `retry`, starting at 0
+    pub fn backoff(&self, retry: usize) -> Duration {
+        let millis = self.backoff_millis.unwrap_or(DEFAULT_BACKOFF_MILLIS);
+        Duration::from_millis(millis.saturating_mul(1 << retry.min(16)))
+    }
+
+    pub fn reference_depth(&self) -> usize {
+        self.reference_depth.unwrap_or(1)
+    }
+
+    pub fn max_blank_node_depth(&self) -> usize {
+        self.max_blank_node_depth
+            .unwrap_or(DEFAULT_MAX_BLANK_NODE_DEPTH)
+    }
+}
@@ -0,0 +1,454 @@
+use crate::srdfsparql::{
+    graph_pattern, is_truncated, neighbourhood_batches, neighbourhood_query, next_blank_node_depth,
+    objects_queries, parse_solutions, predicate_query, solutions_as_depth_triples,
+    solutions_as_triples, BlankNodeLabels, TripleRow,
+};
+use crate::{
+    AsyncSRDF, AsyncSparqlConfig, EndpointDescription, SRDFSparqlError, SparqlConnectionConfig,
+    DEFAULT_BLANK_NODE_DEPTH,
+};
+use async_trait::async_trait;
+use iri_s::IriS;
+use oxrdf::{
+    BlankNode as OxBlankNode, Literal as OxLiteral, NamedNode as OxNamedNode, Subject as OxSubject,
+    Term as OxTerm,
+};
+use prefixmap::PrefixMap;
+use reqwest::{Client, StatusCode, Url};
+use sparesults::QuerySolution as OxQuerySolution;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+type Result<A> = std::result::Result<A, SRDFSparqlError>;
+
+/// Implements [`AsyncSRDF`] as a SPARQL endpoint with non-blocking requests.
+///
+/// The triples of several nodes are fetched with a single request using `VALUES` blocks,
+/// the requests of a batch are sent concurrently up to the configured limit,
+/// and requests that fail with transient errors are retried with exponential backoff.
+/// The triples of the blank nodes that can be reached from a node are obtained with the triples of the node,
+/// as blank node labels are local to each result set
+#[derive(Debug, Clone)]
+pub struct AsyncSRDFSparql {
+    endpoint_iri: IriS,
+    prefixmap: PrefixMap,
+    client: Client,
+
+    /// Named graph used to answer queries. If `None`, the default graph of the endpoint is used
+    graph: Option<IriS>,
+    config: AsyncSparqlConfig,
+    connection: SparqlConnectionConfig,
+
+    /// Blank nodes returned by the endpoint
+    blank_nodes: Arc<Mutex<ReturnedBlankNodes>>,
+}
+
+/// Labels of the blank nodes returned by an endpoint, which are renamed with fresh labels in each result set.
+/// The labels tell if the triples of a blank node were obtained, so no set of blank nodes has to be kept
+#[derive(Debug)]
+struct ReturnedBlankNodes {
+    /// Blank nodes whose triples were obtained with the triples of the nodes from which they are reached
+    complete: BlankNodeLabels,
+
+    /// Blank nodes whose triples can't be obtained
+    incomplete: BlankNodeLabels,
+}
+
+impl Default for ReturnedBlankNodes {
+    fn default() -> Self {
+        ReturnedBlankNodes {
+            complete: BlankNodeLabels::new(),
+            incomplete: BlankNodeLabels::new(),
+        }
+    }
+}
+
+impl AsyncSRDFSparql {
+    pub fn new(iri: &IriS, prefixmap: &PrefixMap) -> Result<AsyncSRDFSparql> {
+        AsyncSRDFSparql::new_with_connection(iri, prefixmap, &SparqlConnectionConfig::default())
+    }
+
+    /// Creates an endpoint whose requests use the authentication, headers, timeout
+    /// and query method of `connection`
+    pub fn new_with_connection(
+        iri: &IriS,
+        prefixmap: &PrefixMap,
+        connection: &SparqlConnectionConfig,
+    ) -> Result<AsyncSRDFSparql> {
+        let client = async_sparql_client(connection)?;
+        Ok(AsyncSRDFSparql {
+            endpoint_iri: iri.clone(),
+            prefixmap: prefixmap.clone(),
+            client,
+            graph: None,
+            config: AsyncSparqlConfig::default(),
+            connection: connection.clone(),
+            blank_nodes: Arc::new(Mutex::new(ReturnedBlankNodes::default())),
+        })
+    }
+
+    /// Creates an endpoint from its description in the configuration
+    pub fn from_endpoint_description(description: &EndpointDescription) -> Result<AsyncSRDFSparql> {
+        AsyncSRDFSparql::new_with_connection(
+            description.query_url(),
+            description.prefixmap(),
+            description.connection(),
+        )
+    }
+
+    pub fn iri(&self) -> &IriS {
+        &self.endpoint_iri
+    }
+
+    pub fn prefixmap(&self) -> &PrefixMap {
+        &self.prefixmap
+    }
+
+    pub fn graph(&self) -> Option<&IriS> {
+        self.graph.as_ref()
+    }
+
+    pub fn config(&self) -> &AsyncSparqlConfig {
+        &self.config
+    }
+
+    /// Restricts the queries to a named graph of the endpoint
+    pub fn with_graph(mut self, graph: Option<IriS>) -> Self {
+        self.graph = graph;
+        self
+    }
+
+    pub fn with_config(mut self, config: AsyncSparqlConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Runs a query retrying it when it fails with a transient error
+    pub async fn query(&self, query: &str) -> Result<Vec<OxQuerySolution>> {
+        let params = [("query", query)];
+        let url = Url::parse_with_params(self.endpoint_iri.as_str(), &params)?;
+        let post = self.connection.use_post(url.as_str().len());
+        let mut retry = 0;
+        loop {
+            tracing::debug!("SPARQL query (attempt {}): {}", retry + 1, url);
+            let request = if post {
+                self.client.post(self.endpoint_iri.as_str()).form(&params)
+            } else {
+                self.client.get(url.clone())
+            };
+            let response = match request.send().await {
+                Ok(response) => response.error_for_status(),
+                Err(e) => Err(e),
+            };
+            match response {
+                Ok(response) => {
+                    let body = response.text().await?;
+                    return parse_solutions(body);
+                }
+                Err(e) if retry < self.config.max_retries() && is_transient(&e) => {
+                    let backoff = self.config.backoff(retry);
+                    tracing::debug!("Retrying SPARQL query in {backoff:?} after error: {e}");
+                    tokio::time::sleep(backoff).await;
+                    retry += 1;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Runs the queries concurrently and joins their triples
+    async fn query_triples(&self, queries: Vec<String>) -> Result<Vec<TripleRow>> {
+        self.concurrent_triples(queries, |endpoint, query| async move {
+            let solutions = endpoint.query(&query).await?;
+            Ok(endpoint.rename_blank_nodes(solutions_as_triples(solutions)?, false))
+        })
+        .await
+    }
+
+    /// Obtains the triples of a batch of subjects together with the triples of the blank nodes
+    /// that can be reached from them, repeating the query with a greater depth when some chain of blank nodes
+    /// is longer than the depth of the query, up to the maximum depth of the configuration
+    async fn neighbourhood_triples(&self, batch: Vec<String>) -> Result<Vec<TripleRow>> {
+        let max_depth = self.config.max_blank_node_depth();
+        let mut depth = DEFAULT_BLANK_NODE_DEPTH.min(max_depth);
+        let rows = loop {
+            let query = neighbourhood_query(&self.graph, &batch, depth, None);
+            let rows = solutions_as_depth_triples(self.query(&query).await?)?;
+            if !is_truncated(&rows, depth) {
+                break rows;
+            }
+            depth = next_blank_node_depth(depth, max_depth)?;
+        };
+        let triples = rows.into_iter().map(|(triple, _)| triple).collect();
+        Ok(self.rename_blank_nodes(triples, true))
+    }
+
+    /// Renames the blank nodes of the triples of a result set with fresh labels,
+    /// so they don't clash with the blank nodes of other result sets.
+    /// `complete` tells if the result set contains all the triples of its blank nodes
+    fn rename_blank_nodes(&self, triples: Vec<TripleRow>, complete: bool) -> Vec<TripleRow> {
+        let mut blank_nodes = self.blank_nodes();
+        let returned = if complete {
+            &mut blank_nodes.complete
+        } else {
+            &mut blank_nodes.incomplete
+        };
+        let mut labels: HashMap<OxBlankNode, OxBlankNode> = HashMap::new();
+        let mut rename = |bnode: OxBlankNode| {
+            labels
+                .entry(bnode)
+                .or_insert_with(|| returned.fresh())
+                .clone()
+        };
+        triples
+            .into_iter()
+            .map(|(subj, pred, obj)| {
+                let subj = match subj {
+                    OxSubject::BlankNode(bnode) => OxSubject::BlankNode(rename(bnode)),
+                    _ => subj,
+                };
+                let obj = match obj {
+                    OxTerm::BlankNode(bnode) => OxTerm::BlankNode(rename(bnode)),
+                    _ => obj,
+                };
+                (subj, pred, obj)
+            })
+            .collect()
+    }
+
+    fn blank_nodes(&self) -> std::sync::MutexGuard<'_, ReturnedBlankNodes> {
+        // A panic while the lock was held can't leave the set of blank nodes in an inconsistent state
+        self.blank_nodes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Checks that the triples of the blank nodes of `nodes` that were returned by the endpoint
+    /// were obtained with the nodes from which they are reached.
+    /// Blank nodes that were not returned by the endpoint, like the ones of other RDF data, have no triples in it
+    fn check_blank_nodes<'a, I: IntoIterator<Item = &'a OxBlankNode>>(
+        &self,
+        nodes: I,
+    ) -> Result<()> {
+        let blank_nodes = self.blank_nodes();
+        for bnode in nodes {
+            if blank_nodes.incomplete.contains(bnode) {
+                return Err(SRDFSparqlError::BlankNodeSubject {
+                    bnode: bnode.as_str().to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs `task` for each input concurrently, with at most `concurrency` requests at the same time,
+    /// and joins their triples
+    async fn concurrent_triples<I, F, Fut>(&self, inputs: Vec<I>, task: F) -> Result<Vec<TripleRow>>
+    where
+        F: Fn(AsyncSRDFSparql, I) -> Fut,
+        Fut: Future<Output = Result<Vec<TripleRow>>> + Send + 'static,
+    {
+        let semaphore = Arc::new(Semaphore::new(self.config.concurrency()));
+        let mut tasks = JoinSet::new();
+        for input in inputs {
+            let semaphore = semaphore.clone();
+            let triples = task(self.clone(), input);
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await.map_err(|e| {
+                    SRDFSparqlError::ConcurrentRequest {
+                        error: e.to_string(),
+                    }
+                })?;
+                triples.await
+            });
+        }
+        let mut triples = Vec::new();
+        while let Some(result) = tasks.join_next().await {
+            let result = result.map_err(|e| SRDFSparqlError::ConcurrentRequest {
+                error: e.to_string(),
+            })?;
+            triples.extend(result?)
+        }
+        Ok(triples)
+    }
+}
+
+#[async_trait]
+impl AsyncSRDF for AsyncSRDFSparql {
+    type IRI = OxNamedNode;
+    type BNode = OxBlankNode;
+    type Literal = OxLiteral;
+    type Subject = OxSubject;
+    type Term = OxTerm;
+    type Err = SRDFSparqlError;
+
+    async fn get_predicates_subject(&self, subject: &OxSubject) -> Result<HashSet<OxNamedNode>> {
+        let triples = self
+            .get_triples_with_subjects(std::slice::from_ref(subject))
+            .await?;
+        Ok(triples.into_iter().map(|(_, pred, _)| pred).collect())
+    }
+
+    async fn get_objects_for_subject_predicate(
+        &self,
+        subject: &OxSubject,
+        pred: &OxNamedNode,
+    ) -> Result<HashSet<OxTerm>> {
+        let pattern = graph_pattern(&self.graph, &format!("{subject} {pred} ?obj ."));
+        let solutions = self
+            .query(&format!("select ?obj where {{ {pattern} }}"))
+            .await?;
+        Ok(solutions
+            .iter()
+            .filter_map(|solution| solution.get("obj").cloned())
+            .collect())
+    }
+
+    async fn get_subjects_for_object_predicate(
+        &self,
+        object: &OxTerm,
+        pred: &OxNamedNode,
+    ) -> Result<HashSet<OxSubject>> {
+        let pattern = graph_pattern(&self.graph, &format!("?subj {pred} {object} ."));
+        let solutions = self
+            .query(&format!("select ?subj where {{ {pattern} }}"))
+            .await?;
+        Ok(solutions
+            .iter()
+            .filter_map(|solution| match solution.get("subj") {
+                Some(OxTerm::NamedNode(n)) => Some(OxSubject::NamedNode(n.clone())),
+                Some(OxTerm::BlankNode(b)) => Some(OxSubject::BlankNode(b.clone())),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// The triples of the blank nodes that can be reached from the subjects are obtained with them,
+    /// so the blank nodes of `subjects` must have been obtained that way
+    async fn get_triples_with_subjects(&self, subjects: &[OxSubject]) -> Result<Vec<TripleRow>> {
+        self.check_blank_nodes(subjects.iter().filter_map(|subject| match subject {
+            OxSubject::BlankNode(bnode) => Some(bnode),
+            _ => None,
+        }))?;
+        let batches = neighbourhood_batches(subjects, self.config.batch_size());
+        self.concurrent_triples(batches, |endpoint, batch| async move {
+            endpoint.neighbourhood_triples(batch).await
+        })
+        .await
+    }
+
+    /// The incoming arcs of the blank nodes of `objects` are obtained with the triples of the nodes
+    /// from which they are reached, so they must have been obtained that way
+    async fn get_triples_with_objects(&self, objects: &[OxTerm]) -> Result<Vec<TripleRow>> {
+        self.check_blank_nodes(objects.iter().filter_map(|object| match object {
+            OxTerm::BlankNode(bnode) => Some(bnode),
+            _ => None,
+        }))?;
+        let queries = objects_queries(&self.graph, objects, self.config.batch_size());
+        self.query_triples(queries).await
+    }
+
+    async fn get_triples_with_predicate(&self, pred: &OxNamedNode) -> Result<Vec<TripleRow>> {
+        let solutions = self.query(&predicate_query(&self.graph, pred)).await?;
+        Ok(self.rename_blank_nodes(solutions_as_triples(solutions)?, false))
+    }
+
+    /// The blank nodes get fresh labels, as the labels of the endpoint are not stable between queries,
+    /// so their triples can't be fetched later
+    async fn get_nodes_for_query(&self, query: &str) -> Result<Vec<OxTerm>> {
+        let solutions = self.query(query).await?;
+        let mut blank_nodes = self.blank_nodes();
+        let mut labels: HashMap<OxBlankNode, OxBlankNode> = HashMap::new();
+        Ok(solutions
+            .iter()
+            .filter_map(|solution| solution.get(0).cloned())
+            .map(|term| match term {
+                OxTerm::BlankNode(bnode) => OxTerm::BlankNode(
+                    labels
+                        .entry(bnode)
+                        .or_insert_with(|| blank_nodes.incomplete.fresh())
+                        .clone(),
+                ),
+                term => term,
+            })
+            .collect())
+    }
+}
+
+/// Timeouts, connection errors, `429 Too Many Requests` and server errors are worth retrying
+fn is_transient(error: &reqwest::Error) -> bool {
+    error.is_timeout()
+        || error.is_connect()
+        || error.status().is_some_and(|status| {
+            status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+        })
+}
+
+fn async_sparql_client(connection: &SparqlConnectionConfig) -> Result<Client> {
+    let mut builder = Client::builder().default_headers(connection.default_headers()?);
+    if let Some(timeout) = connection.timeout() {
+        builder = builder.timeout(timeout);
+    }
+    let client = builder.build()?;
+    Ok(client)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_neighbourhood_batches() {
+        let mut subjects: Vec<OxSubject> = ["a", "b", "c"]
+            .iter()
+            .map(|n| OxNamedNode::new_unchecked(format!("http://example.org/{n}")).into())
+            .collect();
+        subjects.insert(1, OxBlankNode::new_unchecked("b0").into());
+        let batches = neighbourhood_batches(&subjects, 2);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[1], vec!["<http://example.org/c>".to_string()]);
+    }
+
+    #[test]
+    fn check_objects_queries() {
+        let objects: Vec<OxTerm> = vec![
+            OxNamedNode::new_unchecked("http://example.org/a").into(),
+            OxBlankNode::new_unchecked("b0").into(),
+        ];
+        let queries = objects_queries(&None, &objects, 2);
+        assert_eq!(
+            queries,
+            vec!["select ?subj ?pred ?obj where { VALUES ?obj { <http://example.org/a> } ?subj ?pred ?obj . }"]
+        );
+    }
+
+    #[test]
+    fn check_unknown_blank_node_subject() {
+        let endpoint = AsyncSRDFSparql::new(
+            &IriS::new_unchecked("http://example.org/sparql"),
+            &PrefixMap::new(),
+        )
+        .unwrap();
+        let b = OxBlankNode::new_unchecked("b0");
+        let p = OxNamedNode::new_unchecked("http://example.org/p");
+        let triples =
+            endpoint.rename_blank_nodes(vec![(b.clone().into(), p, b.clone().into())], false);
+        let OxSubject::BlankNode(returned) = &triples[0].0 else {
+            panic!("Expected a blank node")
+        };
+        assert_eq!(triples[0].2, OxTerm::BlankNode(returned.clone()));
+        assert!(endpoint.check_blank_nodes([returned]).is_err());
+        // Blank nodes that were not returned by the endpoint have no triples in it
+        assert!(endpoint.check_blank_nodes([&b]).is_ok());
+    }
+
+    #[test]
+    fn check_backoff() {
+        let config = AsyncSparqlConfig::new().with_retries(2, 100);
+        assert_eq!(config.backoff(0), std::time::Duration::from_millis(100));
+        assert_eq!(config.backoff(2), std::time::Duration::from_millis(400));
+    }
+}
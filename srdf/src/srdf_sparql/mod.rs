@@ -1,8 +1,14 @@
 //! Implementation of [`SRDF`] traits based on SPARQL endpoints
 //!
 //! This crate implements the [`SRDF`] traits using a SPARQL endpoint to obtain the RDF data
+pub mod async_sparql_config;
+pub mod async_srdfsparql;
+pub mod sparql_connection;
 pub mod srdf_sparql_error;
 pub mod srdfsparql;
 
+pub use crate::async_sparql_config::*;
+pub use crate::async_srdfsparql::*;
+pub use crate::sparql_connection::*;
 pub use crate::srdf_sparql_error::*;
 pub use crate::srdfsparql::*;
@@ -0,0 +1,317 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use regex::{Captures, Regex};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT};
+use serde_derive::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt, str::FromStr, time::Duration};
+
+use crate::SRDFSparqlError;
+
+/// Default length of the URL of a query above which the `auto` method sends it with POST.
+/// Some servers and proxies reject longer URLs, which appear with large `VALUES` blocks
+pub const DEFAULT_POST_THRESHOLD: usize = 2000;
+
+/// Text shown instead of the secrets when the configuration is printed
+const REDACTED: &str = "***";
+
+/// Parts of the names of headers whose values are considered secrets
+const SENSITIVE_HEADER_NAMES: [&str; 6] = [
+    "authorization",
+    "cookie",
+    "token",
+    "key",
+    "secret",
+    "password",
+];
+
+/// HTTP settings used to send queries to a SPARQL endpoint.
+///
+/// The values of the credentials and the headers can refer to environment variables as `${NAME}`,
+/// which are replaced when the HTTP client is created so secrets don't need to be kept in the configuration.
+/// The credentials and the values of headers like `Authorization` are not shown by its `Debug` output
+#[derive(Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct SparqlConnectionConfig {
+    /// Credentials sent in the `Authorization` header of each request
+    pub auth: Option<EndpointAuth>,
+
+    /// Additional headers sent with each request
+    pub headers: Option<HashMap<String, String>>,
+
+    /// Seconds to wait for each request to complete, there is no timeout if it is `None`
+    pub timeout_secs: Option<u64>,
+
+    /// HTTP method used to send the queries
+    pub query_method: Option<QueryMethod>,
+
+    /// Length of the URL of a query above which the `auto` method sends it with POST
+    pub post_threshold: Option<usize>,
+}
+
+/// Authentication schemes supported for SPARQL endpoints
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum EndpointAuth {
+    /// HTTP Basic authentication
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+
+    /// Bearer token authentication, as used by OAuth 2.0
+    Bearer { token: String },
+}
+
+impl fmt::Debug for SparqlConnectionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let headers = self.headers.as_ref().map(|headers| {
+            headers
+                .iter()
+                .map(|(name, value)| {
+                    let value = if is_sensitive_header(name) {
+                        REDACTED
+                    } else {
+                        value.as_str()
+                    };
+                    (name.as_str(), value)
+                })
+                .collect::<HashMap<_, _>>()
+        });
+        f.debug_struct("SparqlConnectionConfig")
+            .field("auth", &self.auth)
+            .field("headers", &headers)
+            .field("timeout_secs", &self.timeout_secs)
+            .field("query_method", &self.query_method)
+            .field("post_threshold", &self.post_threshold)
+            .finish()
+    }
+}
+
+impl fmt::Debug for EndpointAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EndpointAuth::Basic { username, password } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .field("password", &password.as_ref().map(|_| REDACTED))
+                .finish(),
+            EndpointAuth::Bearer { .. } => {
+                f.debug_struct("Bearer").field("token", &REDACTED).finish()
+            }
+        }
+    }
+}
+
+/// HTTP method used to send queries following the SPARQL 1.1 protocol
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum QueryMethod {
+    /// Queries are sent in the query string of a GET request
+    Get,
+
+    /// Queries are sent URL-encoded in the body of a POST request
+    Post,
+
+    /// Queries are sent with GET unless their URL is longer than the POST threshold
+    #[default]
+    Auto,
+}
+
+impl SparqlConnectionConfig {
+    pub fn new() -> SparqlConnectionConfig {
+        Self::default()
+    }
+
+    pub fn with_basic_auth(mut self, username: &str, password: Option<&str>) -> Self {
+        self.auth = Some(EndpointAuth::Basic {
+            username: username.to_string(),
+            password: password.map(|p| p.to_string()),
+        });
+        self
+    }
+
+    pub fn with_bearer_token(mut self, token: &str) -> Self {
+        self.auth = Some(EndpointAuth::Bearer {
+            token: token.to_string(),
+        });
+        self
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers
+            .get_or_insert_with(HashMap::new)
+            .insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn with_timeout_secs(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = Some(timeout_secs);
+        self
+    }
+
+    pub fn with_query_method(mut self, method: QueryMethod) -> Self {
+        self.query_method = Some(method);
+        self
+    }
+
+    pub fn with_post_threshold(mut self, threshold: usize) -> Self {
+        self.post_threshold = Some(threshold);
+        self
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs.map(Duration::from_secs)
+    }
+
+    pub fn query_method(&self) -> QueryMethod {
+        self.query_method.unwrap_or_default()
+    }
+
+    pub fn post_threshold(&self) -> usize {
+        self.post_threshold.unwrap_or(DEFAULT_POST_THRESHOLD)
+    }
+
+    /// Checks if a query whose GET URL has length `url_len` must be sent with POST
+    pub fn use_post(&self, url_len: usize) -> bool {
+        match self.query_method() {
+            QueryMethod::Get => false,
+            QueryMethod::Post => true,
+            QueryMethod::Auto => url_len > self.post_threshold(),
+        }
+    }
+
+    /// Headers sent with every request, including the `Authorization` header,
+    /// after replacing the environment variables that appear in their values
+    pub fn default_headers(&self) -> Result<HeaderMap, SRDFSparqlError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            ACCEPT,
+            HeaderValue::from_static("application/sparql-results+json"),
+        );
+        headers.insert(USER_AGENT, HeaderValue::from_static("rudof"));
+        for (name, value) in self.headers.iter().flatten() {
+            let header_name =
+                HeaderName::from_str(name).map_err(|e| SRDFSparqlError::InvalidHeader {
+                    name: name.clone(),
+                    error: e.to_string(),
+                })?;
+            let mut value = header_value(name, &interpolate_env(value)?)?;
+            // Sensitive values are not shown by the debug output of the HTTP client
+            value.set_sensitive(is_sensitive_header(name));
+            headers.insert(header_name, value);
+        }
+        if let Some(auth) = &self.auth {
+            let credentials = match auth {
+                EndpointAuth::Basic { username, password } => {
+                    let username = interpolate_env(username)?;
+                    let password = match password {
+                        Some(password) => interpolate_env(password)?,
+                        None => String::new(),
+                    };
+                    format!(
+                        "Basic {}",
+                        STANDARD.encode(format!("{username}:{password}"))
+                    )
+                }
+                EndpointAuth::Bearer { token } => format!("Bearer {}", interpolate_env(token)?),
+            };
+            let mut value = header_value(AUTHORIZATION.as_str(), &credentials)?;
+            value.set_sensitive(true);
+            headers.insert(AUTHORIZATION, value);
+        }
+        Ok(headers)
+    }
+}
+
+/// Checks if the value of a header is a secret, like the ones of `Authorization` or `X-Api-Key`
+fn is_sensitive_header(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SENSITIVE_HEADER_NAMES
+        .iter()
+        .any(|sensitive| name.contains(sensitive))
+}
+
+fn header_value(name: &str, value: &str) -> Result<HeaderValue, SRDFSparqlError> {
+    HeaderValue::from_str(value).map_err(|e| SRDFSparqlError::InvalidHeader {
+        name: name.to_string(),
+        error: e.to_string(),
+    })
+}
+
+/// Replaces the references to environment variables, written as `${NAME}`, by their values
+pub fn interpolate_env(str: &str) -> Result<String, SRDFSparqlError> {
+    let re_var = Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap();
+    let mut undefined = None;
+    let result = re_var.replace_all(str, |caps: &Captures| match std::env::var(&caps[1]) {
+        Ok(value) => value,
+        Err(_) => {
+            undefined.get_or_insert_with(|| caps[1].to_string());
+            String::new()
+        }
+    });
+    match undefined {
+        Some(name) => Err(SRDFSparqlError::UndefinedEnvVar { name }),
+        None => Ok(result.into_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpolate_env() {
+        std::env::set_var("RUDOF_TEST_SPARQL_USER", "alice");
+        assert_eq!(
+            interpolate_env("user ${RUDOF_TEST_SPARQL_USER} and $HOME").unwrap(),
+            "user alice and $HOME"
+        );
+        assert!(matches!(
+            interpolate_env("${RUDOF_TEST_UNDEFINED_VAR}"),
+            Err(SRDFSparqlError::UndefinedEnvVar { name }) if name == "RUDOF_TEST_UNDEFINED_VAR"
+        ));
+    }
+
+    #[test]
+    fn test_default_headers() {
+        std::env::set_var("RUDOF_TEST_SPARQL_TOKEN", "secret");
+        let config = SparqlConnectionConfig::new()
+            .with_bearer_token("${RUDOF_TEST_SPARQL_TOKEN}")
+            .with_header("X-Tenant", "research");
+        let headers = config.default_headers().unwrap();
+        assert_eq!(headers[AUTHORIZATION], "Bearer secret");
+        assert!(headers[AUTHORIZATION].is_sensitive());
+        assert_eq!(headers["x-tenant"], "research");
+
+        let basic = SparqlConnectionConfig::new().with_basic_auth("alice", Some("pwd"));
+        assert_eq!(
+            basic.default_headers().unwrap()[AUTHORIZATION],
+            "Basic YWxpY2U6cHdk"
+        );
+    }
+
+    #[test]
+    fn test_debug_hides_secrets() {
+        let config = SparqlConnectionConfig::new()
+            .with_basic_auth("alice", Some("pwd-secret"))
+            .with_header("Authorization", "Bearer header-secret")
+            .with_header("X-Api-Key", "key-secret")
+            .with_header("X-Tenant", "research");
+        let debug = format!("{config:?}");
+        assert!(!debug.contains("secret"));
+        assert!(debug.contains("alice"));
+        assert!(debug.contains("research"));
+
+        let config = SparqlConnectionConfig::new().with_bearer_token("token-secret");
+        assert!(!format!("{config:?}").contains("secret"));
+        let headers = config.default_headers().unwrap();
+        assert!(!format!("{headers:?}").contains("secret"));
+    }
+
+    #[test]
+    fn test_use_post() {
+        let config = SparqlConnectionConfig::new().with_post_threshold(10);
+        assert!(!config.use_post(10));
+        assert!(config.use_post(11));
+        let config = config.with_query_method(QueryMethod::Get);
+        assert!(!config.use_post(11));
+    }
+}
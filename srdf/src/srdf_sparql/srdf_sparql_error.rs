@@ -4,7 +4,7 @@ use oxrdf::Term;
 //use sparesults::QuerySolution;
 use thiserror::Error;
 
-use crate::SparqlVars;
+use crate::{SRDFGraphError, SparqlVars};
 
 #[derive(Error, Debug)]
 pub enum SRDFSparqlError {
@@ -25,12 +25,33 @@ pub enum SRDFSparqlError {
         err: IriParseError,
     },
 
+    #[error(transparent)]
+    SRDFGraphError {
+        #[from]
+        err: SRDFGraphError,
+    },
+
+    #[error("The triples of blank node _:{bnode} can't be obtained because it was not reached from an IRI through a chain of blank nodes and blank node labels are local to each result set")]
+    BlankNodeSubject { bnode: String },
+
+    #[error("Chains of blank nodes longer than {max} can't be obtained with the triples of a node, the maximum depth of the queries can be increased")]
+    BlankNodeDepth { max: usize },
+
+    #[error("Blank node _:{bnode} can't be pre-bound to ?{var} in a query to an endpoint, where it can't be referenced")]
+    BlankNodeBinding { var: String, bnode: String },
+
+    #[error("Expected group graph pattern to pre-bind variables in query: {query}")]
+    PreBinding { query: String },
+
     #[error("Unknown name for endpoint: {name}")]
     UnknownEndpontName { name: String },
 
     #[error("Error parsing body: {body}")]
     ParsingBody { body: String },
 
+    #[error("Expected a boolean as answer to an ASK query, found solutions: {body}")]
+    ExpectedBoolean { body: String },
+
     #[error("SPARQL solutions error: Expected IRI, got {value}")]
     SPARQLSolutionErrorNoIRI { value: Term },
 
@@ -43,6 +64,15 @@ pub enum SRDFSparqlError {
     #[error("SPARQL solutions error: Not found value for {value} in {solution:?}")]
     NotFoundInSolution { value: String, solution: String },
 
+    #[error("Concurrent SPARQL request failed: {error}")]
+    ConcurrentRequest { error: String },
+
+    #[error("Environment variable {name} referenced in the endpoint configuration is not defined")]
+    UndefinedEnvVar { name: String },
+
+    #[error("Invalid HTTP header {name}: {error}")]
+    InvalidHeader { name: String, error: String },
+
     #[error("Expected term {term} to be a subject")]
     NoSubject { term: Term },
 
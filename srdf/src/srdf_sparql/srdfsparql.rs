@@ -1,5 +1,6 @@
-use crate::{lang::Lang, literal::Literal, Object, SRDFSparqlError};
+use crate::{lang::Lang, literal::Literal, srdf_graph::SRDFGraph, Object, SRDFSparqlError};
 use crate::{AsyncSRDF, QuerySRDF, QuerySolution, QuerySolutions, SRDFBasic, VarName, SRDF};
+use crate::{EndpointDescription, SparqlConnectionConfig};
 use async_trait::async_trait;
 use colored::*;
 use iri_s::IriS;
@@ -9,19 +10,16 @@ use oxrdf::{
 };
 use prefixmap::{IriRef, PrefixMap};
 use regex::Regex;
-use reqwest::{
-    blocking::Client,
-    header::{self, ACCEPT, USER_AGENT},
-    Url,
-};
+use reqwest::{blocking::Client, Url};
 use sparesults::{
     QueryResultsFormat, QueryResultsParser, QuerySolution as OxQuerySolution,
     ReaderQueryResultsParserOutput,
 };
 use std::{
-    collections::{hash_map::Entry, HashMap, HashSet},
+    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
     fmt::Display,
     str::FromStr,
+    sync::{Arc, Mutex, MutexGuard},
 };
 
 type Result<A> = std::result::Result<A, SRDFSparqlError>;
@@ -32,18 +30,60 @@ pub struct SRDFSparql {
     endpoint_iri: IriS,
     prefixmap: PrefixMap,
     client: Client,
+
+    /// Named graph used to answer queries. If `None`, the default graph of the endpoint is used
+    graph: Option<IriS>,
+    connection: SparqlConnectionConfig,
+
+    /// Maximum length of the chains of blank nodes that are obtained with the triples of a node
+    /// before trying with longer chains
+    blank_node_depth: usize,
+
+    /// Maximum length of the chains of blank nodes, longer chains are reported as errors
+    max_blank_node_depth: usize,
+
+    /// Blank nodes returned by the endpoint with the triples that were obtained with them
+    blank_nodes: Arc<Mutex<BlankNodes>>,
 }
 
 impl SRDFSparql {
     pub fn new(iri: &IriS, prefixmap: &PrefixMap) -> Result<SRDFSparql> {
-        let client = sparql_client()?;
+        SRDFSparql::new_with_connection(iri, prefixmap, &SparqlConnectionConfig::default())
+    }
+
+    /// Creates an endpoint whose requests use the authentication, headers, timeout
+    /// and query method of `connection`
+    pub fn new_with_connection(
+        iri: &IriS,
+        prefixmap: &PrefixMap,
+        connection: &SparqlConnectionConfig,
+    ) -> Result<SRDFSparql> {
+        let client = sparql_client(connection)?;
         Ok(SRDFSparql {
             endpoint_iri: iri.clone(),
             prefixmap: prefixmap.clone(),
             client,
+            graph: None,
+            connection: connection.clone(),
+            blank_node_depth: DEFAULT_BLANK_NODE_DEPTH,
+            max_blank_node_depth: DEFAULT_MAX_BLANK_NODE_DEPTH,
+            blank_nodes: Arc::new(Mutex::new(BlankNodes::default())),
         })
     }
 
+    /// Creates an endpoint from its description in the configuration
+    pub fn from_endpoint_description(description: &EndpointDescription) -> Result<SRDFSparql> {
+        SRDFSparql::new_with_connection(
+            description.query_url(),
+            description.prefixmap(),
+            description.connection(),
+        )
+    }
+
+    pub fn connection(&self) -> &SparqlConnectionConfig {
+        &self.connection
+    }
+
     pub fn iri(&self) -> &IriS {
         &self.endpoint_iri
     }
@@ -65,6 +105,51 @@ impl SRDFSparql {
         self
     }
 
+    pub fn graph(&self) -> Option<&IriS> {
+        self.graph.as_ref()
+    }
+
+    /// Restricts the queries to a named graph of the endpoint.
+    /// Triple patterns are wrapped in `GRAPH` and queries run with `query_select` or `query_ask`
+    /// take the graph as default graph, like with `FROM`
+    pub fn with_graph(mut self, graph: Option<IriS>) -> SRDFSparql {
+        self.graph = graph;
+        self
+    }
+
+    pub fn set_graph(&mut self, graph: Option<IriS>) {
+        self.graph = graph;
+    }
+
+    pub fn blank_node_depth(&self) -> usize {
+        self.blank_node_depth
+    }
+
+    /// Sets the length of the chains of blank nodes that are obtained in the first query for the triples of a node.
+    /// Longer chains are obtained with more queries
+    pub fn with_blank_node_depth(mut self, depth: usize) -> SRDFSparql {
+        self.blank_node_depth = depth;
+        self
+    }
+
+    pub fn max_blank_node_depth(&self) -> usize {
+        self.max_blank_node_depth
+    }
+
+    /// Sets the maximum length of the chains of blank nodes that are obtained with the triples of a node.
+    /// Queries for longer chains grow with their length, so they fail with an error
+    pub fn with_max_blank_node_depth(mut self, depth: usize) -> SRDFSparql {
+        self.max_blank_node_depth = depth;
+        self
+    }
+
+    /// Sets the maximum number of blank nodes whose triples are kept in memory.
+    /// The blank nodes that were obtained first are forgotten and asking for their triples fails with an error
+    pub fn with_blank_node_capacity(self, capacity: usize) -> SRDFSparql {
+        self.blank_nodes().capacity = capacity;
+        self
+    }
+
     fn show_blanknode(&self, bn: &OxBlankNode) -> String {
         let str: String = format!("{}", bn);
         format!("{}", str.green())
@@ -83,12 +168,7 @@ impl FromStr for SRDFSparql {
         let re_iri = Regex::new(r"<(.*)>").unwrap();
         if let Some(iri_str) = re_iri.captures(s) {
             let iri_s = IriS::from_str(&iri_str[1])?;
-            let client = sparql_client()?;
-            Ok(SRDFSparql {
-                endpoint_iri: iri_s,
-                prefixmap: PrefixMap::new(),
-                client,
-            })
+            SRDFSparql::new(&iri_s, &PrefixMap::new())
         } else {
             match s.to_lowercase().as_str() {
                 "wikidata" => SRDFSparql::wikidata(),
@@ -190,7 +270,7 @@ impl SRDFBasic for SRDFSparql {
 
     fn term_as_object(term: &Self::Term) -> Object {
         match term {
-            Self::Term::BlankNode(bn) => Object::BlankNode(bn.to_string()),
+            Self::Term::BlankNode(bn) => Object::BlankNode(bn.as_str().to_string()),
             Self::Term::Literal(lit) => match lit.to_owned().destruct() {
                 (s, None, None) => Object::Literal(Literal::StringLiteral {
                     lexical_form: s,
@@ -211,7 +291,7 @@ impl SRDFBasic for SRDFSparql {
             Self::Term::NamedNode(iri) => Object::Iri(Self::iri2iri_s(iri)),
 
             #[cfg(feature = "rdf-star")]
-            OxTerm::Triple(_) => unimplemented!(),
+            OxTerm::Triple(_) => <SRDFGraph as SRDFBasic>::term_as_object(term),
         }
     }
 
@@ -267,12 +347,13 @@ impl SRDFBasic for SRDFSparql {
         OxTerm::BlankNode(bnode)
     }
 
-    fn object_as_term(_obj: &Object) -> Self::Term {
-        todo!()
+    fn object_as_term(obj: &Object) -> Result<Self::Term> {
+        // Both implementations are based on oxrdf terms
+        Ok(<SRDFGraph as SRDFBasic>::object_as_term(obj)?)
     }
 
-    fn bnode_as_subject(_bnode: Self::BNode) -> Self::Subject {
-        todo!()
+    fn bnode_as_subject(bnode: Self::BNode) -> Self::Subject {
+        OxSubject::BlankNode(bnode)
     }
 }
 
@@ -286,11 +367,12 @@ impl AsyncSRDF for SRDFSparql {
     type Err = SRDFSparqlError;
 
     async fn get_predicates_subject(&self, subject: &OxSubject) -> Result<HashSet<OxNamedNode>> {
-        let query = format!(r#"select ?pred where {{ {} ?pred ?obj . }}"#, subject);
-        let solutions = make_sparql_query(query.as_str(), &self.client, &self.endpoint_iri)?;
+        let pattern = graph_pattern(&self.graph, &format!("{} ?pred ?obj .", subject));
+        let query = format!(r#"select ?pred where {{ {pattern} }}"#);
+        let solutions = self.make_sparql_query(query.as_str(), None)?;
         let mut results = HashSet::new();
         for solution in solutions {
-            let n = get_iri_solution(solution, "pred")?;
+            let n = get_iri_solution(&solution, "pred")?;
             results.insert(n.clone());
         }
         Ok(results)
@@ -298,50 +380,110 @@ impl AsyncSRDF for SRDFSparql {
 
     async fn get_objects_for_subject_predicate(
         &self,
-        _subject: &OxSubject,
-        _pred: &OxNamedNode,
+        subject: &OxSubject,
+        pred: &OxNamedNode,
     ) -> Result<HashSet<OxTerm>> {
-        todo!();
+        self.objects_for_subject_predicate(subject, pred)
     }
 
     async fn get_subjects_for_object_predicate(
         &self,
-        _object: &OxTerm,
-        _pred: &OxNamedNode,
+        object: &OxTerm,
+        pred: &OxNamedNode,
     ) -> Result<HashSet<OxSubject>> {
-        todo!();
+        self.subjects_with_predicate_object(pred, object)
+    }
+
+    /// The triples of the blank nodes that can be reached from the subjects are obtained with them
+    async fn get_triples_with_subjects(&self, subjects: &[OxSubject]) -> Result<Vec<TripleRow>> {
+        let mut triples = self.neighbourhood_triples(subjects, None)?;
+        for subject in subjects {
+            if let OxSubject::BlankNode(bnode) = subject {
+                for (pred, objects) in self.blank_nodes().outgoing(bnode)? {
+                    triples.extend(
+                        objects
+                            .into_iter()
+                            .map(|o| (subject.clone(), pred.clone(), o)),
+                    )
+                }
+            }
+        }
+        Ok(triples)
+    }
+
+    async fn get_triples_with_objects(&self, objects: &[OxTerm]) -> Result<Vec<TripleRow>> {
+        let mut triples = Vec::new();
+        for query in objects_queries(&self.graph, objects, DEFAULT_BATCH_SIZE) {
+            let solutions = self.make_sparql_query(&query, None)?;
+            triples.extend(self.blank_nodes().rename(solutions_as_triples(solutions)?))
+        }
+        for object in objects {
+            if let OxTerm::BlankNode(bnode) = object {
+                for (pred, subjects) in self.blank_nodes().incoming(bnode)? {
+                    triples.extend(
+                        subjects
+                            .into_iter()
+                            .map(|s| (s, pred.clone(), object.clone())),
+                    )
+                }
+            }
+        }
+        Ok(triples)
+    }
+
+    async fn get_triples_with_predicate(&self, pred: &OxNamedNode) -> Result<Vec<TripleRow>> {
+        let query = predicate_query(&self.graph, pred);
+        let solutions = self.make_sparql_query(&query, None)?;
+        solutions_as_triples(solutions)
+    }
+
+    async fn get_nodes_for_query(&self, query: &str) -> Result<Vec<OxTerm>> {
+        let solutions = self.make_sparql_query(query, self.graph.as_ref())?;
+        Ok(solutions
+            .iter()
+            .filter_map(|solution| solution.get(0).cloned())
+            .collect())
     }
 }
 
 impl SRDF for SRDFSparql {
     fn predicates_for_subject(&self, subject: &OxSubject) -> Result<HashSet<OxNamedNode>> {
-        let query = format!(r#"select ?pred where {{ {} ?pred ?obj . }}"#, subject);
+        if let OxSubject::BlankNode(bnode) = subject {
+            let arcs = self.blank_nodes().outgoing(bnode)?;
+            return Ok(arcs.into_keys().collect());
+        }
+        let pattern = graph_pattern(&self.graph, &format!("{} ?pred ?obj .", subject));
+        let query = format!(r#"select ?pred where {{ {pattern} }}"#);
         tracing::debug!(
             "SPARQL query (get predicates for subject {subject}): {}",
             query
         );
-        let solutions = make_sparql_query(query.as_str(), &self.client, &self.endpoint_iri)?;
+        let solutions = self.make_sparql_query(query.as_str(), None)?;
         let mut results = HashSet::new();
         for solution in solutions {
-            let n = get_iri_solution(solution, "pred")?;
+            let n = get_iri_solution(&solution, "pred")?;
             results.insert(n.clone());
         }
         Ok(results)
     }
 
+    /// The objects are obtained with the triples of the blank nodes that can be reached from them,
+    /// so the blank nodes in the result can be used as subjects later
     fn objects_for_subject_predicate(
         &self,
         subject: &OxSubject,
         pred: &OxNamedNode,
     ) -> Result<HashSet<OxTerm>> {
-        let query = format!(r#"select ?obj where {{ {} {} ?obj . }}"#, subject, pred);
-        let solutions = make_sparql_query(query.as_str(), &self.client, &self.endpoint_iri)?;
-        let mut results = HashSet::new();
-        for solution in solutions {
-            let n = get_object_solution(solution, "obj")?;
-            results.insert(n.clone());
+        if let OxSubject::BlankNode(bnode) = subject {
+            let mut arcs = self.blank_nodes().outgoing(bnode)?;
+            return Ok(arcs.remove(pred).unwrap_or_default());
         }
-        Ok(results)
+        Ok(self
+            .neighbourhood_triples(std::slice::from_ref(subject), Some(pred))?
+            .into_iter()
+            .filter(|(subj, _, _)| subj == subject)
+            .map(|(_, _, obj)| obj)
+            .collect())
     }
 
     fn subjects_with_predicate_object(
@@ -349,12 +491,19 @@ impl SRDF for SRDFSparql {
         pred: &OxNamedNode,
         object: &OxTerm,
     ) -> Result<HashSet<OxSubject>> {
-        let query = format!(r#"select ?subj where {{ ?subj {} {} . }}"#, pred, object);
-        let solutions = make_sparql_query(query.as_str(), &self.client, &self.endpoint_iri)?;
+        if let OxTerm::BlankNode(bnode) = object {
+            let mut arcs = self.blank_nodes().incoming(bnode)?;
+            return Ok(arcs.remove(pred).unwrap_or_default());
+        }
+        let pattern = graph_pattern(&self.graph, &format!("?subj {} {} .", pred, object));
+        let query = format!(r#"select ?subj where {{ {pattern} }}"#);
+        let solutions = self.make_sparql_query(query.as_str(), None)?;
+        let mut blank_nodes = self.blank_nodes();
+        let mut labels = HashMap::new();
         let mut results = HashSet::new();
         for solution in solutions {
-            let n = get_subject_solution(solution, "subj")?;
-            results.insert(n.clone());
+            let n = get_subject_solution(&solution, "subj")?;
+            results.insert(blank_nodes.subject(&mut labels, n));
         }
         Ok(results)
     }
@@ -363,22 +512,17 @@ impl SRDF for SRDFSparql {
         &self,
         subject: &Self::Subject,
     ) -> Result<HashMap<Self::IRI, HashSet<Self::Term>>> {
-        outgoing_neighs(
-            subject.to_string().as_str(),
-            &self.client,
-            &self.endpoint_iri,
-        )
+        self.outgoing_neighs(subject)
     }
 
     fn incoming_arcs(
         &self,
         object: &Self::Term,
     ) -> Result<HashMap<Self::IRI, HashSet<Self::Subject>>> {
-        incoming_neighs(
-            object.to_string().as_str(),
-            &self.client,
-            &self.endpoint_iri,
-        )
+        if let OxTerm::BlankNode(bnode) = object {
+            return self.blank_nodes().incoming(bnode);
+        }
+        incoming_neighs(object.to_string().as_str(), self)
     }
 
     fn outgoing_arcs_from_list(
@@ -389,14 +533,43 @@ impl SRDF for SRDFSparql {
         (HashMap<Self::IRI, HashSet<Self::Term>>, Vec<Self::IRI>),
         Self::Err,
     > {
-        outgoing_neighs_from_list(subject, preds, &self.client, &self.endpoint_iri)
+        outgoing_neighs_from_list(subject, preds, self)
     }
 
     fn triples_with_predicate(
         &self,
-        _pred: &Self::IRI,
+        pred: &Self::IRI,
     ) -> std::prelude::v1::Result<Vec<crate::Triple<Self>>, Self::Err> {
-        todo!()
+        let query = predicate_query(&self.graph, pred);
+        let solutions = self.make_sparql_query(query.as_str(), None)?;
+        let triples = self.blank_nodes().rename(solutions_as_triples(solutions)?);
+        Ok(triples
+            .into_iter()
+            .map(|(subj, pred, obj)| crate::Triple::new(subj, pred, obj))
+            .collect())
+    }
+
+    /// Obtains the outgoing arcs of the subjects with one query for each batch of subjects,
+    /// which contains the subjects in a `VALUES` block.
+    /// The triples of the blank nodes that can be reached from the subjects are obtained in the same queries
+    fn outgoing_arcs_for_subjects(
+        &self,
+        subjects: &[OxSubject],
+    ) -> Result<HashMap<OxSubject, HashMap<OxNamedNode, HashSet<OxTerm>>>> {
+        let mut result: HashMap<OxSubject, HashMap<OxNamedNode, HashSet<OxTerm>>> = HashMap::new();
+        for subject in subjects {
+            let arcs = match subject {
+                OxSubject::BlankNode(bnode) => self.blank_nodes().outgoing(bnode)?,
+                _ => HashMap::new(),
+            };
+            result.insert(subject.clone(), arcs);
+        }
+        for (subject, pred, object) in self.neighbourhood_triples(subjects, None)? {
+            if let Some(arcs) = result.get_mut(&subject) {
+                arcs.entry(pred).or_default().insert(object);
+            }
+        }
+        Ok(result)
     }
 }
 
@@ -430,24 +603,58 @@ impl QuerySRDF for SRDFSparql {
 
 impl QuerySRDF for SRDFSparql {
     fn query_select(&self, query: &str) -> Result<QuerySolutions<Self>> {
-        let solutions = make_sparql_query(query, &self.client, &self.endpoint_iri)?;
+        let solutions = self.make_sparql_query(query, self.graph.as_ref())?;
         let qs: Vec<QuerySolution<SRDFSparql>> = solutions.iter().map(cnv_query_solution).collect();
         Ok(QuerySolutions::new(qs))
     }
 
     fn query_ask(&self, query: &str) -> Result<bool> {
-        make_sparql_query(query, &self.client, &self.endpoint_iri)?
-            .first()
-            .and_then(|query_solution| query_solution.get(0))
-            .and_then(|term| match term {
-                OxTerm::Literal(literal) => Some(literal.value()),
-                _ => None,
-            })
-            .and_then(|value| value.parse().ok())
-            .ok_or_else(|| todo!())
+        let body = self.send_query(query, self.graph.as_ref())?;
+        parse_boolean(body)
+    }
+
+    fn query_select_with_bindings(
+        &self,
+        query: &str,
+        bindings: &[(VarName, OxTerm)],
+    ) -> Result<QuerySolutions<Self>> {
+        self.query_select(&values_pre_binding(query, bindings)?)
+    }
+
+    fn query_ask_with_bindings(&self, query: &str, bindings: &[(VarName, OxTerm)]) -> Result<bool> {
+        self.query_ask(&values_pre_binding(query, bindings)?)
     }
 }
 
+/// Pre-binds the variables of a query adding a `VALUES` clause at the beginning of its outermost group graph pattern.
+/// The blank nodes returned by an endpoint can't be referenced from a query, so they can't be pre-bound
+fn values_pre_binding(query: &str, bindings: &[(VarName, OxTerm)]) -> Result<String> {
+    if bindings.is_empty() {
+        return Ok(query.to_string());
+    }
+    let mut vars = Vec::new();
+    let mut values = Vec::new();
+    for (var, term) in bindings {
+        if let OxTerm::BlankNode(bnode) = term {
+            return Err(SRDFSparqlError::BlankNodeBinding {
+                var: var.as_str().to_string(),
+                bnode: bnode.as_str().to_string(),
+            });
+        }
+        vars.push(var.to_string());
+        values.push(term.to_string());
+    }
+    let index = query.find('{').ok_or_else(|| SRDFSparqlError::PreBinding {
+        query: query.to_string(),
+    })?;
+    let (head, tail) = query.split_at(index + 1);
+    Ok(format!(
+        "{head}\n  VALUES ({}) {{ ({}) }}{tail}",
+        vars.join(" "),
+        values.join(" ")
+    ))
+}
+
 fn cnv_query_solution(qs: &OxQuerySolution) -> QuerySolution<SRDFSparql> {
     let mut variables = Vec::new();
     let mut values = Vec::new();
@@ -462,27 +669,397 @@ fn cnv_query_solution(qs: &OxQuerySolution) -> QuerySolution<SRDFSparql> {
     QuerySolution::new(variables, values)
 }
 
-fn sparql_client() -> Result<Client> {
-    let mut headers = header::HeaderMap::new();
-    headers.insert(
-        ACCEPT,
-        header::HeaderValue::from_static("application/sparql-results+json"),
-    );
-    headers.insert(USER_AGENT, header::HeaderValue::from_static("rudof"));
-    let client = reqwest::blocking::Client::builder()
-        .default_headers(headers)
-        .build()?;
+fn sparql_client(connection: &SparqlConnectionConfig) -> Result<Client> {
+    let mut builder =
+        reqwest::blocking::Client::builder().default_headers(connection.default_headers()?);
+    if let Some(timeout) = connection.timeout() {
+        builder = builder.timeout(timeout);
+    }
+    let client = builder.build()?;
     Ok(client)
 }
 
-fn make_sparql_query(
-    query: &str,
-    client: &Client,
-    endpoint_iri: &IriS,
-) -> Result<Vec<OxQuerySolution>> {
-    let url = Url::parse_with_params(endpoint_iri.as_str(), &[("query", query)])?;
-    tracing::debug!("SPARQL query: {}", url);
-    let body = client.get(url).send()?.text()?;
+/// Wraps a group graph pattern in `GRAPH` when the queries are restricted to a named graph
+pub(crate) fn graph_pattern(graph: &Option<IriS>, pattern: &str) -> String {
+    match graph {
+        Some(graph) => format!("GRAPH <{}> {{ {pattern} }}", graph.as_str()),
+        None => pattern.to_string(),
+    }
+}
+
+impl SRDFSparql {
+    /// Runs a query in the endpoint. If `default_graph` is set, it is sent as the `default-graph-uri`
+    /// parameter of the SPARQL protocol, which has the same meaning as a `FROM` clause in the query
+    fn make_sparql_query(
+        &self,
+        query: &str,
+        default_graph: Option<&IriS>,
+    ) -> Result<Vec<OxQuerySolution>> {
+        let body = self.send_query(query, default_graph)?;
+        parse_solutions(body)
+    }
+
+    /// Sends a query to the endpoint and returns the body of the response.
+    /// The query goes in the body of a POST request when the connection settings ask for it
+    fn send_query(&self, query: &str, default_graph: Option<&IriS>) -> Result<String> {
+        let mut params = vec![("query", query)];
+        if let Some(graph) = default_graph {
+            params.push(("default-graph-uri", graph.as_str()));
+        }
+        let url = Url::parse_with_params(self.endpoint_iri.as_str(), &params)?;
+        let request = if self.connection.use_post(url.as_str().len()) {
+            tracing::debug!("SPARQL query (POST to {}): {}", self.endpoint_iri, query);
+            self.client.post(self.endpoint_iri.as_str()).form(&params)
+        } else {
+            tracing::debug!("SPARQL query: {}", url);
+            self.client.get(url)
+        };
+        let body = request.send()?.error_for_status()?.text()?;
+        Ok(body)
+    }
+
+    fn blank_nodes(&self) -> MutexGuard<'_, BlankNodes> {
+        // A panic while the lock was held can't leave the blank nodes in an inconsistent state
+        self.blank_nodes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn outgoing_neighs(&self, subject: &OxSubject) -> Result<OutputNodes> {
+        if let OxSubject::BlankNode(bnode) = subject {
+            return self.blank_nodes().outgoing(bnode);
+        }
+        let mut results = OutputNodes::new();
+        for (subj, pred, obj) in self.neighbourhood_triples(std::slice::from_ref(subject), None)? {
+            if &subj == subject {
+                results.entry(pred).or_default().insert(obj);
+            }
+        }
+        Ok(results)
+    }
+
+    /// Obtains the triples of the subjects that are not blank nodes together with the triples of the blank nodes
+    /// that can be reached from them, which are remembered so they can be used as subjects later.
+    /// If `pred` is set, only the triples of the subjects with that predicate are obtained.
+    /// When some chain of blank nodes is longer than the depth of the query, the query is repeated with a greater depth
+    /// up to the maximum depth
+    fn neighbourhood_triples(
+        &self,
+        subjects: &[OxSubject],
+        pred: Option<&OxNamedNode>,
+    ) -> Result<Vec<TripleRow>> {
+        let mut triples = Vec::new();
+        for batch in neighbourhood_batches(subjects, DEFAULT_BATCH_SIZE) {
+            let mut depth = self.blank_node_depth.min(self.max_blank_node_depth);
+            let rows = loop {
+                let query = neighbourhood_query(&self.graph, &batch, depth, pred);
+                tracing::debug!("SPARQL query (neighbourhood of subjects): {query}");
+                let solutions = self.make_sparql_query(query.as_str(), None)?;
+                let rows = solutions_as_depth_triples(solutions)?;
+                if !is_truncated(&rows, depth) {
+                    break rows;
+                }
+                depth = next_blank_node_depth(depth, self.max_blank_node_depth)?;
+            };
+            let rows = rows.into_iter().map(|(triple, _)| triple).collect();
+            triples.extend(self.blank_nodes().add_neighbourhood(rows));
+        }
+        Ok(triples)
+    }
+}
+
+/// Length of the chains of blank nodes obtained by default in the first query for the triples of a node
+pub const DEFAULT_BLANK_NODE_DEPTH: usize = 2;
+
+/// Default maximum length of the chains of blank nodes obtained with the triples of a node
+pub const DEFAULT_MAX_BLANK_NODE_DEPTH: usize = 32;
+
+/// Default maximum number of blank nodes whose triples are kept in memory
+pub const DEFAULT_BLANK_NODE_CAPACITY: usize = 100_000;
+
+/// Depth of the next query for the triples of a node when some chain of blank nodes is longer than `depth`.
+/// The depth is doubled up to `max`, and chains longer than `max` are reported as errors
+pub(crate) fn next_blank_node_depth(depth: usize, max: usize) -> Result<usize> {
+    if depth >= max {
+        Err(SRDFSparqlError::BlankNodeDepth { max })
+    } else {
+        Ok(depth.saturating_mul(2).clamp(1, max))
+    }
+}
+
+/// Generates fresh labels for the blank nodes returned by an endpoint.
+/// The labels start with a random prefix, so the blank nodes returned by the endpoint can be told apart
+/// from other blank nodes without remembering them
+#[derive(Debug)]
+pub(crate) struct BlankNodeLabels {
+    prefix: String,
+    counter: u64,
+}
+
+impl BlankNodeLabels {
+    pub(crate) fn new() -> BlankNodeLabels {
+        BlankNodeLabels {
+            prefix: format!("{}n", OxBlankNode::default().as_str()),
+            counter: 0,
+        }
+    }
+
+    pub(crate) fn fresh(&mut self) -> OxBlankNode {
+        self.counter += 1;
+        OxBlankNode::new_unchecked(format!("{}{}", self.prefix, self.counter))
+    }
+
+    /// Checks if the blank node was generated by these labels
+    pub(crate) fn contains(&self, bnode: &OxBlankNode) -> bool {
+        bnode.as_str().starts_with(&self.prefix)
+    }
+}
+
+type InputNodes = HashMap<OxNamedNode, HashSet<OxSubject>>;
+
+/// Blank nodes returned by an endpoint. Blank node labels are local to the result set in which they appear,
+/// so they are renamed with fresh labels and their triples are kept when they were obtained in the same result set
+#[derive(Debug)]
+struct BlankNodes {
+    /// Outgoing arcs of the blank nodes that were reached from a node that is not a blank node
+    outgoing: HashMap<OxBlankNode, OutputNodes>,
+
+    /// Incoming arcs of the blank nodes that were reached from a node that is not a blank node
+    incoming: HashMap<OxBlankNode, InputNodes>,
+
+    /// Blank nodes with arcs in the order in which they were obtained, so the oldest ones are forgotten first
+    order: VecDeque<OxBlankNode>,
+
+    /// Maximum number of blank nodes whose arcs are kept
+    capacity: usize,
+
+    /// Labels of the blank nodes that have been returned by the endpoint
+    labels: BlankNodeLabels,
+}
+
+impl Default for BlankNodes {
+    fn default() -> Self {
+        BlankNodes {
+            outgoing: HashMap::new(),
+            incoming: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: DEFAULT_BLANK_NODE_CAPACITY,
+            labels: BlankNodeLabels::new(),
+        }
+    }
+}
+
+impl BlankNodes {
+    fn outgoing(&self, bnode: &OxBlankNode) -> Result<OutputNodes> {
+        match self.outgoing.get(bnode) {
+            Some(arcs) => Ok(arcs.clone()),
+            None => self.unknown(bnode),
+        }
+    }
+
+    fn incoming(&self, bnode: &OxBlankNode) -> Result<InputNodes> {
+        match self.incoming.get(bnode) {
+            Some(arcs) => Ok(arcs.clone()),
+            None => self.unknown(bnode),
+        }
+    }
+
+    /// Blank nodes that were not returned by the endpoint, like the ones of other RDF data, have no triples in it.
+    /// The triples of the blank nodes returned by the endpoint are unknown if they were not obtained with the node
+    /// from which they are reached or they have been forgotten
+    fn unknown<A: Default>(&self, bnode: &OxBlankNode) -> Result<A> {
+        if self.labels.contains(bnode) {
+            Err(SRDFSparqlError::BlankNodeSubject {
+                bnode: bnode.as_str().to_string(),
+            })
+        } else {
+            Ok(A::default())
+        }
+    }
+
+    /// Renames a blank node of a result set. `labels` contains the blank nodes already renamed in the same result set
+    fn fresh(
+        &mut self,
+        labels: &mut HashMap<OxBlankNode, OxBlankNode>,
+        bnode: OxBlankNode,
+    ) -> OxBlankNode {
+        labels
+            .entry(bnode)
+            .or_insert_with(|| self.labels.fresh())
+            .clone()
+    }
+
+    fn subject(
+        &mut self,
+        labels: &mut HashMap<OxBlankNode, OxBlankNode>,
+        subject: OxSubject,
+    ) -> OxSubject {
+        match subject {
+            OxSubject::BlankNode(bnode) => OxSubject::BlankNode(self.fresh(labels, bnode)),
+            _ => subject,
+        }
+    }
+
+    fn term(&mut self, labels: &mut HashMap<OxBlankNode, OxBlankNode>, term: OxTerm) -> OxTerm {
+        match term {
+            OxTerm::BlankNode(bnode) => OxTerm::BlankNode(self.fresh(labels, bnode)),
+            _ => term,
+        }
+    }
+
+    /// Renames the blank nodes of the triples of a result set
+    fn rename(&mut self, triples: Vec<TripleRow>) -> Vec<TripleRow> {
+        let mut labels = HashMap::new();
+        triples
+            .into_iter()
+            .map(|(subj, pred, obj)| {
+                (
+                    self.subject(&mut labels, subj),
+                    pred,
+                    self.term(&mut labels, obj),
+                )
+            })
+            .collect()
+    }
+
+    /// Renames the blank nodes of a result set that contains all the triples of the blank nodes that appear in it
+    /// and keeps their arcs, forgetting the oldest blank nodes when there are more than `capacity`
+    fn add_neighbourhood(&mut self, triples: Vec<TripleRow>) -> Vec<TripleRow> {
+        let triples = self.rename(triples);
+        for (subj, pred, obj) in &triples {
+            if let OxSubject::BlankNode(bnode) = subj {
+                self.add_blank_node(bnode);
+                self.outgoing
+                    .entry(bnode.clone())
+                    .or_default()
+                    .entry(pred.clone())
+                    .or_default()
+                    .insert(obj.clone());
+            }
+            if let OxTerm::BlankNode(bnode) = obj {
+                // Blank nodes without outgoing arcs are known to have none
+                self.add_blank_node(bnode);
+                self.incoming
+                    .entry(bnode.clone())
+                    .or_default()
+                    .entry(pred.clone())
+                    .or_default()
+                    .insert(subj.clone());
+            }
+        }
+        while self.order.len() > self.capacity {
+            if let Some(bnode) = self.order.pop_front() {
+                self.outgoing.remove(&bnode);
+                self.incoming.remove(&bnode);
+            }
+        }
+        triples
+    }
+
+    fn add_blank_node(&mut self, bnode: &OxBlankNode) {
+        if let Entry::Vacant(entry) = self.outgoing.entry(bnode.clone()) {
+            entry.insert(OutputNodes::new());
+            self.order.push_back(bnode.clone());
+        }
+    }
+}
+
+/// Query that obtains the triples whose subject is one of `subjects` or a blank node that can be reached from them
+/// through a chain of at most `depth` blank nodes. The length of the chain is returned in `?depth`.
+/// If `pred` is set, only the triples of the subjects with that predicate and the chains that start with it are obtained
+pub(crate) fn neighbourhood_query(
+    graph: &Option<IriS>,
+    subjects: &[String],
+    depth: usize,
+    pred: Option<&OxNamedNode>,
+) -> String {
+    let branches: Vec<String> = (0..=depth)
+        .map(|length| {
+            let mut patterns = Vec::new();
+            let mut node = "?root".to_string();
+            for i in 1..=length {
+                match pred {
+                    Some(pred) if i == 1 => patterns.push(format!("{node} {pred} ?b{i} .")),
+                    _ => patterns.push(format!("{node} ?p{i} ?b{i} .")),
+                }
+                node = format!("?b{i}");
+            }
+            match pred {
+                Some(pred) if length == 0 => {
+                    patterns.push(format!("{node} {pred} ?obj . BIND({pred} AS ?pred)"))
+                }
+                _ => patterns.push(format!("{node} ?pred ?obj .")),
+            }
+            if length > 0 {
+                let filters: Vec<String> =
+                    (1..=length).map(|i| format!("isBlank(?b{i})")).collect();
+                patterns.push(format!("FILTER({})", filters.join(" && ")));
+            }
+            patterns.push(format!("BIND({node} AS ?subj) BIND({length} AS ?depth)"));
+            format!("{{ {} }}", patterns.join(" "))
+        })
+        .collect();
+    let pattern = graph_pattern(graph, &branches.join(" UNION "));
+    format!(
+        "select ?subj ?pred ?obj ?depth where {{ VALUES ?root {{ {} }} {pattern} }}",
+        subjects.join(" ")
+    )
+}
+
+/// Subjects of the queries built by [`neighbourhood_query`] with `batch_size` subjects per query.
+/// Blank nodes are skipped because their labels are local to the result set in which they appeared,
+/// so their triples are obtained with the triples of the nodes from which they are reached
+pub(crate) fn neighbourhood_batches(subjects: &[OxSubject], batch_size: usize) -> Vec<Vec<String>> {
+    let roots: Vec<String> = subjects
+        .iter()
+        .filter(|subject| !matches!(subject, OxSubject::BlankNode(_)))
+        .map(|subject| subject.to_string())
+        .collect();
+    roots
+        .chunks(batch_size.max(1))
+        .map(|chunk| chunk.to_vec())
+        .collect()
+}
+
+/// Converts the solutions of a query built by [`neighbourhood_query`] into triples with the length of their chain
+pub(crate) fn solutions_as_depth_triples(
+    solutions: Vec<OxQuerySolution>,
+) -> Result<Vec<(TripleRow, usize)>> {
+    let mut triples = Vec::new();
+    for solution in solutions {
+        let subject = get_subject_solution(&solution, "subj")?;
+        let pred = get_iri_solution(&solution, "pred")?;
+        let object = get_object_solution(&solution, "obj")?;
+        let depth = match get_object_solution(&solution, "depth")? {
+            OxTerm::Literal(lit) => lit.value().parse::<usize>().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| SRDFSparqlError::NotFoundInSolution {
+            value: "depth".to_string(),
+            solution: format!("{solution:?}"),
+        })?;
+        triples.push(((subject, pred, object), depth))
+    }
+    Ok(triples)
+}
+
+/// Checks if some blank node at the end of the longest chains may have triples that were not obtained
+pub(crate) fn is_truncated(triples: &[(TripleRow, usize)], depth: usize) -> bool {
+    let subjects: HashSet<&OxBlankNode> = triples
+        .iter()
+        .filter_map(|((subj, _, _), _)| match subj {
+            OxSubject::BlankNode(bnode) => Some(bnode),
+            _ => None,
+        })
+        .collect();
+    triples.iter().any(|((_, _, obj), length)| match obj {
+        OxTerm::BlankNode(bnode) => *length == depth && !subjects.contains(bnode),
+        _ => false,
+    })
+}
+
+/// Parses the body of a response in SPARQL results JSON format
+pub(crate) fn parse_solutions(body: String) -> Result<Vec<OxQuerySolution>> {
     let mut results = Vec::new();
     let json_parser = QueryResultsParser::from_format(QueryResultsFormat::Json);
     if let ReaderQueryResultsParserOutput::Solutions(solutions) =
@@ -498,6 +1075,77 @@ fn make_sparql_query(
     }
 }
 
+/// Parses the answer of an ASK query, which is a boolean instead of solutions
+pub(crate) fn parse_boolean(body: String) -> Result<bool> {
+    let json_parser = QueryResultsParser::from_format(QueryResultsFormat::Json);
+    match json_parser.for_reader(body.as_bytes())? {
+        ReaderQueryResultsParserOutput::Boolean(value) => Ok(value),
+        ReaderQueryResultsParserOutput::Solutions(_) => {
+            Err(SRDFSparqlError::ExpectedBoolean { body })
+        }
+    }
+}
+
+/// Number of nodes of the `VALUES` block of each query that fetches the triples of several nodes
+pub const DEFAULT_BATCH_SIZE: usize = 50;
+
+pub(crate) type TripleRow = (OxSubject, OxNamedNode, OxTerm);
+
+/// Queries that obtain the triples whose object is one of `objects` with `batch_size` objects per query.
+/// Blank nodes are skipped because their labels are local to the result set in which they appeared,
+/// so their incoming arcs are obtained with the triples of the nodes from which they are reached
+pub(crate) fn objects_queries(
+    graph: &Option<IriS>,
+    objects: &[OxTerm],
+    batch_size: usize,
+) -> Vec<String> {
+    let values: Vec<String> = objects
+        .iter()
+        .filter_map(|object| match object {
+            OxTerm::NamedNode(n) => Some(n.to_string()),
+            OxTerm::Literal(lit) => Some(lit.to_string()),
+            _ => None,
+        })
+        .collect();
+    values_queries(graph, "obj", &values, batch_size)
+}
+
+fn values_queries(
+    graph: &Option<IriS>,
+    var: &str,
+    values: &[String],
+    batch_size: usize,
+) -> Vec<String> {
+    let pattern = graph_pattern(graph, "?subj ?pred ?obj .");
+    values
+        .chunks(batch_size.max(1))
+        .map(|chunk| {
+            format!(
+                "select ?subj ?pred ?obj where {{ VALUES ?{var} {{ {} }} {pattern} }}",
+                chunk.join(" ")
+            )
+        })
+        .collect()
+}
+
+/// Query that obtains the triples with predicate `pred`
+pub(crate) fn predicate_query(graph: &Option<IriS>, pred: &OxNamedNode) -> String {
+    let pattern = graph_pattern(graph, "?subj ?pred ?obj .");
+    format!("select ?subj ?pred ?obj where {{ VALUES ?pred {{ {pred} }} {pattern} }}")
+}
+
+/// Converts the solutions of the queries built by [`objects_queries`] and [`predicate_query`] into triples
+pub(crate) fn solutions_as_triples(solutions: Vec<OxQuerySolution>) -> Result<Vec<TripleRow>> {
+    let mut triples = Vec::new();
+    for solution in solutions {
+        let subject = get_subject_solution(&solution, "subj")?;
+        let pred = get_iri_solution(&solution, "pred")?;
+        let object = get_object_solution(&solution, "obj")?;
+        triples.push((subject, pred, object))
+    }
+    Ok(triples)
+}
+
 #[derive(Debug)]
 pub struct SparqlVars {
     values: Vec<String>,
@@ -515,75 +1163,17 @@ impl Display for SparqlVars {
     }
 }
 
-fn outgoing_neighs(
-    subject: &str,
-    client: &Client,
-    endpoint_iri: &IriS,
-) -> Result<HashMap<OxNamedNode, HashSet<OxTerm>>> {
-    let pred = "pred";
-    let obj = "obj";
-    let query = format!("select ?{pred} ?{obj} where {{ {subject} ?{pred} ?{obj} }}");
-    let url = Url::parse_with_params(endpoint_iri.as_str(), &[("query", query)])?;
-    let body = client.get(url).send()?.text()?;
-    let mut results: HashMap<OxNamedNode, HashSet<OxTerm>> = HashMap::new();
-    let json_parser = QueryResultsParser::from_format(QueryResultsFormat::Json);
-    if let ReaderQueryResultsParserOutput::Solutions(solutions) =
-        json_parser.for_reader(body.as_bytes())?
-    {
-        for solution in solutions {
-            let sol = solution?;
-            match (sol.get(pred), sol.get(obj)) {
-                (Some(p), Some(v)) => match p {
-                    OxTerm::NamedNode(iri) => match results.entry(iri.clone()) {
-                        Entry::Occupied(mut vs) => {
-                            vs.get_mut().insert(v.clone());
-                        }
-                        Entry::Vacant(vacant) => {
-                            vacant.insert(HashSet::from([v.clone()]));
-                        }
-                    },
-                    _ => {
-                        return Err(SRDFSparqlError::SPARQLSolutionErrorNoIRI { value: p.clone() })
-                    }
-                },
-                (None, None) => {
-                    return Err(SRDFSparqlError::NotFoundVarsInSolution {
-                        vars: SparqlVars::new(vec![pred.to_string(), obj.to_string()]),
-                        solution: format!("{sol:?}"),
-                    })
-                }
-                (None, Some(_)) => {
-                    return Err(SRDFSparqlError::NotFoundVarsInSolution {
-                        vars: SparqlVars::new(vec![pred.to_string()]),
-                        solution: format!("{sol:?}"),
-                    })
-                }
-                (Some(_), None) => {
-                    return Err(SRDFSparqlError::NotFoundVarsInSolution {
-                        vars: SparqlVars::new(vec![obj.to_string()]),
-                        solution: format!("{sol:?}"),
-                    })
-                }
-            }
-        }
-        Ok(results)
-    } else {
-        Err(SRDFSparqlError::ParsingBody { body })
-    }
-}
-
 type OutputNodes = HashMap<OxNamedNode, HashSet<OxTerm>>;
 
 fn outgoing_neighs_from_list(
     subject: &OxSubject,
     preds: &[OxNamedNode],
-    client: &Client,
-    endpoint_iri: &IriS,
+    endpoint: &SRDFSparql,
 ) -> Result<(OutputNodes, Vec<OxNamedNode>)> {
     // This is not an efficient way to obtain the neighbours related with a set of predicates
     // At this moment, it obtains all neighbours and them removes the ones that are not in the list
     let mut remainder = Vec::new();
-    let mut all_results = outgoing_neighs(subject.to_string().as_str(), client, endpoint_iri)?;
+    let mut all_results = endpoint.outgoing_neighs(subject)?;
     let mut remove_keys = Vec::new();
     for key in all_results.keys() {
         if !preds.contains(key) {
@@ -599,14 +1189,15 @@ fn outgoing_neighs_from_list(
 
 fn incoming_neighs(
     object: &str,
-    client: &Client,
-    endpoint_iri: &IriS,
+    endpoint: &SRDFSparql,
 ) -> Result<HashMap<OxNamedNode, HashSet<OxSubject>>> {
     let pred = "pred";
     let subj = "subj";
-    let query = format!("select ?{pred} ?{subj} where {{ ?{subj} ?{pred} {object} }}");
-    let url = Url::parse_with_params(endpoint_iri.as_str(), &[("query", query)])?;
-    let body = client.get(url).send()?.text()?;
+    let pattern = graph_pattern(&endpoint.graph, &format!("?{subj} ?{pred} {object}"));
+    let query = format!("select ?{pred} ?{subj} where {{ {pattern} }}");
+    let body = endpoint.send_query(&query, None)?;
+    let mut blank_nodes = endpoint.blank_nodes();
+    let mut labels = HashMap::new();
     let mut results: HashMap<OxNamedNode, HashSet<OxSubject>> = HashMap::new();
     let json_parser = QueryResultsParser::from_format(QueryResultsFormat::Json);
     if let ReaderQueryResultsParserOutput::Solutions(solutions) =
@@ -616,7 +1207,9 @@ fn incoming_neighs(
             let sol = solution?;
             match (sol.get(pred), sol.get(subj)) {
                 (Some(p), Some(v)) => match p {
-                    OxTerm::NamedNode(iri) => match term_as_subject(v) {
+                    OxTerm::NamedNode(iri) => match term_as_subject(v)
+                        .map(|subj| blank_nodes.subject(&mut labels, subj))
+                    {
                         Some(subj) => match results.entry(iri.clone()) {
                             Entry::Occupied(mut vs) => {
                                 vs.get_mut().insert(subj.clone());
@@ -657,7 +1250,7 @@ fn incoming_neighs(
     }
 }
 
-fn get_iri_solution(solution: OxQuerySolution, name: &str) -> Result<OxNamedNode> {
+fn get_iri_solution(solution: &OxQuerySolution, name: &str) -> Result<OxNamedNode> {
     match solution.get(name) {
         Some(v) => match v {
             OxTerm::NamedNode(n) => Ok(n.clone()),
@@ -670,7 +1263,7 @@ fn get_iri_solution(solution: OxQuerySolution, name: &str) -> Result<OxNamedNode
     }
 }
 
-fn get_object_solution(solution: OxQuerySolution, name: &str) -> Result<OxTerm> {
+fn get_object_solution(solution: &OxQuerySolution, name: &str) -> Result<OxTerm> {
     match solution.get(name) {
         Some(v) => Ok(v.clone()),
         None => Err(SRDFSparqlError::NotFoundInSolution {
@@ -680,7 +1273,7 @@ fn get_object_solution(solution: OxQuerySolution, name: &str) -> Result<OxTerm>
     }
 }
 
-fn get_subject_solution(solution: OxQuerySolution, name: &str) -> Result<OxSubject> {
+fn get_subject_solution(solution: &OxQuerySolution, name: &str) -> Result<OxSubject> {
     match solution.get(name) {
         Some(v) => match term_as_subject(v) {
             Some(s) => Ok(s),
@@ -697,6 +1290,8 @@ fn term_as_subject(object: &OxTerm) -> Option<OxSubject> {
     match object {
         OxTerm::NamedNode(n) => Some(OxSubject::NamedNode(n.clone())),
         OxTerm::BlankNode(b) => Some(OxSubject::BlankNode(b.clone())),
+        #[cfg(feature = "rdf-star")]
+        OxTerm::Triple(t) => Some(OxSubject::Triple(t.clone())),
         _ => None,
     }
 }
@@ -706,8 +1301,7 @@ fn subject_as_term(subject: &OxSubject) -> OxTerm {
         OxSubject::NamedNode(n) => OxTerm::NamedNode(n.clone()),
         OxSubject::BlankNode(b) => OxTerm::BlankNode(b.clone()),
         #[cfg(feature = "rdf-star")]
-        #[cfg(feature = "rdf-star")]
-        OxSubject::Triple(_) => unimplemented!(),
+        OxSubject::Triple(t) => OxTerm::Triple(t.clone()),
     }
 }
 
@@ -729,4 +1323,125 @@ mod tests {
 
         assert!(data.contains(&p19));
     }
+
+    #[test]
+    fn check_debug_hides_credentials() {
+        let connection = SparqlConnectionConfig::new()
+            .with_basic_auth("alice", Some("pwd-secret"))
+            .with_header("X-Api-Key", "key-secret");
+        let endpoint = SRDFSparql::new_with_connection(
+            &IriS::new_unchecked("http://example.org/sparql"),
+            &PrefixMap::new(),
+            &connection,
+        )
+        .unwrap();
+        assert!(!format!("{endpoint:?}").contains("secret"));
+    }
+
+    #[test]
+    fn check_graph_pattern() {
+        let graph = Some(IriS::new_unchecked("http://example.org/g1"));
+        assert_eq!(
+            graph_pattern(&graph, "?s ?p ?o"),
+            "GRAPH <http://example.org/g1> { ?s ?p ?o }"
+        );
+        assert_eq!(graph_pattern(&None, "?s ?p ?o"), "?s ?p ?o");
+    }
+
+    #[test]
+    fn check_neighbourhood_query() {
+        let query = neighbourhood_query(&None, &["<http://example.org/x>".to_string()], 1, None);
+        assert_eq!(
+            query,
+            "select ?subj ?pred ?obj ?depth where { VALUES ?root { <http://example.org/x> } \
+             { ?root ?pred ?obj . BIND(?root AS ?subj) BIND(0 AS ?depth) } UNION \
+             { ?root ?p1 ?b1 . ?b1 ?pred ?obj . FILTER(isBlank(?b1)) BIND(?b1 AS ?subj) BIND(1 AS ?depth) } }"
+        );
+    }
+
+    #[test]
+    fn check_blank_nodes_renamed_per_result_set() {
+        let x = Subject::NamedNode(NamedNode::new_unchecked("http://example.org/x"));
+        let p = NamedNode::new_unchecked("http://example.org/p");
+        let b = OxBlankNode::new_unchecked("b0");
+        let triples = vec![
+            ((x.clone(), p.clone(), OxTerm::BlankNode(b.clone())), 0),
+            (
+                (
+                    Subject::BlankNode(b.clone()),
+                    p.clone(),
+                    OxTerm::BlankNode(b.clone()),
+                ),
+                1,
+            ),
+        ];
+        // The chain of blank nodes is a cycle, so there is nothing else to obtain
+        assert!(!is_truncated(&triples, 1));
+        assert!(is_truncated(&triples[..1], 0));
+
+        let mut blank_nodes = BlankNodes::default();
+        let rows: Vec<TripleRow> = triples.into_iter().map(|(t, _)| t).collect();
+        let first = blank_nodes.add_neighbourhood(rows.clone());
+        let second = blank_nodes.add_neighbourhood(rows);
+        let fresh = |triples: &[TripleRow]| match &triples[0].2 {
+            OxTerm::BlankNode(b) => b.clone(),
+            _ => panic!("Expected a blank node"),
+        };
+        // The same label in different result sets is renamed to different blank nodes
+        assert_ne!(fresh(&first), fresh(&second));
+        assert_eq!(
+            blank_nodes.outgoing(&fresh(&first)).unwrap(),
+            HashMap::from([(p.clone(), HashSet::from([OxTerm::BlankNode(fresh(&first))]))])
+        );
+        // Blank nodes that were not returned by the endpoint have no triples in it
+        assert!(blank_nodes.outgoing(&b).unwrap().is_empty());
+    }
+
+    #[test]
+    fn check_neighbourhood_query_with_predicate() {
+        let p = NamedNode::new_unchecked("http://example.org/p");
+        let query =
+            neighbourhood_query(&None, &["<http://example.org/x>".to_string()], 1, Some(&p));
+        assert_eq!(
+            query,
+            "select ?subj ?pred ?obj ?depth where { VALUES ?root { <http://example.org/x> } \
+             { ?root <http://example.org/p> ?obj . BIND(<http://example.org/p> AS ?pred) BIND(?root AS ?subj) BIND(0 AS ?depth) } UNION \
+             { ?root <http://example.org/p> ?b1 . ?b1 ?pred ?obj . FILTER(isBlank(?b1)) BIND(?b1 AS ?subj) BIND(1 AS ?depth) } }"
+        );
+    }
+
+    #[test]
+    fn check_blank_node_depth_is_capped() {
+        assert_eq!(next_blank_node_depth(0, 8).unwrap(), 1);
+        assert_eq!(next_blank_node_depth(3, 8).unwrap(), 6);
+        assert_eq!(next_blank_node_depth(6, 8).unwrap(), 8);
+        assert!(matches!(
+            next_blank_node_depth(8, 8),
+            Err(SRDFSparqlError::BlankNodeDepth { max: 8 })
+        ));
+    }
+
+    #[test]
+    fn check_blank_nodes_are_forgotten_beyond_capacity() {
+        let x = Subject::NamedNode(NamedNode::new_unchecked("http://example.org/x"));
+        let p = NamedNode::new_unchecked("http://example.org/p");
+        let row = (x, p, OxTerm::BlankNode(OxBlankNode::new_unchecked("b0")));
+        let mut blank_nodes = BlankNodes {
+            capacity: 1,
+            ..Default::default()
+        };
+        let fresh = |triples: Vec<TripleRow>| match &triples[0].2 {
+            OxTerm::BlankNode(b) => b.clone(),
+            _ => panic!("Expected a blank node"),
+        };
+        let first = fresh(blank_nodes.add_neighbourhood(vec![row.clone()]));
+        assert!(blank_nodes.outgoing(&first).unwrap().is_empty());
+        let second = fresh(blank_nodes.add_neighbourhood(vec![row]));
+        assert!(blank_nodes.outgoing(&second).unwrap().is_empty());
+        // The arcs of forgotten blank nodes can't be obtained again from the endpoint
+        assert!(matches!(
+            blank_nodes.outgoing(&first),
+            Err(SRDFSparqlError::BlankNodeSubject { .. })
+        ));
+    }
 }
@@ -0,0 +1,343 @@
+use oxrdf::{Subject as OxSubject, Term as OxTerm, Triple as OxTriple};
+use oxttl::NTriplesParser;
+use redb::{Builder, Database, Durability, MultimapTableDefinition, WriteTransaction};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{SRDFStreamError, SubjectBlock};
+
+/// Triples indexed by their subject
+const OUTGOING: MultimapTableDefinition<&str, &str> = MultimapTableDefinition::new("outgoing");
+
+/// Triples indexed by their object
+const INCOMING: MultimapTableDefinition<&str, &str> = MultimapTableDefinition::new("incoming");
+
+/// Number of triples inserted in each write transaction while the index is built
+const TRIPLES_PER_TRANSACTION: usize = 500_000;
+
+/// Bytes of the index pages that are cached in memory
+const CACHE_SIZE: usize = 64 * 1024 * 1024;
+
+static INDEX_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// On-disk index of the triples of an RDF dump.
+///
+/// It gives the outgoing and incoming arcs of any node without loading the dump in memory.
+/// Triples are kept in a [redb](https://www.redb.org/) database keyed by their subject and by their object,
+/// so each lookup is a B-tree search that only reads the triples of that node.
+/// Triples whose object is a literal are not indexed by object.
+/// The database is written in a fresh directory which is removed when the index is dropped.
+#[derive(Debug)]
+pub struct DiskIndex {
+    dir: PathBuf,
+    db: Option<Database>,
+    triples: usize,
+}
+
+impl DiskIndex {
+    /// Builds the index in a new directory inside the system temporary directory
+    pub fn build<I>(blocks: I) -> Result<DiskIndex, SRDFStreamError>
+    where
+        I: IntoIterator<Item = Result<SubjectBlock, SRDFStreamError>>,
+    {
+        Self::build_in(&std::env::temp_dir(), blocks)
+    }
+
+    /// Builds the index in a new directory inside `parent`
+    pub fn build_in<I>(parent: &Path, blocks: I) -> Result<DiskIndex, SRDFStreamError>
+    where
+        I: IntoIterator<Item = Result<SubjectBlock, SRDFStreamError>>,
+    {
+        let dir = parent.join(format!(
+            "srdf-index-{}-{}",
+            std::process::id(),
+            INDEX_COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        fs::create_dir_all(&dir).map_err(|e| SRDFStreamError::CreatingIndex {
+            path: dir.display().to_string(),
+            error: e,
+        })?;
+        // From now on the directory is removed if anything fails
+        let mut index = DiskIndex {
+            dir,
+            db: None,
+            triples: 0,
+        };
+        let db = Builder::new()
+            .set_cache_size(CACHE_SIZE)
+            .create(index.db_path())
+            .map_err(|e| index.writing_error(e))?;
+        let db = index.db.insert(db);
+        let mut txn = None;
+        let mut pending = 0;
+        for block in blocks {
+            let block = block?;
+            let current = match txn.as_mut() {
+                Some(current) => current,
+                None => txn.insert(begin_write(db, &index.dir)?),
+            };
+            insert_block(current, &block, &index.dir)?;
+            index.triples += block.triples().len();
+            pending += block.triples().len();
+            if pending > TRIPLES_PER_TRANSACTION {
+                if let Some(txn) = txn.take() {
+                    txn.commit().map_err(|e| writing_error(&index.dir, e))?;
+                }
+                pending = 0;
+            }
+        }
+        let mut txn = match txn {
+            Some(txn) => txn,
+            None => begin_write(db, &index.dir)?,
+        };
+        // The last transaction is durable and creates the tables if the dump is empty
+        txn.set_durability(Durability::Immediate);
+        txn.open_multimap_table(OUTGOING)
+            .and(txn.open_multimap_table(INCOMING))
+            .map_err(|e| writing_error(&index.dir, e))?;
+        txn.commit().map_err(|e| writing_error(&index.dir, e))?;
+        Ok(index)
+    }
+
+    /// Number of triples in the index
+    pub fn len(&self) -> usize {
+        self.triples
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triples == 0
+    }
+
+    /// Directory that contains the database of the index
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Triples whose subject is `subject`
+    pub fn outgoing(&self, subject: &OxSubject) -> Result<Vec<OxTriple>, SRDFStreamError> {
+        self.lookup(OUTGOING, &subject.to_string())
+    }
+
+    /// Triples whose object is `object`, which are empty for literals
+    pub fn incoming(&self, object: &OxTerm) -> Result<Vec<OxTriple>, SRDFStreamError> {
+        if matches!(object, OxTerm::Literal(_)) {
+            return Ok(Vec::new());
+        }
+        self.lookup(INCOMING, &object.to_string())
+    }
+
+    /// Triples around `subject` that are not in its block: its incoming arcs and
+    /// the outgoing arcs of the nodes reachable from it following up to `depth` arcs.
+    ///
+    /// If `inverse` is set, arcs are also followed backwards, so the incoming arcs of the reached
+    /// nodes are added too. References are followed until no new nodes are reached,
+    /// so `usize::MAX` gives the whole part of the dump that is connected to the subject
+    pub fn neighbourhood(
+        &self,
+        block: &SubjectBlock,
+        depth: usize,
+        inverse: bool,
+    ) -> Result<Vec<OxTriple>, SRDFStreamError> {
+        let subject = block.subject();
+        let incoming = self.incoming(&OxTerm::from(subject.clone()))?;
+        let mut visited: HashSet<OxSubject> = HashSet::from([subject.clone()]);
+        let mut seen: HashSet<OxTriple> = block.triples().iter().cloned().collect();
+        let mut current: Vec<OxTriple> = block.triples().to_vec();
+        if inverse {
+            current.extend(incoming.iter().cloned());
+        }
+        let mut result = Vec::new();
+        add_new(&mut result, &mut seen, incoming);
+        for _ in 0..depth {
+            let mut next = Vec::new();
+            for triple in current.iter() {
+                let mut nodes = Vec::from_iter(term_as_subject(&triple.object));
+                if inverse {
+                    nodes.push(triple.subject.clone())
+                }
+                for node in nodes {
+                    if visited.insert(node.clone()) {
+                        add_new(&mut next, &mut seen, self.outgoing(&node)?);
+                        if inverse {
+                            add_new(&mut next, &mut seen, self.incoming(&OxTerm::from(node))?);
+                        }
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            result.extend(next.iter().cloned());
+            current = next;
+        }
+        Ok(result)
+    }
+
+    fn db_path(&self) -> PathBuf {
+        self.dir.join("index.redb")
+    }
+
+    fn writing_error<E: Into<redb::Error>>(&self, error: E) -> SRDFStreamError {
+        writing_error(&self.dir, error)
+    }
+
+    fn reading_error<E: Into<redb::Error>>(&self, error: E) -> SRDFStreamError {
+        SRDFStreamError::ReadingIndex {
+            path: self.db_path().display().to_string(),
+            error: Box::new(error.into()),
+        }
+    }
+
+    fn lookup(
+        &self,
+        table: MultimapTableDefinition<&str, &str>,
+        key: &str,
+    ) -> Result<Vec<OxTriple>, SRDFStreamError> {
+        let Some(db) = &self.db else {
+            return Ok(Vec::new());
+        };
+        let txn = db.begin_read().map_err(|e| self.reading_error(e))?;
+        let table = txn
+            .open_multimap_table(table)
+            .map_err(|e| self.reading_error(e))?;
+        let mut lines = String::new();
+        for value in table.get(key).map_err(|e| self.reading_error(e))? {
+            let value = value.map_err(|e| self.reading_error(e))?;
+            lines.push_str(value.value());
+            lines.push_str(" .\n");
+        }
+        let parser = NTriplesParser::new();
+        #[cfg(feature = "rdf-star")]
+        let parser = parser.with_quoted_triples();
+        let mut result = Vec::new();
+        for triple in parser.for_reader(lines.as_bytes()) {
+            result.push(triple?)
+        }
+        Ok(result)
+    }
+}
+
+impl Drop for DiskIndex {
+    fn drop(&mut self) {
+        // The database is closed before its files are removed
+        self.db.take();
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn begin_write(db: &Database, dir: &Path) -> Result<WriteTransaction, SRDFStreamError> {
+    let mut txn = db.begin_write().map_err(|e| writing_error(dir, e))?;
+    // The index is temporary, so intermediate commits don't need to reach the disk
+    txn.set_durability(Durability::None);
+    Ok(txn)
+}
+
+fn insert_block(
+    txn: &WriteTransaction,
+    block: &SubjectBlock,
+    dir: &Path,
+) -> Result<(), SRDFStreamError> {
+    let mut outgoing = txn
+        .open_multimap_table(OUTGOING)
+        .map_err(|e| writing_error(dir, e))?;
+    let mut incoming = txn
+        .open_multimap_table(INCOMING)
+        .map_err(|e| writing_error(dir, e))?;
+    let subject = block.subject().to_string();
+    for triple in block.triples() {
+        let line = triple.to_string();
+        outgoing
+            .insert(subject.as_str(), line.as_str())
+            .map_err(|e| writing_error(dir, e))?;
+        if !matches!(triple.object, OxTerm::Literal(_)) {
+            incoming
+                .insert(triple.object.to_string().as_str(), line.as_str())
+                .map_err(|e| writing_error(dir, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Adds to `triples` the ones of `new` that have not been seen yet
+fn add_new(triples: &mut Vec<OxTriple>, seen: &mut HashSet<OxTriple>, new: Vec<OxTriple>) {
+    triples.extend(new.into_iter().filter(|triple| seen.insert(triple.clone())))
+}
+
+fn writing_error<E: Into<redb::Error>>(dir: &Path, error: E) -> SRDFStreamError {
+    SRDFStreamError::WritingIndex {
+        path: dir.display().to_string(),
+        error: Box::new(error.into()),
+    }
+}
+
+fn term_as_subject(term: &OxTerm) -> Option<OxSubject> {
+    match term {
+        OxTerm::NamedNode(n) => Some(OxSubject::NamedNode(n.clone())),
+        OxTerm::BlankNode(b) => Some(OxSubject::BlankNode(b.clone())),
+        #[cfg(feature = "rdf-star")]
+        OxTerm::Triple(t) => Some(OxSubject::Triple(t.clone())),
+        OxTerm::Literal(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RDFFormat, SubjectBlocks};
+    use oxrdf::NamedNode;
+
+    const DUMP: &str = r#"<http://example.org/alice> <http://example.org/knows> <http://example.org/bob> .
+<http://example.org/alice> <http://example.org/name> "Alice" .
+<http://example.org/bob> <http://example.org/knows> <http://example.org/carol> .
+<http://example.org/carol> <http://example.org/name> "Carol" .
+"#;
+
+    fn ex(name: &str) -> NamedNode {
+        NamedNode::new_unchecked(format!("http://example.org/{name}"))
+    }
+
+    fn index() -> DiskIndex {
+        let blocks = SubjectBlocks::new(DUMP.as_bytes(), &RDFFormat::NTriples).unwrap();
+        DiskIndex::build(blocks).unwrap()
+    }
+
+    #[test]
+    fn test_disk_index_arcs() {
+        let index = index();
+        assert_eq!(index.len(), 4);
+        assert_eq!(index.outgoing(&ex("alice").into()).unwrap().len(), 2);
+        let incoming = index.incoming(&ex("bob").into()).unwrap();
+        assert_eq!(incoming.len(), 1);
+        assert_eq!(incoming[0].subject, ex("alice").into());
+        assert!(index.incoming(&ex("alice").into()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_disk_index_neighbourhood() {
+        let index = index();
+        let blocks: Vec<SubjectBlock> = SubjectBlocks::new(DUMP.as_bytes(), &RDFFormat::NTriples)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let bob = &blocks[1];
+        // incoming arc from alice
+        assert_eq!(index.neighbourhood(bob, 0, false).unwrap().len(), 1);
+        // and the name of carol
+        assert_eq!(index.neighbourhood(bob, 1, false).unwrap().len(), 2);
+        // following arcs backwards also reaches the name of alice
+        assert_eq!(index.neighbourhood(bob, 1, true).unwrap().len(), 3);
+        // nothing else is connected to bob
+        assert_eq!(index.neighbourhood(bob, usize::MAX, true).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_disk_index_is_removed() {
+        let index = index();
+        let dir = index.dir().to_path_buf();
+        assert!(dir.exists());
+        drop(index);
+        assert!(!dir.exists());
+    }
+}
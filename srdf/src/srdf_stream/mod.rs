@@ -0,0 +1,17 @@
+//! Streaming access to RDF dumps whose triples are sorted by subject.
+//!
+//! Large N-Triples or N-Quads dumps can be processed one subject at a time with
+//! [`SubjectBlocks`], keeping only the triples of the current subject in memory.
+//! The arcs that are not in the current block, like incoming arcs or the outgoing
+//! arcs of referenced nodes, can be obtained from a [`DiskIndex`].
+pub mod disk_index;
+pub mod srdf_stream_error;
+pub mod stream_config;
+pub mod stream_progress;
+pub mod subject_blocks;
+
+pub use disk_index::*;
+pub use srdf_stream_error::*;
+pub use stream_config::*;
+pub use stream_progress::*;
+pub use subject_blocks::*;
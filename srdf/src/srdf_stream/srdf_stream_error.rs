@@ -0,0 +1,35 @@
+use oxttl::TurtleParseError;
+use std::io;
+use thiserror::Error;
+
+use crate::RDFFormat;
+
+#[derive(Error, Debug)]
+pub enum SRDFStreamError {
+    #[error("RDF format {format} can't be streamed, only N-Triples and N-Quads are supported")]
+    UnsupportedFormat { format: RDFFormat },
+
+    #[error("Subjects are not sorted: {subject} found after {previous}")]
+    UnsortedSubjects { previous: String, subject: String },
+
+    #[error(transparent)]
+    ParseError {
+        #[from]
+        err: TurtleParseError,
+    },
+
+    #[error("Creating disk index at {path}: {error}")]
+    CreatingIndex { path: String, error: io::Error },
+
+    #[error("Writing disk index at {path}: {error}")]
+    WritingIndex {
+        path: String,
+        error: Box<redb::Error>,
+    },
+
+    #[error("Reading disk index at {path}: {error}")]
+    ReadingIndex {
+        path: String,
+        error: Box<redb::Error>,
+    },
+}
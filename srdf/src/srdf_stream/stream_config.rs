@@ -0,0 +1,42 @@
+use serde_derive::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Default number of subjects between two progress reports
+pub const DEFAULT_PROGRESS_EVERY: usize = 10_000;
+
+/// Configuration of the streaming validation of subject-sorted RDF dumps
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct StreamConfig {
+    /// Number of hops of references whose outgoing arcs are added to the neighbourhood of each subject.
+    /// If it is `None`, the depth is taken from the shapes, following references without bound
+    /// when they are recursive.
+    /// When some references have to be followed, or the shapes use incoming arcs, the dump is read twice:
+    /// the first pass builds a [`crate::DiskIndex`], which also gives the incoming arcs of each subject
+    pub reference_depth: Option<usize>,
+
+    /// Directory where the disk index is created, the system temporary directory by default
+    pub index_dir: Option<PathBuf>,
+
+    /// Number of subjects between two progress reports
+    pub progress_every: Option<usize>,
+}
+
+impl StreamConfig {
+    pub fn new() -> StreamConfig {
+        Self::default()
+    }
+
+    pub fn with_reference_depth(mut self, depth: usize) -> Self {
+        self.reference_depth = Some(depth);
+        self
+    }
+
+    pub fn with_index_dir(mut self, dir: PathBuf) -> Self {
+        self.index_dir = Some(dir);
+        self
+    }
+
+    pub fn progress_every(&self) -> usize {
+        self.progress_every.unwrap_or(DEFAULT_PROGRESS_EVERY).max(1)
+    }
+}
@@ -0,0 +1,24 @@
+use std::fmt::Display;
+
+/// Progress of the streaming validation of a subject-sorted dump
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamProgress {
+    /// Number of subjects read
+    pub subjects: usize,
+
+    /// Number of triples read
+    pub triples: usize,
+
+    /// Number of nodes that have been validated
+    pub validated: usize,
+}
+
+impl Display for StreamProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} subjects ({} triples) read, {} nodes validated",
+            self.subjects, self.triples, self.validated
+        )
+    }
+}
@@ -0,0 +1,205 @@
+use oxrdf::{Subject as OxSubject, Triple as OxTriple};
+use oxttl::nquads::ReaderNQuadsParser;
+use oxttl::ntriples::ReaderNTriplesParser;
+use oxttl::{NQuadsParser, NTriplesParser, TurtleParseError};
+use std::io::Read;
+
+use crate::{RDFFormat, SRDFStreamError};
+
+/// Triples of one subject of a subject-sorted RDF dump
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubjectBlock {
+    subject: OxSubject,
+    triples: Vec<OxTriple>,
+}
+
+impl SubjectBlock {
+    pub fn subject(&self) -> &OxSubject {
+        &self.subject
+    }
+
+    pub fn triples(&self) -> &[OxTriple] {
+        &self.triples
+    }
+
+    pub fn into_triples(self) -> Vec<OxTriple> {
+        self.triples
+    }
+
+    pub fn len(&self) -> usize {
+        self.triples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triples.is_empty()
+    }
+}
+
+enum TripleReader<R: Read> {
+    NTriples(ReaderNTriplesParser<R>),
+    NQuads(ReaderNQuadsParser<R>),
+}
+
+impl<R: Read> Iterator for TripleReader<R> {
+    type Item = Result<OxTriple, TurtleParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            TripleReader::NTriples(reader) => reader.next(),
+            // The graph names are ignored, the neighbourhood of a subject joins all the graphs
+            TripleReader::NQuads(reader) => reader.next().map(|quad| quad.map(OxTriple::from)),
+        }
+    }
+}
+
+/// Iterator over the [`SubjectBlock`]s of an N-Triples or N-Quads dump sorted by subject.
+///
+/// Only the triples of the current subject are kept in memory.
+/// The subjects must be sorted by the byte order of their N-Triples representation,
+/// which is the order obtained by `LC_ALL=C sort` on the lines of the dump.
+/// That allows to detect in constant memory the subjects whose triples are not contiguous,
+/// which are reported as [`SRDFStreamError::UnsortedSubjects`]
+pub struct SubjectBlocks<R: Read> {
+    reader: TripleReader<R>,
+    lookahead: Option<OxTriple>,
+    previous: Option<String>,
+    triples_read: usize,
+}
+
+impl<R: Read> SubjectBlocks<R> {
+    pub fn new(reader: R, format: &RDFFormat) -> Result<SubjectBlocks<R>, SRDFStreamError> {
+        let reader = match format {
+            RDFFormat::NTriples => {
+                let parser = NTriplesParser::new();
+                #[cfg(feature = "rdf-star")]
+                let parser = parser.with_quoted_triples();
+                TripleReader::NTriples(parser.for_reader(reader))
+            }
+            RDFFormat::NQuads => {
+                let parser = NQuadsParser::new();
+                #[cfg(feature = "rdf-star")]
+                let parser = parser.with_quoted_triples();
+                TripleReader::NQuads(parser.for_reader(reader))
+            }
+            _ => return Err(SRDFStreamError::UnsupportedFormat { format: *format }),
+        };
+        Ok(SubjectBlocks {
+            reader,
+            lookahead: None,
+            previous: None,
+            triples_read: 0,
+        })
+    }
+
+    /// Number of triples read from the dump
+    pub fn triples_read(&self) -> usize {
+        self.triples_read
+    }
+
+    fn next_triple(&mut self) -> Option<Result<OxTriple, SRDFStreamError>> {
+        let triple = self.reader.next()?;
+        self.triples_read += 1;
+        Some(triple.map_err(SRDFStreamError::from))
+    }
+
+    fn next_block(&mut self, first: OxTriple) -> Result<SubjectBlock, SRDFStreamError> {
+        let subject = first.subject.clone();
+        let key = subject.to_string();
+        if let Some(previous) = &self.previous {
+            if key <= *previous {
+                return Err(SRDFStreamError::UnsortedSubjects {
+                    previous: previous.clone(),
+                    subject: key,
+                });
+            }
+        }
+        let mut triples = vec![first];
+        while let Some(triple) = self.next_triple() {
+            let triple = triple?;
+            if triple.subject == subject {
+                triples.push(triple)
+            } else {
+                self.lookahead = Some(triple);
+                break;
+            }
+        }
+        self.previous = Some(key);
+        Ok(SubjectBlock { subject, triples })
+    }
+}
+
+impl<R: Read> Iterator for SubjectBlocks<R> {
+    type Item = Result<SubjectBlock, SRDFStreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.lookahead.take() {
+            Some(triple) => triple,
+            None => match self.next_triple()? {
+                Ok(triple) => triple,
+                Err(e) => return Some(Err(e)),
+            },
+        };
+        Some(self.next_block(first))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = r#"<http://example.org/alice> <http://example.org/knows> <http://example.org/bob> .
+<http://example.org/alice> <http://example.org/name> "Alice" .
+<http://example.org/bob> <http://example.org/name> "Bob" .
+_:x <http://example.org/name> "X" .
+"#;
+
+    #[test]
+    fn test_subject_blocks() {
+        let blocks = SubjectBlocks::new(DUMP.as_bytes(), &RDFFormat::NTriples)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let sizes: Vec<(String, usize)> = blocks
+            .iter()
+            .map(|block| (block.subject().to_string(), block.len()))
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![
+                ("<http://example.org/alice>".to_string(), 2),
+                ("<http://example.org/bob>".to_string(), 1),
+                ("_:x".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_subject_blocks_nquads() {
+        let dump = r#"<http://example.org/alice> <http://example.org/p> "1" <http://example.org/g1> .
+<http://example.org/alice> <http://example.org/p> "2" <http://example.org/g2> .
+"#;
+        let mut blocks = SubjectBlocks::new(dump.as_bytes(), &RDFFormat::NQuads).unwrap();
+        assert_eq!(blocks.next().unwrap().unwrap().len(), 2);
+        assert!(blocks.next().is_none());
+        assert_eq!(blocks.triples_read(), 2);
+    }
+
+    #[test]
+    fn test_unsorted_subjects() {
+        let dump = r#"<http://example.org/bob> <http://example.org/name> "Bob" .
+<http://example.org/alice> <http://example.org/name> "Alice" .
+"#;
+        let result = SubjectBlocks::new(dump.as_bytes(), &RDFFormat::NTriples)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>();
+        assert!(matches!(
+            result,
+            Err(SRDFStreamError::UnsortedSubjects { .. })
+        ))
+    }
+
+    #[test]
+    fn test_unsupported_format() {
+        assert!(SubjectBlocks::new(DUMP.as_bytes(), &RDFFormat::Turtle).is_err())
+    }
+}